@@ -19,7 +19,21 @@ pub fn load_wallet_keypair(
         .into());
     }
 
-    let data = std::fs::read_to_string(&wallet_file)?;
+    // Password from env var (for automation/scripting) or interactive prompt
+    let password = match std::env::var("LOS_WALLET_PASSWORD") {
+        Ok(p) if !p.is_empty() => p,
+        _ => rpassword::prompt_password("Enter wallet password: ")?,
+    };
+
+    decrypt_wallet_file(&wallet_file, &password)
+}
+
+/// Decrypt a wallet file with a known password (keystore directories, batch tools).
+pub fn decrypt_wallet_file(
+    wallet_file: &Path,
+    password: &str,
+) -> Result<(String, los_crypto::KeyPair), Box<dyn std::error::Error>> {
+    let data = std::fs::read_to_string(wallet_file)?;
     let wallet: serde_json::Value = serde_json::from_str(&data)?;
 
     let address = wallet["address"]
@@ -27,17 +41,11 @@ pub fn load_wallet_keypair(
         .ok_or("Wallet file missing 'address' field")?
         .to_string();
 
-    // Password from env var (for automation/scripting) or interactive prompt
-    let password = match std::env::var("LOS_WALLET_PASSWORD") {
-        Ok(p) if !p.is_empty() => p,
-        _ => rpassword::prompt_password("Enter wallet password: ")?,
-    };
-
     let encrypted_key: los_crypto::EncryptedKey =
         serde_json::from_value(wallet["encrypted_key"].clone())
             .map_err(|e| format!("Invalid encrypted_key in wallet file: {}", e))?;
 
-    let secret_bytes = los_crypto::decrypt_private_key(&encrypted_key, &password)
+    let secret_bytes = los_crypto::decrypt_private_key(&encrypted_key, password)
         .map_err(|e| format!("Decryption failed (wrong password?): {:?}", e))?;
 
     // Build keypair using the stored public key (known correct) and decrypted secret
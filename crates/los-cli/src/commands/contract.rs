@@ -0,0 +1,181 @@
+use crate::commands::contract_ops;
+use crate::{print_error, print_info, print_success, ContractCommands};
+use colored::*;
+use std::path::Path;
+
+pub async fn handle(
+    action: ContractCommands,
+    rpc: &str,
+    config_dir: &Path,
+) -> Result<(), Box<dyn std::error::Error>> {
+    match action {
+        ContractCommands::Abi { address } => show_abi(&address, rpc).await?,
+        ContractCommands::Deploy {
+            wallet,
+            wasm,
+            abi,
+            amount,
+        } => deploy(&wallet, &wasm, abi.as_deref(), amount, rpc, config_dir).await?,
+        ContractCommands::Call {
+            wallet,
+            address,
+            function,
+            args,
+            gas_limit,
+            amount,
+        } => {
+            call(
+                &wallet, &address, &function, args, gas_limit, amount, rpc, config_dir,
+            )
+            .await?
+        }
+        ContractCommands::Query {
+            address,
+            function,
+            args,
+        } => query(&address, &function, args, rpc).await?,
+    }
+    Ok(())
+}
+
+async fn show_abi(address: &str, rpc: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let client = reqwest::Client::new();
+    let Some(abi) = contract_ops::fetch_abi(&client, rpc, address).await? else {
+        print_info("Contract was deployed without an ABI");
+        return Ok(());
+    };
+
+    println!();
+    if !abi.name.is_empty() {
+        println!("  {}: {}", "Contract".bold(), abi.name.green());
+    }
+    println!("  {}:", "Functions".bold());
+    for f in &abi.functions {
+        let kind = if f.view {
+            "view".cyan()
+        } else {
+            "call".yellow()
+        };
+        let returns = f
+            .returns
+            .map(|t| format!(" -> {}", t.as_str()))
+            .unwrap_or_default();
+        println!(
+            "    [{}] {}({}){}",
+            kind,
+            f.name,
+            f.signature_args(),
+            returns
+        );
+        if !f.doc.is_empty() {
+            println!("           {}", f.doc.dimmed());
+        }
+    }
+    if !abi.events.is_empty() {
+        println!("  {}:", "Events".bold());
+        for e in &abi.events {
+            let fields: Vec<String> = e
+                .fields
+                .iter()
+                .map(|p| format!("{}: {}", p.name, p.ty.as_str()))
+                .collect();
+            println!("    {}({})", e.name, fields.join(", "));
+        }
+    }
+    Ok(())
+}
+
+async fn deploy(
+    wallet: &str,
+    wasm_path: &str,
+    abi_path: Option<&str>,
+    amount: u128,
+    rpc: &str,
+    config_dir: &Path,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let abi = abi_path.map(contract_ops::load_abi_file).transpose()?;
+    let (contract_addr, block_hash) = contract_ops::deploy_contract(
+        wallet,
+        wasm_path,
+        Default::default(),
+        abi,
+        amount,
+        rpc,
+        config_dir,
+    )
+    .await?;
+
+    print_success("Contract deployed!");
+    println!("  {}: {}", "Contract".bold(), contract_addr.green());
+    println!("  {}: {}", "Block Hash".bold(), block_hash);
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn call(
+    wallet: &str,
+    address: &str,
+    function: &str,
+    args: Vec<String>,
+    gas_limit: Option<u64>,
+    amount: u128,
+    rpc: &str,
+    config_dir: &Path,
+) -> Result<(), Box<dyn std::error::Error>> {
+    print_info(&format!("Calling {}.{}...", address, function));
+    let result = contract_ops::call_contract(
+        wallet, address, function, args, gas_limit, amount, rpc, config_dir,
+    )
+    .await?;
+
+    let exec = &result["result"];
+    if exec["success"].as_bool() == Some(true) {
+        print_success("Call succeeded");
+        println!("  {}: {}", "Output".bold(), exec["output"]);
+        println!("  {}: {}", "Gas Used".bold(), exec["gas_used"]);
+        println!(
+            "  {}: {}",
+            "Block Hash".bold(),
+            result["block_hash"].as_str().unwrap_or("?")
+        );
+    } else {
+        print_error(&format!("Call reverted: {}", exec["output"]));
+    }
+    Ok(())
+}
+
+async fn query(
+    address: &str,
+    function: &str,
+    args: Vec<String>,
+    rpc: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let client = reqwest::Client::new();
+    let args = match contract_ops::fetch_abi(&client, rpc, address).await? {
+        Some(abi) => abi.encode_call(function, &args)?,
+        None => args,
+    };
+
+    let url = format!("{}/query-contract", rpc);
+    let payload = serde_json::json!({
+        "contract_address": address,
+        "function": function,
+        "args": args,
+    });
+    let data: serde_json::Value = client
+        .post(&url)
+        .json(&payload)
+        .send()
+        .await?
+        .json()
+        .await?;
+
+    if data["status"].as_str() != Some("success") {
+        let msg = data["msg"].as_str().unwrap_or("Unknown error");
+        return Err(format!("Query failed: {}", msg).into());
+    }
+    let res = &data["result"];
+    println!("  {}: {}", "Output".bold(), res["output"]);
+    println!("  {}: {}", "Gas Used".bold(), res["gas_used"]);
+    Ok(())
+}
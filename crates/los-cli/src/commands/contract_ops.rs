@@ -5,8 +5,9 @@
 use crate::commands::common::load_wallet_keypair;
 use crate::print_info;
 use base64::Engine as _;
+use los_core::abi::ContractAbi;
 use los_core::{
-    Block, BlockType, DEFAULT_GAS_LIMIT, GAS_PRICE_CIL, MIN_CALL_FEE_CIL, MIN_DEPLOY_FEE_CIL,
+    contract_call_fee, Block, BlockType, DEFAULT_GAS_LIMIT, MIN_DEPLOY_FEE_CIL,
     MIN_POW_DIFFICULTY_BITS,
 };
 use std::collections::BTreeMap;
@@ -52,13 +53,72 @@ async fn query_previous(
     Ok(data["head"].as_str().unwrap_or("0").to_string())
 }
 
+/// Dry-run a deploy/call block on the node (`POST /simulate`).
+///
+/// Returns `Ok(None)` if the node predates the endpoint, so callers can fall
+/// back to defaults; a block that would fail returns `Err` before any fee is spent.
+async fn simulate(
+    client: &reqwest::Client,
+    rpc: &str,
+    payload: &serde_json::Value,
+) -> Result<Option<serde_json::Value>, Box<dyn std::error::Error>> {
+    let url = format!("{}/simulate", rpc);
+    let resp = client.post(&url).json(payload).send().await?;
+    if resp.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    let data: serde_json::Value = resp.json().await?;
+    if data["status"].as_str() != Some("success") {
+        let msg = data["msg"].as_str().unwrap_or("Unknown error");
+        return Err(msg.to_string().into());
+    }
+    if data["account"]["sufficient_balance"].as_bool() == Some(false) {
+        return Err(format!(
+            "Insufficient balance: {} CIL needed (fee + value), {} CIL available",
+            data["account"]["total_debit_cil"], data["account"]["balance_cil"]
+        )
+        .into());
+    }
+    Ok(Some(data))
+}
+
+/// Fetch a contract's ABI from the node (`GET /contract/{address}`).
+///
+/// Returns `Ok(None)` if the contract was deployed without one.
+pub async fn fetch_abi(
+    client: &reqwest::Client,
+    rpc: &str,
+    contract_address: &str,
+) -> Result<Option<ContractAbi>, Box<dyn std::error::Error>> {
+    let url = format!("{}/contract/{}", rpc, contract_address);
+    let data: serde_json::Value = client.get(&url).send().await?.json().await?;
+    if data["status"].as_str() != Some("success") {
+        let msg = data["msg"].as_str().unwrap_or("Unknown error");
+        return Err(format!("Contract lookup failed: {}", msg).into());
+    }
+    match &data["contract"]["abi"] {
+        serde_json::Value::Null => Ok(None),
+        abi => Ok(Some(serde_json::from_value(abi.clone())?)),
+    }
+}
+
+/// Load an ABI descriptor from a JSON file
+pub fn load_abi_file(path: &str) -> Result<ContractAbi, Box<dyn std::error::Error>> {
+    let bytes =
+        std::fs::read(path).map_err(|e| format!("Failed to read ABI file '{}': {}", path, e))?;
+    Ok(ContractAbi::from_json(&bytes)?)
+}
+
 /// Deploy a WASM contract (client-signed).
 ///
+/// `abi` overrides any ABI embedded in the bytecode's `los_abi` section.
 /// Returns `(contract_address, block_hash)` on success.
+#[allow(clippy::too_many_arguments)]
 pub async fn deploy_contract(
     wallet_name: &str,
     wasm_path: &str,
     initial_state: BTreeMap<String, String>,
+    abi: Option<ContractAbi>,
     amount_cil: u128,
     rpc: &str,
     config_dir: &Path,
@@ -77,17 +137,39 @@ pub async fn deploy_contract(
         wasm_path
     ));
 
-    // 3. Query previous block hash
+    // 3. Dry-run: reject invalid WASM before paying the deploy fee
     let client = reqwest::Client::new();
+    let sim_payload = serde_json::json!({
+        "type": "deploy",
+        "owner": sender_addr,
+        "bytecode": bytecode_b64,
+        "abi": abi,
+        "amount_cil": amount_cil,
+    });
+    if let Some(sim) = simulate(&client, rpc, &sim_payload)
+        .await
+        .map_err(|e| format!("Deploy simulation failed: {}", e))?
+    {
+        let exports: Vec<&str> = sim["simulation"]["exports"]
+            .as_array()
+            .map(|a| a.iter().filter_map(|v| v.as_str()).collect())
+            .unwrap_or_default();
+        print_info(&format!("Exports: {}", exports.join(", ")));
+        if sim["simulation"]["abi"].is_null() {
+            print_info("No ABI: callers must know argument order");
+        }
+    }
+
+    // 4. Query previous block hash
     let previous = query_previous(&client, rpc, &sender_addr).await?;
 
-    // 4. Build ContractDeploy block
+    // 5. Build ContractDeploy block
     let timestamp = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)?
         .as_secs();
 
     let code_hash = { hex::encode(&blake3::hash(&bytecode).as_bytes()[0..32]) };
-    let link = format!("DEPLOY:{}", code_hash);
+    let link = los_core::abi::deploy_link(&code_hash, abi.as_ref());
 
     let mut block = Block {
         account: sender_addr.clone(),
@@ -100,20 +182,21 @@ pub async fn deploy_contract(
         work: 0,
         timestamp,
         fee: MIN_DEPLOY_FEE_CIL,
+        memo: None,
     };
 
-    // 5. PoW
+    // 6. PoW
     print_info("Computing Proof-of-Work...");
     compute_pow(&mut block);
 
-    // 6. Sign
+    // 7. Sign
     print_info("Signing with Dilithium5...");
     let signing_hash = block.signing_hash();
     let signature = los_crypto::sign_message(signing_hash.as_bytes(), &keypair.secret_key)
         .map_err(|e| format!("Signing failed: {:?}", e))?;
     block.signature = hex::encode(&signature);
 
-    // 7. Submit
+    // 8. Submit
     print_info("Broadcasting deploy transaction...");
     let url = format!("{}/deploy-contract", rpc);
 
@@ -127,6 +210,7 @@ pub async fn deploy_contract(
         "owner": sender_addr,
         "bytecode": bytecode_b64,
         "initial_state": initial_state_opt,
+        "abi": abi,
         "amount_cil": amount_cil,
         "signature": block.signature,
         "public_key": block.public_key,
@@ -154,6 +238,10 @@ pub async fn deploy_contract(
 
 /// Call a smart contract function (client-signed).
 ///
+/// If the contract has an ABI, args are checked against it and encoded in
+/// canonical form (they may also be given as `name=value`). Without an
+/// explicit `gas_limit`, the call is simulated first and the node's
+/// recommended limit (gas used + headroom) is used.
 /// Returns the full JSON response on success.
 #[allow(clippy::too_many_arguments)]
 pub async fn call_contract(
@@ -165,24 +253,89 @@ pub async fn call_contract(
     amount_cil: u128,
     rpc: &str,
     config_dir: &Path,
+) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+    call_contract_with_tokens(
+        wallet_name,
+        contract_address,
+        function,
+        args,
+        gas_limit,
+        amount_cil,
+        &BTreeMap::new(),
+        rpc,
+        config_dir,
+    )
+    .await
+}
+
+/// [`call_contract`], also letting the contract pull up to the given
+/// USP-01 amounts from the wallet during this call. The allowances are
+/// signed in the block link, so no separate `approve` block is needed.
+#[allow(clippy::too_many_arguments)]
+pub async fn call_contract_with_tokens(
+    wallet_name: &str,
+    contract_address: &str,
+    function: &str,
+    args: Vec<String>,
+    gas_limit: Option<u64>,
+    amount_cil: u128,
+    token_allowances: &BTreeMap<String, u128>,
+    rpc: &str,
+    config_dir: &Path,
 ) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
     // 1. Load wallet
     let (sender_addr, keypair) = load_wallet_keypair(wallet_name, config_dir)?;
 
-    // 2. Query previous block hash
+    // 2. Validate args against the contract ABI, if it has one
     let client = reqwest::Client::new();
+    let args = match fetch_abi(&client, rpc, contract_address).await? {
+        Some(abi) => abi.encode_call(function, &args)?,
+        None => args,
+    };
+
+    // 3. Gas limit: explicit, or auto-filled from a dry-run
+    let gas = match gas_limit {
+        Some(gas) => gas,
+        None => {
+            let sim_payload = serde_json::json!({
+                "type": "call",
+                "contract_address": contract_address,
+                "function": function,
+                "args": args,
+                "caller": sender_addr,
+                "amount_cil": amount_cil,
+                "token_allowances": token_allowances,
+            });
+            match simulate(&client, rpc, &sim_payload)
+                .await
+                .map_err(|e| format!("Call simulation failed: {}", e))?
+            {
+                Some(sim) => {
+                    let gas = sim["gas_limit"].as_u64().unwrap_or(DEFAULT_GAS_LIMIT);
+                    print_info(&format!(
+                        "Estimated gas: {} (limit {})",
+                        sim["simulation"]["gas_used"], gas
+                    ));
+                    gas
+                }
+                None => DEFAULT_GAS_LIMIT,
+            }
+        }
+    };
+    let fee = contract_call_fee(gas);
+
+    // 4. Query previous block hash
     let previous = query_previous(&client, rpc, &sender_addr).await?;
 
-    // 3. Build ContractCall block
-    let gas = gas_limit.unwrap_or(DEFAULT_GAS_LIMIT);
-    let fee = MIN_CALL_FEE_CIL.max((gas as u128).saturating_mul(GAS_PRICE_CIL));
+    // 5. Build ContractCall block
     let timestamp = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)?
         .as_secs();
 
     let args_json = serde_json::to_string(&args)?;
     let args_b64 = base64::engine::general_purpose::STANDARD.encode(args_json.as_bytes());
-    let link = format!("CALL:{}:{}:{}", contract_address, function, args_b64);
+    let link =
+        los_core::contract_call_link(contract_address, function, &args_b64, token_allowances);
 
     let mut block = Block {
         account: sender_addr.clone(),
@@ -195,20 +348,21 @@ pub async fn call_contract(
         work: 0,
         timestamp,
         fee,
+        memo: None,
     };
 
-    // 4. PoW
+    // 6. PoW
     print_info("Computing Proof-of-Work...");
     compute_pow(&mut block);
 
-    // 5. Sign
+    // 7. Sign
     print_info("Signing with Dilithium5...");
     let signing_hash = block.signing_hash();
     let signature = los_crypto::sign_message(signing_hash.as_bytes(), &keypair.secret_key)
         .map_err(|e| format!("Signing failed: {:?}", e))?;
     block.signature = hex::encode(&signature);
 
-    // 6. Submit
+    // 8. Submit
     print_info("Broadcasting contract call...");
     let url = format!("{}/call-contract", rpc);
     let payload = serde_json::json!({
@@ -224,6 +378,7 @@ pub async fn call_contract(
         "work": block.work,
         "timestamp": block.timestamp,
         "fee": fee,
+        "token_allowances": token_allowances,
     });
 
     let resp = client.post(&url).json(&payload).send().await?;
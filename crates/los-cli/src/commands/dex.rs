@@ -1,6 +1,7 @@
 use crate::commands::contract_ops;
 use crate::{print_error, print_info, print_success};
 use colored::Colorize;
+use std::collections::BTreeMap;
 use std::path::Path;
 
 /// Handle DEX subcommands.
@@ -220,6 +221,49 @@ async fn get_position(
 //                    RemoveLiquidity, Swap
 // ─────────────────────────────────────────────────────────────
 
+/// What a DEX call carries so the DEX can take its legs in that same
+/// block: `LOS` is CIL sent with the call, a USP-01 token is an allowance
+/// attached to the call.
+#[derive(Default)]
+struct Funding {
+    value_cil: u128,
+    token_allowances: BTreeMap<String, u128>,
+}
+
+impl Funding {
+    /// Add a leg of `amount` of `token`.
+    fn leg(mut self, token: &str, amount: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let needed: u128 = amount
+            .parse()
+            .map_err(|_| format!("Invalid amount: {}", amount))?;
+        if token == "LOS" {
+            self.value_cil += needed;
+        } else {
+            *self.token_allowances.entry(token.to_string()).or_insert(0) += needed;
+        }
+        Ok(self)
+    }
+}
+
+/// Token pair of an existing pool.
+async fn pool_tokens(
+    rpc: &str,
+    contract: &str,
+    pool_id: &str,
+) -> Result<(String, String), Box<dyn std::error::Error>> {
+    let url = format!("{}/dex/pool/{}/{}", rpc, contract, pool_id);
+    let resp: serde_json::Value = reqwest::get(&url).await?.json().await?;
+    let pool = &resp["pool"];
+    match (pool["token_a"].as_str(), pool["token_b"].as_str()) {
+        (Some(a), Some(b)) => Ok((a.to_string(), b.to_string())),
+        _ => Err(resp["msg"]
+            .as_str()
+            .unwrap_or("Pool not found")
+            .to_string()
+            .into()),
+    }
+}
+
 async fn dex_deploy(
     wallet: &str,
     wasm_path: &str,
@@ -230,7 +274,8 @@ async fn dex_deploy(
 
     let initial_state = std::collections::BTreeMap::new();
     let (contract_addr, block_hash) =
-        contract_ops::deploy_contract(wallet, wasm_path, initial_state, 0, rpc, config_dir).await?;
+        contract_ops::deploy_contract(wallet, wasm_path, initial_state, None, 0, rpc, config_dir)
+            .await?;
 
     print_success(&format!("DEX contract deployed: {}", contract_addr));
 
@@ -290,13 +335,17 @@ async fn dex_create_pool(
         args.push(fee);
     }
 
-    let result = contract_ops::call_contract(
+    let funding = Funding::default()
+        .leg(token_a, amount_a)?
+        .leg(token_b, amount_b)?;
+    let result = contract_ops::call_contract_with_tokens(
         wallet,
         contract,
         "create_pool",
         args,
         None,
-        0,
+        funding.value_cil,
+        &funding.token_allowances,
         rpc,
         config_dir,
     )
@@ -351,13 +400,18 @@ async fn dex_add_liquidity(
         min_lp.to_string(),
     ];
 
-    let result = contract_ops::call_contract(
+    let (token_a, token_b) = pool_tokens(rpc, contract, pool_id).await?;
+    let funding = Funding::default()
+        .leg(&token_a, amount_a)?
+        .leg(&token_b, amount_b)?;
+    let result = contract_ops::call_contract_with_tokens(
         wallet,
         contract,
         "add_liquidity",
         args,
         None,
-        0,
+        funding.value_cil,
+        &funding.token_allowances,
         rpc,
         config_dir,
     )
@@ -473,9 +527,19 @@ async fn dex_swap(
         deadline_str,
     ];
 
-    let result =
-        contract_ops::call_contract(wallet, contract, "swap", args, None, 0, rpc, config_dir)
-            .await?;
+    let funding = Funding::default().leg(token_in, amount_in)?;
+    let result = contract_ops::call_contract_with_tokens(
+        wallet,
+        contract,
+        "swap",
+        args,
+        None,
+        funding.value_cil,
+        &funding.token_allowances,
+        rpc,
+        config_dir,
+    )
+    .await?;
 
     let exec = &result["result"];
     if exec["success"].as_bool() == Some(true) {
@@ -0,0 +1,337 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// GOVERNANCE COMMANDS — parameter proposals and validator votes (client-signed)
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use crate::commands::common::load_wallet_keypair;
+use crate::commands::tx::compute_pow;
+use crate::{print_error, print_info, print_success, GovernanceCommands};
+use colored::*;
+use los_core::governance::{
+    proposal_link, settle_link, vote_link, GovParam, ProposalTally, MIN_PROPOSAL_DEPOSIT_CIL,
+};
+use los_core::{Block, BlockType, BASE_FEE_CIL, CIL_PER_LOS};
+use std::path::Path;
+
+pub async fn handle(
+    action: GovernanceCommands,
+    rpc: &str,
+    config_dir: &Path,
+) -> Result<(), Box<dyn std::error::Error>> {
+    match action {
+        GovernanceCommands::Params => show_params(rpc).await?,
+        GovernanceCommands::List => list_proposals(rpc).await?,
+        GovernanceCommands::Show { id } => show_proposal(&id, rpc).await?,
+        GovernanceCommands::Propose {
+            wallet,
+            param,
+            value,
+            activation_time,
+            deposit,
+        } => {
+            let param: GovParam = param.parse()?;
+            let value: u128 = value.parse().map_err(|_| "Invalid value")?;
+            let deposit_cil = match deposit {
+                Some(los) => (los as u128)
+                    .checked_mul(CIL_PER_LOS)
+                    .ok_or("Deposit overflow")?,
+                None => MIN_PROPOSAL_DEPOSIT_CIL,
+            };
+            let link = proposal_link(param, value, activation_time);
+            print_info(&format!(
+                "Proposing {} = {} from time {} ({} LOS deposit)...",
+                param,
+                value,
+                activation_time,
+                deposit_cil / CIL_PER_LOS
+            ));
+            let payload = serde_json::json!({
+                "param": param.as_str(),
+                "value": value,
+                "activation_time": activation_time,
+                "deposit_cil": deposit_cil,
+            });
+            let block = signed_block(
+                &wallet,
+                BlockType::Proposal,
+                deposit_cil,
+                link,
+                rpc,
+                config_dir,
+            )
+            .await?;
+            if let Some(hash) = submit(rpc, "propose", payload, &block).await? {
+                print_success("Proposal submitted, awaiting validator confirmation");
+                println!("  {} {}", "Proposal ID:".bold(), hash.green());
+            }
+        }
+        GovernanceCommands::Vote {
+            wallet,
+            proposal,
+            choice,
+        } => {
+            let approve = choice == "yes";
+            print_info(&format!("Voting {} on proposal {}...", choice, proposal));
+            let payload = serde_json::json!({
+                "proposal_id": proposal,
+                "approve": approve,
+            });
+            let link = vote_link(&proposal, approve);
+            let block = signed_block(&wallet, BlockType::Vote, 0, link, rpc, config_dir).await?;
+            if let Some(hash) = submit(rpc, "vote", payload, &block).await? {
+                print_success("Vote submitted, awaiting validator confirmation");
+                println!("  {} {}", "Block Hash:".bold(), hash.green());
+            }
+        }
+        GovernanceCommands::Settle { wallet, proposal } => {
+            // Validators confirm the settlement only if it carries their epoch tally
+            let data = get_json(rpc, &format!("/governance/proposal/{}", proposal)).await?;
+            let p = &data["proposal"];
+            let power = |k: &str| -> Result<u128, String> {
+                p["tally"][k]
+                    .as_str()
+                    .and_then(|v| v.parse().ok())
+                    .ok_or_else(|| format!("Missing {} in tally", k))
+            };
+            let tally = ProposalTally::new(
+                power("yes_power")?,
+                power("no_power")?,
+                power("total_power")?,
+            )?;
+            let refund = if tally.passed() {
+                p["deposit_cil"]
+                    .as_str()
+                    .and_then(|v| v.parse().ok())
+                    .ok_or("Missing deposit_cil")?
+            } else {
+                0
+            };
+            print_info(&format!(
+                "Settling proposal {} ({}.{:02}% yes, {} LOS refund)...",
+                proposal,
+                tally.yes_bps / 100,
+                tally.yes_bps % 100,
+                refund / CIL_PER_LOS
+            ));
+            let payload = serde_json::json!({
+                "proposal_id": proposal,
+                "yes_power": tally.yes_power,
+                "no_power": tally.no_power,
+                "total_power": tally.total_power,
+            });
+            let link = settle_link(&proposal, &tally);
+            let block =
+                signed_block(&wallet, BlockType::Proposal, refund, link, rpc, config_dir).await?;
+            if let Some(hash) = submit(rpc, "settle", payload, &block).await? {
+                print_success("Settlement submitted, awaiting validator confirmation");
+                println!("  {} {}", "Block Hash:".bold(), hash.green());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Build, PoW-solve and sign a governance block for `wallet_name`
+async fn signed_block(
+    wallet_name: &str,
+    block_type: BlockType,
+    amount: u128,
+    link: String,
+    rpc: &str,
+    config_dir: &Path,
+) -> Result<Block, Box<dyn std::error::Error>> {
+    let (address, keypair) = load_wallet_keypair(wallet_name, config_dir)?;
+    print_success("Wallet loaded.");
+
+    let client = reqwest::Client::new();
+    let account: serde_json::Value = client
+        .get(format!("{}/balance/{}", rpc, address))
+        .send()
+        .await?
+        .json()
+        .await?;
+    let previous = account["head"].as_str().unwrap_or("0").to_string();
+
+    // Governance may have changed the base fee
+    let params: serde_json::Value = client
+        .get(format!("{}/governance/params", rpc))
+        .send()
+        .await?
+        .json()
+        .await?;
+    let fee = params["params"]
+        .as_array()
+        .and_then(|ps| {
+            ps.iter()
+                .find(|p| p["name"] == GovParam::BaseFeeCil.as_str())
+        })
+        .and_then(|p| p["value"].as_str())
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(BASE_FEE_CIL);
+
+    let mut block = Block {
+        account: address,
+        previous,
+        block_type,
+        amount,
+        link,
+        signature: String::new(),
+        public_key: hex::encode(&keypair.public_key),
+        work: 0,
+        timestamp: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)?
+            .as_secs(),
+        fee,
+        memo: None,
+    };
+
+    print_info("Computing Proof-of-Work...");
+    compute_pow(&mut block);
+    let signature = los_crypto::sign_message(block.signing_hash().as_bytes(), &keypair.secret_key)
+        .map_err(|e| format!("Signing failed: {:?}", e))?;
+    block.signature = hex::encode(&signature);
+    Ok(block)
+}
+
+/// POST a signed governance block; returns its hash, or `None` after printing the error
+async fn submit(
+    rpc: &str,
+    action: &str,
+    mut payload: serde_json::Value,
+    block: &Block,
+) -> Result<Option<String>, Box<dyn std::error::Error>> {
+    payload["signature"] = block.signature.clone().into();
+    payload["public_key"] = block.public_key.clone().into();
+    payload["previous"] = block.previous.clone().into();
+    payload["work"] = block.work.into();
+    payload["timestamp"] = block.timestamp.into();
+    payload["fee"] = serde_json::json!(block.fee);
+
+    let client = reqwest::Client::new();
+    let resp: serde_json::Value = client
+        .post(format!("{}/governance/{}", rpc, action))
+        .json(&payload)
+        .send()
+        .await?
+        .json()
+        .await?;
+    if resp["status"].as_str() == Some("success") {
+        Ok(Some(
+            resp["block_hash"].as_str().unwrap_or("unknown").to_string(),
+        ))
+    } else {
+        let msg = resp["msg"].as_str().unwrap_or("Unknown error");
+        print_error(&format!("Governance transaction failed: {}", msg));
+        Ok(None)
+    }
+}
+
+async fn get_json(rpc: &str, path: &str) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+    let data: serde_json::Value = reqwest::Client::new()
+        .get(format!("{}{}", rpc, path))
+        .send()
+        .await?
+        .json()
+        .await?;
+    if data["status"].as_str() != Some("success") {
+        let msg = data["msg"].as_str().unwrap_or("Unknown error");
+        return Err(msg.to_string().into());
+    }
+    Ok(data)
+}
+
+async fn show_params(rpc: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let data = get_json(rpc, "/governance/params").await?;
+    println!();
+    println!(
+        "{} {}",
+        "Chain Parameters at height".bold(),
+        data["block_height"].to_string().cyan()
+    );
+    for p in data["params"].as_array().into_iter().flatten() {
+        let value = p["value"].as_str().unwrap_or("height-based");
+        let source = if p["governed"].as_bool() == Some(true) {
+            "governance".green()
+        } else {
+            "default".dimmed()
+        };
+        println!(
+            "  {:<30} {:<22} ({})",
+            p["name"].as_str().unwrap_or("?"),
+            value,
+            source
+        );
+    }
+    Ok(())
+}
+
+async fn list_proposals(rpc: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let data = get_json(rpc, "/governance/proposals").await?;
+    let proposals = data["proposals"].as_array().cloned().unwrap_or_default();
+    println!();
+    println!(
+        "{} {} (voting window {}s, activation delay {}s)",
+        "Proposals:".bold(),
+        proposals.len(),
+        data["voting_period_secs"],
+        data["activation_delay_secs"]
+    );
+    for p in &proposals {
+        println!(
+            "  {} {} = {} [{}] votes: {}, voting ends {}, activates {}",
+            p["id"].as_str().unwrap_or("?").green(),
+            p["param"].as_str().unwrap_or("?"),
+            p["value"].as_str().unwrap_or("?"),
+            p["status"].as_str().unwrap_or("?").cyan(),
+            p["votes"],
+            p["voting_end_time"],
+            p["activation_time"]
+        );
+    }
+    Ok(())
+}
+
+async fn show_proposal(id: &str, rpc: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let data = get_json(rpc, &format!("/governance/proposal/{}", id)).await?;
+    let p = &data["proposal"];
+    let tally = &p["tally"];
+    println!();
+    println!("{} {}", "Proposal:".bold(), id.green());
+    println!(
+        "  {} {}",
+        "Proposer:".bold(),
+        p["proposer"].as_str().unwrap_or("?")
+    );
+    println!(
+        "  {} {} = {}",
+        "Change:".bold(),
+        p["param"].as_str().unwrap_or("?"),
+        p["value"].as_str().unwrap_or("?")
+    );
+    println!(
+        "  {} {}",
+        "Status:".bold(),
+        p["status"].as_str().unwrap_or("?").cyan()
+    );
+    println!(
+        "  {} {} → {} (activates at {})",
+        "Voting:".bold(),
+        p["submitted_time"],
+        p["voting_end_time"],
+        p["activation_time"]
+    );
+    println!(
+        "  {} yes {} / no {} / total {} ({}.{:02}% yes{})",
+        "Tally:".bold(),
+        tally["yes_power"].as_str().unwrap_or("0"),
+        tally["no_power"].as_str().unwrap_or("0"),
+        tally["total_power"].as_str().unwrap_or("0"),
+        tally["yes_bps"].as_u64().unwrap_or(0) / 100,
+        tally["yes_bps"].as_u64().unwrap_or(0) % 100,
+        if p["final"].as_bool() == Some(true) {
+            ""
+        } else {
+            ", live"
+        }
+    );
+    Ok(())
+}
@@ -1,7 +1,11 @@
 pub mod common;
+pub mod contract;
 pub mod contract_ops;
 pub mod dex;
+pub mod governance;
 pub mod query;
+pub mod receive;
+pub mod sweep;
 pub mod token;
 pub mod tx;
 pub mod validator;
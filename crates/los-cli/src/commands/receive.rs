@@ -0,0 +1,260 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// RECEIVE — claim pending Sends with owner-signed Receive blocks
+//
+// In the block lattice a Send only debits the sender; the recipient's balance
+// moves when a Receive referencing the Send is published. Nodes publish it for
+// Sends they finalize; Sends learned otherwise stay pending until claimed here.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use crate::commands::common::load_wallet_keypair;
+use crate::commands::tx::compute_pow;
+use crate::{print_error, print_info, print_success};
+use colored::*;
+use los_core::{Block, BlockType, CIL_PER_LOS};
+use std::path::Path;
+
+/// Unclaimed Sends to `address` as (Send hash, amount in CIL), oldest first
+pub(crate) async fn pending_sends(
+    client: &reqwest::Client,
+    rpc: &str,
+    address: &str,
+) -> Result<Vec<(String, u128)>, String> {
+    let data = pending_json(client, rpc, address).await?;
+    Ok(data["pending"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|p| {
+            Some((
+                p["hash"].as_str()?.to_string(),
+                p["amount_cil_str"].as_str()?.parse::<u128>().ok()?,
+            ))
+        })
+        .collect())
+}
+
+async fn pending_json(
+    client: &reqwest::Client,
+    rpc: &str,
+    address: &str,
+) -> Result<serde_json::Value, String> {
+    let url = format!("{}/pending/{}", rpc, address);
+    let data: serde_json::Value = client
+        .get(&url)
+        .send()
+        .await
+        .map_err(|e| format!("pending/{}: {}", address, e))?
+        .json()
+        .await
+        .map_err(|e| format!("pending/{}: {}", address, e))?;
+    if data["status"] != "success" {
+        return Err(format!(
+            "pending/{}: {}",
+            address,
+            data["msg"].as_str().unwrap_or("unexpected response")
+        ));
+    }
+    Ok(data)
+}
+
+/// PoW, sign with the account key, and return the block hash
+pub(crate) fn sign_block(
+    block: &mut Block,
+    keypair: &los_crypto::KeyPair,
+    solve_pow: fn(&mut Block),
+) -> Result<String, String> {
+    solve_pow(block);
+    let signature = los_crypto::sign_message(block.signing_hash().as_bytes(), &keypair.secret_key)
+        .map_err(|e| format!("Signing failed: {:?}", e))?;
+    block.signature = hex::encode(signature);
+    Ok(block.calculate_hash())
+}
+
+/// Receive blocks claiming `pending` in order, chained from `head`.
+/// Returns the blocks and the account's new head.
+pub(crate) fn signed_receives(
+    address: &str,
+    keypair: &los_crypto::KeyPair,
+    head: &str,
+    pending: &[(String, u128)],
+    timestamp: u64,
+    solve_pow: fn(&mut Block),
+) -> Result<(Vec<Block>, String), String> {
+    let mut previous = head.to_string();
+    let mut receives = Vec::new();
+    for (send_hash, amount) in pending {
+        let mut receive = Block {
+            account: address.to_string(),
+            previous: previous.clone(),
+            block_type: BlockType::Receive,
+            amount: *amount,
+            link: send_hash.clone(),
+            signature: String::new(),
+            public_key: hex::encode(&keypair.public_key),
+            work: 0,
+            timestamp,
+            fee: 0,
+            memo: None,
+        };
+        previous = sign_block(&mut receive, keypair, solve_pow)?;
+        receives.push(receive);
+    }
+    Ok((receives, previous))
+}
+
+/// Submit a signed Receive block to `POST /receive`; returns its hash
+pub(crate) async fn publish_receive(
+    client: &reqwest::Client,
+    rpc: &str,
+    block: &Block,
+) -> Result<String, String> {
+    let data: serde_json::Value = client
+        .post(format!("{}/receive", rpc))
+        .json(block)
+        .send()
+        .await
+        .map_err(|e| format!("receive: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("receive: {}", e))?;
+    if data["status"] == "success" {
+        Ok(data["hash"]
+            .as_str()
+            .map(str::to_string)
+            .unwrap_or_else(|| block.calculate_hash()))
+    } else {
+        Err(format!(
+            "receive: {}",
+            data["msg"].as_str().unwrap_or("Unknown error")
+        ))
+    }
+}
+
+/// `tx pending <ADDRESS>`
+pub async fn show_pending(address: &str, rpc: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let data = pending_json(&reqwest::Client::new(), rpc, address).await?;
+    let pending = data["pending"].as_array().cloned().unwrap_or_default();
+    if pending.is_empty() {
+        print_info(&format!("No pending receivables for {}", address));
+        return Ok(());
+    }
+    println!(
+        "{} {} pending, {} LOS total",
+        "Pending:".bold(),
+        pending.len(),
+        data["total_cil_str"]
+            .as_str()
+            .and_then(|v| v.parse::<u128>().ok())
+            .unwrap_or(0)
+            / CIL_PER_LOS
+    );
+    for p in &pending {
+        println!(
+            "  {} {} LOS from {} ({}s ago){}",
+            p["hash"].as_str().unwrap_or("?").dimmed(),
+            p["amount"].as_str().unwrap_or("?").cyan(),
+            p["from"].as_str().unwrap_or("?"),
+            p["age_secs"].as_u64().unwrap_or(0),
+            p["memo"]
+                .as_str()
+                .map(|m| format!(" memo={}", m))
+                .unwrap_or_default()
+        );
+    }
+    Ok(())
+}
+
+/// `tx receive --wallet <W>... [--watch]`: claim pending Sends of local wallets,
+/// once or polling every `interval_secs`
+pub async fn receive_pending(
+    wallets: &[String],
+    watch: bool,
+    interval_secs: u64,
+    rpc: &str,
+    config_dir: &Path,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut accounts = Vec::new();
+    for wallet in wallets {
+        accounts.push(load_wallet_keypair(wallet, config_dir)?);
+    }
+    if watch {
+        print_info(&format!(
+            "Watching {} wallet(s) every {}s (Ctrl+C to stop)",
+            accounts.len(),
+            interval_secs
+        ));
+    }
+    let client = reqwest::Client::new();
+    loop {
+        for (address, keypair) in &accounts {
+            if let Err(e) = receive_account(&client, rpc, address, keypair).await {
+                print_error(&format!("{}: {}", address, e));
+            }
+        }
+        if !watch {
+            return Ok(());
+        }
+        tokio::time::sleep(std::time::Duration::from_secs(interval_secs.max(1))).await;
+    }
+}
+
+async fn receive_account(
+    client: &reqwest::Client,
+    rpc: &str,
+    address: &str,
+    keypair: &los_crypto::KeyPair,
+) -> Result<(), String> {
+    let pending = pending_sends(client, rpc, address).await?;
+    if pending.is_empty() {
+        return Ok(());
+    }
+    let account: serde_json::Value = client
+        .get(format!("{}/balance/{}", rpc, address))
+        .send()
+        .await
+        .map_err(|e| e.to_string())?
+        .json()
+        .await
+        .map_err(|e| e.to_string())?;
+    let head = account["head"].as_str().unwrap_or("0");
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let (receives, _) = signed_receives(address, keypair, head, &pending, timestamp, compute_pow)?;
+    for receive in &receives {
+        let hash = publish_receive(client, rpc, receive).await?;
+        print_success(&format!(
+            "Received {} LOS into {} ({})",
+            receive.amount as f64 / CIL_PER_LOS as f64,
+            address,
+            hash
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signed_receives_chain_from_head() {
+        let keypair = los_crypto::generate_keypair();
+        let address = los_crypto::public_key_to_address(&keypair.public_key);
+        let pending = vec![("s1".to_string(), 300), ("s2".to_string(), 700)];
+        // PoW skipped: 16-bit PoW over a Dilithium5 key takes minutes in debug builds
+        let (receives, head) =
+            signed_receives(&address, &keypair, "head0", &pending, 1_700_000_000, |_| {}).unwrap();
+
+        assert_eq!(receives.len(), 2);
+        assert_eq!(receives[0].previous, "head0");
+        assert_eq!((receives[0].link.as_str(), receives[0].amount), ("s1", 300));
+        assert_eq!(receives[1].previous, receives[0].calculate_hash());
+        assert_eq!(head, receives[1].calculate_hash());
+        for receive in &receives {
+            assert_eq!(receive.block_type, BlockType::Receive);
+            assert!(receive.verify_signature());
+        }
+    }
+}
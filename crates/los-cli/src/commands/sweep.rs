@@ -0,0 +1,521 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// SWEEP — consolidate many deposit wallets into one address
+//
+//   1. Read every wallet in the keystore directory (address only, no decryption)
+//   2. Query balance, pending receivables and fee for each account
+//   3. Decrypt, build and sign Receive blocks for pending Sends plus one Send of
+//      everything to the destination; PoW runs on all CPU cores in parallel
+//   4. Submit account by account in concurrent batches (Receives before the Send)
+//   5. Write a JSON reconciliation report
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use crate::commands::common::decrypt_wallet_file;
+use crate::commands::receive::{publish_receive, sign_block, signed_receives};
+use crate::commands::tx::compute_pow;
+use crate::{print_error, print_info, print_success};
+use colored::*;
+use los_core::{Block, BlockType, CIL_PER_LOS};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+pub struct SweepOptions {
+    pub to: String,
+    pub keystore: PathBuf,
+    pub batch_size: usize,
+    pub min_amount_cil: u128,
+    pub report: PathBuf,
+    pub dry_run: bool,
+}
+
+#[derive(Serialize, Debug, Default)]
+struct SweepEntry {
+    wallet: String,
+    address: String,
+    /// planned (dry run), swept, skipped or failed
+    status: &'static str,
+    balance_cil: u128,
+    pending_cil: u128,
+    fee_cil: u128,
+    sweep_cil: u128,
+    receive_hashes: Vec<String>,
+    send_hash: Option<String>,
+    error: Option<String>,
+    #[serde(skip)]
+    plan: Option<AccountPlan>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct AccountPlan {
+    head: String,
+    /// (Send hash, amount) of every unclaimed Send, oldest first
+    pending: Vec<(String, u128)>,
+}
+
+/// Receive blocks claiming the pending Sends, then the Send to the destination
+struct SignedChain {
+    receives: Vec<Block>,
+    send: Block,
+}
+
+pub async fn handle(opts: SweepOptions, rpc: &str) -> Result<(), Box<dyn std::error::Error>> {
+    if !los_crypto::validate_address(&opts.to) {
+        print_error("Invalid destination address format. Must be Base58Check with LOS prefix.");
+        return Ok(());
+    }
+    let started_at = now_secs();
+
+    // 1. Keystore
+    let mut entries = read_keystore(&opts.keystore, &opts.to)?;
+    if entries.is_empty() {
+        print_error(&format!(
+            "No wallets to sweep in {}",
+            opts.keystore.display()
+        ));
+        return Ok(());
+    }
+    print_info(&format!(
+        "Sweeping {} wallets from {} into {}",
+        entries.len(),
+        opts.keystore.display(),
+        opts.to
+    ));
+
+    // 2. Account state from the node
+    let client = reqwest::Client::new();
+    let batch_size = opts.batch_size.max(1);
+    for chunk in entries.chunks_mut(batch_size) {
+        let handles: Vec<_> = chunk
+            .iter()
+            .map(|e| {
+                let (client, rpc, address) = (client.clone(), rpc.to_string(), e.address.clone());
+                tokio::spawn(async move { fetch_account(&client, &rpc, &address).await })
+            })
+            .collect();
+        for (entry, handle) in chunk.iter_mut().zip(handles) {
+            match handle.await.map_err(|e| e.to_string()).and_then(|r| r) {
+                Ok((plan, balance, fee)) => {
+                    entry.balance_cil = balance;
+                    entry.pending_cil = plan.pending.iter().map(|(_, a)| a).sum();
+                    entry.fee_cil = fee;
+                    entry.plan = Some(plan);
+                }
+                Err(e) => fail(entry, e),
+            }
+        }
+    }
+    for entry in entries.iter_mut().filter(|e| e.plan.is_some()) {
+        let total = entry.balance_cil.saturating_add(entry.pending_cil);
+        match sweep_amount(total, entry.fee_cil, opts.min_amount_cil) {
+            Some(amount) => {
+                entry.sweep_cil = amount;
+                entry.status = "planned";
+            }
+            None => {
+                entry.status = "skipped";
+                entry.plan = None;
+            }
+        }
+    }
+    let planned = entries.iter().filter(|e| e.plan.is_some()).count();
+    print_success(&format!(
+        "{} of {} accounts have funds to sweep",
+        planned,
+        entries.len()
+    ));
+
+    if !opts.dry_run && planned > 0 {
+        // 3. Decrypt, build, PoW and sign
+        let password = match std::env::var("LOS_WALLET_PASSWORD") {
+            Ok(p) if !p.is_empty() => p,
+            _ => rpassword::prompt_password("Enter keystore password: ")?,
+        };
+        print_info(&format!(
+            "Computing Proof-of-Work and signing for {} accounts...",
+            planned
+        ));
+        let chains = sign_all(&entries, &opts.keystore, &opts.to, &password);
+
+        // 4. Submit in concurrent batches
+        print_info("Submitting blocks...");
+        let mut work: Vec<(usize, SignedChain)> = Vec::new();
+        for (i, chain) in chains.into_iter().enumerate() {
+            match chain {
+                Some(Ok(chain)) => work.push((i, chain)),
+                Some(Err(e)) => fail(&mut entries[i], e),
+                None => {}
+            }
+        }
+        for batch in work.chunks(batch_size) {
+            let handles: Vec<_> = batch
+                .iter()
+                .map(|(i, chain)| {
+                    let (client, rpc) = (client.clone(), rpc.to_string());
+                    let (receives, send) = (chain.receives.clone(), chain.send.clone());
+                    (
+                        *i,
+                        tokio::spawn(async move { submit(&client, &rpc, receives, send).await }),
+                    )
+                })
+                .collect();
+            for (i, handle) in handles {
+                let entry = &mut entries[i];
+                let (received, result) = match handle.await {
+                    Ok(r) => r,
+                    Err(e) => (Vec::new(), Err(e.to_string())),
+                };
+                entry.receive_hashes = received;
+                match result {
+                    Ok(hash) => {
+                        entry.send_hash = Some(hash);
+                        entry.status = "swept";
+                    }
+                    Err(e) => fail(entry, e),
+                }
+            }
+        }
+    }
+
+    // 5. Reconciliation report
+    let count = |status: &str| entries.iter().filter(|e| e.status == status).count();
+    let sum = |f: fn(&SweepEntry) -> u128| -> u128 {
+        entries
+            .iter()
+            .filter(|e| e.status == "swept" || e.status == "planned")
+            .map(f)
+            .sum()
+    };
+    let (swept_cil, fees_cil) = (sum(|e| e.sweep_cil), sum(|e| e.fee_cil));
+    let report = serde_json::json!({
+        "destination": opts.to,
+        "rpc": rpc,
+        "dry_run": opts.dry_run,
+        "started_at": started_at,
+        "finished_at": now_secs(),
+        "totals": {
+            "accounts": entries.len(),
+            "planned": count("planned"),
+            "swept": count("swept"),
+            "skipped": count("skipped"),
+            "failed": count("failed"),
+            "sweep_cil": swept_cil,
+            "fees_cil": fees_cil,
+        },
+        "accounts": entries,
+    });
+    std::fs::write(&opts.report, serde_json::to_string_pretty(&report)?)?;
+
+    println!();
+    println!("{}", "Sweep Summary".bold());
+    if opts.dry_run {
+        println!("  {} {}", "Planned:".bold(), count("planned"));
+    } else {
+        println!(
+            "  {} {}",
+            "Swept:".bold(),
+            count("swept").to_string().green()
+        );
+    }
+    println!("  {} {}", "Skipped:".bold(), count("skipped"));
+    println!(
+        "  {} {}",
+        "Failed:".bold(),
+        count("failed").to_string().red()
+    );
+    println!(
+        "  {} {} LOS (fees {} CIL)",
+        "Amount:".bold(),
+        (swept_cil / CIL_PER_LOS).to_string().cyan(),
+        fees_cil
+    );
+    print_success(&format!("Report written to {}", opts.report.display()));
+    Ok(())
+}
+
+fn fail(entry: &mut SweepEntry, error: String) {
+    entry.status = "failed";
+    entry.error = Some(error);
+    entry.plan = None;
+}
+
+/// Wallet files of the keystore (sorted by name), excluding the destination itself
+fn read_keystore(dir: &Path, destination: &str) -> Result<Vec<SweepEntry>, String> {
+    let read = std::fs::read_dir(dir)
+        .map_err(|e| format!("Keystore {} not readable: {}", dir.display(), e))?;
+    let mut files: Vec<PathBuf> = read
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.extension().is_some_and(|x| x == "json"))
+        .collect();
+    files.sort();
+    let mut entries = Vec::new();
+    for file in files {
+        let wallet = file
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let address = std::fs::read_to_string(&file)
+            .ok()
+            .and_then(|d| serde_json::from_str::<serde_json::Value>(&d).ok())
+            .and_then(|v| v["address"].as_str().map(str::to_string));
+        let mut entry = SweepEntry {
+            wallet,
+            address: address.clone().unwrap_or_default(),
+            ..Default::default()
+        };
+        match address {
+            Some(a) if a == destination => continue,
+            Some(_) => {}
+            None => fail(
+                &mut entry,
+                "Wallet file missing 'address' field".to_string(),
+            ),
+        }
+        entries.push(entry);
+    }
+    Ok(entries)
+}
+
+/// (chain head + pending Sends, balance, fee) of an account
+async fn fetch_account(
+    client: &reqwest::Client,
+    rpc: &str,
+    address: &str,
+) -> Result<(AccountPlan, u128, u128), String> {
+    let get = |path: String| async move {
+        let resp = client
+            .get(format!("{}/{}", rpc, path))
+            .send()
+            .await
+            .map_err(|e| format!("{}: {}", path, e))?;
+        resp.json::<serde_json::Value>()
+            .await
+            .map_err(|e| format!("{}: {}", path, e))
+    };
+    let account = get(format!("balance/{}", address)).await?;
+    let fee = get(format!("fee-estimate/{}", address)).await?;
+
+    let balance: u128 = account["balance_cil_str"]
+        .as_str()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(0);
+    let plan = AccountPlan {
+        head: account["head"].as_str().unwrap_or("0").to_string(),
+        pending: account["pending_receives"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|p| {
+                Some((
+                    p["hash"].as_str()?.to_string(),
+                    p["amount_cil_str"].as_str()?.parse().ok()?,
+                ))
+            })
+            .collect(),
+    };
+    let fee_cil = fee["estimated_fee_cil"]
+        .as_u64()
+        .map(|v| v as u128)
+        .unwrap_or(los_core::BASE_FEE_CIL);
+    Ok((plan, balance, fee_cil))
+}
+
+/// Amount to send after the fee, or None if the account is not worth sweeping
+fn sweep_amount(total_cil: u128, fee_cil: u128, min_amount_cil: u128) -> Option<u128> {
+    let amount = total_cil.checked_sub(fee_cil)?;
+    (amount > 0 && amount >= min_amount_cil).then_some(amount)
+}
+
+/// Build and sign every planned account's chain, accounts spread over all cores.
+/// Index-aligned with `entries`: None where nothing is planned.
+fn sign_all(
+    entries: &[SweepEntry],
+    keystore: &Path,
+    destination: &str,
+    password: &str,
+) -> Vec<Option<Result<SignedChain, String>>> {
+    let results: Vec<Mutex<Option<Result<SignedChain, String>>>> =
+        entries.iter().map(|_| Mutex::new(None)).collect();
+    let next = AtomicUsize::new(0);
+    let workers = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(4);
+    std::thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(entry) = entries.get(i) else {
+                    break;
+                };
+                let Some(plan) = &entry.plan else {
+                    continue;
+                };
+                let chain = decrypt_wallet_file(&keystore.join(&entry.wallet), password)
+                    .map_err(|e| e.to_string())
+                    .and_then(|(address, keypair)| {
+                        if address != entry.address {
+                            return Err("Wallet address changed since planning".to_string());
+                        }
+                        build_chain(
+                            &address,
+                            &keypair,
+                            plan,
+                            destination,
+                            entry.sweep_cil,
+                            entry.fee_cil,
+                            compute_pow,
+                        )
+                    });
+                if let Ok(mut slot) = results[i].lock() {
+                    *slot = Some(chain);
+                }
+            });
+        }
+    });
+    results
+        .into_iter()
+        .map(|m| m.into_inner().unwrap_or(None))
+        .collect()
+}
+
+fn build_chain(
+    address: &str,
+    keypair: &los_crypto::KeyPair,
+    plan: &AccountPlan,
+    destination: &str,
+    sweep_cil: u128,
+    fee_cil: u128,
+    solve_pow: fn(&mut Block),
+) -> Result<SignedChain, String> {
+    let timestamp = now_secs();
+    let (receives, previous) = signed_receives(
+        address,
+        keypair,
+        &plan.head,
+        &plan.pending,
+        timestamp,
+        solve_pow,
+    )?;
+    let mut send = Block {
+        account: address.to_string(),
+        previous,
+        block_type: BlockType::Send,
+        amount: sweep_cil,
+        link: destination.to_string(),
+        signature: String::new(),
+        public_key: hex::encode(&keypair.public_key),
+        work: 0,
+        timestamp,
+        fee: fee_cil,
+        memo: None,
+    };
+    sign_block(&mut send, keypair, solve_pow)?;
+    Ok(SignedChain { receives, send })
+}
+
+/// Publish an account's Receives, then its Send. Returns the accepted Receive
+/// hashes and the Send hash (or the first error).
+async fn submit(
+    client: &reqwest::Client,
+    rpc: &str,
+    receives: Vec<Block>,
+    send: Block,
+) -> (Vec<String>, Result<String, String>) {
+    let post = |path: &'static str, body: serde_json::Value| async move {
+        let resp = client
+            .post(format!("{}/{}", rpc, path))
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| format!("{}: {}", path, e))?;
+        let data: serde_json::Value = resp.json().await.map_err(|e| format!("{}: {}", path, e))?;
+        if matches!(
+            data["status"].as_str(),
+            Some("ok" | "success" | "confirmed")
+        ) {
+            Ok(data)
+        } else {
+            Err(format!(
+                "{}: {}",
+                path,
+                data["msg"].as_str().unwrap_or("Unknown error")
+            ))
+        }
+    };
+
+    let mut received = Vec::new();
+    for receive in &receives {
+        match publish_receive(client, rpc, receive).await {
+            Ok(hash) => received.push(hash),
+            Err(e) => return (received, Err(e)),
+        }
+    }
+    let payload = serde_json::json!({
+        "from": send.account,
+        "target": send.link,
+        "amount": send.amount / CIL_PER_LOS,
+        "amount_cil": send.amount,
+        "previous": send.previous,
+        "signature": send.signature,
+        "public_key": send.public_key,
+        "work": send.work,
+        "timestamp": send.timestamp,
+        "fee": send.fee,
+    });
+    let result = post("send", payload).await.map(|data| {
+        data["tx_hash"]
+            .as_str()
+            .or_else(|| data["hash"].as_str())
+            .map(str::to_string)
+            .unwrap_or_else(|| send.calculate_hash())
+    });
+    (received, result)
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sweep_amount() {
+        assert_eq!(sweep_amount(1_000_000, 100_000, 0), Some(900_000));
+        assert_eq!(sweep_amount(100_000, 100_000, 0), None);
+        assert_eq!(sweep_amount(50_000, 100_000, 0), None);
+        assert_eq!(sweep_amount(1_000_000, 100_000, 950_000), None);
+    }
+
+    #[test]
+    fn test_build_chain_links_receives_before_send() {
+        let keypair = los_crypto::generate_keypair();
+        let address = los_crypto::public_key_to_address(&keypair.public_key);
+        let plan = AccountPlan {
+            head: "head0".to_string(),
+            pending: vec![("s1".to_string(), 300), ("s2".to_string(), 700)],
+        };
+        // PoW skipped: 16-bit PoW over a Dilithium5 key takes minutes in debug builds
+        let chain = build_chain(&address, &keypair, &plan, "LOS_HOT", 900, 100, |_| {}).unwrap();
+
+        assert_eq!(chain.receives.len(), 2);
+        assert_eq!(chain.receives[0].previous, "head0");
+        assert_eq!(chain.receives[0].link, "s1");
+        assert_eq!(
+            chain.receives[1].previous,
+            chain.receives[0].calculate_hash()
+        );
+        assert_eq!(chain.send.previous, chain.receives[1].calculate_hash());
+        assert_eq!(
+            (chain.send.amount, chain.send.fee, chain.send.link.as_str()),
+            (900, 100, "LOS_HOT")
+        );
+        for block in chain.receives.iter().chain([&chain.send]) {
+            assert!(block.verify_signature());
+        }
+    }
+}
@@ -309,7 +309,8 @@ async fn token_deploy(
     let initial_state = std::collections::BTreeMap::new();
 
     let (contract_addr, block_hash) =
-        contract_ops::deploy_contract(wallet, wasm_path, initial_state, 0, rpc, config_dir).await?;
+        contract_ops::deploy_contract(wallet, wasm_path, initial_state, None, 0, rpc, config_dir)
+            .await?;
 
     print_success(&format!("Contract deployed: {}", contract_addr));
 
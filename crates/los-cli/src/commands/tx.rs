@@ -1,4 +1,5 @@
 use crate::commands::common::load_wallet_keypair;
+use crate::commands::receive;
 use crate::{print_error, print_info, print_success, TxCommands};
 use colored::*;
 use los_core::{Block, BlockType, CIL_PER_LOS, MIN_POW_DIFFICULTY_BITS};
@@ -10,10 +11,19 @@ pub async fn handle(
     config_dir: &Path,
 ) -> Result<(), Box<dyn std::error::Error>> {
     match action {
-        TxCommands::Send { to, amount, from } => {
-            send_tx(&to, amount, &from, rpc, config_dir).await?
-        }
+        TxCommands::Send {
+            to,
+            amount,
+            from,
+            memo,
+        } => send_tx(&to, amount, &from, memo, rpc, config_dir).await?,
         TxCommands::Status { hash } => query_status(&hash, rpc).await?,
+        TxCommands::Pending { address } => receive::show_pending(&address, rpc).await?,
+        TxCommands::Receive {
+            wallet,
+            watch,
+            interval,
+        } => receive::receive_pending(&wallet, watch, interval, rpc, config_dir).await?,
     }
     Ok(())
 }
@@ -48,6 +58,7 @@ async fn send_tx(
     to: &str,
     amount: u64,
     from_wallet: &str,
+    memo: Option<String>,
     rpc: &str,
     config_dir: &Path,
 ) -> Result<(), Box<dyn std::error::Error>> {
@@ -124,7 +135,12 @@ async fn send_tx(
         work: 0,
         timestamp,
         fee: fee_cil, // Include proper fee from fee-estimate
+        memo,
     };
+    if let Err(e) = block.validate_memo() {
+        print_error(&e);
+        return Ok(());
+    }
 
     // 4. Compute PoW (anti-spam)
     print_info("Computing Proof-of-Work...");
@@ -152,6 +168,7 @@ async fn send_tx(
         "work": block.work,
         "timestamp": block.timestamp,
         "fee": block.fee,
+        "memo": block.memo,
     });
 
     let resp = client.post(&send_url).json(&payload).send().await?;
@@ -187,50 +204,72 @@ async fn query_status(tx_hash: &str, rpc: &str) -> Result<(), Box<dyn std::error
     print_info(&format!("Querying transaction {}...", tx_hash));
 
     let client = reqwest::Client::new();
-    let url = format!("{}/tx/{}", rpc, tx_hash);
-
-    match client.get(&url).send().await {
-        Ok(response) => {
-            if response.status().is_success() {
-                let data: serde_json::Value = response.json().await?;
-
-                println!();
-                println!("{} {}", "Transaction Hash:".bold(), tx_hash.green());
-                println!(
-                    "{} {}",
-                    "Status:".bold(),
-                    if data["confirmed"].as_bool().unwrap_or(false) {
-                        "Confirmed ✓".green()
-                    } else {
-                        "Pending...".yellow()
-                    }
-                );
-                println!(
-                    "{} {}",
-                    "Block Height:".bold(),
-                    data["block_height"].as_u64().unwrap_or(0)
-                );
-                println!(
-                    "{} {} → {}",
-                    "Transfer:".bold(),
-                    data["from"].as_str().unwrap_or("Unknown").dimmed(),
-                    data["to"].as_str().unwrap_or("Unknown").dimmed()
-                );
-                println!(
-                    "{} {} LOS",
-                    "Amount:".bold(),
-                    data["amount"].as_u64().unwrap_or(0).to_string().cyan()
-                );
-            } else {
-                print_error(&format!(
-                    "Transaction not found: HTTP {}",
-                    response.status()
-                ));
-            }
-        }
-        Err(e) => {
-            print_error(&format!("Network error: {}", e));
+    let data: serde_json::Value = client
+        .get(format!("{}/transaction/{}/status", rpc, tx_hash))
+        .send()
+        .await?
+        .json()
+        .await?;
+    if data["status"].as_str() != Some("success") {
+        let msg = data["msg"].as_str().unwrap_or("Unknown error");
+        print_error(&format!("Transaction not found: {}", msg));
+        return Ok(());
+    }
+    let tx = &data["transaction"];
+    let state = tx["state"].as_str().unwrap_or("unknown");
+
+    println!();
+    println!("{} {}", "Transaction Hash:".bold(), tx_hash.green());
+    println!(
+        "{} {}",
+        "Status:".bold(),
+        match state {
+            "final" => "Final ✓ (checkpointed)".green(),
+            "applied" => "Applied (awaiting checkpoint)".green(),
+            "rejected" => "Rejected ✗".red(),
+            other => other.replace('_', " ").yellow(),
         }
+    );
+    if let Some(account) = tx["account"].as_str() {
+        println!("{} {}", "Sender:".bold(), account.dimmed());
+    }
+    if let Some(reason) = tx["reason"].as_str() {
+        println!("{} {}", "Reason:".bold(), reason.red());
+    }
+    if state == "awaiting_quorum" {
+        println!(
+            "{} {}/{} power, {}/{} voters",
+            "Votes:".bold(),
+            tx["vote_power"],
+            tx["vote_threshold"],
+            tx["voters"],
+            tx["min_voters"]
+        );
+    }
+    if let Some(height) = tx["checkpoint_height"].as_u64() {
+        println!("{} {}", "Latest Checkpoint:".bold(), height);
+    }
+
+    // Applied transactions also have block details
+    if state == "applied" || state == "final" {
+        let block: serde_json::Value = client
+            .get(format!("{}/transaction/{}", rpc, tx_hash))
+            .send()
+            .await?
+            .json()
+            .await?;
+        let t = &block["transaction"];
+        println!(
+            "{} {} → {}",
+            "Transfer:".bold(),
+            t["from"].as_str().unwrap_or("Unknown").dimmed(),
+            t["to"].as_str().unwrap_or("Unknown").dimmed()
+        );
+        println!(
+            "{} {} LOS",
+            "Amount:".bold(),
+            t["amount"].as_u64().unwrap_or(0).to_string().cyan()
+        );
     }
 
     Ok(())
@@ -27,6 +27,10 @@ struct Cli {
     #[arg(short, long)]
     config_dir: Option<PathBuf>,
 
+    /// Chain ID to sign for (default: this build's network; devnets use their genesis chain_id)
+    #[arg(long, env = "LOS_CHAIN_ID")]
+    chain_id: Option<u64>,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -68,6 +72,111 @@ enum Commands {
         #[command(subcommand)]
         action: DexCommands,
     },
+
+    /// Generic smart contract operations (ABI-aware)
+    Contract {
+        #[command(subcommand)]
+        action: ContractCommands,
+    },
+
+    /// On-chain governance (chain parameter proposals)
+    Governance {
+        #[command(subcommand)]
+        action: GovernanceCommands,
+    },
+
+    /// Consolidate every wallet of a keystore into one address
+    /// (claims pending receives, sends the rest minus fee, writes a report)
+    Sweep {
+        /// Destination (hot wallet) address
+        #[arg(long)]
+        to: String,
+
+        /// Keystore directory of wallet files (default: <config-dir>/wallets).
+        /// All wallets must share one password (LOS_WALLET_PASSWORD or prompt).
+        #[arg(long)]
+        keystore: Option<PathBuf>,
+
+        /// Accounts submitted concurrently
+        #[arg(long, default_value = "50")]
+        batch_size: usize,
+
+        /// Skip accounts whose amount after fee is below this (CIL)
+        #[arg(long, default_value = "0")]
+        min_amount_cil: u128,
+
+        /// Reconciliation report path (default: sweep-report-<unix time>.json)
+        #[arg(long)]
+        report: Option<PathBuf>,
+
+        /// Query and plan only: nothing is signed or submitted
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum ContractCommands {
+    /// Show a contract's ABI (functions, argument types, events)
+    Abi {
+        /// Contract address (LOSCon...)
+        address: String,
+    },
+
+    /// Deploy a WASM contract
+    Deploy {
+        /// Wallet name
+        #[arg(short, long)]
+        wallet: String,
+
+        /// Path to compiled WASM file
+        #[arg(long)]
+        wasm: String,
+
+        /// ABI JSON file (overrides an ABI embedded in the WASM)
+        #[arg(long)]
+        abi: Option<String>,
+
+        /// Initial contract funding (CIL)
+        #[arg(long, default_value = "0")]
+        amount: u128,
+    },
+
+    /// Call a contract function (args validated against the ABI if present)
+    Call {
+        /// Wallet name
+        #[arg(short, long)]
+        wallet: String,
+
+        /// Contract address
+        address: String,
+
+        /// Function name
+        function: String,
+
+        /// Arguments, positional or name=value
+        args: Vec<String>,
+
+        /// Gas limit (default: simulated usage + headroom)
+        #[arg(long)]
+        gas_limit: Option<u64>,
+
+        /// CIL to send with the call
+        #[arg(long, default_value = "0")]
+        amount: u128,
+    },
+
+    /// Run a read-only query (no transaction, no fee)
+    Query {
+        /// Contract address
+        address: String,
+
+        /// Function name
+        function: String,
+
+        /// Arguments, positional or name=value
+        args: Vec<String>,
+    },
 }
 
 #[derive(Subcommand)]
@@ -175,6 +284,10 @@ enum TxCommands {
         /// Sender wallet name
         #[arg(short, long)]
         from: String,
+
+        /// Payment ID / deposit tag for the recipient (max 64 bytes; needs chain params v2)
+        #[arg(long)]
+        memo: Option<String>,
     },
 
     /// Query transaction status
@@ -182,6 +295,91 @@ enum TxCommands {
         /// Transaction hash
         hash: String,
     },
+
+    /// List unclaimed Sends addressed to an account
+    Pending {
+        /// Account address
+        address: String,
+    },
+
+    /// Publish Receive blocks for the pending Sends of local wallets
+    Receive {
+        /// Wallet name (repeat for several wallets)
+        #[arg(short, long, required = true)]
+        wallet: Vec<String>,
+
+        /// Keep running and receive new Sends as they arrive
+        #[arg(long)]
+        watch: bool,
+
+        /// Polling interval in seconds (with --watch)
+        #[arg(long, default_value = "10")]
+        interval: u64,
+    },
+}
+
+#[derive(Subcommand)]
+enum GovernanceCommands {
+    /// Show governable parameters and the values in force
+    Params,
+
+    /// List proposals (newest first)
+    List,
+
+    /// Show a proposal with its stake-weighted tally
+    Show {
+        /// Proposal ID (hash of the Proposal block)
+        id: String,
+    },
+
+    /// Propose a parameter change (locks a deposit until the tally)
+    Propose {
+        /// Proposer wallet name
+        #[arg(short, long)]
+        wallet: String,
+
+        /// Parameter name (see `governance params`)
+        #[arg(short, long)]
+        param: String,
+
+        /// New value (atomic units)
+        #[arg(long)]
+        value: String,
+
+        /// Unix time (seconds) at which a passed proposal takes effect
+        #[arg(long)]
+        activation_time: u64,
+
+        /// Deposit in LOS (default: the protocol minimum)
+        #[arg(long)]
+        deposit: Option<u64>,
+    },
+
+    /// Vote on an open proposal (validators only)
+    Vote {
+        /// Validator wallet name
+        #[arg(short, long)]
+        wallet: String,
+
+        /// Proposal ID
+        #[arg(long)]
+        proposal: String,
+
+        /// Vote: yes or no
+        #[arg(long, value_parser = ["yes", "no"])]
+        choice: String,
+    },
+
+    /// Settle your proposal after its voting window (refunds the deposit if it passed)
+    Settle {
+        /// Proposer wallet name
+        #[arg(short, long)]
+        wallet: String,
+
+        /// Proposal ID
+        #[arg(long)]
+        proposal: String,
+    },
 }
 
 #[derive(Subcommand)]
@@ -532,6 +730,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Ensure config directory exists
     std::fs::create_dir_all(&config_dir)?;
 
+    // Signed blocks commit to the chain ID (replay protection)
+    if let Some(chain_id) = cli.chain_id {
+        los_core::chain_params::set_network_params(&los_core::chain_params::ChainParams {
+            chain_id,
+            ..los_core::chain_params::ChainParams::for_build()
+        });
+    }
+
     match cli.command {
         Commands::Wallet { action } => {
             commands::wallet::handle(action, &cli.rpc, &config_dir).await?;
@@ -549,6 +755,37 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             commands::token::handle(action, &cli.rpc, &config_dir).await?;
         }
         Commands::Dex { action } => commands::dex::handle(action, &cli.rpc, &config_dir).await?,
+        Commands::Contract { action } => {
+            commands::contract::handle(action, &cli.rpc, &config_dir).await?
+        }
+        Commands::Governance { action } => {
+            commands::governance::handle(action, &cli.rpc, &config_dir).await?
+        }
+        Commands::Sweep {
+            to,
+            keystore,
+            batch_size,
+            min_amount_cil,
+            report,
+            dry_run,
+        } => {
+            let report = report.unwrap_or_else(|| {
+                let now = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs();
+                PathBuf::from(format!("sweep-report-{}.json", now))
+            });
+            let opts = commands::sweep::SweepOptions {
+                to,
+                keystore: keystore.unwrap_or_else(|| config_dir.join("wallets")),
+                batch_size,
+                min_amount_cil,
+                report,
+                dry_run,
+            };
+            commands::sweep::handle(opts, &cli.rpc).await?
+        }
     }
 
     Ok(())
@@ -722,14 +959,84 @@ mod tests {
         assert!(cli.is_ok());
         match cli.unwrap().command {
             Commands::Tx {
-                action: TxCommands::Send { to, amount, from },
+                action:
+                    TxCommands::Send {
+                        to,
+                        amount,
+                        from,
+                        memo,
+                    },
             } => {
                 assert_eq!(to, "LOSabc");
                 assert_eq!(amount, 50);
                 assert_eq!(from, "w1");
+                assert!(memo.is_none());
             }
             _ => panic!("Expected Tx::Send"),
         }
+
+        let cli = Cli::try_parse_from([
+            "los-cli", "tx", "send", "--to", "LOSabc", "--amount", "50", "--from", "w1", "--memo",
+            "user-42",
+        ]);
+        match cli.unwrap().command {
+            Commands::Tx {
+                action: TxCommands::Send { memo, .. },
+            } => assert_eq!(memo.as_deref(), Some("user-42")),
+            _ => panic!("Expected Tx::Send"),
+        }
+    }
+
+    #[test]
+    fn test_cli_tx_receive() {
+        let cli = Cli::try_parse_from([
+            "los-cli", "tx", "receive", "--wallet", "w1", "--wallet", "w2", "--watch",
+        ]);
+        match cli.unwrap().command {
+            Commands::Tx {
+                action:
+                    TxCommands::Receive {
+                        wallet,
+                        watch,
+                        interval,
+                    },
+            } => {
+                assert_eq!(wallet, vec!["w1".to_string(), "w2".to_string()]);
+                assert!(watch);
+                assert_eq!(interval, 10);
+            }
+            _ => panic!("Expected Tx::Receive"),
+        }
+        assert!(Cli::try_parse_from(["los-cli", "tx", "receive"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_sweep() {
+        let cli = Cli::try_parse_from([
+            "los-cli",
+            "sweep",
+            "--to",
+            "LOSabc",
+            "--keystore",
+            "/tmp/deposits",
+            "--dry-run",
+        ]);
+        match cli.unwrap().command {
+            Commands::Sweep {
+                to,
+                keystore,
+                batch_size,
+                dry_run,
+                ..
+            } => {
+                assert_eq!(to, "LOSabc");
+                assert_eq!(keystore, Some(PathBuf::from("/tmp/deposits")));
+                assert_eq!(batch_size, 50);
+                assert!(dry_run);
+            }
+            _ => panic!("Expected Sweep"),
+        }
+        assert!(Cli::try_parse_from(["los-cli", "sweep"]).is_err());
     }
 
     #[test]
@@ -792,6 +1099,38 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cli_contract_call_args() {
+        let cli = Cli::try_parse_from([
+            "los-cli",
+            "contract",
+            "call",
+            "-w",
+            "alice",
+            "LOSConXYZ",
+            "transfer",
+            "to=LOSbob",
+            "amount=5",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Contract {
+                action:
+                    ContractCommands::Call {
+                        function,
+                        args,
+                        gas_limit,
+                        ..
+                    },
+            } => {
+                assert_eq!(function, "transfer");
+                assert_eq!(args, vec!["to=LOSbob", "amount=5"]);
+                assert_eq!(gas_limit, None);
+            }
+            _ => panic!("Expected Contract::Call"),
+        }
+    }
+
     #[test]
     fn test_cli_token_info() {
         let cli = Cli::try_parse_from(["los-cli", "token", "info", "LOSConXYZ"]);
@@ -832,6 +1171,61 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cli_chain_id_flag() {
+        let cli = Cli::try_parse_from(["los-cli", "--chain-id", "1337", "wallet", "list"]).unwrap();
+        assert_eq!(cli.chain_id, Some(1337));
+    }
+
+    #[test]
+    fn test_cli_governance_propose_and_vote() {
+        let cli = Cli::try_parse_from([
+            "los-cli",
+            "governance",
+            "propose",
+            "--wallet",
+            "w1",
+            "--param",
+            "base_fee_cil",
+            "--value",
+            "200000",
+            "--activation-time",
+            "1800000000",
+        ]);
+        match cli.map(|c| c.command) {
+            Ok(Commands::Governance {
+                action:
+                    GovernanceCommands::Propose {
+                        value,
+                        activation_time,
+                        deposit,
+                        ..
+                    },
+            }) => {
+                assert_eq!(value, "200000");
+                assert_eq!(activation_time, 1_800_000_000);
+                assert_eq!(deposit, None);
+            }
+            _ => panic!("Expected Governance::Propose"),
+        }
+
+        let vote = [
+            "los-cli",
+            "governance",
+            "vote",
+            "-w",
+            "v1",
+            "--proposal",
+            "abc",
+        ];
+        assert!(Cli::try_parse_from(vote.iter().chain(&["--choice", "yes"])).is_ok());
+        assert!(Cli::try_parse_from(vote.iter().chain(&["--choice", "maybe"])).is_err());
+
+        let settle = ["los-cli", "governance", "settle", "-w", "w1"];
+        assert!(Cli::try_parse_from(settle.iter().chain(&["--proposal", "abc"])).is_ok());
+        assert!(Cli::try_parse_from(settle).is_err());
+    }
+
     #[test]
     fn test_cli_dex_swap() {
         let cli = Cli::try_parse_from([
@@ -897,6 +1291,7 @@ mod tests {
             work: 0,
             timestamp: 1700000000,
             fee: 100_000,
+            memo: None,
         };
 
         commands::tx::compute_pow(&mut block);
@@ -921,6 +1316,7 @@ mod tests {
             work: 0,
             timestamp: 1700000000,
             fee: 100_000,
+            memo: None,
         };
 
         // With a random nonce of 0, this is likely invalid (but not guaranteed)
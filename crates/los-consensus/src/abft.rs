@@ -8,6 +8,8 @@
 // - Cryptographic message authentication codes (MAC)
 // ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
 
+use crate::epoch::{EpochCommit, EpochSignature, EpochTransition, ValidatorSetEpoch};
+use crate::wal::{ConfirmVote, ConsensusWal};
 use serde::{Deserialize, Serialize};
 use sha3::{Digest, Keccak256};
 use std::collections::{BTreeMap, VecDeque};
@@ -194,10 +196,45 @@ pub struct ABFTConsensus {
     #[serde(skip, default)]
     pub shared_secret: Vec<u8>,
 
-    // Validator set: ordered list of real validator addresses for leader selection (C-04 fix)
-    // Index-based round-robin uses these instead of synthetic "validator-N" names.
+    // Validator set: ordered list of real validator addresses (C-04 fix).
+    // Mirrors `current_epoch` addresses; kept for callers that only need the list.
     #[serde(default)]
     pub validator_set: Vec<String>,
+
+    // Validator set epochs: the active set only changes through a transition
+    // signed by a quorum of the running epoch (replayed from the WAL).
+    // Leader selection is stake-weighted from `current_epoch.seed`.
+    #[serde(default)]
+    pub current_epoch: ValidatorSetEpoch,
+
+    // Set staged by register/unregister: this node's projection of the next
+    // epoch, until an agreed transition opens it.
+    #[serde(default)]
+    pub pending_validator_set: Option<Vec<(String, u128)>>,
+
+    // Transition into the next epoch this validator signed (replayed from the WAL)
+    #[serde(default)]
+    pub epoch_lock: Option<EpochTransition>,
+
+    // Signatures collected for next-epoch transitions, keyed by digest
+    #[serde(skip)]
+    epoch_signatures: BTreeMap<String, EpochCommit>,
+
+    // Own signed votes per sequence (anti-equivocation, replayed from the WAL)
+    #[serde(default)]
+    pub signed_votes: BTreeMap<u64, Vec<ConsensusMessage>>,
+
+    // Highest finalized sequence — never vote at or below it again
+    #[serde(default)]
+    pub finalized_sequence: u64,
+
+    // Own account-chain confirmations, keyed "account:previous"
+    #[serde(default)]
+    pub confirm_votes: BTreeMap<String, ConfirmVote>,
+
+    // Crash recovery: write-ahead log of own votes + view (None = in-memory only)
+    #[serde(skip)]
+    wal: Option<ConsensusWal>,
 }
 
 impl ABFTConsensus {
@@ -225,39 +262,457 @@ impl ABFTConsensus {
             view_changes: 0,
             shared_secret: Vec::new(),
             validator_set: Vec::new(),
+            current_epoch: ValidatorSetEpoch::default(),
+            pending_validator_set: None,
+            epoch_lock: None,
+            epoch_signatures: BTreeMap::new(),
+            signed_votes: BTreeMap::new(),
+            finalized_sequence: 0,
+            confirm_votes: BTreeMap::new(),
+            wal: None,
         }
     }
 
+    /// Create aBFT consensus engine backed by a write-ahead log.
+    ///
+    /// Replays view, sequence, locked block, own signed votes and the running
+    /// epoch from `wal`, so a validator that restarts mid-round resumes where it
+    /// crashed and refuses to sign a conflicting vote for a sequence it already
+    /// voted on.
+    pub fn with_wal(
+        validator_id: String,
+        total_validators: usize,
+        wal: ConsensusWal,
+    ) -> Result<Self, String> {
+        let mut engine = Self::new(validator_id, total_validators);
+        let recovered = wal.recover()?;
+
+        if let Some(vs) = recovered.view_state {
+            engine.view = vs.view;
+            engine.sequence = vs.sequence;
+        }
+        engine.finalized_sequence = recovered.finalized_sequence;
+        engine.confirm_votes = recovered.confirm_votes;
+        if let Some(epoch) = recovered.epoch {
+            engine.install_epoch(epoch);
+        }
+        engine.epoch_lock = recovered
+            .epoch_lock
+            .filter(|t| t.epoch > engine.current_epoch.epoch);
+        if let Some(block) = recovered.locked_block {
+            engine.locked_block = Some(block);
+            engine.locked_view = engine.view;
+            engine.state = ValidatorState::Locked;
+        }
+        for msg in recovered.votes {
+            engine.sequence = engine.sequence.max(msg.sequence);
+            if msg.msg_type == ConsensusMessageType::PrePrepare {
+                engine
+                    .pre_prepare_messages
+                    .insert(msg.sequence, msg.clone());
+            }
+            engine
+                .signed_votes
+                .entry(msg.sequence)
+                .or_default()
+                .push(msg);
+        }
+
+        engine.wal = Some(wal);
+        Ok(engine)
+    }
+
     /// Set shared secret for MAC authentication.
     /// Should be derived from node's secret key (e.g., Keccak256(sk)).
     pub fn set_shared_secret(&mut self, secret: Vec<u8>) {
         self.shared_secret = secret;
     }
 
-    /// Update the ordered validator set for leader selection.
-    /// Must be sorted deterministically (e.g., by address) to ensure all nodes agree.
+    /// Replace the active validator set immediately with equal stake weights.
+    /// Only for tests — live membership changes go through an agreed
+    /// `EpochTransition`.
     pub fn update_validator_set(&mut self, validators: Vec<String>) {
-        self.total_validators = validators.len().max(1);
+        self.update_validator_set_with_stake(validators.into_iter().map(|v| (v, 0)).collect());
+    }
+
+    /// Replace the active validator set immediately, keeping the current epoch
+    /// number and seed. Not persisted: only for tests and simulations.
+    pub fn update_validator_set_with_stake(&mut self, stakes: Vec<(String, u128)>) {
+        let epoch = ValidatorSetEpoch::new(
+            self.current_epoch.epoch,
+            self.current_epoch.start_height,
+            self.current_epoch.seed.clone(),
+            stakes,
+        );
+        self.install_epoch(epoch);
+        self.pending_validator_set = None;
+    }
+
+    /// Open the genesis epoch from `stakes` unless the WAL restored one.
+    /// Returns true if the genesis epoch was installed.
+    pub fn bootstrap_epoch(&mut self, stakes: Vec<(String, u128)>) -> Result<bool, String> {
+        if !self.current_epoch.seed.is_empty() {
+            return Ok(false);
+        }
+        let epoch = ValidatorSetEpoch::genesis(stakes);
+        if let Some(wal) = &self.wal {
+            wal.record_epoch(&epoch)?;
+            wal.flush()?;
+        }
+        self.install_epoch(epoch);
+        Ok(true)
+    }
+
+    /// Stage a validator set change (register/unregister).
+    /// Only a projection: the next epoch opens with the agreed transition.
+    pub fn schedule_validator_set(&mut self, stakes: Vec<(String, u128)>) {
+        self.pending_validator_set = Some(stakes);
+    }
+
+    /// Validator that proposes the next epoch's set in `round`
+    pub fn epoch_proposer(&self, round: u64) -> Option<&str> {
+        self.current_epoch.transition_proposer(round)
+    }
+
+    /// Transition to re-propose for the next epoch: the one this validator
+    /// signed, else the one with the most signatures seen so far.
+    pub fn epoch_candidate(&self) -> Option<EpochTransition> {
+        let next = self.current_epoch.epoch + 1;
+        self.epoch_lock
+            .clone()
+            .filter(|t| t.epoch == next)
+            .or_else(|| {
+                self.epoch_signatures
+                    .values()
+                    .filter(|c| c.transition.epoch == next)
+                    .max_by_key(|c| c.signatures.len())
+                    .map(|c| c.transition.clone())
+            })
+    }
+
+    /// Sign a transition into the next epoch (this node's EPOCH_SIGN).
+    ///
+    /// One transition per epoch: signing the same one again is allowed, a
+    /// different one is refused, so two conflicting sets can never both reach
+    /// a quorum. The lock is written to the WAL before returning; flush the
+    /// WAL (see `wal`) before sending the signature.
+    pub fn sign_epoch_transition(&mut self, transition: &EpochTransition) -> Result<(), String> {
+        if transition.epoch != self.current_epoch.epoch + 1 {
+            return Err(format!(
+                "transition into epoch {} while epoch {} is running",
+                transition.epoch, self.current_epoch.epoch
+            ));
+        }
+        if !self.validator_set.contains(&self.validator_id) {
+            return Err("not a validator of the running epoch".to_string());
+        }
+        match &self.epoch_lock {
+            Some(locked) if locked == transition => return Ok(()),
+            Some(locked) if locked.epoch == transition.epoch => {
+                return Err(format!(
+                    "already signed transition {} for epoch {}",
+                    locked.digest(),
+                    locked.epoch
+                ));
+            }
+            _ => {}
+        }
+        if let Some(wal) = &self.wal {
+            wal.record_epoch_lock(Some(transition))?;
+        }
+        self.epoch_lock = Some(transition.clone());
+        Ok(())
+    }
+
+    /// Count a signature (already verified by the caller) on a transition into
+    /// the next epoch. Signers outside the running epoch are ignored. Once a
+    /// quorum of the running epoch has signed, the next epoch is opened and
+    /// its commit returned.
+    pub fn add_epoch_signature(
+        &mut self,
+        transition: &EpochTransition,
+        signature: EpochSignature,
+    ) -> Result<Option<EpochCommit>, String> {
+        if transition.epoch != self.current_epoch.epoch + 1
+            || !self.validator_set.contains(&signature.signer)
+        {
+            return Ok(None);
+        }
+        let commit = self
+            .epoch_signatures
+            .entry(transition.digest())
+            .or_insert_with(|| EpochCommit {
+                transition: transition.clone(),
+                signatures: Vec::new(),
+            });
+        if commit
+            .signatures
+            .iter()
+            .any(|s| s.signer == signature.signer)
+        {
+            return Ok(None);
+        }
+        commit.signatures.push(signature);
+
+        if commit.signatures.len() < EpochTransition::quorum(self.current_epoch.len()) {
+            return Ok(None);
+        }
+        let commit = commit.clone();
+        self.open_epoch(&commit)?;
+        Ok(Some(commit))
+    }
+
+    /// Open the next epoch from a commit received from a peer (signatures
+    /// already verified by the caller). Returns true if the epoch was opened;
+    /// commits for any other epoch, or without a quorum of the running epoch,
+    /// are ignored.
+    pub fn apply_epoch_commit(&mut self, commit: &EpochCommit) -> Result<bool, String> {
+        if commit.transition.epoch != self.current_epoch.epoch + 1 {
+            return Ok(false);
+        }
+        let signers: std::collections::BTreeSet<&str> = commit
+            .signatures
+            .iter()
+            .map(|s| s.signer.as_str())
+            .filter(|s| self.validator_set.iter().any(|v| v == s))
+            .collect();
+        if signers.len() < EpochTransition::quorum(self.current_epoch.len()) {
+            return Ok(false);
+        }
+        self.open_epoch(commit)?;
+        Ok(true)
+    }
+
+    /// Agreed transitions into epochs after `epoch`, for peers replaying the chain
+    pub fn epoch_commits_after(&self, epoch: u64, limit: usize) -> Vec<EpochCommit> {
+        self.wal
+            .as_ref()
+            .map(|wal| wal.epoch_commits_after(epoch, limit))
+            .unwrap_or_default()
+    }
+
+    /// Install the epoch an agreed transition opens (WAL first)
+    fn open_epoch(&mut self, commit: &EpochCommit) -> Result<(), String> {
+        let epoch = commit.transition.open(&self.current_epoch.seed);
+        if let Some(wal) = &self.wal {
+            wal.record_epoch_commit(commit)?;
+            wal.record_epoch(&epoch)?;
+            wal.record_epoch_lock(None)?;
+        }
+        let opened = epoch.epoch;
+        self.install_epoch(epoch);
+        self.epoch_lock = None;
+        self.epoch_signatures
+            .retain(|_, c| c.transition.epoch > opened);
+        self.pending_validator_set = None;
+        Ok(())
+    }
+
+    /// Validator set of the running epoch
+    pub fn current_epoch_set(&self) -> &ValidatorSetEpoch {
+        &self.current_epoch
+    }
+
+    /// Projected validator set of the next epoch.
+    /// The seed is empty until the transition is agreed.
+    pub fn next_epoch_set(&self) -> ValidatorSetEpoch {
+        let stakes = self
+            .pending_validator_set
+            .clone()
+            .unwrap_or_else(|| self.current_epoch.stakes());
+        let next = self.current_epoch.epoch + 1;
+        ValidatorSetEpoch::new(
+            next,
+            next * crate::checkpoint::CHECKPOINT_INTERVAL,
+            String::new(),
+            stakes,
+        )
+    }
+
+    fn install_epoch(&mut self, mut epoch: ValidatorSetEpoch) {
+        if epoch.seed.is_empty() {
+            epoch.seed = ValidatorSetEpoch::genesis(Vec::new()).seed;
+        }
+        self.total_validators = epoch.len().max(1);
         self.f_max_faulty = (self.total_validators - 1) / 3;
-        self.validator_set = validators;
+        self.validator_set = epoch.addresses();
+        self.current_epoch = epoch;
     }
 
     /// Create a MAC-authenticated consensus message using the engine's shared secret.
     fn create_message(
         &self,
         msg_type: ConsensusMessageType,
+        sequence: u64,
         block_hash: String,
     ) -> ConsensusMessage {
         ConsensusMessage::new_with_secret(
             msg_type,
             self.view,
-            self.sequence,
+            sequence,
             block_hash,
             self.validator_id.clone(),
             &self.shared_secret,
         )
     }
 
+    /// Sign a PREPARE vote for `block_hash` at `sequence`.
+    pub fn vote_prepare(
+        &mut self,
+        sequence: u64,
+        block_hash: String,
+    ) -> Result<ConsensusMessage, String> {
+        self.sign_vote(ConsensusMessageType::Prepare, sequence, block_hash)
+    }
+
+    /// Sign a COMMIT vote for `block_hash` at `sequence`.
+    pub fn vote_commit(
+        &mut self,
+        sequence: u64,
+        block_hash: String,
+    ) -> Result<ConsensusMessage, String> {
+        self.sign_vote(ConsensusMessageType::Commit, sequence, block_hash)
+    }
+
+    /// Sign a vote with equivocation protection.
+    ///
+    /// Re-signing the same block at the same view and sequence returns the
+    /// original message (safe to re-broadcast); a later view may re-sign the
+    /// same block. A vote for a different block at a sequence we already voted
+    /// on is refused in every view, so a view change cannot lead to two
+    /// conflicting votes. The vote is written to the WAL before it is
+    /// returned, so a crash cannot erase it.
+    fn sign_vote(
+        &mut self,
+        msg_type: ConsensusMessageType,
+        sequence: u64,
+        block_hash: String,
+    ) -> Result<ConsensusMessage, String> {
+        if sequence <= self.finalized_sequence {
+            return Err(format!(
+                "Sequence {} already finalized (finalized up to {})",
+                sequence, self.finalized_sequence
+            ));
+        }
+
+        if let Some(previous) = self.find_signed_vote(&msg_type, sequence) {
+            if previous.block_hash != block_hash {
+                return Err(format!(
+                    "Equivocation refused: already signed {:?} for {} at sequence {}",
+                    msg_type, previous.block_hash, sequence
+                ));
+            }
+            if previous.view == self.view {
+                return Ok(previous.clone());
+            }
+        }
+
+        let message = self.create_message(msg_type, sequence, block_hash);
+        if let Some(wal) = &self.wal {
+            wal.record_vote(&message)?;
+        }
+        self.signed_votes
+            .entry(sequence)
+            .or_default()
+            .push(message.clone());
+
+        Ok(message)
+    }
+
+    /// Block this node confirmed after `previous` on `account`, if any
+    pub fn confirmed_block(&self, account: &str, previous: &str) -> Option<&str> {
+        self.confirm_votes
+            .get(&format!("{}:{}", account, previous))
+            .map(|vote| vote.block_hash.as_str())
+    }
+
+    /// Write-ahead log handle, so callers can make writes durable (`ConsensusWal::flush`)
+    /// without holding the engine lock
+    pub fn wal(&self) -> Option<ConsensusWal> {
+        self.wal.clone()
+    }
+
+    /// Confirm an account-chain block (this node's CONFIRM_RES vote).
+    ///
+    /// One confirmation per (account, previous): confirming the same block
+    /// again is allowed, a competing block for the same slot is refused. The
+    /// confirmation is written to the WAL before it is returned; flush the WAL
+    /// (see `wal`) before sending it.
+    pub fn vote_confirm(
+        &mut self,
+        account: &str,
+        previous: &str,
+        block_hash: &str,
+    ) -> Result<(), String> {
+        let key = format!("{}:{}", account, previous);
+        if let Some(confirmed) = self.confirm_votes.get(&key) {
+            if confirmed.block_hash == block_hash {
+                return Ok(());
+            }
+            return Err(format!(
+                "Equivocation refused: already confirmed {} after {} on {}",
+                confirmed.block_hash, previous, account
+            ));
+        }
+        let vote = ConfirmVote {
+            block_hash: block_hash.to_string(),
+            confirmed_at: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+        };
+        if let Some(wal) = &self.wal {
+            wal.record_confirm_vote(&key, &vote)?;
+        }
+        self.confirm_votes.insert(key, vote);
+        Ok(())
+    }
+
+    /// Forget confirmations made before `expired_before` (unix seconds) and those
+    /// whose slot is settled, i.e. `settled(account, previous)` holds.
+    /// Returns how many were dropped.
+    pub fn prune_confirm_votes(
+        &mut self,
+        expired_before: u64,
+        settled: impl Fn(&str, &str) -> bool,
+    ) -> Result<usize, String> {
+        let keys: Vec<String> = self
+            .confirm_votes
+            .iter()
+            .filter(|(key, vote)| {
+                vote.confirmed_at < expired_before
+                    || key
+                        .split_once(':')
+                        .is_some_and(|(account, previous)| settled(account, previous))
+            })
+            .map(|(key, _)| key.clone())
+            .collect();
+        if keys.is_empty() {
+            return Ok(0);
+        }
+        if let Some(wal) = &self.wal {
+            wal.remove_confirm_votes(&keys)?;
+        }
+        for key in &keys {
+            self.confirm_votes.remove(key);
+        }
+        Ok(keys.len())
+    }
+
+    /// Own latest vote of `msg_type` at `sequence`, if already signed.
+    /// All of them are for the same block (see `sign_vote`).
+    fn find_signed_vote(
+        &self,
+        msg_type: &ConsensusMessageType,
+        sequence: u64,
+    ) -> Option<&ConsensusMessage> {
+        self.signed_votes
+            .get(&sequence)?
+            .iter()
+            .filter(|v| &v.msg_type == msg_type)
+            .max_by_key(|v| v.view)
+    }
+
     /// Verify a consensus message's MAC using the engine's shared secret.
     fn verify_message(&self, msg: &ConsensusMessage) -> bool {
         msg.verify_mac_with_secret(&self.shared_secret)
@@ -274,13 +729,35 @@ impl ABFTConsensus {
             return Err("Currently in view change".to_string());
         }
 
-        self.sequence += 1;
+        let sequence = self.sequence + 1;
         let block_hash = block.calculate_hash();
 
-        let message = self.create_message(ConsensusMessageType::PrePrepare, block_hash.clone());
+        // A later view may only re-propose the block already proposed here
+        if let Some(previous) = self.find_signed_vote(&ConsensusMessageType::PrePrepare, sequence) {
+            if previous.block_hash != block_hash {
+                return Err(format!(
+                    "Equivocation refused: already proposed {} at sequence {}",
+                    previous.block_hash, sequence
+                ));
+            }
+        }
+
+        let message = self.create_message(ConsensusMessageType::PrePrepare, sequence, block_hash);
 
+        // WAL first: proposal, lock and sequence must survive a crash
+        if let Some(wal) = &self.wal {
+            wal.record_vote(&message)?;
+            wal.record_locked_block(Some(&block))?;
+            wal.record_view(self.view, sequence)?;
+        }
+
+        self.sequence = sequence;
         self.pre_prepare_messages
             .insert(self.sequence, message.clone());
+        self.signed_votes
+            .entry(sequence)
+            .or_default()
+            .push(message.clone());
 
         // Lock the block
         self.locked_block = Some(block);
@@ -290,6 +767,79 @@ impl ABFTConsensus {
         Ok(message)
     }
 
+    /// PRE-PREPARE (replica side): accept the leader's proposal and lock on it.
+    ///
+    /// The caller validates the block contents; this checks authentication,
+    /// view, leader identity and that `block` matches the signed hash.
+    /// Returns an error on a second, different proposal for the same
+    /// view/sequence (leader equivocation).
+    pub fn accept_pre_prepare(
+        &mut self,
+        msg: ConsensusMessage,
+        block: Block,
+    ) -> Result<(), String> {
+        if msg.msg_type != ConsensusMessageType::PrePrepare {
+            return Err(format!("Expected PrePrepare, got {:?}", msg.msg_type));
+        }
+        if !self.verify_message(&msg) {
+            return Err("Invalid message authentication".to_string());
+        }
+        if msg.view != self.view {
+            return Err(format!(
+                "Message from wrong view: {} vs {}",
+                msg.view, self.view
+            ));
+        }
+        let leader = self.get_leader(msg.view);
+        if msg.sender != leader {
+            return Err(format!(
+                "PrePrepare from {} but leader of view {} is {}",
+                msg.sender, msg.view, leader
+            ));
+        }
+        if msg.sequence <= self.finalized_sequence {
+            return Err(format!(
+                "Sequence {} already finalized (finalized up to {})",
+                msg.sequence, self.finalized_sequence
+            ));
+        }
+        if block.calculate_hash() != msg.block_hash {
+            return Err("PrePrepare hash does not match proposed block".to_string());
+        }
+        if let Some(existing) = self.pre_prepare_messages.get(&msg.sequence) {
+            if existing.view == msg.view && existing.block_hash != msg.block_hash {
+                return Err(format!(
+                    "Conflicting PrePrepare from {} at view {} sequence {}",
+                    msg.sender, msg.view, msg.sequence
+                ));
+            }
+        }
+        // Never lock a block we could not vote for
+        if let Some(previous) = self.find_signed_vote(&ConsensusMessageType::Prepare, msg.sequence)
+        {
+            if previous.block_hash != msg.block_hash {
+                return Err(format!(
+                    "Equivocation refused: already signed Prepare for {} at sequence {}",
+                    previous.block_hash, msg.sequence
+                ));
+            }
+        }
+
+        let sequence = self.sequence.max(msg.sequence);
+        if let Some(wal) = &self.wal {
+            wal.record_locked_block(Some(&block))?;
+            wal.record_view(self.view, sequence)?;
+        }
+
+        self.sequence = sequence;
+        self.pre_prepare_messages.insert(msg.sequence, msg);
+        self.locked_block = Some(block);
+        self.locked_view = self.view;
+        self.state = ValidatorState::Locked;
+
+        Ok(())
+    }
+
     /// PREPARE phase: Validators accept block and vote
     pub fn prepare(&mut self, msg: ConsensusMessage) -> Result<(), String> {
         // Verify message authentication with shared secret
@@ -325,6 +875,15 @@ impl ABFTConsensus {
         }
     }
 
+    /// Check if 2f+1 prepare votes agree on `block_hash` (votes for other
+    /// blocks at the same sequence do not count toward its quorum)
+    pub fn can_commit_block(&self, sequence: u64, block_hash: &str) -> bool {
+        self.prepare_votes.get(&sequence).is_some_and(|votes| {
+            votes.iter().filter(|v| v.block_hash == block_hash).count()
+                >= self.get_quorum_threshold()
+        })
+    }
+
     /// COMMIT phase: After 2f+1 prepares, commit block
     pub fn commit(&mut self, msg: ConsensusMessage) -> Result<bool, String> {
         // Verify message authentication with shared secret
@@ -342,9 +901,16 @@ impl ABFTConsensus {
             votes.push(msg);
         }
 
-        // Check if we reached consensus (2f+1 commits)
+        // Check if we reached consensus (2f+1 commits).
+        // Only commits for the locked block count — an equivocating leader
+        // must not be able to finalize our block with votes for another one.
+        let locked_hash = self.locked_block.as_ref().map(|b| b.calculate_hash());
         if let Some(commit_votes) = self.commit_votes.get(&sequence) {
-            if commit_votes.len() >= self.get_quorum_threshold() {
+            let matching = commit_votes
+                .iter()
+                .filter(|v| locked_hash.as_deref().is_none_or(|h| v.block_hash == h))
+                .count();
+            if matching >= self.get_quorum_threshold() {
                 return self.finalize_block(sequence);
             }
         }
@@ -371,6 +937,14 @@ impl ABFTConsensus {
             self.prepare_votes.remove(&sequence);
             self.commit_votes.remove(&sequence);
 
+            // Own votes at or below a finalized sequence can never be needed again
+            self.finalized_sequence = self.finalized_sequence.max(sequence);
+            self.signed_votes = self.signed_votes.split_off(&(self.finalized_sequence + 1));
+            if let Some(wal) = &self.wal {
+                wal.record_finalized(self.finalized_sequence)?;
+                wal.record_locked_block(None)?;
+            }
+
             self.state = ValidatorState::Normal;
             self.locked_block = None;
 
@@ -380,8 +954,26 @@ impl ABFTConsensus {
         }
     }
 
+    /// Adopt a block the network finalized while this node was behind.
+    /// The caller must have verified a 2f+1 commit certificate for it.
+    pub fn sync_finalized(&mut self, block: Block, sequence: u64) -> Result<(), String> {
+        if sequence <= self.finalized_sequence {
+            return Err(format!(
+                "Sequence {} already finalized (finalized up to {})",
+                sequence, self.finalized_sequence
+            ));
+        }
+        self.locked_block = Some(block);
+        self.finalize_block(sequence)?;
+        self.sequence = self.sequence.max(sequence);
+        Ok(())
+    }
+
     /// VIEW CHANGE protocol: Change leader if current one fails
     pub fn initiate_view_change(&mut self) -> Result<ConsensusMessage, String> {
+        if let Some(wal) = &self.wal {
+            wal.record_view(self.view + 1, self.sequence)?;
+        }
         self.state = ValidatorState::ViewChanging;
         self.view += 1;
         self.view_changes += 1;
@@ -404,6 +996,9 @@ impl ABFTConsensus {
             return Err(format!("Invalid new view: {} < {}", new_view, self.view));
         }
 
+        if let Some(wal) = &self.wal {
+            wal.record_view(new_view, self.sequence)?;
+        }
         self.view = new_view;
         self.state = ValidatorState::Normal;
         self.prepare_votes.clear();
@@ -412,17 +1007,33 @@ impl ABFTConsensus {
         Ok(())
     }
 
+    /// Drop an uncommitted proposal after a view change.
+    /// Clears the lock but keeps the sequence: the new view's leader proposes
+    /// at a fresh sequence. Own votes stay in memory and in the WAL, so this
+    /// validator still refuses to vote for a different block at a sequence it
+    /// already voted on.
+    pub fn abandon_proposal(&mut self) -> Result<(), String> {
+        if let Some(wal) = &self.wal {
+            wal.record_locked_block(None)?;
+        }
+        self.locked_block = None;
+        if self.state == ValidatorState::Locked {
+            self.state = ValidatorState::Normal;
+        }
+        Ok(())
+    }
+
     /// Get current leader address for view.
-    /// Uses real validator addresses from `validator_set` when available,
-    /// falls back to synthetic names for backward compatibility in tests.
+    /// Stake-weighted draw from the current epoch (see `ValidatorSetEpoch::select_leader`),
+    /// falls back to synthetic round-robin names for backward compatibility in tests.
     pub fn get_leader(&self, view: u64) -> String {
-        if self.validator_set.is_empty() {
-            // Fallback for tests that don't populate validator_set
-            let leader_index = (view as usize) % self.total_validators;
-            format!("validator-{}", leader_index)
-        } else {
-            let leader_index = (view as usize) % self.validator_set.len();
-            self.validator_set[leader_index].clone()
+        match self.current_epoch.select_leader(view) {
+            Some(leader) => leader.to_string(),
+            None => {
+                // Fallback for tests that don't populate the validator set
+                let leader_index = (view as usize) % self.total_validators;
+                format!("validator-{}", leader_index)
+            }
         }
     }
 
@@ -835,4 +1446,456 @@ mod tests {
             "5 unique votes should reach quorum"
         );
     }
+    fn sig(signer: &str) -> EpochSignature {
+        EpochSignature {
+            signer: signer.to_string(),
+            signature: "00".to_string(),
+            public_key: "00".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_validator_changes_wait_for_epoch_boundary() {
+        const LOS: u128 = 100_000_000_000;
+        let mut consensus = ABFTConsensus::new("LOS_A".to_string(), 4);
+        consensus.update_validator_set_with_stake(vec![
+            ("LOS_A".to_string(), 1_000 * LOS),
+            ("LOS_B".to_string(), 1_000 * LOS),
+        ]);
+        assert_eq!(consensus.current_epoch_set().epoch, 0);
+
+        // Registration mid-epoch is staged, not applied
+        let next = vec![
+            ("LOS_A".to_string(), 1_000 * LOS),
+            ("LOS_B".to_string(), 1_000 * LOS),
+            ("LOS_C".to_string(), 1_000 * LOS),
+        ];
+        consensus.schedule_validator_set(next.clone());
+        assert_eq!(consensus.validator_set, vec!["LOS_A", "LOS_B"]);
+        assert_eq!(consensus.total_validators, 2);
+        assert_eq!(consensus.next_epoch_set().len(), 3);
+
+        // Both validators of the running epoch must sign (quorum of 2 is 2);
+        // the new validator's signature does not count yet
+        let transition = EpochTransition::new(1, next);
+        assert!(consensus
+            .add_epoch_signature(&transition, sig("LOS_C"))
+            .unwrap()
+            .is_none());
+        assert!(consensus
+            .add_epoch_signature(&transition, sig("LOS_A"))
+            .unwrap()
+            .is_none());
+        assert!(consensus
+            .add_epoch_signature(&transition, sig("LOS_A"))
+            .unwrap()
+            .is_none());
+        let commit = consensus
+            .add_epoch_signature(&transition, sig("LOS_B"))
+            .unwrap()
+            .expect("quorum reached");
+        assert_eq!(commit.signatures.len(), 2);
+        assert_eq!(consensus.current_epoch_set().epoch, 1);
+        assert_eq!(consensus.current_epoch_set().start_height, 1000);
+        assert_eq!(consensus.validator_set, vec!["LOS_A", "LOS_B", "LOS_C"]);
+        assert_eq!(consensus.total_validators, 3);
+        assert!(consensus.pending_validator_set.is_none());
+
+        // Replaying the same commit is a no-op
+        assert!(!consensus.apply_epoch_commit(&commit).unwrap());
+    }
+
+    #[test]
+    fn test_leader_agreement_across_nodes() {
+        const LOS: u128 = 100_000_000_000;
+        let genesis = vec![
+            ("LOS_A".to_string(), 1_000 * LOS),
+            ("LOS_B".to_string(), 1_000 * LOS),
+            ("LOS_C".to_string(), 1_000 * LOS),
+        ];
+        let stakes = vec![
+            ("LOS_A".to_string(), 1_000 * LOS),
+            ("LOS_B".to_string(), 3_000 * LOS),
+            ("LOS_C".to_string(), 9_000 * LOS),
+        ];
+        let transition = EpochTransition::new(1, stakes.clone());
+
+        // Node A collects the signatures itself
+        let mut node_a = ABFTConsensus::new("LOS_A".to_string(), 3);
+        node_a.bootstrap_epoch(genesis.clone()).unwrap();
+        let mut commit = None;
+        for signer in ["LOS_A", "LOS_B", "LOS_C"] {
+            commit = node_a
+                .add_epoch_signature(&transition, sig(signer))
+                .unwrap();
+        }
+        let commit = commit.expect("quorum reached");
+
+        // Node B staged a different set locally but installs the agreed commit
+        let mut node_b = ABFTConsensus::new("LOS_B".to_string(), 3);
+        node_b
+            .bootstrap_epoch(genesis.into_iter().rev().collect())
+            .unwrap();
+        node_b.schedule_validator_set(stakes[..2].to_vec());
+        assert!(node_b.apply_epoch_commit(&commit).unwrap());
+
+        assert_eq!(node_a.current_epoch_set(), node_b.current_epoch_set());
+        for view in 0..50 {
+            assert_eq!(node_a.get_leader(view), node_b.get_leader(view));
+        }
+    }
+
+    #[test]
+    fn test_epoch_commit_needs_quorum_of_running_epoch() {
+        let mut consensus = ABFTConsensus::new("LOS_A".to_string(), 4);
+        consensus
+            .bootstrap_epoch(
+                ["LOS_A", "LOS_B", "LOS_C", "LOS_D"]
+                    .iter()
+                    .map(|v| (v.to_string(), 0))
+                    .collect(),
+            )
+            .unwrap();
+        let transition = EpochTransition::new(1, vec![("LOS_X".to_string(), 0)]);
+
+        // 2 of 4 (and an outsider) is below the quorum of 3
+        let commit = EpochCommit {
+            transition: transition.clone(),
+            signatures: vec![sig("LOS_A"), sig("LOS_B"), sig("LOS_B"), sig("LOS_X")],
+        };
+        assert!(!consensus.apply_epoch_commit(&commit).unwrap());
+
+        // A commit that skips an epoch is ignored
+        let skipping = EpochCommit {
+            transition: EpochTransition::new(2, vec![("LOS_X".to_string(), 0)]),
+            signatures: vec![sig("LOS_A"), sig("LOS_B"), sig("LOS_C")],
+        };
+        assert!(!consensus.apply_epoch_commit(&skipping).unwrap());
+        assert_eq!(consensus.current_epoch_set().epoch, 0);
+
+        let agreed = EpochCommit {
+            transition,
+            signatures: vec![sig("LOS_A"), sig("LOS_B"), sig("LOS_D")],
+        };
+        assert!(consensus.apply_epoch_commit(&agreed).unwrap());
+        assert_eq!(consensus.validator_set, vec!["LOS_X"]);
+    }
+
+    /// A restart throws away the engine and replays a fresh handle on the same
+    /// tree. The Db stays open for the whole test: reopening the directory would
+    /// race sled's background threads for the file lock.
+    fn open_wal(db: &sled::Db) -> ConsensusWal {
+        ConsensusWal::new(db.open_tree("consensus_wal").unwrap())
+    }
+
+    fn temp_db() -> sled::Db {
+        sled::Config::new().temporary(true).open().unwrap()
+    }
+
+    fn test_block(data: &[u8]) -> Block {
+        Block {
+            height: 1,
+            timestamp: 1000,
+            data: data.to_vec(),
+            proposer: "validator-0".to_string(),
+            parent_hash: "0".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_equivocation_refused_in_memory() {
+        let mut consensus = ABFTConsensus::new("validator-1".to_string(), 4);
+        let hash_a = test_block(b"a").calculate_hash();
+        let hash_b = test_block(b"b").calculate_hash();
+
+        let first = consensus.vote_prepare(1, hash_a.clone()).unwrap();
+        // Same vote again is idempotent (safe re-broadcast)
+        let again = consensus.vote_prepare(1, hash_a).unwrap();
+        assert_eq!(first.mac, again.mac);
+        // Conflicting block at the same sequence is refused
+        assert!(consensus.vote_prepare(1, hash_b.clone()).is_err());
+        // Different phase / sequence are independent
+        assert!(consensus.vote_commit(1, hash_b.clone()).is_ok());
+        assert!(consensus.vote_prepare(2, hash_b).is_ok());
+    }
+
+    #[test]
+    fn test_wal_crash_restart_mid_round() {
+        let db = temp_db();
+        let secret = b"wal-test-secret".to_vec();
+        let hash_a = test_block(b"a").calculate_hash();
+        let hash_b = test_block(b"b").calculate_hash();
+
+        // Round in progress: leader proposes, validator-1 prepares and commits A
+        let mut leader = ABFTConsensus::new("validator-0".to_string(), 4);
+        leader.set_shared_secret(secret.clone());
+        let proposal = leader.pre_prepare(test_block(b"a")).unwrap();
+
+        let (prepare_mac, view_before) = {
+            let wal = open_wal(&db);
+            let mut node = ABFTConsensus::with_wal("validator-1".to_string(), 4, wal).unwrap();
+            node.set_shared_secret(secret.clone());
+            node.accept_pre_prepare(proposal.clone(), test_block(b"a"))
+                .unwrap();
+            let prepare = node
+                .vote_prepare(proposal.sequence, hash_a.clone())
+                .unwrap();
+            node.vote_commit(proposal.sequence, hash_a.clone()).unwrap();
+            let view = node.view;
+            drop(node); // kill: nothing but the WAL survives
+            (prepare.mac, view)
+        };
+
+        // Restart on the same database
+        {
+            let wal = open_wal(&db);
+            let mut node = ABFTConsensus::with_wal("validator-1".to_string(), 4, wal).unwrap();
+            node.set_shared_secret(secret.clone());
+
+            assert_eq!(node.view, view_before);
+            assert_eq!(node.sequence, proposal.sequence);
+            assert_eq!(node.locked_block, Some(test_block(b"a")));
+            // A conflicting vote after restart must be refused in both phases
+            assert!(node
+                .vote_prepare(proposal.sequence, hash_b.clone())
+                .is_err());
+            assert!(node.vote_commit(proposal.sequence, hash_b.clone()).is_err());
+            // The original vote is re-issued unchanged
+            let replayed = node.vote_prepare(proposal.sequence, hash_a).unwrap();
+            assert_eq!(replayed.mac, prepare_mac);
+
+            // Crash again in the middle of a view change
+            node.initiate_view_change().unwrap();
+        }
+
+        let wal = open_wal(&db);
+        let mut node = ABFTConsensus::with_wal("validator-1".to_string(), 4, wal).unwrap();
+        node.set_shared_secret(secret);
+        assert_eq!(node.view, view_before + 1);
+        // The new view still may not vote for a different block at this sequence
+        assert!(node
+            .vote_prepare(proposal.sequence, hash_b.clone())
+            .is_err());
+        node.abandon_proposal().unwrap();
+        assert!(node.vote_prepare(proposal.sequence, hash_b).is_err());
+    }
+
+    #[test]
+    fn test_confirm_votes_survive_restart() {
+        let db = temp_db();
+        {
+            let mut node =
+                ABFTConsensus::with_wal("validator-1".to_string(), 4, open_wal(&db)).unwrap();
+            node.vote_confirm("LOSalice", "head-1", "send-a").unwrap();
+            node.vote_confirm("LOSbob", "head-2", "send-b").unwrap();
+        }
+
+        let mut node =
+            ABFTConsensus::with_wal("validator-1".to_string(), 4, open_wal(&db)).unwrap();
+        assert!(node.vote_confirm("LOSalice", "head-1", "send-a").is_ok());
+        assert!(node.vote_confirm("LOSalice", "head-1", "send-x").is_err());
+
+        // Once Alice's slot is settled the record goes, from the log too
+        assert_eq!(
+            node.prune_confirm_votes(0, |account, _| account == "LOSalice"),
+            Ok(1)
+        );
+        drop(node);
+        let mut node =
+            ABFTConsensus::with_wal("validator-1".to_string(), 4, open_wal(&db)).unwrap();
+        assert_eq!(node.confirm_votes.len(), 1);
+        assert!(node.confirm_votes.contains_key("LOSbob:head-2"));
+
+        // Expired confirmations go too, settled or not
+        let confirmed_at = node.confirm_votes["LOSbob:head-2"].confirmed_at;
+        assert_eq!(node.prune_confirm_votes(confirmed_at, |_, _| false), Ok(0));
+        assert_eq!(
+            node.prune_confirm_votes(confirmed_at + 1, |_, _| false),
+            Ok(1)
+        );
+        assert!(node.vote_confirm("LOSbob", "head-2", "send-y").is_ok());
+    }
+
+    #[test]
+    fn test_epoch_and_transition_lock_survive_restart() {
+        let db = temp_db();
+        let genesis: Vec<(String, u128)> = ["LOS_A", "LOS_B"]
+            .iter()
+            .map(|v| (v.to_string(), 0))
+            .collect();
+        let next = EpochTransition::new(1, vec![("LOS_A".to_string(), 0)]);
+        let rival = EpochTransition::new(1, vec![("LOS_B".to_string(), 0)]);
+        {
+            let mut node = ABFTConsensus::with_wal("LOS_A".to_string(), 2, open_wal(&db)).unwrap();
+            assert!(node.bootstrap_epoch(genesis.clone()).unwrap());
+            node.sign_epoch_transition(&next).unwrap();
+            node.wal().unwrap().flush().unwrap();
+        }
+
+        // The ledger's current set does not replace the restored epoch,
+        // and the signed transition still binds this validator
+        let mut node = ABFTConsensus::with_wal("LOS_A".to_string(), 2, open_wal(&db)).unwrap();
+        assert!(!node
+            .bootstrap_epoch(vec![("LOS_C".to_string(), 0)])
+            .unwrap());
+        assert_eq!(node.validator_set, vec!["LOS_A", "LOS_B"]);
+        assert_eq!(node.epoch_candidate(), Some(next.clone()));
+        assert!(node.sign_epoch_transition(&rival).is_err());
+
+        node.add_epoch_signature(&next, sig("LOS_A")).unwrap();
+        let commit = node
+            .add_epoch_signature(&next, sig("LOS_B"))
+            .unwrap()
+            .expect("quorum reached");
+        let opened = node.current_epoch_set().clone();
+        node.wal().unwrap().flush().unwrap();
+        drop(node);
+
+        let node = ABFTConsensus::with_wal("LOS_A".to_string(), 2, open_wal(&db)).unwrap();
+        assert_eq!(node.current_epoch_set(), &opened);
+        assert!(node.epoch_lock.is_none());
+        assert_eq!(node.epoch_commits_after(0, 10), vec![commit]);
+        assert!(node.epoch_commits_after(1, 10).is_empty());
+    }
+
+    #[test]
+    fn test_wal_restores_locked_block_and_prunes_on_finality() {
+        let db = temp_db();
+        let block = test_block(b"leader-proposal");
+
+        let seq = {
+            let wal = open_wal(&db);
+            let mut leader = ABFTConsensus::with_wal("validator-0".to_string(), 4, wal).unwrap();
+            leader.pre_prepare(block.clone()).unwrap();
+            leader.sequence
+        };
+
+        let wal = open_wal(&db);
+        let mut leader = ABFTConsensus::with_wal("validator-0".to_string(), 4, wal).unwrap();
+        assert_eq!(leader.sequence, seq);
+        assert_eq!(leader.state, ValidatorState::Locked);
+        assert_eq!(leader.locked_block, Some(block.clone()));
+        assert!(leader.pre_prepare_messages.contains_key(&seq));
+
+        // Reach commit quorum (4 validators → quorum 3) and finalize
+        for i in 0..3 {
+            let msg = ConsensusMessage::new(
+                ConsensusMessageType::Commit,
+                0,
+                seq,
+                block.calculate_hash(),
+                format!("validator-{}", i),
+            );
+            leader.commit(msg).unwrap();
+        }
+        assert_eq!(leader.finalized_sequence, seq);
+        assert!(leader.signed_votes.is_empty());
+        drop(leader);
+
+        let wal = open_wal(&db);
+        let mut leader = ABFTConsensus::with_wal("validator-0".to_string(), 4, wal).unwrap();
+        assert!(leader.locked_block.is_none());
+        assert_eq!(leader.state, ValidatorState::Normal);
+        // Finalized sequences can never be voted on again
+        assert!(leader.vote_prepare(seq, "other".to_string()).is_err());
+    }
+    #[test]
+    fn test_replica_accepts_leader_proposal() {
+        let ids: Vec<String> = (0..4).map(|i| format!("LOS_V{}", i)).collect();
+        let mut probe = ABFTConsensus::new(ids[0].clone(), 4);
+        probe.update_validator_set(ids.clone());
+        let leader_id = probe.get_leader(0);
+        let replica_id = ids.iter().find(|id| **id != leader_id).unwrap().clone();
+
+        let mut leader = ABFTConsensus::new(leader_id.clone(), 4);
+        leader.update_validator_set(ids.clone());
+        let mut replica = ABFTConsensus::new(replica_id.clone(), 4);
+        replica.update_validator_set(ids.clone());
+
+        let block = test_block(b"proposal");
+        let pp = leader.pre_prepare(block.clone()).unwrap();
+
+        // Tampered body is rejected
+        assert!(replica
+            .accept_pre_prepare(pp.clone(), test_block(b"other"))
+            .is_err());
+        replica.accept_pre_prepare(pp.clone(), block).unwrap();
+        assert_eq!(replica.state, ValidatorState::Locked);
+        assert_eq!(replica.sequence, pp.sequence);
+
+        // A second, different proposal in the same view is equivocation
+        let forged = ConsensusMessage::new(
+            ConsensusMessageType::PrePrepare,
+            0,
+            pp.sequence,
+            test_block(b"forged").calculate_hash(),
+            leader_id,
+        );
+        assert!(replica
+            .accept_pre_prepare(forged, test_block(b"forged"))
+            .is_err());
+
+        // Proposals from non-leaders are rejected
+        let mut other = ABFTConsensus::new(replica_id, 4);
+        other.update_validator_set(ids);
+        let not_leader = other.pre_prepare(test_block(b"x")).unwrap();
+        assert!(replica
+            .accept_pre_prepare(not_leader, test_block(b"x"))
+            .is_err());
+    }
+
+    #[test]
+    fn test_commit_quorum_counts_only_locked_block() {
+        let mut consensus = ABFTConsensus::new("validator-0".to_string(), 4);
+        let block = test_block(b"locked");
+        let seq = consensus.pre_prepare(block.clone()).unwrap().sequence;
+
+        // Three commits for a different block must not finalize ours
+        for i in 1..=3 {
+            let msg = ConsensusMessage::new(
+                ConsensusMessageType::Commit,
+                0,
+                seq,
+                "other".to_string(),
+                format!("validator-{}", i),
+            );
+            assert!(!consensus.commit(msg).unwrap());
+        }
+        assert!(!consensus.can_commit_block(seq, &block.calculate_hash()));
+        assert_eq!(consensus.blocks_finalized, 0);
+    }
+
+    #[test]
+    fn test_abandon_proposal_keeps_sequence() {
+        let mut consensus = ABFTConsensus::new("validator-0".to_string(), 4);
+        consensus.pre_prepare(test_block(b"a")).unwrap();
+        assert_eq!(consensus.sequence, 1);
+
+        consensus.initiate_view_change().unwrap();
+        consensus.complete_view_change(1).unwrap();
+        consensus.abandon_proposal().unwrap();
+        assert!(consensus.locked_block.is_none());
+        assert_eq!(consensus.sequence, 1);
+
+        // The new view proposes at a fresh sequence
+        let pp = consensus.pre_prepare(test_block(b"b")).unwrap();
+        assert_eq!(pp.sequence, 2);
+        assert_eq!(pp.view, 1);
+    }
+
+    #[test]
+    fn test_votes_bind_sequence_across_views() {
+        let mut consensus = ABFTConsensus::new("validator-1".to_string(), 4);
+        let hash_a = test_block(b"a").calculate_hash();
+        let hash_b = test_block(b"b").calculate_hash();
+        consensus.vote_prepare(1, hash_a.clone()).unwrap();
+
+        consensus.initiate_view_change().unwrap();
+        consensus.complete_view_change(1).unwrap();
+        consensus.abandon_proposal().unwrap();
+
+        assert!(consensus.vote_prepare(1, hash_b).is_err());
+        let again = consensus.vote_prepare(1, hash_a).unwrap();
+        assert_eq!(again.view, 1);
+    }
 }
@@ -14,6 +14,7 @@
 use serde::{Deserialize, Serialize};
 use sha3::{Digest, Keccak256};
 use std::path::Path;
+use tracing::{info, warn};
 
 /// Checkpoint interval (every 1,000 blocks)
 pub const CHECKPOINT_INTERVAL: u64 = 1000;
@@ -111,8 +112,8 @@ impl CheckpointManager {
         match Self::try_open(path_ref) {
             Ok(mgr) => return Ok(mgr),
             Err(e) if Self::is_lock_error(&*e) => {
-                eprintln!(
-                    "⚠️  Checkpoint DB lock held at {} — retrying ({} attempts remain)",
+                warn!(
+                    "Checkpoint DB lock held at {} — retrying ({} attempts remain)",
                     path_ref.display(),
                     retry_delays_ms.len()
                 );
@@ -125,7 +126,7 @@ impl CheckpointManager {
             std::thread::sleep(std::time::Duration::from_millis(*delay_ms));
             match Self::try_open(path_ref) {
                 Ok(mgr) => {
-                    eprintln!("✅ Checkpoint DB lock acquired on retry {}", i + 1);
+                    info!("Checkpoint DB lock acquired on retry {}", i + 1);
                     return Ok(mgr);
                 }
                 Err(e) if Self::is_lock_error(&*e) && i + 1 < retry_delays_ms.len() => {
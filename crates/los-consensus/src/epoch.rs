@@ -0,0 +1,344 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - VALIDATOR SET EPOCHS
+//
+// Validator set changes are staged and only take effect at epoch boundaries.
+// - One epoch per CHECKPOINT_INTERVAL blocks
+// - Register/unregister during an epoch → queued for the next epoch
+// - The next set is proposed by a leader of the running epoch and co-signed
+//   by its validators; only a quorum-signed transition is installed, so every
+//   node switches to the same set whatever its own ledger holds
+// - Leader selection is stake-weighted (quadratic voting power) and
+//   deterministic from a seed chained through the agreed transitions
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use crate::checkpoint::CHECKPOINT_INTERVAL;
+use crate::voting::calculate_voting_power;
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Keccak256};
+
+/// Domain separator for epoch seeds (prevents cross-protocol hash reuse)
+const EPOCH_SEED_DOMAIN: &[u8] = b"LOS_EPOCH_SEED_V1";
+
+/// Domain separator for epoch transition digests
+const EPOCH_TRANSITION_DOMAIN: &[u8] = b"LOS_EPOCH_TRANSITION_V1";
+
+/// Transition proposers are drawn from views far above any aBFT view
+const PROPOSER_VIEW_BASE: u64 = 1 << 63;
+
+/// A validator as seen by a single epoch
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct EpochValidator {
+    pub address: String,
+    pub stake_cil: u128,
+    /// √stake via `voting::calculate_voting_power` (0 if below minimum stake)
+    pub voting_power: u128,
+}
+
+/// Frozen validator set for one epoch
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct ValidatorSetEpoch {
+    /// Epoch number (0 = genesis, then start_height / CHECKPOINT_INTERVAL)
+    pub epoch: u64,
+    /// Block height at which this epoch started (0 for genesis)
+    pub start_height: u64,
+    /// Hex-encoded leader selection seed.
+    /// Empty for a projected next epoch whose transition is not agreed yet.
+    pub seed: String,
+    /// MAINNET: sorted by address for deterministic ordering across nodes
+    pub validators: Vec<EpochValidator>,
+    pub total_voting_power: u128,
+}
+
+impl ValidatorSetEpoch {
+    /// Build an epoch from (address, stake) pairs.
+    /// Duplicates are collapsed (last stake wins) and the set is sorted by address.
+    pub fn new(epoch: u64, start_height: u64, seed: String, stakes: Vec<(String, u128)>) -> Self {
+        let mut sorted = stakes;
+        sorted.sort_by(|(a, _), (b, _)| a.cmp(b));
+        let mut validators: Vec<EpochValidator> = Vec::with_capacity(sorted.len());
+        for (address, stake_cil) in sorted {
+            let entry = EpochValidator {
+                voting_power: calculate_voting_power(stake_cil),
+                address,
+                stake_cil,
+            };
+            match validators.last_mut() {
+                Some(last) if last.address == entry.address => *last = entry,
+                _ => validators.push(entry),
+            }
+        }
+        let total_voting_power = validators.iter().map(|v| v.voting_power).sum();
+
+        Self {
+            epoch,
+            start_height,
+            seed,
+            validators,
+            total_voting_power,
+        }
+    }
+
+    /// Genesis epoch: seed is derived from the domain separator alone
+    pub fn genesis(stakes: Vec<(String, u128)>) -> Self {
+        let mut hasher = Keccak256::new();
+        hasher.update(EPOCH_SEED_DOMAIN);
+        hasher.update(b"genesis");
+        Self::new(0, 0, hex_encode(&hasher.finalize()), stakes)
+    }
+
+    /// Ordered validator addresses
+    pub fn addresses(&self) -> Vec<String> {
+        self.validators.iter().map(|v| v.address.clone()).collect()
+    }
+
+    /// (address, stake) pairs, e.g. to carry the set into the next epoch
+    pub fn stakes(&self) -> Vec<(String, u128)> {
+        self.validators
+            .iter()
+            .map(|v| (v.address.clone(), v.stake_cil))
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.validators.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.validators.is_empty()
+    }
+
+    /// Stake-weighted leader for a view.
+    ///
+    /// Draws r = Keccak256(seed || view) mod total_weight and walks the
+    /// cumulative voting power of the address-sorted set. Pure integer math,
+    /// so every node with the same epoch picks the same leader.
+    /// Falls back to equal weights when no validator meets the minimum stake.
+    pub fn select_leader(&self, view: u64) -> Option<&str> {
+        if self.validators.is_empty() {
+            return None;
+        }
+
+        let uniform = self.total_voting_power == 0;
+        let total_weight = if uniform {
+            self.validators.len() as u128
+        } else {
+            self.total_voting_power
+        };
+
+        let mut hasher = Keccak256::new();
+        hasher.update(self.seed.as_bytes());
+        hasher.update(view.to_le_bytes());
+        let digest = hasher.finalize();
+        let mut draw_bytes = [0u8; 16];
+        draw_bytes.copy_from_slice(&digest[..16]);
+        let mut draw = u128::from_le_bytes(draw_bytes) % total_weight;
+
+        for v in &self.validators {
+            let weight = if uniform { 1 } else { v.voting_power };
+            if draw < weight {
+                return Some(&v.address);
+            }
+            draw -= weight;
+        }
+
+        // Unreachable: draw < total_weight = Σ weights
+        self.validators.last().map(|v| v.address.as_str())
+    }
+
+    /// Validator that proposes the next epoch's set in `round`.
+    /// A new round starts when the previous proposer failed to gather a quorum.
+    pub fn transition_proposer(&self, round: u64) -> Option<&str> {
+        self.select_leader(PROPOSER_VIEW_BASE.saturating_add(round))
+    }
+}
+
+/// Validator set proposed for the next epoch.
+///
+/// Signed as a digest by the validators of the running epoch; the proposer
+/// and round are not part of it, so a later round can re-propose a set that
+/// already collected signatures.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct EpochTransition {
+    pub epoch: u64,
+    pub start_height: u64,
+    /// (address, stake), sorted by address
+    pub stakes: Vec<(String, u128)>,
+}
+
+impl EpochTransition {
+    /// Transition into `epoch`. Stakes are normalized like `ValidatorSetEpoch::new`.
+    pub fn new(epoch: u64, stakes: Vec<(String, u128)>) -> Self {
+        let stakes = ValidatorSetEpoch::new(epoch, 0, String::new(), stakes).stakes();
+        Self {
+            epoch,
+            start_height: epoch.saturating_mul(CHECKPOINT_INTERVAL),
+            stakes,
+        }
+    }
+
+    /// Hex digest the validators sign
+    pub fn digest(&self) -> String {
+        let mut hasher = Keccak256::new();
+        hasher.update(EPOCH_TRANSITION_DOMAIN);
+        hasher.update(self.epoch.to_le_bytes());
+        hasher.update(self.start_height.to_le_bytes());
+        for (address, stake) in &self.stakes {
+            hasher.update((address.len() as u64).to_le_bytes());
+            hasher.update(address.as_bytes());
+            hasher.update(stake.to_le_bytes());
+        }
+        hex_encode(&hasher.finalize())
+    }
+
+    /// Message signed by each validator
+    pub fn signing_payload(&self) -> String {
+        format!("EPOCH:{}:{}", self.epoch, self.digest())
+    }
+
+    /// Ordered validator addresses
+    pub fn addresses(&self) -> Vec<&str> {
+        self.stakes.iter().map(|(a, _)| a.as_str()).collect()
+    }
+
+    /// The epoch this transition opens. The seed chains the previous epoch's
+    /// seed with this digest, so it is only known once the set is agreed.
+    pub fn open(&self, previous_seed: &str) -> ValidatorSetEpoch {
+        let mut hasher = Keccak256::new();
+        hasher.update(EPOCH_SEED_DOMAIN);
+        hasher.update(previous_seed.as_bytes());
+        hasher.update(self.digest().as_bytes());
+        ValidatorSetEpoch::new(
+            self.epoch,
+            self.start_height,
+            hex_encode(&hasher.finalize()),
+            self.stakes.clone(),
+        )
+    }
+
+    /// Signatures needed from a running epoch of `validators`
+    /// (same threshold as `FinalityCheckpoint::verify_quorum`)
+    pub fn quorum(validators: usize) -> usize {
+        (validators * 67).div_ceil(100).max(1)
+    }
+}
+
+/// A validator's signature over `EpochTransition::signing_payload` (hex-encoded)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct EpochSignature {
+    pub signer: String,
+    pub signature: String,
+    pub public_key: String,
+}
+
+/// An agreed transition: signed by a quorum of the epoch it replaces.
+/// Gossiped and kept in the WAL so lagging nodes can replay the epoch chain.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct EpochCommit {
+    pub transition: EpochTransition,
+    pub signatures: Vec<EpochSignature>,
+}
+
+fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOS: u128 = 100_000_000_000;
+
+    #[test]
+    fn test_epoch_sorted_and_deduped() {
+        let epoch = ValidatorSetEpoch::genesis(vec![
+            ("LOS_C".to_string(), 1_000 * LOS),
+            ("LOS_A".to_string(), 1_000 * LOS),
+            ("LOS_C".to_string(), 4_000 * LOS),
+        ]);
+
+        assert_eq!(epoch.addresses(), vec!["LOS_A", "LOS_C"]);
+        assert_eq!(epoch.validators[1].stake_cil, 4_000 * LOS);
+        assert_eq!(
+            epoch.total_voting_power,
+            calculate_voting_power(1_000 * LOS) + calculate_voting_power(4_000 * LOS)
+        );
+    }
+
+    #[test]
+    fn test_leader_selection_deterministic() {
+        let stakes = vec![
+            ("LOS_A".to_string(), 1_000 * LOS),
+            ("LOS_B".to_string(), 2_000 * LOS),
+            ("LOS_C".to_string(), 5_000 * LOS),
+        ];
+        let genesis = ValidatorSetEpoch::genesis(stakes.clone());
+        let a = EpochTransition::new(1, stakes.clone()).open(&genesis.seed);
+        let b = EpochTransition::new(1, stakes.into_iter().rev().collect()).open(&genesis.seed);
+        assert_eq!(a, b);
+
+        for view in 0..100 {
+            assert_eq!(a.select_leader(view), b.select_leader(view));
+        }
+    }
+
+    #[test]
+    fn test_seed_chains_previous_epoch_and_set() {
+        let one = vec![("LOS_A".to_string(), 1_000 * LOS)];
+        let two = vec![
+            ("LOS_A".to_string(), 1_000 * LOS),
+            ("LOS_B".to_string(), 1_000 * LOS),
+        ];
+        let t1 = EpochTransition::new(1, one);
+        let t2 = EpochTransition::new(1, two);
+        assert_eq!(t1.start_height, CHECKPOINT_INTERVAL);
+        assert_ne!(t1.digest(), t2.digest());
+        assert_ne!(t1.open("seed").seed, t2.open("seed").seed);
+        assert_ne!(t1.open("seed").seed, t1.open("other").seed);
+        assert_eq!(t1.open("seed"), t1.open("seed"));
+    }
+
+    #[test]
+    fn test_transition_quorum_matches_checkpoint_threshold() {
+        assert_eq!(EpochTransition::quorum(1), 1);
+        assert_eq!(EpochTransition::quorum(3), 3);
+        assert_eq!(EpochTransition::quorum(4), 3);
+        assert_eq!(EpochTransition::quorum(10), 7);
+    }
+
+    #[test]
+    fn test_leader_selection_stake_weighted() {
+        // Whale: √(100_000 LOS) = 10× the power of √(1_000 LOS)
+        let epoch = ValidatorSetEpoch::genesis(vec![
+            ("LOS_SMALL".to_string(), 1_000 * LOS),
+            ("LOS_WHALE".to_string(), 100_000 * LOS),
+        ]);
+
+        let whale_views = (0..2_000)
+            .filter(|v| epoch.select_leader(*v) == Some("LOS_WHALE"))
+            .count();
+        // Expected ≈ 10/11 of views (≈1818); allow generous sampling slack
+        assert!(
+            whale_views > 1_600,
+            "whale led {} of 2000 views",
+            whale_views
+        );
+        assert!(whale_views < 2_000, "small validator never led");
+    }
+
+    #[test]
+    fn test_uniform_fallback_below_min_stake() {
+        let epoch =
+            ValidatorSetEpoch::genesis(vec![("LOS_A".to_string(), 0), ("LOS_B".to_string(), 0)]);
+        assert_eq!(epoch.total_voting_power, 0);
+
+        let leaders: std::collections::HashSet<&str> =
+            (0..64).filter_map(|v| epoch.select_leader(v)).collect();
+        assert_eq!(leaders.len(), 2);
+    }
+
+    #[test]
+    fn test_empty_epoch_has_no_leader() {
+        let epoch = ValidatorSetEpoch::genesis(Vec::new());
+        assert!(epoch.select_leader(0).is_none());
+    }
+}
@@ -4,5 +4,9 @@
 
 pub mod abft;
 pub mod checkpoint;
+pub mod epoch;
 pub mod slashing;
-pub mod voting;
+pub mod wal;
+
+// Quadratic voting lives in los-core (governance tallies use it too)
+pub use los_core::voting;
@@ -0,0 +1,307 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - CONSENSUS WRITE-AHEAD LOG
+//
+// Crash recovery for aBFT: every vote this validator signs is persisted to
+// sled BEFORE it leaves the engine, together with the current view.
+// - Replayed by ABFTConsensus::with_wal() on startup
+// - A restarted validator refuses to sign a conflicting vote for a
+//   sequence it already voted on (prevents accidental equivocation)
+// - Entries at or below the finalized sequence are pruned
+// - Account-chain confirmations (CONFIRM_RES) are logged per (account,
+//   previous), so a restart cannot confirm a competing block either. They
+//   are written without an fsync: the node calls `flush` outside its engine
+//   lock before sending the vote.
+// - The running validator set epoch, the epoch transition this validator
+//   signed and every agreed transition are kept, so a restart resumes the
+//   exact epoch and lagging peers can replay the chain of transitions.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use crate::abft::{Block, ConsensusMessage, ConsensusMessageType};
+use crate::epoch::{EpochCommit, EpochTransition, ValidatorSetEpoch};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+const KEY_VIEW: &[u8] = b"view";
+const KEY_LOCKED_BLOCK: &[u8] = b"locked_block";
+const KEY_FINALIZED_SEQUENCE: &[u8] = b"finalized_sequence";
+const VOTE_PREFIX: &str = "vote_";
+const CONFIRM_PREFIX: &str = "confirm_";
+const KEY_EPOCH: &[u8] = b"epoch";
+const KEY_EPOCH_LOCK: &[u8] = b"epoch_lock";
+const EPOCH_COMMIT_PREFIX: &str = "epoch_commit_";
+
+/// Persisted view/sequence pointer
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct WalViewState {
+    pub view: u64,
+    pub sequence: u64,
+}
+
+/// Everything recovered from the log on startup
+#[derive(Debug, Clone, Default)]
+pub struct WalRecovery {
+    pub view_state: Option<WalViewState>,
+    pub locked_block: Option<Block>,
+    pub finalized_sequence: u64,
+    /// Own signed votes, in (sequence, phase) order
+    pub votes: Vec<ConsensusMessage>,
+    /// Own account-chain confirmations, keyed "account:previous"
+    pub confirm_votes: BTreeMap<String, ConfirmVote>,
+    /// Running validator set epoch
+    pub epoch: Option<ValidatorSetEpoch>,
+    /// Epoch transition this validator signed and is locked on
+    pub epoch_lock: Option<EpochTransition>,
+}
+
+/// A confirmation of one block for an account-chain slot
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConfirmVote {
+    pub block_hash: String,
+    /// Unix seconds; confirmations expire together with the mempool entry
+    pub confirmed_at: u64,
+}
+
+impl ConfirmVote {
+    /// Stored as "hash:unix_secs" (entries written before expiry carry only the hash)
+    fn encode(&self) -> String {
+        format!("{}:{}", self.block_hash, self.confirmed_at)
+    }
+
+    fn decode(value: &str) -> Self {
+        match value
+            .rsplit_once(':')
+            .and_then(|(hash, at)| Some((hash, at.parse().ok()?)))
+        {
+            Some((hash, confirmed_at)) => Self {
+                block_hash: hash.to_string(),
+                confirmed_at,
+            },
+            None => Self {
+                block_hash: value.to_string(),
+                confirmed_at: 0,
+            },
+        }
+    }
+}
+
+/// Write-ahead log of this validator's own consensus votes.
+///
+/// Backed by a dedicated sled tree (the node passes `consensus_wal` from its
+/// main database). Every write is flushed before returning so a vote is never
+/// broadcast without being durable first.
+#[derive(Debug, Clone)]
+pub struct ConsensusWal {
+    tree: sled::Tree,
+}
+
+impl ConsensusWal {
+    pub fn new(tree: sled::Tree) -> Self {
+        Self { tree }
+    }
+
+    /// Sortable key: zero-padded sequence so prefix scans come back in order
+    fn vote_key(msg_type: &ConsensusMessageType, sequence: u64) -> String {
+        format!("{}{:020}_{:?}", VOTE_PREFIX, sequence, msg_type)
+    }
+
+    /// Make every write so far durable
+    pub fn flush(&self) -> Result<(), String> {
+        self.tree
+            .flush()
+            .map(|_| ())
+            .map_err(|e| format!("WAL flush failed: {}", e))
+    }
+
+    /// Persist a signed vote (durable on return)
+    pub fn record_vote(&self, msg: &ConsensusMessage) -> Result<(), String> {
+        let bytes =
+            bincode::serialize(msg).map_err(|e| format!("WAL vote serialize failed: {}", e))?;
+        self.tree
+            .insert(
+                Self::vote_key(&msg.msg_type, msg.sequence).as_bytes(),
+                bytes,
+            )
+            .map_err(|e| format!("WAL vote write failed: {}", e))?;
+        self.flush()
+    }
+
+    /// Persist the current view and sequence
+    pub fn record_view(&self, view: u64, sequence: u64) -> Result<(), String> {
+        let bytes = bincode::serialize(&WalViewState { view, sequence })
+            .map_err(|e| format!("WAL view serialize failed: {}", e))?;
+        self.tree
+            .insert(KEY_VIEW, bytes)
+            .map_err(|e| format!("WAL view write failed: {}", e))?;
+        self.flush()
+    }
+
+    /// Persist (or clear) the block this validator is locked on
+    pub fn record_locked_block(&self, block: Option<&Block>) -> Result<(), String> {
+        match block {
+            Some(b) => {
+                let bytes = bincode::serialize(b)
+                    .map_err(|e| format!("WAL block serialize failed: {}", e))?;
+                self.tree
+                    .insert(KEY_LOCKED_BLOCK, bytes)
+                    .map_err(|e| format!("WAL block write failed: {}", e))?;
+            }
+            None => {
+                self.tree
+                    .remove(KEY_LOCKED_BLOCK)
+                    .map_err(|e| format!("WAL block remove failed: {}", e))?;
+            }
+        }
+        self.flush()
+    }
+
+    /// Record a finalized sequence and drop votes at or below it.
+    /// The watermark itself is kept so old sequences can never be re-voted.
+    pub fn record_finalized(&self, sequence: u64) -> Result<(), String> {
+        self.tree
+            .insert(KEY_FINALIZED_SEQUENCE, &sequence.to_le_bytes())
+            .map_err(|e| format!("WAL watermark write failed: {}", e))?;
+
+        let upper = format!("{}{:020}_~", VOTE_PREFIX, sequence);
+        let stale: Vec<sled::IVec> = self
+            .tree
+            .range(VOTE_PREFIX.as_bytes()..upper.as_bytes())
+            .keys()
+            .filter_map(|k| k.ok())
+            .collect();
+        for key in stale {
+            self.tree
+                .remove(key)
+                .map_err(|e| format!("WAL prune failed: {}", e))?;
+        }
+        self.flush()
+    }
+
+    /// Write a confirmation for the account-chain slot `key` ("account:previous").
+    /// Durable only after `flush`.
+    pub fn record_confirm_vote(&self, key: &str, vote: &ConfirmVote) -> Result<(), String> {
+        self.tree
+            .insert(
+                format!("{}{}", CONFIRM_PREFIX, key).as_bytes(),
+                vote.encode().as_bytes(),
+            )
+            .map_err(|e| format!("WAL confirm write failed: {}", e))?;
+        Ok(())
+    }
+
+    /// Drop confirmations for slots that are settled or expired. Not flushed:
+    /// a removal lost in a crash only keeps a slot locked a little longer.
+    pub fn remove_confirm_votes(&self, keys: &[String]) -> Result<(), String> {
+        for key in keys {
+            self.tree
+                .remove(format!("{}{}", CONFIRM_PREFIX, key).as_bytes())
+                .map_err(|e| format!("WAL confirm remove failed: {}", e))?;
+        }
+        Ok(())
+    }
+
+    /// Write the running validator set epoch. Durable only after `flush`;
+    /// an epoch lost in a crash is replayed from peers' commits.
+    pub fn record_epoch(&self, epoch: &ValidatorSetEpoch) -> Result<(), String> {
+        let bytes =
+            bincode::serialize(epoch).map_err(|e| format!("WAL epoch serialize failed: {}", e))?;
+        self.tree
+            .insert(KEY_EPOCH, bytes)
+            .map_err(|e| format!("WAL epoch write failed: {}", e))?;
+        Ok(())
+    }
+
+    /// Write (or clear) the epoch transition this validator signed.
+    /// Durable only after `flush`.
+    pub fn record_epoch_lock(&self, transition: Option<&EpochTransition>) -> Result<(), String> {
+        match transition {
+            Some(t) => {
+                let bytes = bincode::serialize(t)
+                    .map_err(|e| format!("WAL epoch lock serialize failed: {}", e))?;
+                self.tree
+                    .insert(KEY_EPOCH_LOCK, bytes)
+                    .map_err(|e| format!("WAL epoch lock write failed: {}", e))?;
+            }
+            None => {
+                self.tree
+                    .remove(KEY_EPOCH_LOCK)
+                    .map_err(|e| format!("WAL epoch lock remove failed: {}", e))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Write an agreed epoch transition. Durable only after `flush`.
+    pub fn record_epoch_commit(&self, commit: &EpochCommit) -> Result<(), String> {
+        let bytes = bincode::serialize(commit)
+            .map_err(|e| format!("WAL epoch commit serialize failed: {}", e))?;
+        self.tree
+            .insert(
+                format!("{}{:020}", EPOCH_COMMIT_PREFIX, commit.transition.epoch).as_bytes(),
+                bytes,
+            )
+            .map_err(|e| format!("WAL epoch commit write failed: {}", e))?;
+        Ok(())
+    }
+
+    /// Agreed transitions into epochs after `epoch`, oldest first
+    pub fn epoch_commits_after(&self, epoch: u64, limit: usize) -> Vec<EpochCommit> {
+        let start = format!("{}{:020}", EPOCH_COMMIT_PREFIX, epoch.saturating_add(1));
+        self.tree
+            .range(start.as_bytes()..)
+            .take_while(|item| {
+                item.as_ref()
+                    .is_ok_and(|(k, _)| k.starts_with(EPOCH_COMMIT_PREFIX.as_bytes()))
+            })
+            .filter_map(|item| item.ok())
+            .filter_map(|(_, v)| bincode::deserialize(&v).ok())
+            .take(limit)
+            .collect()
+    }
+
+    /// Load everything needed to resume after a crash.
+    /// Corrupt entries are skipped rather than aborting startup.
+    pub fn recover(&self) -> Result<WalRecovery, String> {
+        let read = |key: &[u8]| {
+            self.tree
+                .get(key)
+                .map_err(|e| format!("WAL read failed: {}", e))
+        };
+
+        let view_state = read(KEY_VIEW)?.and_then(|b| bincode::deserialize(&b).ok());
+        let locked_block = read(KEY_LOCKED_BLOCK)?.and_then(|b| bincode::deserialize(&b).ok());
+        let finalized_sequence = read(KEY_FINALIZED_SEQUENCE)?
+            .map(|b| {
+                let arr: [u8; 8] = b.as_ref().try_into().unwrap_or([0u8; 8]);
+                u64::from_le_bytes(arr)
+            })
+            .unwrap_or(0);
+
+        let mut votes = Vec::new();
+        for item in self.tree.scan_prefix(VOTE_PREFIX.as_bytes()) {
+            let (_, value) = item.map_err(|e| format!("WAL scan failed: {}", e))?;
+            if let Ok(msg) = bincode::deserialize::<ConsensusMessage>(&value) {
+                votes.push(msg);
+            }
+        }
+
+        let mut confirm_votes = BTreeMap::new();
+        for item in self.tree.scan_prefix(CONFIRM_PREFIX.as_bytes()) {
+            let (key, value) = item.map_err(|e| format!("WAL scan failed: {}", e))?;
+            let key = String::from_utf8_lossy(&key[CONFIRM_PREFIX.len()..]).into_owned();
+            confirm_votes.insert(key, ConfirmVote::decode(&String::from_utf8_lossy(&value)));
+        }
+
+        let epoch = read(KEY_EPOCH)?.and_then(|b| bincode::deserialize(&b).ok());
+        let epoch_lock = read(KEY_EPOCH_LOCK)?.and_then(|b| bincode::deserialize(&b).ok());
+
+        Ok(WalRecovery {
+            view_state,
+            locked_block,
+            finalized_sequence,
+            votes,
+            confirm_votes,
+            epoch,
+            epoch_lock,
+        })
+    }
+}
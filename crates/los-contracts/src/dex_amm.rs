@@ -10,12 +10,22 @@
 //! - 0.3% swap fee (30 bps) distributed to liquidity providers
 //! - MEV Protection: max slippage + deadline enforcement
 //! - LP token tracking (proportional share)
+//! - Real token movement in the same call (see below)
 //! - No floating-point arithmetic — consensus-safe
 //!
+//! ## Token Movement
+//! USP-01 legs are pulled from the caller with `token_transfer`, so the
+//! caller either attaches a token allowance to the call itself or has
+//! `approve`d the DEX on the token contract. `LOS` legs are paid by sending
+//! CIL with the call, read with `call_value()` (any surplus is refunded). Payouts go
+//! back the same way. A leg that can't move aborts the call, so swaps and
+//! liquidity changes are all-or-nothing.
+//!
 //! ## State Layout
 //! - `dex:init`                              → "1" when initialized
 //! - `dex:owner`                             → DEX deployer address
 //! - `dex:pool_count`                        → Number of pools (decimal string)
+//! - `dex:los_held`                          → CIL held for `LOS` reserves (decimal string)
 //! - `pool:{id}:token_a`                     → Token A address (or "LOS")
 //! - `pool:{id}:token_b`                     → Token B address (or "LOS")
 //! - `pool:{id}:reserve_a`                   → Reserve A (decimal string)
@@ -40,889 +50,950 @@
 //! | `get_position`     | pool_id                                                  |
 //! | `list_pools`       | (none)                                                   |
 //!
+//! Exports, argument decoding and the embedded ABI are generated by
+//! `#[los_sdk::contract]`. Entry points reply `{"success":true,"message":..,"data":..}`;
+//! views reply `{"success":true,"data":..}`.
+//!
 //! ## Compilation
 //! ```bash
 //! cargo build --target wasm32-unknown-unknown --release \
-//!     -p los-contract-examples --bin dex_amm_wasm --features sdk
+//!     --manifest-path crates/los-contracts/Cargo.toml --bin dex_amm
 //! ```
 
 #![no_std]
-#![no_main]
+#![cfg_attr(not(test), no_main)]
 
 extern crate alloc;
-extern crate los_sdk;
-
-use alloc::format;
-use alloc::string::String;
-use los_sdk::*;
-
-// ─────────────────────────────────────────────────────────────
-// CONSTANTS (integer-only, no f32/f64)
-// ─────────────────────────────────────────────────────────────
-
-/// Default swap fee: 30 bps = 0.3%
-const DEFAULT_FEE_BPS: u128 = 30;
-/// Basis point denominator
-const BPS_DENOMINATOR: u128 = 10_000;
-/// Minimum liquidity locked forever (prevent price manipulation)
-const MINIMUM_LIQUIDITY: u128 = 1_000;
-/// Max fee: 1000 bps = 10%
-const MAX_FEE_BPS: u128 = 1_000;
-/// Precision multiplier for overflow-safe calculations
-const PRECISION: u128 = 1_000_000_000_000;
-
-// ─────────────────────────────────────────────────────────────
-// HELPERS
-// ─────────────────────────────────────────────────────────────
-
-/// Parse a decimal string to u128. Returns 0 on failure.
-fn parse_u128(s: &str) -> u128 {
-    let mut result: u128 = 0;
-    for b in s.as_bytes() {
-        if *b >= b'0' && *b <= b'9' {
-            result = match result.checked_mul(10) {
-                Some(v) => v,
-                None => return 0,
-            };
-            result = match result.checked_add((*b - b'0') as u128) {
-                Some(v) => v,
-                None => return 0,
-            };
-        } else {
-            return 0;
-        }
-    }
-    result
-}
-
-/// Convert u128 to decimal string without std.
-fn u128_to_str(val: u128) -> String {
-    if val == 0 {
-        return String::from("0");
-    }
-    let mut buf = [0u8; 40];
-    let mut pos = buf.len();
-    let mut v = val;
-    while v > 0 {
-        pos -= 1;
-        buf[pos] = b'0' + (v % 10) as u8;
-        v /= 10;
-    }
-    let bytes = &buf[pos..];
-    // SAFETY: we only write ASCII digits
-    unsafe { String::from_utf8_unchecked(alloc::vec::Vec::from(bytes)) }
-}
-
-/// Parse u64 from decimal string.
-fn parse_u64(s: &str) -> u64 {
-    let mut result: u64 = 0;
-    for b in s.as_bytes() {
-        if *b >= b'0' && *b <= b'9' {
-            result = match result.checked_mul(10) {
-                Some(v) => v,
-                None => return 0,
-            };
-            result = match result.checked_add((*b - b'0') as u64) {
-                Some(v) => v,
-                None => return 0,
-            };
-        } else {
-            return 0;
-        }
-    }
-    result
-}
+#[cfg(test)]
+extern crate std;
 
-/// Escape a string for JSON output.
-fn json_escape(s: &str) -> String {
-    let mut out = String::with_capacity(s.len() + 8);
-    for c in s.chars() {
-        match c {
-            '"' => out.push_str("\\\""),
-            '\\' => out.push_str("\\\\"),
-            '\n' => out.push_str("\\n"),
-            _ => out.push(c),
-        }
-    }
-    out
-}
+#[los_sdk::contract(name = "DEX-AMM")]
+pub mod dex {
+    use alloc::format;
+    use alloc::string::String;
+    use alloc::vec::Vec;
+    use los_contracts::{
+        compute_output, deduct_fee, isqrt, make_pool_id, parse_u128, parse_u64, u128_to_str,
+    };
+    use los_sdk::codec::{write_field, Address, ToJson};
+    use los_sdk::{
+        abort, call_value, caller, self_address, state, timestamp, token_transfer, transfer,
+    };
 
-/// Return failure JSON.
-fn fail(msg: &str) -> i32 {
-    let resp = format!(
-        "{{\"success\":false,\"message\":\"{}\"}}",
-        json_escape(msg)
-    );
-    set_return_str(&resp);
-    1
-}
+    type CallResult<T> = Result<T, String>;
 
-/// Return success JSON.
-fn ok(msg: &str) -> i32 {
-    let resp = format!(
-        "{{\"success\":true,\"message\":\"{}\"}}",
-        json_escape(msg)
-    );
-    set_return_str(&resp);
-    0
-}
+    // ─────────────────────────────────────────────────────────────
+    // CONSTANTS (integer-only, no f32/f64)
+    // ─────────────────────────────────────────────────────────────
 
-/// Return success JSON with data.
-fn ok_data(msg: &str, data: &str) -> i32 {
-    let resp = format!(
-        "{{\"success\":true,\"message\":\"{}\",\"data\":{}}}",
-        json_escape(msg),
-        data
-    );
-    set_return_str(&resp);
-    0
-}
+    /// Default swap fee: 30 bps = 0.3%
+    const DEFAULT_FEE_BPS: u128 = 30;
+    /// Basis point denominator
+    const BPS_DENOMINATOR: u128 = 10_000;
+    /// Minimum liquidity locked forever (prevent price manipulation)
+    const MINIMUM_LIQUIDITY: u128 = 1_000;
+    /// Max fee: 1000 bps = 10%
+    const MAX_FEE_BPS: u128 = 1_000;
+    /// Precision multiplier for overflow-safe calculations
+    const PRECISION: u128 = 1_000_000_000_000;
+    /// Pool leg paid in native CIL rather than a USP-01 token contract
+    const NATIVE_TOKEN: &str = "LOS";
 
-// ─────────────────────────────────────────────────────────────
-// INTEGER MATH (NO f32/f64)
-// ─────────────────────────────────────────────────────────────
+    // ─────────────────────────────────────────────────────────────
+    // STATE HELPERS
+    // ─────────────────────────────────────────────────────────────
 
-/// Integer square root — Newton's method. Returns floor(√n).
-fn isqrt(n: u128) -> u128 {
-    if n == 0 {
-        return 0;
-    }
-    let mut x = n;
-    let mut y = x.div_ceil(2); // safe: no overflow for u128::MAX
-    while y < x {
-        x = y;
-        y = (x + n / x) / 2;
+    fn get_state_str(key: &str) -> String {
+        state::get_str(key).unwrap_or_default()
     }
-    x
-}
 
-/// Compute swap output using constant product formula.
-/// `amount_out = (amount_in * reserve_out) / (reserve_in + amount_in)`
-fn compute_output(amount_in: u128, reserve_in: u128, reserve_out: u128) -> u128 {
-    if reserve_in == 0 || reserve_out == 0 || amount_in == 0 {
-        return 0;
-    }
-    match (
-        amount_in.checked_mul(reserve_out),
-        reserve_in.checked_add(amount_in),
-    ) {
-        (Some(num), Some(den)) if den > 0 => num / den,
-        _ => {
-            // Overflow fallback: scaled division
-            let ratio_scaled =
-                (amount_in as u128 * PRECISION) / reserve_in.saturating_add(amount_in);
-            (ratio_scaled * reserve_out) / PRECISION
-        }
+    fn get_state_u128(key: &str) -> u128 {
+        parse_u128(&get_state_str(key))
     }
-}
-
-/// Deduct fee from input amount. Returns (after_fee, fee).
-fn deduct_fee(amount: u128, fee_bps: u128) -> (u128, u128) {
-    let fee = amount * fee_bps / BPS_DENOMINATOR;
-    (amount - fee, fee)
-}
-
-// ─────────────────────────────────────────────────────────────
-// STATE HELPERS
-// ─────────────────────────────────────────────────────────────
-
-fn get_state_str(key: &str) -> String {
-    state::get_str(key).unwrap_or_default()
-}
-
-fn get_state_u128(key: &str) -> u128 {
-    parse_u128(&get_state_str(key))
-}
-
-fn get_state_u64(key: &str) -> u64 {
-    parse_u64(&get_state_str(key))
-}
-
-fn set_state_u128(key: &str, val: u128) {
-    state::set_str(key, &u128_to_str(val));
-}
 
-fn set_state_u64(key: &str, val: u64) {
-    state::set_str(key, &u128_to_str(val as u128));
-}
-
-/// Generate deterministic pool ID from token pair (sorted).
-fn make_pool_id(token_a: &str, token_b: &str) -> String {
-    if token_a < token_b {
-        format!("POOL:{}:{}", token_a, token_b)
-    } else {
-        format!("POOL:{}:{}", token_b, token_a)
+    fn get_state_u64(key: &str) -> u64 {
+        parse_u64(&get_state_str(key))
     }
-}
 
-/// Check if a pool exists.
-fn pool_exists(pool_id: &str) -> bool {
-    let key = format!("pool:{}:token_a", pool_id);
-    !get_state_str(&key).is_empty()
-}
-
-// ─────────────────────────────────────────────────────────────
-// EXPORTED FUNCTIONS
-// ─────────────────────────────────────────────────────────────
-
-/// Initialize the DEX contract.
-#[no_mangle]
-pub extern "C" fn init() -> i32 {
-    if get_state_str("dex:init") == "1" {
-        return fail("DEX already initialized");
+    fn set_state_u128(key: &str, val: u128) {
+        state::set_str(key, &u128_to_str(val));
     }
-    let who = caller();
-    state::set_str("dex:init", "1");
-    state::set_str("dex:owner", &who);
-    set_state_u64("dex:pool_count", 0);
-
-    event::emit("DexInit", &format!("{{\"owner\":\"{}\"}}", json_escape(&who)));
-    ok("DEX initialized")
-}
 
-/// Create a new liquidity pool for a token pair.
-/// Args: token_a, token_b, amount_a, amount_b [, fee_bps]
-#[no_mangle]
-pub extern "C" fn create_pool() -> i32 {
-    if get_state_str("dex:init") != "1" {
-        return fail("DEX not initialized");
+    fn set_state_u64(key: &str, val: u64) {
+        state::set_str(key, &u128_to_str(val as u128));
     }
 
-    let token_a = match arg(0) {
-        Some(v) if !v.is_empty() => v,
-        _ => return fail("Missing token_a"),
-    };
-    let token_b = match arg(1) {
-        Some(v) if !v.is_empty() => v,
-        _ => return fail("Missing token_b"),
-    };
-    let amount_a = match arg(2) {
-        Some(v) => parse_u128(&v),
-        None => return fail("Missing amount_a"),
-    };
-    let amount_b = match arg(3) {
-        Some(v) => parse_u128(&v),
-        None => return fail("Missing amount_b"),
-    };
-    let fee_bps = match arg(4) {
-        Some(v) if !v.is_empty() => parse_u128(&v),
-        _ => DEFAULT_FEE_BPS,
-    };
-
-    // Validation
-    if token_a == token_b {
-        return fail("Cannot create pool with identical tokens");
+    /// Fail unless `init` has run.
+    fn require_init() -> CallResult<()> {
+        if get_state_str("dex:init") == "1" {
+            Ok(())
+        } else {
+            Err("DEX not initialized".into())
+        }
     }
-    if amount_a == 0 || amount_b == 0 {
-        return fail("Initial liquidity must be > 0 for both tokens");
+
+    /// Fail unless the pool exists; returns its `pool:{id}` state prefix.
+    fn require_pool(pool_id: &str) -> CallResult<String> {
+        let prefix = format!("pool:{}", pool_id);
+        if get_state_str(&format!("{}:token_a", prefix)).is_empty() {
+            return Err("Pool not found".into());
+        }
+        Ok(prefix)
     }
-    if fee_bps > MAX_FEE_BPS {
-        return fail("Fee too high (max 1000 bps = 10%)");
+
+    fn require_non_empty(value: &str, name: &str) -> CallResult<()> {
+        if value.is_empty() {
+            return Err(format!("Missing {}", name));
+        }
+        Ok(())
+    }
+
+    // ─────────────────────────────────────────────────────────────
+    // TOKEN MOVEMENT (a failed leg aborts, reverting earlier legs)
+    // ─────────────────────────────────────────────────────────────
+
+    /// Take `amount` of `token` from `from` into the DEX. `LOS` is the CIL
+    /// sent with this call; the balance also holds the storage deposit, so
+    /// it can't tell what was paid in.
+    fn pay_in(token: &str, from: &str, amount: u128) {
+        if token == NATIVE_TOKEN {
+            let held = get_state_u128("dex:los_held");
+            let sent = call_value();
+            if sent < amount {
+                abort(&format!(
+                    "Send {} CIL with the call (got {})",
+                    u128_to_str(amount),
+                    u128_to_str(sent)
+                ));
+            }
+            if sent > amount {
+                if let Err(e) = transfer(from, sent - amount) {
+                    abort(e);
+                }
+            }
+            set_state_u128("dex:los_held", held + amount);
+        } else if let Err(e) = token_transfer(token, from, &self_address(), amount) {
+            abort(&format!("{}: {}", token, e));
+        }
     }
 
-    let pool_id = make_pool_id(&token_a, &token_b);
-    if pool_exists(&pool_id) {
-        return fail(&format!("Pool {} already exists", pool_id));
+    /// Send `amount` of `token` from the DEX to `to`.
+    fn pay_out(token: &str, to: &str, amount: u128) {
+        let moved = if token == NATIVE_TOKEN {
+            let held = get_state_u128("dex:los_held");
+            set_state_u128("dex:los_held", held.saturating_sub(amount));
+            transfer(to, amount)
+        } else {
+            token_transfer(token, &self_address(), to, amount)
+        };
+        if let Err(e) = moved {
+            abort(&format!("{}: {}", token, e));
+        }
     }
 
-    // Initial LP = sqrt(amount_a * amount_b) - MINIMUM_LIQUIDITY
-    let product = match amount_a.checked_mul(amount_b) {
-        Some(p) => p,
-        None => return fail("Overflow: amounts too large"),
-    };
-    let initial_lp = isqrt(product);
-    if initial_lp <= MINIMUM_LIQUIDITY {
-        return fail("Initial liquidity too small");
-    }
-    let lp_tokens = initial_lp - MINIMUM_LIQUIDITY;
-
-    let who = caller();
-
-    // Store pool state
-    let prefix = format!("pool:{}", pool_id);
-    state::set_str(&format!("{}:token_a", prefix), &token_a);
-    state::set_str(&format!("{}:token_b", prefix), &token_b);
-    set_state_u128(&format!("{}:reserve_a", prefix), amount_a);
-    set_state_u128(&format!("{}:reserve_b", prefix), amount_b);
-    set_state_u128(&format!("{}:total_lp", prefix), initial_lp);
-    set_state_u128(&format!("{}:fee_bps", prefix), fee_bps);
-    state::set_str(&format!("{}:creator", prefix), &who);
-    set_state_u64(&format!("{}:last_trade", prefix), 0);
-
-    // Store LP shares for creator
-    let lp_key = format!("lp:{}:{}", pool_id, who);
-    set_state_u128(&lp_key, lp_tokens);
-
-    // Add to pool index
-    let count = get_state_u64("dex:pool_count");
-    state::set_str(&format!("pool_list:{}", count), &pool_id);
-    set_state_u64("dex:pool_count", count + 1);
-
-    event::emit(
-        "PoolCreated",
-        &format!(
-            "{{\"pool_id\":\"{}\",\"token_a\":\"{}\",\"token_b\":\"{}\",\"reserve_a\":\"{}\",\"reserve_b\":\"{}\",\"lp_tokens\":\"{}\"}}",
-            json_escape(&pool_id),
-            json_escape(&token_a),
-            json_escape(&token_b),
-            u128_to_str(amount_a),
-            u128_to_str(amount_b),
-            u128_to_str(lp_tokens),
-        ),
-    );
-
-    ok_data(
-        &format!("Pool {} created", pool_id),
-        &format!(
-            "{{\"pool_id\":\"{}\",\"lp_tokens\":\"{}\",\"reserve_a\":\"{}\",\"reserve_b\":\"{}\"}}",
-            json_escape(&pool_id),
-            u128_to_str(lp_tokens),
-            u128_to_str(amount_a),
-            u128_to_str(amount_b),
-        ),
-    )
-}
+    // ─────────────────────────────────────────────────────────────
+    // EVENTS & RETURN VALUES
+    // ─────────────────────────────────────────────────────────────
 
-/// Add liquidity to an existing pool.
-/// Args: pool_id, amount_a, amount_b, min_lp_tokens
-#[no_mangle]
-pub extern "C" fn add_liquidity() -> i32 {
-    if get_state_str("dex:init") != "1" {
-        return fail("DEX not initialized");
+    #[event]
+    pub struct DexInit {
+        owner: Address,
     }
 
-    let pool_id = match arg(0) {
-        Some(v) if !v.is_empty() => v,
-        _ => return fail("Missing pool_id"),
-    };
-    let amount_a = match arg(1) {
-        Some(v) => parse_u128(&v),
-        None => return fail("Missing amount_a"),
-    };
-    let amount_b = match arg(2) {
-        Some(v) => parse_u128(&v),
-        None => return fail("Missing amount_b"),
-    };
-    let min_lp_tokens = match arg(3) {
-        Some(v) => parse_u128(&v),
-        None => return fail("Missing min_lp_tokens"),
-    };
+    #[event]
+    pub struct PoolCreated {
+        pool_id: String,
+        token_a: String,
+        token_b: String,
+        reserve_a: u128,
+        reserve_b: u128,
+        lp_tokens: u128,
+    }
 
-    if !pool_exists(&pool_id) {
-        return fail("Pool not found");
+    #[event]
+    pub struct LiquidityAdded {
+        pool_id: String,
+        provider: Address,
+        amount_a: u128,
+        amount_b: u128,
+        lp_tokens: u128,
     }
-    if amount_a == 0 || amount_b == 0 {
-        return fail("Amounts must be > 0");
+
+    #[event]
+    pub struct LiquidityRemoved {
+        pool_id: String,
+        provider: Address,
+        amount_a: u128,
+        amount_b: u128,
+        lp_burned: u128,
     }
 
-    let prefix = format!("pool:{}", pool_id);
-    let reserve_a = get_state_u128(&format!("{}:reserve_a", prefix));
-    let reserve_b = get_state_u128(&format!("{}:reserve_b", prefix));
-    let total_lp = get_state_u128(&format!("{}:total_lp", prefix));
+    #[event]
+    pub struct Swap {
+        pool_id: String,
+        trader: Address,
+        token_in: String,
+        amount_in: u128,
+        token_out: String,
+        amount_out: u128,
+        fee: u128,
+    }
 
-    if reserve_a == 0 || reserve_b == 0 || total_lp == 0 {
-        return fail("Pool has no liquidity");
+    /// Entry point reply: a human-readable `message` plus structured `data`.
+    pub struct Reply<T> {
+        message: String,
+        data: T,
     }
 
-    // LP = min(amount_a * total_lp / reserve_a, amount_b * total_lp / reserve_b)
-    let lp_from_a = amount_a * total_lp / reserve_a;
-    let lp_from_b = amount_b * total_lp / reserve_b;
-    let lp_tokens = if lp_from_a < lp_from_b {
-        lp_from_a
-    } else {
-        lp_from_b
-    };
+    impl<T: ToJson> ToJson for Reply<T> {
+        fn write_json(&self, out: &mut String) {
+            out.push('{');
+            write_field(out, true, "message", &self.message);
+            write_field(out, false, "data", &self.data);
+            out.push('}');
+        }
+    }
 
-    if lp_tokens < min_lp_tokens {
-        return fail(&format!(
-            "Slippage: would mint {} LP but minimum is {}",
-            u128_to_str(lp_tokens),
-            u128_to_str(min_lp_tokens)
-        ));
-    }
-
-    // Calculate actual amounts used (proportional)
-    let actual_a = lp_tokens * reserve_a / total_lp;
-    let actual_b = lp_tokens * reserve_b / total_lp;
-
-    // Update reserves
-    set_state_u128(&format!("{}:reserve_a", prefix), reserve_a + actual_a);
-    set_state_u128(&format!("{}:reserve_b", prefix), reserve_b + actual_b);
-    set_state_u128(&format!("{}:total_lp", prefix), total_lp + lp_tokens);
-
-    // Update LP shares
-    let who = caller();
-    let lp_key = format!("lp:{}:{}", pool_id, who);
-    let existing_lp = get_state_u128(&lp_key);
-    set_state_u128(&lp_key, existing_lp + lp_tokens);
-
-    event::emit(
-        "LiquidityAdded",
-        &format!(
-            "{{\"pool_id\":\"{}\",\"provider\":\"{}\",\"amount_a\":\"{}\",\"amount_b\":\"{}\",\"lp_tokens\":\"{}\"}}",
-            json_escape(&pool_id),
-            json_escape(&who),
-            u128_to_str(actual_a),
-            u128_to_str(actual_b),
-            u128_to_str(lp_tokens),
-        ),
-    );
-
-    ok_data(
-        &format!("Added liquidity: {} LP tokens minted", u128_to_str(lp_tokens)),
-        &format!(
-            "{{\"lp_tokens\":\"{}\",\"amount_a_used\":\"{}\",\"amount_b_used\":\"{}\"}}",
-            u128_to_str(lp_tokens),
-            u128_to_str(actual_a),
-            u128_to_str(actual_b),
-        ),
-    )
-}
+    #[json]
+    pub struct Ack {
+        message: &'static str,
+    }
 
-/// Remove liquidity from a pool.
-/// Args: pool_id, lp_amount, min_amount_a, min_amount_b
-#[no_mangle]
-pub extern "C" fn remove_liquidity() -> i32 {
-    if get_state_str("dex:init") != "1" {
-        return fail("DEX not initialized");
+    #[json]
+    pub struct NewPool {
+        pool_id: String,
+        lp_tokens: u128,
+        reserve_a: u128,
+        reserve_b: u128,
     }
 
-    let pool_id = match arg(0) {
-        Some(v) if !v.is_empty() => v,
-        _ => return fail("Missing pool_id"),
-    };
-    let lp_amount = match arg(1) {
-        Some(v) => parse_u128(&v),
-        None => return fail("Missing lp_amount"),
-    };
-    let min_amount_a = match arg(2) {
-        Some(v) => parse_u128(&v),
-        None => return fail("Missing min_amount_a"),
-    };
-    let min_amount_b = match arg(3) {
-        Some(v) => parse_u128(&v),
-        None => return fail("Missing min_amount_b"),
-    };
+    #[json]
+    pub struct Added {
+        lp_tokens: u128,
+        amount_a_used: u128,
+        amount_b_used: u128,
+    }
 
-    if !pool_exists(&pool_id) {
-        return fail("Pool not found");
-    }
-
-    let who = caller();
-    let lp_key = format!("lp:{}:{}", pool_id, who);
-    let caller_lp = get_state_u128(&lp_key);
-    if caller_lp < lp_amount {
-        return fail(&format!(
-            "Insufficient LP tokens: have {} need {}",
-            u128_to_str(caller_lp),
-            u128_to_str(lp_amount)
-        ));
-    }
-
-    let prefix = format!("pool:{}", pool_id);
-    let reserve_a = get_state_u128(&format!("{}:reserve_a", prefix));
-    let reserve_b = get_state_u128(&format!("{}:reserve_b", prefix));
-    let total_lp = get_state_u128(&format!("{}:total_lp", prefix));
-
-    if total_lp == 0 {
-        return fail("Pool has no liquidity");
-    }
-
-    // Proportional token amounts
-    let amount_a = lp_amount * reserve_a / total_lp;
-    let amount_b = lp_amount * reserve_b / total_lp;
-
-    // Slippage protection
-    if amount_a < min_amount_a || amount_b < min_amount_b {
-        return fail(&format!(
-            "Slippage: would receive ({}, {}) but minimum is ({}, {})",
-            u128_to_str(amount_a),
-            u128_to_str(amount_b),
-            u128_to_str(min_amount_a),
-            u128_to_str(min_amount_b),
-        ));
-    }
-
-    // Update reserves
-    set_state_u128(&format!("{}:reserve_a", prefix), reserve_a - amount_a);
-    set_state_u128(&format!("{}:reserve_b", prefix), reserve_b - amount_b);
-    set_state_u128(&format!("{}:total_lp", prefix), total_lp - lp_amount);
-
-    // Update LP shares
-    let new_lp = caller_lp - lp_amount;
-    if new_lp == 0 {
-        state::del(&lp_key);
-    } else {
-        set_state_u128(&lp_key, new_lp);
-    }
-
-    event::emit(
-        "LiquidityRemoved",
-        &format!(
-            "{{\"pool_id\":\"{}\",\"provider\":\"{}\",\"amount_a\":\"{}\",\"amount_b\":\"{}\",\"lp_burned\":\"{}\"}}",
-            json_escape(&pool_id),
-            json_escape(&who),
-            u128_to_str(amount_a),
-            u128_to_str(amount_b),
-            u128_to_str(lp_amount),
-        ),
-    );
-
-    ok_data(
-        &format!(
-            "Removed liquidity: {} LP tokens burned",
-            u128_to_str(lp_amount)
-        ),
-        &format!(
-            "{{\"amount_a\":\"{}\",\"amount_b\":\"{}\",\"lp_burned\":\"{}\"}}",
-            u128_to_str(amount_a),
-            u128_to_str(amount_b),
-            u128_to_str(lp_amount),
-        ),
-    )
-}
+    #[json]
+    pub struct Removed {
+        amount_a: u128,
+        amount_b: u128,
+        lp_burned: u128,
+    }
 
-/// Swap tokens via constant product AMM.
-/// Args: pool_id, token_in, amount_in, min_amount_out, deadline
-#[no_mangle]
-pub extern "C" fn swap() -> i32 {
-    if get_state_str("dex:init") != "1" {
-        return fail("DEX not initialized");
+    #[json]
+    pub struct Swapped {
+        amount_out: u128,
+        fee: u128,
+        price_impact_bps: u128,
     }
 
-    let pool_id = match arg(0) {
-        Some(v) if !v.is_empty() => v,
-        _ => return fail("Missing pool_id"),
-    };
-    let token_in = match arg(1) {
-        Some(v) if !v.is_empty() => v,
-        _ => return fail("Missing token_in"),
-    };
-    let amount_in = match arg(2) {
-        Some(v) => parse_u128(&v),
-        None => return fail("Missing amount_in"),
-    };
-    let min_amount_out = match arg(3) {
-        Some(v) => parse_u128(&v),
-        None => return fail("Missing min_amount_out"),
-    };
-    let deadline = match arg(4) {
-        Some(v) => parse_u64(&v),
-        None => return fail("Missing deadline"),
-    };
+    #[json]
+    pub struct PoolInfo {
+        pool_id: String,
+        token_a: String,
+        token_b: String,
+        reserve_a: u128,
+        reserve_b: u128,
+        total_lp: u128,
+        fee_bps: u128,
+        creator: String,
+        /// u128 so it is encoded as a decimal string, as before
+        last_trade: u128,
+        spot_price_scaled: u128,
+    }
 
-    if !pool_exists(&pool_id) {
-        return fail("Pool not found");
+    #[json]
+    pub struct Quote {
+        amount_out: u128,
+        fee: u128,
+        price_impact_bps: u128,
+        spot_price_scaled: u128,
     }
-    if amount_in == 0 {
-        return fail("Amount must be > 0");
+
+    #[json]
+    pub struct Position {
+        lp_shares: u128,
+        total_lp: u128,
+        amount_a: u128,
+        amount_b: u128,
+        share_pct_bps: u128,
     }
 
-    // MEV Protection: deadline check
-    let now = timestamp();
-    if deadline > 0 && now > deadline {
-        return fail(&format!(
-            "Transaction expired: deadline {} < current {}",
-            deadline, now
-        ));
+    #[json]
+    pub struct PoolSummary {
+        pool_id: String,
+        token_a: String,
+        token_b: String,
+        reserve_a: u128,
+        reserve_b: u128,
+        total_lp: u128,
     }
 
-    let prefix = format!("pool:{}", pool_id);
-    let pool_token_a = get_state_str(&format!("{}:token_a", prefix));
-    let pool_token_b = get_state_str(&format!("{}:token_b", prefix));
-    let reserve_a = get_state_u128(&format!("{}:reserve_a", prefix));
-    let reserve_b = get_state_u128(&format!("{}:reserve_b", prefix));
-    let fee_bps = get_state_u128(&format!("{}:fee_bps", prefix));
+    // ─────────────────────────────────────────────────────────────
+    // EXPORTED FUNCTIONS
+    // ─────────────────────────────────────────────────────────────
 
-    // Determine swap direction
-    let is_a_to_b = token_in == pool_token_a;
-    let is_b_to_a = token_in == pool_token_b;
-    if !is_a_to_b && !is_b_to_a {
-        return fail(&format!(
-            "Token {} is not in pool (expected {} or {})",
-            token_in, pool_token_a, pool_token_b
-        ));
-    }
+    /// Initialize the DEX contract.
+    #[entry]
+    pub fn init() -> CallResult<Ack> {
+        if get_state_str("dex:init") == "1" {
+            return Err("DEX already initialized".into());
+        }
+        let who = caller();
+        state::set_str("dex:init", "1");
+        state::set_str("dex:owner", &who);
+        set_state_u64("dex:pool_count", 0);
 
-    let (reserve_in, reserve_out, token_out) = if is_a_to_b {
-        (reserve_a, reserve_b, pool_token_b.clone())
-    } else {
-        (reserve_b, reserve_a, pool_token_a.clone())
-    };
+        DexInit {
+            owner: Address::from(who),
+        }
+        .emit();
+        Ok(Ack {
+            message: "DEX initialized",
+        })
+    }
+
+    /// Create a new liquidity pool for a token pair (`fee_bps` defaults to 30).
+    #[entry]
+    pub fn create_pool(
+        token_a: String,
+        token_b: String,
+        amount_a: u128,
+        amount_b: u128,
+        fee_bps: Option<u128>,
+    ) -> CallResult<Reply<NewPool>> {
+        require_init()?;
+        require_non_empty(&token_a, "token_a")?;
+        require_non_empty(&token_b, "token_b")?;
+        let fee_bps = fee_bps.unwrap_or(DEFAULT_FEE_BPS);
+
+        // Validation
+        if token_a == token_b {
+            return Err("Cannot create pool with identical tokens".into());
+        }
+        if amount_a == 0 || amount_b == 0 {
+            return Err("Initial liquidity must be > 0 for both tokens".into());
+        }
+        if fee_bps > MAX_FEE_BPS {
+            return Err("Fee too high (max 1000 bps = 10%)".into());
+        }
 
-    // Deduct fee
-    let (amount_after_fee, fee) = deduct_fee(amount_in, fee_bps);
+        let pool_id = make_pool_id(&token_a, &token_b);
+        if require_pool(&pool_id).is_ok() {
+            return Err(format!("Pool {} already exists", pool_id));
+        }
 
-    // Constant product output
-    let amount_out = compute_output(amount_after_fee, reserve_in, reserve_out);
+        // Initial LP = sqrt(amount_a * amount_b) - MINIMUM_LIQUIDITY
+        let product = amount_a
+            .checked_mul(amount_b)
+            .ok_or("Overflow: amounts too large")?;
+        let initial_lp = isqrt(product);
+        if initial_lp <= MINIMUM_LIQUIDITY {
+            return Err("Initial liquidity too small".into());
+        }
+        let lp_tokens = initial_lp - MINIMUM_LIQUIDITY;
+
+        let who = caller();
+        pay_in(&token_a, &who, amount_a);
+        pay_in(&token_b, &who, amount_b);
+
+        // Store pool state
+        let prefix = format!("pool:{}", pool_id);
+        state::set_str(&format!("{}:token_a", prefix), &token_a);
+        state::set_str(&format!("{}:token_b", prefix), &token_b);
+        set_state_u128(&format!("{}:reserve_a", prefix), amount_a);
+        set_state_u128(&format!("{}:reserve_b", prefix), amount_b);
+        set_state_u128(&format!("{}:total_lp", prefix), initial_lp);
+        set_state_u128(&format!("{}:fee_bps", prefix), fee_bps);
+        state::set_str(&format!("{}:creator", prefix), &who);
+        set_state_u64(&format!("{}:last_trade", prefix), 0);
+
+        // Store LP shares for creator
+        let lp_key = format!("lp:{}:{}", pool_id, who);
+        set_state_u128(&lp_key, lp_tokens);
+
+        // Add to pool index
+        let count = get_state_u64("dex:pool_count");
+        state::set_str(&format!("pool_list:{}", count), &pool_id);
+        set_state_u64("dex:pool_count", count + 1);
+
+        PoolCreated {
+            pool_id: pool_id.clone(),
+            token_a,
+            token_b,
+            reserve_a: amount_a,
+            reserve_b: amount_b,
+            lp_tokens,
+        }
+        .emit();
+
+        Ok(Reply {
+            message: format!("Pool {} created", pool_id),
+            data: NewPool {
+                pool_id,
+                lp_tokens,
+                reserve_a: amount_a,
+                reserve_b: amount_b,
+            },
+        })
+    }
+
+    /// Add liquidity to an existing pool, minting at least `min_lp_tokens`.
+    #[entry]
+    pub fn add_liquidity(
+        pool_id: String,
+        amount_a: u128,
+        amount_b: u128,
+        min_lp_tokens: u128,
+    ) -> CallResult<Reply<Added>> {
+        require_init()?;
+        require_non_empty(&pool_id, "pool_id")?;
+        let prefix = require_pool(&pool_id)?;
+        if amount_a == 0 || amount_b == 0 {
+            return Err("Amounts must be > 0".into());
+        }
 
-    // MEV Protection: slippage check
-    if amount_out < min_amount_out {
-        return fail(&format!(
-            "Slippage exceeded: output {} < minimum {}",
-            u128_to_str(amount_out),
-            u128_to_str(min_amount_out)
-        ));
-    }
-    if amount_out == 0 {
-        return fail("Output amount is zero (insufficient liquidity)");
-    }
-    if amount_out >= reserve_out {
-        return fail("Insufficient liquidity for this trade");
-    }
+        let reserve_a = get_state_u128(&format!("{}:reserve_a", prefix));
+        let reserve_b = get_state_u128(&format!("{}:reserve_b", prefix));
+        let total_lp = get_state_u128(&format!("{}:total_lp", prefix));
 
-    // Update reserves — fee stays in pool for LPs
-    if is_a_to_b {
-        set_state_u128(&format!("{}:reserve_a", prefix), reserve_a + amount_in);
-        set_state_u128(&format!("{}:reserve_b", prefix), reserve_b - amount_out);
-    } else {
-        set_state_u128(&format!("{}:reserve_b", prefix), reserve_b + amount_in);
-        set_state_u128(&format!("{}:reserve_a", prefix), reserve_a - amount_out);
-    }
+        if reserve_a == 0 || reserve_b == 0 || total_lp == 0 {
+            return Err("Pool has no liquidity".into());
+        }
 
-    set_state_u64(&format!("{}:last_trade", prefix), now);
+        // LP = min(amount_a * total_lp / reserve_a, amount_b * total_lp / reserve_b)
+        let lp_from_a = amount_a * total_lp / reserve_a;
+        let lp_from_b = amount_b * total_lp / reserve_b;
+        let lp_tokens = lp_from_a.min(lp_from_b);
+
+        if lp_tokens < min_lp_tokens {
+            return Err(format!(
+                "Slippage: would mint {} LP but minimum is {}",
+                u128_to_str(lp_tokens),
+                u128_to_str(min_lp_tokens)
+            ));
+        }
 
-    let who = caller();
-    event::emit(
-        "Swap",
-        &format!(
-            "{{\"pool_id\":\"{}\",\"trader\":\"{}\",\"token_in\":\"{}\",\"amount_in\":\"{}\",\"token_out\":\"{}\",\"amount_out\":\"{}\",\"fee\":\"{}\"}}",
-            json_escape(&pool_id),
-            json_escape(&who),
-            json_escape(&token_in),
-            u128_to_str(amount_in),
-            json_escape(&token_out),
-            u128_to_str(amount_out),
-            u128_to_str(fee),
-        ),
-    );
-
-    // Price impact (bps)
-    let impact_bps = if reserve_out > 0 {
-        (amount_out * BPS_DENOMINATOR) / reserve_out
-    } else {
-        0
-    };
+        // Calculate actual amounts used (proportional)
+        let actual_a = lp_tokens * reserve_a / total_lp;
+        let actual_b = lp_tokens * reserve_b / total_lp;
+
+        let who = caller();
+        pay_in(
+            &get_state_str(&format!("{}:token_a", prefix)),
+            &who,
+            actual_a,
+        );
+        pay_in(
+            &get_state_str(&format!("{}:token_b", prefix)),
+            &who,
+            actual_b,
+        );
+
+        // Update reserves
+        set_state_u128(&format!("{}:reserve_a", prefix), reserve_a + actual_a);
+        set_state_u128(&format!("{}:reserve_b", prefix), reserve_b + actual_b);
+        set_state_u128(&format!("{}:total_lp", prefix), total_lp + lp_tokens);
+
+        // Update LP shares
+        let lp_key = format!("lp:{}:{}", pool_id, who);
+        let existing_lp = get_state_u128(&lp_key);
+        set_state_u128(&lp_key, existing_lp + lp_tokens);
+
+        LiquidityAdded {
+            pool_id,
+            provider: Address::from(who),
+            amount_a: actual_a,
+            amount_b: actual_b,
+            lp_tokens,
+        }
+        .emit();
+
+        Ok(Reply {
+            message: format!(
+                "Added liquidity: {} LP tokens minted",
+                u128_to_str(lp_tokens)
+            ),
+            data: Added {
+                lp_tokens,
+                amount_a_used: actual_a,
+                amount_b_used: actual_b,
+            },
+        })
+    }
+
+    /// Remove liquidity from a pool, receiving at least the given minimums.
+    #[entry]
+    pub fn remove_liquidity(
+        pool_id: String,
+        lp_amount: u128,
+        min_amount_a: u128,
+        min_amount_b: u128,
+    ) -> CallResult<Reply<Removed>> {
+        require_init()?;
+        require_non_empty(&pool_id, "pool_id")?;
+        let prefix = require_pool(&pool_id)?;
+
+        let who = caller();
+        let lp_key = format!("lp:{}:{}", pool_id, who);
+        let caller_lp = get_state_u128(&lp_key);
+        if caller_lp < lp_amount {
+            return Err(format!(
+                "Insufficient LP tokens: have {} need {}",
+                u128_to_str(caller_lp),
+                u128_to_str(lp_amount)
+            ));
+        }
 
-    ok_data(
-        &format!(
-            "Swapped {} {} -> {} {}",
-            u128_to_str(amount_in),
-            token_in,
-            u128_to_str(amount_out),
-            token_out
-        ),
-        &format!(
-            "{{\"amount_out\":\"{}\",\"fee\":\"{}\",\"price_impact_bps\":\"{}\"}}",
-            u128_to_str(amount_out),
-            u128_to_str(fee),
-            u128_to_str(impact_bps),
-        ),
-    )
-}
+        let reserve_a = get_state_u128(&format!("{}:reserve_a", prefix));
+        let reserve_b = get_state_u128(&format!("{}:reserve_b", prefix));
+        let total_lp = get_state_u128(&format!("{}:total_lp", prefix));
 
-/// Get pool info (read-only).
-/// Args: pool_id
-#[no_mangle]
-pub extern "C" fn get_pool() -> i32 {
-    let pool_id = match arg(0) {
-        Some(v) if !v.is_empty() => v,
-        _ => return fail("Missing pool_id"),
-    };
+        if total_lp == 0 {
+            return Err("Pool has no liquidity".into());
+        }
 
-    if !pool_exists(&pool_id) {
-        return fail("Pool not found");
-    }
-
-    let prefix = format!("pool:{}", pool_id);
-    let token_a = get_state_str(&format!("{}:token_a", prefix));
-    let token_b = get_state_str(&format!("{}:token_b", prefix));
-    let reserve_a = get_state_u128(&format!("{}:reserve_a", prefix));
-    let reserve_b = get_state_u128(&format!("{}:reserve_b", prefix));
-    let total_lp = get_state_u128(&format!("{}:total_lp", prefix));
-    let fee_bps = get_state_u128(&format!("{}:fee_bps", prefix));
-    let creator = get_state_str(&format!("{}:creator", prefix));
-    let last_trade = get_state_u64(&format!("{}:last_trade", prefix));
-
-    // Spot price: price_b = reserve_a * PRECISION / reserve_b (A per B)
-    let spot_price_scaled = if reserve_b > 0 {
-        reserve_a * PRECISION / reserve_b
-    } else {
-        0
-    };
+        // Proportional token amounts
+        let amount_a = lp_amount * reserve_a / total_lp;
+        let amount_b = lp_amount * reserve_b / total_lp;
+
+        // Slippage protection
+        if amount_a < min_amount_a || amount_b < min_amount_b {
+            return Err(format!(
+                "Slippage: would receive ({}, {}) but minimum is ({}, {})",
+                u128_to_str(amount_a),
+                u128_to_str(amount_b),
+                u128_to_str(min_amount_a),
+                u128_to_str(min_amount_b),
+            ));
+        }
 
-    ok_data(
-        "Pool found",
-        &format!(
-            "{{\"pool_id\":\"{}\",\"token_a\":\"{}\",\"token_b\":\"{}\",\"reserve_a\":\"{}\",\"reserve_b\":\"{}\",\"total_lp\":\"{}\",\"fee_bps\":\"{}\",\"creator\":\"{}\",\"last_trade\":\"{}\",\"spot_price_scaled\":\"{}\"}}",
-            json_escape(&pool_id),
-            json_escape(&token_a),
-            json_escape(&token_b),
-            u128_to_str(reserve_a),
-            u128_to_str(reserve_b),
-            u128_to_str(total_lp),
-            u128_to_str(fee_bps),
-            json_escape(&creator),
-            last_trade,
-            u128_to_str(spot_price_scaled),
-        ),
-    )
-}
+        // Update reserves
+        set_state_u128(&format!("{}:reserve_a", prefix), reserve_a - amount_a);
+        set_state_u128(&format!("{}:reserve_b", prefix), reserve_b - amount_b);
+        set_state_u128(&format!("{}:total_lp", prefix), total_lp - lp_amount);
 
-/// Get a swap quote without executing (read-only).
-/// Args: pool_id, token_in, amount_in
-#[no_mangle]
-pub extern "C" fn quote() -> i32 {
-    let pool_id = match arg(0) {
-        Some(v) if !v.is_empty() => v,
-        _ => return fail("Missing pool_id"),
-    };
-    let token_in = match arg(1) {
-        Some(v) if !v.is_empty() => v,
-        _ => return fail("Missing token_in"),
-    };
-    let amount_in = match arg(2) {
-        Some(v) => parse_u128(&v),
-        None => return fail("Missing amount_in"),
-    };
+        // Update LP shares
+        let new_lp = caller_lp - lp_amount;
+        if new_lp == 0 {
+            state::del(&lp_key);
+        } else {
+            set_state_u128(&lp_key, new_lp);
+        }
 
-    if !pool_exists(&pool_id) {
-        return fail("Pool not found");
-    }
+        pay_out(
+            &get_state_str(&format!("{}:token_a", prefix)),
+            &who,
+            amount_a,
+        );
+        pay_out(
+            &get_state_str(&format!("{}:token_b", prefix)),
+            &who,
+            amount_b,
+        );
+
+        LiquidityRemoved {
+            pool_id,
+            provider: Address::from(who),
+            amount_a,
+            amount_b,
+            lp_burned: lp_amount,
+        }
+        .emit();
+
+        Ok(Reply {
+            message: format!(
+                "Removed liquidity: {} LP tokens burned",
+                u128_to_str(lp_amount)
+            ),
+            data: Removed {
+                amount_a,
+                amount_b,
+                lp_burned: lp_amount,
+            },
+        })
+    }
+
+    /// Swap tokens via constant product AMM, with slippage and deadline
+    /// (unix seconds, 0 = none) protection.
+    #[entry]
+    pub fn swap(
+        pool_id: String,
+        token_in: String,
+        amount_in: u128,
+        min_amount_out: u128,
+        deadline: u64,
+    ) -> CallResult<Reply<Swapped>> {
+        require_init()?;
+        require_non_empty(&pool_id, "pool_id")?;
+        require_non_empty(&token_in, "token_in")?;
+        let prefix = require_pool(&pool_id)?;
+        if amount_in == 0 {
+            return Err("Amount must be > 0".into());
+        }
 
-    let prefix = format!("pool:{}", pool_id);
-    let pool_token_a = get_state_str(&format!("{}:token_a", prefix));
-    let pool_token_b = get_state_str(&format!("{}:token_b", prefix));
-    let reserve_a = get_state_u128(&format!("{}:reserve_a", prefix));
-    let reserve_b = get_state_u128(&format!("{}:reserve_b", prefix));
-    let fee_bps = get_state_u128(&format!("{}:fee_bps", prefix));
+        // MEV Protection: deadline check
+        let now = timestamp();
+        if deadline > 0 && now > deadline {
+            return Err(format!(
+                "Transaction expired: deadline {} < current {}",
+                deadline, now
+            ));
+        }
 
-    let is_a_to_b = token_in == pool_token_a;
-    let (reserve_in, reserve_out) = if is_a_to_b {
-        (reserve_a, reserve_b)
-    } else {
-        (reserve_b, reserve_a)
-    };
+        let pool_token_a = get_state_str(&format!("{}:token_a", prefix));
+        let pool_token_b = get_state_str(&format!("{}:token_b", prefix));
+        let reserve_a = get_state_u128(&format!("{}:reserve_a", prefix));
+        let reserve_b = get_state_u128(&format!("{}:reserve_b", prefix));
+        let fee_bps = get_state_u128(&format!("{}:fee_bps", prefix));
+
+        // Determine swap direction
+        let is_a_to_b = token_in == pool_token_a;
+        let is_b_to_a = token_in == pool_token_b;
+        if !is_a_to_b && !is_b_to_a {
+            return Err(format!(
+                "Token {} is not in pool (expected {} or {})",
+                token_in, pool_token_a, pool_token_b
+            ));
+        }
+
+        let (reserve_in, reserve_out, token_out) = if is_a_to_b {
+            (reserve_a, reserve_b, pool_token_b)
+        } else {
+            (reserve_b, reserve_a, pool_token_a)
+        };
+
+        // Deduct fee
+        let (amount_after_fee, fee) = deduct_fee(amount_in, fee_bps);
+
+        // Constant product output
+        let amount_out = compute_output(amount_after_fee, reserve_in, reserve_out);
+
+        // MEV Protection: slippage check
+        if amount_out < min_amount_out {
+            return Err(format!(
+                "Slippage exceeded: output {} < minimum {}",
+                u128_to_str(amount_out),
+                u128_to_str(min_amount_out)
+            ));
+        }
+        if amount_out == 0 {
+            return Err("Output amount is zero (insufficient liquidity)".into());
+        }
+        if amount_out >= reserve_out {
+            return Err("Insufficient liquidity for this trade".into());
+        }
 
-    let (after_fee, fee) = deduct_fee(amount_in, fee_bps);
-    let amount_out = compute_output(after_fee, reserve_in, reserve_out);
+        let trader = caller();
+        pay_in(&token_in, &trader, amount_in);
 
-    // Price impact
-    let spot_price_scaled = if reserve_in > 0 {
-        reserve_out * PRECISION / reserve_in
-    } else {
-        0
-    };
-    let exec_price_scaled = if amount_in > 0 {
-        amount_out * PRECISION / amount_in
-    } else {
-        0
-    };
-    let impact_bps = if spot_price_scaled > 0 && spot_price_scaled > exec_price_scaled {
-        ((spot_price_scaled - exec_price_scaled) * BPS_DENOMINATOR) / spot_price_scaled
-    } else {
-        0
-    };
+        // Update reserves — fee stays in pool for LPs
+        if is_a_to_b {
+            set_state_u128(&format!("{}:reserve_a", prefix), reserve_a + amount_in);
+            set_state_u128(&format!("{}:reserve_b", prefix), reserve_b - amount_out);
+        } else {
+            set_state_u128(&format!("{}:reserve_b", prefix), reserve_b + amount_in);
+            set_state_u128(&format!("{}:reserve_a", prefix), reserve_a - amount_out);
+        }
+
+        set_state_u64(&format!("{}:last_trade", prefix), now);
+        pay_out(&token_out, &trader, amount_out);
 
-    ok_data(
-        &format!(
-            "Quote: {} in -> {} out",
+        let message = format!(
+            "Swapped {} {} -> {} {}",
             u128_to_str(amount_in),
-            u128_to_str(amount_out)
-        ),
-        &format!(
-            "{{\"amount_out\":\"{}\",\"fee\":\"{}\",\"price_impact_bps\":\"{}\",\"spot_price_scaled\":\"{}\"}}",
+            token_in,
             u128_to_str(amount_out),
-            u128_to_str(fee),
-            u128_to_str(impact_bps),
-            u128_to_str(spot_price_scaled),
-        ),
-    )
-}
+            token_out
+        );
+        Swap {
+            pool_id,
+            trader: Address::from(trader),
+            token_in,
+            amount_in,
+            token_out,
+            amount_out,
+            fee,
+        }
+        .emit();
 
-/// Get caller's LP position in a pool (read-only).
-/// Args: pool_id
-#[no_mangle]
-pub extern "C" fn get_position() -> i32 {
-    let pool_id = match arg(0) {
-        Some(v) if !v.is_empty() => v,
-        _ => return fail("Missing pool_id"),
-    };
+        // Price impact (bps)
+        let price_impact_bps = (amount_out * BPS_DENOMINATOR) / reserve_out;
 
-    if !pool_exists(&pool_id) {
-        return fail("Pool not found");
+        Ok(Reply {
+            message,
+            data: Swapped {
+                amount_out,
+                fee,
+                price_impact_bps,
+            },
+        })
     }
 
-    let who = caller();
-    let lp_key = format!("lp:{}:{}", pool_id, who);
-    let shares = get_state_u128(&lp_key);
-
-    let prefix = format!("pool:{}", pool_id);
-    let reserve_a = get_state_u128(&format!("{}:reserve_a", prefix));
-    let reserve_b = get_state_u128(&format!("{}:reserve_b", prefix));
-    let total_lp = get_state_u128(&format!("{}:total_lp", prefix));
+    /// Get pool info.
+    #[view]
+    pub fn get_pool(pool_id: String) -> CallResult<PoolInfo> {
+        require_non_empty(&pool_id, "pool_id")?;
+        let prefix = require_pool(&pool_id)?;
 
-    let (amount_a, amount_b) = if total_lp > 0 && shares > 0 {
-        (shares * reserve_a / total_lp, shares * reserve_b / total_lp)
-    } else {
-        (0, 0)
-    };
+        let reserve_a = get_state_u128(&format!("{}:reserve_a", prefix));
+        let reserve_b = get_state_u128(&format!("{}:reserve_b", prefix));
 
-    let share_pct_bps = if total_lp > 0 {
-        (shares * BPS_DENOMINATOR) / total_lp
-    } else {
-        0
-    };
+        // Spot price: price_b = reserve_a * PRECISION / reserve_b (A per B)
+        let spot_price_scaled = (reserve_a * PRECISION).checked_div(reserve_b).unwrap_or(0);
+
+        Ok(PoolInfo {
+            token_a: get_state_str(&format!("{}:token_a", prefix)),
+            token_b: get_state_str(&format!("{}:token_b", prefix)),
+            reserve_a,
+            reserve_b,
+            total_lp: get_state_u128(&format!("{}:total_lp", prefix)),
+            fee_bps: get_state_u128(&format!("{}:fee_bps", prefix)),
+            creator: get_state_str(&format!("{}:creator", prefix)),
+            last_trade: get_state_u64(&format!("{}:last_trade", prefix)) as u128,
+            spot_price_scaled,
+            pool_id,
+        })
+    }
+
+    /// Get a swap quote without executing.
+    #[view]
+    pub fn quote(pool_id: String, token_in: String, amount_in: u128) -> CallResult<Quote> {
+        require_non_empty(&pool_id, "pool_id")?;
+        require_non_empty(&token_in, "token_in")?;
+        let prefix = require_pool(&pool_id)?;
+
+        let pool_token_a = get_state_str(&format!("{}:token_a", prefix));
+        let reserve_a = get_state_u128(&format!("{}:reserve_a", prefix));
+        let reserve_b = get_state_u128(&format!("{}:reserve_b", prefix));
+        let fee_bps = get_state_u128(&format!("{}:fee_bps", prefix));
 
-    ok_data(
-        "Position found",
-        &format!(
-            "{{\"lp_shares\":\"{}\",\"total_lp\":\"{}\",\"amount_a\":\"{}\",\"amount_b\":\"{}\",\"share_pct_bps\":\"{}\"}}",
-            u128_to_str(shares),
-            u128_to_str(total_lp),
-            u128_to_str(amount_a),
-            u128_to_str(amount_b),
-            u128_to_str(share_pct_bps),
-        ),
-    )
-}
+        let (reserve_in, reserve_out) = if token_in == pool_token_a {
+            (reserve_a, reserve_b)
+        } else {
+            (reserve_b, reserve_a)
+        };
+
+        let (after_fee, fee) = deduct_fee(amount_in, fee_bps);
+        let amount_out = compute_output(after_fee, reserve_in, reserve_out);
+
+        // Price impact
+        let spot_price_scaled = (reserve_out * PRECISION)
+            .checked_div(reserve_in)
+            .unwrap_or(0);
+        let exec_price_scaled = (amount_out * PRECISION).checked_div(amount_in).unwrap_or(0);
+        let price_impact_bps = if spot_price_scaled > exec_price_scaled {
+            ((spot_price_scaled - exec_price_scaled) * BPS_DENOMINATOR) / spot_price_scaled
+        } else {
+            0
+        };
 
-/// List all pools (read-only).
-#[no_mangle]
-pub extern "C" fn list_pools() -> i32 {
-    let count = get_state_u64("dex:pool_count");
-    if count == 0 {
-        return ok_data("0 pools", "[]");
+        Ok(Quote {
+            amount_out,
+            fee,
+            price_impact_bps,
+            spot_price_scaled,
+        })
     }
 
-    let mut data = String::from("[");
-    for i in 0..count {
-        let pid = get_state_str(&format!("pool_list:{}", i));
-        if pid.is_empty() {
-            continue;
-        }
-        if i > 0 {
-            data.push(',');
-        }
+    /// Get caller's LP position in a pool.
+    #[view]
+    pub fn get_position(pool_id: String) -> CallResult<Position> {
+        require_non_empty(&pool_id, "pool_id")?;
+        let prefix = require_pool(&pool_id)?;
+
+        let lp_key = format!("lp:{}:{}", pool_id, caller());
+        let lp_shares = get_state_u128(&lp_key);
 
-        let prefix = format!("pool:{}", pid);
-        let token_a = get_state_str(&format!("{}:token_a", prefix));
-        let token_b = get_state_str(&format!("{}:token_b", prefix));
         let reserve_a = get_state_u128(&format!("{}:reserve_a", prefix));
         let reserve_b = get_state_u128(&format!("{}:reserve_b", prefix));
         let total_lp = get_state_u128(&format!("{}:total_lp", prefix));
 
-        data.push_str(&format!(
-            "{{\"pool_id\":\"{}\",\"token_a\":\"{}\",\"token_b\":\"{}\",\"reserve_a\":\"{}\",\"reserve_b\":\"{}\",\"total_lp\":\"{}\"}}",
-            json_escape(&pid),
-            json_escape(&token_a),
-            json_escape(&token_b),
-            u128_to_str(reserve_a),
-            u128_to_str(reserve_b),
-            u128_to_str(total_lp),
-        ));
+        let (amount_a, amount_b) = if total_lp > 0 && lp_shares > 0 {
+            (
+                lp_shares * reserve_a / total_lp,
+                lp_shares * reserve_b / total_lp,
+            )
+        } else {
+            (0, 0)
+        };
+
+        let share_pct_bps = (lp_shares * BPS_DENOMINATOR)
+            .checked_div(total_lp)
+            .unwrap_or(0);
+
+        Ok(Position {
+            lp_shares,
+            total_lp,
+            amount_a,
+            amount_b,
+            share_pct_bps,
+        })
+    }
+
+    /// List all pools.
+    #[view]
+    pub fn list_pools() -> Vec<PoolSummary> {
+        let count = get_state_u64("dex:pool_count");
+        (0..count)
+            .map(|i| get_state_str(&format!("pool_list:{}", i)))
+            .filter(|pid| !pid.is_empty())
+            .map(|pool_id| {
+                let prefix = format!("pool:{}", pool_id);
+                PoolSummary {
+                    token_a: get_state_str(&format!("{}:token_a", prefix)),
+                    token_b: get_state_str(&format!("{}:token_b", prefix)),
+                    reserve_a: get_state_u128(&format!("{}:reserve_a", prefix)),
+                    reserve_b: get_state_u128(&format!("{}:reserve_b", prefix)),
+                    total_lp: get_state_u128(&format!("{}:total_lp", prefix)),
+                    pool_id,
+                }
+            })
+            .collect()
     }
-    data.push(']');
+}
 
-    ok_data(&format!("{} pools", count), &data)
+// ─────────────────────────────────────────────────────────────────
+// Tests (native, against the `los_sdk::mock` host)
+// ─────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::dex::exports;
+    use los_sdk::mock::{self, MOCK_CONTRACT_ADDRESS as DEX};
+
+    /// Initialized DEX with a 1:1 LOS/TK pool created by LOSalice, who
+    /// sends the LOS with the call and approved the DEX for her TK.
+    fn with_pool() -> std::string::String {
+        mock::reset();
+        mock::set_caller("LOSalice");
+        assert!(mock::call(exports::init, &[]).success);
+        mock::set_token_balance("TK", "LOSalice", 1_000_000);
+        mock::set_token_allowance("TK", "LOSalice", DEX, 1_000_000);
+        mock::set_call_value(1_000_000);
+        let r = mock::call(exports::create_pool, &["LOS", "TK", "1000000", "1000000"]);
+        assert!(r.success, "{}", r.output);
+        los_contracts::make_pool_id("LOS", "TK")
+    }
+
+    #[test]
+    fn test_create_pool_rejects_duplicates() {
+        with_pool();
+        let r = mock::call(exports::create_pool, &["TK", "LOS", "1000000", "1000000"]);
+        assert!(!r.success);
+        assert!(r.output.contains("already exists"), "{}", r.output);
+    }
+
+    #[test]
+    fn test_create_pool_takes_both_legs() {
+        with_pool();
+        assert_eq!(mock::token_balance("TK", DEX), 1_000_000);
+        assert_eq!(mock::token_balance("TK", "LOSalice"), 0);
+        assert_eq!(mock::state_str("dex:los_held").as_deref(), Some("1000000"));
+    }
+
+    #[test]
+    fn test_create_pool_without_allowance_is_reverted() {
+        mock::reset();
+        mock::set_caller("LOSalice");
+        assert!(mock::call(exports::init, &[]).success);
+        mock::set_token_balance("TK", "LOSalice", 1_000_000);
+        mock::set_call_value(1_000_000);
+        let r = mock::call(exports::create_pool, &["LOS", "TK", "1000000", "1000000"]);
+        assert!(r.aborted.unwrap().contains("allowance"));
+        assert_eq!(mock::state_str("dex:los_held"), None);
+        assert_eq!(mock::state_str("dex:pool_count").as_deref(), Some("0"));
+    }
+
+    #[test]
+    fn test_swap_updates_reserves() {
+        let pool = with_pool();
+        mock::set_caller("LOSbob");
+        mock::set_call_value(1_000);
+        let r = mock::call(exports::swap, &[&pool, "LOS", "1000", "1", "0"]);
+        assert!(r.success, "{}", r.output);
+        assert!(r.events.iter().any(|e| e.event_type == "Swap"));
+
+        let key = std::format!("pool:{}:reserve_a", pool);
+        assert_eq!(mock::state_str(&key).as_deref(), Some("1001000"));
+        // 1000 LOS in, 0.3% fee, against 1M/1M reserves
+        assert_eq!(mock::token_balance("TK", "LOSbob"), 996);
+    }
+
+    #[test]
+    fn test_swap_pays_out_los_for_tokens() {
+        let pool = with_pool();
+        mock::set_caller("LOSbob");
+        mock::set_token_balance("TK", "LOSbob", 1_000);
+        // Approved within the swap call itself
+        mock::set_call_token_allowance("TK", 1_000);
+        let r = mock::call(exports::swap, &[&pool, "TK", "1000", "1", "0"]);
+        assert!(r.success, "{}", r.output);
+        assert_eq!(r.transfers, std::vec![("LOSbob".into(), 996)]);
+        assert_eq!(mock::token_balance("TK", "LOSbob"), 0);
+        assert_eq!(mock::state_str("dex:los_held").as_deref(), Some("999004"));
+    }
+
+    #[test]
+    fn test_swap_without_payment_is_reverted() {
+        let pool = with_pool();
+        mock::set_caller("LOSbob");
+        let r = mock::call(exports::swap, &[&pool, "LOS", "1000", "1", "0"]);
+        assert!(r.aborted.unwrap().contains("Send 1000 CIL"));
+        let key = std::format!("pool:{}:reserve_a", pool);
+        assert_eq!(mock::state_str(&key).as_deref(), Some("1000000"));
+        assert_eq!(mock::token_balance("TK", "LOSbob"), 0);
+    }
+
+    #[test]
+    fn test_storage_deposit_is_not_a_payment() {
+        let pool = with_pool();
+        mock::set_caller("LOSbob");
+        // CIL locked for storage sits in the balance above `dex:los_held`
+        mock::set_balance(mock::balance() + 5_000);
+        let r = mock::call(exports::swap, &[&pool, "LOS", "1000", "1", "0"]);
+        assert!(r.aborted.unwrap().contains("Send 1000 CIL"));
+    }
+
+    #[test]
+    fn test_swap_respects_deadline_and_slippage() {
+        let pool = with_pool();
+        mock::set_timestamp(2_000);
+        let r = mock::call(exports::swap, &[&pool, "LOS", "1000", "1", "1999"]);
+        assert!(r.output.contains("expired"), "{}", r.output);
+
+        let r = mock::call(exports::swap, &[&pool, "LOS", "1000", "1000", "0"]);
+        assert!(r.output.contains("Slippage exceeded"), "{}", r.output);
+    }
+
+    #[test]
+    fn test_add_liquidity_refunds_surplus_los() {
+        let pool = with_pool();
+        mock::set_caller("LOSbob");
+        mock::set_token_balance("TK", "LOSbob", 500);
+        mock::set_token_allowance("TK", "LOSbob", DEX, 500);
+        mock::set_call_value(800);
+        let r = mock::call(exports::add_liquidity, &[&pool, "800", "500", "1"]);
+        assert!(r.success, "{}", r.output);
+        assert_eq!(r.transfers, std::vec![("LOSbob".into(), 300)]);
+        assert_eq!(mock::token_balance("TK", "LOSbob"), 0);
+    }
+
+    #[test]
+    fn test_remove_liquidity_pays_out_both_legs() {
+        let pool = with_pool();
+        let r = mock::call(exports::remove_liquidity, &[&pool, "499000", "1", "1"]);
+        assert!(r.success, "{}", r.output);
+        assert_eq!(r.transfers, std::vec![("LOSalice".into(), 499_000)]);
+        assert_eq!(mock::token_balance("TK", "LOSalice"), 499_000);
+    }
+
+    #[test]
+    fn test_remove_liquidity_requires_shares() {
+        let pool = with_pool();
+        mock::set_caller("LOSbob");
+        let r = mock::call(exports::remove_liquidity, &[&pool, "1", "0", "0"]);
+        assert!(r.output.contains("Insufficient LP tokens"), "{}", r.output);
+    }
 }
@@ -35,85 +35,25 @@
 //! (not LE bytes) to avoid `String::from_utf8_lossy` corruption
 //! in `Contract.state: BTreeMap<String, String>`.
 
-// ─────────────────────────────────────────────────────────────────
-// Shared pure helper functions (tested natively, duplicated in bins)
-// ─────────────────────────────────────────────────────────────────
-// These helpers mirror the logic inside usp01_token.rs and dex_amm.rs.
-// Unit tests below verify correctness of all pure arithmetic, string
-// conversion, and JSON formatting used by both WASM contracts.
-// ─────────────────────────────────────────────────────────────────
+#![no_std]
 
-/// Parse a decimal string to u128 with overflow protection.
-/// Returns 0 on empty string, non-digit chars, or overflow.
-pub fn parse_u128(s: &str) -> u128 {
-    let mut result: u128 = 0;
-    for b in s.as_bytes() {
-        if *b >= b'0' && *b <= b'9' {
-            result = match result.checked_mul(10) {
-                Some(v) => v,
-                None => return 0,
-            };
-            result = match result.checked_add((*b - b'0') as u128) {
-                Some(v) => v,
-                None => return 0,
-            };
-        } else {
-            return 0;
-        }
-    }
-    result
-}
+extern crate alloc;
+#[cfg(test)]
+extern crate std;
 
-/// Parse a decimal string to u64 with overflow protection.
-pub fn parse_u64(s: &str) -> u64 {
-    let mut result: u64 = 0;
-    for b in s.as_bytes() {
-        if *b >= b'0' && *b <= b'9' {
-            result = match result.checked_mul(10) {
-                Some(v) => v,
-                None => return 0,
-            };
-            result = match result.checked_add((*b - b'0') as u64) {
-                Some(v) => v,
-                None => return 0,
-            };
-        } else {
-            return 0;
-        }
-    }
-    result
-}
+use alloc::format;
+use alloc::string::String;
 
-/// Convert u128 to decimal string without allocation (returns owned String).
-pub fn u128_to_str(val: u128) -> String {
-    if val == 0 {
-        return String::from("0");
-    }
-    let mut buf = [0u8; 40];
-    let mut pos = buf.len();
-    let mut v = val;
-    while v > 0 {
-        pos -= 1;
-        buf[pos] = b'0' + (v % 10) as u8;
-        v /= 10;
-    }
-    // SAFETY: we only write ASCII digits
-    String::from_utf8(buf[pos..].to_vec()).unwrap_or_default()
-}
+// ─────────────────────────────────────────────────────────────────
+// Shared pure helper functions (tested natively, used by the bins)
+// ─────────────────────────────────────────────────────────────────
+// The numeric/string codec lives in `los_sdk::codec` (it also backs
+// `#[los_sdk::contract]` argument decoding); it is re-exported here so
+// the tests below keep pinning its behaviour. The DEX math and state
+// key builders are used directly by usp01_token.rs and dex_amm.rs.
+// ─────────────────────────────────────────────────────────────────
 
-/// Escape a string for JSON output (double-quote, backslash, newline).
-pub fn json_escape(s: &str) -> String {
-    let mut out = String::with_capacity(s.len() + 8);
-    for c in s.chars() {
-        match c {
-            '"' => out.push_str("\\\""),
-            '\\' => out.push_str("\\\\"),
-            '\n' => out.push_str("\\n"),
-            _ => out.push(c),
-        }
-    }
-    out
-}
+pub use los_sdk::codec::{json_escape, parse_u128, parse_u64, u128_to_str};
 
 /// Integer square root via Newton's method. Returns floor(√n).
 /// Used by DEX AMM for initial LP token calculation.
@@ -183,6 +123,7 @@ pub fn allow_key(owner: &str, spender: &str) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::{vec, vec::Vec};
 
     // ── parse_u128 ──────────────────────────────────────────────
 
@@ -198,7 +139,10 @@ mod tests {
 
     #[test]
     fn test_parse_u128_large() {
-        assert_eq!(parse_u128("340282366920938463463374607431768211455"), u128::MAX);
+        assert_eq!(
+            parse_u128("340282366920938463463374607431768211455"),
+            u128::MAX
+        );
     }
 
     #[test]
@@ -274,7 +218,10 @@ mod tests {
 
     #[test]
     fn test_u128_to_str_max() {
-        assert_eq!(u128_to_str(u128::MAX), "340282366920938463463374607431768211455");
+        assert_eq!(
+            u128_to_str(u128::MAX),
+            "340282366920938463463374607431768211455"
+        );
     }
 
     #[test]
@@ -388,7 +335,11 @@ mod tests {
         let amount_a: u128 = 500;
         let amount_b: u128 = 500;
         let lp = isqrt(amount_a * amount_b); // sqrt(250000) = 500
-        assert!(lp <= 1000, "LP tokens {} should be <= MINIMUM_LIQUIDITY", lp);
+        assert!(
+            lp <= 1000,
+            "LP tokens {} should be <= MINIMUM_LIQUIDITY",
+            lp
+        );
     }
 
     // ── compute_output ──────────────────────────────────────────
@@ -435,7 +386,7 @@ mod tests {
     fn test_compute_output_large_reserves() {
         // DEX-scale: billions of CIL in reserves
         let out = compute_output(
-            1_000_000_000_000, // 10 LOS input
+            1_000_000_000_000,       // 10 LOS input
             100_000_000_000_000_000, // 1M LOS reserve
             50_000_000_000_000_000,  // 500K LOS reserve
         );
@@ -493,7 +444,10 @@ mod tests {
     fn test_make_pool_id_sorted() {
         let id1 = make_pool_id("LOS", "TOKEN_A");
         let id2 = make_pool_id("TOKEN_A", "LOS");
-        assert_eq!(id1, id2, "Pool ID must be deterministic regardless of order");
+        assert_eq!(
+            id1, id2,
+            "Pool ID must be deterministic regardless of order"
+        );
     }
 
     #[test]
@@ -538,7 +492,12 @@ mod tests {
         let out = compute_output(after_fee, reserve_in, reserve_out);
 
         // Output should be less than input (slippage + fee)
-        assert!(out < amount_in, "Output {} should be < input {}", out, amount_in);
+        assert!(
+            out < amount_in,
+            "Output {} should be < input {}",
+            out,
+            amount_in
+        );
         assert!(out > 0, "Output should be positive");
 
         // Step 3: verify LP tokens for pool creation
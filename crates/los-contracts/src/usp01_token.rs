@@ -43,804 +43,663 @@
 //! | `wrap_mint`      | to, amount, proof                                  |
 //! | `wrap_burn`      | amount, destination                                |
 //!
+//! Exports, argument decoding and the embedded ABI are generated by
+//! `#[los_sdk::contract]`; see `los_sdk::codec` for the response envelope.
+//!
 //! ## Compilation
 //! ```bash
 //! cargo build --target wasm32-unknown-unknown --release \
-//!     -p los-contract-examples --bin usp01_token --features sdk
+//!     --manifest-path crates/los-contracts/Cargo.toml --bin usp01_token
 //! ```
 
 #![no_std]
-#![no_main]
+#![cfg_attr(not(test), no_main)]
 
 extern crate alloc;
-extern crate los_sdk;
-
-use alloc::format;
-use alloc::string::String;
-use los_sdk::*;
-
-// ─────────────────────────────────────────────────────────────
-// HELPERS
-// ─────────────────────────────────────────────────────────────
-
-/// Parse a u128 from a decimal string. Returns 0 on failure.
-fn parse_u128(s: &str) -> u128 {
-    let mut result: u128 = 0;
-    for b in s.as_bytes() {
-        if *b < b'0' || *b > b'9' {
-            return 0;
-        }
-        let digit = (*b - b'0') as u128;
-        result = match result.checked_mul(10) {
-            Some(v) => v,
-            None => return 0,
-        };
-        result = match result.checked_add(digit) {
-            Some(v) => v,
-            None => return 0,
-        };
+#[cfg(test)]
+extern crate std;
+
+#[los_sdk::contract(name = "USP-01")]
+pub mod usp01 {
+    use alloc::format;
+    use alloc::string::String;
+    use los_contracts::{allow_key, bal_key, parse_u128, parse_u64, u128_to_str};
+    use los_sdk::codec::Address;
+    use los_sdk::{caller, log, self_address, state};
+
+    type CallResult<T> = Result<T, &'static str>;
+
+    // ─────────────────────────────────────────────────────────────
+    // HELPERS
+    // ─────────────────────────────────────────────────────────────
+
+    /// Get balance for an address (stored as decimal string).
+    fn get_balance(addr: &str) -> u128 {
+        parse_u128(&state::get_str(&bal_key(addr)).unwrap_or_default())
     }
-    result
-}
 
-/// Return a u128 as decimal string.
-fn u128_to_str(val: u128) -> String {
-    if val == 0 {
-        return String::from("0");
+    /// Set balance for an address (stored as decimal string).
+    fn set_balance(addr: &str, amount: u128) {
+        state::set_str(&bal_key(addr), &u128_to_str(amount));
     }
-    let mut digits = alloc::vec::Vec::new();
-    let mut v = val;
-    while v > 0 {
-        digits.push(b'0' + (v % 10) as u8);
-        v /= 10;
+
+    /// Get allowance for (owner, spender) (stored as decimal string).
+    fn get_allowance(owner: &str, spender: &str) -> u128 {
+        parse_u128(&state::get_str(&allow_key(owner, spender)).unwrap_or_default())
     }
-    digits.reverse();
-    // Safety: all bytes are ASCII digits
-    unsafe { String::from_utf8_unchecked(digits) }
-}
 
-/// Build balance state key.
-fn bal_key(addr: &str) -> String {
-    format!("bal:{}", addr)
-}
+    /// Set allowance for (owner, spender) (stored as decimal string).
+    fn set_allowance(owner: &str, spender: &str, amount: u128) {
+        state::set_str(&allow_key(owner, spender), &u128_to_str(amount));
+    }
 
-/// Build allowance state key.
-fn allow_key(owner: &str, spender: &str) -> String {
-    format!("allow:{}:{}", owner, spender)
-}
+    /// Get total supply from state (stored as decimal string).
+    fn get_total_supply() -> u128 {
+        parse_u128(&state::get_str("usp01:total_supply").unwrap_or_default())
+    }
 
-/// Get balance for an address (stored as decimal string).
-fn get_balance(addr: &str) -> u128 {
-    parse_u128(&state::get_str(&bal_key(addr)).unwrap_or_default())
-}
+    /// Set total supply in state (stored as decimal string).
+    fn set_total_supply(val: u128) {
+        state::set_str("usp01:total_supply", &u128_to_str(val));
+    }
 
-/// Set balance for an address (stored as decimal string).
-fn set_balance(addr: &str, amount: u128) {
-    state::set_str(&bal_key(addr), &u128_to_str(amount));
-}
+    /// Fail unless `init` has run.
+    fn require_init() -> CallResult<()> {
+        if state::get_str("usp01:init").is_some_and(|v| v == "1") {
+            Ok(())
+        } else {
+            Err("Contract not initialized")
+        }
+    }
 
-/// Get allowance for (owner, spender) (stored as decimal string).
-fn get_allowance(owner: &str, spender: &str) -> u128 {
-    parse_u128(&state::get_str(&allow_key(owner, spender)).unwrap_or_default())
-}
+    /// Fail unless this token is a wrapped asset.
+    fn require_wrapped() -> CallResult<()> {
+        if state::get_str("usp01:is_wrapped").unwrap_or_default() == "1" {
+            Ok(())
+        } else {
+            Err("not a wrapped asset")
+        }
+    }
 
-/// Set allowance for (owner, spender) (stored as decimal string).
-fn set_allowance(owner: &str, spender: &str, amount: u128) {
-    state::set_str(&allow_key(owner, spender), &u128_to_str(amount));
-}
+    /// Caller address, which the host must always provide.
+    fn require_caller() -> CallResult<Address> {
+        let who = caller();
+        if who.is_empty() {
+            return Err("caller address not available");
+        }
+        Ok(Address::from(who))
+    }
+
+    /// Move `amount` between balances, checking both sides.
+    fn move_balance(
+        from: &str,
+        to: &str,
+        amount: u128,
+        low_balance: &'static str,
+    ) -> CallResult<()> {
+        let from_bal = get_balance(from);
+        if from_bal < amount {
+            return Err(low_balance);
+        }
+        // Debit sender (checked_sub for defense-in-depth)
+        let new_from = from_bal.checked_sub(amount).ok_or("arithmetic underflow")?;
+        set_balance(from, new_from);
+
+        // Credit recipient (checked_add prevents u128 overflow)
+        let new_to = get_balance(to)
+            .checked_add(amount)
+            .ok_or("arithmetic overflow")?;
+        set_balance(to, new_to);
+        Ok(())
+    }
+
+    /// Debit `amount` from `from` and reduce total supply. Returns the new supply.
+    fn burn_from(from: &str, amount: u128, low_balance: &'static str) -> CallResult<u128> {
+        let bal = get_balance(from);
+        if bal < amount {
+            return Err(low_balance);
+        }
+        let new_bal = bal.checked_sub(amount).ok_or("arithmetic underflow")?;
+        set_balance(from, new_bal);
 
-/// Get total supply from state (stored as decimal string).
-fn get_total_supply() -> u128 {
-    parse_u128(&state::get_str("usp01:total_supply").unwrap_or_default())
-}
+        // Reduce total supply permanently
+        let new_supply = get_total_supply().saturating_sub(amount);
+        set_total_supply(new_supply);
+        Ok(new_supply)
+    }
 
-/// Set total supply in state (stored as decimal string).
-fn set_total_supply(val: u128) {
-    state::set_str("usp01:total_supply", &u128_to_str(val));
-}
+    // ─────────────────────────────────────────────────────────────
+    // EVENTS & RETURN VALUES
+    // ─────────────────────────────────────────────────────────────
 
-/// Check if contract is initialized.
-fn is_initialized() -> bool {
-    state::get_str("usp01:init").map_or(false, |v| v == "1")
-}
+    #[event(name = "USP01:Init")]
+    pub struct Init {
+        name: String,
+        symbol: String,
+        decimals: u64,
+        total_supply: u128,
+        creator: Address,
+    }
 
-/// Fail with JSON error response.
-fn fail(msg: &str) -> i32 {
-    set_return_str(&format!(r#"{{"success":false,"msg":"{}"}}"#, msg));
-    1
-}
+    /// Emitted by `transfer` and `transfer_from` (which also sets `spender`).
+    #[event(name = "USP01:Transfer")]
+    pub struct Transfer {
+        from: Address,
+        to: Address,
+        amount: u128,
+        spender: Option<Address>,
+    }
 
-/// Succeed with JSON success response.
-fn ok(msg: &str) -> i32 {
-    set_return_str(&format!(r#"{{"success":true,"msg":"{}"}}"#, msg));
-    0
-}
+    #[event(name = "USP01:Approval")]
+    pub struct Approval {
+        owner: Address,
+        spender: Address,
+        amount: u128,
+    }
+
+    #[event(name = "USP01:Burn")]
+    pub struct Burn {
+        from: Address,
+        amount: u128,
+        new_supply: u128,
+    }
+
+    #[event(name = "USP01:WrapMint")]
+    pub struct WrapMint {
+        to: Address,
+        amount: u128,
+        proof: String,
+        new_supply: u128,
+    }
+
+    #[event(name = "USP01:WrapBurn")]
+    pub struct WrapBurn {
+        from: Address,
+        amount: u128,
+        destination: String,
+        new_supply: u128,
+    }
+
+    #[json]
+    pub struct Initialized {
+        name: String,
+        symbol: String,
+        total_supply: u128,
+        owner: Address,
+        contract: String,
+    }
+
+    #[json]
+    pub struct Burned {
+        burned: u128,
+        new_supply: u128,
+    }
+
+    #[json]
+    pub struct Balance {
+        account: Address,
+        balance: u128,
+    }
+
+    #[json]
+    pub struct Allowance {
+        owner: Address,
+        spender: Address,
+        allowance: u128,
+    }
+
+    #[json]
+    pub struct Supply {
+        total_supply: u128,
+    }
+
+    #[json]
+    pub struct TokenInfo {
+        name: String,
+        symbol: String,
+        decimals: u64,
+        total_supply: u128,
+        is_wrapped: bool,
+        wrapped_origin: String,
+        max_supply: u128,
+        bridge_operator: String,
+        owner: String,
+        contract: String,
+        standard: &'static str,
+    }
+
+    #[json]
+    pub struct Minted {
+        to: Address,
+        amount: u128,
+        proof: String,
+    }
+
+    #[json]
+    pub struct Redeemed {
+        from: Address,
+        amount: u128,
+        destination: String,
+    }
+
+    // ─────────────────────────────────────────────────────────────
+    // INIT — Called once at deployment
+    // ─────────────────────────────────────────────────────────────
+
+    /// Initialize a new USP-01 token; the caller receives the full supply.
+    ///
+    /// `is_wrapped` tokens must name their `wrapped_origin` chain and a
+    /// `bridge_operator`; `max_supply` 0 means uncapped.
+    #[allow(clippy::too_many_arguments)]
+    #[entry]
+    pub fn init(
+        name: String,
+        symbol: String,
+        decimals: u64,
+        total_supply: u128,
+        is_wrapped: Option<bool>,
+        wrapped_origin: Option<String>,
+        max_supply: Option<u128>,
+        bridge_operator: Option<Address>,
+    ) -> CallResult<Initialized> {
+        if state::get_str("usp01:init").is_some_and(|v| v == "1") {
+            return Err("Already initialized");
+        }
+        if name.is_empty() || name.len() > 64 {
+            return Err("name required (1-64 chars)");
+        }
+        if symbol.is_empty() || symbol.len() > 8 {
+            return Err("symbol required (1-8 chars)");
+        }
+        if decimals > 18 {
+            return Err("decimals must be 0-18");
+        }
+        if total_supply == 0 {
+            return Err("total_supply must be > 0");
+        }
 
-/// Succeed with JSON data response.
-fn ok_data(data: &str) -> i32 {
-    set_return_str(&format!(r#"{{"success":true,"data":{}}}"#, data));
-    0
-}
+        let is_wrapped = is_wrapped.unwrap_or(false);
+        let wrapped_origin = wrapped_origin.unwrap_or_default();
+        let max_supply = max_supply.unwrap_or(0);
+        let bridge_operator = bridge_operator.unwrap_or_default();
 
-/// Escape a string for JSON (minimal — handles quotes and backslashes).
-fn json_escape(s: &str) -> String {
-    let mut out = String::with_capacity(s.len());
-    for c in s.chars() {
-        match c {
-            '"' => out.push_str("\\\""),
-            '\\' => out.push_str("\\\\"),
-            '\n' => out.push_str("\\n"),
-            '\r' => out.push_str("\\r"),
-            '\t' => out.push_str("\\t"),
-            _ => out.push(c),
+        // Validate
+        if max_supply > 0 && total_supply > max_supply {
+            return Err("total_supply exceeds max_supply");
+        }
+        if is_wrapped && wrapped_origin.is_empty() {
+            return Err("wrapped tokens must specify wrapped_origin");
+        }
+        if is_wrapped && bridge_operator.is_empty() {
+            return Err("wrapped tokens must specify bridge_operator");
         }
-    }
-    out
-}
 
-// ─────────────────────────────────────────────────────────────
-// INIT — Called once at deployment
-// ─────────────────────────────────────────────────────────────
-
-/// Initialize a new USP-01 token.
-///
-/// Args:
-///   0: name (string, 1-64 chars)
-///   1: symbol (string, 1-8 chars)
-///   2: decimals (u8, 0-18)
-///   3: total_supply (u128 decimal string)
-///   4: is_wrapped ("1" or "0", optional, default "0")
-///   5: wrapped_origin (string, optional)
-///   6: max_supply (u128 decimal string, optional, default "0")
-///   7: bridge_operator (address, optional)
-#[no_mangle]
-pub extern "C" fn init() -> i32 {
-    if is_initialized() {
-        return fail("Already initialized");
-    }
-
-    // Parse required args
-    let name = match arg(0) {
-        Some(n) if !n.is_empty() && n.len() <= 64 => n,
-        _ => return fail("name required (1-64 chars)"),
-    };
-    let symbol = match arg(1) {
-        Some(s) if !s.is_empty() && s.len() <= 8 => s,
-        _ => return fail("symbol required (1-8 chars)"),
-    };
-    let decimals_str = arg(2).unwrap_or_default();
-    let decimals = match decimals_str.parse::<u64>() {
-        Ok(d) if d <= 18 => d,
-        _ => return fail("decimals must be 0-18"),
-    };
-    let total_supply_str = arg(3).unwrap_or_default();
-    let total_supply = parse_u128(&total_supply_str);
-    if total_supply == 0 {
-        return fail("total_supply must be > 0");
-    }
-
-    // Parse optional args
-    let is_wrapped = arg(4).unwrap_or_default() == "1";
-    let wrapped_origin = arg(5).unwrap_or_default();
-    let max_supply_str = arg(6).unwrap_or_default();
-    let max_supply = if max_supply_str.is_empty() {
-        0u128
-    } else {
-        parse_u128(&max_supply_str)
-    };
-    let bridge_operator = arg(7).unwrap_or_default();
-
-    // Validate
-    if max_supply > 0 && total_supply > max_supply {
-        return fail("total_supply exceeds max_supply");
-    }
-    if is_wrapped && wrapped_origin.is_empty() {
-        return fail("wrapped tokens must specify wrapped_origin");
-    }
-    if is_wrapped && bridge_operator.is_empty() {
-        return fail("wrapped tokens must specify bridge_operator");
-    }
-
-    let creator = caller();
-    if creator.is_empty() {
-        return fail("caller address not available");
-    }
-
-    // Store metadata
-    state::set_str("usp01:init", "1");
-    state::set_str("usp01:name", &name);
-    state::set_str("usp01:symbol", &symbol);
-    state::set_str("usp01:decimals", &format!("{}", decimals));
-    set_total_supply(total_supply);
-    state::set_str("usp01:is_wrapped", if is_wrapped { "1" } else { "0" });
-    state::set_str("usp01:wrapped_origin", &wrapped_origin);
-    state::set_str("usp01:max_supply", &u128_to_str(max_supply));
-    state::set_str("usp01:bridge_operator", &bridge_operator);
-    state::set_str("usp01:owner", &creator);
-
-    // Assign total supply to creator
-    set_balance(&creator, total_supply);
-
-    // Emit init event
-    event::emit(
-        "USP01:Init",
-        &format!(
-            r#"{{"name":"{}","symbol":"{}","decimals":{},"total_supply":"{}","creator":"{}"}}"#,
-            json_escape(&name),
-            json_escape(&symbol),
+        let creator = require_caller()?;
+
+        // Store metadata
+        state::set_str("usp01:init", "1");
+        state::set_str("usp01:name", &name);
+        state::set_str("usp01:symbol", &symbol);
+        state::set_str("usp01:decimals", &format!("{}", decimals));
+        set_total_supply(total_supply);
+        state::set_str("usp01:is_wrapped", if is_wrapped { "1" } else { "0" });
+        state::set_str("usp01:wrapped_origin", &wrapped_origin);
+        state::set_str("usp01:max_supply", &u128_to_str(max_supply));
+        state::set_str("usp01:bridge_operator", &bridge_operator);
+        state::set_str("usp01:owner", &creator);
+
+        // Assign total supply to creator
+        set_balance(&creator, total_supply);
+
+        Init {
+            name: name.clone(),
+            symbol: symbol.clone(),
             decimals,
-            u128_to_str(total_supply),
-            json_escape(&creator)
-        ),
-    );
-
-    log(&format!(
-        "USP-01 token initialized: {} ({}) supply={}",
-        name,
-        symbol,
-        u128_to_str(total_supply)
-    ));
-
-    set_return_str(&format!(
-        r#"{{"success":true,"name":"{}","symbol":"{}","total_supply":"{}","owner":"{}","contract":"{}"}}"#,
-        json_escape(&name),
-        json_escape(&symbol),
-        u128_to_str(total_supply),
-        json_escape(&creator),
-        json_escape(&self_address())
-    ));
-    0
-}
+            total_supply,
+            creator: creator.clone(),
+        }
+        .emit();
+
+        log(&format!(
+            "USP-01 token initialized: {} ({}) supply={}",
+            name,
+            symbol,
+            u128_to_str(total_supply)
+        ));
+
+        Ok(Initialized {
+            name,
+            symbol,
+            total_supply,
+            owner: creator,
+            contract: self_address(),
+        })
+    }
+
+    // ─────────────────────────────────────────────────────────────
+    // TRANSFER — Send tokens from caller to recipient
+    // ─────────────────────────────────────────────────────────────
+
+    /// Transfer tokens from caller to recipient.
+    #[entry]
+    pub fn transfer(to: Address, amount: u128) -> CallResult<Transfer> {
+        require_init()?;
+        if amount == 0 {
+            return Err("amount must be > 0");
+        }
+        let from = require_caller()?;
+        if from == to {
+            return Err("cannot transfer to self");
+        }
 
-// ─────────────────────────────────────────────────────────────
-// TRANSFER — Send tokens from caller to recipient
-// ─────────────────────────────────────────────────────────────
-
-/// Transfer tokens from caller to recipient.
-///
-/// Args:
-///   0: to (recipient address)
-///   1: amount (u128 decimal string)
-#[no_mangle]
-pub extern "C" fn transfer() -> i32 {
-    if !is_initialized() {
-        return fail("Contract not initialized");
-    }
-
-    let to = match arg(0) {
-        Some(a) if !a.is_empty() => a,
-        _ => return fail("recipient address required"),
-    };
-    let amount_str = arg(1).unwrap_or_default();
-    let amount = parse_u128(&amount_str);
-    if amount == 0 {
-        return fail("amount must be > 0");
-    }
-
-    let from = caller();
-    if from.is_empty() {
-        return fail("caller address not available");
-    }
-    if from == to {
-        return fail("cannot transfer to self");
-    }
-
-    let from_bal = get_balance(&from);
-    if from_bal < amount {
-        return fail("insufficient balance");
-    }
-
-    // Debit sender (checked_sub for defense-in-depth)
-    let new_from = match from_bal.checked_sub(amount) {
-        Some(v) => v,
-        None => return fail("arithmetic underflow"),
-    };
-    set_balance(&from, new_from);
-
-    // Credit recipient (checked_add prevents u128 overflow)
-    let to_bal = get_balance(&to);
-    let new_to = match to_bal.checked_add(amount) {
-        Some(v) => v,
-        None => return fail("arithmetic overflow"),
-    };
-    set_balance(&to, new_to);
-
-    // Emit transfer event
-    event::emit(
-        "USP01:Transfer",
-        &format!(
-            r#"{{"from":"{}","to":"{}","amount":"{}"}}"#,
-            json_escape(&from),
-            json_escape(&to),
-            u128_to_str(amount)
-        ),
-    );
-
-    set_return_str(&format!(
-        r#"{{"success":true,"from":"{}","to":"{}","amount":"{}"}}"#,
-        json_escape(&from),
-        json_escape(&to),
-        u128_to_str(amount)
-    ));
-    0
-}
+        move_balance(&from, &to, amount, "insufficient balance")?;
 
-// ─────────────────────────────────────────────────────────────
-// APPROVE — Set spending allowance
-// ─────────────────────────────────────────────────────────────
-
-/// Approve spender to spend up to `amount` on behalf of caller.
-///
-/// Args:
-///   0: spender (address)
-///   1: amount (u128 decimal string, 0 to revoke)
-#[no_mangle]
-pub extern "C" fn approve() -> i32 {
-    if !is_initialized() {
-        return fail("Contract not initialized");
-    }
-
-    let spender = match arg(0) {
-        Some(s) if !s.is_empty() => s,
-        _ => return fail("spender address required"),
-    };
-    let amount_str = arg(1).unwrap_or_default();
-    let amount = parse_u128(&amount_str);
-
-    let owner = caller();
-    if owner.is_empty() {
-        return fail("caller address not available");
-    }
-    if owner == spender {
-        return fail("cannot approve self");
-    }
-
-    set_allowance(&owner, &spender, amount);
-
-    // Emit approval event
-    event::emit(
-        "USP01:Approval",
-        &format!(
-            r#"{{"owner":"{}","spender":"{}","amount":"{}"}}"#,
-            json_escape(&owner),
-            json_escape(&spender),
-            u128_to_str(amount)
-        ),
-    );
-
-    set_return_str(&format!(
-        r#"{{"success":true,"owner":"{}","spender":"{}","amount":"{}"}}"#,
-        json_escape(&owner),
-        json_escape(&spender),
-        u128_to_str(amount)
-    ));
-    0
-}
+        let transfer = Transfer {
+            from,
+            to,
+            amount,
+            spender: None,
+        };
+        transfer.emit();
+        Ok(transfer)
+    }
 
-// ─────────────────────────────────────────────────────────────
-// TRANSFER_FROM — Spend tokens on behalf of owner (requires allowance)
-// ─────────────────────────────────────────────────────────────
-
-/// Transfer tokens from `from` to `to` using caller's allowance.
-///
-/// Args:
-///   0: from (owner address)
-///   1: to (recipient address)
-///   2: amount (u128 decimal string)
-#[no_mangle]
-pub extern "C" fn transfer_from() -> i32 {
-    if !is_initialized() {
-        return fail("Contract not initialized");
-    }
-
-    let from = match arg(0) {
-        Some(a) if !a.is_empty() => a,
-        _ => return fail("from address required"),
-    };
-    let to = match arg(1) {
-        Some(a) if !a.is_empty() => a,
-        _ => return fail("to address required"),
-    };
-    let amount_str = arg(2).unwrap_or_default();
-    let amount = parse_u128(&amount_str);
-    if amount == 0 {
-        return fail("amount must be > 0");
-    }
-    if from == to {
-        return fail("from and to must differ");
-    }
-
-    let spender = caller();
-    if spender.is_empty() {
-        return fail("caller address not available");
-    }
-
-    // Check allowance
-    let allowance = get_allowance(&from, &spender);
-    if allowance < amount {
-        return fail("allowance exceeded");
-    }
-
-    // Check balance
-    let from_bal = get_balance(&from);
-    if from_bal < amount {
-        return fail("insufficient balance");
-    }
-
-    // Debit owner
-    let new_from = match from_bal.checked_sub(amount) {
-        Some(v) => v,
-        None => return fail("arithmetic underflow"),
-    };
-    set_balance(&from, new_from);
-
-    // Credit recipient
-    let to_bal = get_balance(&to);
-    let new_to = match to_bal.checked_add(amount) {
-        Some(v) => v,
-        None => return fail("arithmetic overflow"),
-    };
-    set_balance(&to, new_to);
-
-    // Reduce allowance
-    let new_allowance = match allowance.checked_sub(amount) {
-        Some(v) => v,
-        None => 0,
-    };
-    set_allowance(&from, &spender, new_allowance);
-
-    // Emit transfer event
-    event::emit(
-        "USP01:Transfer",
-        &format!(
-            r#"{{"from":"{}","to":"{}","amount":"{}","spender":"{}"}}"#,
-            json_escape(&from),
-            json_escape(&to),
-            u128_to_str(amount),
-            json_escape(&spender)
-        ),
-    );
-
-    set_return_str(&format!(
-        r#"{{"success":true,"from":"{}","to":"{}","amount":"{}","spender":"{}"}}"#,
-        json_escape(&from),
-        json_escape(&to),
-        u128_to_str(amount),
-        json_escape(&spender)
-    ));
-    0
-}
+    // ─────────────────────────────────────────────────────────────
+    // APPROVE — Set spending allowance
+    // ─────────────────────────────────────────────────────────────
 
-// ─────────────────────────────────────────────────────────────
-// BURN — Permanently destroy tokens
-// ─────────────────────────────────────────────────────────────
-
-/// Burn tokens from caller's balance, reducing total supply permanently.
-///
-/// Args:
-///   0: amount (u128 decimal string)
-#[no_mangle]
-pub extern "C" fn burn() -> i32 {
-    if !is_initialized() {
-        return fail("Contract not initialized");
-    }
-
-    let amount_str = arg(0).unwrap_or_default();
-    let amount = parse_u128(&amount_str);
-    if amount == 0 {
-        return fail("amount must be > 0");
-    }
-
-    let from = caller();
-    if from.is_empty() {
-        return fail("caller address not available");
-    }
-
-    let bal = get_balance(&from);
-    if bal < amount {
-        return fail("insufficient balance to burn");
-    }
-
-    // Debit caller
-    let new_bal = match bal.checked_sub(amount) {
-        Some(v) => v,
-        None => return fail("arithmetic underflow"),
-    };
-    set_balance(&from, new_bal);
-
-    // Reduce total supply permanently
-    let supply = get_total_supply();
-    let new_supply = supply.saturating_sub(amount);
-    set_total_supply(new_supply);
-
-    // Emit burn event
-    event::emit(
-        "USP01:Burn",
-        &format!(
-            r#"{{"from":"{}","amount":"{}","new_supply":"{}"}}"#,
-            json_escape(&from),
-            u128_to_str(amount),
-            u128_to_str(new_supply)
-        ),
-    );
-
-    set_return_str(&format!(
-        r#"{{"success":true,"burned":"{}","new_supply":"{}"}}"#,
-        u128_to_str(amount),
-        u128_to_str(new_supply)
-    ));
-    0
-}
+    /// Approve spender to spend up to `amount` on behalf of caller (0 revokes).
+    #[entry]
+    pub fn approve(spender: Address, amount: u128) -> CallResult<Approval> {
+        require_init()?;
+        let owner = require_caller()?;
+        if owner == spender {
+            return Err("cannot approve self");
+        }
 
-// ─────────────────────────────────────────────────────────────
-// BALANCE_OF — Query balance (read-only)
-// ─────────────────────────────────────────────────────────────
-
-/// Return balance of an account.
-///
-/// Args:
-///   0: account (address)
-#[no_mangle]
-pub extern "C" fn balance_of() -> i32 {
-    if !is_initialized() {
-        return fail("Contract not initialized");
-    }
-
-    let account = match arg(0) {
-        Some(a) if !a.is_empty() => a,
-        _ => return fail("account address required"),
-    };
-
-    let bal = get_balance(&account);
-    ok_data(&format!(
-        r#"{{"account":"{}","balance":"{}"}}"#,
-        json_escape(&account),
-        u128_to_str(bal)
-    ))
-}
+        set_allowance(&owner, &spender, amount);
 
-// ─────────────────────────────────────────────────────────────
-// ALLOWANCE_OF — Query allowance (read-only)
-// ─────────────────────────────────────────────────────────────
-
-/// Return allowance granted by owner to spender.
-///
-/// Args:
-///   0: owner (address)
-///   1: spender (address)
-#[no_mangle]
-pub extern "C" fn allowance_of() -> i32 {
-    if !is_initialized() {
-        return fail("Contract not initialized");
-    }
-
-    let owner = match arg(0) {
-        Some(a) if !a.is_empty() => a,
-        _ => return fail("owner address required"),
-    };
-    let spender = match arg(1) {
-        Some(a) if !a.is_empty() => a,
-        _ => return fail("spender address required"),
-    };
-
-    let allowance = get_allowance(&owner, &spender);
-    ok_data(&format!(
-        r#"{{"owner":"{}","spender":"{}","allowance":"{}"}}"#,
-        json_escape(&owner),
-        json_escape(&spender),
-        u128_to_str(allowance)
-    ))
-}
+        let approval = Approval {
+            owner,
+            spender,
+            amount,
+        };
+        approval.emit();
+        Ok(approval)
+    }
 
-// ─────────────────────────────────────────────────────────────
-// TOTAL_SUPPLY — Query total supply (read-only)
-// ─────────────────────────────────────────────────────────────
+    // ─────────────────────────────────────────────────────────────
+    // TRANSFER_FROM — Spend tokens on behalf of owner (requires allowance)
+    // ─────────────────────────────────────────────────────────────
 
-/// Return current total supply.
-#[no_mangle]
-pub extern "C" fn total_supply() -> i32 {
-    if !is_initialized() {
-        return fail("Contract not initialized");
-    }
+    /// Transfer tokens from `from` to `to` using caller's allowance.
+    #[entry]
+    pub fn transfer_from(from: Address, to: Address, amount: u128) -> CallResult<Transfer> {
+        require_init()?;
+        if amount == 0 {
+            return Err("amount must be > 0");
+        }
+        if from == to {
+            return Err("from and to must differ");
+        }
+        let spender = require_caller()?;
 
-    let supply = get_total_supply();
-    ok_data(&format!(r#"{{"total_supply":"{}"}}"#, u128_to_str(supply)))
-}
+        // Check allowance
+        let allowance = get_allowance(&from, &spender);
+        if allowance < amount {
+            return Err("allowance exceeded");
+        }
 
-// ─────────────────────────────────────────────────────────────
-// TOKEN_INFO — Query full metadata (read-only)
-// ─────────────────────────────────────────────────────────────
-
-/// Return complete token metadata.
-#[no_mangle]
-pub extern "C" fn token_info() -> i32 {
-    if !is_initialized() {
-        return fail("Contract not initialized");
-    }
-
-    let name = state::get_str("usp01:name").unwrap_or_default();
-    let symbol = state::get_str("usp01:symbol").unwrap_or_default();
-    let decimals = parse_u128(&state::get_str("usp01:decimals").unwrap_or_default());
-    let total_supply = get_total_supply();
-    let is_wrapped = state::get_str("usp01:is_wrapped").unwrap_or_default() == "1";
-    let wrapped_origin = state::get_str("usp01:wrapped_origin").unwrap_or_default();
-    let max_supply = parse_u128(&state::get_str("usp01:max_supply").unwrap_or_default());
-    let bridge_operator = state::get_str("usp01:bridge_operator").unwrap_or_default();
-    let owner = state::get_str("usp01:owner").unwrap_or_default();
-    let contract = self_address();
-
-    ok_data(&format!(
-        r#"{{"name":"{}","symbol":"{}","decimals":{},"total_supply":"{}","is_wrapped":{},"wrapped_origin":"{}","max_supply":"{}","bridge_operator":"{}","owner":"{}","contract":"{}","standard":"USP-01"}}"#,
-        json_escape(&name),
-        json_escape(&symbol),
-        decimals,
-        u128_to_str(total_supply),
-        is_wrapped,
-        json_escape(&wrapped_origin),
-        u128_to_str(max_supply),
-        json_escape(&bridge_operator),
-        json_escape(&owner),
-        json_escape(&contract)
-    ))
-}
+        move_balance(&from, &to, amount, "insufficient balance")?;
 
-// ─────────────────────────────────────────────────────────────
-// WRAP_MINT — Mint wrapped tokens (bridge operator only)
-// ─────────────────────────────────────────────────────────────
-
-/// Mint wrapped tokens when a deposit is confirmed on the source chain.
-/// Only callable by the designated bridge operator.
-///
-/// Args:
-///   0: to (recipient address)
-///   1: amount (u128 decimal string)
-///   2: proof (deposit proof from source chain)
-#[no_mangle]
-pub extern "C" fn wrap_mint() -> i32 {
-    if !is_initialized() {
-        return fail("Contract not initialized");
-    }
-
-    let is_wrapped = state::get_str("usp01:is_wrapped").unwrap_or_default() == "1";
-    if !is_wrapped {
-        return fail("not a wrapped asset");
-    }
-
-    let bridge_op = state::get_str("usp01:bridge_operator").unwrap_or_default();
-    let who = caller();
-    if who != bridge_op {
-        return fail("only bridge operator can mint wrapped tokens");
-    }
-
-    let to = match arg(0) {
-        Some(a) if !a.is_empty() => a,
-        _ => return fail("recipient address required"),
-    };
-    let amount_str = arg(1).unwrap_or_default();
-    let amount = parse_u128(&amount_str);
-    if amount == 0 {
-        return fail("amount must be > 0");
-    }
-    let proof = match arg(2) {
-        Some(p) if !p.is_empty() => p,
-        _ => return fail("deposit proof required"),
-    };
-
-    // Check max supply cap
-    let max_supply = parse_u128(&state::get_str("usp01:max_supply").unwrap_or_default());
-    let supply = get_total_supply();
-    if max_supply > 0 {
-        let new_supply = match supply.checked_add(amount) {
-            Some(v) => v,
-            None => return fail("supply overflow"),
+        // Reduce allowance
+        set_allowance(&from, &spender, allowance.saturating_sub(amount));
+
+        let transfer = Transfer {
+            from,
+            to,
+            amount,
+            spender: Some(spender),
         };
-        if new_supply > max_supply {
-            return fail("would exceed max supply cap");
+        transfer.emit();
+        Ok(transfer)
+    }
+
+    // ─────────────────────────────────────────────────────────────
+    // BURN — Permanently destroy tokens
+    // ─────────────────────────────────────────────────────────────
+
+    /// Burn tokens from caller's balance, reducing total supply permanently.
+    #[entry]
+    pub fn burn(amount: u128) -> CallResult<Burned> {
+        require_init()?;
+        if amount == 0 {
+            return Err("amount must be > 0");
+        }
+        let from = require_caller()?;
+
+        let new_supply = burn_from(&from, amount, "insufficient balance to burn")?;
+
+        Burn {
+            from,
+            amount,
+            new_supply,
+        }
+        .emit();
+
+        Ok(Burned {
+            burned: amount,
+            new_supply,
+        })
+    }
+
+    // ─────────────────────────────────────────────────────────────
+    // QUERIES (read-only)
+    // ─────────────────────────────────────────────────────────────
+
+    /// Return balance of an account.
+    #[view]
+    pub fn balance_of(account: Address) -> CallResult<Balance> {
+        require_init()?;
+        let balance = get_balance(&account);
+        Ok(Balance { account, balance })
+    }
+
+    /// Return allowance granted by owner to spender.
+    #[view]
+    pub fn allowance_of(owner: Address, spender: Address) -> CallResult<Allowance> {
+        require_init()?;
+        let allowance = get_allowance(&owner, &spender);
+        Ok(Allowance {
+            owner,
+            spender,
+            allowance,
+        })
+    }
+
+    /// Return current total supply.
+    #[view]
+    pub fn total_supply() -> CallResult<Supply> {
+        require_init()?;
+        Ok(Supply {
+            total_supply: get_total_supply(),
+        })
+    }
+
+    /// Return complete token metadata.
+    #[view]
+    pub fn token_info() -> CallResult<TokenInfo> {
+        require_init()?;
+        let get = |key: &str| state::get_str(key).unwrap_or_default();
+        Ok(TokenInfo {
+            name: get("usp01:name"),
+            symbol: get("usp01:symbol"),
+            decimals: parse_u64(&get("usp01:decimals")),
+            total_supply: get_total_supply(),
+            is_wrapped: get("usp01:is_wrapped") == "1",
+            wrapped_origin: get("usp01:wrapped_origin"),
+            max_supply: parse_u128(&get("usp01:max_supply")),
+            bridge_operator: get("usp01:bridge_operator"),
+            owner: get("usp01:owner"),
+            contract: self_address(),
+            standard: "USP-01",
+        })
+    }
+
+    // ─────────────────────────────────────────────────────────────
+    // WRAP_MINT — Mint wrapped tokens (bridge operator only)
+    // ─────────────────────────────────────────────────────────────
+
+    /// Mint wrapped tokens for a deposit confirmed on the source chain.
+    /// Only callable by the designated bridge operator.
+    #[entry]
+    pub fn wrap_mint(to: Address, amount: u128, proof: String) -> CallResult<Minted> {
+        require_init()?;
+        require_wrapped()?;
+
+        let bridge_op = state::get_str("usp01:bridge_operator").unwrap_or_default();
+        if caller() != bridge_op {
+            return Err("only bridge operator can mint wrapped tokens");
+        }
+        if amount == 0 {
+            return Err("amount must be > 0");
+        }
+        if proof.is_empty() {
+            return Err("deposit proof required");
+        }
+
+        // Check max supply cap
+        let max_supply = parse_u128(&state::get_str("usp01:max_supply").unwrap_or_default());
+        let supply = get_total_supply();
+        if max_supply > 0 {
+            let new_supply = supply.checked_add(amount).ok_or("supply overflow")?;
+            if new_supply > max_supply {
+                return Err("would exceed max supply cap");
+            }
+        }
+
+        // Credit recipient
+        let new_to = get_balance(&to)
+            .checked_add(amount)
+            .ok_or("balance overflow")?;
+        set_balance(&to, new_to);
+
+        // Increase total supply
+        let new_supply = supply.saturating_add(amount);
+        set_total_supply(new_supply);
+
+        WrapMint {
+            to: to.clone(),
+            amount,
+            proof: proof.clone(),
+            new_supply,
         }
+        .emit();
+
+        Ok(Minted { to, amount, proof })
     }
 
-    // Credit recipient
-    let to_bal = get_balance(&to);
-    let new_to = match to_bal.checked_add(amount) {
-        Some(v) => v,
-        None => return fail("balance overflow"),
-    };
-    set_balance(&to, new_to);
-
-    // Increase total supply
-    let new_supply = supply.saturating_add(amount);
-    set_total_supply(new_supply);
-
-    // Emit event
-    event::emit(
-        "USP01:WrapMint",
-        &format!(
-            r#"{{"to":"{}","amount":"{}","proof":"{}","new_supply":"{}"}}"#,
-            json_escape(&to),
-            u128_to_str(amount),
-            json_escape(&proof),
-            u128_to_str(new_supply)
-        ),
-    );
-
-    set_return_str(&format!(
-        r#"{{"success":true,"to":"{}","amount":"{}","proof":"{}"}}"#,
-        json_escape(&to),
-        u128_to_str(amount),
-        json_escape(&proof)
-    ));
-    0
+    // ─────────────────────────────────────────────────────────────
+    // WRAP_BURN — Burn wrapped tokens for redemption
+    // ─────────────────────────────────────────────────────────────
+
+    /// Burn wrapped tokens for redemption to `destination` on the source chain.
+    #[entry]
+    pub fn wrap_burn(amount: u128, destination: String) -> CallResult<Redeemed> {
+        require_init()?;
+        require_wrapped()?;
+        if amount == 0 {
+            return Err("amount must be > 0");
+        }
+        if destination.is_empty() {
+            return Err("destination address required");
+        }
+        let from = require_caller()?;
+
+        let new_supply = burn_from(&from, amount, "insufficient balance for wrap burn")?;
+
+        WrapBurn {
+            from: from.clone(),
+            amount,
+            destination: destination.clone(),
+            new_supply,
+        }
+        .emit();
+
+        Ok(Redeemed {
+            from,
+            amount,
+            destination,
+        })
+    }
 }
 
-// ─────────────────────────────────────────────────────────────
-// WRAP_BURN — Burn wrapped tokens for redemption
-// ─────────────────────────────────────────────────────────────
-
-/// Burn wrapped tokens for redemption on the source chain.
-///
-/// Args:
-///   0: amount (u128 decimal string)
-///   1: destination (address on the source chain)
-#[no_mangle]
-pub extern "C" fn wrap_burn() -> i32 {
-    if !is_initialized() {
-        return fail("Contract not initialized");
-    }
-
-    let is_wrapped = state::get_str("usp01:is_wrapped").unwrap_or_default() == "1";
-    if !is_wrapped {
-        return fail("not a wrapped asset");
-    }
-
-    let amount_str = arg(0).unwrap_or_default();
-    let amount = parse_u128(&amount_str);
-    if amount == 0 {
-        return fail("amount must be > 0");
-    }
-    let destination = match arg(1) {
-        Some(d) if !d.is_empty() => d,
-        _ => return fail("destination address required"),
-    };
-
-    let from = caller();
-    if from.is_empty() {
-        return fail("caller address not available");
-    }
-
-    let bal = get_balance(&from);
-    if bal < amount {
-        return fail("insufficient balance for wrap burn");
-    }
-
-    // Debit caller
-    let new_bal = match bal.checked_sub(amount) {
-        Some(v) => v,
-        None => return fail("arithmetic underflow"),
-    };
-    set_balance(&from, new_bal);
-
-    // Reduce total supply
-    let supply = get_total_supply();
-    let new_supply = supply.saturating_sub(amount);
-    set_total_supply(new_supply);
-
-    // Emit event
-    event::emit(
-        "USP01:WrapBurn",
-        &format!(
-            r#"{{"from":"{}","amount":"{}","destination":"{}","new_supply":"{}"}}"#,
-            json_escape(&from),
-            u128_to_str(amount),
-            json_escape(&destination),
-            u128_to_str(new_supply)
-        ),
-    );
-
-    set_return_str(&format!(
-        r#"{{"success":true,"from":"{}","amount":"{}","destination":"{}"}}"#,
-        json_escape(&from),
-        u128_to_str(amount),
-        json_escape(&destination)
-    ));
-    0
+// ─────────────────────────────────────────────────────────────────
+// Tests (native, against the `los_sdk::mock` host)
+// ─────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::usp01::exports;
+    use los_sdk::mock;
+
+    fn deploy(supply: &str) {
+        mock::reset();
+        mock::set_caller("LOSalice");
+        let r = mock::call(exports::init, &["Token", "TK", "8", supply]);
+        assert!(r.success, "{}", r.output);
+    }
+
+    #[test]
+    fn test_init_assigns_supply_to_creator() {
+        deploy("1000");
+        assert_eq!(mock::state_str("bal:LOSalice").as_deref(), Some("1000"));
+        assert_eq!(mock::state_str("usp01:owner").as_deref(), Some("LOSalice"));
+
+        let again = mock::call(exports::init, &["Token", "TK", "8", "1"]);
+        assert!(!again.success);
+        assert!(again.output.contains("Already initialized"));
+    }
+
+    #[test]
+    fn test_transfer_moves_balance_and_emits() {
+        deploy("1000");
+        let r = mock::call(exports::transfer, &["LOSbob", "250"]);
+        assert!(r.success, "{}", r.output);
+        assert_eq!(r.events.len(), 1);
+        assert_eq!(r.events[0].event_type, "USP01:Transfer");
+        assert_eq!(mock::state_str("bal:LOSalice").as_deref(), Some("750"));
+        assert_eq!(mock::state_str("bal:LOSbob").as_deref(), Some("250"));
+
+        let r = mock::call(exports::transfer, &["LOSbob", "751"]);
+        assert!(!r.success);
+        assert!(r.output.contains("insufficient balance"));
+        assert!(r.events.is_empty());
+    }
+
+    #[test]
+    fn test_transfer_from_spends_allowance() {
+        deploy("1000");
+        assert!(mock::call(exports::approve, &["LOSdex", "100"]).success);
+
+        mock::set_caller("LOSdex");
+        let r = mock::call(exports::transfer_from, &["LOSalice", "LOSpool", "60"]);
+        assert!(r.success, "{}", r.output);
+        assert_eq!(
+            mock::state_str("allow:LOSalice:LOSdex").as_deref(),
+            Some("40")
+        );
+
+        let r = mock::call(exports::transfer_from, &["LOSalice", "LOSpool", "41"]);
+        assert!(r.output.contains("allowance exceeded"));
+    }
+
+    #[test]
+    fn test_burn_reduces_supply() {
+        deploy("1000");
+        let r = mock::call(exports::burn, &["300"]);
+        assert!(r.success, "{}", r.output);
+        let r = mock::call(exports::total_supply, &[]);
+        assert!(r.output.contains("700"), "{}", r.output);
+    }
 }
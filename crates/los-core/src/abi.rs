@@ -0,0 +1,548 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - CONTRACT ABI METADATA
+//
+// Contracts read positional string args (`los_sdk::arg(idx)`), so callers
+// need to know each function's argument order. An ABI describes it:
+// - Functions with named, typed args and a view/mutating flag
+// - Events the contract emits
+// The ABI is either embedded in the WASM as a custom section named
+// `los_abi` (JSON payload) or attached to the deploy request. The node
+// stores it next to the contract; clients validate and encode calls with it.
+// It is metadata only: execution never depends on it. An attached ABI is
+// committed to by hash in the deploy block's signed link
+// (`DEPLOY:{code_hash}:ABI:{abi_hash}`), so relays cannot swap it.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+use std::collections::BTreeSet;
+
+/// WASM custom section holding the JSON-encoded ABI
+pub const ABI_SECTION_NAME: &str = "los_abi";
+/// Current ABI descriptor version
+pub const ABI_VERSION: u32 = 1;
+/// Maximum encoded ABI size (bounds what every node stores per contract)
+pub const MAX_ABI_SIZE: usize = 64 * 1024;
+/// Separates the code hash from the attached ABI hash in a deploy link
+const DEPLOY_ABI_MARKER: &str = ":ABI:";
+/// Maximum functions or events in one ABI
+const MAX_ABI_ENTRIES: usize = 256;
+
+/// Argument / return value type. All values travel as strings on the wire.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AbiType {
+    String,
+    /// LOS account address or `LOSCon…` contract address
+    Address,
+    U64,
+    U128,
+    /// "true"/"false" (also accepts "1"/"0", encoded as "1"/"0")
+    Bool,
+    /// Hex-encoded bytes
+    Bytes,
+    /// Any JSON document (structured return values and event fields)
+    Json,
+}
+
+impl AbiType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AbiType::String => "string",
+            AbiType::Address => "address",
+            AbiType::U64 => "u64",
+            AbiType::U128 => "u128",
+            AbiType::Bool => "bool",
+            AbiType::Bytes => "bytes",
+            AbiType::Json => "json",
+        }
+    }
+
+    /// Check `value` against this type and return its canonical wire form
+    pub fn encode(&self, value: &str) -> Result<String, String> {
+        let invalid = || format!("expected {}, got '{}'", self.as_str(), value);
+        match self {
+            AbiType::String => Ok(value.to_string()),
+            AbiType::Address => {
+                if is_valid_address(value) {
+                    Ok(value.to_string())
+                } else {
+                    Err(invalid())
+                }
+            }
+            AbiType::U64 => value
+                .parse::<u64>()
+                .map(|v| v.to_string())
+                .map_err(|_| invalid()),
+            AbiType::U128 => value
+                .parse::<u128>()
+                .map(|v| v.to_string())
+                .map_err(|_| invalid()),
+            AbiType::Bool => match value {
+                "true" | "1" => Ok("1".to_string()),
+                "false" | "0" => Ok("0".to_string()),
+                _ => Err(invalid()),
+            },
+            AbiType::Bytes => {
+                let hex_part = value.strip_prefix("0x").unwrap_or(value);
+                hex::decode(hex_part)
+                    .map(hex::encode)
+                    .map_err(|_| invalid())
+            }
+            AbiType::Json => serde_json::from_str::<serde_json::Value>(value)
+                .map(|v| v.to_string())
+                .map_err(|_| invalid()),
+        }
+    }
+}
+
+/// User addresses carry a checksum; contract addresses are `LOSCon` + 32 hex chars
+fn is_valid_address(value: &str) -> bool {
+    match value.strip_prefix("LOSCon") {
+        Some(hex_part) if hex_part.len() == 32 => hex::decode(hex_part).is_ok(),
+        _ => los_crypto::validate_address(value),
+    }
+}
+
+/// A named, typed argument or event field
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AbiParam {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: AbiType,
+    /// May be omitted or left empty (only allowed after all required args)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub optional: bool,
+}
+
+/// An exported contract function
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AbiFunction {
+    pub name: String,
+    #[serde(default)]
+    pub args: Vec<AbiParam>,
+    /// Read-only: safe to run via `/query-contract`
+    #[serde(default)]
+    pub view: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub returns: Option<AbiType>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub doc: String,
+}
+
+/// An event emitted via `los_sdk::event::emit`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AbiEvent {
+    pub name: String,
+    #[serde(default)]
+    pub fields: Vec<AbiParam>,
+}
+
+/// Contract ABI descriptor
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContractAbi {
+    pub version: u32,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub name: String,
+    #[serde(default)]
+    pub functions: Vec<AbiFunction>,
+    #[serde(default)]
+    pub events: Vec<AbiEvent>,
+}
+
+impl ContractAbi {
+    /// Parse and validate a JSON-encoded ABI
+    pub fn from_json(bytes: &[u8]) -> Result<Self, String> {
+        if bytes.len() > MAX_ABI_SIZE {
+            return Err(format!(
+                "ABI too large: {} bytes (max {} bytes)",
+                bytes.len(),
+                MAX_ABI_SIZE
+            ));
+        }
+        let abi: ContractAbi =
+            serde_json::from_slice(bytes).map_err(|e| format!("Invalid ABI JSON: {}", e))?;
+        abi.validate()?;
+        Ok(abi)
+    }
+
+    /// SHA3-256 of the canonical JSON encoding (hex)
+    pub fn hash(&self) -> String {
+        let encoded = serde_json::to_vec(self).unwrap_or_default();
+        hex::encode(Sha3_256::digest(&encoded))
+    }
+
+    /// Structural checks: supported version, bounded size, unique names
+    pub fn validate(&self) -> Result<(), String> {
+        if self.version != ABI_VERSION {
+            return Err(format!(
+                "Unsupported ABI version {} (expected {})",
+                self.version, ABI_VERSION
+            ));
+        }
+        if self.functions.len() > MAX_ABI_ENTRIES || self.events.len() > MAX_ABI_ENTRIES {
+            return Err(format!(
+                "ABI has too many entries (max {} functions / {} events)",
+                MAX_ABI_ENTRIES, MAX_ABI_ENTRIES
+            ));
+        }
+        let mut seen = BTreeSet::new();
+        for f in &self.functions {
+            if f.name.is_empty() {
+                return Err("ABI function with empty name".to_string());
+            }
+            if !seen.insert(f.name.as_str()) {
+                return Err(format!("Duplicate ABI function '{}'", f.name));
+            }
+            unique_params(&f.args, &f.name)?;
+            if f.args.windows(2).any(|w| w[0].optional && !w[1].optional) {
+                return Err(format!(
+                    "{}: optional arguments must come after required ones",
+                    f.name
+                ));
+            }
+        }
+        let mut seen = BTreeSet::new();
+        for e in &self.events {
+            if !seen.insert(e.name.as_str()) {
+                return Err(format!("Duplicate ABI event '{}'", e.name));
+            }
+            unique_params(&e.fields, &e.name)?;
+        }
+        Ok(())
+    }
+
+    pub fn function(&self, name: &str) -> Option<&AbiFunction> {
+        self.functions.iter().find(|f| f.name == name)
+    }
+
+    /// ABI functions that are missing from the module's exports
+    pub fn missing_exports(&self, exports: &[String]) -> Vec<String> {
+        self.functions
+            .iter()
+            .filter(|f| !exports.contains(&f.name))
+            .map(|f| f.name.clone())
+            .collect()
+    }
+
+    /// Validate call args against the ABI and return them in canonical form.
+    ///
+    /// Args are positional, or `name=value` pairs in any order (not mixed).
+    /// Optional args may be omitted or empty; trailing empty ones are dropped.
+    pub fn encode_call(&self, function: &str, args: &[String]) -> Result<Vec<String>, String> {
+        let f = self
+            .function(function)
+            .ok_or_else(|| format!("Function '{}' is not in the contract ABI", function))?;
+        let required = f.args.iter().filter(|p| !p.optional).count();
+        let named = !args.is_empty()
+            && args.iter().all(|a| {
+                a.split_once('=')
+                    .is_some_and(|(k, _)| f.args.iter().any(|p| p.name == k))
+            });
+
+        let ordered: Vec<&str> = if named {
+            let mut values = vec![None; f.args.len()];
+            for a in args {
+                let (k, v) = a.split_once('=').unwrap_or_default();
+                let idx = f.args.iter().position(|p| p.name == k).unwrap_or_default();
+                if values[idx].replace(v).is_some() {
+                    return Err(format!("{}: argument '{}' given twice", function, k));
+                }
+            }
+            values
+                .into_iter()
+                .zip(&f.args)
+                .map(|(v, p)| match v {
+                    Some(v) => Ok(v),
+                    None if p.optional => Ok(""),
+                    None => Err(format!("{}: missing argument '{}'", function, p.name)),
+                })
+                .collect::<Result<_, _>>()?
+        } else {
+            if args.len() < required || args.len() > f.args.len() {
+                let expected = if required == f.args.len() {
+                    required.to_string()
+                } else {
+                    format!("{}-{}", required, f.args.len())
+                };
+                return Err(format!(
+                    "{} takes {} argument(s) ({}), got {}",
+                    function,
+                    expected,
+                    f.signature_args(),
+                    args.len()
+                ));
+            }
+            args.iter().map(String::as_str).collect()
+        };
+
+        let mut encoded = ordered
+            .iter()
+            .zip(&f.args)
+            .map(|(v, p)| {
+                if p.optional && v.is_empty() {
+                    return Ok(String::new());
+                }
+                p.ty.encode(v)
+                    .map_err(|e| format!("{}: argument '{}': {}", function, p.name, e))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        while encoded.last().is_some_and(String::is_empty) && encoded.len() > required {
+            encoded.pop();
+        }
+        Ok(encoded)
+    }
+}
+
+impl AbiFunction {
+    /// "to: address, amount: u128, memo?: string"
+    pub fn signature_args(&self) -> String {
+        self.args
+            .iter()
+            .map(|p| {
+                let opt = if p.optional { "?" } else { "" };
+                format!("{}{}: {}", p.name, opt, p.ty.as_str())
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+fn unique_params(params: &[AbiParam], owner: &str) -> Result<(), String> {
+    let mut seen = BTreeSet::new();
+    for p in params {
+        if p.name.is_empty() || p.name.contains('=') {
+            return Err(format!("{}: invalid parameter name '{}'", owner, p.name));
+        }
+        if !seen.insert(p.name.as_str()) {
+            return Err(format!("{}: duplicate parameter '{}'", owner, p.name));
+        }
+    }
+    Ok(())
+}
+
+/// Read an unsigned LEB128 u32 at `pos`, advancing it
+fn read_leb_u32(bytes: &[u8], pos: &mut usize) -> Result<u32, String> {
+    let mut result: u32 = 0;
+    for shift in (0..35).step_by(7) {
+        let byte = *bytes
+            .get(*pos)
+            .ok_or_else(|| "Truncated WASM section header".to_string())?;
+        *pos += 1;
+        result |= ((byte & 0x7f) as u32)
+            .checked_shl(shift)
+            .ok_or_else(|| "Invalid LEB128 in WASM".to_string())?;
+        if byte & 0x80 == 0 {
+            return Ok(result);
+        }
+    }
+    Err("Invalid LEB128 in WASM".to_string())
+}
+
+/// Extract the ABI embedded in a `los_abi` custom section, if any.
+///
+/// Walks the section headers only (no compilation), so it is cheap enough to
+/// run on every deploy. An embedded but malformed ABI is an error.
+pub fn extract_embedded(bytecode: &[u8]) -> Result<Option<ContractAbi>, String> {
+    if bytecode.len() < 8 || &bytecode[0..4] != b"\0asm" {
+        return Err("Invalid WASM bytecode (missing magic header)".to_string());
+    }
+    let mut pos = 8; // magic + version
+    while pos < bytecode.len() {
+        let id = bytecode[pos];
+        pos += 1;
+        let size = read_leb_u32(bytecode, &mut pos)? as usize;
+        let end = pos
+            .checked_add(size)
+            .filter(|&end| end <= bytecode.len())
+            .ok_or_else(|| "WASM section exceeds module size".to_string())?;
+        if id == 0 {
+            let mut name_pos = pos;
+            let name_len = read_leb_u32(bytecode, &mut name_pos)? as usize;
+            let name_end = name_pos
+                .checked_add(name_len)
+                .filter(|&e| e <= end)
+                .ok_or_else(|| "Invalid custom section name".to_string())?;
+            if &bytecode[name_pos..name_end] == ABI_SECTION_NAME.as_bytes() {
+                return ContractAbi::from_json(&bytecode[name_end..end]).map(Some);
+            }
+        }
+        pos = end;
+    }
+    Ok(None)
+}
+
+/// Link of a ContractDeploy block: `DEPLOY:{code_hash}`, extended with
+/// `:ABI:{abi_hash}` when an ABI is attached so the signature covers it
+pub fn deploy_link(code_hash: &str, attached: Option<&ContractAbi>) -> String {
+    match attached {
+        Some(abi) => format!("DEPLOY:{}{}{}", code_hash, DEPLOY_ABI_MARKER, abi.hash()),
+        None => format!("DEPLOY:{}", code_hash),
+    }
+}
+
+/// Split a ContractDeploy link into its code hash and attached ABI hash
+pub fn parse_deploy_link(link: &str) -> Option<(&str, Option<&str>)> {
+    let rest = link.strip_prefix("DEPLOY:")?;
+    match rest.split_once(DEPLOY_ABI_MARKER) {
+        Some((code_hash, abi_hash)) => Some((code_hash, Some(abi_hash))),
+        None => Some((rest, None)),
+    }
+}
+
+/// Append a `los_abi` custom section to a WASM module (tooling helper)
+pub fn embed(bytecode: &[u8], abi: &ContractAbi) -> Result<Vec<u8>, String> {
+    abi.validate()?;
+    let payload = serde_json::to_vec(abi).map_err(|e| e.to_string())?;
+    let mut section = Vec::new();
+    write_leb_u32(&mut section, ABI_SECTION_NAME.len() as u32);
+    section.extend_from_slice(ABI_SECTION_NAME.as_bytes());
+    section.extend_from_slice(&payload);
+
+    let mut out = bytecode.to_vec();
+    out.push(0);
+    write_leb_u32(&mut out, section.len() as u32);
+    out.extend_from_slice(&section);
+    Ok(out)
+}
+
+fn write_leb_u32(out: &mut Vec<u8>, mut value: u32) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            out.push(byte);
+            return;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Smallest valid module: header only
+    const EMPTY_MODULE: &[u8] = b"\0asm\x01\0\0\0";
+
+    fn token_abi() -> ContractAbi {
+        ContractAbi::from_json(
+            br#"{
+                "version": 1,
+                "name": "USP-01",
+                "functions": [
+                    {"name": "transfer", "args": [
+                        {"name": "to", "type": "address"},
+                        {"name": "amount", "type": "u128"}
+                    ]},
+                    {"name": "balance_of", "view": true, "returns": "u128",
+                     "args": [{"name": "account", "type": "address"}]}
+                ],
+                "events": [{"name": "Transfer", "fields": [{"name": "amount", "type": "u128"}]}]
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_embedded_abi_roundtrip() {
+        assert_eq!(extract_embedded(EMPTY_MODULE).unwrap(), None);
+        let abi = token_abi();
+        let wasm = embed(EMPTY_MODULE, &abi).unwrap();
+        assert_eq!(extract_embedded(&wasm).unwrap(), Some(abi));
+
+        // Truncated section is rejected, not silently ignored
+        assert!(extract_embedded(&wasm[..wasm.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn test_deploy_link_commits_to_abi() {
+        let abi = token_abi();
+        assert_eq!(
+            parse_deploy_link(&deploy_link("abcd1234", None)),
+            Some(("abcd1234", None))
+        );
+        let link = deploy_link("abcd1234", Some(&abi));
+        let (code_hash, abi_hash) = parse_deploy_link(&link).unwrap();
+        assert_eq!(code_hash, "abcd1234");
+        assert_eq!(abi_hash, Some(abi.hash().as_str()));
+
+        // Any change to the ABI changes the committed hash
+        let mut tampered = abi.clone();
+        tampered.functions[0].args.reverse();
+        assert_ne!(tampered.hash(), abi.hash());
+        assert_eq!(parse_deploy_link("SEND:abcd"), None);
+    }
+
+    #[test]
+    fn test_encode_call_validates_args() {
+        let abi = token_abi();
+        let to = format!("LOSCon{}", "ab".repeat(16));
+
+        let positional = abi
+            .encode_call("transfer", &[to.clone(), "100".to_string()])
+            .unwrap();
+        assert_eq!(positional, vec![to.clone(), "100".to_string()]);
+
+        // Named args may come in any order
+        let named = abi
+            .encode_call(
+                "transfer",
+                &["amount=100".to_string(), format!("to={}", to)],
+            )
+            .unwrap();
+        assert_eq!(named, positional);
+
+        assert!(abi
+            .encode_call("transfer", std::slice::from_ref(&to))
+            .unwrap_err()
+            .contains("takes 2 argument(s)"));
+        assert!(abi
+            .encode_call("transfer", &[to, "-5".to_string()])
+            .unwrap_err()
+            .contains("expected u128"));
+        assert!(abi
+            .encode_call("transfer", &["bob".to_string(), "1".to_string()])
+            .unwrap_err()
+            .contains("expected address"));
+        assert!(abi.encode_call("mint", &[]).is_err());
+    }
+
+    #[test]
+    fn test_optional_args_may_be_omitted() {
+        let abi = ContractAbi::from_json(
+            br#"{"version": 1, "functions": [{"name": "create_pool", "args": [
+                {"name": "token_a", "type": "string"},
+                {"name": "fee_bps", "type": "u128", "optional": true},
+                {"name": "operator", "type": "address", "optional": true}
+            ]}]}"#,
+        )
+        .unwrap();
+        let encode = |args: &[&str]| {
+            let args: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+            abi.encode_call("create_pool", &args)
+        };
+        assert_eq!(encode(&["LOS"]).unwrap(), vec!["LOS"]);
+        // Empty optionals skip type checks; trailing ones are dropped
+        assert_eq!(encode(&["LOS", "", ""]).unwrap(), vec!["LOS"]);
+        assert!(encode(&["LOS", "", "bad"])
+            .unwrap_err()
+            .contains("expected address"));
+        assert_eq!(
+            encode(&["token_a=LOS", "fee_bps=30"]).unwrap(),
+            vec!["LOS", "30"]
+        );
+        assert!(encode(&[]).unwrap_err().contains("takes 1-3 argument(s)"));
+    }
+
+    #[test]
+    fn test_validate_rejects_duplicates() {
+        let mut abi = token_abi();
+        abi.functions.push(abi.functions[0].clone());
+        assert!(abi.validate().unwrap_err().contains("Duplicate"));
+
+        let mut abi = token_abi();
+        abi.version = 2;
+        assert!(abi.validate().is_err());
+    }
+}
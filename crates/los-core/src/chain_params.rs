@@ -0,0 +1,461 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - RUNTIME CHAIN PARAMETERS
+//
+// Economic and network knobs loaded from genesis instead of compiled in:
+// - `ChainParams`: one complete, versioned parameter set
+// - `ChainParamsSchedule`: the genesis set plus upgrades that override
+//   individual values from an activation time (unix seconds)
+//
+// A block is checked against the parameters in force at its own signed
+// timestamp (`Ledger::chain_params_at`), never at a node-local height, so every
+// validator applies the same set to the same block whenever it arrives.
+//
+// `chain_id` and `min_pow_difficulty_bits` are fixed at genesis: they enter
+// every block's signing hash / PoW check, so changing them mid-chain would
+// invalidate history. The running process adopts them via `set_network_params`.
+//
+// The compile-time constants in lib.rs remain the mainnet/testnet defaults
+// used when a genesis file carries no `chain_params` section.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use crate::{
+    BASE_FEE_CIL, CHAIN_ID, DEFAULT_GAS_LIMIT, MAX_MINT_PER_BLOCK_CIL, MIN_POW_DIFFICULTY_BITS,
+    MIN_VALIDATOR_STAKE_CIL, REWARD_EPOCH_SECS, REWARD_RATE_INITIAL_CIL, TESTNET_REWARD_EPOCH_SECS,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+
+/// Version of the genesis parameter set
+pub const CHAIN_PARAMS_VERSION: u32 = 1;
+
+/// Parameter-set version from which Send blocks may carry a memo.
+/// Existing chains enable memos with an upgrade to this version.
+pub const MEMO_PARAMS_VERSION: u32 = 2;
+
+/// Accepted PoW difficulty range (leading zero bits)
+pub const POW_DIFFICULTY_BITS_RANGE: (u32, u32) = (8, 32);
+
+static ACTIVE_CHAIN_ID: AtomicU64 = AtomicU64::new(CHAIN_ID);
+static ACTIVE_POW_BITS: AtomicU32 = AtomicU32::new(MIN_POW_DIFFICULTY_BITS);
+
+/// Chain ID mixed into every block's signing hash (replay protection)
+pub fn chain_id() -> u64 {
+    ACTIVE_CHAIN_ID.load(Ordering::Relaxed)
+}
+
+/// PoW difficulty enforced by `Block::verify_pow`
+pub fn pow_difficulty_bits() -> u32 {
+    ACTIVE_POW_BITS.load(Ordering::Relaxed)
+}
+
+/// Label of the active chain: `los-mainnet`, `los-testnet`, or `los-devnet` for any other ID
+pub fn network_name() -> &'static str {
+    match chain_id() {
+        1 => "los-mainnet",
+        2 => "los-testnet",
+        _ => "los-devnet",
+    }
+}
+
+/// Adopt the genesis-fixed parameters of `params` for this process.
+/// Called once at startup, before any block is signed or verified.
+pub fn set_network_params(params: &ChainParams) {
+    ACTIVE_CHAIN_ID.store(params.chain_id, Ordering::Relaxed);
+    ACTIVE_POW_BITS.store(params.min_pow_difficulty_bits, Ordering::Relaxed);
+}
+
+/// Complete parameter set in force at some time
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct ChainParams {
+    /// Parameter-set version: genesis version, or that of the last activated upgrade
+    #[serde(default = "default_version")]
+    pub version: u32,
+    pub chain_id: u64,
+    pub min_pow_difficulty_bits: u32,
+    pub base_fee_cil: u128,
+    pub min_validator_stake_cil: u128,
+    pub max_mint_per_block_cil: u128,
+    pub reward_rate_initial_cil: u128,
+    pub reward_epoch_secs: u64,
+    pub default_gas_limit: u64,
+    /// Per-call gas cap below the VM's built-in MAX_GAS_PER_CALL (which always
+    /// applies). Unset keeps the built-in cap.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_gas_per_call: Option<u64>,
+}
+
+fn default_version() -> u32 {
+    CHAIN_PARAMS_VERSION
+}
+
+impl ChainParams {
+    pub fn mainnet() -> Self {
+        Self {
+            version: CHAIN_PARAMS_VERSION,
+            chain_id: 1,
+            min_pow_difficulty_bits: MIN_POW_DIFFICULTY_BITS,
+            base_fee_cil: BASE_FEE_CIL,
+            min_validator_stake_cil: MIN_VALIDATOR_STAKE_CIL,
+            max_mint_per_block_cil: MAX_MINT_PER_BLOCK_CIL,
+            reward_rate_initial_cil: REWARD_RATE_INITIAL_CIL,
+            reward_epoch_secs: REWARD_EPOCH_SECS,
+            default_gas_limit: DEFAULT_GAS_LIMIT,
+            max_gas_per_call: None,
+        }
+    }
+
+    pub fn testnet() -> Self {
+        Self {
+            chain_id: 2,
+            reward_epoch_secs: TESTNET_REWARD_EPOCH_SECS,
+            ..Self::mainnet()
+        }
+    }
+
+    /// Defaults of the network this binary was compiled for (`CHAIN_ID`)
+    pub fn for_build() -> Self {
+        if CHAIN_ID == 1 {
+            Self::mainnet()
+        } else {
+            Self::testnet()
+        }
+    }
+
+    pub fn is_mainnet(&self) -> bool {
+        self.chain_id == 1
+    }
+
+    /// Reject values that would halt the chain or reopen zero-fee spam
+    pub fn validate(&self) -> Result<(), String> {
+        let (min_bits, max_bits) = POW_DIFFICULTY_BITS_RANGE;
+        if !(min_bits..=max_bits).contains(&self.min_pow_difficulty_bits) {
+            return Err(format!(
+                "min_pow_difficulty_bits {} outside {}..={}",
+                self.min_pow_difficulty_bits, min_bits, max_bits
+            ));
+        }
+        if self.chain_id == 0 {
+            return Err("chain_id must be non-zero".to_string());
+        }
+        for (name, value) in [
+            ("base_fee_cil", self.base_fee_cil),
+            ("min_validator_stake_cil", self.min_validator_stake_cil),
+            ("max_mint_per_block_cil", self.max_mint_per_block_cil),
+            ("reward_epoch_secs", self.reward_epoch_secs as u128),
+            ("default_gas_limit", self.default_gas_limit as u128),
+        ] {
+            if value == 0 {
+                return Err(format!("{} must be greater than 0", name));
+            }
+        }
+        if let Some(cap) = self.max_gas_per_call {
+            if cap < self.default_gas_limit {
+                return Err(format!(
+                    "max_gas_per_call {} is below default_gas_limit {}",
+                    cap, self.default_gas_limit
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Values changed for blocks timestamped at or after `activation_time` (unix
+/// seconds). Unset fields keep their previous value.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct ParamsUpgrade {
+    pub version: u32,
+    pub activation_time: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_fee_cil: Option<u128>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_validator_stake_cil: Option<u128>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_mint_per_block_cil: Option<u128>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reward_rate_initial_cil: Option<u128>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reward_epoch_secs: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_gas_limit: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_gas_per_call: Option<u64>,
+}
+
+impl ParamsUpgrade {
+    fn apply(&self, params: &mut ChainParams) {
+        params.version = self.version;
+        if let Some(v) = self.base_fee_cil {
+            params.base_fee_cil = v;
+        }
+        if let Some(v) = self.min_validator_stake_cil {
+            params.min_validator_stake_cil = v;
+        }
+        if let Some(v) = self.max_mint_per_block_cil {
+            params.max_mint_per_block_cil = v;
+        }
+        if let Some(v) = self.reward_rate_initial_cil {
+            params.reward_rate_initial_cil = v;
+        }
+        if let Some(v) = self.reward_epoch_secs {
+            params.reward_epoch_secs = v;
+        }
+        if let Some(v) = self.default_gas_limit {
+            params.default_gas_limit = v;
+        }
+        if let Some(v) = self.max_gas_per_call {
+            params.max_gas_per_call = Some(v);
+        }
+    }
+}
+
+/// Genesis parameters plus scheduled upgrades (the `chain_params` genesis section)
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct ChainParamsSchedule {
+    pub genesis: ChainParams,
+    #[serde(default)]
+    pub upgrades: Vec<ParamsUpgrade>,
+}
+
+impl Default for ChainParamsSchedule {
+    fn default() -> Self {
+        Self::new(ChainParams::for_build())
+    }
+}
+
+impl ChainParamsSchedule {
+    pub fn new(genesis: ChainParams) -> Self {
+        Self {
+            genesis,
+            upgrades: Vec::new(),
+        }
+    }
+
+    /// Parameters in force for a block timestamped `time`
+    pub fn at(&self, time: u64) -> ChainParams {
+        let mut params = self.genesis.clone();
+        for upgrade in self
+            .upgrades
+            .iter()
+            .take_while(|u| u.activation_time <= time)
+        {
+            upgrade.apply(&mut params);
+        }
+        params
+    }
+
+    /// Versions and activation times must strictly increase, and every
+    /// intermediate parameter set must be valid.
+    pub fn validate(&self) -> Result<(), String> {
+        self.genesis.validate()?;
+        let mut version = self.genesis.version;
+        let mut time = 0u64;
+        let mut params = self.genesis.clone();
+        for upgrade in &self.upgrades {
+            if upgrade.version <= version {
+                return Err(format!(
+                    "Upgrade version {} must be greater than {}",
+                    upgrade.version, version
+                ));
+            }
+            if upgrade.activation_time <= time {
+                return Err(format!(
+                    "Upgrade v{} activation time {} must be greater than {}",
+                    upgrade.version, upgrade.activation_time, time
+                ));
+            }
+            upgrade.apply(&mut params);
+            params
+                .validate()
+                .map_err(|e| format!("Upgrade v{}: {}", upgrade.version, e))?;
+            version = upgrade.version;
+            time = upgrade.activation_time;
+        }
+        Ok(())
+    }
+
+    /// Check that `newer` (e.g. from an updated genesis file) only schedules
+    /// changes after `time`: upgrades blocks may already have been checked
+    /// against must stay identical.
+    pub fn ensure_extends(&self, newer: &ChainParamsSchedule, time: u64) -> Result<(), String> {
+        if self.genesis != newer.genesis {
+            return Err("genesis chain parameters differ from the stored chain".to_string());
+        }
+        let applied = |s: &ChainParamsSchedule| -> Vec<ParamsUpgrade> {
+            s.upgrades
+                .iter()
+                .filter(|u| u.activation_time <= time)
+                .cloned()
+                .collect()
+        };
+        if applied(self) != applied(newer) {
+            return Err(format!(
+                "upgrades activated at or before time {} differ from the stored chain",
+                time
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schedule() -> ChainParamsSchedule {
+        ChainParamsSchedule {
+            genesis: ChainParams::testnet(),
+            upgrades: vec![
+                ParamsUpgrade {
+                    version: 2,
+                    activation_time: 100,
+                    base_fee_cil: Some(200_000),
+                    ..Default::default()
+                },
+                ParamsUpgrade {
+                    version: 3,
+                    activation_time: 500,
+                    default_gas_limit: Some(2_000_000),
+                    ..Default::default()
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_schedule_activates_by_time() {
+        let s = schedule();
+        assert!(s.validate().is_ok());
+        assert_eq!(s.at(0), ChainParams::testnet());
+        assert_eq!(s.at(99).base_fee_cil, BASE_FEE_CIL);
+
+        let v2 = s.at(100);
+        assert_eq!((v2.version, v2.base_fee_cil), (2, 200_000));
+        assert_eq!(v2.default_gas_limit, DEFAULT_GAS_LIMIT);
+
+        let v3 = s.at(10_000);
+        assert_eq!(v3.version, 3);
+        assert_eq!(v3.max_gas_per_call, None);
+        assert_eq!(v3.base_fee_cil, 200_000, "earlier upgrades stay applied");
+        assert_eq!(v3.default_gas_limit, 2_000_000);
+    }
+
+    #[test]
+    fn test_schedule_validation() {
+        let mut s = schedule();
+        s.upgrades[1].version = 2;
+        assert!(s.validate().unwrap_err().contains("version"));
+
+        let mut s = schedule();
+        s.upgrades[1].activation_time = 100;
+        assert!(s.validate().unwrap_err().contains("activation time"));
+
+        let mut s = schedule();
+        s.upgrades[0].base_fee_cil = Some(0);
+        assert!(s.validate().unwrap_err().contains("base_fee_cil"));
+
+        let mut s = schedule();
+        s.genesis.min_pow_difficulty_bits = 64;
+        assert!(s.validate().is_err());
+
+        let mut s = schedule();
+        s.upgrades[0].max_gas_per_call = Some(1);
+        assert!(s.validate().unwrap_err().contains("max_gas_per_call"));
+    }
+
+    #[test]
+    fn test_gas_cap_activates_by_time() {
+        let mut s = schedule();
+        s.upgrades.push(ParamsUpgrade {
+            version: 4,
+            activation_time: 800,
+            max_gas_per_call: Some(100_000_000),
+            ..Default::default()
+        });
+        assert!(s.validate().is_ok());
+        assert_eq!(s.at(799).max_gas_per_call, None);
+        assert_eq!(s.at(800).max_gas_per_call, Some(100_000_000));
+    }
+
+    #[test]
+    fn test_ensure_extends_protects_applied_history() {
+        let stored = schedule();
+        let mut newer = schedule();
+        newer.upgrades.push(ParamsUpgrade {
+            version: 4,
+            activation_time: 900,
+            reward_epoch_secs: Some(600),
+            ..Default::default()
+        });
+        assert!(stored.ensure_extends(&newer, 600).is_ok());
+
+        // Rewriting an upgrade that already activated forks the chain
+        newer.upgrades[0].base_fee_cil = Some(300_000);
+        assert!(stored.ensure_extends(&newer, 600).is_err());
+        // ...but is fine while it is still in the future
+        assert!(stored.ensure_extends(&newer, 50).is_ok());
+
+        let mut other_chain = schedule();
+        other_chain.genesis.chain_id = 1337;
+        assert!(stored.ensure_extends(&other_chain, 0).is_err());
+    }
+
+    #[test]
+    fn test_ledger_params_follow_schedule_and_governance() {
+        let mut ledger = crate::Ledger::with_chain_params(schedule());
+
+        // Blocks are checked against the set in force at their own timestamp
+        assert_eq!(ledger.chain_params_at(99).base_fee_cil, BASE_FEE_CIL);
+        assert_eq!(ledger.chain_params_at(100).base_fee_cil, 200_000);
+        // API defaults follow the wall clock, long past both upgrades
+        assert_eq!(ledger.base_fee_cil(), 200_000);
+
+        // Governance overrides the scheduled value from its activation time
+        use crate::governance::{GovParam, Proposal, ProposalStatus};
+        ledger.governance.proposals.insert(
+            "p1".to_string(),
+            Proposal {
+                id: "p1".to_string(),
+                proposer: "LOS_USER".to_string(),
+                param: GovParam::BaseFeeCil,
+                value: 300_000,
+                deposit_cil: 0,
+                submitted_time: 0,
+                voting_end_time: 0,
+                activation_time: 100,
+                votes: Default::default(),
+                status: ProposalStatus::Passed,
+                tally: None,
+                settlement: None,
+            },
+        );
+        let params = ledger.chain_params_at(100);
+        assert_eq!((params.version, params.base_fee_cil), (2, 300_000));
+        assert_eq!(ledger.chain_params_at(99).base_fee_cil, BASE_FEE_CIL);
+    }
+
+    #[test]
+    fn test_genesis_section_json() {
+        let json = r#"{
+            "genesis": {
+                "chain_id": 1337, "min_pow_difficulty_bits": 8,
+                "base_fee_cil": 1000, "min_validator_stake_cil": 100000000000000,
+                "max_mint_per_block_cil": 100000000000000,
+                "reward_rate_initial_cil": 500000000000000,
+                "reward_epoch_secs": 60, "default_gas_limit": 1000000
+            },
+            "upgrades": [{"version": 2, "activation_time": 50, "base_fee_cil": 2000}]
+        }"#;
+        let s: ChainParamsSchedule = serde_json::from_str(json).unwrap();
+        assert!(s.validate().is_ok());
+        assert_eq!(s.genesis.version, CHAIN_PARAMS_VERSION);
+        assert_eq!(s.at(50).base_fee_cil, 2000);
+
+        // chain_id cannot be changed by an upgrade
+        let bad = json.replace(r#""base_fee_cil": 2000"#, r#""chain_id": 7"#);
+        assert!(serde_json::from_str::<ChainParamsSchedule>(&bad).is_err());
+    }
+}
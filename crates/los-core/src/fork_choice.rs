@@ -0,0 +1,523 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - ACCOUNT-CHAIN FORK CHOICE
+//
+// A fork is two or more signed blocks on the same account with the same
+// `previous`. The ledger only applies one of them; the rest are kept here.
+// - Competing candidates are tracked per (account, previous)
+// - Validators vote for one candidate; votes are weighted by voting power
+// - Once a candidate has a quorum the fork is resolved: if the ledger holds
+//   the loser, the losing branch is rolled back and the winner applied
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use crate::{Block, Ledger};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+
+/// Max competing blocks kept for one predecessor (anti-spam)
+pub const MAX_FORK_CANDIDATES: usize = 8;
+/// Max unresolved forks tracked at once
+pub const MAX_TRACKED_FORKS: usize = 1_024;
+/// Max fork events kept for the API
+pub const MAX_FORK_EVENTS: usize = 256;
+/// How far behind an account head a conflicting block may attach
+pub const MAX_FORK_DEPTH: usize = 64;
+/// Max blocks undone by a single resolution (including cascaded Receives)
+pub const MAX_ROLLBACK_BLOCKS: usize = 256;
+
+/// Map key for a fork: one entry per (account, previous)
+pub fn fork_key(account: &str, previous: &str) -> String {
+    format!("{}:{}", account, previous)
+}
+
+/// Competing blocks that share a predecessor
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForkSet {
+    pub account: String,
+    pub previous: String,
+    /// Block hash → block
+    pub candidates: BTreeMap<String, Block>,
+    /// Candidate hash → (voter → voting power)
+    pub votes: BTreeMap<String, BTreeMap<String, u128>>,
+    pub detected_at: u64,
+}
+
+impl ForkSet {
+    /// Total voting power behind a candidate
+    pub fn tally(&self, hash: &str) -> u128 {
+        self.votes
+            .get(hash)
+            .map(|v| v.values().fold(0u128, |acc, p| acc.saturating_add(*p)))
+            .unwrap_or(0)
+    }
+
+    /// Candidate with the most voting power.
+    /// Ties break on the smaller hash so every node picks the same one.
+    pub fn leading(&self) -> Option<(String, u128)> {
+        self.candidates
+            .keys()
+            .map(|h| (h.clone(), self.tally(h)))
+            .fold(None, |best, (h, t)| match best {
+                Some((_, bt)) if bt >= t => best,
+                _ => Some((h, t)),
+            })
+    }
+
+    /// Candidate a validator should vote for: the one its ledger applied,
+    /// otherwise the smallest hash
+    pub fn preferred(&self, ledger: &Ledger) -> Option<String> {
+        self.candidates
+            .keys()
+            .find(|h| ledger.blocks.contains_key(*h))
+            .or_else(|| self.candidates.keys().next())
+            .cloned()
+    }
+
+    pub fn has_voted(&self, voter: &str) -> bool {
+        self.votes.values().any(|v| v.contains_key(voter))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ForkEventKind {
+    Detected,
+    Resolved,
+}
+
+/// Entry in the fork history exposed via the API
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForkEvent {
+    pub kind: ForkEventKind,
+    pub account: String,
+    pub previous: String,
+    pub candidates: Vec<String>,
+    pub winner: Option<String>,
+    /// Hashes undone by the resolution (empty if the ledger already held the winner)
+    pub rolled_back: Vec<String>,
+    pub timestamp: u64,
+}
+
+/// Tracks unresolved forks and the votes cast on them
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ForkChoice {
+    forks: BTreeMap<String, ForkSet>,
+    events: VecDeque<ForkEvent>,
+    pub detected_total: u64,
+    pub resolved_total: u64,
+    pub rolled_back_total: u64,
+}
+
+impl ForkChoice {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a block that competes with `existing` (the one the ledger applied).
+    /// Returns true if this opened a new fork.
+    pub fn observe(
+        &mut self,
+        existing: &Block,
+        incoming: &Block,
+        timestamp: u64,
+    ) -> Result<bool, String> {
+        if existing.account != incoming.account || existing.previous != incoming.previous {
+            return Err("Fork Error: blocks do not share account and predecessor".to_string());
+        }
+        let existing_hash = existing.calculate_hash();
+        let incoming_hash = incoming.calculate_hash();
+        if existing_hash == incoming_hash {
+            return Err("Fork Error: blocks are identical".to_string());
+        }
+
+        let key = fork_key(&incoming.account, &incoming.previous);
+        if let Some(fork) = self.forks.get_mut(&key) {
+            if !fork.candidates.contains_key(&incoming_hash) {
+                if fork.candidates.len() >= MAX_FORK_CANDIDATES {
+                    return Err(format!(
+                        "Fork Error: more than {} candidates for one predecessor",
+                        MAX_FORK_CANDIDATES
+                    ));
+                }
+                fork.candidates.insert(incoming_hash, incoming.clone());
+            }
+            return Ok(false);
+        }
+
+        if self.forks.len() >= MAX_TRACKED_FORKS {
+            return Err(format!(
+                "Fork Error: already tracking {} unresolved forks",
+                MAX_TRACKED_FORKS
+            ));
+        }
+
+        let mut candidates = BTreeMap::new();
+        candidates.insert(existing_hash, existing.clone());
+        candidates.insert(incoming_hash, incoming.clone());
+        self.push_event(ForkEvent {
+            kind: ForkEventKind::Detected,
+            account: incoming.account.clone(),
+            previous: incoming.previous.clone(),
+            candidates: candidates.keys().cloned().collect(),
+            winner: None,
+            rolled_back: Vec::new(),
+            timestamp,
+        });
+        self.forks.insert(
+            key,
+            ForkSet {
+                account: incoming.account.clone(),
+                previous: incoming.previous.clone(),
+                candidates,
+                votes: BTreeMap::new(),
+                detected_at: timestamp,
+            },
+        );
+        self.detected_total += 1;
+        Ok(true)
+    }
+
+    /// Count one validator's vote. Each voter gets one vote per fork;
+    /// later votes from the same voter are ignored (returns false).
+    pub fn record_vote(
+        &mut self,
+        account: &str,
+        previous: &str,
+        hash: &str,
+        voter: &str,
+        power: u128,
+    ) -> Result<bool, String> {
+        let fork = self
+            .forks
+            .get_mut(&fork_key(account, previous))
+            .ok_or_else(|| "Fork Error: no such fork".to_string())?;
+        if !fork.candidates.contains_key(hash) {
+            return Err(format!("Fork Error: {} is not a candidate", hash));
+        }
+        if fork.has_voted(voter) {
+            return Ok(false);
+        }
+        fork.votes
+            .entry(hash.to_string())
+            .or_default()
+            .insert(voter.to_string(), power);
+        Ok(true)
+    }
+
+    /// Winning candidate, once its voting power exceeds `quorum_power`
+    pub fn decided(&self, key: &str, quorum_power: u128) -> Option<String> {
+        let (hash, power) = self.forks.get(key)?.leading()?;
+        (power > quorum_power).then_some(hash)
+    }
+
+    /// Close a fork in favour of `winner`, switching the ledger's branch if it
+    /// currently holds a losing candidate. Returns the rolled-back blocks.
+    pub fn resolve(
+        &mut self,
+        ledger: &mut Ledger,
+        key: &str,
+        winner: &str,
+        timestamp: u64,
+    ) -> Result<Vec<Block>, String> {
+        let fork = self
+            .forks
+            .get(key)
+            .ok_or_else(|| "Fork Error: no such fork".to_string())?;
+        let winning_block = fork
+            .candidates
+            .get(winner)
+            .cloned()
+            .ok_or_else(|| format!("Fork Error: {} is not a candidate", winner))?;
+
+        let rolled_back = if ledger.blocks.contains_key(winner) {
+            Vec::new()
+        } else {
+            ledger.switch_branch(&winning_block)?
+        };
+
+        if let Some(fork) = self.forks.remove(key) {
+            self.push_event(ForkEvent {
+                kind: ForkEventKind::Resolved,
+                account: fork.account,
+                previous: fork.previous,
+                candidates: fork.candidates.keys().cloned().collect(),
+                winner: Some(winner.to_string()),
+                rolled_back: rolled_back.iter().map(|b| b.calculate_hash()).collect(),
+                timestamp,
+            });
+        }
+        self.resolved_total += 1;
+        self.rolled_back_total += rolled_back.len() as u64;
+        Ok(rolled_back)
+    }
+
+    pub fn get(&self, key: &str) -> Option<&ForkSet> {
+        self.forks.get(key)
+    }
+
+    /// Unresolved fork that has `hash` as a candidate
+    pub fn fork_of(&self, hash: &str) -> Option<&ForkSet> {
+        self.forks
+            .values()
+            .find(|f| f.candidates.contains_key(hash))
+    }
+
+    /// Unresolved forks
+    pub fn active(&self) -> impl Iterator<Item = &ForkSet> {
+        self.forks.values()
+    }
+
+    /// Recent fork events, oldest first
+    pub fn events(&self) -> impl Iterator<Item = &ForkEvent> {
+        self.events.iter()
+    }
+
+    fn push_event(&mut self, event: ForkEvent) {
+        if self.events.len() >= MAX_FORK_EVENTS {
+            self.events.pop_front();
+        }
+        self.events.push_back(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BlockType, CIL_PER_LOS};
+
+    fn unsigned(account: &str, previous: &str, amount: u128) -> Block {
+        Block {
+            account: account.to_string(),
+            previous: previous.to_string(),
+            block_type: BlockType::Send,
+            amount,
+            link: "LOS_DEST".to_string(),
+            signature: String::new(),
+            public_key: String::new(),
+            work: 0,
+            timestamp: 1_700_000_000,
+            fee: 100_000,
+            memo: None,
+        }
+    }
+
+    /// Apply a block's state transition without PoW/signature checks
+    fn apply(ledger: &mut Ledger, block: &Block) -> String {
+        let hash = block.calculate_hash();
+        let state = ledger
+            .accounts
+            .entry(block.account.clone())
+            .or_insert(crate::AccountState {
+                head: "0".to_string(),
+                balance: 0,
+                block_count: 0,
+                is_validator: false,
+            });
+        match block.block_type {
+            BlockType::Mint => {
+                state.balance += block.amount;
+                ledger.distribution.remaining_supply -= block.amount;
+            }
+            BlockType::Send => {
+                state.balance -= block.amount + block.fee;
+                ledger.accumulated_fees_cil += block.fee;
+            }
+            BlockType::Receive => {
+                state.balance += block.amount;
+                ledger.claimed_sends.insert(block.link.clone());
+            }
+            _ => {}
+        }
+        state.head = hash.clone();
+        state.block_count += 1;
+        ledger.blocks.insert(hash.clone(), block.clone());
+        ledger.index_block(&hash, block);
+        hash
+    }
+
+    #[test]
+    fn test_observe_and_vote_resolution() {
+        let a = unsigned("LOS_A", "prev", 1);
+        let b = unsigned("LOS_A", "prev", 2);
+        let (ha, hb) = (a.calculate_hash(), b.calculate_hash());
+        let key = fork_key("LOS_A", "prev");
+
+        let mut fc = ForkChoice::new();
+        assert!(fc.observe(&a, &b, 1).unwrap());
+        assert!(!fc.observe(&a, &b, 2).unwrap());
+        assert!(fc.observe(&a, &a, 2).is_err());
+        assert!(fc.observe(&a, &unsigned("LOS_B", "prev", 3), 2).is_err());
+        assert_eq!(fc.detected_total, 1);
+        assert_eq!(fc.fork_of(&hb).map(|f| f.previous.as_str()), Some("prev"));
+        assert!(fc.fork_of("nope").is_none());
+
+        assert!(fc.record_vote("LOS_A", "prev", &hb, "V1", 40).unwrap());
+        // Second vote from the same validator is ignored, even for another candidate
+        assert!(!fc.record_vote("LOS_A", "prev", &ha, "V1", 40).unwrap());
+        assert!(fc.record_vote("LOS_A", "prev", "nope", "V2", 40).is_err());
+        assert_eq!(fc.decided(&key, 66), None);
+
+        fc.record_vote("LOS_A", "prev", &hb, "V2", 30).unwrap();
+        assert_eq!(fc.decided(&key, 66), Some(hb));
+    }
+
+    #[test]
+    fn test_candidate_limit() {
+        let base = unsigned("LOS_A", "prev", 0);
+        let mut fc = ForkChoice::new();
+        for i in 1..MAX_FORK_CANDIDATES as u128 {
+            fc.observe(&base, &unsigned("LOS_A", "prev", i), 0).unwrap();
+        }
+        assert!(fc
+            .observe(&base, &unsigned("LOS_A", "prev", 999), 0)
+            .is_err());
+    }
+
+    #[test]
+    fn test_tie_breaks_on_smaller_hash() {
+        let a = unsigned("LOS_A", "prev", 1);
+        let b = unsigned("LOS_A", "prev", 2);
+        let mut fc = ForkChoice::new();
+        fc.observe(&a, &b, 0).unwrap();
+        let smaller = a.calculate_hash().min(b.calculate_hash());
+        let fork = fc.get(&fork_key("LOS_A", "prev")).unwrap();
+        assert_eq!(fork.leading().map(|(h, _)| h), Some(smaller));
+    }
+
+    #[test]
+    fn test_rollback_cascades_into_receives() {
+        let mut ledger = Ledger::new();
+        let supply = ledger.distribution.remaining_supply;
+        let mut mint = unsigned("LOS_ALICE", "0", 100 * CIL_PER_LOS);
+        mint.block_type = BlockType::Mint;
+        mint.fee = 0;
+        let mint_hash = apply(&mut ledger, &mint);
+
+        let mut to_bob = unsigned("LOS_ALICE", &mint_hash, 60 * CIL_PER_LOS);
+        to_bob.link = "LOS_BOB".to_string();
+        let bob_send = apply(&mut ledger, &to_bob);
+
+        let mut receive = unsigned("LOS_BOB", "0", 60 * CIL_PER_LOS);
+        receive.block_type = BlockType::Receive;
+        receive.link = bob_send.clone();
+        receive.fee = 0;
+        let receive_hash = apply(&mut ledger, &receive);
+
+        // Bob spends part of the received funds; that must be undone too
+        let mut bob_spend = unsigned("LOS_BOB", &receive_hash, 10 * CIL_PER_LOS);
+        bob_spend.link = "LOS_DAVE".to_string();
+        let bob_spend_hash = apply(&mut ledger, &bob_spend);
+
+        // A competing Send from Alice attaches to the same predecessor
+        let mut to_carol = unsigned("LOS_ALICE", &mint_hash, 70 * CIL_PER_LOS);
+        to_carol.link = "LOS_CAROL".to_string();
+        assert_eq!(ledger.find_conflict(&to_carol), Some(bob_send.clone()));
+        assert_eq!(ledger.find_conflict(&to_bob), None);
+
+        let rolled = ledger.rollback_to("LOS_ALICE", &mint_hash).unwrap();
+        assert_eq!(
+            rolled
+                .iter()
+                .map(|b| b.calculate_hash())
+                .collect::<Vec<_>>(),
+            vec![bob_spend_hash, receive_hash, bob_send.clone()]
+        );
+
+        let alice = &ledger.accounts["LOS_ALICE"];
+        assert_eq!(
+            (alice.head.as_str(), alice.balance),
+            (mint_hash.as_str(), 100 * CIL_PER_LOS)
+        );
+        assert_eq!(alice.block_count, 1);
+        let bob = &ledger.accounts["LOS_BOB"];
+        assert_eq!(
+            (bob.head.as_str(), bob.balance, bob.block_count),
+            ("0", 0, 0)
+        );
+        assert!(!ledger.claimed_sends.contains(&bob_send));
+        assert_eq!(ledger.accumulated_fees_cil, 0);
+        assert_eq!(ledger.blocks.len(), 1);
+
+        // Undoing the Mint restores the distribution supply
+        ledger.rollback_to("LOS_ALICE", "0").unwrap();
+        assert_eq!(ledger.distribution.remaining_supply, supply);
+    }
+
+    #[test]
+    fn test_rollback_refuses_final_blocks() {
+        let mut ledger = Ledger::new();
+        let mut mint = unsigned("LOS_A", "0", CIL_PER_LOS);
+        mint.block_type = BlockType::Mint;
+        let mint_hash = apply(&mut ledger, &mint);
+        let mut slash = unsigned("LOS_A", &mint_hash, 0);
+        slash.block_type = BlockType::Slash;
+        apply(&mut ledger, &slash);
+
+        assert!(ledger.rollback_to("LOS_A", &mint_hash).is_err());
+        assert!(ledger.rollback_to("LOS_A", "not-an-ancestor").is_err());
+        // Nothing was undone
+        assert_eq!(ledger.accounts["LOS_A"].block_count, 2);
+    }
+
+    #[test]
+    fn test_rollback_stops_at_the_finality_horizon() {
+        let mut ledger = Ledger::new();
+        let mut mint = unsigned("LOS_A", "0", 100 * CIL_PER_LOS);
+        mint.block_type = BlockType::Mint;
+        let mint_hash = apply(&mut ledger, &mint);
+        let mut to_b = unsigned("LOS_A", &mint_hash, 10 * CIL_PER_LOS);
+        to_b.link = "LOS_B".to_string();
+        let send_hash = apply(&mut ledger, &to_b);
+
+        // Covered by a checkpoint: final
+        ledger.finalized_height = 2;
+        let err = ledger.rollback_to("LOS_A", &mint_hash).unwrap_err();
+        assert!(err.contains("checkpoint"));
+        ledger.finalized_height = 0;
+
+        // Older than the prune horizon: final
+        ledger.pruned_before = to_b.timestamp + 1;
+        assert!(ledger.rollback_to("LOS_A", &mint_hash).is_err());
+        ledger.pruned_before = 0;
+
+        // A claimed Send whose Receive is no longer held cannot be undone
+        let mut receive = unsigned("LOS_B", "0", 10 * CIL_PER_LOS);
+        receive.block_type = BlockType::Receive;
+        receive.link = send_hash.clone();
+        let receive_hash = apply(&mut ledger, &receive);
+        let removed = ledger.blocks.remove(&receive_hash).unwrap();
+        ledger.unindex_block(&receive_hash, &removed);
+        let err = ledger.rollback_to("LOS_A", &mint_hash).unwrap_err();
+        assert!(err.contains("Receive"));
+        assert_eq!(ledger.accounts["LOS_A"].head, send_hash);
+    }
+
+    #[test]
+    fn test_resolve_keeps_ledger_when_winner_applied() {
+        let mut ledger = Ledger::new();
+        let mut mint = unsigned("LOS_A", "0", 100 * CIL_PER_LOS);
+        mint.block_type = BlockType::Mint;
+        let mint_hash = apply(&mut ledger, &mint);
+        let applied = unsigned("LOS_A", &mint_hash, 1);
+        let applied_hash = apply(&mut ledger, &applied);
+        let competing = unsigned("LOS_A", &mint_hash, 2);
+
+        let mut fc = ForkChoice::new();
+        fc.observe(&applied, &competing, 0).unwrap();
+        fc.record_vote("LOS_A", &mint_hash, &applied_hash, "V1", 10)
+            .unwrap();
+        let key = fork_key("LOS_A", &mint_hash);
+        let winner = fc.decided(&key, 0).unwrap();
+
+        assert!(fc
+            .resolve(&mut ledger, &key, &winner, 1)
+            .unwrap()
+            .is_empty());
+        assert_eq!(ledger.accounts["LOS_A"].head, applied_hash);
+        assert_eq!(fc.resolved_total, 1);
+        assert!(fc.active().next().is_none());
+        let kinds: Vec<_> = fc.events().map(|e| e.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![ForkEventKind::Detected, ForkEventKind::Resolved]
+        );
+    }
+}
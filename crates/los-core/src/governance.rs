@@ -0,0 +1,947 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - ON-CHAIN GOVERNANCE
+//
+// Parameter-change proposals as ledger operations. Every rule is decided by
+// data in the block (its signed timestamp, the account's own chain) or agreed
+// by a validator quorum, never by how far a node has synced:
+// - Proposal block (link = "PROPOSE:{param}:{value}:{activation_time}")
+//   locks `amount` as a deposit and opens a voting window of
+//   `effective_voting_period_secs()` from its timestamp
+// - Vote block (link = "VOTE:{proposal_id}:YES|NO") timestamped inside the
+//   window, from an account holding the validator stake minimum; a later
+//   vote replaces the earlier one. Validators confirm votes only from members
+//   of the running, quorum-signed validator epoch (`check_electorate`).
+// - Settlement: once the window closed, the proposer submits a Proposal block
+//   with link "SETTLE:{proposal_id}:{yes_power}:{no_power}:{total_power}",
+//   the √stake tally over the epoch's validators (`VotingSystem`). It passes
+//   with >50% of ALL their power, so abstaining counts against it.
+//   Validators confirm the block only if they compute the same tally.
+// - Passed: the settlement block's `amount` refunds the deposit and the value
+//   applies to blocks timestamped from `activation_time` on.
+//   Rejected: amount 0, deposit burned (anti-spam).
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use crate::anti_whale::AntiWhaleConfig;
+use crate::chain_params::ChainParams;
+use crate::voting::{VotingSystem, MAX_STAKE_FOR_VOTING_CIL};
+use crate::{
+    is_testnet_build, AccountState, Block, BlockType, CIL_PER_LOS, REWARD_RATE_INITIAL_CIL,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Minimum deposit locked by a Proposal block (100 LOS)
+pub const MIN_PROPOSAL_DEPOSIT_CIL: u128 = 100 * CIL_PER_LOS;
+
+/// Voting window in seconds (mainnet: 7 days)
+pub const GOVERNANCE_VOTING_PERIOD_SECS: u64 = 7 * 24 * 3600;
+
+/// Testnet voting window (for rapid testing of governance)
+pub const TESTNET_GOVERNANCE_VOTING_PERIOD_SECS: u64 = 600;
+
+/// Minimum time between a settlement and the activation it schedules
+/// (mainnet: 1 day), so the settlement reaches every node before any block
+/// is checked against the new value
+pub const GOVERNANCE_ACTIVATION_DELAY_SECS: u64 = 24 * 3600;
+
+/// Testnet activation delay
+pub const TESTNET_GOVERNANCE_ACTIVATION_DELAY_SECS: u64 = 120;
+
+/// Open proposals an account may have at once (anti-spam)
+pub const MAX_OPEN_PROPOSALS_PER_ACCOUNT: usize = 3;
+
+/// Get the effective voting window based on network type.
+pub const fn effective_voting_period_secs() -> u64 {
+    if is_testnet_build() {
+        TESTNET_GOVERNANCE_VOTING_PERIOD_SECS
+    } else {
+        GOVERNANCE_VOTING_PERIOD_SECS
+    }
+}
+
+/// Get the effective activation delay based on network type.
+pub const fn effective_activation_delay_secs() -> u64 {
+    if is_testnet_build() {
+        TESTNET_GOVERNANCE_ACTIVATION_DELAY_SECS
+    } else {
+        GOVERNANCE_ACTIVATION_DELAY_SECS
+    }
+}
+
+/// Chain parameters that governance can change
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum GovParam {
+    /// Minimum fee of Send, Proposal and Vote blocks (CIL)
+    BaseFeeCil,
+    /// Anti-whale cap on a single user Mint block (CIL)
+    MaxMintPerBlockCil,
+    /// Anti-whale: transactions per address before dynamic fees kick in
+    AntiWhaleMaxTxPerBlock,
+    /// Pin host-function gas schedule to this version (`los_vm::gas_schedule`)
+    GasScheduleVersion,
+    /// Validator reward rate per epoch before halving (CIL)
+    RewardRateCil,
+}
+
+impl GovParam {
+    pub const ALL: [GovParam; 5] = [
+        GovParam::BaseFeeCil,
+        GovParam::MaxMintPerBlockCil,
+        GovParam::AntiWhaleMaxTxPerBlock,
+        GovParam::GasScheduleVersion,
+        GovParam::RewardRateCil,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GovParam::BaseFeeCil => "base_fee_cil",
+            GovParam::MaxMintPerBlockCil => "max_mint_per_block_cil",
+            GovParam::AntiWhaleMaxTxPerBlock => "anti_whale_max_tx_per_block",
+            GovParam::GasScheduleVersion => "gas_schedule_version",
+            GovParam::RewardRateCil => "reward_rate_cil",
+        }
+    }
+
+    /// Value in `params` (the genesis schedule) used until governance
+    /// activates another. `None` for the gas schedule, which is otherwise
+    /// selected by the call's timestamp.
+    pub fn base_value(&self, params: &ChainParams) -> Option<u128> {
+        match self {
+            GovParam::BaseFeeCil => Some(params.base_fee_cil),
+            GovParam::MaxMintPerBlockCil => Some(params.max_mint_per_block_cil),
+            GovParam::AntiWhaleMaxTxPerBlock => {
+                Some(AntiWhaleConfig::new().max_tx_per_block as u128)
+            }
+            GovParam::GasScheduleVersion => None,
+            GovParam::RewardRateCil => Some(params.reward_rate_initial_cil),
+        }
+    }
+
+    /// Accepted value range (inclusive). Bounds keep a single proposal from
+    /// halting the chain, e.g. a base fee nobody can pay.
+    pub fn bounds(&self) -> (u128, u128) {
+        match self {
+            GovParam::BaseFeeCil => (1_000, CIL_PER_LOS),
+            GovParam::MaxMintPerBlockCil => (CIL_PER_LOS, 1_000_000 * CIL_PER_LOS),
+            GovParam::AntiWhaleMaxTxPerBlock => (1, 10_000),
+            GovParam::GasScheduleVersion => (0, u32::MAX as u128),
+            GovParam::RewardRateCil => (0, 10 * REWARD_RATE_INITIAL_CIL),
+        }
+    }
+}
+
+impl std::fmt::Display for GovParam {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for GovParam {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_ascii_lowercase();
+        GovParam::ALL
+            .into_iter()
+            .find(|p| p.as_str() == s)
+            .ok_or_else(|| {
+                format!(
+                    "Unknown governance parameter '{}' (expected one of: {})",
+                    s,
+                    GovParam::ALL.map(|p| p.as_str()).join(", ")
+                )
+            })
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ProposalStatus {
+    /// Not settled yet; votes are accepted until `voting_end_time`
+    Voting,
+    /// Settled in favour (deposit refunded), waiting for `activation_time`
+    Passed,
+    /// Settled against (deposit burned)
+    Rejected,
+    /// Passed and in force. Reported by `Proposal::status_at`, never stored.
+    Activated,
+}
+
+/// Stake-weighted result, carried by the settlement block
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ProposalTally {
+    pub yes_power: u128,
+    pub no_power: u128,
+    /// Power of every validator in the epoch, voting or not
+    pub total_power: u128,
+    /// yes_power / total_power in basis points (passes above 5000)
+    pub yes_bps: u32,
+}
+
+impl ProposalTally {
+    pub fn new(yes_power: u128, no_power: u128, total_power: u128) -> Result<Self, String> {
+        if yes_power.saturating_add(no_power) > total_power {
+            return Err(format!(
+                "Settlement Error: yes {} + no {} exceed total power {}",
+                yes_power, no_power, total_power
+            ));
+        }
+        Ok(Self {
+            yes_power,
+            no_power,
+            total_power,
+            yes_bps: (yes_power.saturating_mul(10_000))
+                .checked_div(total_power)
+                .unwrap_or(0) as u32,
+        })
+    }
+
+    /// Strictly more than 50% of all power voted yes
+    pub fn passed(&self) -> bool {
+        self.yes_bps > 5_000
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Proposal {
+    /// Hash of the Proposal block
+    pub id: String,
+    pub proposer: String,
+    pub param: GovParam,
+    pub value: u128,
+    pub deposit_cil: u128,
+    /// Timestamp of the Proposal block
+    pub submitted_time: u64,
+    /// Votes must be timestamped before this
+    pub voting_end_time: u64,
+    /// Blocks timestamped from here on use `value` once the proposal passed.
+    /// A late settlement moves it to settlement time + activation delay.
+    pub activation_time: u64,
+    /// Voter address → approve. MAINNET: BTreeMap for deterministic tallies.
+    pub votes: BTreeMap<String, bool>,
+    pub status: ProposalStatus,
+    #[serde(default)]
+    pub tally: Option<ProposalTally>,
+    /// Hash of the settlement block
+    #[serde(default)]
+    pub settlement: Option<String>,
+}
+
+impl Proposal {
+    /// Status for a block timestamped `time` (`Activated` once a passed value applies)
+    pub fn status_at(&self, time: u64) -> ProposalStatus {
+        if self.status == ProposalStatus::Passed && time >= self.activation_time {
+            ProposalStatus::Activated
+        } else {
+            self.status
+        }
+    }
+}
+
+/// All proposals, keyed by id
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct GovernanceState {
+    pub proposals: BTreeMap<String, Proposal>,
+}
+
+/// Link of a Proposal block
+pub fn proposal_link(param: GovParam, value: u128, activation_time: u64) -> String {
+    format!("PROPOSE:{}:{}:{}", param, value, activation_time)
+}
+
+/// Parse `PROPOSE:{param}:{value}:{activation_time}`
+pub fn parse_proposal_link(link: &str) -> Result<(GovParam, u128, u64), String> {
+    let body = link
+        .strip_prefix("PROPOSE:")
+        .ok_or("Proposal Error: link must start with 'PROPOSE:'")?;
+    let parts: Vec<&str> = body.split(':').collect();
+    if parts.len() != 3 {
+        return Err(
+            "Proposal Error: link must be PROPOSE:{param}:{value}:{activation_time}".to_string(),
+        );
+    }
+    let param: GovParam = parts[0].parse()?;
+    let value = parts[1]
+        .parse::<u128>()
+        .map_err(|_| format!("Proposal Error: invalid value '{}'", parts[1]))?;
+    let activation_time = parts[2]
+        .parse::<u64>()
+        .map_err(|_| format!("Proposal Error: invalid activation time '{}'", parts[2]))?;
+    Ok((param, value, activation_time))
+}
+
+/// Link of a settlement (Proposal block that closes `proposal_id`)
+pub fn settle_link(proposal_id: &str, tally: &ProposalTally) -> String {
+    format!(
+        "SETTLE:{}:{}:{}:{}",
+        proposal_id, tally.yes_power, tally.no_power, tally.total_power
+    )
+}
+
+/// Parse `SETTLE:{proposal_id}:{yes_power}:{no_power}:{total_power}`
+pub fn parse_settle_link(link: &str) -> Result<(String, ProposalTally), String> {
+    let body = link
+        .strip_prefix("SETTLE:")
+        .ok_or("Settlement Error: link must start with 'SETTLE:'")?;
+    let parts: Vec<&str> = body.split(':').collect();
+    if parts.len() != 4 || parts[0].is_empty() {
+        return Err(
+            "Settlement Error: link must be SETTLE:{proposal_id}:{yes_power}:{no_power}:{total_power}"
+                .to_string(),
+        );
+    }
+    let power = |s: &str| {
+        s.parse::<u128>()
+            .map_err(|_| format!("Settlement Error: invalid power '{}'", s))
+    };
+    let tally = ProposalTally::new(power(parts[1])?, power(parts[2])?, power(parts[3])?)?;
+    Ok((parts[0].to_string(), tally))
+}
+
+/// Link of a Vote block
+pub fn vote_link(proposal_id: &str, approve: bool) -> String {
+    format!(
+        "VOTE:{}:{}",
+        proposal_id,
+        if approve { "YES" } else { "NO" }
+    )
+}
+
+/// Parse `VOTE:{proposal_id}:YES|NO`
+pub fn parse_vote_link(link: &str) -> Result<(String, bool), String> {
+    let body = link
+        .strip_prefix("VOTE:")
+        .ok_or("Vote Error: link must start with 'VOTE:'")?;
+    let (id, choice) = body
+        .rsplit_once(':')
+        .ok_or("Vote Error: link must be VOTE:{proposal_id}:YES|NO")?;
+    let approve = match choice {
+        "YES" => true,
+        "NO" => false,
+        other => {
+            return Err(format!(
+                "Vote Error: choice must be YES or NO, got '{}'",
+                other
+            ))
+        }
+    };
+    if id.is_empty() {
+        return Err("Vote Error: missing proposal id".to_string());
+    }
+    Ok((id.to_string(), approve))
+}
+
+impl GovernanceState {
+    /// Value of `param` for a block timestamped `time`, if governance changed it
+    pub fn param_at(&self, param: GovParam, time: u64) -> Option<u128> {
+        // Activate in time order; same time → proposal id order
+        let mut due: Vec<&Proposal> = self
+            .proposals
+            .values()
+            .filter(|p| p.param == param && p.status_at(time) == ProposalStatus::Activated)
+            .collect();
+        due.sort_by_key(|p| p.activation_time);
+        due.last().map(|p| p.value)
+    }
+
+    /// Validate a Proposal block timestamped `time` and open its voting window.
+    /// The caller debits `deposit_cil` (plus fee) from the proposer.
+    pub fn submit(
+        &mut self,
+        id: &str,
+        proposer: &str,
+        deposit_cil: u128,
+        link: &str,
+        time: u64,
+    ) -> Result<(), String> {
+        let (param, value, activation_time) = parse_proposal_link(link)?;
+        let (min, max) = param.bounds();
+        if value < min || value > max {
+            return Err(format!(
+                "Proposal Error: {} must be within {}..={}, got {}",
+                param, min, max, value
+            ));
+        }
+        if deposit_cil < MIN_PROPOSAL_DEPOSIT_CIL {
+            return Err(format!(
+                "Proposal Error: deposit {} CIL < minimum {} CIL ({} LOS)",
+                deposit_cil,
+                MIN_PROPOSAL_DEPOSIT_CIL,
+                MIN_PROPOSAL_DEPOSIT_CIL / CIL_PER_LOS
+            ));
+        }
+        let voting_end_time = time.saturating_add(effective_voting_period_secs());
+        let earliest = voting_end_time.saturating_add(effective_activation_delay_secs());
+        if activation_time < earliest {
+            return Err(format!(
+                "Proposal Error: activation time {} must be at least {} (voting ends at {}, plus {}s activation delay)",
+                activation_time,
+                earliest,
+                voting_end_time,
+                effective_activation_delay_secs()
+            ));
+        }
+        // Only the proposer's own blocks open or settle its proposals,
+        // so this count is the same on every node
+        let open = self
+            .proposals
+            .values()
+            .filter(|p| p.proposer == proposer && p.status == ProposalStatus::Voting)
+            .count();
+        if open >= MAX_OPEN_PROPOSALS_PER_ACCOUNT {
+            return Err(format!(
+                "Proposal Error: {} already has {} unsettled proposals",
+                proposer, open
+            ));
+        }
+
+        self.proposals.insert(
+            id.to_string(),
+            Proposal {
+                id: id.to_string(),
+                proposer: proposer.to_string(),
+                param,
+                value,
+                deposit_cil,
+                submitted_time: time,
+                voting_end_time,
+                activation_time,
+                votes: BTreeMap::new(),
+                status: ProposalStatus::Voting,
+                tally: None,
+                settlement: None,
+            },
+        );
+        Ok(())
+    }
+
+    /// Record a Vote block timestamped `time` from an account holding
+    /// `balance`. Membership of the validator epoch is checked by the
+    /// validators confirming the block (`check_electorate`).
+    pub fn vote(
+        &mut self,
+        voter: &str,
+        balance: u128,
+        link: &str,
+        time: u64,
+        min_stake_cil: u128,
+    ) -> Result<(), String> {
+        let (id, approve) = parse_vote_link(link)?;
+        if balance < min_stake_cil {
+            return Err(format!(
+                "Vote Error: {} holds less than the validator stake minimum",
+                voter
+            ));
+        }
+        let proposal = self
+            .proposals
+            .get_mut(&id)
+            .ok_or_else(|| format!("Vote Error: proposal {} not found", id))?;
+        if time >= proposal.voting_end_time {
+            return Err(format!("Vote Error: voting on proposal {} has closed", id));
+        }
+        // A vote that reaches this node after the settlement still applies
+        // (its fee is paid) but no longer counts: the settlement fixed the tally
+        if proposal.status == ProposalStatus::Voting {
+            proposal.votes.insert(voter.to_string(), approve);
+        }
+        Ok(())
+    }
+
+    /// Apply a settlement block timestamped `time`: the tally it carries
+    /// decides the outcome. Returns the deposit refunded to the proposer,
+    /// which must equal the block's `amount`.
+    pub fn settle(
+        &mut self,
+        proposer: &str,
+        amount: u128,
+        link: &str,
+        time: u64,
+        block_hash: &str,
+    ) -> Result<u128, String> {
+        let (id, tally) = parse_settle_link(link)?;
+        let proposal = self
+            .proposals
+            .get_mut(&id)
+            .ok_or_else(|| format!("Settlement Error: proposal {} not found", id))?;
+        if proposal.proposer != proposer {
+            return Err(format!(
+                "Settlement Error: only the proposer settles proposal {}",
+                id
+            ));
+        }
+        if proposal.status != ProposalStatus::Voting {
+            return Err(format!(
+                "Settlement Error: proposal {} is already settled",
+                id
+            ));
+        }
+        if time < proposal.voting_end_time {
+            return Err(format!(
+                "Settlement Error: voting on proposal {} is open until {}",
+                id, proposal.voting_end_time
+            ));
+        }
+        let refund = if tally.passed() {
+            proposal.deposit_cil
+        } else {
+            0
+        };
+        if amount != refund {
+            return Err(format!(
+                "Settlement Error: amount must be {} CIL (refunded deposit), got {}",
+                refund, amount
+            ));
+        }
+        if tally.passed() {
+            proposal.status = ProposalStatus::Passed;
+            proposal.activation_time = proposal
+                .activation_time
+                .max(time.saturating_add(effective_activation_delay_secs()));
+        } else {
+            proposal.status = ProposalStatus::Rejected;
+        }
+        proposal.tally = Some(tally);
+        proposal.settlement = Some(block_hash.to_string());
+        Ok(refund)
+    }
+
+    /// Apply a governance block: a proposal locks `amount` as deposit, a
+    /// settlement refunds `amount`, voters send 0. Adjusts `state` by the
+    /// deposit or refund and the fee.
+    pub fn apply_block(
+        &mut self,
+        block: &Block,
+        block_hash: &str,
+        state: &mut AccountState,
+        params: &ChainParams,
+    ) -> Result<(), String> {
+        if block.fee < params.base_fee_cil {
+            return Err(format!(
+                "Governance fee too low: {} CIL < minimum {} CIL",
+                block.fee, params.base_fee_cil
+            ));
+        }
+        match block.block_type {
+            BlockType::Proposal if block.link.starts_with("SETTLE:") => {
+                if state.balance < block.fee {
+                    return Err("Insufficient Funds: balance < settlement fee".to_string());
+                }
+                let refund = self.settle(
+                    &block.account,
+                    block.amount,
+                    &block.link,
+                    block.timestamp,
+                    block_hash,
+                )?;
+                state.balance = (state.balance - block.fee).saturating_add(refund);
+            }
+            BlockType::Proposal => {
+                let total_debit = block
+                    .amount
+                    .checked_add(block.fee)
+                    .ok_or("Overflow: amount + fee exceeds u128")?;
+                if state.balance < total_debit {
+                    return Err("Insufficient Funds: balance < proposal deposit + fee".to_string());
+                }
+                self.submit(
+                    block_hash,
+                    &block.account,
+                    block.amount,
+                    &block.link,
+                    block.timestamp,
+                )?;
+                state.balance -= total_debit;
+            }
+            BlockType::Vote => {
+                if block.amount != 0 {
+                    return Err("Vote Error: amount must be 0".to_string());
+                }
+                if state.balance < block.fee {
+                    return Err("Insufficient Funds: balance < vote fee".to_string());
+                }
+                self.vote(
+                    &block.account,
+                    state.balance,
+                    &block.link,
+                    block.timestamp,
+                    params.min_validator_stake_cil,
+                )?;
+                state.balance -= block.fee;
+            }
+            _ => return Err("Not a governance block".to_string()),
+        }
+        Ok(())
+    }
+
+    /// Stake-weighted tally of `proposal` over `electorate` (address, stake):
+    /// the validators of the running epoch
+    pub fn tally(proposal: &Proposal, electorate: &[(String, u128)]) -> ProposalTally {
+        let no_key = format!("{}:NO", proposal.id);
+        let mut system = VotingSystem::new();
+        for (address, stake) in electorate {
+            let preference = match proposal.votes.get(address) {
+                Some(true) => proposal.id.clone(),
+                Some(false) => no_key.clone(),
+                None => "abstain".to_string(),
+            };
+            // Clamped, so registration cannot fail
+            let _ = system.register_validator(
+                address.clone(),
+                (*stake).min(MAX_STAKE_FOR_VOTING_CIL),
+                preference,
+                true,
+            );
+        }
+        let (yes_power, yes_bps, _) = system.calculate_proposal_consensus(&proposal.id);
+        let (no_power, _, _) = system.calculate_proposal_consensus(&no_key);
+        ProposalTally {
+            yes_power,
+            no_power,
+            total_power: system.get_summary().total_voting_power,
+            yes_bps,
+        }
+    }
+
+    /// Rules a validator checks before confirming a governance block, against
+    /// the validator set of the running epoch: votes come from its members and
+    /// a settlement carries the tally over it.
+    pub fn check_electorate(
+        &self,
+        block: &Block,
+        electorate: &[(String, u128)],
+    ) -> Result<(), String> {
+        match block.block_type {
+            BlockType::Vote if !electorate.iter().any(|(a, _)| *a == block.account) => {
+                return Err(format!(
+                    "Vote Error: {} is not a validator of the running epoch",
+                    block.account
+                ));
+            }
+            BlockType::Proposal if block.link.starts_with("SETTLE:") => {
+                let (id, carried) = parse_settle_link(&block.link)?;
+                let proposal = self
+                    .proposals
+                    .get(&id)
+                    .ok_or_else(|| format!("Settlement Error: proposal {} not found", id))?;
+                let tally = Self::tally(proposal, electorate);
+                if tally != carried {
+                    return Err(format!(
+                        "Settlement Error: tally yes {} / no {} / total {} differs from {} / {} / {} over the running epoch",
+                        carried.yes_power,
+                        carried.no_power,
+                        carried.total_power,
+                        tally.yes_power,
+                        tally.no_power,
+                        tally.total_power
+                    ));
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STAKE: u128 = 1_000 * CIL_PER_LOS;
+    /// Proposal block timestamp
+    const T0: u64 = 1_700_000_000;
+
+    fn electorate() -> Vec<(String, u128)> {
+        ["LOS_V1", "LOS_V2", "LOS_V3"]
+            .into_iter()
+            .map(|v| (v.to_string(), 10_000 * CIL_PER_LOS))
+            .collect()
+    }
+
+    fn activation() -> u64 {
+        T0 + effective_voting_period_secs() + effective_activation_delay_secs()
+    }
+
+    fn submit(gov: &mut GovernanceState, id: &str, value: u128) -> Proposal {
+        let link = proposal_link(GovParam::BaseFeeCil, value, activation());
+        gov.submit(id, "LOS_USER", MIN_PROPOSAL_DEPOSIT_CIL, &link, T0)
+            .unwrap();
+        gov.proposals[id].clone()
+    }
+
+    fn vote(
+        gov: &mut GovernanceState,
+        voter: &str,
+        approve: bool,
+        time: u64,
+    ) -> Result<(), String> {
+        gov.vote(voter, 10 * STAKE, &vote_link("p1", approve), time, STAKE)
+    }
+
+    #[test]
+    fn test_links_round_trip() {
+        let link = proposal_link(GovParam::RewardRateCil, 42, 7);
+        assert_eq!(link, "PROPOSE:reward_rate_cil:42:7");
+        assert_eq!(
+            parse_proposal_link(&link),
+            Ok((GovParam::RewardRateCil, 42, 7))
+        );
+        assert!(parse_proposal_link("PROPOSE:chain_id:1:7").is_err());
+        assert_eq!(
+            parse_vote_link(&vote_link("abc", false)),
+            Ok(("abc".to_string(), false))
+        );
+        assert!(parse_vote_link("VOTE:abc:MAYBE").is_err());
+        assert_eq!(" Base_Fee_CIL ".parse(), Ok(GovParam::BaseFeeCil));
+
+        let tally = ProposalTally::new(3, 1, 6).unwrap();
+        assert_eq!(tally.yes_bps, 5_000);
+        assert!(!tally.passed());
+        let link = settle_link("abc", &tally);
+        assert_eq!(link, "SETTLE:abc:3:1:6");
+        assert_eq!(parse_settle_link(&link), Ok(("abc".to_string(), tally)));
+        assert!(parse_settle_link("SETTLE:abc:5:2:6").is_err());
+        assert!(parse_settle_link("SETTLE::1:1:6").is_err());
+    }
+
+    #[test]
+    fn test_submit_validates_proposal() {
+        let mut gov = GovernanceState::default();
+        let fee = |v: u128, t: u64| proposal_link(GovParam::BaseFeeCil, v, t);
+
+        // Out of bounds, activation before the window end + delay, deposit too small
+        assert!(gov
+            .submit(
+                "p",
+                "LOS_USER",
+                MIN_PROPOSAL_DEPOSIT_CIL,
+                &fee(1, activation()),
+                T0
+            )
+            .is_err());
+        assert!(gov
+            .submit(
+                "p",
+                "LOS_USER",
+                MIN_PROPOSAL_DEPOSIT_CIL,
+                &fee(200_000, activation() - 1),
+                T0
+            )
+            .unwrap_err()
+            .contains("activation time"));
+        assert!(gov
+            .submit(
+                "p",
+                "LOS_USER",
+                CIL_PER_LOS,
+                &fee(200_000, activation()),
+                T0
+            )
+            .is_err());
+        assert!(gov.proposals.is_empty());
+
+        for i in 0..MAX_OPEN_PROPOSALS_PER_ACCOUNT {
+            submit(&mut gov, &format!("p{}", i), 200_000);
+        }
+        assert_eq!(
+            gov.proposals["p0"].voting_end_time,
+            T0 + effective_voting_period_secs()
+        );
+        assert!(gov
+            .submit(
+                "px",
+                "LOS_USER",
+                MIN_PROPOSAL_DEPOSIT_CIL,
+                &fee(200_000, activation()),
+                T0
+            )
+            .unwrap_err()
+            .contains("unsettled proposals"));
+    }
+
+    #[test]
+    fn test_votes_are_stake_checked_and_timestamped_inside_the_window() {
+        let mut gov = GovernanceState::default();
+        let p = submit(&mut gov, "p1", 200_000);
+
+        // Below the stake minimum in force, unknown proposal, after the window
+        assert!(gov
+            .vote("LOS_USER", STAKE - 1, &vote_link("p1", true), T0 + 1, STAKE)
+            .unwrap_err()
+            .contains("stake minimum"));
+        assert!(gov
+            .vote("LOS_V1", STAKE, &vote_link("nope", true), T0 + 1, STAKE)
+            .is_err());
+        assert!(vote(&mut gov, "LOS_V1", true, p.voting_end_time).is_err());
+
+        // A second vote replaces the first
+        vote(&mut gov, "LOS_V1", true, T0 + 1).unwrap();
+        vote(&mut gov, "LOS_V1", false, T0 + 2).unwrap();
+        assert!(!gov.proposals["p1"].votes["LOS_V1"]);
+
+        // Only members of the running epoch are confirmed as voters
+        let block = |account: &str| Block {
+            account: account.to_string(),
+            previous: "0".to_string(),
+            block_type: BlockType::Vote,
+            amount: 0,
+            link: vote_link("p1", true),
+            signature: String::new(),
+            public_key: String::new(),
+            work: 0,
+            timestamp: T0 + 1,
+            fee: 0,
+            memo: None,
+        };
+        assert!(gov
+            .check_electorate(&block("LOS_V1"), &electorate())
+            .is_ok());
+        assert!(gov
+            .check_electorate(&block("LOS_USER"), &electorate())
+            .is_err());
+    }
+
+    #[test]
+    fn test_settlement_refunds_and_activates_by_time() {
+        let mut gov = GovernanceState::default();
+        let p = submit(&mut gov, "p1", 200_000);
+        for v in ["LOS_V1", "LOS_V2"] {
+            vote(&mut gov, v, true, T0 + 1).unwrap();
+        }
+        let tally = GovernanceState::tally(&gov.proposals["p1"], &electorate());
+        assert_eq!(tally.yes_power * 3, tally.total_power * 2);
+        assert_eq!(tally.no_power, 0);
+        let link = settle_link("p1", &tally);
+        let end = p.voting_end_time;
+
+        // Too early, not the proposer, wrong refund
+        let deposit = MIN_PROPOSAL_DEPOSIT_CIL;
+        assert!(gov
+            .settle("LOS_USER", deposit, &link, end - 1, "s")
+            .is_err());
+        assert!(gov.settle("LOS_V1", deposit, &link, end, "s").is_err());
+        assert!(gov.settle("LOS_USER", 0, &link, end, "s").is_err());
+        assert_eq!(gov.proposals["p1"].status, ProposalStatus::Voting);
+
+        assert_eq!(
+            gov.settle("LOS_USER", deposit, &link, end, "s"),
+            Ok(deposit)
+        );
+        let settled = &gov.proposals["p1"];
+        assert_eq!(settled.status, ProposalStatus::Passed);
+        assert_eq!(settled.settlement.as_deref(), Some("s"));
+        assert!(gov.settle("LOS_USER", deposit, &link, end, "s2").is_err());
+
+        // Votes that arrive late still apply but no longer count
+        vote(&mut gov, "LOS_V3", false, T0 + 2).unwrap();
+        assert_eq!(gov.proposals["p1"].votes.len(), 2);
+
+        // The value applies to blocks timestamped from the activation time on
+        let at = p.activation_time;
+        assert_eq!(gov.param_at(GovParam::BaseFeeCil, at - 1), None);
+        assert_eq!(gov.param_at(GovParam::BaseFeeCil, at), Some(200_000));
+        assert_eq!(
+            gov.proposals["p1"].status_at(at - 1),
+            ProposalStatus::Passed
+        );
+        assert_eq!(gov.proposals["p1"].status_at(at), ProposalStatus::Activated);
+    }
+
+    #[test]
+    fn test_settlement_must_match_the_epoch_tally() {
+        let mut gov = GovernanceState::default();
+        let p = submit(&mut gov, "p1", 200_000);
+        vote(&mut gov, "LOS_V1", true, T0 + 1).unwrap();
+        let settle = |yes: u128, no: u128, total: u128| Block {
+            account: "LOS_USER".to_string(),
+            previous: "0".to_string(),
+            block_type: BlockType::Proposal,
+            amount: 0,
+            link: settle_link("p1", &ProposalTally::new(yes, no, total).unwrap()),
+            signature: String::new(),
+            public_key: String::new(),
+            work: 0,
+            timestamp: p.voting_end_time,
+            fee: 0,
+            memo: None,
+        };
+        let tally = GovernanceState::tally(&gov.proposals["p1"], &electorate());
+        assert!(gov
+            .check_electorate(
+                &settle(tally.yes_power, tally.no_power, tally.total_power),
+                &electorate()
+            )
+            .is_ok());
+        // Claiming a pass validators don't see
+        assert!(gov
+            .check_electorate(
+                &settle(tally.total_power, 0, tally.total_power),
+                &electorate()
+            )
+            .is_err());
+        // Leaving out a validator that abstained
+        assert!(gov
+            .check_electorate(
+                &settle(tally.yes_power, 0, tally.yes_power * 2),
+                &electorate()
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn test_abstention_counts_against_and_burns_deposit() {
+        let mut gov = GovernanceState::default();
+        let p = submit(&mut gov, "p1", 200_000);
+        // One of three validators in favour: 33% of all power
+        vote(&mut gov, "LOS_V1", true, T0 + 1).unwrap();
+        vote(&mut gov, "LOS_V2", false, T0 + 1).unwrap();
+
+        let tally = GovernanceState::tally(&gov.proposals["p1"], &electorate());
+        assert_eq!(tally.yes_power, tally.no_power);
+        assert!(!tally.passed());
+        let link = settle_link("p1", &tally);
+        assert!(gov
+            .settle(
+                "LOS_USER",
+                MIN_PROPOSAL_DEPOSIT_CIL,
+                &link,
+                p.voting_end_time,
+                "s"
+            )
+            .is_err());
+        assert_eq!(
+            gov.settle("LOS_USER", 0, &link, p.voting_end_time, "s"),
+            Ok(0)
+        );
+        assert_eq!(gov.proposals["p1"].status, ProposalStatus::Rejected);
+        assert_eq!(gov.param_at(GovParam::BaseFeeCil, u64::MAX), None);
+    }
+
+    #[test]
+    fn test_late_settlement_delays_activation() {
+        let mut gov = GovernanceState::default();
+        let p = submit(&mut gov, "p1", 200_000);
+        for v in ["LOS_V1", "LOS_V2", "LOS_V3"] {
+            vote(&mut gov, v, true, T0 + 1).unwrap();
+        }
+        let tally = GovernanceState::tally(&gov.proposals["p1"], &electorate());
+        let late = p.activation_time + 10;
+        gov.settle(
+            "LOS_USER",
+            MIN_PROPOSAL_DEPOSIT_CIL,
+            &settle_link("p1", &tally),
+            late,
+            "s",
+        )
+        .unwrap();
+        // Blocks already checked against the old value keep it
+        let activation = late + effective_activation_delay_secs();
+        assert_eq!(gov.proposals["p1"].activation_time, activation);
+        assert_eq!(gov.param_at(GovParam::BaseFeeCil, late), None);
+        assert_eq!(
+            gov.param_at(GovParam::BaseFeeCil, activation),
+            Some(200_000)
+        );
+    }
+}
@@ -13,14 +13,25 @@ use std::collections::{BTreeMap, BTreeSet};
 /// Maximum allowed timestamp drift from current time (5 minutes)
 pub const MAX_TIMESTAMP_DRIFT_SECS: u64 = 300;
 
+/// Maximum memo (payment ID / deposit tag) on a Send block, in bytes
+pub const MAX_MEMO_BYTES: usize = 64;
+
+pub mod abi;
 pub mod anti_whale;
 #[cfg(not(feature = "mainnet"))]
 pub mod bonding_curve;
+pub mod chain_params;
 pub mod distribution;
+pub mod fork_choice;
+pub mod governance;
 pub mod oracle_consensus;
+pub mod pruning;
 pub mod validator_config;
 pub mod validator_rewards;
+pub mod voting;
+use crate::chain_params::{ChainParams, ChainParamsSchedule, MEMO_PARAMS_VERSION};
 use crate::distribution::DistributionState;
+use crate::governance::{GovParam, GovernanceState};
 
 /// 1 LOS = 100_000_000_000 CIL (10^11 precision)
 /// Higher precision than Bitcoin (10^8) for DeFi/smart contract flexibility
@@ -32,18 +43,24 @@ pub const MIN_VALIDATOR_STAKE_CIL: u128 = 1_000 * CIL_PER_LOS;
 /// Single source of truth — wallet fetches this via /node-info.
 /// Anti-whale engine may multiply this for high-frequency senders.
 ///
-/// Future: This will become a governance-adjustable parameter.
-/// For mainnet launch, validators can vote to change the base fee
-/// through on-chain governance without requiring a binary upgrade.
-/// The /node-info endpoint ensures wallets always get the current value.
+/// Default of `ChainParams::base_fee_cil`; genesis upgrades and governance
+/// (`GovParam::BaseFeeCil`) may change it: use `Ledger::base_fee_cil()`
+/// for the value in force. The /node-info endpoint ensures wallets always get it.
 pub const BASE_FEE_CIL: u128 = 100_000;
 
-/// Minimum PoW difficulty: 16 leading zero bits (anti-spam)
+/// Anti-whale cap on a single user Mint block (1,000 LOS).
+/// Default of `ChainParams::max_mint_per_block_cil`; governance-adjustable
+/// (`GovParam::MaxMintPerBlockCil`).
+pub const MAX_MINT_PER_BLOCK_CIL: u128 = 1_000 * CIL_PER_LOS;
+
+/// Minimum PoW difficulty: 16 leading zero bits (anti-spam).
+/// Default of `ChainParams::min_pow_difficulty_bits`.
 pub const MIN_POW_DIFFICULTY_BITS: u32 = 16;
 
-/// Chain ID to prevent cross-chain replay attacks
-/// Mainnet = 1, Testnet = 2. Included in every block's signing hash.
+/// Default chain ID of this build (Mainnet = 1, Testnet = 2).
 /// Compile with `--features mainnet` for mainnet build.
+/// The chain ID in force comes from the genesis `chain_params` section
+/// (see `chain_params::chain_id()`), so a testnet build can also run devnets.
 #[cfg(feature = "mainnet")]
 pub const CHAIN_ID: u64 = 1; // Mainnet
 #[cfg(not(feature = "mainnet"))]
@@ -122,6 +139,97 @@ pub const MIN_CALL_FEE_CIL: u128 = BASE_FEE_CIL;
 /// Default gas limit for contract calls (1,000,000 gas units)
 pub const DEFAULT_GAS_LIMIT: u64 = 1_000_000;
 
+/// Fee a ContractCall block must carry for `gas_limit` (never below MIN_CALL_FEE_CIL)
+pub fn contract_call_fee(gas_limit: u64) -> u128 {
+    MIN_CALL_FEE_CIL.max((gas_limit as u128).saturating_mul(GAS_PRICE_CIL))
+}
+
+/// Gas a ContractCall block's fee pays for: the gas limit every node executes it with
+pub fn call_gas_paid(fee: u128) -> u64 {
+    u64::try_from(fee / GAS_PRICE_CIL.max(1)).unwrap_or(u64::MAX)
+}
+
+/// Most USP-01 tokens one call may carry an allowance for
+pub const MAX_CALL_TOKEN_ALLOWANCES: usize = 8;
+
+/// Parsed `link` of a ContractCall block
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallLink {
+    pub contract: String,
+    pub function: String,
+    /// Base64 JSON array of string args (empty if none)
+    pub args_b64: String,
+    /// USP-01 amounts the caller lets the contract pull from it during this
+    /// call only, so e.g. a swap needs no separate `approve` block
+    pub token_allowances: BTreeMap<String, u128>,
+}
+
+/// `CALL:{contract}:{function}:{args_b64}`, plus `:{token}={amount},...`
+/// when the call carries token allowances (signed with the block)
+pub fn contract_call_link(
+    contract: &str,
+    function: &str,
+    args_b64: &str,
+    token_allowances: &BTreeMap<String, u128>,
+) -> String {
+    let mut link = format!("CALL:{}:{}:{}", contract, function, args_b64);
+    if !token_allowances.is_empty() {
+        let allowances: Vec<String> = token_allowances
+            .iter()
+            .map(|(token, amount)| format!("{}={}", token, amount))
+            .collect();
+        link.push(':');
+        link.push_str(&allowances.join(","));
+    }
+    link
+}
+
+/// Split a ContractCall link (see `contract_call_link`)
+pub fn parse_call_link(link: &str) -> Result<CallLink, String> {
+    let call_data = link
+        .strip_prefix("CALL:")
+        .ok_or("ContractCall Error: link must start with 'CALL:'")?;
+    let mut parts = call_data.splitn(3, ':');
+    let (Some(contract), Some(function)) = (parts.next(), parts.next()) else {
+        return Err(
+            "ContractCall Error: link must contain contract address and function".to_string(),
+        );
+    };
+    // Base64 never contains ':', so anything after the args is the allowance list
+    let (args_b64, allowances) = match parts.next() {
+        Some(rest) => rest.split_once(':').unwrap_or((rest, "")),
+        None => ("", ""),
+    };
+    let mut token_allowances = BTreeMap::new();
+    for entry in allowances.split(',').filter(|e| !e.is_empty()) {
+        let (token, amount) = entry
+            .split_once('=')
+            .ok_or_else(|| format!("ContractCall Error: invalid token allowance '{}'", entry))?;
+        let amount: u128 =
+            amount.parse().ok().filter(|a| *a > 0).ok_or_else(|| {
+                format!("ContractCall Error: invalid token allowance '{}'", entry)
+            })?;
+        if token.is_empty() || token_allowances.insert(token.to_string(), amount).is_some() {
+            return Err(format!(
+                "ContractCall Error: invalid token allowance '{}'",
+                entry
+            ));
+        }
+    }
+    if token_allowances.len() > MAX_CALL_TOKEN_ALLOWANCES {
+        return Err(format!(
+            "ContractCall Error: at most {} token allowances per call",
+            MAX_CALL_TOKEN_ALLOWANCES
+        ));
+    }
+    Ok(CallLink {
+        contract: contract.to_string(),
+        function: function.to_string(),
+        args_b64: args_b64.to_string(),
+        token_allowances,
+    })
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub enum BlockType {
     Send,
@@ -129,10 +237,16 @@ pub enum BlockType {
     Change,
     Mint,
     Slash,
-    /// Deploy a WASM smart contract. link = "DEPLOY:{code_hash}"
+    /// Deploy a WASM smart contract. link = "DEPLOY:{code_hash}[:ABI:{abi_hash}]"
     ContractDeploy,
-    /// Call a smart contract function. link = "CALL:{contract_addr}:{function}:{args_b64}"
+    /// Call a smart contract function. link = "CALL:{contract_addr}:{function}:{args_b64}[:{token}={amount},...]"
     ContractCall,
+    /// Governance proposal, amount = deposit. link = "PROPOSE:{param}:{value}:{activation_time}".
+    /// Also its settlement, amount = refunded deposit.
+    /// link = "SETTLE:{proposal_id}:{yes_power}:{no_power}:{total_power}"
+    Proposal,
+    /// Validator vote on a proposal. link = "VOTE:{proposal_id}:YES|NO"
+    Vote,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -149,6 +263,10 @@ pub struct Block {
     /// Transaction fee in CIL (deducted from sender on Send blocks)
     #[serde(default)]
     pub fee: u128,
+    /// Payment ID / deposit tag (Send blocks only, chain params v2+).
+    /// Omitted when absent, so memo-less blocks serialize and hash as before.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
 }
 
 impl Block {
@@ -158,8 +276,8 @@ impl Block {
     pub fn signing_hash(&self) -> String {
         let mut hasher = Keccak256::new();
 
-        // Chain ID domain separation — prevents replay across testnet/mainnet
-        hasher.update(CHAIN_ID.to_le_bytes());
+        // Chain ID domain separation — prevents replay across testnet/mainnet/devnets
+        hasher.update(chain_params::chain_id().to_le_bytes());
 
         hasher.update(self.account.as_bytes());
         hasher.update(self.previous.as_bytes());
@@ -172,6 +290,8 @@ impl Block {
             BlockType::Slash => 4,
             BlockType::ContractDeploy => 5,
             BlockType::ContractCall => 6,
+            BlockType::Proposal => 7,
+            BlockType::Vote => 8,
         };
         hasher.update([type_byte]);
 
@@ -190,6 +310,14 @@ impl Block {
         // fee MUST be included in hash (prevent fee manipulation)
         hasher.update(self.fee.to_le_bytes());
 
+        // memo is appended only when present: memo-less blocks keep the v1
+        // signing hash, so every block signed before memos existed still verifies
+        if let Some(memo) = &self.memo {
+            hasher.update(b"MEMO");
+            hasher.update((memo.len() as u32).to_le_bytes());
+            hasher.update(memo.as_bytes());
+        }
+
         hex::encode(hasher.finalize())
     }
 
@@ -220,14 +348,41 @@ impl Block {
         los_crypto::verify_signature(msg_hash.as_bytes(), &sig_bytes, &pk_bytes)
     }
 
+    /// Memo format: Send blocks only, 1..=MAX_MEMO_BYTES bytes, no control characters
+    pub fn validate_memo(&self) -> Result<(), String> {
+        let Some(memo) = &self.memo else {
+            return Ok(());
+        };
+        if self.block_type != BlockType::Send {
+            return Err("Memo Error: only Send blocks may carry a memo".to_string());
+        }
+        if memo.is_empty() || memo.len() > MAX_MEMO_BYTES {
+            return Err(format!(
+                "Memo Error: memo must be 1-{} bytes, got {}",
+                MAX_MEMO_BYTES,
+                memo.len()
+            ));
+        }
+        if memo.chars().any(char::is_control) {
+            return Err("Memo Error: memo must not contain control characters".to_string());
+        }
+        Ok(())
+    }
+
     /// Verify Proof-of-Work meets minimum difficulty (anti-spam protection)
     /// This is NOT consensus PoW - just anti-spam measure
-    /// Minimum: 16 leading zero bits (≈65,536 average attempts)
+    /// Minimum: `chain_params::pow_difficulty_bits()` leading zero bits
+    /// (16 by default, ≈65,536 average attempts)
     pub fn verify_pow(&self) -> bool {
+        self.pow_zero_bits() >= chain_params::pow_difficulty_bits()
+    }
+
+    /// Leading zero bits of the signing hash (0 if the hash is malformed)
+    pub fn pow_zero_bits(&self) -> u32 {
         let hash = self.signing_hash();
         let hash_bytes = match hex::decode(&hash) {
             Ok(bytes) => bytes,
-            Err(_) => return false,
+            Err(_) => return 0,
         };
 
         // Count leading zero bits
@@ -241,7 +396,7 @@ impl Block {
             }
         }
 
-        zero_bits >= MIN_POW_DIFFICULTY_BITS
+        zero_bits
     }
 }
 
@@ -302,6 +457,41 @@ pub struct Ledger {
     /// Accumulated transaction fees (CIL units) — available for validator distribution
     #[serde(default)]
     pub accumulated_fees_cil: u128,
+    /// Blocks removed by `prune` (pruned nodes only). `total_blocks()` adds these
+    /// back so heights stay comparable with archive nodes.
+    #[serde(default)]
+    pub pruned_blocks: u64,
+    /// History horizon: blocks older than this timestamp may have been pruned (0 = none)
+    #[serde(default)]
+    pub pruned_before: u64,
+    /// Local block count at this node's latest finality checkpoint. Only used
+    /// to refuse rollbacks behind it: the count depends on when the node
+    /// applied gossip, so no consensus rule may be gated on it.
+    #[serde(default)]
+    pub finalized_height: u64,
+    /// On-chain governance: proposals and the parameter values they activated
+    #[serde(default)]
+    pub governance: GovernanceState,
+    /// Runtime chain parameters from genesis (defaults of this build if absent)
+    #[serde(default)]
+    pub chain_params: ChainParamsSchedule,
+    /// (account, memo) → Send blocks carrying that memo, under both sender and
+    /// recipient. Derived from `blocks`: not serialized, see `rebuild_indexes`.
+    #[serde(skip)]
+    memo_index: BTreeMap<(String, String), BTreeSet<String>>,
+    /// `total_blocks()` right after each block was applied on this node: the block
+    /// is final once a finality checkpoint reaches that height. Not serialized with
+    /// the ledger; the database stores it separately (see `restore_applied_heights`).
+    #[serde(skip)]
+    applied_heights: BTreeMap<String, u64>,
+    /// Recipient → Send blocks addressed to it that no Receive has claimed yet.
+    /// Derived from `blocks` and `claimed_sends`: not serialized, see `rebuild_indexes`.
+    #[serde(skip)]
+    receivables: BTreeMap<String, BTreeSet<String>>,
+    /// Send → the Receive that claimed it, for rollbacks.
+    /// Derived from `blocks`: not serialized, see `rebuild_indexes`.
+    #[serde(skip)]
+    claimed_by: BTreeMap<String, String>,
 }
 
 impl Default for Ledger {
@@ -318,15 +508,37 @@ impl Ledger {
             distribution: DistributionState::new(),
             claimed_sends: BTreeSet::new(),
             accumulated_fees_cil: 0,
+            pruned_blocks: 0,
+            pruned_before: 0,
+            finalized_height: 0,
+            governance: GovernanceState::default(),
+            chain_params: ChainParamsSchedule::default(),
+            memo_index: BTreeMap::new(),
+            applied_heights: BTreeMap::new(),
+            receivables: BTreeMap::new(),
+            claimed_by: BTreeMap::new(),
+        }
+    }
+
+    /// Ledger for a chain started from `chain_params` (genesis section)
+    pub fn with_chain_params(chain_params: ChainParamsSchedule) -> Self {
+        Self {
+            chain_params,
+            ..Self::new()
         }
     }
 
     pub fn process_block(&mut self, block: &Block) -> Result<ProcessResult, String> {
-        // 1. PROOF-OF-WORK VALIDATION (Anti-spam: 16 leading zero bits)
-        if !block.verify_pow() {
-            return Err(
-                "Invalid PoW: Block does not meet minimum difficulty (16 zero bits)".to_string(),
-            );
+        // Every rule below uses the parameters in force at the block's own
+        // signed timestamp, so the result does not depend on when it arrives
+        let params = self.chain_params_at(block.timestamp);
+
+        // 1. PROOF-OF-WORK VALIDATION (Anti-spam: 16 leading zero bits by default)
+        if block.pow_zero_bits() < params.min_pow_difficulty_bits {
+            return Err(format!(
+                "Invalid PoW: Block does not meet minimum difficulty ({} zero bits)",
+                params.min_pow_difficulty_bits
+            ));
         }
 
         // 2. SIGNATURE VALIDATION (Dilithium5 post-quantum)
@@ -343,6 +555,8 @@ impl Ledger {
                 | BlockType::Change
                 | BlockType::ContractDeploy
                 | BlockType::ContractCall
+                | BlockType::Proposal
+                | BlockType::Vote
         ) {
             let pk_bytes = hex::decode(&block.public_key)
                 .map_err(|e| format!("Authorization Error: Invalid public_key hex: {}", e))?;
@@ -364,6 +578,9 @@ impl Ledger {
             return Ok(ProcessResult::Duplicate(block_hash));
         }
 
+        // 4. MEMO: well-formed, and only once chain params v2 activated them
+        Self::check_memo_with(&params, block)?;
+
         // MAINNET SECURITY: Debit block types require the account to already exist.
         // Only Mint and Receive may auto-create accounts (they credit funds).
         // Without this, Change/Slash blocks could create empty accounts (state bloat attack).
@@ -431,9 +648,9 @@ impl Ledger {
                     return Err("Distribution Error: Supply exhausted!".to_string());
                 }
 
-                // ANTI-WHALE: Enforce max mint per block (1,000 LOS)
+                // ANTI-WHALE: Enforce max mint per block (1,000 LOS unless chain params changed it)
                 // Prevents single entity from acquiring disproportionate supply
-                const MAX_MINT_PER_BLOCK: u128 = 1_000 * CIL_PER_LOS;
+                let max_mint = params.max_mint_per_block_cil;
                 // Faucet blocks (FAUCET:TESTNET:*) and burn mints (Src:*) are exempt ONLY on testnet builds.
                 // On testnet, mock burn TXIDs produce amounts exceeding the limit (no real burns).
                 // SECURITY: On mainnet build, nobody can bypass anti-whale via link prefix.
@@ -448,11 +665,11 @@ impl Ledger {
                 } else {
                     false // Mainnet: NO exemptions for user-initiated mints
                 };
-                if !is_system_mint && !is_faucet && block.amount > MAX_MINT_PER_BLOCK {
+                if !is_system_mint && !is_faucet && block.amount > max_mint {
                     return Err(format!(
                         "Anti-Whale: Mint amount {} CIL exceeds max {} LOS per block",
                         block.amount,
-                        MAX_MINT_PER_BLOCK / CIL_PER_LOS
+                        max_mint / CIL_PER_LOS
                     ));
                 }
 
@@ -472,11 +689,11 @@ impl Ledger {
             }
             BlockType::Send => {
                 // FIX C11-H1: Enforce minimum transaction fee to prevent zero-fee spam
-                const MIN_TX_FEE_CIL: u128 = 100_000; // 0.001 LOS minimum fee
-                if block.fee < MIN_TX_FEE_CIL {
+                let min_tx_fee = params.base_fee_cil;
+                if block.fee < min_tx_fee {
                     return Err(format!(
-                        "Fee too low: {} CIL < minimum {} CIL (0.001 LOS)",
-                        block.fee, MIN_TX_FEE_CIL
+                        "Fee too low: {} CIL < minimum {} CIL (base fee)",
+                        block.fee, min_tx_fee
                     ));
                 }
                 let total_debit = block
@@ -548,14 +765,17 @@ impl Ledger {
             }
             BlockType::ContractDeploy => {
                 // Contract deployment: deployer pays fee, optionally funds contract
-                // link format: "DEPLOY:{code_hash}" — bytecode hash for integrity verification
-                if !block.link.starts_with("DEPLOY:") {
+                // link format: "DEPLOY:{code_hash}[:ABI:{abi_hash}]" — bytecode hash for
+                // integrity verification, plus the hash of an attached ABI
+                let Some((code_hash, abi_hash)) = abi::parse_deploy_link(&block.link) else {
                     return Err("ContractDeploy Error: link must start with 'DEPLOY:'".to_string());
-                }
-                let code_hash = &block.link[7..]; // After "DEPLOY:"
+                };
                 if code_hash.is_empty() || code_hash.len() < 8 {
                     return Err("ContractDeploy Error: invalid code hash in link field".to_string());
                 }
+                if abi_hash.is_some_and(|h| h.len() != 64 || hex::decode(h).is_err()) {
+                    return Err("ContractDeploy Error: invalid ABI hash in link field".to_string());
+                }
                 // Fee validation (higher minimum than regular transactions)
                 if block.fee < MIN_DEPLOY_FEE_CIL {
                     return Err(format!(
@@ -578,18 +798,8 @@ impl Ledger {
             }
             BlockType::ContractCall => {
                 // Contract call: caller pays gas fee, optionally sends CIL to contract
-                // link format: "CALL:{contract_addr}:{function}:{args_b64}"
-                if !block.link.starts_with("CALL:") {
-                    return Err("ContractCall Error: link must start with 'CALL:'".to_string());
-                }
-                let call_data = &block.link[5..]; // After "CALL:"
-                let call_parts: Vec<&str> = call_data.splitn(3, ':').collect();
-                if call_parts.len() < 2 {
-                    return Err(
-                        "ContractCall Error: link must contain contract address and function"
-                            .to_string(),
-                    );
-                }
+                // link format: "CALL:{contract_addr}:{function}:{args_b64}[:{allowances}]"
+                parse_call_link(&block.link)?;
                 // Fee validation (at least base fee)
                 if block.fee < MIN_CALL_FEE_CIL {
                     return Err(format!(
@@ -610,6 +820,13 @@ impl Ledger {
                 state.balance -= total_debit;
                 self.accumulated_fees_cil = self.accumulated_fees_cil.saturating_add(block.fee);
             }
+            BlockType::Proposal | BlockType::Vote => {
+                // Windows and activation follow the block's signed timestamp;
+                // a settlement carries the tally validators confirmed
+                self.governance
+                    .apply_block(block, &block_hash, &mut state, &params)?;
+                self.accumulated_fees_cil = self.accumulated_fees_cil.saturating_add(block.fee);
+            }
             BlockType::Slash => {
                 // Slash: penalty deduction for validator misbehavior
                 // Signed by detecting validator (public_key is validator's, not cheater's)
@@ -625,7 +842,7 @@ impl Ledger {
                     let pk_bytes = hex::decode(&block.public_key)
                         .map_err(|e| format!("Slash Error: Invalid public_key hex: {}", e))?;
                     let signer_addr = los_crypto::public_key_to_address(&pk_bytes);
-                    let min_validator_stake = MIN_VALIDATOR_STAKE_CIL;
+                    let min_validator_stake = params.min_validator_stake_cil;
                     match self.accounts.get(&signer_addr) {
                         Some(signer_state) => {
                             if !signer_state.is_validator {
@@ -660,6 +877,7 @@ impl Ledger {
 
         self.accounts.insert(block.account.clone(), state);
         self.blocks.insert(block_hash.clone(), block.clone());
+        self.index_block(&block_hash, block);
 
         // Track claimed Sends for O(1) double-receive prevention
         if block.block_type == BlockType::Receive {
@@ -669,6 +887,572 @@ impl Ledger {
         Ok(ProcessResult::Applied(block_hash))
     }
 
+    /// Parameters in force now, for API defaults and blocks this node builds
+    /// (see `chain_params_at`)
+    pub fn chain_params(&self) -> ChainParams {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        self.chain_params_at(now)
+    }
+
+    /// Parameters in force for a block timestamped `time`: the genesis schedule,
+    /// overridden by values that governance has activated.
+    pub fn chain_params_at(&self, time: u64) -> ChainParams {
+        let mut params = self.chain_params.at(time);
+        if let Some(v) = self.governance.param_at(GovParam::BaseFeeCil, time) {
+            params.base_fee_cil = v;
+        }
+        if let Some(v) = self.governance.param_at(GovParam::MaxMintPerBlockCil, time) {
+            params.max_mint_per_block_cil = v;
+        }
+        if let Some(v) = self.governance.param_at(GovParam::RewardRateCil, time) {
+            params.reward_rate_initial_cil = v;
+        }
+        params
+    }
+
+    /// Base fee in force (see `chain_params`)
+    pub fn base_fee_cil(&self) -> u128 {
+        self.chain_params().base_fee_cil
+    }
+
+    /// Minimum validator stake in force (see `chain_params`)
+    pub fn min_validator_stake_cil(&self) -> u128 {
+        self.chain_params().min_validator_stake_cil
+    }
+
+    /// Memo rules at the block's timestamp (for blocks applied outside `process_block`)
+    pub fn check_memo(&self, block: &Block) -> Result<(), String> {
+        Self::check_memo_with(&self.chain_params_at(block.timestamp), block)
+    }
+
+    fn check_memo_with(params: &ChainParams, block: &Block) -> Result<(), String> {
+        if block.memo.is_some() && params.version < MEMO_PARAMS_VERSION {
+            return Err(format!(
+                "Memo Error: memos require chain params v{} (v{} in force)",
+                MEMO_PARAMS_VERSION, params.version
+            ));
+        }
+        block.validate_memo()
+    }
+
+    /// Record a block just inserted into `blocks`: its applied height, the
+    /// receivable it opens (Send) or claims (Receive) and the memo index
+    pub fn index_block(&mut self, hash: &str, block: &Block) {
+        self.applied_heights
+            .insert(hash.to_string(), self.total_blocks() as u64);
+        match block.block_type {
+            BlockType::Send if !self.claimed_sends.contains(hash) => {
+                self.receivables
+                    .entry(block.link.clone())
+                    .or_default()
+                    .insert(hash.to_string());
+            }
+            BlockType::Receive => {
+                self.remove_receivable(&block.account, &block.link);
+                self.claimed_by.insert(block.link.clone(), hash.to_string());
+            }
+            _ => {}
+        }
+        if let (BlockType::Send, Some(memo)) = (&block.block_type, &block.memo) {
+            for account in [&block.account, &block.link] {
+                self.memo_index
+                    .entry((account.clone(), memo.clone()))
+                    .or_default()
+                    .insert(hash.to_string());
+            }
+        }
+    }
+
+    /// Inverse of `index_block` for a block removed from `blocks` (prune, rollback)
+    pub fn unindex_block(&mut self, hash: &str, block: &Block) {
+        self.applied_heights.remove(hash);
+        if block.block_type == BlockType::Receive
+            && self.claimed_by.get(&block.link).is_some_and(|h| h == hash)
+        {
+            self.claimed_by.remove(&block.link);
+        }
+        match block.block_type {
+            BlockType::Send => self.remove_receivable(&block.link, hash),
+            // A rolled-back Receive reopens its Send (a pruned one's Send is gone too)
+            BlockType::Receive
+                if !self.claimed_sends.contains(&block.link)
+                    && self.blocks.contains_key(&block.link) =>
+            {
+                self.receivables
+                    .entry(block.account.clone())
+                    .or_default()
+                    .insert(block.link.clone());
+            }
+            _ => {}
+        }
+        if let Some(memo) = &block.memo {
+            for account in [&block.account, &block.link] {
+                let key = (account.clone(), memo.clone());
+                if let Some(hashes) = self.memo_index.get_mut(&key) {
+                    hashes.remove(hash);
+                    if hashes.is_empty() {
+                        self.memo_index.remove(&key);
+                    }
+                }
+            }
+        }
+    }
+
+    fn remove_receivable(&mut self, recipient: &str, send_hash: &str) {
+        if let Some(hashes) = self.receivables.get_mut(recipient) {
+            hashes.remove(send_hash);
+            if hashes.is_empty() {
+                self.receivables.remove(recipient);
+            }
+        }
+    }
+
+    /// Rebuild the indexes of `index_block` (after loading or replacing `blocks`
+    /// and `claimed_sends`). Blocks without a known applied height (databases
+    /// written before heights were stored) count as applied now.
+    pub fn rebuild_indexes(&mut self) {
+        let height = self.total_blocks() as u64;
+        self.applied_heights
+            .retain(|h, _| self.blocks.contains_key(h));
+        self.receivables.clear();
+        self.claimed_by.clear();
+        for (hash, block) in &self.blocks {
+            self.applied_heights.entry(hash.clone()).or_insert(height);
+            match block.block_type {
+                BlockType::Send if !self.claimed_sends.contains(hash) => {
+                    self.receivables
+                        .entry(block.link.clone())
+                        .or_default()
+                        .insert(hash.clone());
+                }
+                BlockType::Receive => {
+                    self.claimed_by.insert(block.link.clone(), hash.clone());
+                }
+                _ => {}
+            }
+        }
+        self.memo_index.clear();
+        let sends: Vec<(String, Block)> = self
+            .blocks
+            .iter()
+            .filter(|(_, b)| b.memo.is_some())
+            .map(|(h, b)| (h.clone(), b.clone()))
+            .collect();
+        for (hash, block) in &sends {
+            for account in [&block.account, &block.link] {
+                if let Some(memo) = &block.memo {
+                    self.memo_index
+                        .entry((account.clone(), memo.clone()))
+                        .or_default()
+                        .insert(hash.clone());
+                }
+            }
+        }
+    }
+
+    /// `total_blocks()` when `hash` was applied on this node (see `applied_heights`)
+    pub fn applied_height(&self, hash: &str) -> Option<u64> {
+        self.applied_heights.get(hash).copied()
+    }
+
+    /// Applied height of every block in `blocks`, for persisting across restarts
+    pub fn applied_heights(&self) -> &BTreeMap<String, u64> {
+        &self.applied_heights
+    }
+
+    /// Restore applied heights loaded from disk; `rebuild_indexes` then keeps
+    /// the ones whose block is still present
+    pub fn restore_applied_heights(&mut self, heights: BTreeMap<String, u64>) {
+        self.applied_heights = heights;
+    }
+
+    /// Send blocks addressed to `account` that no Receive has claimed yet, oldest first
+    pub fn pending_receivables(&self, account: &str) -> Vec<(&String, &Block)> {
+        let mut pending: Vec<(&String, &Block)> = self
+            .receivables
+            .get(account)
+            .into_iter()
+            .flatten()
+            .filter(|hash| !self.claimed_sends.contains(*hash))
+            .filter_map(|hash| self.blocks.get_key_value(hash))
+            .collect();
+        pending.sort_by_key(|(_, b)| b.timestamp);
+        pending
+    }
+
+    /// Send blocks from or to `account` carrying `memo`, newest first
+    pub fn sends_with_memo(&self, account: &str, memo: &str) -> Vec<(&String, &Block)> {
+        let mut sends: Vec<(&String, &Block)> = self
+            .memo_index
+            .get(&(account.to_string(), memo.to_string()))
+            .into_iter()
+            .flatten()
+            .filter_map(|h| self.blocks.get_key_value(h))
+            .collect();
+        sends.sort_by_key(|(_, b)| std::cmp::Reverse(b.timestamp));
+        sends
+    }
+
+    /// Check a Proposal or Vote block against the governance rules without
+    /// applying it, so validators can confirm it before it is final.
+    /// The block must be timestamped within MAX_TIMESTAMP_DRIFT_SECS of this
+    /// node's clock, so a vote or settlement cannot be backdated across the
+    /// end of a voting window.
+    pub fn check_governance(&self, block: &Block) -> Result<(), String> {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        if now.abs_diff(block.timestamp) > MAX_TIMESTAMP_DRIFT_SECS {
+            return Err(format!(
+                "Governance Error: block timestamp {} is more than {}s from now ({})",
+                block.timestamp, MAX_TIMESTAMP_DRIFT_SECS, now
+            ));
+        }
+        let mut state = self
+            .accounts
+            .get(&block.account)
+            .cloned()
+            .ok_or("Account not found")?;
+        self.governance.clone().apply_block(
+            block,
+            &block.calculate_hash(),
+            &mut state,
+            &self.chain_params_at(block.timestamp),
+        )
+    }
+
+    /// Chain height including pruned blocks (equals `blocks.len()` on archive nodes)
+    pub fn total_blocks(&self) -> usize {
+        self.blocks.len() + self.pruned_blocks as usize
+    }
+
+    /// Record a new finality checkpoint height (never moves backwards)
+    pub fn advance_finalized_height(&mut self, height: u64) {
+        self.finalized_height = self.finalized_height.max(height);
+    }
+
+    /// Drop finalized history older than `finalized_before` (unix seconds).
+    ///
+    /// Keeps each account's head plus `keep_recent` blocks behind it, unclaimed
+    /// Sends, Receives whose Send is still present, and every Mint and Slash
+    /// block. See `pruning` for why this is enough to validate every future block.
+    pub fn prune(&mut self, finalized_before: u64, keep_recent: usize) -> pruning::PruneReport {
+        let mut protected = BTreeSet::new();
+        for state in self.accounts.values() {
+            let mut cursor = state.head.as_str();
+            for _ in 0..=keep_recent {
+                match self.blocks.get(cursor) {
+                    Some(block) => {
+                        protected.insert(cursor.to_string());
+                        cursor = block.previous.as_str();
+                    }
+                    None => break,
+                }
+            }
+        }
+
+        let prunable = |hash: &String, block: &Block| {
+            block.timestamp < finalized_before && !protected.contains(hash)
+        };
+        let sends: BTreeSet<String> = self
+            .blocks
+            .iter()
+            .filter(|(h, b)| {
+                b.block_type == BlockType::Send && self.claimed_sends.contains(*h) && prunable(h, b)
+            })
+            .map(|(h, _)| h.clone())
+            .collect();
+        let pruned: Vec<String> = self
+            .blocks
+            .iter()
+            .filter(|(h, b)| match b.block_type {
+                BlockType::Send => sends.contains(*h),
+                BlockType::Receive => {
+                    prunable(h, b)
+                        && (sends.contains(&b.link) || !self.blocks.contains_key(&b.link))
+                }
+                // Burn double-claim checks and supply recalculation read these
+                BlockType::Mint | BlockType::Slash => false,
+                _ => prunable(h, b),
+            })
+            .map(|(h, _)| h.clone())
+            .collect();
+
+        for hash in &pruned {
+            if let Some(block) = self.blocks.remove(hash) {
+                self.unindex_block(hash, &block);
+            }
+        }
+        let before = self.claimed_sends.len();
+        self.claimed_sends.retain(|h| !sends.contains(h));
+        self.pruned_blocks += pruned.len() as u64;
+        self.pruned_before = self.pruned_before.max(finalized_before);
+
+        pruning::PruneReport {
+            claimed_sends_dropped: before - self.claimed_sends.len(),
+            pruned,
+        }
+    }
+
+    /// Whether a Mint block already references burn `txid` (double-claim guard).
+    /// Mint blocks are never pruned, so this also holds on pruned nodes.
+    pub fn txid_minted(&self, txid: &str) -> bool {
+        self.blocks
+            .values()
+            .any(|b| b.block_type == BlockType::Mint && b.link.contains(txid))
+    }
+
+    /// Hash of the applied block that shares `block`'s predecessor, if `block`
+    /// competes with it (same account, same `previous`, different content).
+    /// Only looks `fork_choice::MAX_FORK_DEPTH` blocks behind the account head.
+    pub fn find_conflict(&self, block: &Block) -> Option<String> {
+        let state = self.accounts.get(&block.account)?;
+        if block.previous == state.head {
+            return None;
+        }
+        let block_hash = block.calculate_hash();
+        let mut cursor = state.head.clone();
+        for _ in 0..fork_choice::MAX_FORK_DEPTH {
+            let applied = self.blocks.get(&cursor)?;
+            if applied.previous == block.previous {
+                return (cursor != block_hash).then_some(cursor);
+            }
+            cursor = applied.previous.clone();
+        }
+        None
+    }
+
+    /// Replace the branch applied after `winner.previous` with `winner`.
+    ///
+    /// Rolls the account back to `winner.previous` (see `rollback_to`), then
+    /// applies `winner`. Returns the rolled-back blocks in undo order. If `winner` does not
+    /// apply, the original branch is restored and the error is returned.
+    pub fn switch_branch(&mut self, winner: &Block) -> Result<Vec<Block>, String> {
+        let rolled_back = self.rollback_to(&winner.account, &winner.previous)?;
+        if let Err(e) = self.process_block(winner) {
+            // Restore oldest first: Sends are re-applied before the Receives that claim them
+            for block in rolled_back.iter().rev() {
+                self.redo_block(block);
+            }
+            return Err(e);
+        }
+        Ok(rolled_back)
+    }
+
+    /// Undo blocks until `account`'s head is `previous`, first undoing any
+    /// Receive (and its successors) that claimed a Send being undone.
+    /// Returns the undone blocks, newest first. Slash, contract and governance
+    /// blocks are final, and so is everything behind the last finality checkpoint
+    /// or the prune horizon.
+    pub fn rollback_to(&mut self, account: &str, previous: &str) -> Result<Vec<Block>, String> {
+        let mut plan = Vec::new();
+        self.plan_rollback(account, previous, &mut BTreeMap::new(), &mut plan)?;
+
+        let mut rolled_back = Vec::with_capacity(plan.len());
+        for hash in &plan {
+            rolled_back.push(self.undo_block(hash)?);
+        }
+        Ok(rolled_back)
+    }
+
+    /// Collect the blocks to undo (in order) so `account`'s head becomes `previous`.
+    /// `heads` tracks each account's head as if the plan had already run.
+    fn plan_rollback(
+        &self,
+        account: &str,
+        previous: &str,
+        heads: &mut BTreeMap<String, String>,
+        plan: &mut Vec<String>,
+    ) -> Result<(), String> {
+        if !heads.contains_key(account) {
+            let head = self
+                .accounts
+                .get(account)
+                .map(|a| a.head.clone())
+                .ok_or_else(|| format!("Rollback Error: account {} not in ledger", account))?;
+            heads.insert(account.to_string(), head);
+        }
+
+        loop {
+            let cursor = heads.get(account).cloned().unwrap_or_default();
+            if cursor == previous {
+                return Ok(());
+            }
+            if plan.len() >= fork_choice::MAX_ROLLBACK_BLOCKS {
+                return Err(format!(
+                    "Rollback Error: more than {} blocks to undo",
+                    fork_choice::MAX_ROLLBACK_BLOCKS
+                ));
+            }
+            let block = self.blocks.get(&cursor).ok_or_else(|| {
+                format!(
+                    "Rollback Error: {} is not an ancestor of the head of {}",
+                    previous, account
+                )
+            })?;
+            // History a checkpoint covers is final, and pruning may already have
+            // dropped the Receives that claimed it
+            if block.timestamp < self.pruned_before
+                || self
+                    .applied_height(&cursor)
+                    .is_some_and(|h| h <= self.finalized_height)
+            {
+                return Err(format!(
+                    "Rollback Error: block {} is behind the last finality checkpoint",
+                    cursor
+                ));
+            }
+
+            match block.block_type {
+                BlockType::Slash => {
+                    return Err("Rollback Error: Slash blocks are final".to_string());
+                }
+                BlockType::ContractDeploy | BlockType::ContractCall => {
+                    return Err("Rollback Error: contract state cannot be rolled back".to_string());
+                }
+                BlockType::Proposal | BlockType::Vote => {
+                    return Err("Rollback Error: governance blocks are final".to_string());
+                }
+                BlockType::Send if self.claimed_sends.contains(&cursor) => {
+                    // Undo the recipient's Receive (and everything after it) first.
+                    // Without it the Send cannot be undone: the funds would exist twice.
+                    let receive = self
+                        .claimed_by
+                        .get(&cursor)
+                        .and_then(|h| self.blocks.get_key_value(h))
+                        .ok_or_else(|| {
+                            format!("Rollback Error: the Receive claiming {} is gone", cursor)
+                        })?;
+                    let (receive_hash, receive) = receive;
+                    if !plan.contains(receive_hash) {
+                        self.plan_rollback(&receive.account, &receive.previous, heads, plan)?;
+                    }
+                    if heads.get(account) != Some(&cursor) {
+                        continue; // The cascade already moved this account's head
+                    }
+                }
+                _ => {}
+            }
+
+            plan.push(cursor);
+            heads.insert(account.to_string(), block.previous.clone());
+        }
+    }
+
+    /// Undo the head block of its account (inverse of `process_block`)
+    fn undo_block(&mut self, hash: &str) -> Result<Block, String> {
+        let block = self
+            .blocks
+            .get(hash)
+            .cloned()
+            .ok_or_else(|| format!("Rollback Error: block {} not found", hash))?;
+        let state = self
+            .accounts
+            .get_mut(&block.account)
+            .ok_or_else(|| format!("Rollback Error: account {} not found", block.account))?;
+        if state.head != hash {
+            return Err(format!(
+                "Rollback Error: {} is not the head of {}",
+                hash, block.account
+            ));
+        }
+
+        match block.block_type {
+            BlockType::Send | BlockType::ContractDeploy | BlockType::ContractCall => {
+                state.balance = state
+                    .balance
+                    .saturating_add(block.amount.saturating_add(block.fee));
+                self.accumulated_fees_cil = self.accumulated_fees_cil.saturating_sub(block.fee);
+            }
+            BlockType::Receive => {
+                state.balance = state.balance.saturating_sub(block.amount);
+                self.claimed_sends.remove(&block.link);
+            }
+            BlockType::Mint => {
+                state.balance = state.balance.saturating_sub(block.amount);
+                self.distribution.remaining_supply = self
+                    .distribution
+                    .remaining_supply
+                    .saturating_add(block.amount);
+                let parts: Vec<&str> = block.link.split(':').collect();
+                if parts.len() >= 4 {
+                    if let Ok(fiat_price) = parts[3].trim().parse::<u128>() {
+                        self.distribution.total_burned_usd = self
+                            .distribution
+                            .total_burned_usd
+                            .saturating_sub(fiat_price);
+                    }
+                }
+            }
+            BlockType::Change => {}
+            BlockType::Slash => {
+                return Err("Rollback Error: Slash blocks are final".to_string());
+            }
+            BlockType::Proposal | BlockType::Vote => {
+                return Err("Rollback Error: governance blocks are final".to_string());
+            }
+        }
+
+        state.head = block.previous.clone();
+        state.block_count = state.block_count.saturating_sub(1);
+        self.blocks.remove(hash);
+        self.unindex_block(hash, &block);
+        Ok(block)
+    }
+
+    /// Re-apply a block removed by `undo_block` (exact inverse, no re-validation:
+    /// node-created Receives are not always valid under `process_block`)
+    fn redo_block(&mut self, block: &Block) {
+        let state = self
+            .accounts
+            .entry(block.account.clone())
+            .or_insert(AccountState {
+                head: "0".to_string(),
+                balance: 0,
+                block_count: 0,
+                is_validator: false,
+            });
+        match block.block_type {
+            BlockType::Send | BlockType::ContractDeploy | BlockType::ContractCall => {
+                state.balance = state
+                    .balance
+                    .saturating_sub(block.amount.saturating_add(block.fee));
+                self.accumulated_fees_cil = self.accumulated_fees_cil.saturating_add(block.fee);
+            }
+            BlockType::Receive => {
+                state.balance = state.balance.saturating_add(block.amount);
+                self.claimed_sends.insert(block.link.clone());
+            }
+            BlockType::Mint => {
+                state.balance = state.balance.saturating_add(block.amount);
+                self.distribution.remaining_supply = self
+                    .distribution
+                    .remaining_supply
+                    .saturating_sub(block.amount);
+                let parts: Vec<&str> = block.link.split(':').collect();
+                if parts.len() >= 4 {
+                    if let Ok(fiat_price) = parts[3].trim().parse::<u128>() {
+                        self.distribution.total_burned_usd = self
+                            .distribution
+                            .total_burned_usd
+                            .saturating_add(fiat_price);
+                    }
+                }
+            }
+            // Change moves no funds; the others are never undone (see `plan_rollback`)
+            BlockType::Change | BlockType::Slash | BlockType::Proposal | BlockType::Vote => {}
+        }
+        let hash = block.calculate_hash();
+        state.head = hash.clone();
+        state.block_count = state.block_count.saturating_add(1);
+        self.blocks.insert(hash.clone(), block.clone());
+        self.index_block(&hash, block);
+    }
+
     /// Claim and reset accumulated transaction fees.
     /// Returns the total fees (CIL) collected since last claim.
     /// Used by the epoch reward system to redistribute fees to validators.
@@ -679,3 +1463,322 @@ impl Ledger {
         fees
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn send(memo: Option<&str>) -> Block {
+        Block {
+            account: "LOS_SENDER".to_string(),
+            previous: "0".to_string(),
+            block_type: BlockType::Send,
+            amount: 5 * CIL_PER_LOS,
+            link: "LOS_EXCHANGE".to_string(),
+            signature: String::new(),
+            public_key: String::new(),
+            work: 0,
+            timestamp: 1_700_000_000,
+            fee: 100_000,
+            memo: memo.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_memo_less_block_hash_unchanged() {
+        // Pinned v1 signing hash: blocks signed before memos existed must still verify
+        let block = send(None);
+        assert_eq!(
+            block.signing_hash(),
+            "5ba6e2fcec01fca97186ff8e5c388ca5972a52b82da50eb8d9c59372d43b62d5"
+        );
+        let json = serde_json::to_string(&block).unwrap();
+        assert!(!json.contains("memo"));
+        let decoded: Block = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.signing_hash(), block.signing_hash());
+
+        let tagged = send(Some("user-42"));
+        assert_ne!(tagged.signing_hash(), block.signing_hash());
+        assert_ne!(tagged.signing_hash(), send(Some("user-43")).signing_hash());
+    }
+
+    #[test]
+    fn test_call_link_carries_token_allowances() {
+        let plain = parse_call_link("CALL:LOSConDex:swap:WyIxIl0=").unwrap();
+        assert_eq!(
+            (plain.contract.as_str(), plain.function.as_str()),
+            ("LOSConDex", "swap")
+        );
+        assert_eq!(plain.args_b64, "WyIxIl0=");
+        assert!(plain.token_allowances.is_empty());
+        assert!(parse_call_link("CALL:LOSConDex:init").is_ok());
+
+        let mut allowances = BTreeMap::new();
+        allowances.insert("LOSConTok".to_string(), 30u128);
+        let link = contract_call_link("LOSConDex", "swap", "WyIxIl0=", &allowances);
+        assert_eq!(link, "CALL:LOSConDex:swap:WyIxIl0=:LOSConTok=30");
+        assert_eq!(parse_call_link(&link).unwrap().token_allowances, allowances);
+
+        for bad in [
+            "CALL:LOSConDex",
+            "CALL:LOSConDex:swap:WyIxIl0=:LOSConTok",
+            "CALL:LOSConDex:swap:WyIxIl0=:LOSConTok=0",
+            "CALL:LOSConDex:swap:WyIxIl0=:LOSConTok=1,LOSConTok=2",
+        ] {
+            assert!(parse_call_link(bad).is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_memo_rules() {
+        assert!(send(Some("user-42")).validate_memo().is_ok());
+        assert!(send(Some(&"x".repeat(MAX_MEMO_BYTES)))
+            .validate_memo()
+            .is_ok());
+        assert!(send(Some(&"x".repeat(MAX_MEMO_BYTES + 1)))
+            .validate_memo()
+            .is_err());
+        assert!(send(Some("")).validate_memo().is_err());
+        assert!(send(Some("a\nb")).validate_memo().is_err());
+        let mut change = send(Some("user-42"));
+        change.block_type = BlockType::Change;
+        assert!(change.validate_memo().is_err());
+
+        // Gated on the chain params version in force
+        let v1 = Ledger::with_chain_params(ChainParamsSchedule::new(ChainParams::testnet()));
+        assert!(v1.check_memo(&send(None)).is_ok());
+        assert!(v1.check_memo(&send(Some("user-42"))).is_err());
+        let v2 = Ledger::with_chain_params(ChainParamsSchedule::new(ChainParams {
+            version: MEMO_PARAMS_VERSION,
+            ..ChainParams::testnet()
+        }));
+        assert!(v2.check_memo(&send(Some("user-42"))).is_ok());
+
+        // Existing chains: a version-only upgrade enables memos from its activation time
+        let mut schedule = ChainParamsSchedule::new(ChainParams::testnet());
+        schedule.upgrades.push(chain_params::ParamsUpgrade {
+            version: MEMO_PARAMS_VERSION,
+            activation_time: 5_000,
+            ..Default::default()
+        });
+        assert!(schedule.validate().is_ok());
+        let upgraded = Ledger::with_chain_params(schedule);
+        let mut tagged = send(Some("user-42"));
+        tagged.timestamp = 4_999;
+        assert!(upgraded.check_memo(&tagged).is_err());
+        tagged.timestamp = 5_000;
+        assert!(upgraded.check_memo(&tagged).is_ok());
+    }
+
+    #[test]
+    fn test_memo_index() {
+        let mut ledger = Ledger::new();
+        for (i, memo) in [Some("user-42"), Some("user-7"), None, Some("user-42")]
+            .into_iter()
+            .enumerate()
+        {
+            let mut block = send(memo);
+            block.timestamp += i as u64;
+            let hash = block.calculate_hash();
+            ledger.blocks.insert(hash.clone(), block.clone());
+            ledger.index_block(&hash, &block);
+        }
+
+        let tagged = ledger.sends_with_memo("LOS_EXCHANGE", "user-42");
+        assert_eq!(tagged.len(), 2);
+        assert!(tagged[0].1.timestamp > tagged[1].1.timestamp);
+        assert_eq!(ledger.sends_with_memo("LOS_SENDER", "user-42").len(), 2);
+        assert_eq!(ledger.sends_with_memo("LOS_EXCHANGE", "user-7").len(), 1);
+        assert!(ledger.sends_with_memo("LOS_OTHER", "user-42").is_empty());
+
+        let removed = tagged[0].0.clone();
+        let kept = tagged[1].0.clone();
+        let block = ledger.blocks.remove(&removed).unwrap();
+        ledger.unindex_block(&removed, &block);
+        assert_eq!(ledger.sends_with_memo("LOS_EXCHANGE", "user-42").len(), 1);
+
+        ledger.rebuild_indexes();
+        assert_eq!(ledger.sends_with_memo("LOS_EXCHANGE", "user-42").len(), 1);
+        assert_eq!(ledger.sends_with_memo("LOS_EXCHANGE", "user-7").len(), 1);
+        assert_eq!(ledger.applied_height(&removed), None);
+        assert_eq!(ledger.applied_height(&kept), Some(1));
+    }
+
+    #[test]
+    fn test_governance_follows_block_timestamps() {
+        use governance::{GovParam, ProposalStatus};
+
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let mut ledger = Ledger::new();
+        for account in ["LOS_USER", "LOS_V1", "LOS_V2", "LOS_V3"] {
+            ledger.accounts.insert(
+                account.to_string(),
+                AccountState {
+                    head: "0".to_string(),
+                    balance: 10_000 * CIL_PER_LOS,
+                    block_count: 1,
+                    is_validator: false,
+                },
+            );
+        }
+        let electorate: Vec<(String, u128)> = ["LOS_V1", "LOS_V2", "LOS_V3"]
+            .into_iter()
+            .map(|v| (v.to_string(), 10_000 * CIL_PER_LOS))
+            .collect();
+        let gov_block =
+            |account: &str, block_type: BlockType, amount: u128, link: String, timestamp: u64| {
+                Block {
+                    account: account.to_string(),
+                    block_type,
+                    amount,
+                    link,
+                    fee: BASE_FEE_CIL,
+                    timestamp,
+                    ..send(None)
+                }
+            };
+        let end = now + governance::effective_voting_period_secs();
+        let activation = end + governance::effective_activation_delay_secs();
+        let proposal = gov_block(
+            "LOS_USER",
+            BlockType::Proposal,
+            governance::MIN_PROPOSAL_DEPOSIT_CIL,
+            governance::proposal_link(GovParam::BaseFeeCil, 200_000, activation),
+            now,
+        );
+
+        // Checking leaves the ledger untouched; stale blocks are not confirmed
+        assert!(ledger.check_governance(&proposal).is_ok());
+        assert!(ledger.governance.proposals.is_empty());
+        let stale = Block {
+            timestamp: now - 2 * MAX_TIMESTAMP_DRIFT_SECS,
+            ..proposal.clone()
+        };
+        assert!(ledger.check_governance(&stale).is_err());
+
+        let apply = |ledger: &mut Ledger, block: &Block, hash: &str| {
+            let mut state = ledger.accounts[&block.account].clone();
+            let params = ledger.chain_params_at(block.timestamp);
+            ledger
+                .governance
+                .apply_block(block, hash, &mut state, &params)?;
+            ledger.accounts.insert(block.account.clone(), state);
+            Ok::<(), String>(())
+        };
+        apply(&mut ledger, &proposal, "p1").unwrap();
+        for voter in ["LOS_V1", "LOS_V2"] {
+            let vote = gov_block(
+                voter,
+                BlockType::Vote,
+                0,
+                governance::vote_link("p1", true),
+                now + 1,
+            );
+            assert!(ledger.check_governance(&vote).is_ok());
+            assert!(ledger
+                .governance
+                .check_electorate(&vote, &electorate)
+                .is_ok());
+            apply(&mut ledger, &vote, voter).unwrap();
+        }
+        let user_vote = gov_block(
+            "LOS_USER",
+            BlockType::Vote,
+            0,
+            governance::vote_link("p1", true),
+            now + 1,
+        );
+        assert!(ledger
+            .governance
+            .check_electorate(&user_vote, &electorate)
+            .is_err());
+
+        // Neither the local block count nor the finalized height closes the
+        // window; only a settlement block timestamped after it does
+        ledger.pruned_blocks = 1_000_000;
+        ledger.advance_finalized_height(1_000_000);
+        assert_eq!(
+            ledger.governance.proposals["p1"].status,
+            ProposalStatus::Voting
+        );
+        let balance = ledger.accounts["LOS_USER"].balance;
+        let tally =
+            governance::GovernanceState::tally(&ledger.governance.proposals["p1"], &electorate);
+        let settlement = gov_block(
+            "LOS_USER",
+            BlockType::Proposal,
+            governance::MIN_PROPOSAL_DEPOSIT_CIL,
+            governance::settle_link("p1", &tally),
+            end,
+        );
+        assert!(ledger
+            .governance
+            .check_electorate(&settlement, &electorate)
+            .is_ok());
+        apply(&mut ledger, &settlement, "s1").unwrap();
+        assert_eq!(
+            ledger.governance.proposals["p1"].status,
+            ProposalStatus::Passed
+        );
+        // The refund is the settlement block's amount, credited by that block
+        assert_eq!(
+            ledger.accounts["LOS_USER"].balance,
+            balance + governance::MIN_PROPOSAL_DEPOSIT_CIL - BASE_FEE_CIL
+        );
+
+        // Blocks are checked against the fee in force at their own timestamp
+        assert_eq!(
+            ledger.chain_params_at(activation - 1).base_fee_cil,
+            BASE_FEE_CIL
+        );
+        assert_eq!(ledger.chain_params_at(activation).base_fee_cil, 200_000);
+    }
+
+    #[test]
+    fn test_pending_receivables() {
+        let mut ledger = Ledger::new();
+        let mut hashes = Vec::new();
+        for i in 0..3u64 {
+            let mut block = send(None);
+            block.timestamp -= i;
+            let hash = block.calculate_hash();
+            ledger.blocks.insert(hash.clone(), block.clone());
+            ledger.index_block(&hash, &block);
+            hashes.push(hash);
+        }
+        let pending = ledger.pending_receivables("LOS_EXCHANGE");
+        assert_eq!(pending.len(), 3);
+        assert_eq!(pending[0].0, &hashes[2]);
+        assert!(ledger.pending_receivables("LOS_SENDER").is_empty());
+
+        // A Receive claims hashes[0]; rolling it back reopens the Send
+        let receive = Block {
+            account: "LOS_EXCHANGE".to_string(),
+            block_type: BlockType::Receive,
+            link: hashes[0].clone(),
+            fee: 0,
+            ..send(None)
+        };
+        let receive_hash = receive.calculate_hash();
+        ledger.blocks.insert(receive_hash.clone(), receive.clone());
+        ledger.index_block(&receive_hash, &receive);
+        ledger.claimed_sends.insert(hashes[0].clone());
+        let pending = ledger.pending_receivables("LOS_EXCHANGE");
+        assert_eq!(pending.len(), 2);
+        assert!(pending.iter().all(|(h, _)| **h != hashes[0]));
+
+        ledger.blocks.remove(&receive_hash);
+        ledger.claimed_sends.remove(&hashes[0]);
+        ledger.unindex_block(&receive_hash, &receive);
+        assert_eq!(ledger.pending_receivables("LOS_EXCHANGE").len(), 3);
+
+        let pruned = ledger.blocks.remove(&hashes[1]).unwrap();
+        ledger.unindex_block(&hashes[1], &pruned);
+        assert_eq!(ledger.pending_receivables("LOS_EXCHANGE").len(), 2);
+        ledger.rebuild_indexes();
+        assert_eq!(ledger.pending_receivables("LOS_EXCHANGE").len(), 2);
+    }
+}
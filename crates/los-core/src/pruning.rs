@@ -0,0 +1,226 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - HISTORY MODES & ACCOUNT-CHAIN PRUNING
+//
+// Archive nodes keep every block forever. Pruned nodes drop finalized history
+// once a FinalityCheckpoint is past, keeping only what validation needs:
+// - Every account head (timestamp check + chain sequence)
+// - The most recent `keep_recent` blocks of each account (fork-choice rollback)
+// - Unclaimed Sends (recipients still need them to Receive)
+// - Receives whose Send is still present (claimed_sends is rebuilt from them)
+// - Every Mint and Slash block (burn double-claim guards, supply recalculation)
+// A pruned Send also leaves `claimed_sends`: a Receive for it fails with
+// "Send block not found" anyway, so the entry is no longer needed.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use crate::fork_choice::MAX_FORK_DEPTH;
+use serde::{Deserialize, Serialize};
+
+/// Blocks kept behind each account head by default (covers the fork-choice window)
+pub const DEFAULT_KEEP_RECENT_BLOCKS: usize = MAX_FORK_DEPTH;
+
+/// How much block history a node keeps
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HistoryMode {
+    /// Keep every block (serves full history and state sync)
+    #[default]
+    Archive,
+    /// Drop finalized history below the checkpoint horizon
+    Pruned,
+}
+
+impl HistoryMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HistoryMode::Archive => "archive",
+            HistoryMode::Pruned => "pruned",
+        }
+    }
+}
+
+impl std::str::FromStr for HistoryMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "archive" | "full" => Ok(HistoryMode::Archive),
+            "pruned" | "prune" => Ok(HistoryMode::Pruned),
+            other => Err(format!(
+                "Unknown history mode '{}' (expected 'archive' or 'pruned')",
+                other
+            )),
+        }
+    }
+}
+
+/// Node pruning policy
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PruningConfig {
+    pub mode: HistoryMode,
+    /// Blocks kept behind each account head (the head itself always stays)
+    pub keep_recent: usize,
+}
+
+impl Default for PruningConfig {
+    fn default() -> Self {
+        Self::archive()
+    }
+}
+
+impl PruningConfig {
+    pub fn archive() -> Self {
+        Self {
+            mode: HistoryMode::Archive,
+            keep_recent: DEFAULT_KEEP_RECENT_BLOCKS,
+        }
+    }
+
+    pub fn pruned(keep_recent: usize) -> Self {
+        Self {
+            mode: HistoryMode::Pruned,
+            keep_recent,
+        }
+    }
+
+    pub fn is_pruned(&self) -> bool {
+        self.mode == HistoryMode::Pruned
+    }
+}
+
+/// Result of one `Ledger::prune` pass
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PruneReport {
+    /// Hashes removed from `Ledger.blocks` (the caller deletes them from storage)
+    pub pruned: Vec<String>,
+    /// Entries dropped from `claimed_sends` along with their Send blocks
+    pub claimed_sends_dropped: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AccountState, Block, BlockType, Ledger};
+
+    /// Append a block to its account chain without validation
+    fn push(
+        ledger: &mut Ledger,
+        account: &str,
+        block_type: BlockType,
+        link: &str,
+        timestamp: u64,
+    ) -> String {
+        let state = ledger
+            .accounts
+            .entry(account.to_string())
+            .or_insert(AccountState {
+                head: "0".to_string(),
+                balance: 0,
+                block_count: 0,
+                is_validator: false,
+            });
+        let block = Block {
+            account: account.to_string(),
+            previous: state.head.clone(),
+            block_type,
+            amount: 1,
+            link: link.to_string(),
+            signature: String::new(),
+            public_key: String::new(),
+            work: timestamp,
+            timestamp,
+            fee: 0,
+            memo: None,
+        };
+        let hash = block.calculate_hash();
+        if block.block_type == BlockType::Receive {
+            ledger.claimed_sends.insert(link.to_string());
+        }
+        state.head = hash.clone();
+        state.block_count += 1;
+        ledger.blocks.insert(hash.clone(), block);
+        hash
+    }
+
+    #[test]
+    fn test_history_mode_parse() {
+        assert_eq!("pruned".parse(), Ok(HistoryMode::Pruned));
+        assert_eq!(" Archive ".parse(), Ok(HistoryMode::Archive));
+        assert!("light".parse::<HistoryMode>().is_err());
+        assert_eq!(HistoryMode::default().as_str(), "archive");
+    }
+
+    #[test]
+    fn test_prune_keeps_what_validation_needs() {
+        let mut ledger = Ledger::new();
+        let mint = push(&mut ledger, "LOS_A", BlockType::Mint, "SRC", 10);
+        let claimed = push(&mut ledger, "LOS_A", BlockType::Send, "LOS_B", 11);
+        let unclaimed = push(&mut ledger, "LOS_A", BlockType::Send, "LOS_C", 12);
+        let change = push(&mut ledger, "LOS_A", BlockType::Change, "REP", 13);
+        let recent = push(&mut ledger, "LOS_A", BlockType::Change, "REP2", 14);
+        let head = push(&mut ledger, "LOS_A", BlockType::Change, "REP3", 15);
+        let receive = push(&mut ledger, "LOS_B", BlockType::Receive, &claimed, 12);
+        push(&mut ledger, "LOS_B", BlockType::Change, "REP", 13);
+        let b_head = push(&mut ledger, "LOS_B", BlockType::Change, "REP2", 14);
+        let height = ledger.total_blocks();
+
+        let report = ledger.prune(100, 1);
+
+        let mut pruned = report.pruned.clone();
+        pruned.sort();
+        let mut expected = vec![claimed.clone(), change, receive];
+        expected.sort();
+        assert_eq!(pruned, expected);
+        assert_eq!(report.claimed_sends_dropped, 1);
+        assert!(!ledger.claimed_sends.contains(&claimed));
+        for kept in [&mint, &unclaimed, &recent, &head, &b_head] {
+            assert!(ledger.blocks.contains_key(kept));
+        }
+        assert_eq!(ledger.total_blocks(), height);
+        assert_eq!(ledger.pruned_before, 100);
+    }
+
+    #[test]
+    fn test_prune_respects_horizon_and_live_sends() {
+        let mut ledger = Ledger::new();
+        push(&mut ledger, "LOS_A", BlockType::Mint, "SRC", 10);
+        let send = push(&mut ledger, "LOS_A", BlockType::Send, "LOS_B", 50);
+        push(&mut ledger, "LOS_A", BlockType::Change, "REP", 60);
+        let receive = push(&mut ledger, "LOS_B", BlockType::Receive, &send, 20);
+        push(&mut ledger, "LOS_B", BlockType::Change, "REP", 21);
+
+        // The Send is past the horizon, so the Receive that claims it must stay
+        let report = ledger.prune(30, 0);
+        assert!(report.pruned.is_empty());
+        assert!(ledger.blocks.contains_key(&send));
+        assert!(ledger.blocks.contains_key(&receive));
+        assert!(ledger.claimed_sends.contains(&send));
+
+        // Archive-style call with nothing old enough is a no-op
+        assert!(ledger.prune(0, 0).pruned.is_empty());
+        assert_eq!(ledger.pruned_before, 30);
+    }
+
+    #[test]
+    fn test_pruned_burn_txid_cannot_be_claimed_again() {
+        let mut ledger = Ledger::new();
+        let mint = push(&mut ledger, "LOS_A", BlockType::Mint, "ETH:0xburn", 10);
+        let slash = push(
+            &mut ledger,
+            "LOS_A",
+            BlockType::Slash,
+            "PENALTY:DOWNTIME:1",
+            11,
+        );
+        for t in 12..20 {
+            push(&mut ledger, "LOS_A", BlockType::Change, "REP", t);
+        }
+
+        let report = ledger.prune(100, 0);
+        assert!(!report.pruned.is_empty());
+        assert!(ledger.blocks.contains_key(&mint));
+        assert!(ledger.blocks.contains_key(&slash));
+        // Re-submitting the same burn TXID is still recognised as a double claim
+        assert!(ledger.txid_minted("0xburn"));
+        assert!(!ledger.txid_minted("0xother"));
+    }
+}
@@ -11,6 +11,7 @@
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
+use crate::chain_params::ChainParams;
 use crate::{
     effective_reward_epoch_secs, CIL_PER_LOS, MIN_VALIDATOR_STAKE_CIL,
     REWARD_HALVING_INTERVAL_EPOCHS, REWARD_MIN_UPTIME_PCT, REWARD_PROBATION_EPOCHS,
@@ -80,7 +81,7 @@ impl ValidatorRewardState {
     ///
     /// Genesis bootstrap validators ARE eligible for rewards — they secure
     /// the network from day one and deserve the same compensation as any validator.
-    pub fn is_eligible(&self, current_epoch: u64) -> bool {
+    pub fn is_eligible(&self, current_epoch: u64, min_stake_cil: u128) -> bool {
         // Probation: must complete at least 1 full epoch before earning rewards.
         // A validator joining at epoch N is eligible starting at epoch N + PROBATION_EPOCHS.
         // This applies to ALL validators equally — genesis and non-genesis.
@@ -91,7 +92,7 @@ impl ValidatorRewardState {
         if self.uptime_pct() < REWARD_MIN_UPTIME_PCT {
             return false;
         }
-        if self.stake_cil < MIN_VALIDATOR_STAKE_CIL {
+        if self.stake_cil < min_stake_cil {
             return false;
         }
         true
@@ -125,12 +126,27 @@ pub struct ValidatorRewardPool {
     /// Defaults to effective_reward_epoch_secs() if not present (backwards-compatible).
     #[serde(default = "default_epoch_duration")]
     pub epoch_duration_secs: u64,
+    /// Reward rate before halving. Governance may change it
+    /// (`GovParam::RewardRateCil`); the node copies the value in force here.
+    #[serde(default = "default_reward_rate")]
+    pub reward_rate_initial_cil: u128,
+    /// Minimum stake to earn rewards, copied from the chain params in force
+    #[serde(default = "default_min_stake")]
+    pub min_stake_cil: u128,
 }
 
 fn default_epoch_duration() -> u64 {
     effective_reward_epoch_secs()
 }
 
+fn default_reward_rate() -> u128 {
+    REWARD_RATE_INITIAL_CIL
+}
+
+fn default_min_stake() -> u128 {
+    MIN_VALIDATOR_STAKE_CIL
+}
+
 impl ValidatorRewardPool {
     /// Create a new reward pool with full funding.
     /// `genesis_timestamp` = network genesis time (Unix seconds).
@@ -143,6 +159,8 @@ impl ValidatorRewardPool {
             total_distributed_cil: 0,
             validators: BTreeMap::new(),
             epoch_duration_secs: effective_reward_epoch_secs(),
+            reward_rate_initial_cil: REWARD_RATE_INITIAL_CIL,
+            min_stake_cil: MIN_VALIDATOR_STAKE_CIL,
         }
     }
 
@@ -156,9 +174,19 @@ impl ValidatorRewardPool {
             total_distributed_cil: 0,
             validators: BTreeMap::new(),
             epoch_duration_secs: effective_reward_epoch_secs(),
+            reward_rate_initial_cil: REWARD_RATE_INITIAL_CIL,
+            min_stake_cil: MIN_VALIDATOR_STAKE_CIL,
         }
     }
 
+    /// Adopt the epoch length, reward rate and minimum stake in force.
+    /// A new epoch length counts from the start of the current epoch.
+    pub fn apply_params(&mut self, params: &ChainParams) {
+        self.epoch_duration_secs = params.reward_epoch_secs;
+        self.reward_rate_initial_cil = params.reward_rate_initial_cil;
+        self.min_stake_cil = params.min_validator_stake_cil;
+    }
+
     /// Register a validator for reward tracking.
     /// If already registered, updates stake and genesis status.
     pub fn register_validator(&mut self, address: &str, is_genesis: bool, stake_cil: u128) {
@@ -204,13 +232,13 @@ impl ValidatorRewardPool {
 
     /// Calculate the reward rate for the current epoch (with halving).
     /// Rate halves every `REWARD_HALVING_INTERVAL_EPOCHS` epochs.
-    /// After n halvings: rate = initial_rate >> n
+    /// After n halvings: rate = reward_rate_initial_cil >> n
     pub fn epoch_reward_rate(&self) -> u128 {
         let halvings = self.current_epoch / REWARD_HALVING_INTERVAL_EPOCHS;
         if halvings >= 128 {
             return 0; // Effectively zero after 128 halvings
         }
-        REWARD_RATE_INITIAL_CIL >> halvings
+        self.reward_rate_initial_cil >> halvings
     }
 
     /// Check if the current epoch has ended (based on timestamp).
@@ -260,11 +288,10 @@ impl ValidatorRewardPool {
     /// When called at epoch START (after advance_epoch reset counters),
     /// all validators have heartbeats=0 so they get the full expected count.
     pub fn set_expected_heartbeats(&mut self, heartbeat_interval_secs: u64) {
-        let full_expected = if heartbeat_interval_secs > 0 {
-            self.epoch_duration_secs / heartbeat_interval_secs
-        } else {
-            0
-        };
+        let full_expected = self
+            .epoch_duration_secs
+            .checked_div(heartbeat_interval_secs)
+            .unwrap_or(0);
         for state in self.validators.values_mut() {
             if state.heartbeats_current_epoch == 0 {
                 // Epoch start or no heartbeats yet: set full expected
@@ -305,7 +332,7 @@ impl ValidatorRewardPool {
         let eligible: Vec<(String, u128)> = self
             .validators
             .iter()
-            .filter(|(_, v)| v.is_eligible(self.current_epoch))
+            .filter(|(_, v)| v.is_eligible(self.current_epoch, self.min_stake_cil))
             .map(|(addr, v)| (addr.clone(), v.sqrt_stake_weight()))
             .filter(|(_, w)| *w > 0)
             .collect();
@@ -413,7 +440,7 @@ impl ValidatorRewardPool {
         let eligible_count = self
             .validators
             .values()
-            .filter(|v| v.is_eligible(self.current_epoch))
+            .filter(|v| v.is_eligible(self.current_epoch, self.min_stake_cil))
             .count() as u64;
         let total_validators = self.validators.len() as u64;
 
@@ -425,13 +452,10 @@ impl ValidatorRewardPool {
             halvings_occurred: self.halvings_occurred,
             total_validators,
             eligible_validators: eligible_count,
-            pool_exhaustion_bps: if VALIDATOR_REWARD_POOL_CIL > 0 {
-                // Basis points (10000 = 100%) — pure integer math
-                ((VALIDATOR_REWARD_POOL_CIL - self.remaining_cil) * 10_000
-                    / VALIDATOR_REWARD_POOL_CIL) as u64
-            } else {
-                0
-            },
+            // Basis points (10000 = 100%) — pure integer math
+            pool_exhaustion_bps: ((VALIDATOR_REWARD_POOL_CIL - self.remaining_cil) * 10_000)
+                .checked_div(VALIDATOR_REWARD_POOL_CIL)
+                .unwrap_or(0) as u64,
         }
     }
 }
@@ -512,6 +536,10 @@ mod tests {
         // Epoch 144 (third halving)
         pool.current_epoch = 144;
         assert_eq!(pool.epoch_reward_rate(), 625 * CIL_PER_LOS);
+
+        // A governance-set rate halves on the same schedule
+        pool.reward_rate_initial_cil = 8_000 * CIL_PER_LOS;
+        assert_eq!(pool.epoch_reward_rate(), 1_000 * CIL_PER_LOS);
     }
 
     #[test]
@@ -536,10 +564,10 @@ mod tests {
 
         let genesis_state = pool.validators.get(genesis_addr).unwrap();
         // Genesis validators are eligible (both testnet and mainnet)
-        assert!(genesis_state.is_eligible(pool.current_epoch));
+        assert!(genesis_state.is_eligible(pool.current_epoch, MIN_VALIDATOR_STAKE_CIL));
 
         let normal_state = pool.validators.get(normal_addr).unwrap();
-        assert!(normal_state.is_eligible(pool.current_epoch));
+        assert!(normal_state.is_eligible(pool.current_epoch, MIN_VALIDATOR_STAKE_CIL));
     }
 
     #[test]
@@ -555,11 +583,19 @@ mod tests {
         }
 
         // Epoch 0: in probation, NOT eligible
-        assert!(!pool.validators.get(genesis_addr).unwrap().is_eligible(0));
+        assert!(!pool
+            .validators
+            .get(genesis_addr)
+            .unwrap()
+            .is_eligible(0, MIN_VALIDATOR_STAKE_CIL));
 
         // Epoch 1: past probation, eligible
         pool.current_epoch = 1;
-        assert!(pool.validators.get(genesis_addr).unwrap().is_eligible(1));
+        assert!(pool
+            .validators
+            .get(genesis_addr)
+            .unwrap()
+            .is_eligible(1, MIN_VALIDATOR_STAKE_CIL));
     }
 
     #[test]
@@ -610,7 +646,11 @@ mod tests {
             let v = pool.validators.get_mut(addr).unwrap();
             v.heartbeats_current_epoch = v.expected_heartbeats; // 100% uptime
         }
-        assert!(!pool.validators.get(addr).unwrap().is_eligible(0));
+        assert!(!pool
+            .validators
+            .get(addr)
+            .unwrap()
+            .is_eligible(0, MIN_VALIDATOR_STAKE_CIL));
 
         // Epoch 1 — past probation → eligible
         pool.current_epoch = 1;
@@ -618,7 +658,11 @@ mod tests {
             let v = pool.validators.get_mut(addr).unwrap();
             v.heartbeats_current_epoch = v.expected_heartbeats;
         }
-        assert!(pool.validators.get(addr).unwrap().is_eligible(1));
+        assert!(pool
+            .validators
+            .get(addr)
+            .unwrap()
+            .is_eligible(1, MIN_VALIDATOR_STAKE_CIL));
     }
 
     #[test]
@@ -638,7 +682,11 @@ mod tests {
             let expected = v.expected_heartbeats;
             v.heartbeats_current_epoch = expected * 90 / 100;
         }
-        assert!(!pool.validators.get(addr).unwrap().is_eligible(2));
+        assert!(!pool
+            .validators
+            .get(addr)
+            .unwrap()
+            .is_eligible(2, MIN_VALIDATOR_STAKE_CIL));
 
         // 95% uptime — meets threshold
         {
@@ -646,7 +694,11 @@ mod tests {
             let expected = v.expected_heartbeats;
             v.heartbeats_current_epoch = expected * 95 / 100;
         }
-        assert!(pool.validators.get(addr).unwrap().is_eligible(2));
+        assert!(pool
+            .validators
+            .get(addr)
+            .unwrap()
+            .is_eligible(2, MIN_VALIDATOR_STAKE_CIL));
     }
 
     #[test]
@@ -801,6 +853,18 @@ mod tests {
             v.heartbeats_current_epoch = v.expected_heartbeats;
         }
 
-        assert!(!pool.validators.get("LOSpoor").unwrap().is_eligible(2));
+        assert!(!pool
+            .validators
+            .get("LOSpoor")
+            .unwrap()
+            .is_eligible(2, MIN_VALIDATOR_STAKE_CIL));
+
+        // A chain whose params lower the minimum makes it eligible
+        let mut params = crate::chain_params::ChainParams::testnet();
+        params.min_validator_stake_cil = 100 * CIL_PER_LOS;
+        params.reward_epoch_secs = 300;
+        pool.apply_params(&params);
+        assert_eq!(pool.epoch_duration_secs, 300);
+        assert_eq!(pool.pool_summary().eligible_validators, 1);
     }
 }
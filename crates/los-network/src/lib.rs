@@ -17,6 +17,7 @@ use libp2p::{
 use std::error::Error;
 use std::time::Duration;
 use tokio::sync::mpsc;
+use tracing::{debug, info, warn};
 
 // Public modules
 pub mod fee_scaling;
@@ -56,20 +57,20 @@ impl LosNode {
         let tor_dialer = tor_config.socks5_proxy.map(TorDialer::new);
 
         if tor_config.enabled {
-            println!(
-                "🧅 Tor transport enabled (SOCKS5: {})",
+            info!(
+                "Tor transport enabled (SOCKS5: {})",
                 tor_config
                     .socks5_proxy
                     .map(|a| a.to_string())
                     .unwrap_or_default()
             );
             if let Some(ref onion) = tor_config.onion_address {
-                println!("🧅 This node's .onion address: {}", onion);
+                info!("This node's .onion address: {}", onion);
             }
         }
 
         if !bootstrap_nodes.is_empty() {
-            println!("📡 Bootstrap nodes: {}", bootstrap_nodes.len());
+            info!("Bootstrap nodes: {}", bootstrap_nodes.len());
         }
 
         let mut swarm = libp2p::SwarmBuilder::with_new_identity()
@@ -102,7 +103,9 @@ impl LosNode {
                 // SECURITY: mDNS leaks node presence on LAN via multicast UDP.
                 // When Tor is enabled, disable mDNS to preserve anonymity.
                 let mdns = if tor_config.enabled {
-                    println!("🔒 mDNS disabled (Tor mode — prevents LAN presence leak)");
+                    info!("mDNS disabled (Tor mode — prevents LAN presence leak)");
+                    Toggle::from(None)
+                } else if tor_config.local_only {
                     Toggle::from(None)
                 } else {
                     Toggle::from(Some(mdns::tokio::Behaviour::new(
@@ -122,21 +125,21 @@ impl LosNode {
         // Listen on configured port
         // SECURITY: When Tor SOCKS5 is configured, bind 127.0.0.1 only to prevent IP leaks.
         // The Tor hidden service forwards external traffic to this local port.
-        let bind_ip = if tor_config.socks5_proxy.is_some() {
+        let bind_ip = if tor_config.socks5_proxy.is_some() || tor_config.local_only {
             "127.0.0.1"
         } else {
             "0.0.0.0"
         };
         let listen_addr = format!("/ip4/{}/tcp/{}", bind_ip, tor_config.listen_port);
         swarm.listen_on(listen_addr.parse()?)?;
-        println!("📡 P2P listening on port {}", tor_config.listen_port);
+        info!("P2P listening on port {}", tor_config.listen_port);
 
         // Bootstrap: dial all configured bootstrap nodes
         for node in &bootstrap_nodes {
             match node {
                 BootstrapNode::Multiaddr(addr) => {
                     if let Ok(maddr) = addr.parse::<libp2p::Multiaddr>() {
-                        println!("📡 Dialing bootstrap peer: {}", addr);
+                        info!("Dialing bootstrap peer: {}", addr);
                         let _ = swarm.dial(maddr);
                     }
                 }
@@ -144,18 +147,18 @@ impl LosNode {
                     if let Some(ref dialer) = tor_dialer {
                         match dialer.create_onion_proxy(host.clone(), *port).await {
                             Ok(local_addr) => {
-                                println!("🧅 Tor proxy created for {} → {}", host, local_addr);
+                                info!("Tor proxy created for {} → {}", host, local_addr);
                                 if let Ok(maddr) = local_addr.parse::<libp2p::Multiaddr>() {
                                     let _ = swarm.dial(maddr);
                                 }
                             }
                             Err(e) => {
-                                eprintln!("🧅 Failed to create Tor proxy for {}: {}", host, e);
+                                warn!("Failed to create Tor proxy for {}: {}", host, e);
                             }
                         }
                     } else {
-                        eprintln!(
-                            "🧅 Cannot dial .onion {} — set LOS_SOCKS5_PROXY=socks5h://127.0.0.1:9050",
+                        warn!(
+                            "Cannot dial .onion {} — set LOS_SOCKS5_PROXY=socks5h://127.0.0.1:9050",
                             host
                         );
                     }
@@ -191,19 +194,19 @@ impl LosNode {
                                 if let BootstrapNode::Onion { host, port } = parsed {
                                     match dialer.create_onion_proxy(host.clone(), port).await {
                                         Ok(local_addr) => {
-                                            println!("🧅 Tor proxy for {} → {}", host, local_addr);
+                                            info!("Tor proxy for {} → {}", host, local_addr);
                                             if let Ok(maddr) = local_addr.parse::<libp2p::Multiaddr>() {
                                                 let _ = swarm.dial(maddr);
                                             }
                                         }
-                                        Err(e) => eprintln!("🧅 Tor dial failed: {}", e),
+                                        Err(e) => warn!("Tor dial failed: {}", e),
                                     }
                                 }
                             } else {
-                                eprintln!("🧅 Cannot dial .onion — set LOS_SOCKS5_PROXY=socks5h://127.0.0.1:9050");
+                                warn!("Cannot dial .onion — set LOS_SOCKS5_PROXY=socks5h://127.0.0.1:9050");
                             }
                         } else if let Ok(maddr) = addr_str.parse::<libp2p::Multiaddr>() {
-                            println!("📡 Swarm: Dialing {}...", maddr);
+                            info!("Swarm: Dialing {}...", maddr);
                             let _ = swarm.dial(maddr);
                         }
                     } else if let Err(e) = swarm.behaviour_mut().gossipsub.publish(topic.clone(), msg_to_send.as_bytes()) {
@@ -215,10 +218,10 @@ impl LosNode {
                                 .duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_secs();
                             if now.saturating_sub(LAST_WARN.load(std::sync::atomic::Ordering::Relaxed)) >= 30 {
                                 LAST_WARN.store(now, std::sync::atomic::Ordering::Relaxed);
-                                eprintln!("⚠️ Gossipsub: InsufficientPeers — messages not being delivered (connected: {})", connected_peers.len());
+                                warn!(connected = connected_peers.len(), "Gossipsub: InsufficientPeers — messages not being delivered");
                             }
                         } else {
-                            eprintln!("⚠️ Broadcast Error: {:?}", e);
+                            warn!(error = ?e, "Broadcast error");
                         }
                     }
                 },
@@ -226,7 +229,7 @@ impl LosNode {
                 _ = reconnect_timer.tick() => {
                     let peer_count = connected_peers.len();
                     if peer_count < min_peers {
-                        println!("🔄 P2P reconnect: only {}/{} peers connected, re-dialing bootstrap nodes...", peer_count, min_peers);
+                        info!(peers = peer_count, min_peers, "P2P reconnect: re-dialing bootstrap nodes");
                         for node in &bootstrap_nodes {
                             match node {
                                 BootstrapNode::Multiaddr(addr) => {
@@ -242,14 +245,14 @@ impl LosNode {
                                                     let _ = swarm.dial(maddr);
                                                 }
                                             }
-                                            Err(e) => eprintln!("🔄 Reconnect dial failed for {}: {}", host, e),
+                                            Err(e) => warn!(%host, error = %e, "Reconnect dial failed"),
                                         }
                                     }
                                 }
                             }
                         }
                     } else {
-                        println!("📡 P2P mesh healthy: {}/{} peers connected", peer_count, min_peers);
+                        debug!(peers = peer_count, min_peers, "P2P mesh healthy");
                     }
                 },
                 event = swarm.select_next_some() => match event {
@@ -264,19 +267,19 @@ impl LosNode {
                         let _ = tx.send(NetworkEvent::NewBlock(content)).await;
                     },
                     SwarmEvent::NewListenAddr { address, .. } => {
-                        println!("📍 P2P listening on: {:?}", address);
+                        info!(%address, "P2P listening");
                     },
                     SwarmEvent::ConnectionEstablished { peer_id, .. } => {
                         swarm.behaviour_mut().gossipsub.add_explicit_peer(&peer_id);
                         connected_peers.insert(peer_id);
-                        println!("🤝 P2P connected: {:?} (total: {})", peer_id, connected_peers.len());
+                        info!(peer = %peer_id, total = connected_peers.len(), "P2P connected");
                     },
                     SwarmEvent::ConnectionClosed { peer_id, cause, .. } => {
                         connected_peers.remove(&peer_id);
-                        println!("🔌 P2P disconnected: {:?} (reason: {:?}, remaining: {})", peer_id, cause, connected_peers.len());
+                        info!(peer = %peer_id, reason = ?cause, remaining = connected_peers.len(), "P2P disconnected");
                     },
                     SwarmEvent::OutgoingConnectionError { error, .. } => {
-                        eprintln!("❌ P2P dial error: {:?}", error);
+                        warn!(%error, "P2P dial error");
                     },
                     _ => {}
                 }
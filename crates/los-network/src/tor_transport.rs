@@ -17,6 +17,7 @@
 use std::net::SocketAddr;
 use tokio::io;
 use tokio::net::{TcpListener, TcpStream};
+use tracing::{info, warn};
 
 /// Configuration for Tor connectivity
 #[derive(Debug, Clone)]
@@ -29,11 +30,16 @@ pub struct TorConfig {
     pub listen_port: u16,
     /// Whether Tor is enabled
     pub enabled: bool,
+    /// Loopback-only mode (LOS_LOCAL_ONLY=1): no Tor, no mDNS, bind 127.0.0.1.
+    /// Used by local devnets whose peers all come from LOS_BOOTSTRAP_NODES.
+    pub local_only: bool,
 }
 
 impl TorConfig {
     /// Load Tor configuration from environment variables
     pub fn from_env() -> Self {
+        let local_only = std::env::var("LOS_LOCAL_ONLY").unwrap_or_default() == "1";
+
         // Accept both LOS_SOCKS5_PROXY (with socks5h:// prefix) and LOS_TOR_SOCKS5 (bare addr)
         let socks5_proxy = std::env::var("LOS_SOCKS5_PROXY")
             .or_else(|_| std::env::var("LOS_TOR_SOCKS5"))
@@ -44,7 +50,11 @@ impl TorConfig {
                     .to_string()
             })
             .and_then(|s| s.parse::<SocketAddr>().ok())
+            .filter(|_| !local_only)
             .or_else(|| {
+                if local_only {
+                    return None;
+                }
                 // Auto-detect: try default Tor SOCKS5 proxy at 127.0.0.1:9050
                 let default_addr: SocketAddr = match "127.0.0.1:9050".parse() {
                     Ok(a) => a,
@@ -55,7 +65,7 @@ impl TorConfig {
                     std::time::Duration::from_millis(500),
                 ) {
                     Ok(_) => {
-                        println!("🧅 Auto-detected Tor SOCKS5 proxy at 127.0.0.1:9050");
+                        info!("Auto-detected Tor SOCKS5 proxy at 127.0.0.1:9050");
                         Some(default_addr)
                     }
                     Err(_) => None,
@@ -76,6 +86,7 @@ impl TorConfig {
             onion_address,
             listen_port,
             enabled,
+            local_only,
         }
     }
 }
@@ -133,15 +144,12 @@ impl TorDialer {
                                 proxy_connection(inbound, proxy_addr, &target_host, target_port)
                                     .await
                             {
-                                eprintln!(
-                                    "Tor proxy error to {}:{} — {}",
-                                    target_host, target_port, e
-                                );
+                                warn!("Tor proxy error to {}:{} — {}", target_host, target_port, e);
                             }
                         });
                     }
                     Err(e) => {
-                        eprintln!("Tor proxy accept error: {}", e);
+                        warn!("Tor proxy accept error: {}", e);
                         break;
                     }
                 }
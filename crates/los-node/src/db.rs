@@ -5,10 +5,12 @@
 // Provides ACID-compliant atomic operations for blocks, accounts, and metadata.
 // ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
 
+use crate::mempool::MempoolTransaction;
 use los_core::{AccountState, Block, Ledger};
 use sled::{Db, Tree};
 use std::path::Path;
 use std::sync::Arc;
+use tracing::{info, warn};
 
 #[cfg(unix)]
 use std::os::unix::io::AsRawFd;
@@ -20,6 +22,8 @@ const TREE_META: &str = "metadata";
 const TREE_FAUCET_COOLDOWNS: &str = "faucet_cooldowns";
 const TREE_PEERS: &str = "known_peers";
 const TREE_CONTRACTS: &str = "contracts"; // Smart contract VM state
+const TREE_CONSENSUS_WAL: &str = "consensus_wal"; // aBFT write-ahead log (own votes + view)
+const TREE_MEMPOOL: &str = "mempool"; // Pending transactions awaiting confirmation
 
 /// Database wrapper with ACID guarantees
 pub struct LosDatabase {
@@ -91,8 +95,8 @@ impl LosDatabase {
         match Self::is_db_lock_available(path_ref) {
             Ok(true) => { /* Lock available — proceed with sled::open */ }
             Ok(false) => {
-                eprintln!(
-                    "⚠️  Database flock held by another process at {} — \
+                warn!(
+                    "Database flock held by another process at {} — \
                      will retry with backoff (NOT blocking in kernel I/O)",
                     path_ref.display()
                 );
@@ -100,8 +104,8 @@ impl LosDatabase {
                 // which uses the non-blocking probe on each iteration
                 for (i, delay_ms) in retry_delays_ms.iter().enumerate() {
                     std::thread::sleep(std::time::Duration::from_millis(*delay_ms));
-                    eprintln!(
-                        "🔄 Lock probe retry {}/{} after {}ms...",
+                    warn!(
+                        "Lock probe retry {}/{} after {}ms...",
                         i + 1,
                         retry_delays_ms.len(),
                         delay_ms
@@ -118,17 +122,14 @@ impl LosDatabase {
                         }
                         Ok(false) => continue,
                         Err(e) => {
-                            eprintln!("⚠️ flock probe error: {}", e);
+                            warn!("flock probe error: {}", e);
                             break; // Fall through to sled::open and let it handle
                         }
                     }
                 }
             }
             Err(e) => {
-                eprintln!(
-                    "⚠️ flock probe error: {} — falling through to sled::open",
-                    e
-                );
+                warn!("flock probe error: {} — falling through to sled::open", e);
             }
         }
 
@@ -136,8 +137,8 @@ impl LosDatabase {
         match sled::open(path_ref) {
             Ok(db) => return Ok(LosDatabase { db: Arc::new(db) }),
             Err(e) if Self::is_lock_error(&e) => {
-                eprintln!(
-                    "⚠️  Database lock held at {} — retrying ({} attempts remain)",
+                warn!(
+                    "Database lock held at {} — retrying ({} attempts remain)",
                     path_ref.display(),
                     retry_delays_ms.len()
                 );
@@ -148,8 +149,8 @@ impl LosDatabase {
         // Retry with exponential backoff — only for lock errors
         for (i, delay_ms) in retry_delays_ms.iter().enumerate() {
             std::thread::sleep(std::time::Duration::from_millis(*delay_ms));
-            eprintln!(
-                "🔄 Database lock retry {}/{} after {}ms...",
+            warn!(
+                "Database lock retry {}/{} after {}ms...",
                 i + 1,
                 retry_delays_ms.len(),
                 delay_ms
@@ -157,7 +158,7 @@ impl LosDatabase {
 
             match sled::open(path_ref) {
                 Ok(db) => {
-                    eprintln!("✅ Database lock acquired on retry {}", i + 1);
+                    info!("Database lock acquired on retry {}", i + 1);
                     return Ok(LosDatabase { db: Arc::new(db) });
                 }
                 Err(e) if Self::is_lock_error(&e) => {
@@ -257,6 +258,12 @@ impl LosDatabase {
 
         let distribution_json = serde_json::to_vec(&ledger.distribution)
             .map_err(|e| format!("Failed to serialize distribution: {}", e))?;
+        let governance_json = serde_json::to_vec(&ledger.governance)
+            .map_err(|e| format!("Failed to serialize governance: {}", e))?;
+        let chain_params_json = serde_json::to_vec(&ledger.chain_params)
+            .map_err(|e| format!("Failed to serialize chain params: {}", e))?;
+        let applied_heights_json = serde_json::to_vec(ledger.applied_heights())
+            .map_err(|e| format!("Failed to serialize applied heights: {}", e))?;
 
         // Atomic cross-tree transaction: all-or-nothing commit
         (&blocks_tree, &accounts_tree, &meta_tree)
@@ -268,11 +275,29 @@ impl LosDatabase {
                     tx_accounts.insert(key.as_slice(), value.as_slice())?;
                 }
                 tx_meta.insert(b"distribution".as_ref(), distribution_json.as_slice())?;
+                tx_meta.insert(b"governance".as_ref(), governance_json.as_slice())?;
+                tx_meta.insert(b"chain_params".as_ref(), chain_params_json.as_slice())?;
+                // Finality of a block is judged from its applied height: keep it
+                // across restarts instead of re-dating loaded blocks
+                tx_meta.insert(b"applied_heights".as_ref(), applied_heights_json.as_slice())?;
                 // FIX C11-H2: Persist accumulated_fees_cil (lives on Ledger, not DistributionState)
                 tx_meta.insert(
                     b"accumulated_fees_cil".as_ref(),
                     &ledger.accumulated_fees_cil.to_le_bytes() as &[u8],
                 )?;
+                // Pruned nodes: keep chain height and history horizon across restarts
+                tx_meta.insert(
+                    b"pruned_blocks".as_ref(),
+                    &ledger.pruned_blocks.to_le_bytes() as &[u8],
+                )?;
+                tx_meta.insert(
+                    b"pruned_before".as_ref(),
+                    &ledger.pruned_before.to_le_bytes() as &[u8],
+                )?;
+                tx_meta.insert(
+                    b"finalized_height".as_ref(),
+                    &ledger.finalized_height.to_le_bytes() as &[u8],
+                )?;
                 Ok(())
             })
             .map_err(|e: sled::transaction::TransactionError<()>| {
@@ -330,6 +355,32 @@ impl LosDatabase {
                 .map_err(|e| format!("Failed to deserialize distribution: {}", e))?;
         }
 
+        if let Some(gov_bytes) = meta_tree
+            .get(b"governance")
+            .map_err(|e| format!("Failed to read governance: {}", e))?
+        {
+            ledger.governance = serde_json::from_slice(&gov_bytes)
+                .map_err(|e| format!("Failed to deserialize governance: {}", e))?;
+        }
+
+        if let Some(params_bytes) = meta_tree
+            .get(b"chain_params")
+            .map_err(|e| format!("Failed to read chain params: {}", e))?
+        {
+            ledger.chain_params = serde_json::from_slice(&params_bytes)
+                .map_err(|e| format!("Failed to deserialize chain params: {}", e))?;
+        }
+
+        if let Some(heights_bytes) = meta_tree
+            .get(b"applied_heights")
+            .map_err(|e| format!("Failed to read applied heights: {}", e))?
+        {
+            ledger.restore_applied_heights(
+                serde_json::from_slice(&heights_bytes)
+                    .map_err(|e| format!("Failed to deserialize applied heights: {}", e))?,
+            );
+        }
+
         // FIX C11-H2: Restore accumulated_fees_cil from persistent storage
         if let Some(fee_bytes) = meta_tree
             .get(b"accumulated_fees_cil")
@@ -342,6 +393,23 @@ impl LosDatabase {
             }
         }
 
+        for (key, field) in [
+            (b"pruned_blocks".as_ref(), &mut ledger.pruned_blocks),
+            (b"pruned_before".as_ref(), &mut ledger.pruned_before),
+            (b"finalized_height".as_ref(), &mut ledger.finalized_height),
+        ] {
+            if let Some(bytes) = meta_tree
+                .get(key)
+                .map_err(|e| format!("Failed to read chain height metadata: {}", e))?
+            {
+                if bytes.len() >= 8 {
+                    let mut buf = [0u8; 8];
+                    buf.copy_from_slice(&bytes[..8]);
+                    *field = u64::from_le_bytes(buf);
+                }
+            }
+        }
+
         // 4. Rebuild claimed_sends index from loaded Receive blocks (O(1) double-receive check)
         for block in ledger.blocks.values() {
             if block.block_type == los_core::BlockType::Receive {
@@ -349,9 +417,24 @@ impl LosDatabase {
             }
         }
 
+        ledger.rebuild_indexes();
         Ok(ledger)
     }
 
+    /// Delete pruned blocks (`save_ledger` only upserts, so pruning must delete explicitly)
+    pub fn remove_blocks(&self, hashes: &[String]) -> Result<(), String> {
+        let tree = self.blocks_tree()?;
+        let mut batch = sled::Batch::default();
+        for hash in hashes {
+            batch.remove(hash.as_bytes());
+        }
+        tree.apply_batch(batch)
+            .map_err(|e| format!("Failed to remove pruned blocks: {}", e))?;
+        tree.flush()
+            .map_err(|e| format!("Failed to flush block removal: {}", e))?;
+        Ok(())
+    }
+
     /// Save single block (ATOMIC)
     #[allow(dead_code)]
     pub fn save_block(&self, hash: &str, block: &Block) -> Result<(), String> {
@@ -478,6 +561,7 @@ impl LosDatabase {
         let blocks = self.blocks_tree()?;
         let accounts = self.accounts_tree()?;
         let meta = self.meta_tree()?;
+        let mempool = self.mempool_tree()?;
 
         blocks
             .clear()
@@ -487,6 +571,9 @@ impl LosDatabase {
             .map_err(|e| format!("Failed to clear accounts: {}", e))?;
         meta.clear()
             .map_err(|e| format!("Failed to clear metadata: {}", e))?;
+        mempool
+            .clear()
+            .map_err(|e| format!("Failed to clear mempool: {}", e))?;
 
         self.db
             .flush()
@@ -525,6 +612,60 @@ impl LosDatabase {
         }
     }
 
+    // --- aBFT Consensus Write-Ahead Log ---
+
+    /// Get the consensus WAL tree (owned by `los_consensus::wal::ConsensusWal`)
+    pub fn consensus_wal_tree(&self) -> Result<Tree, String> {
+        self.db
+            .open_tree(TREE_CONSENSUS_WAL)
+            .map_err(|e| format!("Failed to open consensus WAL tree: {}", e))
+    }
+
+    // --- Mempool Persistence ---
+
+    fn mempool_tree(&self) -> Result<Tree, String> {
+        self.db
+            .open_tree(TREE_MEMPOOL)
+            .map_err(|e| format!("Failed to open mempool tree: {}", e))
+    }
+
+    /// Apply a mempool journal (see `Mempool::take_changes`) in one batch
+    pub fn apply_mempool_changes(
+        &self,
+        changes: &[(String, Option<MempoolTransaction>)],
+    ) -> Result<(), String> {
+        let tree = self.mempool_tree()?;
+        let mut batch = sled::Batch::default();
+        for (hash, tx) in changes {
+            match tx {
+                Some(tx) => {
+                    let tx_json = serde_json::to_vec(tx)
+                        .map_err(|e| format!("Failed to serialize mempool tx: {}", e))?;
+                    batch.insert(hash.as_bytes(), tx_json);
+                }
+                None => batch.remove(hash.as_bytes()),
+            }
+        }
+        tree.apply_batch(batch)
+            .map_err(|e| format!("Failed to save mempool: {}", e))?;
+        tree.flush()
+            .map_err(|e| format!("Failed to flush mempool: {}", e))?;
+        Ok(())
+    }
+
+    /// Load persisted mempool transactions (unreadable entries are skipped)
+    pub fn load_mempool(&self) -> Result<Vec<MempoolTransaction>, String> {
+        let tree = self.mempool_tree()?;
+        let mut txs = Vec::new();
+        for item in tree.iter() {
+            let (_, value) = item.map_err(|e| format!("Failed to read mempool: {}", e))?;
+            if let Ok(tx) = serde_json::from_slice::<MempoolTransaction>(&value) {
+                txs.push(tx);
+            }
+        }
+        Ok(txs)
+    }
+
     // --- Faucet Cooldown Persistence ---
 
     /// Get faucet cooldowns tree
@@ -650,6 +791,24 @@ mod tests {
                 is_validator: false,
             },
         );
+        ledger.governance.proposals.insert(
+            "p1".to_string(),
+            los_core::governance::Proposal {
+                id: "p1".to_string(),
+                proposer: "test_account".to_string(),
+                param: los_core::governance::GovParam::BaseFeeCil,
+                value: 200_000,
+                deposit_cil: 0,
+                submitted_time: 0,
+                voting_end_time: 0,
+                activation_time: 0,
+                votes: Default::default(),
+                status: los_core::governance::ProposalStatus::Passed,
+                tally: None,
+                settlement: None,
+            },
+        );
+        ledger.chain_params.genesis.chain_id = 1337;
 
         // Save
         db.save_ledger(&ledger).unwrap();
@@ -661,6 +820,8 @@ mod tests {
             loaded.accounts.get("test_account").unwrap().balance,
             1000 * CIL_PER_LOS
         );
+        assert_eq!(loaded.base_fee_cil(), 200_000);
+        assert_eq!(loaded.chain_params.genesis.chain_id, 1337);
 
         // Cleanup
         std::fs::remove_dir_all("test_db_ledger").ok();
@@ -681,6 +842,7 @@ mod tests {
             work: 0,
             timestamp: 1234567890,
             fee: 0,
+            memo: None,
         };
 
         // Save
@@ -695,6 +857,85 @@ mod tests {
         std::fs::remove_dir_all("test_db_block").ok();
     }
 
+    #[test]
+    fn test_pruned_blocks_stay_pruned() {
+        let db = LosDatabase::open("test_db_pruned").unwrap();
+
+        let mut ledger = Ledger::new();
+        for i in 0..3u64 {
+            let block = Block {
+                account: "test".to_string(),
+                previous: "0".to_string(),
+                link: format!("REP{}", i),
+                block_type: BlockType::Change,
+                amount: 0,
+                signature: String::new(),
+                public_key: String::new(),
+                work: i,
+                timestamp: 1234567890,
+                fee: 0,
+                memo: None,
+            };
+            ledger.blocks.insert(format!("hash_{}", i), block);
+        }
+        db.save_ledger(&ledger).unwrap();
+
+        // Simulate a prune pass: drop two blocks and persist the counters
+        ledger.blocks.remove("hash_0");
+        ledger.blocks.remove("hash_1");
+        ledger.pruned_blocks = 2;
+        ledger.pruned_before = 1234567999;
+        ledger.finalized_height = 1000;
+        db.remove_blocks(&["hash_0".to_string(), "hash_1".to_string()])
+            .unwrap();
+        db.save_ledger(&ledger).unwrap();
+
+        let loaded = db.load_ledger().unwrap();
+        assert_eq!(loaded.blocks.len(), 1);
+        assert_eq!(loaded.total_blocks(), 3);
+        assert_eq!(loaded.pruned_before, 1234567999);
+        assert_eq!(loaded.finalized_height, 1000);
+        // Not indexed before the save: dated at the height it was loaded at
+        assert_eq!(loaded.applied_height("hash_2"), Some(3));
+
+        // Cleanup
+        std::fs::remove_dir_all("test_db_pruned").ok();
+    }
+
+    #[test]
+    fn test_applied_heights_survive_restart() {
+        let db = LosDatabase::open("test_db_applied").unwrap();
+
+        let mut ledger = Ledger::new();
+        for i in 0..3u64 {
+            let block = Block {
+                account: "test".to_string(),
+                previous: "0".to_string(),
+                link: format!("REP{}", i),
+                block_type: BlockType::Change,
+                amount: 0,
+                signature: String::new(),
+                public_key: String::new(),
+                work: i,
+                timestamp: 1234567890,
+                fee: 0,
+                memo: None,
+            };
+            let hash = format!("hash_{}", i);
+            ledger.blocks.insert(hash.clone(), block.clone());
+            ledger.index_block(&hash, &block);
+        }
+        db.save_ledger(&ledger).unwrap();
+
+        let loaded = db.load_ledger().unwrap();
+        for i in 0..3u64 {
+            assert_eq!(loaded.applied_height(&format!("hash_{}", i)), Some(i + 1));
+        }
+
+        // Cleanup
+        std::fs::remove_dir_all("test_db_applied").ok();
+    }
+
     #[test]
     fn test_atomic_batch() {
         let db = LosDatabase::open("test_db_atomic").unwrap();
@@ -749,4 +990,34 @@ mod tests {
         // Cleanup
         std::fs::remove_dir_all("test_db_stats").ok();
     }
+
+    #[test]
+    fn test_mempool_persistence() {
+        let db = LosDatabase::open("test_db_mempool").unwrap();
+        let mut mempool = crate::mempool::Mempool::new();
+        let block = Block {
+            account: "sender".to_string(),
+            previous: "0".to_string(),
+            link: "target".to_string(),
+            block_type: BlockType::Send,
+            amount: 100,
+            signature: "sig".to_string(),
+            public_key: "pk".to_string(),
+            work: 0,
+            timestamp: 1234567890,
+            fee: 100_000,
+            memo: None,
+        };
+        let hash = mempool.add_transaction(block, 100_000, 1).unwrap();
+        db.apply_mempool_changes(&mempool.take_changes()).unwrap();
+        let loaded = db.load_mempool().unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].block.calculate_hash(), hash);
+
+        mempool.remove_transaction(&hash);
+        db.apply_mempool_changes(&mempool.take_changes()).unwrap();
+        assert!(db.load_mempool().unwrap().is_empty());
+
+        std::fs::remove_dir_all("test_db_mempool").ok();
+    }
 }
@@ -0,0 +1,459 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - PRIVATE DEVNET LAUNCHER (`los-node devnet`)
+//
+// Spins up a throwaway multi-validator chain on localhost for integration
+// tests and app development:
+//   1. Generate fresh BIP39 seeds for N bootstrap validators + 1 dev wallet
+//   2. Write a legacy-format testnet genesis (the `testnet_generator` format)
+//      with a `chain_params` section carrying the devnet chain ID
+//   3. Start N child `los-node` processes on consecutive localhost ports with
+//      LOS_LOCAL_ONLY=1 (no Tor, no mDNS, P2P on 127.0.0.1)
+//   4. Wait for every /health, print the endpoints and write devnet.json
+//   5. On Ctrl+C, kill the children and delete the devnet directory
+//
+// TESTNET BUILDS ONLY: the seeds are generated on the fly and written to
+// disk in plain text.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use los_core::chain_params::{
+    ChainParams, ChainParamsSchedule, MEMO_PARAMS_VERSION, POW_DIFFICULTY_BITS_RANGE,
+};
+use los_genesis::{testnet_genesis_json, TestnetWallet};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::process::{Child, Command};
+
+/// Stake given to each genesis validator (the minimum validator stake)
+const VALIDATOR_BALANCE_LOS: u128 = 1_000;
+/// Balance of the funded dev wallet used by tests and apps
+const DEV_WALLET_BALANCE_LOS: u128 = 1_000_000;
+/// Chain ID used when `--chain-id` is not given
+const DEFAULT_DEVNET_CHAIN_ID: u64 = 1337;
+/// How long a node may take to answer /health before the launch is aborted
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Command-line options of `los-node devnet`
+#[derive(Debug, Clone, PartialEq)]
+pub struct DevnetOptions {
+    pub nodes: usize,
+    pub base_port: u16,
+    pub dir: Option<PathBuf>,
+    pub chain_id: u64,
+    pub level: String,
+    pub keep: bool,
+}
+
+impl Default for DevnetOptions {
+    fn default() -> Self {
+        Self {
+            nodes: 4,
+            base_port: 3030,
+            dir: None,
+            chain_id: DEFAULT_DEVNET_CHAIN_ID,
+            level: "consensus".to_string(),
+            keep: false,
+        }
+    }
+}
+
+impl DevnetOptions {
+    pub fn parse(args: &[String]) -> Result<Self, String> {
+        let mut opts = Self::default();
+        let mut i = 0;
+        while i < args.len() {
+            let value = |i: usize| {
+                args.get(i + 1)
+                    .cloned()
+                    .ok_or_else(|| format!("{} requires a value", args[i]))
+            };
+            match args[i].as_str() {
+                "--nodes" => {
+                    opts.nodes = value(i)?.parse().map_err(|_| "Invalid --nodes")?;
+                    i += 1;
+                }
+                "--base-port" => {
+                    opts.base_port = value(i)?.parse().map_err(|_| "Invalid --base-port")?;
+                    i += 1;
+                }
+                "--dir" => {
+                    opts.dir = Some(PathBuf::from(value(i)?));
+                    i += 1;
+                }
+                "--chain-id" => {
+                    opts.chain_id = value(i)?.parse().map_err(|_| "Invalid --chain-id")?;
+                    i += 1;
+                }
+                "--level" => {
+                    opts.level = value(i)?;
+                    i += 1;
+                }
+                "--keep" => opts.keep = true,
+                other => return Err(format!("Unknown devnet option: {}", other)),
+            }
+            i += 1;
+        }
+
+        if !(1..=16).contains(&opts.nodes) {
+            return Err("--nodes must be between 1 and 16".to_string());
+        }
+        if !["functional", "consensus", "production"].contains(&opts.level.as_str()) {
+            return Err("--level must be functional, consensus or production".to_string());
+        }
+        // REST, P2P (+1000) and gRPC (+20000) ports of the last node must fit
+        if opts.base_port as usize + opts.nodes + 20_000 > u16::MAX as usize {
+            return Err("--base-port too high for the requested node count".to_string());
+        }
+        let params = opts.chain_params();
+        if params.is_mainnet() {
+            return Err("Chain ID 1 is reserved for mainnet".to_string());
+        }
+        params.validate()?;
+        Ok(opts)
+    }
+
+    /// Testnet defaults with the devnet chain ID and the cheapest allowed PoW
+    /// (blocks solved at higher difficulty, e.g. by los-cli, still verify).
+    /// Devnets start at the memo parameter version so Send memos work out of the box,
+    /// with the per-call gas cap active from genesis.
+    pub fn chain_params(&self) -> ChainParams {
+        ChainParams {
+            version: MEMO_PARAMS_VERSION,
+            chain_id: self.chain_id,
+            min_pow_difficulty_bits: POW_DIFFICULTY_BITS_RANGE.0,
+            max_gas_per_call: Some(los_vm::MAX_GAS_PER_CALL),
+            ..ChainParams::testnet()
+        }
+    }
+
+    fn rest_port(&self, i: usize) -> u16 {
+        self.base_port + i as u16
+    }
+
+    fn p2p_port(&self, i: usize) -> u16 {
+        self.rest_port(i) + 1000
+    }
+}
+
+/// Fresh bootstrap validator wallets plus the funded dev wallet
+pub fn generate_wallets(nodes: usize) -> (Vec<TestnetWallet>, TestnetWallet) {
+    let validators = (1..=nodes)
+        .map(|n| {
+            TestnetWallet::generate(
+                &format!("BootstrapNode({})", n),
+                VALIDATOR_BALANCE_LOS,
+                &format!("Devnet validator #{}", n),
+            )
+        })
+        .collect();
+    let dev_wallet =
+        TestnetWallet::generate("DevWallet", DEV_WALLET_BALANCE_LOS, "Devnet dev wallet");
+    (validators, dev_wallet)
+}
+
+/// Testnet genesis for `validators` and `dev_wallet`, on the devnet chain params
+pub fn genesis_json(
+    opts: &DevnetOptions,
+    validators: &[TestnetWallet],
+    dev_wallet: &TestnetWallet,
+) -> serde_json::Value {
+    let wallets: Vec<TestnetWallet> = validators
+        .iter()
+        .chain(std::iter::once(dev_wallet))
+        .cloned()
+        .collect();
+    let mut genesis = testnet_genesis_json(
+        &format!("Private devnet — {} validators", validators.len()),
+        "DEVNET ONLY — throwaway keys",
+        &wallets,
+    );
+    genesis["network_id"] = opts.chain_id.into();
+    genesis["chain_params"] = serde_json::json!(ChainParamsSchedule::new(opts.chain_params()));
+    genesis
+}
+
+/// Entry point of `los-node devnet [options]`
+pub async fn run(args: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    if los_core::is_mainnet_build() {
+        return Err("los-node devnet is not available on mainnet builds".into());
+    }
+    let opts = DevnetOptions::parse(args)?;
+
+    let dir = match &opts.dir {
+        Some(d) => d.clone(),
+        None => std::env::temp_dir().join(format!("los-devnet-{}", std::process::id())),
+    };
+    if dir.exists() && std::fs::read_dir(&dir)?.next().is_some() {
+        return Err(format!(
+            "Devnet directory {} is not empty — remove it or pass another --dir",
+            dir.display()
+        )
+        .into());
+    }
+    std::fs::create_dir_all(&dir)?;
+
+    println!(
+        "🧪 Creating devnet: {} validators, chain ID {}, level {}",
+        opts.nodes, opts.chain_id, opts.level
+    );
+    let (validators, dev_wallet) = generate_wallets(opts.nodes);
+    let genesis_path = dir.join("genesis.json");
+    std::fs::write(
+        &genesis_path,
+        serde_json::to_string_pretty(&genesis_json(&opts, &validators, &dev_wallet))?,
+    )?;
+
+    let exe = std::env::current_exe()?;
+    let mut children: Vec<Child> = Vec::new();
+    for (i, validator) in validators.iter().enumerate() {
+        match spawn_node(&exe, &opts, &dir, &genesis_path, i, validator).await {
+            Ok(child) => children.push(child),
+            Err(e) => {
+                teardown(&mut children, &dir, opts.keep).await;
+                return Err(format!("Failed to start devnet-{}: {}", i + 1, e).into());
+            }
+        }
+    }
+
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(2))
+        .build()?;
+    for i in 0..opts.nodes {
+        if let Err(e) = wait_healthy(&client, &mut children[i], opts.rest_port(i)).await {
+            teardown(&mut children, &dir, opts.keep).await;
+            return Err(format!(
+                "devnet-{} did not come up: {} (log: {})",
+                i + 1,
+                e,
+                node_dir(&dir, i).join("node.log").display()
+            )
+            .into());
+        }
+    }
+
+    let nodes: Vec<serde_json::Value> = validators
+        .iter()
+        .enumerate()
+        .map(|(i, v)| {
+            serde_json::json!({
+                "node_id": format!("devnet-{}", i + 1),
+                "address": v.address,
+                "rest": format!("http://127.0.0.1:{}", opts.rest_port(i)),
+                "grpc": format!("127.0.0.1:{}", opts.rest_port(i) as u32 + 20_000),
+                "p2p": format!("127.0.0.1:{}", opts.p2p_port(i)),
+                "data_dir": node_dir(&dir, i),
+            })
+        })
+        .collect();
+    let manifest = serde_json::json!({
+        "chain_id": opts.chain_id,
+        "genesis": genesis_path,
+        "nodes": nodes,
+        "dev_wallet": {
+            "address": dev_wallet.address,
+            "seed_phrase": dev_wallet.seed_phrase,
+            "balance_los": DEV_WALLET_BALANCE_LOS.to_string(),
+        },
+    });
+    std::fs::write(
+        dir.join("devnet.json"),
+        serde_json::to_string_pretty(&manifest)?,
+    )?;
+
+    println!();
+    println!("✅ Devnet is up (chain ID {})", opts.chain_id);
+    println!("------------------------------------------------------------------");
+    for node in &nodes {
+        println!(
+            "  {:<10} REST {:<24} gRPC {:<17} P2P {}",
+            node["node_id"].as_str().unwrap_or("?"),
+            node["rest"].as_str().unwrap_or("?"),
+            node["grpc"].as_str().unwrap_or("?"),
+            node["p2p"].as_str().unwrap_or("?")
+        );
+    }
+    println!("------------------------------------------------------------------");
+    println!(
+        "  Dev wallet: {} ({} LOS)",
+        dev_wallet.address, DEV_WALLET_BALANCE_LOS
+    );
+    println!("  Manifest:   {}", dir.join("devnet.json").display());
+    println!(
+        "  CLI:        los-cli --rpc http://127.0.0.1:{} --chain-id {} ...",
+        opts.rest_port(0),
+        opts.chain_id
+    );
+    println!("Press Ctrl+C to stop the devnet.");
+
+    let mut reported_exit = vec![false; children.len()];
+    let mut watchdog = tokio::time::interval(Duration::from_secs(2));
+    loop {
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => break,
+            _ = watchdog.tick() => {
+                for (i, child) in children.iter_mut().enumerate() {
+                    if let Ok(Some(status)) = child.try_wait() {
+                        if !reported_exit[i] {
+                            reported_exit[i] = true;
+                            eprintln!("⚠️ devnet-{} exited ({}) — see its node.log", i + 1, status);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    println!("\n🛑 Stopping devnet...");
+    teardown(&mut children, &dir, opts.keep).await;
+    Ok(())
+}
+
+fn node_dir(dir: &Path, i: usize) -> PathBuf {
+    dir.join(format!("node-{}", i + 1))
+}
+
+/// Start validator `i` as a child process, seed piped on stdin
+async fn spawn_node(
+    exe: &Path,
+    opts: &DevnetOptions,
+    dir: &Path,
+    genesis_path: &Path,
+    i: usize,
+    validator: &TestnetWallet,
+) -> Result<Child, Box<dyn std::error::Error>> {
+    let data_dir = node_dir(dir, i);
+    std::fs::create_dir_all(&data_dir)?;
+    let log = std::fs::File::create(data_dir.join("node.log"))?;
+
+    // Every other node is a bootstrap peer
+    let peers: Vec<String> = (0..opts.nodes)
+        .filter(|&j| j != i)
+        .map(|j| format!("127.0.0.1:{}", opts.p2p_port(j)))
+        .collect();
+
+    let mut child = Command::new(exe)
+        .arg("--port")
+        .arg(opts.rest_port(i).to_string())
+        .arg("--data-dir")
+        .arg(&data_dir)
+        .arg("--node-id")
+        .arg(format!("devnet-{}", i + 1))
+        .env("LOS_GENESIS_FILE", genesis_path)
+        .env("LOS_LOCAL_ONLY", "1")
+        .env("LOS_P2P_PORT", opts.p2p_port(i).to_string())
+        .env("LOS_BOOTSTRAP_NODES", peers.join(","))
+        .env("LOS_TESTNET_LEVEL", &opts.level)
+        .env_remove("LOS_SOCKS5_PROXY")
+        .env_remove("LOS_TOR_SOCKS5")
+        .env_remove("LOS_ONION_ADDRESS")
+        .env_remove("LOS_SEED_PHRASE")
+        .env_remove("LOS_WALLET_PASSWORD")
+        .env_remove("LOS_BIND_ALL")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::from(log.try_clone()?))
+        .stderr(Stdio::from(log))
+        .kill_on_drop(true)
+        .spawn()?;
+
+    // Line 1: wallet password (empty = testnet auto-key), line 2: seed phrase
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(format!("\n{}\n", validator.seed_phrase).as_bytes())
+            .await?;
+    }
+    Ok(child)
+}
+
+async fn wait_healthy(
+    client: &reqwest::Client,
+    child: &mut Child,
+    port: u16,
+) -> Result<(), String> {
+    let url = format!("http://127.0.0.1:{}/health", port);
+    let deadline = tokio::time::Instant::now() + STARTUP_TIMEOUT;
+    loop {
+        if let Ok(Some(status)) = child.try_wait() {
+            return Err(format!("process exited ({})", status));
+        }
+        if let Ok(resp) = client.get(&url).send().await {
+            if resp.status().is_success() {
+                return Ok(());
+            }
+        }
+        if tokio::time::Instant::now() >= deadline {
+            return Err(format!(
+                "no /health answer on port {} after {:?}",
+                port, STARTUP_TIMEOUT
+            ));
+        }
+        tokio::time::sleep(Duration::from_millis(250)).await;
+    }
+}
+
+async fn teardown(children: &mut [Child], dir: &Path, keep: bool) {
+    for child in children.iter_mut() {
+        let _ = child.start_kill();
+    }
+    for child in children.iter_mut() {
+        let _ = child.wait().await;
+    }
+    if keep {
+        println!("📂 Devnet data kept in {}", dir.display());
+    } else if let Err(e) = std::fs::remove_dir_all(dir) {
+        eprintln!("⚠️ Failed to remove {}: {}", dir.display(), e);
+    } else {
+        println!("🧹 Removed {}", dir.display());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_parse_options() {
+        assert_eq!(DevnetOptions::parse(&[]).unwrap(), DevnetOptions::default());
+
+        let opts =
+            DevnetOptions::parse(&args("--nodes 3 --base-port 5000 --chain-id 9 --keep")).unwrap();
+        assert_eq!(opts.nodes, 3);
+        assert_eq!(opts.p2p_port(2), 6002);
+        assert_eq!(opts.chain_id, 9);
+        assert!(opts.keep);
+
+        assert!(DevnetOptions::parse(&args("--nodes 0")).is_err());
+        assert!(DevnetOptions::parse(&args("--level turbo")).is_err());
+        assert!(DevnetOptions::parse(&args("--chain-id")).is_err());
+        assert!(DevnetOptions::parse(&args("--base-port 65000")).is_err());
+        // Chain ID 1 is reserved for mainnet
+        assert!(DevnetOptions::parse(&args("--chain-id 1")).is_err());
+    }
+
+    #[test]
+    fn test_genesis_json() {
+        let opts = DevnetOptions {
+            nodes: 2,
+            ..DevnetOptions::default()
+        };
+        let (validators, dev_wallet) = generate_wallets(2);
+        assert!(dev_wallet.address.starts_with("LOS"));
+        assert_ne!(validators[0].address, validators[1].address);
+
+        let genesis = genesis_json(&opts, &validators, &dev_wallet);
+        let wallets = genesis["wallets"].as_array().unwrap();
+        assert_eq!(wallets.len(), 3);
+        assert_eq!(wallets[1]["wallet_type"], "BootstrapNode(2)");
+        assert_eq!(wallets[1]["address"], validators[1].address.as_str());
+        assert_eq!(wallets[2]["address"], dev_wallet.address.as_str());
+
+        let schedule = crate::genesis::chain_params_from_json(&genesis.to_string())
+            .unwrap()
+            .unwrap();
+        assert_eq!(schedule.genesis.chain_id, DEFAULT_DEVNET_CHAIN_ID);
+    }
+}
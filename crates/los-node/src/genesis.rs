@@ -7,6 +7,7 @@
 #![allow(dead_code)]
 
 use crate::{AccountState, CIL_PER_LOS};
+use los_core::chain_params::{ChainParams, ChainParamsSchedule};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -72,6 +73,9 @@ pub struct GenesisConfig {
     pub dev_allocation: Option<String>,
     #[serde(default)]
     pub wallets: Option<Vec<GenesisWallet>>,
+    // === Runtime chain parameters (both formats) ===
+    #[serde(default)]
+    pub chain_params: Option<ChainParamsSchedule>,
 }
 
 /// Initialize ledger with genesis state from JSON file.
@@ -86,6 +90,31 @@ pub fn load_genesis_from_file(path: &str) -> Result<HashMap<String, AccountState
     load_genesis_from_config(&genesis_config)
 }
 
+/// Read and validate the optional `chain_params` section of a genesis file.
+/// Works on both schemas (the testnet wallets file is parsed as raw JSON).
+/// `None` = no section: the chain runs on the defaults of this build.
+pub fn chain_params_from_json(json_data: &str) -> Result<Option<ChainParamsSchedule>, String> {
+    let value: serde_json::Value = serde_json::from_str(json_data)
+        .map_err(|e| format!("Failed to parse genesis JSON: {}", e))?;
+    let Some(section) = value.get("chain_params") else {
+        return Ok(None);
+    };
+    let schedule: ChainParamsSchedule = serde_json::from_value(section.clone())
+        .map_err(|e| format!("Invalid chain_params in genesis: {}", e))?;
+    schedule
+        .validate()
+        .map_err(|e| format!("Invalid chain_params in genesis: {}", e))?;
+    if let Some(network_id) = value["network_id"].as_u64() {
+        if network_id != schedule.genesis.chain_id {
+            return Err(format!(
+                "Genesis network_id {} does not match chain_params chain_id {}",
+                network_id, schedule.genesis.chain_id
+            ));
+        }
+    }
+    Ok(Some(schedule))
+}
+
 /// Resolve the CIL balance from a GenesisWallet.
 /// Prefers balance_cil (integer), falls back to stake_cil, then balance_los (parsed).
 fn resolve_wallet_balance(wallet: &GenesisWallet) -> Result<u128, String> {
@@ -278,8 +307,13 @@ pub fn validate_genesis(config: &GenesisConfig) -> Result<(), String> {
                 nodes.len()
             ));
         }
-        // Validate each bootstrap node has sufficient stake
-        let min_stake = los_core::MIN_VALIDATOR_STAKE_CIL;
+        // Validate each bootstrap node has sufficient stake (genesis params, else build defaults)
+        let min_stake = config
+            .chain_params
+            .as_ref()
+            .map(|c| c.genesis.clone())
+            .unwrap_or_else(ChainParams::for_build)
+            .min_validator_stake_cil;
         for node in nodes {
             if let Some(sv) = node.stake_cil {
                 if sv < min_stake {
@@ -334,6 +368,7 @@ mod tests {
             dev_supply_cil: None,
             bootstrap_nodes: None,
             dev_accounts: None,
+            chain_params: None,
         }
     }
 
@@ -368,6 +403,7 @@ mod tests {
             total_supply: None,
             dev_allocation: None,
             wallets: None,
+            chain_params: None,
         }
     }
 
@@ -466,6 +502,7 @@ mod tests {
             dev_supply_cil: None,
             bootstrap_nodes: None,
             dev_accounts: None,
+            chain_params: None,
         };
         let accounts = load_genesis_from_config(&config).unwrap();
         assert_eq!(accounts.len(), 1);
@@ -474,4 +511,34 @@ mod tests {
             1000 * CIL_PER_LOS
         );
     }
+
+    #[test]
+    fn test_chain_params_section() {
+        assert_eq!(chain_params_from_json(r#"{"network":"testnet"}"#), Ok(None));
+
+        let json = r#"{
+            "network_id": 1337,
+            "chain_params": {
+                "genesis": {
+                    "chain_id": 1337, "min_pow_difficulty_bits": 8,
+                    "base_fee_cil": 1000, "min_validator_stake_cil": 100000000000000,
+                    "max_mint_per_block_cil": 100000000000000,
+                    "reward_rate_initial_cil": 500000000000000,
+                    "reward_epoch_secs": 60, "default_gas_limit": 1000000
+                }
+            }
+        }"#;
+        let schedule = chain_params_from_json(json).unwrap().unwrap();
+        assert_eq!(schedule.genesis.chain_id, 1337);
+        let config: GenesisConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(config.chain_params, Some(schedule));
+
+        let mismatch = json.replace(r#""network_id": 1337"#, r#""network_id": 2"#);
+        assert!(chain_params_from_json(&mismatch)
+            .unwrap_err()
+            .contains("does not match"));
+        let invalid = json.replace(r#""base_fee_cil": 1000"#, r#""base_fee_cil": 0"#);
+        assert!(chain_params_from_json(&invalid).is_err());
+        assert!(chain_params_from_json("not json {").is_err());
+    }
 }
@@ -1,6 +1,6 @@
 /// Unauthority gRPC Server Implementation
 ///
-/// Provides 8 core gRPC services for external integration:
+/// Provides 11 core gRPC services for external integration:
 /// 1. GetBalance - Query account balance
 /// 2. GetAccount - Get full account details
 /// 3. GetBlock - Get block by hash
@@ -9,12 +9,16 @@
 /// 6. GetNodeInfo - Get node/oracle/supply info
 /// 7. GetValidators - List all active validators
 /// 8. GetBlockHeight - Get current blockchain height
+/// 9. QueryContract - Read-only contract call (state discarded)
+/// 10. GetTransactionStatus - Transaction lifecycle state
+/// 11. GetPendingReceivables - Unclaimed Sends addressed to an account
 use los_consensus::voting::calculate_voting_power;
-use los_core::{Ledger, CIL_PER_LOS, MIN_VALIDATOR_STAKE_CIL};
+use los_core::{Ledger, CIL_PER_LOS};
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 use tokio::sync::mpsc;
 use tonic::{transport::Server, Request, Response, Status};
+use tracing::{debug, info};
 
 // Include generated protobuf code
 pub mod proto {
@@ -23,10 +27,13 @@ pub mod proto {
 
 use proto::{
     los_node_server::{LosNode, LosNodeServer},
-    GetAccountRequest, GetAccountResponse, GetBalanceRequest, GetBalanceResponse,
-    GetBlockHeightRequest, GetBlockHeightResponse, GetBlockRequest, GetBlockResponse,
-    GetLatestBlockRequest, GetNodeInfoRequest, GetNodeInfoResponse, GetValidatorsRequest,
-    GetValidatorsResponse, SendTransactionRequest, SendTransactionResponse, ValidatorInfo,
+    ContractEvent, ContractKeyValue, GetAccountRequest, GetAccountResponse, GetBalanceRequest,
+    GetBalanceResponse, GetBlockHeightRequest, GetBlockHeightResponse, GetBlockRequest,
+    GetBlockResponse, GetLatestBlockRequest, GetNodeInfoRequest, GetNodeInfoResponse,
+    GetPendingReceivablesRequest, GetPendingReceivablesResponse, GetTransactionStatusRequest,
+    GetTransactionStatusResponse, GetValidatorsRequest, GetValidatorsResponse, PendingReceivable,
+    QueryContractRequest, QueryContractResponse, SendTransactionRequest, SendTransactionResponse,
+    ValidatorInfo,
 };
 
 /// gRPC Service Implementation
@@ -103,6 +110,20 @@ fn get_short_addr(full: &str) -> String {
     }
 }
 
+/// Flatten a JSON object of strings (REST event data, state changes) into key/value pairs
+fn key_values(obj: &serde_json::Value) -> Vec<ContractKeyValue> {
+    obj.as_object()
+        .map(|map| {
+            map.iter()
+                .map(|(key, value)| ContractKeyValue {
+                    key: key.clone(),
+                    value: value.as_str().unwrap_or_default().to_string(),
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
 #[tonic::async_trait]
 impl LosNode for LosGrpcService {
     /// 1. Get account balance
@@ -141,11 +162,10 @@ impl LosNode for LosGrpcService {
             balance_cil_str: account.balance.to_string(), // Full-precision u128 as string
         };
 
-        println!(
-            "📊 gRPC GetBalance: {} -> {}.{} LOS",
-            get_short_addr(&response.address),
-            balance_los,
-            balance_remainder
+        debug!(
+            address = %get_short_addr(&response.address),
+            balance = %format!("{}.{}", balance_los, balance_remainder),
+            "gRPC GetBalance"
         );
 
         Ok(Response::new(response))
@@ -199,10 +219,10 @@ impl LosNode for LosGrpcService {
             },
         };
 
-        println!(
-            "🔍 gRPC GetAccount: {} (validator: {})",
-            get_short_addr(&full_addr),
-            is_validator
+        debug!(
+            address = %get_short_addr(&full_addr),
+            is_validator,
+            "gRPC GetAccount"
         );
 
         Ok(Response::new(response))
@@ -249,10 +269,10 @@ impl LosNode for LosGrpcService {
             },
         };
 
-        println!(
-            "📦 gRPC GetBlock: {} (type: {})",
-            &hash[..12],
-            response.block_type
+        debug!(
+            hash = &hash[..12],
+            block_type = %response.block_type,
+            "gRPC GetBlock"
         );
 
         Ok(Response::new(response))
@@ -297,10 +317,10 @@ impl LosNode for LosGrpcService {
             representative: "".to_string(),
         };
 
-        println!(
-            "🆕 gRPC GetLatestBlock: {} (ts: {})",
-            &hash[..12.min(hash.len())],
-            block.timestamp
+        debug!(
+            hash = &hash[..12.min(hash.len())],
+            timestamp = block.timestamp,
+            "gRPC GetLatestBlock"
         );
 
         Ok(Response::new(response))
@@ -386,10 +406,11 @@ impl LosNode for LosGrpcService {
             .map_err(|_| Status::internal("Failed to lock ledger"))?;
 
         // Check if this node is validator
+        let min_stake = ledger.min_validator_stake_cil();
         let is_validator = ledger
             .accounts
             .get(&self.my_address)
-            .map(|a| a.balance >= MIN_VALIDATOR_STAKE_CIL)
+            .map(|a| a.balance >= min_stake)
             .unwrap_or(false);
 
         // Oracle prices not available in gRPC context (use REST /oracle endpoint)
@@ -400,11 +421,11 @@ impl LosNode for LosGrpcService {
         let btc_price = 0.0_f64;
 
         // Calculate latest block height (count total blocks)
-        let latest_height = ledger.blocks.len() as u64;
+        let latest_height = ledger.total_blocks() as u64;
 
         let response = GetNodeInfoResponse {
             node_address: self.my_address.clone(),
-            network_id: los_core::CHAIN_ID as u32, // CHAIN_ID: 1=mainnet, 2=testnet
+            network_id: los_core::chain_params::chain_id() as u32, // 1=mainnet, 2=testnet, else devnet
             chain_name: "Unauthority".to_string(),
             version: env!("CARGO_PKG_VERSION").to_string(),
             // FIX C11-M4: Use .min() saturation instead of hard-coding 0
@@ -424,10 +445,10 @@ impl LosNode for LosGrpcService {
             is_validator,
         };
 
-        println!(
-            "ℹ️  gRPC GetNodeInfo: {} (validator: {})",
-            get_short_addr(&self.my_address),
-            is_validator
+        debug!(
+            address = %get_short_addr(&self.my_address),
+            is_validator,
+            "gRPC GetNodeInfo"
         );
 
         Ok(Response::new(response))
@@ -443,7 +464,7 @@ impl LosNode for LosGrpcService {
             .lock()
             .map_err(|_| Status::internal("Failed to lock ledger"))?;
 
-        let min_stake = MIN_VALIDATOR_STAKE_CIL;
+        let min_stake = ledger.min_validator_stake_cil();
 
         let peer_addresses: std::collections::HashSet<String> = self
             .address_book
@@ -493,7 +514,7 @@ impl LosNode for LosGrpcService {
 
         let total_count = validators.len() as u32;
 
-        println!("👥 gRPC GetValidators: {} active validators", total_count);
+        debug!(active = total_count, "gRPC GetValidators");
 
         let response = GetValidatorsResponse {
             validators,
@@ -514,7 +535,7 @@ impl LosNode for LosGrpcService {
             .map_err(|_| Status::internal("Failed to lock ledger"))?;
 
         // Find latest block by timestamp (or use total count as height)
-        let total_blocks = ledger.blocks.len() as u64;
+        let total_blocks = ledger.total_blocks() as u64;
 
         let latest_hash = ledger
             .blocks
@@ -529,10 +550,184 @@ impl LosNode for LosGrpcService {
             timestamp: chrono::Utc::now().timestamp() as u64,
         };
 
-        println!("📏 gRPC GetBlockHeight: {}", response.height);
+        debug!(height = response.height, "gRPC GetBlockHeight");
 
         Ok(Response::new(response))
     }
+
+    /// 9. Read-only contract query
+    async fn query_contract(
+        &self,
+        request: Request<QueryContractRequest>,
+    ) -> Result<Response<QueryContractResponse>, Status> {
+        let req = request.into_inner();
+        if req.contract_address.is_empty() || req.function.is_empty() {
+            return Err(Status::invalid_argument(
+                "contract_address and function are required",
+            ));
+        }
+
+        // Forward to local REST /query-contract, which owns the WASM engine
+        let rest_url = format!(
+            "http://{}:{}/query-contract",
+            self.rest_bind_host, self.rest_api_port
+        );
+        let payload = serde_json::json!({
+            "contract_address": req.contract_address,
+            "function": req.function,
+            "args": req.args,
+            "gas_limit": if req.gas_limit == 0 { None } else { Some(req.gas_limit) },
+            "caller": req.caller,
+        });
+
+        let resp = self
+            .http_client
+            .post(&rest_url)
+            .json(&payload)
+            .send()
+            .await
+            .map_err(|e| {
+                Status::unavailable(format!(
+                    "REST API unavailable: {}. Ensure the node is running.",
+                    e
+                ))
+            })?;
+        let body: serde_json::Value = resp
+            .json()
+            .await
+            .map_err(|e| Status::internal(format!("Failed to parse REST response: {}", e)))?;
+
+        if body["status"].as_str() != Some("success") {
+            return Err(Status::failed_precondition(
+                body["msg"].as_str().unwrap_or("Query failed").to_string(),
+            ));
+        }
+        let result = &body["result"];
+        Ok(Response::new(QueryContractResponse {
+            success: result["success"].as_bool().unwrap_or(false),
+            output: result["output"].as_str().unwrap_or_default().to_string(),
+            return_code: result["return_code"].as_i64().unwrap_or(0) as i32,
+            return_data: hex::decode(result["return_data"].as_str().unwrap_or_default())
+                .unwrap_or_default(),
+            gas_used: result["gas_used"].as_u64().unwrap_or(0),
+            logs: result["logs"]
+                .as_array()
+                .map(|logs| {
+                    logs.iter()
+                        .filter_map(|l| l.as_str().map(str::to_string))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            events: result["events"]
+                .as_array()
+                .map(|events| {
+                    events
+                        .iter()
+                        .map(|e| ContractEvent {
+                            contract: e["contract"].as_str().unwrap_or_default().to_string(),
+                            event_type: e["event_type"].as_str().unwrap_or_default().to_string(),
+                            data: key_values(&e["data"]),
+                            timestamp: e["timestamp"].as_u64().unwrap_or(0),
+                        })
+                        .collect()
+                })
+                .unwrap_or_default(),
+            state_changes: key_values(&result["state_changes"]),
+        }))
+    }
+
+    /// 10. Transaction lifecycle status
+    async fn get_transaction_status(
+        &self,
+        request: Request<GetTransactionStatusRequest>,
+    ) -> Result<Response<GetTransactionStatusResponse>, Status> {
+        let req = request.into_inner();
+        if req.tx_hash.is_empty() {
+            return Err(Status::invalid_argument("tx_hash is required"));
+        }
+
+        // Forward to local REST /transaction/{hash}/status, which sees mempool and votes
+        let rest_url = format!(
+            "http://{}:{}/transaction/{}/status",
+            self.rest_bind_host, self.rest_api_port, req.tx_hash
+        );
+        let body: serde_json::Value = self
+            .http_client
+            .get(&rest_url)
+            .send()
+            .await
+            .map_err(|e| {
+                Status::unavailable(format!(
+                    "REST API unavailable: {}. Ensure the node is running.",
+                    e
+                ))
+            })?
+            .json()
+            .await
+            .map_err(|e| Status::internal(format!("Failed to parse REST response: {}", e)))?;
+
+        if body["status"].as_str() != Some("success") {
+            return Err(Status::not_found(
+                body["msg"]
+                    .as_str()
+                    .unwrap_or("Unknown transaction")
+                    .to_string(),
+            ));
+        }
+        let tx = &body["transaction"];
+        Ok(Response::new(GetTransactionStatusResponse {
+            tx_hash: req.tx_hash,
+            state: tx["state"].as_str().unwrap_or_default().to_string(),
+            account: tx["account"].as_str().unwrap_or_default().to_string(),
+            reason: tx["reason"].as_str().unwrap_or_default().to_string(),
+            vote_power: tx["vote_power"].as_u64().unwrap_or(0),
+            voters: tx["voters"].as_u64().unwrap_or(0) as u32,
+            vote_threshold: tx["vote_threshold"].as_u64().unwrap_or(0),
+            checkpoint_height: tx["checkpoint_height"].as_u64().unwrap_or(0),
+        }))
+    }
+
+    /// 11. Unclaimed Sends addressed to an account, oldest first
+    async fn get_pending_receivables(
+        &self,
+        request: Request<GetPendingReceivablesRequest>,
+    ) -> Result<Response<GetPendingReceivablesResponse>, Status> {
+        let addr = request.into_inner().address;
+        if addr.is_empty() {
+            return Err(Status::invalid_argument("address is required"));
+        }
+        // The recipient may not have an account yet: fall back to the address as given
+        let full_addr = self.resolve_address(&addr).unwrap_or(addr);
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        let ledger = self
+            .ledger
+            .lock()
+            .map_err(|_| Status::internal("Failed to lock ledger"))?;
+        let sends = ledger.pending_receivables(&full_addr);
+        let total: u128 = sends.iter().map(|(_, b)| b.amount).sum();
+        let pending: Vec<PendingReceivable> = sends
+            .iter()
+            .map(|(hash, b)| PendingReceivable {
+                send_hash: hash.to_string(),
+                from: b.account.clone(),
+                amount_cil_str: b.amount.to_string(),
+                timestamp: b.timestamp,
+                age_secs: now.saturating_sub(b.timestamp),
+                memo: b.memo.clone().unwrap_or_default(),
+            })
+            .collect();
+
+        Ok(Response::new(GetPendingReceivablesResponse {
+            address: full_addr,
+            count: pending.len() as u32,
+            pending,
+            total_cil_str: total.to_string(),
+        }))
+    }
 }
 
 /// Start gRPC server (runs alongside REST API)
@@ -562,13 +757,12 @@ pub async fn start_grpc_server(
         rest_api_port,
     );
 
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("🚀 gRPC Server STARTED");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("   Address: {}", addr);
-    println!("   Node: {}", get_short_addr(&my_address));
-    println!("   Services: 8 core gRPC endpoints");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    info!(
+        %addr,
+        node = %get_short_addr(&my_address),
+        services = 11,
+        "gRPC Server STARTED"
+    );
 
     Server::builder()
         .add_service(LosNodeServer::new(service))
@@ -681,4 +875,62 @@ mod tests {
         assert!(val1.voting_power > 0.0);
         assert!(val1.is_active);
     }
+
+    #[tokio::test]
+    async fn test_grpc_get_pending_receivables() {
+        let mut ledger = Ledger::new();
+        let mut claimed = String::new();
+        for (i, memo) in [None, Some("user-42")].into_iter().enumerate() {
+            let block = los_core::Block {
+                account: "sender".to_string(),
+                previous: "0".to_string(),
+                block_type: los_core::BlockType::Send,
+                amount: (i as u128 + 1) * CIL_PER_LOS,
+                link: "recipient".to_string(),
+                signature: String::new(),
+                public_key: String::new(),
+                work: 0,
+                timestamp: 1_700_000_000 + i as u64,
+                fee: 100_000,
+                memo: memo.map(str::to_string),
+            };
+            claimed = block.calculate_hash();
+            ledger.blocks.insert(claimed.clone(), block.clone());
+            ledger.index_block(&claimed, &block);
+        }
+        let ledger = Arc::new(Mutex::new(ledger));
+        let (tx, _rx) = mpsc::channel(1);
+        let service = LosGrpcService::new(
+            ledger.clone(),
+            "node".to_string(),
+            tx,
+            Arc::new(Mutex::new(HashMap::new())),
+            vec![],
+            3030,
+        );
+        let request = || {
+            Request::new(GetPendingReceivablesRequest {
+                address: "recipient".to_string(),
+            })
+        };
+
+        let pending = service
+            .get_pending_receivables(request())
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(pending.count, 2);
+        assert_eq!(pending.total_cil_str, (3 * CIL_PER_LOS).to_string());
+        assert_eq!(pending.pending[0].from, "sender");
+        assert_eq!(pending.pending[1].memo, "user-42");
+
+        ledger.lock().unwrap().claimed_sends.insert(claimed);
+        let pending = service
+            .get_pending_receivables(request())
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(pending.count, 1);
+        assert!(pending.pending[0].memo.is_empty());
+    }
 }
@@ -0,0 +1,137 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - STRUCTURED LOGGING
+//
+// tracing subscriber for the node and the crates it drives (consensus,
+// network, VM). Configuration:
+//   LOS_LOG (or RUST_LOG)      per-module filter, e.g. "info,los_network=debug"
+//   LOS_LOG_FORMAT / --log-format   text (default) | json
+//
+// Warnings and errors go to stderr, everything else to stdout — the same split
+// the old println!/eprintln! output had, which process monitors rely on.
+// The filter can be replaced at runtime via POST /admin/log-level.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use std::io::IsTerminal;
+use std::str::FromStr;
+use std::sync::OnceLock;
+use tracing::Level;
+use tracing_subscriber::fmt::writer::MakeWriterExt;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{reload, EnvFilter, Registry};
+
+/// Node crates at info, dependencies (libp2p, sled, wasmer...) at warn. warp
+/// would warn on every 4xx response, so it only reports server errors.
+pub const DEFAULT_FILTER: &str =
+    "warn,warp=error,los_node=info,los_core=info,los_consensus=info,los_network=info,los_vm=info";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogFormat {
+    Text,
+    Json,
+}
+
+impl FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "text" | "pretty" => Ok(LogFormat::Text),
+            "json" => Ok(LogFormat::Json),
+            other => Err(format!(
+                "Unknown log format '{}' (expected text or json)",
+                other
+            )),
+        }
+    }
+}
+
+static FILTER_HANDLE: OnceLock<reload::Handle<EnvFilter, Registry>> = OnceLock::new();
+
+/// Install the global subscriber. `format` overrides LOS_LOG_FORMAT.
+pub fn init(format: Option<LogFormat>) {
+    let format = format
+        .or_else(|| {
+            std::env::var("LOS_LOG_FORMAT")
+                .ok()
+                .and_then(|v| v.parse().ok())
+        })
+        .unwrap_or(LogFormat::Text);
+    let directives = std::env::var("LOS_LOG")
+        .or_else(|_| std::env::var("RUST_LOG"))
+        .unwrap_or_else(|_| DEFAULT_FILTER.to_string());
+    let (filter, invalid) = match EnvFilter::try_new(&directives) {
+        Ok(f) => (f, None),
+        Err(e) => (EnvFilter::new(DEFAULT_FILTER), Some(e)),
+    };
+    let (filter, handle) = reload::Layer::new(filter);
+    let writer = std::io::stderr
+        .with_max_level(Level::WARN)
+        .or_else(std::io::stdout);
+
+    let registry = tracing_subscriber::registry().with(filter);
+    let installed = match format {
+        LogFormat::Json => registry
+            .with(
+                tracing_subscriber::fmt::layer()
+                    .json()
+                    .with_current_span(true)
+                    .with_span_list(false)
+                    .with_writer(writer),
+            )
+            .try_init(),
+        LogFormat::Text => registry
+            .with(
+                tracing_subscriber::fmt::layer()
+                    .with_ansi(std::io::stdout().is_terminal())
+                    .with_writer(writer),
+            )
+            .try_init(),
+    };
+    if installed.is_ok() {
+        let _ = FILTER_HANDLE.set(handle);
+    }
+    if let Some(e) = invalid {
+        tracing::warn!(
+            "Invalid log filter '{}': {} — using default '{}'",
+            directives,
+            e,
+            DEFAULT_FILTER
+        );
+    }
+}
+
+/// The active filter directives, if the subscriber is installed
+pub fn current_filter() -> Option<String> {
+    FILTER_HANDLE.get()?.with_current(|f| f.to_string()).ok()
+}
+
+/// Replace the active filter; returns the new directives
+pub fn set_filter(directives: &str) -> Result<String, String> {
+    let filter = EnvFilter::try_new(directives.trim())
+        .map_err(|e| format!("Invalid log filter '{}': {}", directives, e))?;
+    let handle = FILTER_HANDLE.get().ok_or("Logging is not initialized")?;
+    let applied = filter.to_string();
+    handle.reload(filter).map_err(|e| e.to_string())?;
+    Ok(applied)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_format_parse() {
+        assert_eq!("json".parse::<LogFormat>(), Ok(LogFormat::Json));
+        assert_eq!("TEXT".parse::<LogFormat>(), Ok(LogFormat::Text));
+        assert!("xml".parse::<LogFormat>().is_err());
+    }
+
+    #[test]
+    fn test_filters_validated() {
+        assert!(EnvFilter::try_new(DEFAULT_FILTER).is_ok());
+        // Rejected at parse time, before the reload handle is touched
+        let err = set_filter("los_network=loud").unwrap_err();
+        assert!(err.starts_with("Invalid log filter"), "{}", err);
+    }
+}
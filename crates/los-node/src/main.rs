@@ -11,20 +11,27 @@
 use base64::Engine as _;
 use los_consensus::abft::ABFTConsensus; // aBFT engine for consensus stats & safety validation
 use los_consensus::checkpoint::{CheckpointManager, FinalityCheckpoint, CHECKPOINT_INTERVAL}; // Finality checkpoints
+use los_consensus::epoch::{EpochCommit, EpochSignature, EpochTransition}; // Agreed validator set epochs
 use los_consensus::slashing::SlashingManager; // Slashing enforcement
 use los_consensus::voting::calculate_voting_power; // Quadratic voting: Power = √Stake
+use los_consensus::wal::ConsensusWal; // aBFT crash-recovery write-ahead log
+use los_core::abi::ContractAbi; // Contract ABI metadata
 use los_core::anti_whale::{AntiWhaleConfig, AntiWhaleEngine}; // NEW: Anti-whale mechanisms
+use los_core::fork_choice::{fork_key, ForkChoice}; // Account-chain fork resolution
+use los_core::governance::{GovParam, ProposalStatus}; // On-chain parameter governance
 use los_core::oracle_consensus::OracleConsensus; // NEW: Oracle consensus
+use los_core::pruning::{HistoryMode, PruningConfig, DEFAULT_KEEP_RECENT_BLOCKS}; // Pruned/archive history
 use los_core::validator_rewards::ValidatorRewardPool;
-use los_core::{AccountState, Block, BlockType, Ledger, CIL_PER_LOS, MIN_VALIDATOR_STAKE_CIL};
+use los_core::{AccountState, Block, BlockType, Ledger, CIL_PER_LOS};
 use los_network::{LosNode, NetworkEvent};
 use los_vm::{dex_registry, token_registry, ContractCall, WasmEngine};
 use rate_limiter::{filters::rate_limit, RateLimiter};
 use std::collections::{BTreeMap, HashMap, HashSet};
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, Mutex, MutexGuard};
 use tokio::io::{self, AsyncBufReadExt, BufReader};
 use tokio::sync::mpsc;
+use tracing::{debug, error, info, warn, Instrument};
 use zeroize::Zeroizing;
 
 /// Safe mutex lock that recovers from poisoned state instead of panicking.
@@ -34,7 +41,7 @@ fn safe_lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
     match mutex.lock() {
         Ok(guard) => guard,
         Err(poisoned) => {
-            eprintln!("⚠️ WARNING: Mutex was poisoned, recovering...");
+            warn!("Mutex was poisoned, recovering...");
             poisoned.into_inner()
         }
     }
@@ -65,13 +72,16 @@ const TOTAL_SUPPLY_CIL: u128 = TOTAL_SUPPLY_LOS * CIL_PER_LOS;
 use serde_json::Value;
 
 mod db; // NEW: Database module (sled)
+mod devnet; // `los-node devnet`: private local cluster launcher
 mod genesis;
 mod grpc_server; // NEW: gRPC server module
+mod logging; // tracing subscriber: per-module filters, JSON output, runtime reload
 mod mempool; // NEW: Mempool for transaction management
 mod metrics; // NEW: Prometheus metrics module
 mod rate_limiter; // NEW: Rate limiter module
 mod testnet_config;
 mod tor_service; // Automatic Tor Hidden Service generation
+mod tx_status; // Transaction lifecycle tracking (received → final / rejected)
 mod validator_api; // Validator key management (generate, import)
 mod validator_rewards; // Testnet configuration module (graduated levels)
                        // --- TAMBAHAN: HTTP API MODULE ---
@@ -89,6 +99,9 @@ const BURN_ADDRESS_BTC: &str = "1BitcoinEaterAddressDontSendf59kuE";
 static SAVE_IN_PROGRESS: AtomicBool = AtomicBool::new(false);
 static SAVE_DIRTY: AtomicBool = AtomicBool::new(false);
 
+/// Sequence number for REST request spans (correlates a request's log lines)
+static REQUEST_SEQ: AtomicU64 = AtomicU64::new(1);
+
 /// Create a JSON API reply with automatic HTTP status code based on body content.
 ///
 /// Rules:
@@ -118,6 +131,67 @@ fn api_json(body: serde_json::Value) -> warp::reply::WithStatus<warp::reply::Jso
     warp::reply::with_status(warp::reply::json(&body), status)
 }
 
+/// Constant-time comparison of the X-Admin-Token header with LOS_ADMIN_TOKEN
+fn admin_token_matches(given: Option<&str>, expected: &str) -> bool {
+    given.is_some_and(|g| {
+        g.len() == expected.len()
+            && g.bytes()
+                .zip(expected.bytes())
+                .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+                == 0
+    })
+}
+
+/// History mode from the environment:
+///   LOS_HISTORY_MODE=archive|pruned (default: archive)
+///   LOS_PRUNE_KEEP_RECENT=<blocks kept behind each account head> (pruned only)
+fn pruning_config_from_env() -> PruningConfig {
+    let mode = match std::env::var("LOS_HISTORY_MODE") {
+        Ok(val) => val.parse::<HistoryMode>().unwrap_or_else(|e| {
+            warn!("{} — falling back to archive mode", e);
+            HistoryMode::Archive
+        }),
+        Err(_) => HistoryMode::Archive,
+    };
+    match mode {
+        HistoryMode::Archive => PruningConfig::archive(),
+        HistoryMode::Pruned => PruningConfig::pruned(
+            std::env::var("LOS_PRUNE_KEEP_RECENT")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(DEFAULT_KEEP_RECENT_BLOCKS),
+        ),
+    }
+}
+
+/// Archive peers (LOS_ARCHIVE_PEERS, comma-separated base URLs) that serve
+/// history a pruned node no longer has.
+fn archive_peers_from_env() -> Vec<String> {
+    std::env::var("LOS_ARCHIVE_PEERS")
+        .unwrap_or_default()
+        .split(',')
+        .map(|s| s.trim().trim_end_matches('/').to_string())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+/// Reply for a lookup that missed on a pruned node: 307 to the first archive
+/// peer if one is configured, otherwise `body` (which should say why).
+fn archive_redirect(
+    path: &str,
+    archive_peers: &[String],
+    body: serde_json::Value,
+) -> warp::reply::Response {
+    use warp::Reply;
+    match archive_peers
+        .first()
+        .and_then(|peer| format!("{}{}", peer, path).parse::<warp::http::Uri>().ok())
+    {
+        Some(uri) => warp::redirect::temporary(uri).into_response(),
+        None => api_json(body).into_response(),
+    }
+}
+
 /// Insert a validator endpoint, deduplicating by .onion address.
 /// One .onion can only map to ONE current LOS address — if a validator
 /// restarts with a new keypair, the old stale entry is removed.
@@ -126,8 +200,8 @@ fn insert_validator_endpoint(ve: &mut HashMap<String, String>, address: String,
     // (stale address from previous restart of the same node)
     ve.retain(|existing_addr, existing_onion| {
         if existing_onion == &onion && existing_addr != &address {
-            println!(
-                "🧹 Replacing stale endpoint: {} → {} (new: {})",
+            info!(
+                "Replacing stale endpoint: {} → {} (new: {})",
                 get_short_addr(existing_addr),
                 onion,
                 get_short_addr(&address)
@@ -140,6 +214,128 @@ fn insert_validator_endpoint(ve: &mut HashMap<String, String>, address: String,
     ve.insert(address, onion);
 }
 
+/// A pending send can still finalize: unconfirmed, on the current account head, and funded.
+/// Governance blocks wait for the same quorum and must also pass the governance rules.
+/// Returns why it cannot otherwise.
+fn pending_send_check(ledger: &Mutex<Ledger>, blk: &Block) -> Result<(), String> {
+    let governance = matches!(blk.block_type, BlockType::Proposal | BlockType::Vote);
+    if blk.block_type != BlockType::Send && !governance {
+        return Err("Not a Send or governance block".to_string());
+    }
+    let l = safe_lock(ledger);
+    if l.blocks.contains_key(&blk.calculate_hash()) {
+        return Err("Already in ledger".to_string());
+    }
+    let acct = l
+        .accounts
+        .get(&blk.account)
+        .ok_or("Sender account not found")?;
+    if acct.head != blk.previous {
+        return Err(format!(
+            "Account chain moved on: head {} is not previous {}",
+            acct.head, blk.previous
+        ));
+    }
+    if governance {
+        return l.check_governance(blk);
+    }
+    if acct.balance < blk.amount.saturating_add(blk.fee) {
+        return Err("Insufficient balance for amount + fee".to_string());
+    }
+    Ok(())
+}
+
+/// Live state a transaction status is resolved from (shared by the status routes)
+#[derive(Clone)]
+#[allow(clippy::type_complexity)]
+struct TxStatusSources {
+    ledger: Arc<Mutex<Ledger>>,
+    pending_sends: Arc<Mutex<HashMap<String, (Block, u128)>>>,
+    send_voters: Arc<Mutex<HashMap<String, HashSet<String>>>>,
+    mempool: Arc<Mutex<mempool::Mempool>>,
+    checkpoints: Arc<Mutex<CheckpointManager>>,
+    tracker: Arc<Mutex<tx_status::TxStatusTracker>>,
+}
+
+/// Status of a transaction hash (lifecycle in tx_status.rs); `None` if the node never saw it
+fn tx_status_json(src: &TxStatusSources, hash: &str) -> Option<serde_json::Value> {
+    // One lock at a time: L, CP, PS, SV, MP, TT
+    let (applied_height, ledger_account) = {
+        let l = safe_lock(&src.ledger);
+        l.blocks
+            .get(hash)
+            .map(|b| {
+                (
+                    l.applied_height(hash).unwrap_or(u64::MAX),
+                    b.account.clone(),
+                )
+            })
+            .unzip()
+    };
+    let checkpoint = safe_lock(&src.checkpoints)
+        .get_latest_checkpoint()
+        .ok()
+        .flatten();
+    let pending = safe_lock(&src.pending_sends)
+        .get(hash)
+        .map(|(b, power)| (b.account.clone(), *power));
+    let voters = safe_lock(&src.send_voters).get(hash).map_or(0, |v| v.len());
+    let mempool_account = safe_lock(&src.mempool)
+        .get_transaction(hash)
+        .map(|tx| tx.block.account.clone());
+
+    let obs = tx_status::TxObservation {
+        applied_height,
+        checkpoint_height: checkpoint.as_ref().map(|cp| cp.height),
+        votes: pending.as_ref().map(|(_, power)| (*power, voters)),
+        in_mempool: mempool_account.is_some(),
+    };
+    let status = safe_lock(&src.tracker).status(hash, &obs)?;
+    let account = ledger_account
+        .or(pending.map(|(a, _)| a))
+        .or(mempool_account)
+        .or(status.account);
+    let consensus = testnet_config::get_testnet_config().should_enable_consensus();
+    Some(serde_json::json!({
+        "hash": hash,
+        "state": status.state.as_str(),
+        "account": account,
+        "reason": status.reason,
+        "vote_power": status.vote_power,
+        "voters": status.voters,
+        "vote_threshold": if consensus { SEND_CONSENSUS_THRESHOLD } else { TESTNET_FUNCTIONAL_THRESHOLD },
+        "min_voters": if consensus { MIN_DISTINCT_VOTERS } else { 1 },
+        "checkpoint_height": checkpoint.map(|cp| cp.height),
+    }))
+}
+
+/// Fee market summary: base fee under the current mempool pressure and the
+/// rules for replacing a pending send (shared by both /fee-estimate routes)
+fn fee_market_json(mp: &mempool::Mempool, base_fee: u128) -> serde_json::Value {
+    let dynamic = mp.dynamic_base_fee(base_fee);
+    serde_json::json!({
+        "base_fee_cil": base_fee as u64,
+        "dynamic_base_fee_cil": dynamic as u64,
+        "suggested_fee_cil": dynamic as u64,
+        "fast_fee_cil": mp.fast_fee(base_fee) as u64,
+        "mempool_size": mp.len(),
+        "mempool_pressure_bps": mp.pressure_bps(),
+        "rbf_min_bump_bps": mempool::RBF_MIN_BUMP_BPS
+    })
+}
+
+/// Genesis file of this build: genesis_config.json (mainnet) or the testnet
+/// wallets file. Testnet builds honour LOS_GENESIS_FILE (used by `los-node devnet`).
+fn genesis_file_path() -> String {
+    if los_core::is_mainnet_build() {
+        return "genesis_config.json".to_string();
+    }
+    std::env::var("LOS_GENESIS_FILE")
+        .ok()
+        .filter(|p| !p.trim().is_empty())
+        .unwrap_or_else(|| "testnet-genesis/testnet_wallets.json".to_string())
+}
+
 /// Bootstrap nodes — resolved from env var OR auto-discovered from genesis config.
 ///
 /// Priority:
@@ -178,12 +374,7 @@ fn get_bootstrap_nodes() -> Vec<String> {
     }
 
     // Priority 2: Auto-discover from genesis config bootstrap_nodes[].onion_address
-    let genesis_path = if los_core::is_mainnet_build() {
-        "genesis_config.json"
-    } else {
-        "testnet-genesis/testnet_wallets.json"
-    };
-    if let Ok(json_data) = std::fs::read_to_string(genesis_path) {
+    if let Ok(json_data) = std::fs::read_to_string(genesis_file_path()) {
         if let Ok(config) = serde_json::from_str::<genesis::GenesisConfig>(&json_data) {
             if let Some(ref nodes) = config.bootstrap_nodes {
                 // Filter out our own onion address to avoid self-dialing
@@ -207,8 +398,8 @@ fn get_bootstrap_nodes() -> Vec<String> {
                     })
                     .collect();
                 if !peers.is_empty() {
-                    println!(
-                        "🧅 Auto-discovered {} bootstrap peers from genesis config",
+                    info!(
+                        "Auto-discovered {} bootstrap peers from genesis config",
                         peers.len()
                     );
                     return peers;
@@ -232,7 +423,10 @@ struct SendRequest {
     previous: Option<String>,   // Previous block hash (for client-side signing)
     work: Option<u64>,          // PoW nonce (if client pre-computed)
     timestamp: Option<u64>,     // Client timestamp (used when client_signed to match signing_hash)
-    fee: Option<u128>,          // Client fee (used when client_signed to match signing_hash)
+    fee: Option<u128>, // Client fee (client_signed: must match signing_hash; node-signed: max fee to pay)
+    memo: Option<String>, // Payment ID / deposit tag (client_signed: part of signing_hash)
+    #[serde(default)]
+    replace: bool, // Without `previous`: replace the latest pending send (RBF) instead of queueing behind it
 }
 
 #[derive(serde::Deserialize, serde::Serialize)]
@@ -253,6 +447,7 @@ struct DeployContractRequest {
     bytecode: String, // base64 encoded WASM
     initial_state: Option<BTreeMap<String, String>>,
     amount_cil: Option<u128>,   // Initial CIL funding for contract
+    abi: Option<ContractAbi>,   // Overrides an ABI embedded in the bytecode
     signature: Option<String>,  // Client-signed: Dilithium5 sig
     public_key: Option<String>, // Client-signed: deployer's pubkey (hex)
     previous: Option<String>,   // Client-signed: previous block hash
@@ -275,6 +470,100 @@ struct CallContractRequest {
     work: Option<u64>,         // Client-signed: PoW nonce
     timestamp: Option<u64>,    // Client-signed: block timestamp
     fee: Option<u128>,         // Client-signed: fee in CIL
+    /// USP-01 token → amount the contract may pull from the caller in this
+    /// call (signed in the link, no separate approve block)
+    #[serde(default)]
+    token_allowances: BTreeMap<String, u128>,
+}
+
+/// Governance proposal (Proposal block). Without a signature the node signs
+/// for its own account.
+#[derive(serde::Deserialize, serde::Serialize)]
+struct ProposeRequest {
+    param: String,
+    value: u128,
+    activation_time: u64,       // Unix seconds
+    deposit_cil: Option<u128>,  // Default: MIN_PROPOSAL_DEPOSIT_CIL
+    signature: Option<String>,  // Client-signed: Dilithium5 sig
+    public_key: Option<String>, // Client-signed: proposer's pubkey (hex)
+    previous: Option<String>,   // Client-signed: previous block hash
+    work: Option<u64>,          // Client-signed: PoW nonce
+    timestamp: Option<u64>,     // Client-signed: block timestamp
+    fee: Option<u128>,          // Client-signed: fee in CIL
+}
+
+/// Validator vote on a proposal (Vote block)
+#[derive(serde::Deserialize, serde::Serialize)]
+struct VoteRequest {
+    proposal_id: String,
+    approve: bool,
+    signature: Option<String>,  // Client-signed: Dilithium5 sig
+    public_key: Option<String>, // Client-signed: voter's pubkey (hex)
+    previous: Option<String>,   // Client-signed: previous block hash
+    work: Option<u64>,          // Client-signed: PoW nonce
+    timestamp: Option<u64>,     // Client-signed: block timestamp
+    fee: Option<u128>,          // Client-signed: fee in CIL
+}
+
+/// Settlement of the caller's own proposal once its voting window closed.
+/// Without a tally the node uses its own over the running validator epoch.
+#[derive(serde::Deserialize, serde::Serialize)]
+struct SettleRequest {
+    proposal_id: String,
+    yes_power: Option<u128>,
+    no_power: Option<u128>,
+    total_power: Option<u128>,
+    signature: Option<String>,  // Client-signed: Dilithium5 sig
+    public_key: Option<String>, // Client-signed: proposer's pubkey (hex)
+    previous: Option<String>,   // Client-signed: previous block hash
+    work: Option<u64>,          // Client-signed: PoW nonce
+    timestamp: Option<u64>,     // Client-signed: block timestamp
+    fee: Option<u128>,          // Client-signed: fee in CIL
+}
+
+/// Unsigned governance block plus the client's signing fields (if any)
+struct GovernanceTx {
+    block_type: BlockType,
+    amount: u128,
+    link: String,
+    signature: Option<String>,
+    public_key: Option<String>,
+    previous: Option<String>,
+    work: Option<u64>,
+    timestamp: Option<u64>,
+    fee: Option<u128>,
+}
+
+#[derive(serde::Deserialize, serde::Serialize)]
+struct QueryContractRequest {
+    contract_address: String,
+    function: String,
+    #[serde(default)]
+    args: Vec<String>,
+    gas_limit: Option<u64>, // Capped at MAX_QUERY_GAS
+    caller: Option<String>, // Caller seen by host_get_caller (default: empty)
+}
+
+/// Dry-run of a ContractDeploy ("deploy") or ContractCall ("call") block
+#[derive(serde::Deserialize, serde::Serialize)]
+struct SimulateRequest {
+    #[serde(rename = "type")]
+    kind: String,
+    // ContractCall fields
+    contract_address: Option<String>,
+    function: Option<String>,
+    #[serde(default)]
+    args: Vec<String>,
+    caller: Option<String>,
+    gas_limit: Option<u64>, // Default: MAX_GAS_PER_CALL (find the real usage)
+    // ContractDeploy fields
+    owner: Option<String>,
+    bytecode: Option<String>, // base64 encoded WASM
+    abi: Option<ContractAbi>,
+    #[serde(default)]
+    token_allowances: BTreeMap<String, u128>,
+    // Both
+    amount_cil: Option<u128>,
 }
 
 /// Per-address endpoint rate limiter
@@ -436,6 +725,18 @@ pub struct ApiServerConfig {
     /// WASM Smart Contract Engine — shared between API server and P2P event loop.
     /// Contracts deployed via REST are persisted to sled and replicated via gossip.
     pub wasm_engine: Arc<WasmEngine>,
+    /// Account-chain fork tracker — shared with the P2P event loop (CONFIRM_REQ/CONFIRM_RES)
+    pub fork_choice: Arc<Mutex<ForkChoice>>,
+    /// Archive or pruned history (advertised in /node-info)
+    pub pruning: PruningConfig,
+    /// Where pruned nodes send history queries they cannot answer
+    pub archive_peers: Vec<String>,
+    /// Send vote deduplication — distinct CONFIRM_RES voters per pending send
+    pub send_voters: Arc<Mutex<HashMap<String, HashSet<String>>>>,
+    /// Finality checkpoints — applied blocks at or before the latest one are final
+    pub checkpoint_manager: Arc<Mutex<CheckpointManager>>,
+    /// Received and rejected transaction hashes for the status API
+    pub tx_tracker: Arc<Mutex<tx_status::TxStatusTracker>>,
 }
 
 #[allow(clippy::type_complexity)]
@@ -463,6 +764,12 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
         abft_consensus,
         local_registered_validators,
         wasm_engine,
+        fork_choice,
+        pruning,
+        archive_peers,
+        send_voters,
+        checkpoint_manager,
+        tx_tracker,
     } = cfg;
     // Rate Limiter: 100 req/sec per IP, burst 200
     let limiter = RateLimiter::new(100, Some(200));
@@ -475,6 +782,7 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
     let send_limiter = Arc::new(EndpointRateLimiter::new(10, 60)); // /send: 10 tx per 60 seconds
     let burn_limiter = Arc::new(EndpointRateLimiter::new(1, 60)); // /burn: 1 per 60 seconds (testnet)
     let faucet_limiter = Arc::new(EndpointRateLimiter::new(1, 120)); // /faucet: 1 per 2 minutes (testnet)
+    let simulate_limiter = Arc::new(EndpointRateLimiter::new(20, 60)); // /simulate: 20 per minute per IP
 
     // aBFT Consensus Engine — passed from main() via ApiServerConfig, shared with event loop
     // Initialize shared secret and validator set
@@ -487,20 +795,11 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
         hasher.update(b"LOS_CONSENSUS_MAC_V1");
         abft.set_shared_secret(hasher.finalize().to_vec());
 
-        // C-04 FIX: Populate validator set with real addresses for leader selection
-        let l = safe_lock(&ledger);
-        let mut validators: Vec<String> = l
-            .accounts
-            .iter()
-            .filter(|(_, a)| a.balance >= MIN_VALIDATOR_STAKE_CIL && a.is_validator)
-            .map(|(addr, _)| addr.clone())
-            .collect();
-        validators.sort(); // Deterministic ordering across all nodes
-        abft.update_validator_set(validators);
-        drop(l);
+        // C-04 FIX: Validator set (real addresses + stake) is loaded in main()
+        // together with the epoch seed from the latest checkpoint.
 
-        println!(
-            "🔗 aBFT Consensus: n={}, f={}, quorum={}, safety={}",
+        info!(
+            "aBFT Consensus: n={}, f={}, quorum={}, safety={}",
             abft.total_validators,
             abft.f_max_faulty,
             abft.get_statistics().quorum_threshold,
@@ -554,12 +853,14 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
             }))
         });
 
-    // 3. GET /history/:address
+    // 3. GET /history/:address (?memo= lists the Sends from/to the address carrying that memo)
     let l_his = ledger.clone();
     let ab_his = address_book.clone();
+    let archive_his = archive_peers.clone();
     let history_route = warp::path!("history" / String)
+        .and(warp::query::<HashMap<String, String>>())
         .and(with_state((l_his, ab_his)))
-        .map(#[allow(clippy::type_complexity)] |addr: String, (l, ab): (Arc<Mutex<Ledger>>, Arc<Mutex<HashMap<String, String>>>)| {
+        .map(#[allow(clippy::type_complexity)] move |addr: String, query: HashMap<String, String>, (l, ab): (Arc<Mutex<Ledger>>, Arc<Mutex<HashMap<String, String>>>)| {
             let l_guard = safe_lock(&l);
             let target_full = if l_guard.accounts.contains_key(&addr) {
                 Some(addr)
@@ -572,7 +873,29 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                 }
             };
 
+            // Memo lookup uses the ledger's memo index, not a chain walk
+            if let Some(memo) = query.get("memo") {
+                let transactions: Vec<serde_json::Value> = target_full
+                    .map(|full| l_guard.sends_with_memo(&full, memo))
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|(hash, blk)| serde_json::json!({
+                        "hash": hash,
+                        "from": blk.account,
+                        "to": blk.link,
+                        "amount": format!("{}.{:011}", blk.amount / CIL_PER_LOS, blk.amount % CIL_PER_LOS),
+                        "timestamp": blk.timestamp,
+                        "type": "send",
+                        "fee": blk.fee,
+                        "memo": memo,
+                        "claimed": l_guard.claimed_sends.contains(hash)
+                    }))
+                    .collect();
+                return api_json(serde_json::json!({"memo": memo, "transactions": transactions}));
+            }
+
             let mut history = Vec::new();
+            let mut truncated = false;
             if let Some(full) = target_full {
                 if let Some(acct) = l_guard.accounts.get(&full) {
                     let mut curr = acct.head.clone();
@@ -592,7 +915,13 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                                 BlockType::Receive => blk.account.clone(),
                                 _ => blk.link.clone(),
                             };
-                            history.push(serde_json::json!({
+                            // A Receive shows the memo of the Send it claims
+                            let memo = match blk.block_type {
+                                BlockType::Send => blk.memo.clone(),
+                                BlockType::Receive => l_guard.blocks.get(&blk.link).and_then(|s| s.memo.clone()),
+                                _ => None,
+                            };
+                            let mut entry = serde_json::json!({
                                 "hash": curr,
                                 "from": from_addr,
                                 "to": to_addr,
@@ -600,12 +929,28 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                                 "timestamp": blk.timestamp,
                                 "type": format!("{:?}", blk.block_type).to_lowercase(),
                                 "fee": blk.fee
-                            }));
+                            });
+                            if let Some(m) = memo {
+                                entry["memo"] = serde_json::json!(m);
+                            }
+                            history.push(entry);
                             curr = blk.previous.clone();
-                        } else { break; }
+                        } else {
+                            // PRUNING: older blocks were dropped below the history horizon
+                            truncated = true;
+                            break;
+                        }
                     }
                 }
             }
+            if truncated {
+                return api_json(serde_json::json!({
+                    "transactions": history,
+                    "history_truncated": true,
+                    "pruned_before": l_guard.pruned_before,
+                    "archive_peers": archive_his
+                }));
+            }
             api_json(serde_json::json!({"transactions": history}))
         });
 
@@ -705,11 +1050,12 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
     let aw_send = anti_whale.clone();
     let pk_send = node_public_key.clone();
     let mp_send = mempool_pool.clone();
+    let tt_send = tx_tracker.clone();
     let send_route = warp::path("send")
         .and(warp::post())
         .and(warp::body::bytes())
-        .and(with_state((l_send, tx_send, p_send, my_address.clone(), secret_key.clone(), sl_send, aw_send, pk_send, mp_send)))
-        .then(#[allow(clippy::type_complexity)] |body: bytes::Bytes, (l, tx, p, my_addr, key, rate_lim, aw, node_pk, mp): (Arc<Mutex<Ledger>>, mpsc::Sender<String>, Arc<Mutex<HashMap<String, (Block, u128)>>>, String, Zeroizing<Vec<u8>>, Arc<EndpointRateLimiter>, Arc<Mutex<AntiWhaleEngine>>, Vec<u8>, Arc<Mutex<mempool::Mempool>>)| async move {
+        .and(with_state((l_send, tx_send, p_send, my_address.clone(), secret_key.clone(), sl_send, aw_send, pk_send, mp_send, tt_send)))
+        .then(#[allow(clippy::type_complexity)] |body: bytes::Bytes, (l, tx, p, my_addr, key, rate_lim, aw, node_pk, mp, tt): (Arc<Mutex<Ledger>>, mpsc::Sender<String>, Arc<Mutex<HashMap<String, (Block, u128)>>>, String, Zeroizing<Vec<u8>>, Arc<EndpointRateLimiter>, Arc<Mutex<AntiWhaleEngine>>, Vec<u8>, Arc<Mutex<mempool::Mempool>>, Arc<Mutex<tx_status::TxStatusTracker>>)| async move {
             // FIX BUG-1/2/6: Parse JSON manually to return proper 400 instead of 500
             let req: SendRequest = match serde_json::from_slice(&body) {
                 Ok(r) => r,
@@ -831,35 +1177,50 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                     } else {
                         std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_secs()
                     },
-                    // When client-signed, use client's fee (part of signing_hash)
-                    // Server still validates the fee is >= base_fee
-                    fee: if client_signed { req.fee.unwrap_or(0) } else { 0 },
+                    // Client's fee (part of signing_hash when client-signed); server
+                    // validates it is >= the required fee. Node-signed sends may bid higher.
+                    fee: req.fee.unwrap_or(0),
+                    memo: req.memo.clone(),
                 };
+                if let Err(e) = safe_lock(&l).check_memo(&blk) {
+                    return api_json(serde_json::json!({"status": "error", "msg": e}));
+                } // L dropped
 
                 let initial_power: u128;
-                let base_fee = los_core::BASE_FEE_CIL; // Protocol constant from los-core
                 let final_fee: u128;
 
                 // DEADLOCK FIX #4a: Never hold L and AW simultaneously.
-                // Step 1: Read state from Ledger, drop lock
-                let sender_state = {
+                // Step 1: Read state (and governance-set fee parameters) from Ledger, drop lock
+                let (sender_state, base_fee, gov_max_tx) = {
                     let l_guard = safe_lock(&l);
-                    l_guard.accounts.get(&sender_addr).cloned()
+                    (
+                        l_guard.accounts.get(&sender_addr).cloned(),
+                        l_guard.base_fee_cil(),
+                        l_guard.governance.param_at(GovParam::AntiWhaleMaxTxPerBlock, blk.timestamp),
+                    )
                 }; // L dropped
+                // Fee market: the base fee rises with mempool pressure
+                let dynamic_base_fee = safe_lock(&mp).dynamic_base_fee(base_fee);
 
                 if let Some(st) = sender_state {
+                    // Without an explicit `previous`, a new send queues behind the account's
+                    // pending sends; only `replace: true` takes the latest one's slot (RBF).
                     if req.previous.is_none() {
-                        blk.previous = st.head.clone();
+                        let (tip, tip_previous) = safe_lock(&mp).pending_tip(&sender_addr, &st.head);
+                        blk.previous = if req.replace { tip_previous } else { tip };
                     }
 
                     // Step 2: Anti-Whale fee calculation (separate lock scope)
                     {
                         let mut aw_guard = safe_lock(&aw);
-                        match aw_guard.register_transaction(sender_addr.clone(), base_fee as u64) {
+                        if let Some(max_tx) = gov_max_tx {
+                            aw_guard.config.max_tx_per_block = max_tx as u32;
+                        }
+                        match aw_guard.register_transaction(sender_addr.clone(), dynamic_base_fee as u64) {
                             Ok(fee) => {
                                 final_fee = fee as u128;
                                 if final_fee > base_fee {
-                                    println!("⚠️ Dynamic fee applied to {}: {} CIL ({}x multiplier)",
+                                    warn!("Dynamic fee applied to {}: {} CIL ({}x multiplier)",
                                         get_short_addr(&sender_addr), final_fee, final_fee as f64 / base_fee as f64);
                                 }
                             }
@@ -873,17 +1234,19 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                     } // AW dropped
 
                     // Step 3: Check balance INCLUDING pending transactions (TOCTOU prevention)
+                    // A pending send on the same `previous` is being replaced (RBF), not added to.
                     let pending_total: u128 = {
                         let ps = safe_lock(&p);
                         ps.values()
-                            .filter(|(b, _)| b.account == sender_addr)
+                            .filter(|(b, _)| b.account == sender_addr && b.previous != blk.previous)
                             .map(|(b, _)| b.amount)
                             .sum()
                     };
-                    if st.balance < amt + final_fee + pending_total {
+                    let fee_to_pay = final_fee.max(blk.fee);
+                    if st.balance < amt + fee_to_pay + pending_total {
                         return api_json(serde_json::json!({
                             "status":"error",
-                            "msg": format!("Insufficient balance (need {} CIL for tx + {} CIL fee + {} CIL pending)", amt, final_fee, pending_total)
+                            "msg": format!("Insufficient balance (need {} CIL for tx + {} CIL fee + {} CIL pending)", amt, fee_to_pay, pending_total)
                         }));
                     }
                     initial_power = st.balance / CIL_PER_LOS;
@@ -903,7 +1266,7 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                     }
                     // Keep client's fee (already set on blk) — it's part of their signing_hash
                 } else {
-                    blk.fee = final_fee;
+                    blk.fee = final_fee.max(blk.fee);
                 }
 
                 // Compute PoW if not provided by client
@@ -933,7 +1296,7 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                             "msg": format!("Invalid signature: verification failed (sig={} bytes, pk={} bytes). signing_hash={}", sig_len, pk_len, sh)
                         }));
                     }
-                    println!("✅ Client signature verified successfully");
+                    info!("Client signature verified successfully");
                 } else {
                     // MAINNET SAFETY: On Production level, ALL transactions MUST be client-signed.
                     // Node auto-signing (even for its own address) is a testnet convenience only.
@@ -954,9 +1317,9 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                         }));
                     }
                     if sender_addr != my_addr {
-                        println!("🧪 TESTNET functional: node signing on behalf of external address {}", sender_addr);
+                        info!("TESTNET functional: node signing on behalf of external address {}", sender_addr);
                     } else {
-                        println!("🔑 Node auto-signing for own address (testnet convenience)");
+                        info!("Node auto-signing for own address (testnet convenience)");
                     }
                     blk.signature = match try_sign_hex(blk.signing_hash().as_bytes(), &key) {
                         Ok(sig) => sig,
@@ -966,6 +1329,7 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
 
                 // Block ID sekarang mencakup signature
                 let hash = blk.calculate_hash();
+                safe_lock(&tt).received(&hash, &sender_addr);
 
                 // FIX v1.0.7+: Finalize immediately when:
                 //   (a) Functional testnet (no consensus needed), OR
@@ -985,7 +1349,7 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                 // correctly-signed send from the account owner.
                 let skip_consensus = !testnet_config::get_testnet_config().should_enable_consensus() || client_signed;
                 if skip_consensus {
-                    {
+                    let applied: Result<(), String> = {
                         let mut l_guard = safe_lock(&l);
                         // Debit sender: amount + fee
                         // Use blk.fee (not final_fee) because for client-signed blocks,
@@ -998,36 +1362,35 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                             // Without this check, a malicious client could submit conflicting
                             // sends to the same node and both would succeed (balance check
                             // alone is insufficient if the first tx hasn't been processed yet).
-                            if sender_state.head != blk.previous {
-                                return api_json(serde_json::json!({
-                                    "status": "error",
-                                    "msg": format!("Chain sequence error: expected previous={}, got={}",
-                                        sender_state.head, blk.previous)
-                                }));
-                            }
                             let total_debit = amt.saturating_add(actual_fee);
-                            if sender_state.balance < total_debit {
-                                return api_json(serde_json::json!({
-                                    "status": "error",
-                                    "msg": "Insufficient balance for amount + fee"
-                                }));
+                            if sender_state.head != blk.previous {
+                                Err(format!("Chain sequence error: expected previous={}, got={}",
+                                    sender_state.head, blk.previous))
+                            } else if sender_state.balance < total_debit {
+                                Err("Insufficient balance for amount + fee".to_string())
+                            } else {
+                                sender_state.balance -= total_debit;
+                                sender_state.head = hash.clone();
+                                sender_state.block_count += 1;
+                                // Insert block
+                                l_guard.blocks.insert(hash.clone(), blk.clone());
+                                l_guard.index_block(&hash, &blk);
+                                // Accumulate fees
+                                l_guard.accumulated_fees_cil = l_guard.accumulated_fees_cil.saturating_add(actual_fee);
+                                Ok(())
                             }
-                            sender_state.balance -= total_debit;
-                            sender_state.head = hash.clone();
-                            sender_state.block_count += 1;
                         } else {
-                            return api_json(serde_json::json!({
-                                "status":"error","msg":"Sender account not found"
-                            }));
+                            Err("Sender account not found".to_string())
                         }
-                        // Insert block
-                        l_guard.blocks.insert(hash.clone(), blk.clone());
-                        // Accumulate fees
-                        l_guard.accumulated_fees_cil = l_guard.accumulated_fees_cil.saturating_add(actual_fee);
+                    }; // L dropped
+                    if let Err(msg) = applied {
+                        safe_lock(&tt).rejected(&hash, &sender_addr, msg.as_str());
+                        return api_json(serde_json::json!({"status": "error", "msg": msg}));
                     }
+                    safe_lock(&tt).admitted(&hash);
                     SAVE_DIRTY.store(true, Ordering::Relaxed);
                     let reason = if client_signed { "client-signed" } else { "functional testnet" };
-                    println!("✅ Send finalized immediately ({}): {} → {} ({} LOS, fee {} CIL)",
+                    info!("Send finalized immediately ({}): {} → {} ({} LOS, fee {} CIL)",
                         reason, get_short_addr(&sender_addr), get_short_addr(&target), amt / CIL_PER_LOS, blk.fee);
 
                     // GOSSIP FIX v1.0.10: Broadcast finalized Send block to peers using
@@ -1040,14 +1403,15 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                     // minimum stake after this send, automatically unregister them.
                     {
                         let mut l_guard = safe_lock(&l);
+                        let min_stake = l_guard.min_validator_stake_cil();
                         if let Some(sender_acct) = l_guard.accounts.get_mut(&sender_addr) {
-                            if sender_acct.is_validator && sender_acct.balance < MIN_VALIDATOR_STAKE_CIL {
+                            if sender_acct.is_validator && sender_acct.balance < min_stake {
                                 sender_acct.is_validator = false;
                                 SAVE_DIRTY.store(true, Ordering::Relaxed);
-                                println!("⚠️ Auto-unregistered validator {}: balance {} < minimum stake {} LOS",
+                                warn!("Auto-unregistered validator {}: balance {} < minimum stake {} LOS",
                                     get_short_addr(&sender_addr),
                                     sender_acct.balance / CIL_PER_LOS,
-                                    MIN_VALIDATOR_STAKE_CIL / CIL_PER_LOS);
+                                    min_stake / CIL_PER_LOS);
                             }
                         }
                     }
@@ -1075,11 +1439,12 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                                 timestamp: std::time::SystemTime::now()
                                     .duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_secs(),
                                 fee: 0,
+                                memo: None,
                             };
                             solve_pow(&mut recv_blk);
                             recv_blk.signature = match try_sign_hex(recv_blk.signing_hash().as_bytes(), &key) {
                                 Ok(sig) => sig,
-                                Err(e) => { eprintln!("❌ Auto-Receive signing failed: {}", e); return api_json(serde_json::json!({"status": "error", "msg": e})); }
+                                Err(e) => { error!("Auto-Receive signing failed: {}", e); return api_json(serde_json::json!({"status": "error", "msg": e})); }
                             };
                             // Direct ledger manipulation for Receive block — bypass process_block()
                             // because the node's public_key doesn't match the target's account address.
@@ -1090,13 +1455,14 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                                 recv_acct.block_count += 1;
                             }
                             l_guard.blocks.insert(recv_hash.clone(), recv_blk.clone());
+                            l_guard.index_block(&recv_hash, &recv_blk);
                             // SECURITY FIX M-4: Track claimed Send for double-receive prevention.
                             // Direct ledger manipulation bypasses process_block() which normally
                             // inserts into claimed_sends. Without this, a second Receive referencing
                             // the same Send could pass the claimed_sends check in process_block().
                             l_guard.claimed_sends.insert(hash.clone());
                             SAVE_DIRTY.store(true, Ordering::Relaxed);
-                            println!("✅ Auto-Receive created for {} ({} CIL)", get_short_addr(&target), amt);
+                            info!("Auto-Receive created for {} ({} CIL)", get_short_addr(&target), amt);
                             let recv_json = serde_json::to_string(&recv_blk).unwrap_or_default();
                             let recv_b64_for_gossip = base64::engine::general_purpose::STANDARD.encode(recv_json.as_bytes());
                             Some(recv_b64_for_gossip)
@@ -1115,37 +1481,71 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                         "tx_hash":hash,
                         "initial_power": initial_power,
                         "fee_paid_cil": blk.fee,
-                        "fee_multiplier_bps": if base_fee > 0 { blk.fee * 10_000 / base_fee } else { 10_000 }
+                        "fee_multiplier_bps": (blk.fee * 10_000).checked_div(base_fee).unwrap_or(10_000)
                     }));
                 }
 
+                // FEE MARKET: Admit to the mempool first. A queued send on the same
+                // `previous` is replaced only by a sufficiently higher fee (RBF);
+                // one already sent for confirmation is not replaceable at all.
+                // A full mempool only takes fees above its cheapest entry.
+                let head = safe_lock(&l).accounts.get(&sender_addr).map(|a| a.head.clone()).unwrap_or_else(|| "0".to_string()); // L dropped
+                let admission = {
+                    let mut mp_guard = safe_lock(&mp);
+                    mp_guard.replaceable_conflict(&blk, &head).and_then(|conflict| {
+                        mp_guard.add_transaction(blk.clone(), blk.fee as u64, initial_power as u64).map(|_| conflict)
+                    })
+                }; // MP dropped
+                let replaced = match admission {
+                    Ok(replaced) => {
+                        let mut tt_guard = safe_lock(&tt);
+                        tt_guard.admitted(&hash);
+                        if let Some(ref old) = replaced {
+                            tt_guard.rejected(old, &sender_addr, format!("Replaced by fee bump: {}", hash));
+                        }
+                        replaced
+                    }
+                    Err(e) => {
+                        safe_lock(&tt).rejected(&hash, &sender_addr, e.as_str());
+                        return api_json(serde_json::json!({"status": "error", "msg": e}));
+                    }
+                }; // TT dropped
+
                 // CONSENSUS FIX: Start total_power_votes at 0 instead of initial_power.
                 // The sender doesn't self-vote — only distinct external validators contribute
                 // voting power via CONFIRM_RES. initial_power is kept for API response & mempool.
-                safe_lock(&p).insert(hash.clone(), (blk.clone(), 0u128));
-
-                // CONSENSUS FIX: Serialize block BEFORE mempool takes ownership.
-                // Include block data (base64) so peers can validate and vote.
-                // Without this, peers receive CONFIRM_REQ but can't verify the block
-                // because it only exists locally in pending_sends — causing zero votes.
-                let block_json = serde_json::to_string(&blk).unwrap_or_default();
-                let block_b64 = base64::engine::general_purpose::STANDARD.encode(block_json.as_bytes());
-
-                // Track in mempool for stats and future block assembly
                 {
-                    let fee_u64 = blk.fee as u64;
-                    let priority = initial_power as u64;
-                    let _ = safe_lock(&mp).add_transaction(blk, fee_u64, priority);
+                    let mut ps = safe_lock(&p);
+                    // Drop the replaced send: its CONFIRM_RES votes are ignored from now on
+                    ps.retain(|_, (b, _)| !(b.account == blk.account && b.previous == blk.previous));
+                    ps.insert(hash.clone(), (blk.clone(), 0u128));
+                }
+                if let Some(ref old) = replaced {
+                    info!("RBF: {} replaced pending {} (fee {} CIL)",
+                        get_short_addr(&sender_addr), &old[..12.min(old.len())], blk.fee);
                 }
 
-                let ts = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_millis();
-                let _ = tx.send(format!("CONFIRM_REQ:{}:{}:{}:{}:{}", hash, sender_addr, amt, ts, block_b64)).await;
+                // A send queued behind a pending one asks for quorum once that one is
+                // applied (see CONFIRM_RES); validators only vote on the account head.
+                let queued = safe_lock(&l).accounts.get(&sender_addr).is_some_and(|a| a.head != blk.previous);
+                if !queued {
+                    // CONSENSUS FIX: Include block data (base64) so peers can validate and vote.
+                    // Without this, peers receive CONFIRM_REQ but can't verify the block
+                    // because it only exists locally in pending_sends — causing zero votes.
+                    let block_json = serde_json::to_string(&blk).unwrap_or_default();
+                    let block_b64 = base64::engine::general_purpose::STANDARD.encode(block_json.as_bytes());
+
+                    let ts = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_millis();
+                    let _ = tx.send(format!("CONFIRM_REQ:{}:{}:{}:{}:{}", hash, sender_addr, amt, ts, block_b64)).await;
+                }
                 api_json(serde_json::json!({
                     "status":"success",
                     "tx_hash":hash,
                     "initial_power": initial_power,
-                    "fee_paid_cil": final_fee,
-                    "fee_multiplier_bps": if base_fee > 0 { final_fee * 10_000 / base_fee } else { 10_000 }
+                    "fee_paid_cil": blk.fee,
+                    "fee_multiplier_bps": (blk.fee * 10_000).checked_div(base_fee).unwrap_or(10_000),
+                    "replaced_tx_hash": replaced,
+                    "queued_behind": if queued { Some(&blk.previous) } else { None }
                 }))
             } else {
                 api_json(serde_json::json!({"status":"error","msg":"Address not found"}))
@@ -1242,7 +1642,7 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                                 "msg": "Invalid burn signature: Dilithium5 verification failed"
                             }));
                         }
-                        println!("✅ Burn signature verified for recipient {}", get_short_addr(recipient));
+                        info!("Burn signature verified for recipient {}", get_short_addr(recipient));
                     }
                     _ => {
                         return api_json(serde_json::json!({
@@ -1266,7 +1666,7 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
             // 2. Double-Claim Protection (Ledger & Pending)
             let (in_ledger, my_power) = {
                 let l_guard = safe_lock(&l);
-                let exists = l_guard.blocks.values().any(|b| b.block_type == BlockType::Mint && b.link.contains(&clean_txid));
+                let exists = l_guard.txid_minted(&clean_txid);
                 // SECURITY FIX C-03: Self-vote must use quadratic voting power
                 // consistent with external VOTE_RES accumulation (× 1000 scale).
                 // Previously: balance / CIL_PER_LOS (raw LOS, e.g. 1000)
@@ -1293,11 +1693,11 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
 
             let (ep, bp) = match consensus_price_opt {
                 Some((eth_median, btc_median)) => {
-                    println!("✅ Using Oracle Consensus for burn calculation");
+                    info!("Using Oracle Consensus for burn calculation");
                     (eth_median, btc_median)
                 },
                 None => {
-                    println!("⚠️ Consensus not yet available, using single-node oracle");
+                    warn!("Consensus not yet available, using single-node oracle");
                     get_crypto_prices().await
                 }
             };
@@ -1332,7 +1732,7 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                     // Step 1: Anti-whale check (separate lock scope)
                     // On functional testnet, skip anti-whale to allow testing with mock burn amounts
                     let mint_result = if testnet_config::get_testnet_config().level == testnet_config::TestnetLevel::Functional {
-                        println!("🧪 TESTNET functional: bypassing anti-whale for {} LOS burn", los_to_mint_display);
+                        info!("TESTNET functional: bypassing anti-whale for {} LOS burn", los_to_mint_display);
                         Ok(())
                     } else {
                         let mut aw_guard = safe_lock(&aw);
@@ -1378,6 +1778,7 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                                 work: 0,
                                 timestamp: std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_secs(),
                                 fee: 0,
+                                memo: None,
                             };
 
                             solve_pow(&mut mint_blk);
@@ -1390,7 +1791,7 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                                 Ok(result) => {
                                     let hash = result.into_hash();
                                     SAVE_DIRTY.store(true, Ordering::Relaxed);
-                                    println!("🧪 TESTNET (Functional): Instant mint {} → {} LOS to {}", sym, los_to_mint / CIL_PER_LOS, recipient);
+                                    info!("TESTNET (Functional): Instant mint {} → {} LOS to {}", sym, los_to_mint / CIL_PER_LOS, recipient);
                                     // Return hash AND serialized block for gossip
                                     Ok((hash, serde_json::to_string(&mint_blk).unwrap_or_default()))
                                 }
@@ -1438,9 +1839,9 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                             "msg": format!("Anti-whale burn limit: {}", e)
                         }));
                     }
-                    println!("🐋 Burn registered: {} LOS for {} (within limits)", los_to_mint_display, get_short_addr(recipient));
+                    info!("Burn registered: {} LOS for {} (within limits)", los_to_mint_display, get_short_addr(recipient));
                 } else {
-                    println!("🧪 TESTNET: bypassing anti-whale for {} LOS burn (mock amount)", los_to_mint_display);
+                    info!("TESTNET: bypassing anti-whale for {} LOS burn (mock amount)", los_to_mint_display);
                 }
 
                 // Production: Add to pending with initial power = our own balance + recipient address
@@ -1472,7 +1873,7 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
 
                 let voter_count = safe_lock(&bv).get(&clean_txid).map(|s| s.len()).unwrap_or(0);
                 if my_power >= threshold && voter_count >= min_voters {
-                    println!("✅ Self-Consensus Achieved (Power: {} >= Threshold: {}, Voters: {}/{})!", my_power, threshold, voter_count, min_voters);
+                    info!("Self-Consensus Achieved (Power: {} >= Threshold: {}, Voters: {}/{})!", my_power, threshold, voter_count, min_voters);
 
                     // Mint using same logic as VOTE_RES consensus path
                     let mint_result: Result<(u128, String), String> = {
@@ -1499,6 +1900,7 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                             work: 0,
                             timestamp: std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_secs(),
                             fee: 0,
+                            memo: None,
                         };
 
                         solve_pow(&mut mint_blk);
@@ -1511,7 +1913,7 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                         match l_guard.process_block(&mint_blk) {
                             Ok(_result) => {
                                 SAVE_DIRTY.store(true, Ordering::Relaxed);
-                                println!("🔥 Burn Minting Successful: +{} LOS to {}!", format_u128(los_to_mint / CIL_PER_LOS), get_short_addr(&burn_recipient));
+                                info!("Burn Minting Successful: +{} LOS to {}!", format_u128(los_to_mint / CIL_PER_LOS), get_short_addr(&burn_recipient));
                                 // Gossip the mint block to peers
                                 Ok((los_to_mint, serde_json::to_string(&mint_blk).unwrap_or_default()))
                             }
@@ -1555,7 +1957,7 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
 
                 let ts = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_millis();
                 let vote_msg = format!("VOTE_REQ:{}:{}:{}:{}", req.coin_type.to_lowercase(), clean_txid, my_addr, ts);
-                println!("📡 Broadcasting VOTE_REQ: {} (Initial Power: {})", &vote_msg[..50], my_power);
+                info!("Broadcasting VOTE_REQ: {} (Initial Power: {})", &vote_msg[..50], my_power);
                 let _ = tx.send(vote_msg).await;
 
                 api_json(serde_json::json!({
@@ -1566,8 +1968,8 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                 }))
             } else {
                 let coin = req.coin_type.to_lowercase();
-                println!("❌ Burn verification FAILED for {} TXID: {} — Oracle returned None", coin.to_uppercase(), &clean_txid[..clean_txid.len().min(16)]);
-                println!("   ↳ Possible causes: TX not yet confirmed on-chain, burn address mismatch, or API unreachable");
+                warn!("Burn verification FAILED for {} TXID: {} — Oracle returned None", coin.to_uppercase(), &clean_txid[..clean_txid.len().min(16)]);
+                warn!("Possible causes: TX not yet confirmed on-chain, burn address mismatch, or API unreachable");
                 api_json(serde_json::json!({
                     "status": "error",
                     "msg": format!("Oracle verification failed for {} TXID. This can happen if: (1) TX is not yet confirmed on the {} blockchain, (2) TX does not send to the official burn address, or (3) the oracle API is currently unreachable. Please wait for blockchain confirmation and try again.", coin.to_uppercase(), coin.to_uppercase())
@@ -1607,9 +2009,18 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                         return api_json(serde_json::json!({"status":"error","msg":"Invalid base64 bytecode"}))
                     }
                 };
-                // Compute code hash for block link
+                // Reject a malformed ABI before any fee is charged
+                let abi_check = match &req.abi {
+                    Some(abi) => abi.validate(),
+                    None => los_core::abi::extract_embedded(&bytecode).map(|_| ()),
+                };
+                if let Err(e) = abi_check {
+                    return api_json(serde_json::json!({"status":"error","msg":format!("Invalid contract ABI: {}", e)}));
+                }
+                // Compute code hash for block link; an attached ABI is committed to
+                // by hash so the deployer's signature covers it
                 let code_hash = WasmEngine::compute_code_hash(&bytecode);
-                let link = format!("DEPLOY:{}", code_hash);
+                let link = los_core::abi::deploy_link(&code_hash, req.abi.as_ref());
                 let amount_cil = req.amount_cil.unwrap_or(0);
                 let is_client_signed = req.signature.is_some() && req.public_key.is_some();
                 let fee = req.fee.unwrap_or(los_core::MIN_DEPLOY_FEE_CIL);
@@ -1648,6 +2059,7 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                     work: req.work.unwrap_or(0),
                     timestamp: req.timestamp.unwrap_or(now_ts),
                     fee,
+                    memo: None,
                 };
 
                 // PoW + Signing
@@ -1675,11 +2087,12 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                 };
 
                 // Deploy bytecode to WASM engine
-                let contract_addr = match engine.deploy_contract(
+                let contract_addr = match engine.deploy_contract_with_abi(
                     account.clone(),
                     bytecode.clone(),
                     req.initial_state.unwrap_or_default(),
                     now_ts,
+                    req.abi.clone(),
                 ) {
                     Ok(addr) => addr,
                     Err(e) => {
@@ -1690,7 +2103,7 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                 // Fund contract if amount > 0
                 if amount_cil > 0 {
                     if let Err(e) = engine.send_to_contract(&contract_addr, amount_cil) {
-                        eprintln!("Warning: Failed to fund contract: {}", e);
+                        warn!("Failed to fund contract: {}", e);
                     }
                 }
 
@@ -1699,12 +2112,20 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                     let _ = db.save_contracts(&vm_data);
                 }
 
-                // Gossip to peers: CONTRACT_DEPLOYED:{block_b64}:{bytecode_b64}:{initial_state_b64}
+                // Gossip to peers: CONTRACT_DEPLOYED:{block_b64}:{bytecode_b64}:{contract_addr}[:{abi_b64}]
+                // The attached ABI rides as an optional trailing field; peers check it
+                // against the ABI hash in the signed block link.
                 let block_b64 = base64::engine::general_purpose::STANDARD.encode(
                     serde_json::to_vec(&block).unwrap_or_default()
                 );
                 let bytecode_b64 = base64::engine::general_purpose::STANDARD.encode(&bytecode);
-                let gossip = format!("CONTRACT_DEPLOYED:{}:{}:{}", block_b64, bytecode_b64, contract_addr);
+                let mut gossip = format!("CONTRACT_DEPLOYED:{}:{}:{}", block_b64, bytecode_b64, contract_addr);
+                if let Some(abi) = &req.abi {
+                    gossip.push(':');
+                    gossip.push_str(&base64::engine::general_purpose::STANDARD.encode(
+                        serde_json::to_vec(abi).unwrap_or_default()
+                    ));
+                }
                 let _ = tx.send(gossip).await;
 
                 SAVE_DIRTY.store(true, Ordering::Relaxed);
@@ -1717,7 +2138,8 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                     "block_hash": block_hash,
                     "owner": account,
                     "fee_cil": fee,
-                    "deployed_at": now_ts
+                    "deployed_at": now_ts,
+                    "has_abi": engine.get_contract(&contract_addr).map(|c| c.abi.is_some()).unwrap_or(false)
                 }))
             });
 
@@ -1745,11 +2167,28 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                         }))
                     }
                 };
-                let gas_limit = req.gas_limit.unwrap_or(los_core::DEFAULT_GAS_LIMIT);
+                // BACKPRESSURE: turn new calls away at admission. Once the block is
+                // applied its execution must run, however long it waits for a worker.
+                if !los_vm::executor::global().has_capacity() {
+                    return api_json(serde_json::json!({
+                        "status": "error", "code": 503,
+                        "msg": "Contract executor is busy, retry shortly"
+                    }));
+                }
+                let gas_limit = req
+                    .gas_limit
+                    .unwrap_or_else(|| safe_lock(&l).chain_params().default_gas_limit);
                 let amount_cil = req.amount_cil.unwrap_or(0);
-                let fee = req.fee.unwrap_or(los_core::MIN_CALL_FEE_CIL.max(
-                    (gas_limit as u128).saturating_mul(los_core::GAS_PRICE_CIL)
-                ));
+                let fee = req.fee.unwrap_or(los_core::contract_call_fee(gas_limit));
+                // Peers only see the fee: execute with the gas it pays for, as they will
+                let paid_gas = los_core::call_gas_paid(fee);
+                if gas_limit > paid_gas {
+                    return api_json(serde_json::json!({
+                        "status": "error", "code": 400,
+                        "msg": format!("Fee {} CIL pays for {} gas, below gas_limit {}", fee, paid_gas, gas_limit)
+                    }));
+                }
+                let gas_limit = paid_gas;
                 let is_client_signed = req.signature.is_some() && req.public_key.is_some();
                 let now_ts = std::time::SystemTime::now()
                     .duration_since(std::time::UNIX_EPOCH)
@@ -1759,7 +2198,12 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                 // Encode args as base64 JSON for deterministic link field
                 let args_json = serde_json::to_string(&req.args).unwrap_or_else(|_| "[]".to_string());
                 let args_b64 = base64::engine::general_purpose::STANDARD.encode(args_json.as_bytes());
-                let link = format!("CALL:{}:{}:{}", req.contract_address, req.function, args_b64);
+                let link = los_core::contract_call_link(
+                    &req.contract_address,
+                    &req.function,
+                    &args_b64,
+                    &req.token_allowances,
+                );
 
                 let (account, pub_key_hex) = if is_client_signed {
                     let pk_hex = req.public_key.clone().unwrap_or_default();
@@ -1793,6 +2237,7 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                     work: req.work.unwrap_or(0),
                     timestamp: req.timestamp.unwrap_or(now_ts),
                     fee,
+                    memo: None,
                 };
 
                 if is_client_signed {
@@ -1811,7 +2256,10 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                 let block_hash = {
                     let mut l_guard = safe_lock(&l);
                     match l_guard.process_block(&block) {
-                        Ok(result) => result.into_hash(),
+                        Ok(result) => {
+                            sync_gas_schedule(&l_guard, &engine, block.timestamp);
+                            result.into_hash()
+                        }
                         Err(e) => {
                             return api_json(serde_json::json!({"status":"error","msg":e}))
                         }
@@ -1833,13 +2281,20 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                     gas_limit,
                     caller: account.clone(),
                     block_timestamp: block.timestamp,
+                    value_cil: amount_cil,
+                    token_allowances: req.token_allowances.clone(),
                 };
 
-                let exec_result = match engine.call_contract(call) {
-                    Ok(result) => result,
-                    Err(e) => {
+                // WASM execution blocks until the executor returns; keep it off the async workers
+                let call_engine = Arc::clone(&engine);
+                let exec_result = match tokio::task::spawn_blocking(move || call_engine.call_contract(call)).await {
+                    Ok(Ok(result)) => result,
+                    Ok(Err(e)) => {
                         return api_json(serde_json::json!({"status":"error","msg":format!("Execution failed: {}", e)}))
                     }
+                    Err(e) => {
+                        return api_json(serde_json::json!({"status":"error","msg":format!("Execution task failed: {}", e)}))
+                    }
                 };
 
                 // Persist VM state to DB
@@ -1886,7 +2341,15 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                             "balance": contract.balance,
                             "owner": contract.owner,
                             "created_at_block": contract.created_at_block,
-                            "state": contract.state
+                            "state": contract.state,
+                            "abi": contract.abi,
+                            "storage": {
+                                "bytes_used": contract.storage_bytes(),
+                                "quota_bytes": los_vm::host::MAX_CONTRACT_STORAGE_BYTES,
+                                "free_bytes": los_vm::host::FREE_STORAGE_BYTES,
+                                "deposit_cil": contract.storage_deposit,
+                                "deposit_per_byte_cil": los_vm::host::STORAGE_DEPOSIT_PER_BYTE
+                            }
                         }
                     })),
                     Err(e) => api_json(serde_json::json!({
@@ -1910,9 +2373,208 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                     Err(e) => api_json(serde_json::json!({"status":"error","msg":e})),
                 });
 
+        // 9c. POST /query-contract (read-only call: no block, no fee, state discarded)
+        let l_query = ledger.clone();
+        let engine_query = wasm_engine.clone();
+        let query = warp::path("query-contract")
+            .and(warp::path::end())
+            .and(warp::post())
+            .and(warp::body::bytes())
+            .and(with_state((l_query, engine_query)))
+            .then(
+                |body: bytes::Bytes, state: (Arc<Mutex<Ledger>>, Arc<WasmEngine>)| async move {
+                    let (l, engine) = state;
+                    let req: QueryContractRequest = match serde_json::from_slice(&body) {
+                        Ok(r) => r,
+                        Err(e) => {
+                            return api_json(serde_json::json!({
+                                "status": "error", "code": 400,
+                                "msg": format!("Invalid request body: {}", e)
+                            }))
+                        }
+                    };
+                    // Queries run as if in a block built now
+                    let now_ts = std::time::SystemTime::now()
+                        .duration_since(std::time::UNIX_EPOCH)
+                        .unwrap_or_default()
+                        .as_secs();
+                    let default_gas_limit = {
+                        let l_guard = safe_lock(&l);
+                        sync_gas_schedule(&l_guard, &engine, now_ts);
+                        l_guard.chain_params_at(now_ts).default_gas_limit
+                    };
+                    let call = ContractCall {
+                        contract: req.contract_address,
+                        function: req.function,
+                        args: req.args,
+                        gas_limit: req.gas_limit.unwrap_or(default_gas_limit),
+                        caller: req.caller.unwrap_or_default(),
+                        block_timestamp: now_ts,
+                        value_cil: 0,
+                        token_allowances: Default::default(),
+                    };
+                    // WASM execution blocks until the executor returns; keep it off the async workers
+                    let result =
+                        tokio::task::spawn_blocking(move || engine.query_contract(&call)).await;
+                    match result {
+                        Ok(Ok(r)) => api_json(serde_json::json!({
+                            "status": "success",
+                            "result": r
+                        })),
+                        Ok(Err(e)) => api_json(serde_json::json!({
+                            "status": "error",
+                            "msg": format!("Query failed: {}", e)
+                        })),
+                        Err(e) => api_json(serde_json::json!({
+                            "status": "error",
+                            "msg": format!("Query task failed: {}", e)
+                        })),
+                    }
+                },
+            );
+
+        // 9d. POST /simulate (dry-run a deploy/call block: gas, state diff, events, fee)
+        let l_sim = ledger.clone();
+        let engine_sim = wasm_engine.clone();
+        let sl_sim = simulate_limiter.clone();
+        let simulate = warp::path("simulate")
+            .and(warp::path::end())
+            .and(warp::post())
+            .and(rate_limiter::filters::client_ip())
+            .and(warp::body::bytes())
+            .and(with_state((l_sim, engine_sim, sl_sim)))
+            .then(|ip: std::net::IpAddr, body: bytes::Bytes, state: (Arc<Mutex<Ledger>>, Arc<WasmEngine>, Arc<EndpointRateLimiter>)| async move {
+                let (l, engine, rate_lim) = state;
+                // Simulations run up to MAX_GAS_PER_CALL for anyone: cap them per IP
+                if let Err(wait_secs) = rate_lim.check_and_record(&ip.to_string()) {
+                    return api_json(serde_json::json!({
+                        "status": "error",
+                        "code": 429,
+                        "msg": format!("Rate limit exceeded: max 20 simulations per minute. Try again in {} seconds.", wait_secs)
+                    }));
+                }
+                let req: SimulateRequest = match serde_json::from_slice(&body) {
+                    Ok(r) => r,
+                    Err(e) => {
+                        return api_json(serde_json::json!({
+                            "status": "error", "code": 400,
+                            "msg": format!("Invalid request body: {}", e)
+                        }))
+                    }
+                };
+                let amount_cil = req.amount_cil.unwrap_or(0);
+                let now_ts = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs();
+
+                let (account, simulation, gas_limit, required_fee) = match req.kind.as_str() {
+                    "call" => {
+                        let (Some(contract), Some(function)) = (req.contract_address, req.function) else {
+                            return api_json(serde_json::json!({
+                                "status": "error", "code": 400,
+                                "msg": "call simulation requires contract_address and function"
+                            }));
+                        };
+                        let caller = req.caller.unwrap_or_default();
+                        sync_gas_schedule(&safe_lock(&l), &engine, now_ts);
+                        let call = ContractCall {
+                            contract,
+                            function,
+                            args: req.args,
+                            gas_limit: req.gas_limit.unwrap_or(los_vm::MAX_GAS_PER_CALL),
+                            caller: caller.clone(),
+                            block_timestamp: now_ts,
+                            value_cil: amount_cil,
+                            token_allowances: req.token_allowances,
+                        };
+                        let sim = match tokio::task::spawn_blocking(move || engine.simulate_call(&call)).await {
+                            Ok(Ok(sim)) => sim,
+                            Ok(Err(e)) => {
+                                return api_json(serde_json::json!({
+                                    "status": "error",
+                                    "msg": format!("Simulation failed: {}", e)
+                                }))
+                            }
+                            Err(e) => {
+                                return api_json(serde_json::json!({
+                                    "status": "error",
+                                    "msg": format!("Simulation task failed: {}", e)
+                                }))
+                            }
+                        };
+                        // An explicit limit is kept as-is; otherwise recommend one
+                        let gas_limit = req.gas_limit.unwrap_or(sim.recommended_gas_limit);
+                        let fee = los_core::contract_call_fee(gas_limit);
+                        (caller, serde_json::json!(sim), Some(gas_limit), fee)
+                    }
+                    "deploy" => {
+                        let (Some(owner), Some(bytecode_b64)) = (req.owner, req.bytecode) else {
+                            return api_json(serde_json::json!({
+                                "status": "error", "code": 400,
+                                "msg": "deploy simulation requires owner and bytecode"
+                            }));
+                        };
+                        let bytecode = match base64::engine::general_purpose::STANDARD.decode(&bytecode_b64) {
+                            Ok(bytes) => bytes,
+                            Err(_) => {
+                                return api_json(serde_json::json!({"status":"error","msg":"Invalid base64 bytecode"}))
+                            }
+                        };
+                        let sim_owner = owner.clone();
+                        let abi = req.abi;
+                        let sim = match tokio::task::spawn_blocking(move || engine.simulate_deploy(&sim_owner, &bytecode, now_ts, abi)).await {
+                            Ok(Ok(sim)) => sim,
+                            Ok(Err(e)) => {
+                                return api_json(serde_json::json!({
+                                    "status": "error",
+                                    "msg": format!("Simulation failed: {}", e)
+                                }))
+                            }
+                            Err(e) => {
+                                return api_json(serde_json::json!({
+                                    "status": "error",
+                                    "msg": format!("Simulation task failed: {}", e)
+                                }))
+                            }
+                        };
+                        (owner, serde_json::json!(sim), None, los_core::MIN_DEPLOY_FEE_CIL)
+                    }
+                    other => {
+                        return api_json(serde_json::json!({
+                            "status": "error", "code": 400,
+                            "msg": format!("Unknown simulation type '{}' (expected 'deploy' or 'call')", other)
+                        }))
+                    }
+                };
+
+                // Ledger side of the block: can the sender cover fee + value?
+                let balance = {
+                    let l_guard = safe_lock(&l);
+                    l_guard.accounts.get(&account).map(|a| a.balance).unwrap_or(0)
+                };
+                let total_debit = required_fee.saturating_add(amount_cil);
+
+                api_json(serde_json::json!({
+                    "status": "success",
+                    "type": req.kind,
+                    "simulation": simulation,
+                    "gas_limit": gas_limit,
+                    "required_fee_cil": required_fee,
+                    "account": {
+                        "address": account,
+                        "balance_cil": balance,
+                        "total_debit_cil": total_debit,
+                        "sufficient_balance": balance >= total_debit
+                    }
+                }))
+            });
+
         deploy
             .boxed()
             .or(call.boxed())
+            .or(query.boxed())
+            .or(simulate.boxed())
             .or(get_contract.boxed())
             .or(list_contracts_route.boxed())
             .boxed()
@@ -2124,6 +2786,7 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
     let my_addr_info = my_address.clone();
     let bv_info = bootstrap_validators.clone();
     let aw_info = anti_whale.clone();
+    let archive_info = archive_peers.clone();
     let node_info_route = warp::path("node-info")
         .and(with_state((l_info, ab_info, aw_info)))
         .map(
@@ -2143,28 +2806,32 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                 // (Does NOT include treasury wallets with high balances)
                 let validator_count = bv_info.len();
                 let peer_count = safe_lock(&ab).len();
-                let network = if los_core::CHAIN_ID == 1 {
-                    "los-mainnet"
-                } else {
-                    "los-testnet"
-                };
+                let network = los_core::chain_params::network_name();
 
                 api_json(serde_json::json!({
                     "chain_id": network,
                     "network": network,
                     "address": my_addr_info,
                     "version": env!("CARGO_PKG_VERSION"),
-                    "block_height": l_guard.blocks.len(),
+                    "block_height": l_guard.total_blocks(),
                     "validator_count": validator_count,
                     "peer_count": peer_count,
                     "total_supply": format_balance_precise(total_supply),
                     "circulating_supply": format_balance_precise(circulating),
                     "network_tps": 0,
+                    "history": {
+                        "mode": pruning.mode.as_str(),
+                        "keep_recent_blocks": pruning.keep_recent,
+                        "pruned_blocks": l_guard.pruned_blocks,
+                        "pruned_before": l_guard.pruned_before,
+                        "archive_peers": archive_info
+                    },
                     "protocol": {
-                        "base_fee_cil": los_core::BASE_FEE_CIL,
-                        "pow_difficulty_bits": los_core::MIN_POW_DIFFICULTY_BITS,
+                        "base_fee_cil": l_guard.base_fee_cil(),
+                        "pow_difficulty_bits": los_core::chain_params::pow_difficulty_bits(),
                         "cil_per_los": los_core::CIL_PER_LOS,
-                        "chain_id_numeric": los_core::CHAIN_ID,
+                        "chain_id_numeric": los_core::chain_params::chain_id(),
+                        "chain_params_version": l_guard.chain_params().version,
                         "anti_whale": {
                             "max_tx_per_window": aw_guard.config().max_tx_per_block,
                             "fee_scale_multiplier": aw_guard.config().fee_scale_multiplier,
@@ -2220,6 +2887,7 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                 // Get real uptime data from reward pool
                 let rp_guard = safe_lock(&rp_validators);
                 let ve_guard = safe_lock(&ve_validators);
+                let min_stake = l_guard.min_validator_stake_cil();
 
                 let validators: Vec<serde_json::Value> = all_validator_addrs
                     .iter()
@@ -2238,7 +2906,7 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                             // known in validator_endpoints (announced via VALIDATOR_REG
                             // or seeded from genesis onion data)
                             let in_endpoints = ve_guard.contains_key(addr.as_str());
-                            let has_min_stake = acc.balance >= MIN_VALIDATOR_STAKE_CIL;
+                            let has_min_stake = acc.balance >= min_stake;
                             // Connected = evidence of P2P liveness (online indicator)
                             let connected = is_self || in_peers || in_endpoints;
                             // in_reward_pool = registered via verified Dilithium5 signature
@@ -2305,6 +2973,19 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
             let bal = acct.map(|a| a.balance).unwrap_or(0);
             let head = acct.map(|a| a.head.as_str()).unwrap_or("0");
             let block_count = acct.map(|a| a.block_count).unwrap_or(0);
+            // Unclaimed Sends, so a wallet can claim them before spending
+            let pending = l_guard.pending_receivables(&full_addr);
+            let pending_cil: u128 = pending.iter().map(|(_, b)| b.amount).sum();
+            let pending_receives: Vec<serde_json::Value> = pending
+                .iter()
+                .map(|(hash, b)| {
+                    serde_json::json!({
+                        "hash": hash,
+                        "from": b.account,
+                        "amount_cil_str": b.amount.to_string()
+                    })
+                })
+                .collect();
             api_json(serde_json::json!({
                 "address": full_addr,
                 "balance": format_balance_precise(bal),
@@ -2312,17 +2993,21 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                 "balance_cil": bal,
                 "balance_cil_str": bal.to_string(),
                 "head": head,
-                "block_count": block_count
+                "block_count": block_count,
+                "pending_cil_str": pending_cil.to_string(),
+                "pending_receives": pending_receives
             }))
         });
 
-    // 13b. GET /fee-estimate/:address (Dynamic fee estimate for anti-whale)
+    // 13b. GET /fee-estimate/:address (Dynamic fee estimate: mempool pressure × anti-whale)
     // Returns estimated fee for the NEXT transaction from this address.
     // Wallet MUST call this before constructing a signed block.
     let aw_fee_estimate = anti_whale.clone();
+    let l_fee_estimate = ledger.clone();
+    let mp_fee_estimate = mempool_pool.clone();
     let fee_estimate_route = warp::path!("fee-estimate" / String)
-        .and(with_state(aw_fee_estimate))
-        .map(|addr: String, aw: Arc<Mutex<AntiWhaleEngine>>| {
+        .and(with_state((aw_fee_estimate, l_fee_estimate, mp_fee_estimate)))
+        .map(|addr: String, (aw, l, mp): (Arc<Mutex<AntiWhaleEngine>>, Arc<Mutex<Ledger>>, Arc<Mutex<mempool::Mempool>>)| {
             // Validate address format (Base58Check with LOS prefix)
             if !los_crypto::validate_address(&addr) {
                 return api_json(serde_json::json!({
@@ -2331,9 +3016,18 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                     "msg": "Invalid address format. Must be Base58Check with LOS prefix."
                 }));
             }
-            let aw_guard = safe_lock(&aw);
-            let base_fee = los_core::BASE_FEE_CIL;
-            let estimated_fee = aw_guard.estimate_fee(&addr, base_fee as u64);
+            let (base_fee, gov_max_tx) = {
+                let l_guard = safe_lock(&l);
+                let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_secs();
+                (l_guard.base_fee_cil(), l_guard.governance.param_at(GovParam::AntiWhaleMaxTxPerBlock, now))
+            };
+            let market = fee_market_json(&safe_lock(&mp), base_fee);
+            let dynamic_base_fee = market["dynamic_base_fee_cil"].as_u64().unwrap_or(base_fee as u64);
+            let mut aw_guard = safe_lock(&aw);
+            if let Some(max_tx) = gov_max_tx {
+                aw_guard.config.max_tx_per_block = max_tx as u32;
+            }
+            let estimated_fee = aw_guard.estimate_fee(&addr, dynamic_base_fee);
             let multiplier = estimated_fee / (base_fee as u64);
             let window_secs = AntiWhaleEngine::ACTIVITY_WINDOW_SECS;
             let max_tx = aw_guard.config().max_tx_per_block;
@@ -2355,14 +3049,30 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                 "base_fee_cil": base_fee,
                 "estimated_fee_cil": estimated_fee,
                 "fee_multiplier": multiplier,
-                "fee_multiplier_bps": if (base_fee as u64) > 0 { estimated_fee * 10_000 / (base_fee as u64) } else { 10_000 },
+                "fee_multiplier_bps": (estimated_fee * 10_000).checked_div(base_fee as u64).unwrap_or(10_000),
                 "tx_count_in_window": tx_count,
                 "max_tx_per_window": max_tx,
                 "window_remaining_secs": window_remaining,
-                "window_duration_secs": window_secs
+                "window_duration_secs": window_secs,
+                "fee_market": market
             }))
         });
 
+    // 13c. GET /fee-estimate (Network-wide fee market: base fee under mempool pressure)
+    let l_fee_market = ledger.clone();
+    let mp_fee_market = mempool_pool.clone();
+    let fee_market_route = warp::path("fee-estimate")
+        .and(warp::path::end())
+        .and(with_state((l_fee_market, mp_fee_market)))
+        .map(
+            |(l, mp): (Arc<Mutex<Ledger>>, Arc<Mutex<mempool::Mempool>>)| {
+                let base_fee = safe_lock(&l).base_fee_cil();
+                let mut market = fee_market_json(&safe_lock(&mp), base_fee);
+                market["status"] = serde_json::json!("success");
+                api_json(market)
+            },
+        );
+
     // 14. GET /block (Latest block) — FIX: added path::end() to prevent stealing /block/{hash}
     let l_block = ledger.clone();
     let block_route = warp::path("block")
@@ -2374,7 +3084,7 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
             let latest = l_guard.blocks.values().max_by_key(|b| b.timestamp);
             if let Some(b) = latest {
                 api_json(serde_json::json!({
-                    "height": l_guard.blocks.len(),
+                    "height": l_guard.total_blocks(),
                     "hash": b.calculate_hash(),
                     "account": b.account,
                     "previous": b.previous,
@@ -2503,6 +3213,7 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                     work: 0,
                     timestamp: std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_secs(),
                     fee: 0,
+                    memo: None,
                 };
 
                 solve_pow(&mut faucet_block);
@@ -2611,6 +3322,7 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
 
                 for hash in &matching_hashes {
                     if let Some(block) = l_guard.blocks.remove(hash) {
+                        l_guard.unindex_block(hash, &block);
                         // Reverse the balance: subtract minted amount from account
                         if let Some(account) = l_guard.accounts.get_mut(&block.account) {
                             account.balance = account.balance.saturating_sub(block.amount);
@@ -2649,14 +3361,14 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
             let l_guard = safe_lock(&l);
             // SECURITY FIX #13: Sort by timestamp descending for deterministic recent blocks
             let mut block_list: Vec<(&String, &Block)> = l_guard.blocks.iter().collect();
-            block_list.sort_by(|a, b| b.1.timestamp.cmp(&a.1.timestamp));
+            block_list.sort_by_key(|b| std::cmp::Reverse(b.1.timestamp));
             let blocks: Vec<serde_json::Value> = block_list
                 .iter()
                 .take(10) // Last 10 blocks by timestamp
                 .map(|(hash, b)| {
                     serde_json::json!({
                         "hash": hash,
-                        "height": l_guard.blocks.len(),
+                        "height": l_guard.total_blocks(),
                         "timestamp": b.timestamp,
                         "transactions_count": 1,
                         "account": b.account,
@@ -2767,9 +3479,11 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                 "bal": "GET /bal/{address} - Account balance (short alias)",
                 "balance": "GET /balance/{address} - Account balance",
                 "supply": "GET /supply - Total supply, burned, remaining",
-                "fee_estimate": "GET /fee-estimate/{address} - Dynamic fee estimate (anti-whale)",
+                "fee_estimate": "GET /fee-estimate/{address} - Dynamic fee estimate (mempool pressure + anti-whale)",
+                "fee_market": "GET /fee-estimate - Mempool base fee, fast fee and replace-by-fee rules",
                 "account": "GET /account/{address} - Account details + history",
-                "history": "GET /history/{address} - Transaction history",
+                "history": "GET /history/{address} - Transaction history (?memo= filters by payment ID)",
+                "pending": "GET /pending/{address} - Unclaimed Sends addressed to the account",
                 "validators": "GET /validators - Active validators",
                 "peers": "GET /peers - Connected peers + validator endpoints",
                 "network_peers": "GET /network/peers - Validator .onion endpoint discovery",
@@ -2787,13 +3501,17 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                 "metrics": "GET /metrics - Prometheus metrics",
                 "mempool_stats": "GET /mempool/stats - Mempool statistics",
                 "send": "POST /send {from, target, amount} - Send transaction",
+                "receive": "POST /receive {block} - Publish an owner-signed Receive block",
                 "burn": "POST /burn {chain, tx_hash} - Proof-of-burn mint",
                 "faucet": "POST /faucet {address} - Claim testnet tokens",
                 "register_validator": "POST /register-validator - Register as validator",
                 "unregister_validator": "POST /unregister-validator - Unregister validator",
                 "reset_burn_txid": "POST /reset-burn-txid - Reset stuck burn TXID",
+                "admin_log_level": "GET|POST /admin/log-level {filter} - Read or replace the log filter (X-Admin-Token)",
                 "deploy_contract": "POST /deploy-contract - Deploy WASM smart contract",
                 "call_contract": "POST /call-contract - Call smart contract method",
+                "query_contract": "POST /query-contract - Read-only contract call (no block, state discarded)",
+                "simulate": "POST /simulate {type: deploy|call, ...} - Dry-run a contract block (gas, state diff, fee)",
                 "contract": "GET /contract/{address} - Contract info and state",
                 "tokens": "GET /tokens - List all USP-01 tokens",
                 "token_info": "GET /token/{address} - USP-01 token metadata",
@@ -2802,7 +3520,9 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                 "dex_pools": "GET /dex/pools - List all DEX pools",
                 "dex_pool": "GET /dex/pool/{contract}/{pool_id} - Pool info",
                 "dex_quote": "GET /dex/quote/{contract}/{pool_id}/{token_in}/{amount} - Swap quote",
-                "dex_position": "GET /dex/position/{contract}/{pool_id}/{user} - LP position"
+                "dex_position": "GET /dex/position/{contract}/{pool_id}/{user} - LP position",
+                "chain_params": "GET /chain-params - Runtime chain parameters and upgrade schedule",
+                "governance_params": "GET /governance/params - Governable parameters in force"
             },
             "docs": "https://github.com/unauthoritymky-6236/unauthority-core",
             "status": "operational"
@@ -2907,9 +3627,9 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                 "status": status,
                 "uptime_seconds": start_time.elapsed().as_secs(),
                 "chain": {
-                    "id": if los_core::is_mainnet_build() { "los-mainnet" } else { "los-testnet" },
+                    "id": los_core::chain_params::network_name(),
                     "accounts": l_guard.accounts.len(),
-                    "blocks": l_guard.blocks.len()
+                    "blocks": l_guard.total_blocks()
                 },
                 "database": {
                     "accounts_count": db_stats.accounts_count,
@@ -2958,9 +3678,11 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
 
     // 23. GET /block/:hash (Block explorer - get block by hash)
     let l_block_hash = ledger.clone();
+    let archive_block = archive_peers.clone();
     let block_by_hash_route = warp::path!("block" / String)
         .and(with_state(l_block_hash))
-        .map(|hash: String, l: Arc<Mutex<Ledger>>| {
+        .map(move |hash: String, l: Arc<Mutex<Ledger>>| {
+            use warp::Reply;
             let l_guard = safe_lock(&l);
             if let Some(block) = l_guard.blocks.get(&hash) {
                 api_json(serde_json::json!({
@@ -2979,19 +3701,33 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                         "timestamp": block.timestamp
                     }
                 }))
+                .into_response()
+            } else if pruning.is_pruned() {
+                archive_redirect(
+                    &format!("/block/{}", hash),
+                    &archive_block,
+                    serde_json::json!({
+                        "status": "error",
+                        "code": 404,
+                        "msg": format!("Block not found: {} (pruned node, history before {} may be pruned; set LOS_ARCHIVE_PEERS)", hash, l_guard.pruned_before)
+                    }),
+                )
             } else {
                 api_json(serde_json::json!({
                     "status": "error",
                     "msg": format!("Block not found: {}", hash)
                 }))
+                .into_response()
             }
         });
 
     // 24. GET /transaction/:hash (Alias for block by hash - block explorer compatibility)
     let l_tx_hash = ledger.clone();
+    let archive_tx = archive_peers.clone();
     let tx_by_hash_route = warp::path!("transaction" / String)
         .and(with_state(l_tx_hash))
-        .map(|hash: String, l: Arc<Mutex<Ledger>>| {
+        .map(move |hash: String, l: Arc<Mutex<Ledger>>| {
+            use warp::Reply;
             let l_guard = safe_lock(&l);
             if let Some(block) = l_guard.blocks.get(&hash) {
                 api_json(serde_json::json!({
@@ -3008,21 +3744,225 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                         "confirmed": true
                     }
                 }))
+                .into_response()
+            } else if pruning.is_pruned() {
+                archive_redirect(
+                    &format!("/transaction/{}", hash),
+                    &archive_tx,
+                    serde_json::json!({
+                        "status": "error",
+                        "code": 404,
+                        "msg": format!("Transaction not found: {} (pruned node, history before {} may be pruned; set LOS_ARCHIVE_PEERS)", hash, l_guard.pruned_before)
+                    }),
+                )
             } else {
                 api_json(serde_json::json!({
                     "status": "error",
                     "msg": format!("Transaction not found: {}", hash)
                 }))
+                .into_response()
             }
         });
 
-    // 25. GET /search/:query (Block explorer - search for address, block, or transaction)
-    let l_search = ledger.clone();
-    let ab_search = address_book.clone();
-    let search_route = warp::path!("search" / String)
-        .and(with_state((l_search, ab_search)))
+    // 24b. GET /transaction/:hash/status — lifecycle state of a hash:
+    // received → in_mempool → awaiting_quorum → applied → final, or rejected (with reason)
+    let status_sources = TxStatusSources {
+        ledger: ledger.clone(),
+        pending_sends: pending_sends.clone(),
+        send_voters: send_voters.clone(),
+        mempool: mempool_pool.clone(),
+        checkpoints: checkpoint_manager.clone(),
+        tracker: tx_tracker.clone(),
+    };
+    let tx_status_route = warp::path!("transaction" / String / "status")
+        .and(warp::get())
+        .and(with_state(status_sources.clone()))
         .map(
-            #[allow(clippy::type_complexity)]
+            |hash: String, src: TxStatusSources| match tx_status_json(&src, &hash) {
+                Some(status) => api_json(serde_json::json!({
+                    "status": "success",
+                    "transaction": status
+                })),
+                None => api_json(serde_json::json!({
+                    "status": "error",
+                    "code": 404,
+                    "msg": format!("Unknown transaction: {}", hash)
+                })),
+            },
+        );
+
+    // 24c. GET /account/:address/tx-status — this node's in-flight and recently rejected
+    // transactions for an account (mempool first, then remembered hashes newest first)
+    let account_tx_status_route = warp::path!("account" / String / "tx-status")
+        .and(warp::get())
+        .and(with_state(status_sources))
+        .map(|address: String, src: TxStatusSources| {
+            const MAX_ACCOUNT_STATUSES: usize = 100;
+            let mut hashes = safe_lock(&src.mempool).get_transactions_by_sender(&address);
+            for hash in safe_lock(&src.tracker).hashes_for_account(&address) {
+                if !hashes.contains(&hash) {
+                    hashes.push(hash);
+                }
+            }
+            let transactions: Vec<serde_json::Value> = hashes
+                .iter()
+                .take(MAX_ACCOUNT_STATUSES)
+                .filter_map(|hash| tx_status_json(&src, hash))
+                .collect();
+            api_json(serde_json::json!({
+                "status": "success",
+                "address": address,
+                "count": transactions.len(),
+                "transactions": transactions
+            }))
+        });
+
+    // 24d. GET /pending/:address — Sends addressed to the account that no Receive
+    // has claimed yet, oldest first
+    let l_pending = ledger.clone();
+    let pending_route = warp::path!("pending" / String)
+        .and(warp::get())
+        .and(with_state(l_pending))
+        .map(|address: String, l: Arc<Mutex<Ledger>>| {
+            if !los_crypto::validate_address(&address) {
+                return api_json(serde_json::json!({
+                    "status": "error",
+                    "code": 400,
+                    "msg": "Invalid address format. Must be Base58Check with LOS prefix."
+                }));
+            }
+            let now = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+            let l_guard = safe_lock(&l);
+            let pending = l_guard.pending_receivables(&address);
+            let total_cil: u128 = pending.iter().map(|(_, b)| b.amount).sum();
+            let entries: Vec<serde_json::Value> = pending
+                .iter()
+                .map(|(hash, b)| {
+                    let mut entry = serde_json::json!({
+                        "hash": hash,
+                        "from": b.account,
+                        "amount": format_balance_precise(b.amount),
+                        "amount_cil": b.amount,
+                        "amount_cil_str": b.amount.to_string(),
+                        "timestamp": b.timestamp,
+                        "age_secs": now.saturating_sub(b.timestamp)
+                    });
+                    if let Some(memo) = &b.memo {
+                        entry["memo"] = serde_json::json!(memo);
+                    }
+                    entry
+                })
+                .collect();
+            api_json(serde_json::json!({
+                "status": "success",
+                "address": address,
+                "count": entries.len(),
+                "total_cil": total_cil,
+                "total_cil_str": total_cil.to_string(),
+                "pending": entries
+            }))
+        });
+
+    // 24e. POST /receive — publish an owner-signed Receive block claiming a pending Send
+    // (body: the Block JSON with PoW and Dilithium5 signature)
+    let l_receive = ledger.clone();
+    let tx_receive = tx_out.clone();
+    let receive_route = warp::path("receive")
+        .and(warp::post())
+        .and(warp::body::bytes())
+        .and(with_state((l_receive, tx_receive)))
+        .then(|body: bytes::Bytes, (l, tx): (Arc<Mutex<Ledger>>, mpsc::Sender<String>)| async move {
+            let block: Block = match serde_json::from_slice(&body) {
+                Ok(b) => b,
+                Err(e) => {
+                    return api_json(serde_json::json!({
+                        "status": "error",
+                        "code": 400,
+                        "msg": format!("Invalid request body: {}", e)
+                    }));
+                }
+            };
+            if block.block_type != BlockType::Receive {
+                return api_json(serde_json::json!({"status": "error", "msg": "Only Receive blocks can be published here"}));
+            }
+            // Receives are not bound to the account key in process_block (nodes
+            // auto-receive for recipients); this endpoint only accepts the owner's.
+            let owner_signed = hex::decode(&block.public_key)
+                .map(|pk| los_crypto::public_key_to_address(&pk) == block.account)
+                .unwrap_or(false);
+            if !owner_signed {
+                return api_json(serde_json::json!({"status": "error", "msg": "Receive must be signed by the account owner"}));
+            }
+            let result = {
+                let mut l_guard = safe_lock(&l);
+                l_guard.process_block(&block).map(|r| {
+                    let balance = l_guard.accounts.get(&block.account).map(|a| a.balance).unwrap_or(0);
+                    (r.into_hash(), balance)
+                })
+            }; // L dropped
+            match result {
+                Ok((hash, balance)) => {
+                    SAVE_DIRTY.store(true, Ordering::Relaxed);
+                    let _ = tx.send(serde_json::to_string(&block).unwrap_or_default()).await;
+                    api_json(serde_json::json!({
+                        "status": "success",
+                        "hash": hash,
+                        "account": block.account,
+                        "amount_cil": block.amount,
+                        "balance_cil": balance
+                    }))
+                }
+                Err(e) => api_json(serde_json::json!({"status": "error", "msg": e})),
+            }
+        });
+
+    // 24f. GET|POST /admin/log-level — read or replace the tracing filter at runtime
+    // (body: {"filter": "info,los_network=debug"}). Disabled unless LOS_ADMIN_TOKEN is
+    // set; the token must be sent in the X-Admin-Token header.
+    let admin_token = std::env::var("LOS_ADMIN_TOKEN")
+        .ok()
+        .filter(|t| !t.is_empty());
+    let log_level_route = warp::path!("admin" / "log-level")
+        .and(
+            warp::get()
+                .map(|| None)
+                .or(warp::post().and(warp::body::json()).map(Some))
+                .unify(),
+        )
+        .and(warp::header::optional::<String>("x-admin-token"))
+        .and(with_state(admin_token))
+        .map(|body: Option<serde_json::Value>, given: Option<String>, expected: Option<String>| {
+            let Some(expected) = expected else {
+                return api_json(serde_json::json!({"status": "error", "code": 403, "msg": "Admin API disabled (set LOS_ADMIN_TOKEN)"}));
+            };
+            if !admin_token_matches(given.as_deref(), &expected) {
+                return api_json(serde_json::json!({"status": "error", "code": 401, "msg": "Invalid or missing X-Admin-Token"}));
+            }
+            let Some(body) = body else {
+                return api_json(serde_json::json!({"status": "success", "filter": logging::current_filter()}));
+            };
+            let Some(directives) = body["filter"].as_str() else {
+                return api_json(serde_json::json!({"status": "error", "msg": "Missing 'filter' (e.g. \"info,los_network=debug\")"}));
+            };
+            match logging::set_filter(directives) {
+                Ok(applied) => {
+                    warn!(filter = %applied, "Log filter changed via admin API");
+                    api_json(serde_json::json!({"status": "success", "filter": applied}))
+                }
+                Err(e) => api_json(serde_json::json!({"status": "error", "msg": e})),
+            }
+        });
+
+    // 25. GET /search/:query (Block explorer - search for address, block, or transaction)
+    let l_search = ledger.clone();
+    let ab_search = address_book.clone();
+    let search_route = warp::path!("search" / String)
+        .and(with_state((l_search, ab_search)))
+        .map(
+            #[allow(clippy::type_complexity)]
             |query: String, (l, ab): (Arc<Mutex<Ledger>>, Arc<Mutex<HashMap<String, String>>>)| {
                 let l_guard = safe_lock(&l);
                 let mut results = Vec::new();
@@ -3105,18 +4045,27 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
     // 26. GET /sync (HTTP-based state sync for Tor peers)
     // Returns GZIP-compressed ledger state for peers that connect via HTTP
     let l_sync = ledger.clone();
+    let archive_sync = archive_peers.clone();
     let sync_route = warp::path("sync")
         .and(warp::query::<std::collections::HashMap<String, String>>())
         .and(with_state(l_sync))
         .map(
-            |params: std::collections::HashMap<String, String>, l: Arc<Mutex<Ledger>>| {
+            move |params: std::collections::HashMap<String, String>, l: Arc<Mutex<Ledger>>| {
                 let their_blocks: usize = params
                     .get("blocks")
                     .and_then(|s| s.parse().ok())
                     .unwrap_or(0);
 
+                // Pruned nodes cannot replay full history — point the peer at archive nodes
+                if pruning.is_pruned() {
+                    return api_json(serde_json::json!({
+                        "status": "pruned",
+                        "archive_peers": archive_sync
+                    }));
+                }
+
                 let l_guard = safe_lock(&l);
-                let our_blocks = l_guard.blocks.len();
+                let our_blocks = l_guard.total_blocks();
 
                 // Only send state if we have more blocks
                 if our_blocks <= their_blocks {
@@ -3158,13 +4107,14 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                 let abft_guard = safe_lock(&abft);
                 let l_guard = safe_lock(&l);
                 let stats = abft_guard.get_statistics();
+                let min_stake = l_guard.min_validator_stake_cil();
                 // FIX: active_validators must only count accounts that are BOTH
                 // registered validators AND have sufficient stake. Previously this
                 // counted all accounts with enough balance, inflating the number.
                 let active_validators = l_guard
                     .accounts
                     .iter()
-                    .filter(|(_, a)| a.is_validator && a.balance >= MIN_VALIDATOR_STAKE_CIL)
+                    .filter(|(_, a)| a.is_validator && a.balance >= min_stake)
                     .count();
 
                 api_json(serde_json::json!({
@@ -3193,6 +4143,382 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
             },
         );
 
+    // 27b. GET /consensus/epochs (current and next validator set epochs)
+    let abft_epochs = abft_consensus.clone();
+    let consensus_epochs_route = warp::path!("consensus" / "epochs")
+        .and(with_state(abft_epochs))
+        .map(|abft: Arc<Mutex<ABFTConsensus>>| {
+            let abft_guard = safe_lock(&abft);
+            let epoch_json = |e: &los_consensus::epoch::ValidatorSetEpoch| {
+                serde_json::json!({
+                    "epoch": e.epoch,
+                    "start_height": e.start_height,
+                    "seed": e.seed,
+                    "total_voting_power": e.total_voting_power.to_string(),
+                    "validators": e.validators.iter().map(|v| serde_json::json!({
+                        "address": v.address,
+                        "stake_cil": v.stake_cil.to_string(),
+                        "voting_power": v.voting_power.to_string(),
+                    })).collect::<Vec<_>>(),
+                })
+            };
+            let current = epoch_json(abft_guard.current_epoch_set());
+            let next = epoch_json(&abft_guard.next_epoch_set());
+
+            api_json(serde_json::json!({
+                "status": "success",
+                "epoch_length_blocks": CHECKPOINT_INTERVAL,
+                "leader_selection": "stake-weighted (sqrt(stake)), seed = Keccak256(previous seed, agreed transition)",
+                "current_leader": abft_guard.get_leader(abft_guard.view),
+                "pending_changes": abft_guard.pending_validator_set.is_some(),
+                "current": current,
+                "next": next
+            }))
+        });
+
+    // 27c. GET /forks (unresolved account-chain forks and recent fork events)
+    let fc_route = fork_choice.clone();
+    let forks_route = warp::path("forks")
+        .and(warp::path::end())
+        .and(with_state(fc_route))
+        .map(|fc: Arc<Mutex<ForkChoice>>| {
+            let fc_guard = safe_lock(&fc);
+            let active: Vec<serde_json::Value> = fc_guard
+                .active()
+                .map(|f| {
+                    serde_json::json!({
+                        "account": f.account,
+                        "previous": f.previous,
+                        "detected_at": f.detected_at,
+                        "candidates": f.candidates.keys().map(|h| serde_json::json!({
+                            "hash": h,
+                            "votes": f.votes.get(h).map(|v| v.len()).unwrap_or(0),
+                            "voting_power": f.tally(h).to_string(),
+                        })).collect::<Vec<_>>(),
+                    })
+                })
+                .collect();
+            let events: Vec<&los_core::fork_choice::ForkEvent> = fc_guard.events().collect();
+
+            api_json(serde_json::json!({
+                "status": "success",
+                "detected_total": fc_guard.detected_total,
+                "resolved_total": fc_guard.resolved_total,
+                "rolled_back_blocks_total": fc_guard.rolled_back_total,
+                "active": active,
+                "events": events
+            }))
+        });
+
+    // 27d. Governance: GET /governance/params, /governance/proposals, /governance/proposal/:id
+    //      POST /governance/propose, /governance/vote, /governance/settle
+    let l_gov_params = ledger.clone();
+    let governance_params_route = warp::path!("governance" / "params")
+        .and(with_state(l_gov_params))
+        .map(|l: Arc<Mutex<Ledger>>| {
+            let l_guard = safe_lock(&l);
+            // Defaults come from the genesis chain-params schedule in force now
+            let now = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+            let scheduled = l_guard.chain_params.at(now);
+            let params: Vec<serde_json::Value> = GovParam::ALL
+                .iter()
+                .map(|p| {
+                    let governed = l_guard.governance.param_at(*p, now);
+                    let base = p.base_value(&scheduled);
+                    serde_json::json!({
+                        "name": p.as_str(),
+                        "value": governed.or(base).map(|v| v.to_string()),
+                        "default": base.map(|v| v.to_string()),
+                        "governed": governed.is_some(),
+                        "min": p.bounds().0.to_string(),
+                        "max": p.bounds().1.to_string(),
+                    })
+                })
+                .collect();
+            api_json(serde_json::json!({
+                "status": "success",
+                "block_height": l_guard.total_blocks(),
+                "params": params
+            }))
+        });
+
+    // 27e. GET /chain-params (runtime chain parameters: in force + genesis schedule)
+    let l_chain_params = ledger.clone();
+    let chain_params_route = warp::path("chain-params")
+        .and(warp::path::end())
+        .and(with_state(l_chain_params))
+        .map(|l: Arc<Mutex<Ledger>>| {
+            let l_guard = safe_lock(&l);
+            api_json(serde_json::json!({
+                "status": "success",
+                "block_height": l_guard.total_blocks(),
+                "network": los_core::chain_params::network_name(),
+                "in_force": l_guard.chain_params(),
+                "schedule": l_guard.chain_params
+            }))
+        });
+
+    let l_gov_list = ledger.clone();
+    let governance_proposals_route = warp::path!("governance" / "proposals")
+        .and(with_state(l_gov_list))
+        .map(|l: Arc<Mutex<Ledger>>| {
+            let now = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+            let l_guard = safe_lock(&l);
+            let mut proposals: Vec<&los_core::governance::Proposal> =
+                l_guard.governance.proposals.values().collect();
+            proposals.sort_by_key(|p| std::cmp::Reverse(p.submitted_time));
+            let proposals: Vec<serde_json::Value> = proposals
+                .into_iter()
+                .map(|p| {
+                    serde_json::json!({
+                        "id": p.id,
+                        "proposer": p.proposer,
+                        "param": p.param.as_str(),
+                        "value": p.value.to_string(),
+                        "status": p.status_at(now),
+                        "votes": p.votes.len(),
+                        "voting_end_time": p.voting_end_time,
+                        "activation_time": p.activation_time,
+                    })
+                })
+                .collect();
+            api_json(serde_json::json!({
+                "status": "success",
+                "block_height": l_guard.total_blocks(),
+                "voting_period_secs": los_core::governance::effective_voting_period_secs(),
+                "activation_delay_secs": los_core::governance::effective_activation_delay_secs(),
+                "min_deposit_cil": los_core::governance::MIN_PROPOSAL_DEPOSIT_CIL.to_string(),
+                "proposals": proposals
+            }))
+        });
+
+    let l_gov_one = ledger.clone();
+    let abft_gov_one = abft_consensus.clone();
+    let governance_proposal_route = warp::path!("governance" / "proposal" / String)
+        .and(with_state((l_gov_one, abft_gov_one)))
+        .map(
+            |id: String, (l, abft): (Arc<Mutex<Ledger>>, Arc<Mutex<ABFTConsensus>>)| {
+                let now = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs();
+                let electorate = safe_lock(&abft).current_epoch.stakes(); // ABFT dropped
+                let l_guard = safe_lock(&l);
+                let Some(p) = l_guard.governance.proposals.get(&id) else {
+                    return api_json(serde_json::json!({
+                        "status": "error", "code": 404,
+                        "msg": format!("Proposal {} not found", id)
+                    }));
+                };
+                // Unsettled: tally over the running epoch, which a settlement must carry
+                let tally = match &p.tally {
+                    Some(t) => t.clone(),
+                    None => los_core::governance::GovernanceState::tally(p, &electorate),
+                };
+                api_json(serde_json::json!({
+                    "status": "success",
+                    "block_height": l_guard.total_blocks(),
+                    "proposal": {
+                        "id": p.id,
+                        "proposer": p.proposer,
+                        "param": p.param.as_str(),
+                        "value": p.value.to_string(),
+                        "deposit_cil": p.deposit_cil.to_string(),
+                        "status": p.status_at(now),
+                        "submitted_time": p.submitted_time,
+                        "voting_end_time": p.voting_end_time,
+                        "activation_time": p.activation_time,
+                        "votes": p.votes,
+                        "final": p.status != ProposalStatus::Voting,
+                        "settlement": p.settlement,
+                        "tally": {
+                            "yes_power": tally.yes_power.to_string(),
+                            "no_power": tally.no_power.to_string(),
+                            "total_power": tally.total_power.to_string(),
+                            "yes_bps": tally.yes_bps,
+                        }
+                    }
+                }))
+            },
+        );
+
+    let governance_submit = |action: &'static str| {
+        warp::path("governance")
+            .and(warp::path(action))
+            .and(warp::path::end())
+            .and(warp::post())
+            .and(warp::body::bytes())
+            .and(with_state((
+                ledger.clone(),
+                tx_out.clone(),
+                secret_key.clone(),
+                node_public_key.clone(),
+                my_address.clone(),
+                pending_sends.clone(),
+                mempool_pool.clone(),
+                tx_tracker.clone(),
+                abft_consensus.clone(),
+            )))
+            .then(
+                #[allow(clippy::type_complexity)]
+                move |body: bytes::Bytes,
+                      (l, tx, sk, pk, my_addr, p, mp, tt, abft): (
+                    Arc<Mutex<Ledger>>,
+                    mpsc::Sender<String>,
+                    Zeroizing<Vec<u8>>,
+                    Vec<u8>,
+                    String,
+                    Arc<Mutex<HashMap<String, (Block, u128)>>>,
+                    Arc<Mutex<mempool::Mempool>>,
+                    Arc<Mutex<tx_status::TxStatusTracker>>,
+                    Arc<Mutex<ABFTConsensus>>,
+                )| async move {
+                    let parsed = if action == "settle" {
+                        serde_json::from_slice::<SettleRequest>(&body)
+                            .map_err(|e| e.to_string())
+                            .and_then(|req| settlement_tx(&l, &abft, req))
+                    } else if action == "propose" {
+                        serde_json::from_slice::<ProposeRequest>(&body)
+                            .map_err(|e| e.to_string())
+                            .and_then(|req| {
+                                let param: GovParam = req.param.parse()?;
+                                Ok(GovernanceTx {
+                                    block_type: BlockType::Proposal,
+                                    amount: req
+                                        .deposit_cil
+                                        .unwrap_or(los_core::governance::MIN_PROPOSAL_DEPOSIT_CIL),
+                                    link: los_core::governance::proposal_link(
+                                        param,
+                                        req.value,
+                                        req.activation_time,
+                                    ),
+                                    signature: req.signature,
+                                    public_key: req.public_key,
+                                    previous: req.previous,
+                                    work: req.work,
+                                    timestamp: req.timestamp,
+                                    fee: req.fee,
+                                })
+                            })
+                    } else {
+                        serde_json::from_slice::<VoteRequest>(&body)
+                            .map_err(|e| e.to_string())
+                            .map(|req| GovernanceTx {
+                                block_type: BlockType::Vote,
+                                amount: 0,
+                                link: los_core::governance::vote_link(
+                                    &req.proposal_id,
+                                    req.approve,
+                                ),
+                                signature: req.signature,
+                                public_key: req.public_key,
+                                previous: req.previous,
+                                work: req.work,
+                                timestamp: req.timestamp,
+                                fee: req.fee,
+                            })
+                    };
+                    let gov_tx = match parsed {
+                        Ok(t) => t,
+                        Err(e) => {
+                            return api_json(serde_json::json!({
+                                "status": "error", "code": 400,
+                                "msg": format!("Invalid request body: {}", e)
+                            }))
+                        }
+                    };
+                    let block = match governance_block(&l, &sk, &pk, &my_addr, gov_tx) {
+                        Ok(b) => b,
+                        Err(e) => {
+                            return api_json(serde_json::json!({"status": "error", "msg": e}))
+                        }
+                    };
+                    if !block.verify_signature() || !block.verify_pow() {
+                        return api_json(serde_json::json!({
+                            "status": "error",
+                            "msg": "Invalid signature or proof-of-work"
+                        }));
+                    }
+                    if let Err(e) = pending_send_check(&l, &block) {
+                        return api_json(serde_json::json!({"status": "error", "msg": e}));
+                    }
+
+                    // Like sends, governance blocks are applied only once validators
+                    // confirm them (CONFIRM_RES quorum); see the CONFIRM_RES handler.
+                    let block_hash = block.calculate_hash();
+                    let head = safe_lock(&l)
+                        .accounts
+                        .get(&block.account)
+                        .map(|a| a.head.clone())
+                        .unwrap_or_else(|| "0".to_string()); // L dropped
+                    let admission = {
+                        let mut mp_guard = safe_lock(&mp);
+                        mp_guard
+                            .replaceable_conflict(&block, &head)
+                            .and_then(|conflict| {
+                                mp_guard
+                                    .add_transaction(block.clone(), block.fee as u64, 0)
+                                    .map(|_| conflict)
+                            })
+                    }; // MP dropped
+                    match admission {
+                        Ok(replaced) => {
+                            let mut tt_guard = safe_lock(&tt);
+                            tt_guard.admitted(&block_hash);
+                            if let Some(ref old) = replaced {
+                                tt_guard.rejected(
+                                    old,
+                                    &block.account,
+                                    format!("Replaced by fee bump: {}", block_hash),
+                                );
+                            }
+                        }
+                        Err(e) => {
+                            safe_lock(&tt).rejected(&block_hash, &block.account, e.as_str());
+                            return api_json(serde_json::json!({"status": "error", "msg": e}));
+                        }
+                    } // TT dropped
+                    {
+                        let mut ps = safe_lock(&p);
+                        ps.retain(|_, (b, _)| {
+                            !(b.account == block.account && b.previous == block.previous)
+                        });
+                        ps.insert(block_hash.clone(), (block.clone(), 0u128));
+                    } // PS dropped
+
+                    let block_b64 = base64::engine::general_purpose::STANDARD
+                        .encode(serde_json::to_vec(&block).unwrap_or_default());
+                    let ts = std::time::SystemTime::now()
+                        .duration_since(std::time::UNIX_EPOCH)
+                        .unwrap_or_default()
+                        .as_millis();
+                    let _ = tx
+                        .send(format!(
+                            "CONFIRM_REQ:{}:{}:{}:{}:{}",
+                            block_hash, block.account, block.amount, ts, block_b64
+                        ))
+                        .await;
+
+                    api_json(serde_json::json!({
+                        "status": "success",
+                        "block_hash": block_hash,
+                        "account": block.account,
+                        "fee_cil": block.fee.to_string(),
+                        "pending_confirmation": true
+                    }))
+                },
+            )
+    };
+    let governance_propose_route = governance_submit("propose");
+    let governance_vote_route = governance_submit("vote");
+    let governance_settle_route = governance_submit("settle");
+
     // 28. GET /reward-info (Validator reward pool status)
     let rp_info = reward_pool.clone();
     let reward_info_route = warp::path("reward-info").and(with_state(rp_info)).map(
@@ -3221,7 +4547,7 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                         "stake_cil": v.stake_cil,
                         "uptime_pct": v.uptime_pct(),
                         "cumulative_rewards_cil": v.cumulative_rewards_cil,
-                        "eligible": v.is_eligible(pool.current_epoch),
+                        "eligible": v.is_eligible(pool.current_epoch, pool.min_stake_cil),
                         "heartbeats_current_epoch": v.heartbeats_current_epoch,
                         "expected_heartbeats": v.expected_heartbeats,
                     })
@@ -3371,12 +4697,13 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                 }));
             }
 
-            // 5. Check balance >= MIN_VALIDATOR_STAKE_CIL
-            let (balance, already_validator) = {
+            // 5. Check balance >= minimum validator stake in force
+            let (balance, already_validator, min_stake) = {
                 let l_guard = safe_lock(&l);
+                let min_stake = l_guard.chain_params().min_validator_stake_cil;
                 match l_guard.accounts.get(&address) {
-                    Some(acc) => (acc.balance, acc.is_validator),
-                    None => (0, false),
+                    Some(acc) => (acc.balance, acc.is_validator, min_stake),
+                    None => (0, false, min_stake),
                 }
             };
 
@@ -3390,8 +4717,8 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                 }));
             }
 
-            if balance < MIN_VALIDATOR_STAKE_CIL {
-                let min_los = MIN_VALIDATOR_STAKE_CIL / CIL_PER_LOS;
+            if balance < min_stake {
+                let min_los = min_stake / CIL_PER_LOS;
                 let current_los = balance / CIL_PER_LOS;
                 return api_json(serde_json::json!({
                     "status": "error",
@@ -3432,18 +4759,11 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
             // 9. Mark ledger dirty for persistence
             SAVE_DIRTY.store(true, Ordering::Relaxed);
 
-            // 9b. Dynamically update aBFT validator set so new validator
-            // participates in consensus immediately (no restart required).
+            // 9b. Stage the aBFT validator set change — the new validator joins
+            // consensus once the next epoch's transition is agreed (no restart required).
             {
-                let l_guard = safe_lock(&l);
-                let mut validators: Vec<String> = l_guard
-                    .accounts
-                    .iter()
-                    .filter(|(_, a)| a.balance >= MIN_VALIDATOR_STAKE_CIL && a.is_validator)
-                    .map(|(addr, _)| addr.clone())
-                    .collect();
-                validators.sort();
-                safe_lock(&abft_inner).update_validator_set(validators);
+                let validators = staked_validators(&safe_lock(&l));
+                safe_lock(&abft_inner).schedule_validator_set(validators);
             }
 
             // 10. Broadcast to peers so they also register this validator
@@ -3467,11 +4787,11 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
             if let Some(ref onion) = onion_addr {
                 if !onion.is_empty() && onion.ends_with(".onion") {
                     insert_validator_endpoint(&mut safe_lock(&ve_inner), address.clone(), onion.clone());
-                    println!("🧅 Stored validator endpoint: {} → {}", get_short_addr(&address), onion);
+                    info!("Stored validator endpoint: {} → {}", get_short_addr(&address), onion);
                 }
             }
 
-            println!("✅ New validator registered: {} (stake: {} LOS)", get_short_addr(&address), balance / CIL_PER_LOS);
+            info!("New validator registered: {} (stake: {} LOS)", get_short_addr(&address), balance / CIL_PER_LOS);
 
             // Persist immediately
             let _ = db.save_ledger(&safe_lock(&l));
@@ -3661,17 +4981,10 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                 ve.remove(&address);
             }
 
-            // 10. Update aBFT validator set
+            // 10. Stage aBFT validator set change for the next epoch
             {
-                let l_guard = safe_lock(&l);
-                let mut validators: Vec<String> = l_guard
-                    .accounts
-                    .iter()
-                    .filter(|(_, a)| a.balance >= MIN_VALIDATOR_STAKE_CIL && a.is_validator)
-                    .map(|(addr, _)| addr.clone())
-                    .collect();
-                validators.sort();
-                safe_lock(&abft_inner).update_validator_set(validators);
+                let validators = staked_validators(&safe_lock(&l));
+                safe_lock(&abft_inner).schedule_validator_set(validators);
             }
 
             SAVE_DIRTY.store(true, Ordering::Relaxed);
@@ -3686,8 +4999,8 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
             });
             let _ = tx.send(format!("VALIDATOR_UNREG:{}", unreg_msg)).await;
 
-            println!(
-                "🔻 Validator unregistered: {} (balance: {} LOS)",
+            info!(
+                "Validator unregistered: {} (balance: {} LOS)",
                 get_short_addr(&address),
                 balance / CIL_PER_LOS
             );
@@ -3802,7 +5115,7 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
         .map(move || {
             let mut mp = safe_lock(&mp_stats);
             // Expire old transactions while we're here
-            let expired = mp.remove_expired();
+            let expired = mp.remove_expired().len();
             let stats = mp.stats();
             api_json(serde_json::json!({
                 "status": "ok",
@@ -3812,6 +5125,7 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                     "total_accepted": stats.total_accepted,
                     "total_rejected": stats.total_rejected,
                     "total_expired": stats.total_expired,
+                    "total_replaced": stats.total_replaced,
                     "unique_senders": stats.unique_senders,
                     "just_expired": expired,
                 }
@@ -3841,6 +5155,7 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
         .boxed()
         .or(balance_alias_route.boxed())
         .or(fee_estimate_route.boxed())
+        .or(fee_market_route.boxed())
         .or(block_route.boxed())
         .or(faucet_route.boxed())
         .or(reset_burn_route.boxed())
@@ -3856,8 +5171,15 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
         .or(slashing_profile_route.boxed())
         .or(block_by_hash_route.boxed())
         .or(tx_by_hash_route.boxed())
+        .or(tx_status_route.boxed())
+        .or(account_tx_status_route.boxed())
+        .or(pending_route.boxed())
+        .or(receive_route.boxed())
+        .or(log_level_route.boxed())
         .or(search_route.boxed())
         .or(sync_route.boxed())
+        .or(consensus_epochs_route.boxed())
+        .or(forks_route.boxed())
         .or(consensus_route.boxed())
         .or(reward_info_route.boxed())
         .or(register_validator_route.boxed())
@@ -3868,6 +5190,17 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
         .or(validator_api::validator_routes().boxed())
         .boxed();
 
+    // Governance & chain parameter routes
+    let group7 = governance_params_route
+        .boxed()
+        .or(chain_params_route.boxed())
+        .or(governance_proposals_route.boxed())
+        .or(governance_proposal_route.boxed())
+        .or(governance_propose_route.boxed())
+        .or(governance_vote_route.boxed())
+        .or(governance_settle_route.boxed())
+        .boxed();
+
     // Token routes (USP-01)
     let group5 = list_tokens_route
         .boxed()
@@ -3890,8 +5223,16 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
         .or(group4)
         .or(group5)
         .or(group6)
+        .or(group7)
         .with(cors) // Apply CORS
-        .with(warp::log("api"))
+        .with(warp::trace(|info| {
+            tracing::info_span!(
+                "request",
+                id = REQUEST_SEQ.fetch_add(1, Ordering::Relaxed),
+                method = %info.method(),
+                path = %info.path()
+            )
+        }))
         .recover(handle_rejection);
 
     // Apply rate limiting globally
@@ -3905,8 +5246,8 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
     } else {
         [127, 0, 0, 1] // Default: localhost only (safe for Tor hidden service)
     };
-    println!(
-        "🌍 API Server running at http://{}:{} (Rate Limit: 100 req/sec per IP)",
+    info!(
+        "API Server running at http://{}:{} (Rate Limit: 100 req/sec per IP)",
         if bind_addr == [0, 0, 0, 0] {
             "0.0.0.0"
         } else {
@@ -3973,7 +5314,7 @@ async fn handle_rejection(
             warp::http::StatusCode::METHOD_NOT_ALLOWED,
         ))
     } else {
-        eprintln!("⚠️ Unhandled rejection: {:?}", err);
+        warn!("Unhandled rejection: {:?}", err);
         let json = warp::reply::json(&serde_json::json!({
             "status": "error",
             "code": 500,
@@ -4008,8 +5349,8 @@ async fn get_crypto_prices() -> (u128, u128) {
                 // MAINNET SAFETY (W7): If proxy was configured but failed,
                 // DO NOT fall back to direct connection (would leak real IP).
                 // Return fail-closed prices (0) instead.
-                eprintln!(
-                    "🛑 Oracle SOCKS5 proxy failed ({}): {} — returning 0 (fail-closed, no IP leak)",
+                warn!(
+                    "Oracle SOCKS5 proxy failed ({}): {} — returning 0 (fail-closed, no IP leak)",
                     proxy_url, e
                 );
                 return (0, 0);
@@ -4138,10 +5479,10 @@ async fn get_crypto_prices() -> (u128, u128) {
 
     let final_eth: u128 = if eth_prices.is_empty() {
         if is_production_level {
-            println!("🛑 PRODUCTION: All ETH oracle APIs failed — rejecting (fail-closed)");
+            warn!("PRODUCTION: All ETH oracle APIs failed — rejecting (fail-closed)");
             0 // Fail-closed: returning 0 will cause burn validation to reject
         } else {
-            println!("⚠️ Oracle: No ETH prices from APIs, using testnet fallback $2500");
+            warn!("Oracle: No ETH prices from APIs, using testnet fallback $2500");
             FALLBACK_ETH_MICRO
         }
     } else {
@@ -4152,10 +5493,10 @@ async fn get_crypto_prices() -> (u128, u128) {
 
     let final_btc: u128 = if btc_prices.is_empty() {
         if is_production_level {
-            println!("🛑 PRODUCTION: All BTC oracle APIs failed — rejecting (fail-closed)");
+            warn!("PRODUCTION: All BTC oracle APIs failed — rejecting (fail-closed)");
             0 // Fail-closed
         } else {
-            println!("⚠️ Oracle: No BTC prices from APIs, using testnet fallback $83000");
+            warn!("Oracle: No BTC prices from APIs, using testnet fallback $83000");
             FALLBACK_BTC_MICRO
         }
     } else {
@@ -4172,15 +5513,15 @@ async fn get_crypto_prices() -> (u128, u128) {
 
     let final_eth = if !(ETH_MIN_MICRO..=ETH_MAX_MICRO).contains(&final_eth) {
         if is_production_level || final_eth == 0 {
-            println!(
-                "🛑 Oracle ETH price ${}.{:02} out of sanity bounds — fail-closed",
+            warn!(
+                "Oracle ETH price ${}.{:02} out of sanity bounds — fail-closed",
                 final_eth / MICRO,
                 (final_eth % MICRO) / 10_000
             );
             0
         } else {
-            println!(
-                "⚠️ Oracle ETH price ${}.{:02} out of sanity bounds, using fallback $2500",
+            warn!(
+                "Oracle ETH price ${}.{:02} out of sanity bounds, using fallback $2500",
                 final_eth / MICRO,
                 (final_eth % MICRO) / 10_000
             );
@@ -4192,15 +5533,15 @@ async fn get_crypto_prices() -> (u128, u128) {
 
     let final_btc = if !(BTC_MIN_MICRO..=BTC_MAX_MICRO).contains(&final_btc) {
         if is_production_level || final_btc == 0 {
-            println!(
-                "🛑 Oracle BTC price ${}.{:02} out of sanity bounds — fail-closed",
+            warn!(
+                "Oracle BTC price ${}.{:02} out of sanity bounds — fail-closed",
                 final_btc / MICRO,
                 (final_btc % MICRO) / 10_000
             );
             0
         } else {
-            println!(
-                "⚠️ Oracle BTC price ${}.{:02} out of sanity bounds, using fallback $83000",
+            warn!(
+                "Oracle BTC price ${}.{:02} out of sanity bounds, using fallback $83000",
                 final_btc / MICRO,
                 (final_btc % MICRO) / 10_000
             );
@@ -4211,8 +5552,8 @@ async fn get_crypto_prices() -> (u128, u128) {
     };
 
     // Show successful source count (for debugging)
-    println!(
-        "📊 Oracle Prices ({} APIs): ETH ${}.{:02}, BTC ${}.{:02}",
+    info!(
+        "Oracle Prices ({} APIs): ETH ${}.{:02}, BTC ${}.{:02}",
         eth_prices.len(),
         final_eth / MICRO,
         (final_eth % MICRO) / 10_000,
@@ -4232,8 +5573,8 @@ async fn verify_eth_burn_tx(txid: &str) -> Option<u128> {
     if testnet_config::get_testnet_config().enable_faucet {
         let clean_txid = txid.trim().trim_start_matches("0x").to_lowercase();
         if clean_txid.len() == 64 && clean_txid.chars().all(|c| c.is_ascii_hexdigit()) {
-            println!(
-                "🧪 TESTNET: Accepting ETH TXID {} with mock amount 0.01 ETH",
+            info!(
+                "TESTNET: Accepting ETH TXID {} with mock amount 0.01 ETH",
                 &clean_txid[..16]
             );
             return Some(10_000_000_000_000_000); // 0.01 ETH in wei (~30 LOS, within anti-whale limit)
@@ -4259,43 +5600,37 @@ async fn verify_eth_burn_tx(txid: &str) -> Option<u128> {
         if *use_proxy {
             if let Ok(proxy) = reqwest::Proxy::all(&proxy_url) {
                 builder = builder.proxy(proxy);
-                println!(
-                    "🌐 Oracle ETH [{}]: Using SOCKS5 proxy for blockcypher",
-                    label
-                );
+                info!("Oracle ETH [{}]: Using SOCKS5 proxy for blockcypher", label);
             }
         } else if attempts.len() > 1 {
             builder = builder.no_proxy();
-            println!(
-                "🌐 Oracle ETH [{}]: Tor failed/blocked, retrying direct HTTPS",
+            info!(
+                "Oracle ETH [{}]: Tor failed/blocked, retrying direct HTTPS",
                 label
             );
         } else {
             builder = builder.no_proxy();
-            println!(
-                "⚠️ Oracle ETH [{}]: No SOCKS5 proxy set, connecting directly",
+            warn!(
+                "Oracle ETH [{}]: No SOCKS5 proxy set, connecting directly",
                 label
             );
         }
         let client = match builder.build() {
             Ok(c) => c,
             Err(e) => {
-                println!(
-                    "❌ Oracle ETH [{}]: Failed to build HTTP client: {}",
-                    label, e
-                );
+                warn!("Oracle ETH [{}]: Failed to build HTTP client: {}", label, e);
                 continue;
             }
         };
-        println!(
-            "🌐 Oracle ETH [{}]: Verifying TXID {} via blockcypher...",
+        info!(
+            "Oracle ETH [{}]: Verifying TXID {} via blockcypher...",
             label, clean_txid
         );
         match client.get(&url).send().await {
             Ok(resp) => {
                 let status = resp.status();
-                println!(
-                    "🌐 Oracle ETH [{}]: blockcypher responded with HTTP {}",
+                info!(
+                    "Oracle ETH [{}]: blockcypher responded with HTTP {}",
                     label, status
                 );
                 if !status.is_success() {
@@ -4305,7 +5640,11 @@ async fn verify_eth_burn_tx(txid: &str) -> Option<u128> {
                     Ok(json) => {
                         if let Some(outputs) = json["outputs"].as_array() {
                             let target = BURN_ADDRESS_ETH.to_lowercase().replace("0x", "");
-                            println!("🌐 Oracle ETH [{}]: TX has {} outputs, checking for burn address...", label, outputs.len());
+                            info!(
+                                "Oracle ETH [{}]: TX has {} outputs, checking for burn address...",
+                                label,
+                                outputs.len()
+                            );
                             for (i, out) in outputs.iter().enumerate() {
                                 if let Some(addrs) = out["addresses"].as_array() {
                                     for a in addrs {
@@ -4323,32 +5662,32 @@ async fn verify_eth_burn_tx(txid: &str) -> Option<u128> {
                                                         .and_then(|s| s.parse::<u128>().ok())
                                                         .unwrap_or(0)
                                                 });
-                                            println!("✅ Oracle ETH [{}]: Found burn output #{}: {} wei to {}", label, i, wei, BURN_ADDRESS_ETH);
+                                            info!("Oracle ETH [{}]: Found burn output #{}: {} wei to {}", label, i, wei, BURN_ADDRESS_ETH);
                                             return Some(wei);
                                         }
                                     }
                                 }
                             }
-                            println!(
-                                "❌ Oracle ETH [{}]: No output found to burn address {}",
+                            warn!(
+                                "Oracle ETH [{}]: No output found to burn address {}",
                                 label, BURN_ADDRESS_ETH
                             );
                         } else {
-                            println!(
-                                "❌ Oracle ETH [{}]: No 'outputs' field in TX data. Error: {:?}",
+                            warn!(
+                                "Oracle ETH [{}]: No 'outputs' field in TX data. Error: {:?}",
                                 label,
                                 json.get("error")
                             );
                         }
                     }
                     Err(e) => {
-                        println!("❌ Oracle ETH [{}]: Failed to parse JSON: {}", label, e);
+                        warn!("Oracle ETH [{}]: Failed to parse JSON: {}", label, e);
                     }
                 }
             }
             Err(e) => {
-                println!("❌ Oracle ETH [{}]: HTTP request failed: {}", label, e);
-                println!("   ↳ Possible causes: TX not yet broadcast, Tor proxy down, or blockcypher blocks Tor exit nodes");
+                warn!("Oracle ETH [{}]: HTTP request failed: {}", label, e);
+                warn!("Possible causes: TX not yet broadcast, Tor proxy down, or blockcypher blocks Tor exit nodes");
                 continue;
             }
         }
@@ -4365,8 +5704,8 @@ async fn verify_btc_burn_tx(txid: &str) -> Option<u128> {
     if testnet_config::get_testnet_config().enable_faucet {
         let clean_txid = txid.trim().to_lowercase();
         if clean_txid.len() == 64 && clean_txid.chars().all(|c| c.is_ascii_hexdigit()) {
-            println!(
-                "🧪 TESTNET: Accepting BTC TXID {} with mock amount 0.001 BTC",
+            info!(
+                "TESTNET: Accepting BTC TXID {} with mock amount 0.001 BTC",
                 &clean_txid[..16]
             );
             return Some(100_000); // 0.001 BTC in satoshi (~69 LOS, within anti-whale limit)
@@ -4393,50 +5732,47 @@ async fn verify_btc_burn_tx(txid: &str) -> Option<u128> {
         if *use_proxy {
             if let Ok(proxy) = reqwest::Proxy::all(&proxy_url) {
                 builder = builder.proxy(proxy);
-                println!(
-                    "🌐 Oracle BTC [{}]: Using SOCKS5 proxy for mempool.space",
+                info!(
+                    "Oracle BTC [{}]: Using SOCKS5 proxy for mempool.space",
                     label
                 );
             }
         } else if attempts.len() > 1 {
             // reqwest may inherit system proxy; explicitly disable for direct fallback
             builder = builder.no_proxy();
-            println!(
-                "🌐 Oracle BTC [{}]: Tor failed/blocked, retrying direct HTTPS",
+            info!(
+                "Oracle BTC [{}]: Tor failed/blocked, retrying direct HTTPS",
                 label
             );
         } else {
             builder = builder.no_proxy();
-            println!(
-                "⚠️ Oracle BTC [{}]: No SOCKS5 proxy set, connecting directly",
+            warn!(
+                "Oracle BTC [{}]: No SOCKS5 proxy set, connecting directly",
                 label
             );
         }
         let client = match builder.build() {
             Ok(c) => c,
             Err(e) => {
-                println!(
-                    "❌ Oracle BTC [{}]: Failed to build HTTP client: {}",
-                    label, e
-                );
+                warn!("Oracle BTC [{}]: Failed to build HTTP client: {}", label, e);
                 continue;
             }
         };
-        println!(
-            "🌐 Oracle BTC [{}]: Verifying TXID {} via mempool.space...",
+        info!(
+            "Oracle BTC [{}]: Verifying TXID {} via mempool.space...",
             label, txid
         );
         match client.get(&url).send().await {
             Ok(resp) => {
                 let status = resp.status();
-                println!(
-                    "🌐 Oracle BTC [{}]: mempool.space responded with HTTP {}",
+                info!(
+                    "Oracle BTC [{}]: mempool.space responded with HTTP {}",
                     label, status
                 );
                 if !status.is_success() {
                     if let Ok(body) = resp.text().await {
-                        println!(
-                            "❌ Oracle BTC [{}]: Error body: {}",
+                        warn!(
+                            "Oracle BTC [{}]: Error body: {}",
                             label,
                             &body[..body.len().min(200)]
                         );
@@ -4448,7 +5784,7 @@ async fn verify_btc_burn_tx(txid: &str) -> Option<u128> {
                         match serde_json::from_str::<Value>(&body) {
                             Ok(json) => {
                                 if let Some(vout) = json["vout"].as_array() {
-                                    println!("🌐 Oracle BTC [{}]: TX has {} outputs, checking for burn address...", label, vout.len());
+                                    info!("Oracle BTC [{}]: TX has {} outputs, checking for burn address...", label, vout.len());
                                     for (i, out) in vout.iter().enumerate() {
                                         let out_str = out.to_string();
                                         if out_str.contains(BURN_ADDRESS_BTC) {
@@ -4464,18 +5800,18 @@ async fn verify_btc_burn_tx(txid: &str) -> Option<u128> {
                                                         .and_then(|s| s.parse::<u128>().ok())
                                                         .unwrap_or(0)
                                                 });
-                                            println!("✅ Oracle BTC [{}]: Found burn output #{}: {} satoshi to {}", label, i, satoshi, BURN_ADDRESS_BTC);
+                                            info!("Oracle BTC [{}]: Found burn output #{}: {} satoshi to {}", label, i, satoshi, BURN_ADDRESS_BTC);
                                             return Some(satoshi);
                                         }
                                     }
-                                    println!("❌ Oracle BTC [{}]: No output found to burn address {}. Outputs: {}", label, BURN_ADDRESS_BTC, &body[..body.len().min(500)]);
+                                    warn!("Oracle BTC [{}]: No output found to burn address {}. Outputs: {}", label, BURN_ADDRESS_BTC, &body[..body.len().min(500)]);
                                 } else {
-                                    println!("❌ Oracle BTC [{}]: No 'vout' field in TX data. Status field: {:?}", label, json.get("status"));
+                                    warn!("Oracle BTC [{}]: No 'vout' field in TX data. Status field: {:?}", label, json.get("status"));
                                 }
                             }
                             Err(e) => {
-                                println!(
-                                    "❌ Oracle BTC [{}]: Failed to parse JSON: {}. Body: {}",
+                                warn!(
+                                    "Oracle BTC [{}]: Failed to parse JSON: {}. Body: {}",
                                     label,
                                     e,
                                     &body[..body.len().min(200)]
@@ -4484,19 +5820,19 @@ async fn verify_btc_burn_tx(txid: &str) -> Option<u128> {
                         }
                     }
                     Err(e) => {
-                        println!(
-                            "❌ Oracle BTC [{}]: Failed to read response body: {}",
+                        warn!(
+                            "Oracle BTC [{}]: Failed to read response body: {}",
                             label, e
                         );
                     }
                 }
             }
             Err(e) => {
-                println!(
-                    "❌ Oracle BTC [{}]: HTTP request failed: {} (URL: {})",
+                warn!(
+                    "Oracle BTC [{}]: HTTP request failed: {} (URL: {})",
                     label, e, url
                 );
-                println!("   ↳ Possible causes: TX not yet broadcast, Tor proxy down, or mempool.space blocks Tor exit nodes");
+                warn!("Possible causes: TX not yet broadcast, Tor proxy down, or mempool.space blocks Tor exit nodes");
                 // Continue to next attempt (direct fallback)
                 continue;
             }
@@ -4507,6 +5843,453 @@ async fn verify_btc_burn_tx(txid: &str) -> Option<u128> {
 
 // --- UTILS & FORMATTING ---
 
+/// Staked validators as (address, stake) pairs for the aBFT validator set.
+/// Sorted by address so every node builds the same epoch.
+fn staked_validators(l: &Ledger) -> Vec<(String, u128)> {
+    let min_stake = l.min_validator_stake_cil();
+    let mut validators: Vec<(String, u128)> = l
+        .accounts
+        .iter()
+        .filter(|(_, a)| a.balance >= min_stake && a.is_validator)
+        .map(|(addr, a)| (addr.clone(), a.balance))
+        .collect();
+    validators.sort();
+    validators
+}
+
+/// Transitions sent per EPOCH_SYNC_REQ answer
+const EPOCH_SYNC_BATCH: usize = 8;
+
+/// A signature on an epoch transition counts only if it verifies and its key
+/// derives to the claimed signer
+fn verify_epoch_signature(transition: &EpochTransition, signature: &EpochSignature) -> bool {
+    let sig_bytes = hex::decode(&signature.signature).unwrap_or_default();
+    let pk_bytes = hex::decode(&signature.public_key).unwrap_or_default();
+    los_crypto::verify_signature(
+        transition.signing_payload().as_bytes(),
+        &sig_bytes,
+        &pk_bytes,
+    ) && los_crypto::public_key_to_address(&pk_bytes) == signature.signer
+}
+
+fn epoch_commit_message(commit: &EpochCommit) -> String {
+    let json = serde_json::to_string(commit).unwrap_or_default();
+    format!(
+        "EPOCH_COMMIT:{}",
+        base64::engine::general_purpose::STANDARD.encode(json.as_bytes())
+    )
+}
+
+/// Store the quorum checkpoint of an epoch a commit just opened and finalize
+/// up to its start height. The checkpoint records the agreed transition, not
+/// node-local state: block_hash is the transition digest, state_root the new
+/// epoch's seed. `validator_count` is the size of the epoch it replaced.
+/// Lock order: CM alone, then L alone.
+fn record_epoch_checkpoint(
+    checkpoint_manager: &Mutex<CheckpointManager>,
+    ledger: &Mutex<Ledger>,
+    commit: &EpochCommit,
+    seed: String,
+    validator_count: usize,
+    wal: Option<ConsensusWal>,
+) {
+    // The opened epoch is written to the WAL under the engine lock, fsync'd here
+    if let Some(Err(e)) = wal.map(|w| w.flush()) {
+        warn!("Consensus WAL flush failed: {}", e);
+    }
+    let transition = &commit.transition;
+    info!(
+        "Validator epoch {} opened at height {}: {} validators, {} signatures",
+        transition.epoch,
+        transition.start_height,
+        transition.stakes.len(),
+        commit.signatures.len()
+    );
+    let checkpoint = FinalityCheckpoint::new(
+        transition.start_height,
+        transition.digest(),
+        validator_count as u32,
+        seed,
+        commit.signatures.len() as u32,
+    );
+    if let Err(e) = safe_lock(checkpoint_manager).store_checkpoint(checkpoint) {
+        warn!("Epoch checkpoint not stored: {}", e);
+        return;
+    } // CM dropped
+    safe_lock(ledger).advance_finalized_height(transition.start_height);
+    SAVE_DIRTY.store(true, Ordering::Relaxed);
+}
+
+/// Count a (signature-verified) signature on a transition into the next
+/// epoch. Returns the EPOCH_COMMIT to gossip if it completed the quorum.
+/// Lock order: ABFT alone, then CM, then L.
+fn count_epoch_signature(
+    abft: &Mutex<ABFTConsensus>,
+    checkpoint_manager: &Mutex<CheckpointManager>,
+    ledger: &Mutex<Ledger>,
+    transition: &EpochTransition,
+    signature: EpochSignature,
+) -> Option<String> {
+    let (commit, seed, validator_count, wal) = {
+        let mut abft = safe_lock(abft);
+        let validator_count = abft.current_epoch.len();
+        let commit = match abft.add_epoch_signature(transition, signature) {
+            Ok(commit) => commit?,
+            Err(e) => {
+                warn!("Epoch signature not recorded: {}", e);
+                return None;
+            }
+        };
+        (
+            commit,
+            abft.current_epoch.seed.clone(),
+            validator_count,
+            abft.wal(),
+        )
+    }; // ABFT dropped
+    record_epoch_checkpoint(
+        checkpoint_manager,
+        ledger,
+        &commit,
+        seed,
+        validator_count,
+        wal,
+    );
+    Some(epoch_commit_message(&commit))
+}
+
+/// Open the next epoch from a peer's commit. Signatures that do not verify are
+/// dropped before the quorum is counted. Returns whether the epoch was opened.
+/// Lock order: ABFT alone, then CM, then L.
+fn apply_epoch_commit(
+    abft: &Mutex<ABFTConsensus>,
+    checkpoint_manager: &Mutex<CheckpointManager>,
+    ledger: &Mutex<Ledger>,
+    mut commit: EpochCommit,
+) -> bool {
+    let transition = commit.transition.clone();
+    commit
+        .signatures
+        .retain(|s| verify_epoch_signature(&transition, s));
+    let (seed, validator_count, wal) = {
+        let mut abft = safe_lock(abft);
+        let validator_count = abft.current_epoch.len();
+        match abft.apply_epoch_commit(&commit) {
+            Ok(true) => {}
+            Ok(false) => return false,
+            Err(e) => {
+                warn!("Epoch commit not applied: {}", e);
+                return false;
+            }
+        }
+        (abft.current_epoch.seed.clone(), validator_count, abft.wal())
+    }; // ABFT dropped
+    record_epoch_checkpoint(
+        checkpoint_manager,
+        ledger,
+        &commit,
+        seed,
+        validator_count,
+        wal,
+    );
+    true
+}
+
+/// Sign a transition into the next epoch as this validator. The signature is
+/// only sent once the lock on it is durable. Returns the EPOCH_SIGN (and the
+/// EPOCH_COMMIT if this signature completed the quorum) to gossip.
+/// Lock order: ABFT alone, then CM, then L.
+#[allow(clippy::too_many_arguments)]
+fn cast_epoch_signature(
+    abft: &Mutex<ABFTConsensus>,
+    checkpoint_manager: &Mutex<CheckpointManager>,
+    ledger: &Mutex<Ledger>,
+    transition: &EpochTransition,
+    round: u64,
+    my_address: &str,
+    secret_key: &[u8],
+    public_key: &[u8],
+) -> Vec<String> {
+    let wal = {
+        let mut abft = safe_lock(abft);
+        if let Err(e) = abft.sign_epoch_transition(transition) {
+            debug!("Not signing epoch {} transition: {}", transition.epoch, e);
+            return Vec::new();
+        }
+        abft.wal()
+    }; // ABFT dropped
+    if let Some(Err(e)) = wal.map(|w| w.flush()) {
+        warn!("Epoch signature not sent: {}", e);
+        return Vec::new();
+    }
+    let signature = match try_sign_hex(transition.signing_payload().as_bytes(), secret_key) {
+        Ok(sig) => EpochSignature {
+            signer: my_address.to_string(),
+            signature: sig,
+            public_key: hex::encode(public_key),
+        },
+        Err(e) => {
+            warn!("Epoch signature failed: {}", e);
+            return Vec::new();
+        }
+    };
+    let json = serde_json::to_string(transition).unwrap_or_default();
+    let mut msgs = vec![format!(
+        "EPOCH_SIGN:{}:{}:{}:{}:{}",
+        round,
+        base64::engine::general_purpose::STANDARD.encode(json.as_bytes()),
+        signature.signer,
+        signature.signature,
+        signature.public_key
+    )];
+    msgs.extend(count_epoch_signature(
+        abft,
+        checkpoint_manager,
+        ledger,
+        transition,
+        signature,
+    ));
+    msgs
+}
+
+/// Track a block that competes with one this ledger applied. A new candidate
+/// must carry a valid signature, PoW and key binding, otherwise anyone could
+/// open forks against arbitrary accounts. Returns None if the block is not a
+/// fork candidate, otherwise whether it opened a new fork.
+/// Lock order: L → FC.
+fn observe_fork(
+    ledger: &Mutex<Ledger>,
+    fork_choice: &Mutex<ForkChoice>,
+    metrics: &LosMetrics,
+    incoming: &Block,
+    timestamp: u64,
+) -> Option<bool> {
+    let l = safe_lock(ledger);
+    let mut fc = safe_lock(fork_choice);
+    let Some(existing) = l
+        .find_conflict(incoming)
+        .and_then(|h| l.blocks.get(&h).cloned())
+    else {
+        return fc.fork_of(&incoming.calculate_hash()).map(|_| false);
+    };
+    let pk_bytes = hex::decode(&incoming.public_key).unwrap_or_default();
+    if !(incoming.verify_signature()
+        && incoming.verify_pow()
+        && los_crypto::public_key_to_address(&pk_bytes) == incoming.account)
+    {
+        warn!(
+            "Ignoring invalid fork candidate for {}",
+            get_short_addr(&incoming.account)
+        );
+        return None;
+    }
+    match fc.observe(&existing, incoming, timestamp) {
+        Ok(opened) => {
+            if opened {
+                metrics.forks_detected_total.inc();
+                info!(
+                    "Fork detected on {}: {} vs {} (previous {})",
+                    get_short_addr(&incoming.account),
+                    &existing.calculate_hash()[..12],
+                    &incoming.calculate_hash()[..12],
+                    &incoming.previous[..incoming.previous.len().min(12)]
+                );
+            }
+            Some(opened)
+        }
+        Err(fe) => {
+            warn!("{}", fe);
+            None
+        }
+    }
+}
+
+/// CONFIRM_REQ gossip for every candidate of a fork, so peers that only hold
+/// one branch see the competing block too
+fn fork_confirm_requests(
+    fork_choice: &Mutex<ForkChoice>,
+    account: &str,
+    previous: &str,
+) -> Vec<String> {
+    let fc = safe_lock(fork_choice);
+    let Some(fork) = fc.get(&fork_key(account, previous)) else {
+        return Vec::new();
+    };
+    let ts = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    fork.candidates
+        .iter()
+        .map(|(hash, b)| {
+            let b64 = base64::engine::general_purpose::STANDARD
+                .encode(serde_json::to_vec(b).unwrap_or_default());
+            format!(
+                "CONFIRM_REQ:{}:{}:{}:{}:{}",
+                hash, b.account, b.amount, ts, b64
+            )
+        })
+        .collect()
+}
+
+/// Cast this validator's CONFIRM_RES on an account-chain fork and count it.
+/// The vote goes to the block already confirmed for this predecessor (the
+/// consensus WAL refuses any other), otherwise to the one the ledger applied.
+/// Returns the gossip to send: the CONFIRM_RES, plus the winning block if
+/// this vote completed the quorum and the ledger switched branch.
+/// Lock order: L → FC, then ABFT alone.
+#[allow(clippy::too_many_arguments)]
+fn cast_fork_vote(
+    ledger: &Mutex<Ledger>,
+    fork_choice: &Mutex<ForkChoice>,
+    abft: &Mutex<ABFTConsensus>,
+    metrics: &LosMetrics,
+    account: &str,
+    previous: &str,
+    my_address: &str,
+    secret_key: &[u8],
+    public_key: &[u8],
+) -> Vec<String> {
+    let preferred = {
+        let l = safe_lock(ledger);
+        let fc = safe_lock(fork_choice);
+        let i_am_validator = l
+            .accounts
+            .get(my_address)
+            .map(|a| a.is_validator)
+            .unwrap_or(false);
+        match fc.get(&fork_key(account, previous)) {
+            Some(fork) if i_am_validator && !fork.has_voted(my_address) => fork.preferred(&l),
+            _ => None,
+        }
+    }; // L, FC dropped
+    let Some(preferred) = preferred else {
+        return Vec::new();
+    };
+    let (hash, wal) = {
+        let mut abft = safe_lock(abft);
+        let hash = abft
+            .confirmed_block(account, previous)
+            .map(str::to_string)
+            .unwrap_or(preferred);
+        if let Err(e) = abft.vote_confirm(account, previous, &hash) {
+            warn!("Fork vote refused: {}", e);
+            return Vec::new();
+        }
+        (hash, abft.wal())
+    }; // ABFT dropped
+       // The confirmation must be durable before it is sent (fsync outside the lock)
+    if let Some(Err(e)) = wal.map(|w| w.flush()) {
+        warn!("Fork vote not sent: {}", e);
+        return Vec::new();
+    }
+
+    let ts = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+    let payload = format!("{}:{}:YES:{}:{}", hash, account, my_address, ts);
+    let sig = match try_sign_hex(payload.as_bytes(), secret_key) {
+        Ok(sig) => sig,
+        Err(e) => {
+            warn!("Fork vote signing failed: {}", e);
+            return Vec::new();
+        }
+    };
+    let mut msgs = vec![format!(
+        "CONFIRM_RES:{}:{}:YES:{}:{}:{}:{}",
+        hash,
+        account,
+        my_address,
+        ts,
+        sig,
+        hex::encode(public_key)
+    )];
+    if let Some(winner) = apply_fork_vote(
+        ledger,
+        fork_choice,
+        metrics,
+        account,
+        previous,
+        &hash,
+        my_address,
+    ) {
+        msgs.push(serde_json::to_string(&winner).unwrap_or_default());
+    }
+    msgs
+}
+
+/// Count a (signature-verified) CONFIRM_RES on a fork candidate, weighted by
+/// the voter's quadratic voting power. Only staked validators count. The fork
+/// is resolved once one candidate holds > 2/3 of total validator power, in
+/// every network mode. Returns the winning block if the ledger switched to it.
+/// Lock order: L → FC.
+fn apply_fork_vote(
+    ledger: &Mutex<Ledger>,
+    fork_choice: &Mutex<ForkChoice>,
+    metrics: &LosMetrics,
+    account: &str,
+    previous: &str,
+    hash: &str,
+    voter: &str,
+) -> Option<Block> {
+    let mut l = safe_lock(ledger);
+    let power = match l.accounts.get(voter) {
+        Some(a) if a.is_validator => calculate_voting_power(a.balance),
+        _ => 0,
+    };
+    if power == 0 {
+        return None;
+    }
+    let total_power: u128 = staked_validators(&l)
+        .iter()
+        .map(|(_, stake)| calculate_voting_power(*stake))
+        .sum();
+    let quorum_power = total_power * 2 / 3;
+
+    let mut fc = safe_lock(fork_choice);
+    if !matches!(
+        fc.record_vote(account, previous, hash, voter, power),
+        Ok(true)
+    ) {
+        return None; // Unknown fork, non-candidate, or duplicate voter
+    }
+    let key = fork_key(account, previous);
+    let winner = fc.decided(&key, quorum_power)?;
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    match fc.resolve(&mut l, &key, &winner, now) {
+        Ok(rolled_back) => {
+            metrics.forks_resolved_total.inc();
+            metrics
+                .fork_rollback_blocks_total
+                .inc_by(rolled_back.len() as u64);
+            SAVE_DIRTY.store(true, Ordering::Relaxed);
+            info!(
+                "Fork resolved for {}: winner {} ({} block(s) rolled back)",
+                get_short_addr(account),
+                &winner[..winner.len().min(12)],
+                rolled_back.len()
+            );
+            if rolled_back.is_empty() {
+                None
+            } else {
+                l.blocks.get(&winner).cloned()
+            }
+        }
+        Err(e) => {
+            warn!(
+                "Fork resolution failed for {}: {}",
+                get_short_addr(account),
+                e
+            );
+            None
+        }
+    }
+}
+
 fn get_short_addr(full_addr: &str) -> String {
     if full_addr.len() < 12 {
         return full_addr.to_string();
@@ -4610,7 +6393,7 @@ fn save_to_disk_internal(ledger: &Ledger, db: &LosDatabase, force: bool) {
     }
 
     if let Err(e) = db.save_ledger(ledger) {
-        eprintln!("❌ Database save failed: {}", e);
+        error!("Database save failed: {}", e);
         // Fallback to JSON backup
         save_to_disk_legacy(ledger);
     }
@@ -4625,11 +6408,11 @@ fn load_from_disk(db: &LosDatabase) -> Ledger {
     if !db.is_empty() {
         match db.load_ledger() {
             Ok(ledger) => {
-                println!("✅ Loaded ledger from database");
+                info!("Loaded ledger from database");
                 return ledger;
             }
             Err(e) => {
-                eprintln!("⚠️  Database load failed: {}", e);
+                warn!("Database load failed: {}", e);
             }
         }
     }
@@ -4637,13 +6420,14 @@ fn load_from_disk(db: &LosDatabase) -> Ledger {
     // One-time migration: if legacy JSON file exists, migrate to DB then remove
     if std::path::Path::new(LEDGER_FILE).exists() {
         if let Ok(data) = fs::read_to_string(LEDGER_FILE) {
-            if let Ok(ledger) = serde_json::from_str::<Ledger>(&data) {
-                println!("📦 Migrating legacy JSON to database...");
+            if let Ok(mut ledger) = serde_json::from_str::<Ledger>(&data) {
+                ledger.rebuild_indexes();
+                info!("Migrating legacy JSON to database...");
                 if let Err(e) = db.save_ledger(&ledger) {
-                    eprintln!("❌ Migration failed: {}", e);
+                    error!("Migration failed: {}", e);
                 } else {
-                    println!(
-                        "✅ Migration complete: {} accounts, {} blocks",
+                    info!(
+                        "Migration complete: {} accounts, {} blocks",
                         ledger.accounts.len(),
                         ledger.blocks.len()
                     );
@@ -4654,17 +6438,122 @@ fn load_from_disk(db: &LosDatabase) -> Ledger {
         }
     }
 
-    println!("🆕 Creating new ledger");
+    info!("Creating new ledger");
     Ledger::new()
 }
 
+/// Settlement of `req.proposal_id`: the tally the client signed, or this node's
+/// tally over the running validator epoch. The amount refunds the deposit if
+/// the tally passes. Lock order: ABFT alone, then L.
+fn settlement_tx(
+    ledger: &Mutex<Ledger>,
+    abft: &Mutex<ABFTConsensus>,
+    req: SettleRequest,
+) -> Result<GovernanceTx, String> {
+    use los_core::governance::{settle_link, GovernanceState, ProposalTally};
+    let electorate = safe_lock(abft).current_epoch.stakes(); // ABFT dropped
+    let l = safe_lock(ledger);
+    let proposal = l
+        .governance
+        .proposals
+        .get(&req.proposal_id)
+        .ok_or_else(|| format!("Proposal {} not found", req.proposal_id))?;
+    let tally = match (req.yes_power, req.no_power, req.total_power) {
+        (Some(yes), Some(no), Some(total)) => ProposalTally::new(yes, no, total)?,
+        (None, None, None) => GovernanceState::tally(proposal, &electorate),
+        _ => return Err("yes_power, no_power and total_power go together".to_string()),
+    };
+    Ok(GovernanceTx {
+        block_type: BlockType::Proposal,
+        amount: if tally.passed() {
+            proposal.deposit_cil
+        } else {
+            0
+        },
+        link: settle_link(&req.proposal_id, &tally),
+        signature: req.signature,
+        public_key: req.public_key,
+        previous: req.previous,
+        work: req.work,
+        timestamp: req.timestamp,
+        fee: req.fee,
+    })
+}
+
+/// Build a Proposal/Vote block: client-signed when `tx` carries a signature,
+/// otherwise PoW-solved and signed by the node for its own account.
+fn governance_block(
+    l: &Mutex<Ledger>,
+    sk: &[u8],
+    node_pk: &[u8],
+    my_addr: &str,
+    tx: GovernanceTx,
+) -> Result<Block, String> {
+    let client_signed = tx.signature.is_some() && tx.public_key.is_some();
+    let (account, public_key) = if client_signed {
+        let pk_hex = tx.public_key.clone().unwrap_or_default();
+        let pk_bytes =
+            hex::decode(&pk_hex).map_err(|e| format!("Invalid public_key hex: {}", e))?;
+        (los_crypto::public_key_to_address(&pk_bytes), pk_hex)
+    } else {
+        (my_addr.to_string(), hex::encode(node_pk))
+    };
+    let (head, base_fee) = {
+        let l_guard = safe_lock(l);
+        let head = l_guard.accounts.get(&account).map(|a| a.head.clone());
+        (
+            head.unwrap_or_else(|| "0".to_string()),
+            l_guard.base_fee_cil(),
+        )
+    };
+    let mut block = Block {
+        account,
+        previous: tx.previous.unwrap_or(head),
+        block_type: tx.block_type,
+        amount: tx.amount,
+        link: tx.link,
+        signature: String::new(),
+        public_key,
+        work: tx.work.unwrap_or(0),
+        timestamp: tx.timestamp.unwrap_or_else(|| {
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs()
+        }),
+        fee: tx.fee.unwrap_or(base_fee),
+        memo: None,
+    };
+    if client_signed {
+        block.signature = tx.signature.unwrap_or_default();
+    } else {
+        solve_pow(&mut block);
+        block.signature = try_sign_hex(block.signing_hash().as_bytes(), sk)
+            .map_err(|e| format!("Signing failed: {}", e))?;
+    }
+    Ok(block)
+}
+
+/// Apply the governance-pinned gas schedule (if any) and the on-chain per-call
+/// gas cap in force for a call block timestamped `time`. The schedule itself
+/// is picked from the same timestamp (`ContractCall::block_timestamp`), so
+/// every node executes the block with the same limits.
+fn sync_gas_schedule(ledger: &Ledger, engine: &WasmEngine, time: u64) {
+    let pinned = ledger
+        .governance
+        .param_at(GovParam::GasScheduleVersion, time);
+    engine.pin_gas_schedule(pinned.map(|v| v as u32));
+    engine.set_max_gas_per_call(ledger.chain_params_at(time).max_gas_per_call);
+}
+
 /// Maximum PoW iterations before giving up (safety limit)
 /// 16 zero bits should typically be found within ~200k attempts
 const MAX_POW_ITERATIONS: u64 = 10_000_000;
 
 fn solve_pow(block: &mut los_core::Block) {
-    println!(
-        "⏳ Calculating PoW (Anti-Spam: 16 zero bits, limit: {}M iterations)...",
+    info!(
+        "Calculating PoW (Anti-Spam: {} zero bits, limit: {}M iterations)...",
+        los_core::chain_params::pow_difficulty_bits(),
         MAX_POW_ITERATIONS / 1_000_000
     );
     let mut nonce: u64 = 0;
@@ -4673,7 +6562,7 @@ fn solve_pow(block: &mut los_core::Block) {
 
         // Show progress every 100k attempts
         if nonce.is_multiple_of(100_000) && nonce > 0 {
-            println!("   ... trying nonce #{}", nonce);
+            debug!("... trying nonce #{}", nonce);
         }
 
         // Use the same validation logic as process_block (16 leading zero bits)
@@ -4684,15 +6573,15 @@ fn solve_pow(block: &mut los_core::Block) {
 
         // Safety limit: prevent infinite loop on malformed blocks
         if nonce >= MAX_POW_ITERATIONS {
-            eprintln!(
-                "⚠️ PoW safety limit reached ({} iterations). Using best nonce found.",
+            warn!(
+                "PoW safety limit reached ({} iterations). Using best nonce found.",
                 MAX_POW_ITERATIONS
             );
             break;
         }
     }
     if nonce < MAX_POW_ITERATIONS {
-        println!("✅ PoW found in {} iterations", nonce);
+        info!("PoW found in {} iterations", nonce);
     }
 }
 
@@ -4709,7 +6598,7 @@ async fn solve_pow_async(mut block: los_core::Block) -> los_core::Block {
     {
         Ok(solved) => solved,
         Err(e) => {
-            eprintln!("[ERROR] PoW spawn_blocking task failed: {e}. Returning unsolved block.");
+            error!("PoW spawn_blocking task failed: {e}. Returning unsolved block.");
             fallback
         }
     }
@@ -4786,6 +6675,42 @@ fn print_history_table(blocks: Vec<&Block>) {
                 format!("-{}", amt_str),
                 format!("Contract: {}", &b.link[..16.min(b.link.len())]),
             ),
+            BlockType::Proposal if b.link.starts_with("SETTLE:") => (
+                "🏛️ SETTLE",
+                format!("+{}", amt_str),
+                format!(
+                    "Proposal: {}",
+                    b.link
+                        .trim_start_matches("SETTLE:")
+                        .chars()
+                        .take(8)
+                        .collect::<String>()
+                ),
+            ),
+            BlockType::Proposal => (
+                "🏛️ PROPOSE",
+                format!("-{}", amt_str),
+                format!(
+                    "Param: {}",
+                    b.link
+                        .trim_start_matches("PROPOSE:")
+                        .chars()
+                        .take(16)
+                        .collect::<String>()
+                ),
+            ),
+            BlockType::Vote => (
+                "🗳️ VOTE",
+                "0".to_string(),
+                format!(
+                    "Proposal: {}",
+                    b.link
+                        .trim_start_matches("VOTE:")
+                        .chars()
+                        .take(8)
+                        .collect::<String>()
+                ),
+            ),
         };
 
         let hash_short = if b.calculate_hash().len() > 8 {
@@ -4806,20 +6731,21 @@ fn print_history_table(blocks: Vec<&Block>) {
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    // Ensure panics in spawned tasks are logged to stderr
-    std::panic::set_hook(Box::new(|panic_info| {
-        eprintln!("❌ PANIC in spawned task: {}", panic_info);
-    }));
-
     // --- 1. LOGIKA PORT DINAMIS ---
     // Parse command line arguments
     let args: Vec<String> = std::env::args().collect();
 
+    // Subcommand: `los-node devnet [options]` launches a private local cluster
+    if args.get(1).map(String::as_str) == Some("devnet") {
+        return devnet::run(&args[2..]).await;
+    }
+
     // Extended CLI arguments for Flutter Validator launcher
     let mut api_port: u16 = 3030;
     let mut data_dir_override: Option<String> = None;
     let mut node_id_override: Option<String> = None;
     let mut json_log = false; // Machine-readable logs for Flutter
+    let mut log_format: Option<logging::LogFormat> = None; // --log-format text|json
     let mut mainnet_flag = false; // Runtime --mainnet flag
 
     {
@@ -4850,6 +6776,18 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 "--json-log" => {
                     json_log = true;
                 }
+                "--log-format" => {
+                    if let Some(v) = args.get(i + 1) {
+                        match v.parse() {
+                            Ok(f) => log_format = Some(f),
+                            Err(e) => {
+                                eprintln!("❌ {}", e);
+                                std::process::exit(1);
+                            }
+                        }
+                        i += 1;
+                    }
+                }
                 "--config" => {
                     // Legacy: load from validator.toml
                     if let Some(config_path) = args.get(i + 1) {
@@ -4879,19 +6817,27 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     }
 
+    logging::init(log_format);
+
+    // Ensure panics in spawned tasks are logged to stderr
+    std::panic::set_hook(Box::new(|panic_info| {
+        error!("PANIC in spawned task: {}", panic_info);
+    }));
+
     // ── MAINNET / TESTNET SAFETY GATE ──────────────────────────────────
     // Prevent accidental mismatches between binary build and runtime flag.
     if mainnet_flag && !los_core::is_mainnet_build() {
-        eprintln!(
-            "❌ FATAL: --mainnet flag passed but binary was NOT compiled with --features mainnet"
+        error!(
+            "FATAL: --mainnet flag passed but binary was NOT compiled with --features mainnet \
+             (rebuild with: cargo build --release -p los-node --features mainnet)"
         );
-        eprintln!("   Rebuild with: cargo build --release -p los-node --features mainnet");
         std::process::exit(1);
     }
     if los_core::is_mainnet_build() && !mainnet_flag {
-        eprintln!("❌ FATAL: Binary was compiled for MAINNET but --mainnet flag is missing");
-        eprintln!("   Run with: los-node --mainnet --port <PORT> ...");
-        eprintln!("   This safety check prevents accidental mainnet deployment.");
+        error!(
+            "FATAL: Binary was compiled for MAINNET but --mainnet flag is missing \
+             (run with: los-node --mainnet --port <PORT> ...)"
+        );
         std::process::exit(1);
     }
     if los_core::is_mainnet_build() {
@@ -4930,7 +6876,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     }
 
     // --- NEW: INITIALIZE DATABASE ---
-    println!("🗄️  Initializing database...");
+    info!("Initializing database...");
     // AUTO-DETECT NODE ID from override, env var, or port
     // TESTNET ONLY: Port-to-name mapping is a development convenience.
     // MAINNET: Validators are identified by their public key/address, not port.
@@ -4952,8 +6898,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Data directory: --data-dir override, or default node_data/<id>/
     let base_data_dir = data_dir_override.unwrap_or_else(|| format!("node_data/{}", node_id));
 
-    println!("🆔 Node ID: {}", node_id);
-    println!("📂 Data directory: {}/", base_data_dir);
+    info!("Node ID: {}", node_id);
+    info!("Data directory: {}/", base_data_dir);
     json_event!("init", "node_id" => &node_id, "data_dir" => &base_data_dir, "port" => api_port);
 
     // Create node-specific database path (CRITICAL: Multi-node isolation)
@@ -4963,9 +6909,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let database = match LosDatabase::open(&db_path) {
         Ok(db) => {
             let stats = db.stats();
-            println!("✅ Database opened: {}", db_path);
-            println!(
-                "   {} blocks, {} accounts, {:.2} MB on disk",
+            info!("Database opened: {}", db_path);
+            info!(
+                "{} blocks, {} accounts, {:.2} MB on disk",
                 stats.blocks_count,
                 stats.accounts_count,
                 stats.size_on_disk as f64 / 1_048_576.0
@@ -4973,11 +6919,12 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             Arc::new(db)
         }
         Err(e) => {
-            eprintln!("❌ Failed to open database at {}: {}", db_path, e);
-            eprintln!("   Possible causes:");
-            eprintln!("   1. Another los-node instance is still running with the same data-dir");
-            eprintln!("   2. A previous instance was killed and the OS hasn't released the lock");
-            eprintln!("   Fix: kill all los-node processes → pkill -9 -f los-node");
+            error!(
+                path = %db_path,
+                error = %e,
+                "Failed to open database — another los-node may still hold the data-dir lock \
+                 (fix: kill all los-node processes → pkill -9 -f los-node)"
+            );
             json_event!("fatal", "error" => "database_lock_failed", "path" => &db_path);
             return Err(e.into());
         }
@@ -4990,21 +6937,21 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         let pid = std::process::id();
         let pid_path = format!("{}/.los-node.pid", base_data_dir);
         if let Err(e) = std::fs::write(&pid_path, pid.to_string()) {
-            eprintln!("⚠️ Could not write PID lockfile: {}", e);
+            warn!("Could not write PID lockfile: {}", e);
         } else {
-            println!("🔒 PID lockfile: {} (PID {})", pid_path, pid);
+            info!("PID lockfile: {} (PID {})", pid_path, pid);
         }
     }
 
     // --- NEW: INITIALIZE METRICS ---
-    println!("📊 Initializing Prometheus metrics...");
+    info!("Initializing Prometheus metrics...");
     let metrics = match LosMetrics::new() {
         Ok(m) => {
-            println!("✅ Metrics ready: 45+ endpoints registered");
+            info!("Metrics ready: 45+ endpoints registered");
             m
         }
         Err(e) => {
-            eprintln!("❌ Failed to initialize metrics: {}", e);
+            error!("Failed to initialize metrics: {}", e);
             return Err(e);
         }
     };
@@ -5038,36 +6985,33 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // The encryption password is derived from the node ID (for automated startup).
     // MAINNET: operators MUST set LOS_WALLET_PASSWORD — weak auto-key is rejected.
     let wallet_path = format!("{}/wallet.json", &base_data_dir);
-    let wallet_password =
-        match stdin_wallet_pw.or_else(|| std::env::var("LOS_WALLET_PASSWORD").ok()) {
-            Some(pw) if pw.len() >= 12 => pw,
-            Some(pw) if !pw.is_empty() => {
-                if los_core::is_mainnet_build() {
-                    eprintln!(
-                        "❌ FATAL: LOS_WALLET_PASSWORD must be at least 12 characters on mainnet."
-                    );
-                    return Err(Box::<dyn std::error::Error>::from(
-                        "LOS_WALLET_PASSWORD too short for mainnet (min 12 chars)",
-                    ));
-                }
-                pw // Testnet: allow shorter passwords
+    let wallet_password = match stdin_wallet_pw
+        .or_else(|| std::env::var("LOS_WALLET_PASSWORD").ok())
+    {
+        Some(pw) if pw.len() >= 12 => pw,
+        Some(pw) if !pw.is_empty() => {
+            if los_core::is_mainnet_build() {
+                error!("FATAL: LOS_WALLET_PASSWORD must be at least 12 characters on mainnet.");
+                return Err(Box::<dyn std::error::Error>::from(
+                    "LOS_WALLET_PASSWORD too short for mainnet (min 12 chars)",
+                ));
             }
-            _ => {
-                if los_core::is_mainnet_build() {
-                    eprintln!(
-                    "❌ FATAL: LOS_WALLET_PASSWORD environment variable is REQUIRED on mainnet."
-                );
-                    eprintln!("   export LOS_WALLET_PASSWORD='<strong-password-here>'");
-                    return Err(Box::<dyn std::error::Error>::from(
-                        "LOS_WALLET_PASSWORD required for mainnet build",
-                    ));
-                }
-                // Testnet: auto-generate weak password (acceptable for testing)
-                let auto = format!("los-node-{}-autokey", &node_id);
-                println!("⚠️  Using auto-generated wallet password (testnet only)");
-                auto
+            pw // Testnet: allow shorter passwords
+        }
+        _ => {
+            if los_core::is_mainnet_build() {
+                error!("FATAL: LOS_WALLET_PASSWORD environment variable is REQUIRED on mainnet.");
+                error!("export LOS_WALLET_PASSWORD='<strong-password-here>'");
+                return Err(Box::<dyn std::error::Error>::from(
+                    "LOS_WALLET_PASSWORD required for mainnet build",
+                ));
             }
-        };
+            // Testnet: auto-generate weak password (acceptable for testing)
+            let auto = format!("los-node-{}-autokey", &node_id);
+            warn!("Using auto-generated wallet password (testnet only)");
+            auto
+        }
+    };
     let keys: los_crypto::KeyPair = if let Some(seed_phrase) =
         stdin_seed_phrase.or_else(|| std::env::var("LOS_SEED_PHRASE").ok())
     {
@@ -5077,9 +7021,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         let mnemonic = match bip39::Mnemonic::parse_normalized(&seed_phrase) {
             Ok(m) => m,
             Err(e) => {
-                eprintln!("FATAL: Seed phrase contains invalid BIP39 mnemonic: {e}");
-                eprintln!(
-                    "Please check the seed phrase (stdin or LOS_SEED_PHRASE env) and try again."
+                error!(
+                    "FATAL: Seed phrase contains invalid BIP39 mnemonic: {e} \
+                     (check the seed phrase passed via stdin or LOS_SEED_PHRASE)"
                 );
                 std::process::exit(1);
             }
@@ -5087,8 +7031,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         let bip39_seed = mnemonic.to_seed("");
         let kp = los_crypto::generate_keypair_from_seed(&bip39_seed);
         let derived_addr = los_crypto::public_key_to_address(&kp.public_key);
-        println!(
-            "🔑 Derived keypair from LOS_SEED_PHRASE → {}",
+        info!(
+            "Derived keypair from LOS_SEED_PHRASE → {}",
             get_short_addr(&derived_addr)
         );
         // Save/overwrite wallet.json so subsequent restarts without seed phrase still work
@@ -5116,13 +7060,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             }
         } else if let Ok(plain_key) = serde_json::from_str::<los_crypto::KeyPair>(&data) {
             // Legacy plaintext wallet — auto-migrate to encrypted
-            eprintln!("⚠️  Migrating plaintext wallet to encrypted format...");
+            warn!("Migrating plaintext wallet to encrypted format...");
             let encrypted = los_crypto::migrate_to_encrypted(&plain_key, &wallet_password)
                 .map_err(|e| {
                     Box::<dyn std::error::Error>::from(format!("Migration failed: {}", e))
                 })?;
             fs::write(&wallet_path, serde_json::to_string(&encrypted)?)?;
-            println!("🔒 Wallet migrated to encrypted storage");
+            info!("Wallet migrated to encrypted storage");
             plain_key
         } else {
             return Err(Box::from(
@@ -5136,7 +7080,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         let encrypted = los_crypto::migrate_to_encrypted(&new_k, &wallet_password)
             .map_err(|e| Box::<dyn std::error::Error>::from(format!("Encryption failed: {}", e)))?;
         fs::write(&wallet_path, serde_json::to_string(&encrypted)?)?;
-        println!("🔑 Generated new encrypted keypair for {}", node_id);
+        info!("Generated new encrypted keypair for {}", node_id);
         new_k
     };
 
@@ -5155,25 +7099,23 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             .or_else(|_| std::env::var("LOS_TOR_SOCKS5"))
             .is_ok();
         if !has_tor {
-            eprintln!(
-                "❌ FATAL: Mainnet requires Tor. Set LOS_SOCKS5_PROXY=socks5h://127.0.0.1:9050"
-            );
-            eprintln!("   Unauthority mainnet runs EXCLUSIVELY on Tor Hidden Services.");
+            error!("FATAL: Mainnet requires Tor. Set LOS_SOCKS5_PROXY=socks5h://127.0.0.1:9050");
+            error!("Unauthority mainnet runs EXCLUSIVELY on Tor Hidden Services.");
             return Err(Box::<dyn std::error::Error>::from(
                 "LOS_SOCKS5_PROXY or LOS_TOR_SOCKS5 required for mainnet build",
             ));
         }
         // R-2: Mainnet MUST NOT bind to 0.0.0.0 (IP deanonymization risk)
         if std::env::var("LOS_BIND_ALL").unwrap_or_default() == "1" {
-            eprintln!(
-                "❌ FATAL: LOS_BIND_ALL=1 is forbidden on mainnet (IP deanonymization risk)."
+            error!("FATAL: LOS_BIND_ALL=1 is forbidden on mainnet (IP deanonymization risk).");
+            error!(
+                "Mainnet validators MUST bind to 127.0.0.1 only (accessed via Tor hidden service)."
             );
-            eprintln!("   Mainnet validators MUST bind to 127.0.0.1 only (accessed via Tor hidden service).");
             return Err(Box::<dyn std::error::Error>::from(
                 "LOS_BIND_ALL=1 forbidden on mainnet — use Tor hidden service instead",
             ));
         }
-        println!("🧅 Mainnet Tor enforcement: PASSED");
+        info!("Mainnet Tor enforcement: PASSED");
     }
 
     // FIX: Load ledger and genesis BEFORE wrapping in Arc to prevent race condition
@@ -5200,25 +7142,31 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // to avoid hardcoding testnet-specific addresses that would break mainnet.
     let mut bootstrap_validators: Vec<String> = Vec::new();
     let mut genesis_ts_from_config: Option<u64> = None;
+    let mut genesis_chain_params: Option<los_core::chain_params::ChainParamsSchedule> = None;
     {
-        let genesis_path = if los_core::is_mainnet_build() {
-            "genesis_config.json"
-        } else {
-            "testnet-genesis/testnet_wallets.json"
-        };
+        let genesis_path = genesis_file_path();
 
         // MAINNET: genesis_config.json is REQUIRED — refuse to start without it
-        if los_core::is_mainnet_build() && !std::path::Path::new(genesis_path).exists() {
-            eprintln!("❌ FATAL: genesis_config.json not found!");
-            eprintln!("   Mainnet requires genesis_config.json at the working directory root.");
-            eprintln!("   Generate with: cargo run -p genesis --bin genesis");
+        if los_core::is_mainnet_build() && !std::path::Path::new(&genesis_path).exists() {
+            error!(
+                "FATAL: genesis_config.json not found in the working directory \
+                 (generate with: cargo run -p genesis --bin genesis)"
+            );
             return Err(Box::<dyn std::error::Error>::from(
                 "Missing genesis_config.json for mainnet build",
             ));
         }
 
-        if std::path::Path::new(genesis_path).exists() {
-            if let Ok(genesis_json) = std::fs::read_to_string(genesis_path) {
+        if std::path::Path::new(&genesis_path).exists() {
+            if let Ok(genesis_json) = std::fs::read_to_string(&genesis_path) {
+                // Runtime chain parameters (optional `chain_params` section, both formats)
+                genesis_chain_params = match genesis::chain_params_from_json(&genesis_json) {
+                    Ok(schedule) => schedule,
+                    Err(e) => {
+                        error!("FATAL: {}", e);
+                        return Err(Box::<dyn std::error::Error>::from(e));
+                    }
+                };
                 // Mainnet: use validated GenesisConfig parser
                 // Testnet: use the raw JSON wallets parser (legacy format)
                 if los_core::is_mainnet_build() {
@@ -5231,11 +7179,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                     format!("Failed to parse genesis JSON for validation: {}", e)
                                 })
                                 .unwrap_or_else(|e| {
-                                    eprintln!("❌ FATAL: {}", e);
+                                    error!("FATAL: {}", e);
                                     std::process::exit(1);
                                 });
                         if let Err(e) = genesis::validate_genesis(&genesis_config) {
-                            eprintln!("❌ FATAL: Genesis validation failed: {}", e);
+                            error!("FATAL: Genesis validation failed: {}", e);
                             return Err(Box::<dyn std::error::Error>::from(format!(
                                 "Genesis validation failed: {}",
                                 e
@@ -5253,17 +7201,17 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                     }
                                 }
                             }
-                            println!(
-                                "🔍 Loaded {} bootstrap validators from genesis",
+                            info!(
+                                "Loaded {} bootstrap validators from genesis",
                                 bootstrap_validators.len()
                             );
                         }
                         // Store genesis_timestamp for reward pool initialization
                         // (avoids re-reading the file and eliminates stale fallback risk)
                         genesis_ts_from_config = genesis_config.genesis_timestamp;
-                        println!("✅ Genesis config validated (supply, network, addresses)");
+                        info!("Genesis config validated (supply, network, addresses)");
                     }
-                    match genesis::load_genesis_from_file(genesis_path) {
+                    match genesis::load_genesis_from_file(&genesis_path) {
                         Ok(accounts) => {
                             let mut loaded_count = 0;
                             let mut genesis_supply_deducted: u128 = 0;
@@ -5282,14 +7230,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                 // a separate pre-genesis allocation, NOT minted from the PoB pool.
                                 // Do NOT deduct genesis wallets from remaining_supply.
                                 save_to_disk_internal(&ledger_state, &database, true);
-                                println!(
-                                    "🏦 MAINNET genesis: loaded {} accounts ({} CIL pre-allocated)",
+                                info!(
+                                    "MAINNET genesis: loaded {} accounts ({} CIL pre-allocated)",
                                     loaded_count, genesis_supply_deducted
                                 );
                             }
                         }
                         Err(e) => {
-                            eprintln!("❌ FATAL: Invalid genesis_config.json: {}", e);
+                            error!("FATAL: Invalid genesis_config.json: {}", e);
                             return Err(Box::<dyn std::error::Error>::from(format!(
                                 "Invalid genesis config: {}",
                                 e
@@ -5316,18 +7264,24 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                 {
                                     // FIX C11-C02: Validate testnet genesis wallet entries
                                     if !address.starts_with("LOS") || address.len() < 10 {
-                                        eprintln!("⚠️ Testnet genesis: skipping invalid address format: {}", address);
+                                        warn!(
+                                            "Testnet genesis: skipping invalid address format: {}",
+                                            address
+                                        );
                                         continue;
                                     }
                                     let balance_cil =
                                         genesis::parse_los_to_cil(balance_str).unwrap_or(0);
                                     if balance_cil == 0 {
-                                        eprintln!("⚠️ Testnet genesis: skipping zero/invalid balance for {}", address);
+                                        warn!(
+                                            "Testnet genesis: skipping zero/invalid balance for {}",
+                                            address
+                                        );
                                         continue;
                                     }
                                     // Sanity: no single wallet should exceed total supply
                                     if balance_cil > 21_936_236u128 * CIL_PER_LOS {
-                                        eprintln!("⚠️ Testnet genesis: skipping wallet {} (balance exceeds total supply)", address);
+                                        warn!("Testnet genesis: skipping wallet {} (balance exceeds total supply)", address);
                                         continue;
                                     }
                                     // Track validator wallets for /validators endpoint
@@ -5368,7 +7322,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                 // FIX C11-L14: Validate aggregate balance doesn't exceed total supply
                                 let max_supply_cil = 21_936_236u128 * CIL_PER_LOS;
                                 if genesis_supply_deducted > max_supply_cil {
-                                    eprintln!("❌ FATAL: Testnet genesis aggregate balance ({} CIL) exceeds total supply ({} CIL)",
+                                    error!("FATAL: Testnet genesis aggregate balance ({} CIL) exceeds total supply ({} CIL)",
                                         genesis_supply_deducted, max_supply_cil);
                                     return Err(Box::<dyn std::error::Error>::from(
                                         "Testnet genesis aggregate balance exceeds total supply",
@@ -5377,13 +7331,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                 // NOTE: remaining_supply = PUBLIC_SUPPLY_CAP already excludes
                                 // dev allocation. Genesis wallets are pre-allocated, not PoB-minted.
                                 save_to_disk_internal(&ledger_state, &database, true);
-                                println!(
-                                    "🎁 Testnet genesis: loaded {} accounts ({} CIL pre-allocated)",
+                                info!(
+                                    "Testnet genesis: loaded {} accounts ({} CIL pre-allocated)",
                                     loaded_count, genesis_supply_deducted
                                 );
                                 if !bootstrap_validators.is_empty() {
-                                    println!(
-                                        "🔍 Loaded {} bootstrap validators from testnet genesis",
+                                    info!(
+                                        "Loaded {} bootstrap validators from testnet genesis",
                                         bootstrap_validators.len()
                                     );
                                 }
@@ -5395,6 +7349,47 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     }
 
+    // ══════════════════════════════════════════════════════════════════════
+    // CHAIN PARAMETERS — Runtime economics & chain ID from genesis
+    // ══════════════════════════════════════════════════════════════════════
+    // The genesis file is authoritative: a newer file may schedule further
+    // upgrades, but must not rewrite parameters the stored chain already used.
+    // Blocks may be timestamped up to MAX_TIMESTAMP_DRIFT_SECS ahead, so any
+    // upgrade activating before that may already have been applied.
+    // Without a `chain_params` section the stored (or build default) schedule stays.
+    if let Some(schedule) = genesis_chain_params {
+        if ledger_state.total_blocks() > 0 {
+            let applied_until = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs()
+                + los_core::MAX_TIMESTAMP_DRIFT_SECS;
+            if let Err(e) = ledger_state
+                .chain_params
+                .ensure_extends(&schedule, applied_until)
+            {
+                error!(
+                    "FATAL: Genesis chain_params incompatible with database: {}",
+                    e
+                );
+                return Err(Box::<dyn std::error::Error>::from(e));
+            }
+        }
+        ledger_state.chain_params = schedule;
+    }
+    {
+        let genesis_params = &ledger_state.chain_params.genesis;
+        los_core::chain_params::set_network_params(genesis_params);
+        let in_force = ledger_state.chain_params();
+        info!(
+            "Chain ID {} — chain params v{} ({} scheduled upgrades, base fee {} CIL)",
+            genesis_params.chain_id,
+            in_force.version,
+            ledger_state.chain_params.upgrades.len(),
+            in_force.base_fee_cil
+        );
+    }
+
     // ══════════════════════════════════════════════════════════════════════
     // VALIDATOR REWARD POOL — Initialize and register known validators
     // ══════════════════════════════════════════════════════════════════════
@@ -5404,9 +7399,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let genesis_ts: u64 = if los_core::is_mainnet_build() {
         // Use timestamp stored during genesis validation above (no redundant file I/O)
         genesis_ts_from_config.unwrap_or_else(|| {
-            eprintln!(
-                "❌ FATAL: genesis_timestamp missing after validation — this should never happen"
-            );
+            error!("FATAL: genesis_timestamp missing after validation — this should never happen");
             std::process::exit(1);
         })
     } else {
@@ -5418,6 +7411,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             .as_secs()
     };
     let mut reward_pool_state = ValidatorRewardPool::new(genesis_ts);
+    reward_pool_state.apply_params(&ledger_state.chain_params());
 
     // Register all bootstrap validators as genesis
     // (eligible for rewards — same rules as any validator)
@@ -5447,8 +7441,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             .unwrap_or(0);
         let is_bootstrap = bootstrap_validators.contains(&my_address);
         reward_pool_state.register_validator(&my_address, is_bootstrap, my_stake);
-        println!(
-            "📡 Registered node address {} in reward pool",
+        info!(
+            "Registered node address {} in reward pool",
             &my_address[..12.min(my_address.len())]
         );
     }
@@ -5460,8 +7454,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .as_secs();
     let skipped = reward_pool_state.catch_up_epochs(now_secs);
     if skipped > 0 {
-        println!(
-            "⏩ Skipped {} missed epochs (fast-forward to current time)",
+        info!(
+            "Skipped {} missed epochs (fast-forward to current time)",
             skipped
         );
     }
@@ -5471,10 +7465,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     reward_pool_state.set_expected_heartbeats(initial_heartbeat_secs);
 
     let reward_pool = Arc::new(Mutex::new(reward_pool_state));
-    println!(
-        "🏆 Validator reward pool initialized: {} LOS, epoch rate {} LOS/month",
+    info!(
+        "Validator reward pool initialized: {} LOS, epoch rate {} LOS/epoch",
         los_core::VALIDATOR_REWARD_POOL_CIL / CIL_PER_LOS,
-        los_core::REWARD_RATE_INITIAL_CIL / CIL_PER_LOS
+        safe_lock(&reward_pool).reward_rate_initial_cil / CIL_PER_LOS
     );
 
     // Now wrap in Arc after all initialization is complete
@@ -5484,12 +7478,12 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let initial_peers = match database.load_peers() {
         Ok(peers) => {
             if !peers.is_empty() {
-                println!("📚 Loaded {} known peers from database", peers.len());
+                info!("Loaded {} known peers from database", peers.len());
             }
             peers
         }
         Err(e) => {
-            eprintln!("⚠️ Failed to load peers: {}", e);
+            warn!("Failed to load peers: {}", e);
             HashMap::new()
         }
     };
@@ -5517,13 +7511,72 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let pending_sends = Arc::new(Mutex::new(HashMap::<String, (Block, u128)>::new()));
 
     // Mempool: tracks pending transactions with priority ordering and expiration.
-    // Runs alongside pending_sends (shadow mode) to provide stats and future block assembly.
+    // Persisted to sled so sends awaiting CONFIRM_RES quorum survive a restart;
+    // restored entries are revalidated against the loaded ledger and re-enter pending_sends.
     let mempool_pool = Arc::new(Mutex::new(mempool::Mempool::new()));
+    // Remembers received and rejected hashes for the transaction status API
+    let tx_tracker = Arc::new(Mutex::new(tx_status::TxStatusTracker::new()));
+    match database.load_mempool() {
+        Ok(persisted) if !persisted.is_empty() => {
+            let total = persisted.len();
+            // Delete every persisted entry, then re-store the ones admitted below
+            let mut changes: Vec<(String, Option<mempool::MempoolTransaction>)> = persisted
+                .iter()
+                .map(|tx| (tx.block.calculate_hash(), None))
+                .collect();
+            // A send queued behind another restored send is kept if its predecessor is
+            let mut remaining: Vec<mempool::MempoolTransaction> = persisted
+                .into_iter()
+                .filter(|tx| tx.block.verify_signature() && tx.block.verify_pow())
+                .collect();
+            let mut valid: Vec<mempool::MempoolTransaction> = Vec::new();
+            loop {
+                let (ready, rest): (Vec<_>, Vec<_>) = remaining.into_iter().partition(|tx| {
+                    valid.iter().any(|v| {
+                        v.block.account == tx.block.account
+                            && v.block.calculate_hash() == tx.block.previous
+                    }) || pending_send_check(&ledger, &tx.block).is_ok()
+                });
+                remaining = rest;
+                if ready.is_empty() {
+                    break;
+                }
+                valid.extend(ready);
+            }
+            let mut restored = Vec::new();
+            {
+                let mut mp = safe_lock(&mempool_pool);
+                for tx in valid {
+                    let blk = tx.block.clone();
+                    if let Ok(hash) = mp.restore(tx) {
+                        restored.push((hash, blk));
+                    }
+                }
+                changes.extend(mp.take_changes());
+            } // MP dropped
+            if let Err(e) = database.apply_mempool_changes(&changes) {
+                warn!("Failed to save mempool: {}", e);
+            }
+            info!(
+                "Restored {} of {} persisted pending transactions",
+                restored.len(),
+                total
+            );
+            let mut ps = safe_lock(&pending_sends);
+            for (hash, blk) in restored {
+                ps.insert(hash, (blk, 0));
+            }
+        }
+        Ok(_) => {}
+        Err(e) => warn!("Failed to load mempool: {}", e),
+    }
 
     // SECURITY FIX: Vote deduplication — track which validators have already voted
     // Prevents a single validator from reaching consensus alone by sending multiple votes
     let burn_voters = Arc::new(Mutex::new(HashMap::<String, HashSet<String>>::new()));
     let send_voters = Arc::new(Mutex::new(HashMap::<String, HashSet<String>>::new()));
+    // Competing account-chain blocks awaiting stake-weighted resolution
+    let fork_choice = Arc::new(Mutex::new(ForkChoice::new()));
 
     // ══════════════════════════════════════════════════════════════════════
     // VALIDATOR ENDPOINTS — Maps validator_address → onion_address
@@ -5539,7 +7592,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     if let Ok(our_onion) = std::env::var("LOS_ONION_ADDRESS") {
         if !our_onion.is_empty() {
             initial_endpoints.insert(my_address.clone(), our_onion.clone());
-            println!("🧅 Registered own onion endpoint: {}", our_onion);
+            info!("Registered own onion endpoint: {}", our_onion);
         }
     }
     // Seed from genesis validator onion addresses (collected during genesis loading)
@@ -5549,8 +7602,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             .or_insert_with(|| onion.clone());
     }
     if !genesis_onion_map.is_empty() {
-        println!(
-            "🧅 Seeded {} genesis validator endpoints for discovery",
+        info!(
+            "Seeded {} genesis validator endpoints for discovery",
             genesis_onion_map.len()
         );
     }
@@ -5572,8 +7625,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
         let registered = sm.get_safety_stats().total_validators;
         if registered > 0 {
-            println!(
-                "🛡️  SlashingManager: {} validators registered from genesis",
+            info!(
+                "SlashingManager: {} validators registered from genesis",
                 registered
             );
         }
@@ -5584,12 +7637,26 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let anti_whale = Arc::new(Mutex::new(AntiWhaleEngine::new(anti_whale_config)));
     {
         let aw = safe_lock(&anti_whale);
-        println!(
-            "🐋 Anti-Whale Engine initialized (max {} tx/block, max {} LOS burn/block)",
+        info!(
+            "Anti-Whale Engine initialized (max {} tx/block, max {} LOS burn/block)",
             aw.config.max_tx_per_block, aw.config.max_burn_per_block
         );
     }
 
+    // History mode: archive nodes keep every block, pruned nodes drop finalized
+    // history once a checkpoint is past (see los_core::pruning)
+    let pruning = pruning_config_from_env();
+    let archive_peers = archive_peers_from_env();
+    if pruning.is_pruned() {
+        info!(
+            "History mode: pruned (keeping {} recent blocks per account, {} archive peer(s))",
+            pruning.keep_recent,
+            archive_peers.len()
+        );
+    } else {
+        info!("History mode: archive (full block history)");
+    }
+
     // NEW: Finality Checkpoint Manager (prevents long-range attacks)
     // CRITICAL FIX: Use --data-dir path, NOT hardcoded node_data/{node_id}/.
     // The old path was shared across all flutter-validator instances regardless
@@ -5599,30 +7666,30 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         Ok(cm) => {
             let latest = cm.get_latest_checkpoint().ok().flatten();
             if let Some(cp) = &latest {
-                println!(
-                    "🏁 CheckpointManager: resuming from checkpoint at height {}",
+                info!(
+                    "CheckpointManager: resuming from checkpoint at height {}",
                     cp.height
                 );
             } else {
-                println!(
-                    "🏁 CheckpointManager: no checkpoints yet (will create every {} blocks)",
+                info!(
+                    "CheckpointManager: no checkpoints yet (will create every {} blocks)",
                     CHECKPOINT_INTERVAL
                 );
             }
             Arc::new(Mutex::new(cm))
         }
         Err(e) => {
-            eprintln!("⚠️ Failed to open checkpoint DB: {} — trying fallback", e);
+            warn!("Failed to open checkpoint DB: {} — trying fallback", e);
             // Fallback: temp directory that's guaranteed to have no stale locks
             let fallback_path = format!("{}/checkpoints_fallback", base_data_dir);
             match CheckpointManager::new(&fallback_path) {
                 Ok(cm) => Arc::new(Mutex::new(cm)),
                 Err(e2) => {
-                    eprintln!(
-                        "FATAL: Both checkpoint DBs failed: {} — node cannot start safely",
+                    error!(
+                        "FATAL: Both checkpoint DBs failed: {} — node cannot start safely \
+                         (fix: kill all los-node processes → pkill -9 -f los-node)",
                         e2
                     );
-                    eprintln!("   Fix: kill all los-node processes → pkill -9 -f los-node");
                     json_event!("fatal", "error" => "checkpoint_db_lock_failed", "path" => &checkpoint_db_path);
                     return Err(Box::<dyn std::error::Error>::from(e2.to_string()));
                 }
@@ -5667,6 +7734,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                         .unwrap_or_default()
                         .as_secs(),
                     fee: 0,
+                    memo: None,
                 };
 
                 solve_pow(&mut init_block);
@@ -5674,7 +7742,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     match try_sign_hex(init_block.signing_hash().as_bytes(), &secret_key) {
                         Ok(sig) => sig,
                         Err(e) => {
-                            eprintln!(
+                            error!(
                                 "FATAL: Cannot sign init block: {} — node cannot start safely",
                                 e
                             );
@@ -5685,11 +7753,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 match l.process_block(&init_block) {
                     Ok(_) => {
                         SAVE_DIRTY.store(true, Ordering::Relaxed);
-                        println!("🎁 TESTNET (Functional): Node initialized with 1000 LOS via Mint block (supply deducted)");
+                        info!("TESTNET (Functional): Node initialized with 1000 LOS via Mint block (supply deducted)");
                     }
                     Err(e) => {
-                        println!(
-                            "⚠️ TESTNET initial mint failed: {} — creating empty account",
+                        warn!(
+                            "TESTNET initial mint failed: {} — creating empty account",
                             e
                         );
                     }
@@ -5709,6 +7777,65 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     }
 
+    // Create aBFT Consensus Engine in main() so it's shared with both API server and event loop
+    let abft_consensus = {
+        let (validator_count, validators) = {
+            let l = safe_lock(&ledger);
+            let min_stake = l.min_validator_stake_cil();
+            let count = l
+                .accounts
+                .iter()
+                .filter(|(_, a)| a.balance >= min_stake)
+                .count()
+                .max(4);
+            (count, staked_validators(&l))
+        };
+        // Crash recovery: replay own votes + view from the WAL so a restarted
+        // validator never signs a conflicting vote for the same sequence.
+        let mut abft = match database.consensus_wal_tree().and_then(|tree| {
+            ABFTConsensus::with_wal(my_address.clone(), validator_count, ConsensusWal::new(tree))
+        }) {
+            Ok(engine) => {
+                info!(
+                    "aBFT WAL replayed: view={}, sequence={}, finalized={}",
+                    engine.view, engine.sequence, engine.finalized_sequence
+                );
+                engine
+            }
+            Err(e) => {
+                error!("FATAL: aBFT consensus WAL unavailable: {}", e);
+                return Err(Box::<dyn std::error::Error>::from(e));
+            }
+        };
+        // Resume the epoch restored from the WAL exactly (set and seed); only a node
+        // without one opens the genesis epoch from its ledger. The ledger's current
+        // set is only staged: it takes effect once a quorum of the running epoch
+        // signs it as the next epoch's transition (EPOCH_SIGN).
+        match abft.bootstrap_epoch(validators.clone()) {
+            Ok(true) => info!("Opened genesis validator epoch from the ledger"),
+            Ok(false) => {}
+            Err(e) => {
+                error!("FATAL: validator epoch not persisted: {}", e);
+                return Err(Box::<dyn std::error::Error>::from(e));
+            }
+        }
+        abft.schedule_validator_set(validators);
+        if let Some(cp) = safe_lock(&checkpoint_manager)
+            .get_latest_checkpoint()
+            .ok()
+            .flatten()
+        {
+            safe_lock(&ledger).advance_finalized_height(cp.height);
+        }
+        info!(
+            "Validator epoch {}: {} validators, total voting power {}",
+            abft.current_epoch.epoch,
+            abft.current_epoch.len(),
+            abft.current_epoch.total_voting_power
+        );
+        Arc::new(Mutex::new(abft))
+    };
+
     // FIX: Background task for debounced disk saves (prevents race conditions)
     // SECURITY FIX #15: Clone ledger snapshot THEN release lock BEFORE disk I/O
     let save_ledger = Arc::clone(&ledger);
@@ -5716,86 +7843,47 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let save_checkpoint_mgr = Arc::clone(&checkpoint_manager);
     tokio::spawn(async move {
         let mut interval = tokio::time::interval(Duration::from_secs(5));
+        let mut last_checkpoint = safe_lock(&save_checkpoint_mgr)
+            .get_latest_checkpoint()
+            .ok()
+            .flatten();
         loop {
             interval.tick().await;
 
             // Only save if dirty and not currently saving
             if SAVE_DIRTY.load(Ordering::Relaxed) && !SAVE_IN_PROGRESS.load(Ordering::Relaxed) {
                 // Clone ledger under lock, then release lock BEFORE disk I/O
-                let (ledger_snapshot, block_count, validator_count) = {
-                    let l = safe_lock(&save_ledger);
-                    let bc = l.blocks.len() as u64;
-                    let vc = l
-                        .accounts
-                        .iter()
-                        .filter(|(_, a)| a.balance >= MIN_VALIDATOR_STAKE_CIL)
-                        .count() as u32;
-                    (l.clone(), bc, vc)
-                }; // Lock released — API requests can proceed during save
+                let ledger_snapshot = safe_lock(&save_ledger).clone(); // Lock released — API requests can proceed during save
                 save_to_disk_internal(&ledger_snapshot, &save_database, false);
 
-                // CHECKPOINT: Create finality checkpoint when block_count crosses next interval
-                // FIX: Use >= instead of == to handle block-lattice where exact multiples may be skipped
-                if block_count > 0 {
-                    let mut cm = safe_lock(&save_checkpoint_mgr);
-                    let latest_height = cm
-                        .get_latest_checkpoint()
-                        .ok()
-                        .flatten()
-                        .map(|cp| cp.height)
-                        .unwrap_or(0);
-                    let next_checkpoint =
-                        ((latest_height / CHECKPOINT_INTERVAL) + 1) * CHECKPOINT_INTERVAL;
-
-                    if block_count >= next_checkpoint {
-                        // FIX P0-3: Snap block_count DOWN to aligned interval.
-                        // In a block-lattice, block_count rarely lands exactly on a
-                        // multiple of CHECKPOINT_INTERVAL. Without snapping, every
-                        // checkpoint was silently rejected by is_valid_interval().
-                        let checkpoint_height =
-                            (block_count / CHECKPOINT_INTERVAL) * CHECKPOINT_INTERVAL;
-
-                        // Calculate simple state root from account balances
-                        let state_root = {
-                            use sha3::{Digest, Keccak256};
-                            let mut hasher = Keccak256::new();
-                            let mut sorted_accounts: Vec<_> =
-                                ledger_snapshot.accounts.iter().collect();
-                            sorted_accounts.sort_by(|(a, _), (b, _)| a.cmp(b));
-                            for (addr, state) in sorted_accounts {
-                                hasher.update(addr.as_bytes());
-                                hasher.update(state.balance.to_le_bytes());
-                            }
-                            hex::encode(hasher.finalize())
-                        };
-
-                        // Find latest block hash
-                        let latest_block_hash = ledger_snapshot
-                            .blocks
-                            .values()
-                            .max_by_key(|b| b.timestamp)
-                            .map(|b| b.calculate_hash())
-                            .unwrap_or_else(|| "genesis".to_string());
-
-                        // SECURITY FIX S2: sig_count = 1 (only this node signed).
-                        // Previously set to validator_count, falsely claiming full consensus.
-                        // Multi-validator checkpoint coordination requires a separate protocol
-                        // (future: CHECKPOINT_REQ/CHECKPOINT_RES gossip).
-                        // For now, honestly report that only 1 validator signed.
-                        let sig_count = 1_u32;
-                        let checkpoint = FinalityCheckpoint::new(
-                            checkpoint_height,
-                            latest_block_hash,
-                            validator_count.max(1),
-                            state_root,
-                            sig_count,
-                        );
+                // PRUNING: checkpoints are stored when a quorum opens an epoch
+                // (record_epoch_checkpoint). Once a new one lands, history before the
+                // PREVIOUS checkpoint is final — one full interval of slack stays on disk.
+                let latest_checkpoint = safe_lock(&save_checkpoint_mgr)
+                    .get_latest_checkpoint()
+                    .ok()
+                    .flatten(); // CM dropped
+                let mut prune_horizon = None;
+                if latest_checkpoint.as_ref().map(|cp| cp.height)
+                    != last_checkpoint.as_ref().map(|cp| cp.height)
+                {
+                    prune_horizon = last_checkpoint.map(|cp| cp.timestamp);
+                    last_checkpoint = latest_checkpoint;
+                }
 
-                        match cm.store_checkpoint(checkpoint) {
-                            Ok(()) => println!("🏁 Checkpoint created at height {} (block_count={}, {} validators, sig_count=1/{})",
-                                checkpoint_height, block_count, validator_count, validator_count),
-                            Err(e) => eprintln!("⚠️ Checkpoint creation failed: {}", e),
+                if let (true, Some(horizon)) = (pruning.is_pruned(), prune_horizon) {
+                    let report = safe_lock(&save_ledger).prune(horizon, pruning.keep_recent);
+                    if !report.pruned.is_empty() {
+                        match save_database.remove_blocks(&report.pruned) {
+                            Ok(()) => info!(
+                                "Pruned {} blocks older than checkpoint horizon {} ({} claimed sends released)",
+                                report.pruned.len(),
+                                horizon,
+                                report.claimed_sends_dropped
+                            ),
+                            Err(e) => warn!("Failed to delete pruned blocks: {}", e),
                         }
+                        SAVE_DIRTY.store(true, Ordering::Relaxed);
                     }
                 }
             }
@@ -5803,8 +7891,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     });
 
     // FIX V4#15: Periodic cleanup of stale pending transactions
-    // Pending sends/burns older than 5 minutes are removed to prevent memory leaks
-    let cleanup_pending_sends = Arc::clone(&pending_sends);
+    // Pending burns older than 5 minutes are removed to prevent memory leaks.
+    // Pending sends live until they confirm or the mempool expires them (see rebroadcast task).
     let cleanup_pending_burns = Arc::clone(&pending_burns);
     tokio::spawn(async move {
         let mut interval = tokio::time::interval(Duration::from_secs(60));
@@ -5814,20 +7902,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 .duration_since(std::time::UNIX_EPOCH)
                 .unwrap_or_default()
                 .as_secs();
-            const PENDING_TTL_SECS: u64 = 300; // 5 minute TTL for pending transactions
-
-            // Clean stale pending sends
-            if let Ok(mut ps) = cleanup_pending_sends.lock() {
-                let before = ps.len();
-                ps.retain(|_, (block, _)| now.saturating_sub(block.timestamp) < PENDING_TTL_SECS);
-                let removed = before - ps.len();
-                if removed > 0 {
-                    println!(
-                        "🧹 Cleaned {} stale pending sends (TTL: {}s)",
-                        removed, PENDING_TTL_SECS
-                    );
-                }
-            }
+            const PENDING_TTL_SECS: u64 = 300; // 5 minute TTL for pending burns
 
             // Clean stale pending burns by timestamp-based TTL
             // pending_burns: HashMap<txid, (u128_amt_base, u128_price_micro, String_sym, u128_power, u64_created_at, String_recipient)>
@@ -5838,8 +7913,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 });
                 let removed = before - pb.len();
                 if removed > 0 {
-                    println!(
-                        "🧹 Cleaned {} stale pending burns (TTL: {}s)",
+                    info!(
+                        "Cleaned {} stale pending burns (TTL: {}s)",
                         removed, PENDING_TTL_SECS
                     );
                 }
@@ -5847,13 +7922,30 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     });
 
+    // Mempool persistence: flush the change journal to sled every 2 seconds
+    let mp_persist = Arc::clone(&mempool_pool);
+    let db_persist = Arc::clone(&database);
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(2));
+        loop {
+            interval.tick().await;
+            let changes = safe_lock(&mp_persist).take_changes();
+            if changes.is_empty() {
+                continue;
+            }
+            if let Err(e) = db_persist.apply_mempool_changes(&changes) {
+                warn!("Failed to save mempool: {}", e);
+            }
+        }
+    });
+
     // Dynamic P2P port: API port + 1000 (e.g. 3030→4030, 3031→4031)
     // Can still be overridden via LOS_P2P_PORT env var.
     if std::env::var("LOS_P2P_PORT").is_err() {
         let p2p_port = api_port + 1000;
         std::env::set_var("LOS_P2P_PORT", p2p_port.to_string());
-        println!(
-            "📡 P2P port auto-derived: {} (API {} + 1000)",
+        info!(
+            "P2P port auto-derived: {} (API {} + 1000)",
             p2p_port, api_port
         );
     }
@@ -5868,7 +7960,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Otherwise, try to create one via the Tor control port protocol.
     // The generated .onion key is persisted in data_dir for stable address
     // across restarts.
-    if std::env::var("LOS_ONION_ADDRESS").is_err() {
+    // LOS_LOCAL_ONLY=1 (devnets) never touches Tor.
+    let local_only = std::env::var("LOS_LOCAL_ONLY").unwrap_or_default() == "1";
+    if std::env::var("LOS_ONION_ADDRESS").is_err() && !local_only {
         let p2p_port: u16 = std::env::var("LOS_P2P_PORT")
             .ok()
             .and_then(|s| s.parse().ok())
@@ -5886,7 +7980,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 Ok(hs) => {
                     // Set env var so TorConfig::from_env() picks it up in LosNode::start()
                     std::env::set_var("LOS_ONION_ADDRESS", &hs.onion_address);
-                    println!("🧅 Auto-generated Tor hidden service: {}", hs.onion_address);
+                    info!("Auto-generated Tor hidden service: {}", hs.onion_address);
 
                     // Register in validator_endpoints for peer discovery
                     if let Ok(mut endpoints) = validator_endpoints.lock() {
@@ -5894,23 +7988,23 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     }
                 }
                 Err(e) => {
-                    eprintln!("⚠️ Tor auto-generation failed: {}", e);
-                    eprintln!(
-                        "   Node will continue without .onion address. \
+                    warn!("Tor auto-generation failed: {}", e);
+                    warn!(
+                        "Node will continue without .onion address. \
                          Set LOS_ONION_ADDRESS manually or configure Tor ControlPort."
                     );
                 }
             }
         } else {
-            println!(
-                "🧅 Tor control port not available at {} — skipping auto-generation",
+            info!(
+                "Tor control port not available at {} — skipping auto-generation",
                 tor_config.control_addr
             );
-            println!("   To enable: configure Tor with ControlPort 9051 + CookieAuthentication 1");
+            info!("To enable: configure Tor with ControlPort 9051 + CookieAuthentication 1");
         }
     } else {
-        println!(
-            "🧅 Using manually configured onion address: {}",
+        info!(
+            "Using manually configured onion address: {}",
             std::env::var("LOS_ONION_ADDRESS").unwrap_or_default()
         );
     }
@@ -5920,11 +8014,258 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     tokio::spawn(async move {
         match LosNode::start(tx_in, rx_out).await {
-            Ok(()) => eprintln!("⚠️ P2P network task exited normally (unexpected)"),
-            Err(e) => eprintln!("❌ P2P network task failed: {}", e),
+            Ok(()) => warn!("P2P network task exited normally (unexpected)"),
+            Err(e) => error!("P2P network task failed: {}", e),
         }
     });
 
+    // Pending send rebroadcast: re-request CONFIRM_RES quorum for unconfirmed sends
+    // every 30 seconds, until they finalize or the mempool expires them.
+    // Sends that can no longer finalize (confirmed elsewhere, head moved, unfunded) are dropped.
+    {
+        let rb_ledger = Arc::clone(&ledger);
+        let rb_pending = Arc::clone(&pending_sends);
+        let rb_mempool = Arc::clone(&mempool_pool);
+        let rb_tracker = Arc::clone(&tx_tracker);
+        let rb_abft = Arc::clone(&abft_consensus);
+        let rb_tx = tx_out.clone();
+        tokio::spawn(async move {
+            const REBROADCAST_INTERVAL_SECS: u64 = 30;
+            const REBROADCAST_BATCH: usize = 100;
+            let mut interval =
+                tokio::time::interval(Duration::from_secs(REBROADCAST_INTERVAL_SECS));
+            interval.tick().await; // Skip the immediate first tick
+            loop {
+                interval.tick().await;
+
+                // Step 1: Snapshot pending hashes (PS lock only). /send admits to the
+                // mempool before pending_sends, so any of these missing from the mempool
+                // below was finalized, replaced or expired.
+                let pending_accounts: HashMap<String, String> = safe_lock(&rb_pending)
+                    .iter()
+                    .map(|(h, (b, _))| (h.clone(), b.account.clone()))
+                    .collect();
+
+                // Step 2: Expire and snapshot mempool sends (MP lock only)
+                let (expired, candidates, mut dropped): (
+                    Vec<String>,
+                    Vec<(String, Block)>,
+                    Vec<String>,
+                ) = {
+                    let mut mp = safe_lock(&rb_mempool);
+                    let expired = mp.remove_expired();
+                    let candidates = mp
+                        .get_next_transactions(mp.len())
+                        .into_iter()
+                        .filter_map(|h| mp.get_transaction(&h).map(|tx| (h, tx.block.clone())))
+                        .collect();
+                    let orphans = pending_accounts
+                        .keys()
+                        .filter(|h| !mp.contains(h))
+                        .cloned()
+                        .collect();
+                    (expired, candidates, orphans)
+                }; // MP dropped
+
+                // Step 3: Revalidate against the ledger (L lock only, per block).
+                // Sends queued behind another pending send of the account wait for it.
+                let pending_in_pool: HashSet<(String, String)> = candidates
+                    .iter()
+                    .map(|(h, b)| (b.account.clone(), h.clone()))
+                    .collect();
+                let mut live = Vec::new();
+                let mut stale = Vec::new();
+                for (hash, blk) in candidates {
+                    if pending_in_pool.contains(&(blk.account.clone(), blk.previous.clone())) {
+                        continue;
+                    }
+                    match pending_send_check(&rb_ledger, &blk) {
+                        Ok(()) => live.push((hash, blk)),
+                        Err(reason) => stale.push((hash, blk.account, reason)),
+                    }
+                }
+                if !stale.is_empty() {
+                    let mut mp = safe_lock(&rb_mempool);
+                    for (hash, _, _) in &stale {
+                        mp.remove_transaction(hash);
+                    }
+                } // MP dropped
+                if !expired.is_empty() || !stale.is_empty() {
+                    info!(
+                        "Dropped {} expired and {} stale pending sends",
+                        expired.len(),
+                        stale.len()
+                    );
+                    // Remember why (TT lock only)
+                    let mut tt = safe_lock(&rb_tracker);
+                    for hash in &expired {
+                        let account = pending_accounts.get(hash).map_or("", |a| a.as_str());
+                        tt.rejected(hash, account, "Expired: not confirmed within 24 hours");
+                    }
+                    for (hash, account, reason) in &stale {
+                        tt.rejected(hash, account, reason.as_str());
+                    }
+                } // TT dropped
+                dropped.extend(stale.into_iter().map(|(h, _, _)| h));
+
+                // Step 4: Drop dead entries and pick sends still awaiting quorum (PS lock only)
+                let resend: Vec<(String, Block)> = {
+                    let mut ps = safe_lock(&rb_pending);
+                    for hash in &dropped {
+                        ps.remove(hash);
+                    }
+                    live.into_iter()
+                        .filter(|(h, _)| ps.contains_key(h))
+                        .take(REBROADCAST_BATCH)
+                        .collect()
+                }; // PS dropped
+
+                for (hash, blk) in &resend {
+                    let ts = std::time::SystemTime::now()
+                        .duration_since(std::time::UNIX_EPOCH)
+                        .unwrap_or_default()
+                        .as_millis();
+                    let block_json = serde_json::to_string(blk).unwrap_or_default();
+                    let block_b64 =
+                        base64::engine::general_purpose::STANDARD.encode(block_json.as_bytes());
+                    let _ = rb_tx
+                        .send(format!(
+                            "CONFIRM_REQ:{}:{}:{}:{}:{}",
+                            hash, blk.account, blk.amount, ts, block_b64
+                        ))
+                        .await;
+                }
+                if !resend.is_empty() {
+                    info!("Rebroadcast {} pending sends", resend.len());
+                }
+
+                // Step 5: Release confirmations that expired with their mempool entry,
+                // and those for slots the ledger moved past or does not know (ABFT, then L)
+                let slots: Vec<(String, String)> = safe_lock(&rb_abft)
+                    .confirm_votes
+                    .keys()
+                    .filter_map(|key| key.split_once(':'))
+                    .map(|(a, p)| (a.to_string(), p.to_string()))
+                    .collect(); // ABFT dropped
+                let settled: HashSet<(String, String)> = {
+                    let l = safe_lock(&rb_ledger);
+                    slots
+                        .into_iter()
+                        .filter(|(account, previous)| {
+                            l.accounts.get(account).is_none_or(|a| &a.head != previous)
+                        })
+                        .collect()
+                }; // L dropped
+                let expired_before = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs()
+                    .saturating_sub(mempool::TX_EXPIRATION_SECS);
+                let pruned = safe_lock(&rb_abft).prune_confirm_votes(expired_before, |a, p| {
+                    settled.contains(&(a.to_string(), p.to_string()))
+                }); // ABFT dropped
+                match pruned {
+                    Ok(n) if n > 0 => debug!(
+                        "Released {} settled or expired confirmations from the consensus WAL",
+                        n
+                    ),
+                    Ok(_) => {}
+                    Err(e) => warn!("Consensus WAL prune failed: {}", e),
+                }
+            }
+        });
+    }
+
+    // Epoch transitions: once the ledger passes the next epoch's start height, the
+    // round's proposer (stake-weighted draw from the running epoch) signs the next
+    // validator set and gossips EPOCH_SIGN; the running epoch's validators co-sign
+    // it (see EPOCH_SIGN). A round lasts EPOCH_ROUND_SECS, then the next proposer
+    // re-proposes the best-signed candidate. The latest commit is re-gossiped so
+    // nodes that missed it can open the epoch too.
+    {
+        let ep_ledger = Arc::clone(&ledger);
+        let ep_abft = Arc::clone(&abft_consensus);
+        let ep_checkpoints = Arc::clone(&checkpoint_manager);
+        let ep_tx = tx_out.clone();
+        let ep_address = my_address.clone();
+        let ep_secret_key = secret_key.clone();
+        let ep_public_key = keys.public_key.clone();
+        tokio::spawn(async move {
+            const EPOCH_TICK_SECS: u64 = 5;
+            const EPOCH_ROUND_SECS: u64 = 60;
+            const EPOCH_COMMIT_REGOSSIP_SECS: u64 = 300;
+            let mut interval = tokio::time::interval(Duration::from_secs(EPOCH_TICK_SECS));
+            let mut waiting_since: Option<(u64, std::time::Instant)> = None;
+            let mut proposed: Option<(u64, u64)> = None;
+            let mut last_regossip = std::time::Instant::now();
+            loop {
+                interval.tick().await;
+
+                let (current, latest_commit) = {
+                    let abft = safe_lock(&ep_abft);
+                    let current = abft.current_epoch.epoch;
+                    let latest_commit = (last_regossip.elapsed().as_secs()
+                        >= EPOCH_COMMIT_REGOSSIP_SECS)
+                        .then(|| abft.epoch_commits_after(current.saturating_sub(1), 1))
+                        .and_then(|mut commits| commits.pop());
+                    (current, latest_commit)
+                }; // ABFT dropped
+                if let Some(commit) = latest_commit {
+                    last_regossip = std::time::Instant::now();
+                    let _ = ep_tx.send(epoch_commit_message(&commit)).await;
+                }
+
+                let next = current + 1;
+                let block_count = safe_lock(&ep_ledger).total_blocks() as u64; // L dropped
+                if block_count < next * CHECKPOINT_INTERVAL {
+                    waiting_since = None;
+                    continue;
+                }
+                let since = match waiting_since {
+                    Some((epoch, since)) if epoch == next => since,
+                    _ => {
+                        let now = std::time::Instant::now();
+                        waiting_since = Some((next, now));
+                        now
+                    }
+                };
+                let round = since.elapsed().as_secs() / EPOCH_ROUND_SECS;
+                if proposed == Some((next, round)) {
+                    continue;
+                }
+                let candidate = {
+                    let abft = safe_lock(&ep_abft);
+                    if abft.epoch_proposer(round) != Some(ep_address.as_str()) {
+                        continue;
+                    }
+                    abft.epoch_candidate()
+                }; // ABFT dropped
+                let transition = candidate.unwrap_or_else(|| {
+                    EpochTransition::new(next, staked_validators(&safe_lock(&ep_ledger)))
+                });
+                proposed = Some((next, round));
+                info!(
+                    "Proposing validator epoch {} (round {}): {} validators",
+                    transition.epoch,
+                    round,
+                    transition.stakes.len()
+                );
+                for msg in cast_epoch_signature(
+                    &ep_abft,
+                    &ep_checkpoints,
+                    &ep_ledger,
+                    &transition,
+                    round,
+                    &ep_address,
+                    &ep_secret_key,
+                    &ep_public_key,
+                ) {
+                    let _ = ep_tx.send(msg).await;
+                }
+            }
+        });
+    }
+
     // --- TAMBAHAN: JALANKAN HTTP API ---
     let api_ledger = Arc::clone(&ledger);
     let api_tx = tx_out.clone();
@@ -5947,33 +8288,31 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let api_mempool = Arc::clone(&mempool_pool);
     let api_local_validators = Arc::clone(&local_registered_validators);
 
-    // Create aBFT Consensus Engine in main() so it's shared with both API server and event loop
-    let abft_consensus = {
-        let validator_count = {
-            let l = safe_lock(&ledger);
-            l.accounts
-                .iter()
-                .filter(|(_, a)| a.balance >= MIN_VALIDATOR_STAKE_CIL)
-                .count()
-                .max(4)
-        };
-        Arc::new(Mutex::new(ABFTConsensus::new(
-            my_address.clone(),
-            validator_count,
-        )))
-    };
     let api_abft = Arc::clone(&abft_consensus);
+    let api_fork_choice = Arc::clone(&fork_choice);
+    let api_archive_peers = archive_peers.clone();
+    let api_send_voters = Arc::clone(&send_voters);
+    let api_checkpoint_manager = Arc::clone(&checkpoint_manager);
+    let api_tx_tracker = Arc::clone(&tx_tracker);
 
     // --- WASM Smart Contract Engine (shared between API + P2P) ---
-    let wasm_engine = Arc::new(WasmEngine::new());
+    // Compiled modules are persisted so contracts aren't recompiled after a restart
+    let wasm_cache_dir = format!("{}/wasm_cache", base_data_dir);
+    let wasm_engine = Arc::new(match WasmEngine::with_module_cache_dir(&wasm_cache_dir) {
+        Ok(engine) => engine,
+        Err(e) => {
+            warn!("WASM module cache disabled on disk: {}", e);
+            WasmEngine::new()
+        }
+    });
     // Restore contract state from DB (if any contracts were previously deployed)
     match database.load_contracts() {
         Ok(Some(vm_data)) => match wasm_engine.deserialize_all(&vm_data) {
-            Ok(count) => println!("✅ Restored {} smart contracts from database", count),
-            Err(e) => eprintln!("⚠️ Failed to restore contracts: {}", e),
+            Ok(count) => info!("Restored {} smart contracts from database", count),
+            Err(e) => warn!("Failed to restore contracts: {}", e),
         },
         Ok(None) => { /* No contracts deployed yet */ }
-        Err(e) => eprintln!("⚠️ Failed to load contracts from DB: {}", e),
+        Err(e) => warn!("Failed to load contracts from DB: {}", e),
     }
     let api_wasm_engine = Arc::clone(&wasm_engine);
 
@@ -6001,6 +8340,12 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             abft_consensus: api_abft,
             local_registered_validators: api_local_validators,
             wasm_engine: api_wasm_engine,
+            fork_choice: api_fork_choice,
+            pruning,
+            archive_peers: api_archive_peers,
+            send_voters: api_send_voters,
+            checkpoint_manager: api_checkpoint_manager,
+            tx_tracker: api_tx_tracker,
         })
         .await;
     });
@@ -6015,7 +8360,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let grpc_rest_port = api_port;
 
     tokio::spawn(async move {
-        println!("🔧 Starting gRPC server on port {}...", grpc_port);
+        info!("Starting gRPC server on port {}...", grpc_port);
         // Flush stdout for pipe-buffered environments (Flutter process monitor)
         {
             use std::io::Write;
@@ -6032,7 +8377,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         )
         .await
         {
-            eprintln!("❌ gRPC Server error: {}", e);
+            error!("gRPC Server error: {}", e);
         }
     });
 
@@ -6051,9 +8396,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             // Check if node is validator (min 1,000 LOS)
             let is_validator = {
                 let l = safe_lock(&oracle_ledger);
+                let min_stake = l.min_validator_stake_cil();
                 l.accounts
                     .get(&oracle_addr)
-                    .map(|acc| acc.balance >= MIN_VALIDATOR_STAKE_CIL)
+                    .map(|acc| acc.balance >= min_stake)
                     .unwrap_or(false)
             };
 
@@ -6066,7 +8412,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 let sig = match los_crypto::sign_message(payload.as_bytes(), &oracle_sk) {
                     Ok(s) => hex::encode(s),
                     Err(e) => {
-                        eprintln!("❌ Oracle sign error: {:?}", e);
+                        error!("Oracle sign error: {:?}", e);
                         continue;
                     }
                 };
@@ -6079,8 +8425,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 );
                 let _ = oracle_tx.send(oracle_msg).await;
 
-                println!(
-                    "📊 Broadcasting signed oracle prices: ETH=${}.{:02}, BTC=${}.{:02}",
+                info!(
+                    "Broadcasting signed oracle prices: ETH=${}.{:02}, BTC=${}.{:02}",
                     eth_price / 1_000_000,
                     (eth_price % 1_000_000) / 10_000,
                     btc_price / 1_000_000,
@@ -6298,8 +8644,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                         }
                     }
                     if total_checked > 0 {
-                        println!(
-                            "📡 HTTP heartbeat fallback: {}/{} validators reachable",
+                        info!(
+                            "HTTP heartbeat fallback: {}/{} validators reachable",
                             reachable_count, total_checked
                         );
                     }
@@ -6316,7 +8662,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 // PHASE 1: Epoch check + reward calculation (pool lock only, fast)
                 // ═══════════════════════════════════════════════════════════════════
                 let (rewards, is_leader, completed_epoch, fee_data) = {
+                    // Scheduled and governance changes take effect from the next check
+                    let params = safe_lock(&reward_ledger).chain_params();
                     let mut pool = safe_lock(&reward_pool_bg);
+                    pool.apply_params(&params);
 
                     if !pool.is_epoch_complete(now) {
                         // Not epoch boundary — nothing to do
@@ -6342,15 +8691,15 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                 let leader_addr = registered[leader_idx].as_str();
                                 let am_leader = leader_addr == reward_my_addr.as_str();
                                 if am_leader {
-                                    println!(
-                                        "👑 Epoch {} leader election: I am the leader (slot {}/{})",
+                                    info!(
+                                        "Epoch {} leader election: I am the leader (slot {}/{})",
                                         pool.current_epoch,
                                         leader_idx,
                                         registered.len()
                                     );
                                 } else {
-                                    println!(
-                                        "⏳ Epoch {} leader election: leader is {} (slot {}/{})",
+                                    info!(
+                                        "Epoch {} leader election: leader is {} (slot {}/{})",
                                         pool.current_epoch,
                                         &leader_addr[..leader_addr.len().min(15)],
                                         leader_idx,
@@ -6393,12 +8742,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                 let l = safe_lock(&reward_ledger);
                                 let fees = l.accumulated_fees_cil;
                                 if fees > 0 {
+                                    let min_stake = l.min_validator_stake_cil();
                                     let eligible: Vec<(String, u128)> = l
                                         .accounts
                                         .iter()
-                                        .filter(|(_, s)| {
-                                            s.is_validator && s.balance >= MIN_VALIDATOR_STAKE_CIL
-                                        })
+                                        .filter(|(_, s)| s.is_validator && s.balance >= min_stake)
                                         .map(|(addr, s)| {
                                             let weight = calculate_voting_power(s.balance);
                                             (addr.clone(), weight)
@@ -6416,8 +8764,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                             };
 
                             if rewards.is_empty() {
-                                println!(
-                                    "🏆 Epoch {} complete: no eligible validators for rewards",
+                                info!(
+                                    "Epoch {} complete: no eligible validators for rewards",
                                     completed_epoch
                                 );
                             }
@@ -6428,8 +8776,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                             let completed_epoch = pool.current_epoch;
                             pool.advance_epoch_only();
                             pool.set_expected_heartbeats(heartbeat_secs);
-                            println!(
-                                "🏆 Epoch {} complete: not leader, waiting for reward gossip",
+                            info!(
+                                "Epoch {} complete: not leader, waiting for reward gossip",
                                 completed_epoch
                             );
                             (Vec::new(), completed_epoch, None)
@@ -6443,7 +8791,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 let mut fee_gossip_queue: Vec<String> = Vec::new();
 
                 if !rewards.is_empty() && is_leader {
-                    println!("👑 This node is the epoch leader — creating reward blocks");
+                    info!("This node is the epoch leader — creating reward blocks");
 
                     // ═══════════════════════════════════════════════════════════
                     // PHASE 2a: Collect account states (brief ledger lock)
@@ -6458,8 +8806,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                         let l = safe_lock(&reward_ledger);
                         for (addr, reward_cil) in &rewards {
                             if l.distribution.remaining_supply < *reward_cil {
-                                eprintln!(
-                                    "⚠️ Reward skipped for {}: insufficient remaining supply",
+                                warn!(
+                                    "Reward skipped for {}: insufficient remaining supply",
                                     get_short_addr(addr)
                                 );
                                 continue;
@@ -6484,6 +8832,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                     public_key: hex::encode(&reward_pk),
                                     signature: String::new(),
                                     work: 0,
+                                    memo: None,
                                 },
                             ));
                         }
@@ -6499,8 +8848,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                         blk.signature = match try_sign_hex(signing_hash.as_bytes(), &reward_sk) {
                             Ok(sig) => sig,
                             Err(e) => {
-                                eprintln!(
-                                    "❌ Failed to sign reward block for {}: {}",
+                                error!(
+                                    "Failed to sign reward block for {}: {}",
                                     get_short_addr(&addr),
                                     e
                                 );
@@ -6522,8 +8871,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                             // If the previous hash is stale, skip — next epoch will retry.
                             if let Some(acct) = l.accounts.get(addr) {
                                 if acct.head != reward_blk.previous {
-                                    eprintln!(
-                                        "⚠️ Reward block stale for {} (head changed) — will retry next epoch",
+                                    warn!(
+                                        "Reward block stale for {} (head changed) — will retry next epoch",
                                         get_short_addr(addr)
                                     );
                                     continue;
@@ -6536,16 +8885,16 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                     gossip_queue.push(
                                         serde_json::to_string(reward_blk).unwrap_or_default(),
                                     );
-                                    println!(
-                                        "💰 Reward Mint: {} → {} LOS (block: {})",
+                                    info!(
+                                        "Reward Mint: {} → {} LOS (block: {})",
                                         get_short_addr(addr),
                                         reward_cil / CIL_PER_LOS,
                                         &hash[..12]
                                     );
                                 }
                                 Err(e) => {
-                                    eprintln!(
-                                        "❌ Reward block failed for {}: {}",
+                                    error!(
+                                        "Reward block failed for {}: {}",
                                         get_short_addr(addr),
                                         e
                                     );
@@ -6554,8 +8903,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                         }
                         if total_credited > 0 {
                             SAVE_DIRTY.store(true, Ordering::Relaxed);
-                            println!(
-                                "🏆 Epoch {} rewards: {} LOS distributed to {} validators",
+                            info!(
+                                "Epoch {} rewards: {} LOS distributed to {} validators",
                                 completed_epoch,
                                 total_credited / CIL_PER_LOS,
                                 rewards.len()
@@ -6605,6 +8954,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                         public_key: hex::encode(&reward_pk),
                                         signature: String::new(),
                                         work: 0,
+                                        memo: None,
                                     },
                                 ));
                             }
@@ -6619,8 +8969,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                             {
                                 Ok(sig) => sig,
                                 Err(e) => {
-                                    eprintln!(
-                                        "❌ Fee reward sign failed for {}: {}",
+                                    error!(
+                                        "Fee reward sign failed for {}: {}",
                                         get_short_addr(&addr),
                                         e
                                     );
@@ -6638,8 +8988,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                 // Re-check previous hash for staleness
                                 if let Some(acct) = l.accounts.get(addr) {
                                     if acct.head != fee_blk.previous {
-                                        eprintln!(
-                                            "⚠️ Fee block stale for {} — will retry next epoch",
+                                        warn!(
+                                            "Fee block stale for {} — will retry next epoch",
                                             get_short_addr(addr)
                                         );
                                         continue;
@@ -6655,16 +9005,16 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                         fee_gossip_queue.push(
                                             serde_json::to_string(fee_blk).unwrap_or_default(),
                                         );
-                                        println!(
-                                            "💸 Fee Reward: {} → {} CIL (block: {})",
+                                        info!(
+                                            "Fee Reward: {} → {} CIL (block: {})",
                                             get_short_addr(addr),
                                             fee_share,
                                             &hash[..12]
                                         );
                                     }
                                     Err(e) => {
-                                        eprintln!(
-                                            "❌ Fee reward block failed for {}: {}",
+                                        error!(
+                                            "Fee reward block failed for {}: {}",
                                             get_short_addr(addr),
                                             e
                                         );
@@ -6675,8 +9025,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                 l.accumulated_fees_cil =
                                     l.accumulated_fees_cil.saturating_sub(total_fee_credited);
                                 SAVE_DIRTY.store(true, Ordering::Relaxed);
-                                println!(
-                                    "💸 Epoch {} fee distribution: {} CIL ({} LOS) to {} validators",
+                                info!(
+                                    "Epoch {} fee distribution: {} CIL ({} LOS) to {} validators",
                                     completed_epoch,
                                     total_fee_credited,
                                     total_fee_credited / CIL_PER_LOS,
@@ -6709,11 +9059,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         tokio::time::sleep(Duration::from_secs(3)).await; // Wait for P2P to initialize
         let bootstrap_list = get_bootstrap_nodes();
         if bootstrap_list.is_empty() {
-            println!(
-                "📡 No bootstrap nodes found (checked LOS_BOOTSTRAP_NODES env and genesis config)"
-            );
-            println!(
-                "   To connect manually: export LOS_BOOTSTRAP_NODES=peer1.onion:4030,peer2.onion:4031"
+            info!("No bootstrap nodes found (checked LOS_BOOTSTRAP_NODES env and genesis config)");
+            info!(
+                "To connect manually: export LOS_BOOTSTRAP_NODES=peer1.onion:4030,peer2.onion:4031"
             );
         }
         for addr in &bootstrap_list {
@@ -6760,12 +9108,12 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         // After bootstrapping, request state sync from peers (pull-based)
         if !bootstrap_list.is_empty() {
             tokio::time::sleep(Duration::from_secs(3)).await;
-            let block_count = safe_lock(&ledger_boot).blocks.len();
+            let block_count = safe_lock(&ledger_boot).total_blocks();
             let _ = tx_boot
                 .send(format!("SYNC_REQUEST:{}:{}", my_addr_boot, block_count))
                 .await;
-            println!(
-                "📡 Requesting state sync from peers (local blocks: {})",
+            info!(
+                "Requesting state sync from peers (local blocks: {})",
                 block_count
             );
         }
@@ -6781,7 +9129,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 (
                     l.distribution.remaining_supply,
                     l.distribution.total_burned_usd,
-                    l.blocks.len(),
+                    l.total_blocks(),
                 )
             };
             let ts = std::time::SystemTime::now()
@@ -6887,10 +9235,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 let proxy = match reqwest::Proxy::all(format!("socks5h://{}", tor_socks)) {
                     Ok(p) => p,
                     Err(e) => {
-                        eprintln!(
-                            "🧅❌ Tor Health Monitor: failed to create SOCKS5 proxy: {}",
-                            e
-                        );
+                        warn!("Tor Health Monitor: failed to create SOCKS5 proxy: {}", e);
                         return;
                     }
                 };
@@ -6901,10 +9246,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 {
                     Ok(c) => c,
                     Err(e) => {
-                        eprintln!(
-                            "🧅❌ Tor Health Monitor: failed to build HTTP client: {}",
-                            e
-                        );
+                        warn!("Tor Health Monitor: failed to build HTTP client: {}", e);
                         return;
                     }
                 };
@@ -6914,8 +9256,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 // So we always use http://<onion>/health (port 80 default).
                 let health_url = format!("http://{}/health", my_onion.trim_end_matches('/'));
 
-                println!(
-                    "🧅🏥 Tor Health Monitor started (checking {} every {}s)",
+                info!(
+                    "Tor Health Monitor started (checking {} every {}s)",
                     health_url, check_interval_secs
                 );
 
@@ -6926,8 +9268,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     match client.get(&health_url).send().await {
                         Ok(resp) if resp.status().is_success() => {
                             if consecutive_failures > 0 {
-                                println!("🧅✅ Tor Hidden Service RECOVERED after {} consecutive failures",
-                                    consecutive_failures);
+                                info!(
+                                    "Tor Hidden Service RECOVERED after {} consecutive failures",
+                                    consecutive_failures
+                                );
                             }
                             consecutive_failures = 0;
                             tor_health_metrics.tor_onion_reachable.set(1);
@@ -6941,8 +9285,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                 .tor_consecutive_failures
                                 .set(consecutive_failures as i64);
                             tor_health_metrics.tor_self_ping_failures_total.inc();
-                            eprintln!(
-                                "🧅⚠️ Tor self-ping: HTTP {} (failure #{}) — {}",
+                            warn!(
+                                "Tor self-ping: HTTP {} (failure #{}) — {}",
                                 resp.status(),
                                 consecutive_failures,
                                 health_url
@@ -6957,16 +9301,16 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                             tor_health_metrics.tor_self_ping_failures_total.inc();
 
                             if consecutive_failures >= 3 {
-                                eprintln!(
-                                    "🧅🚨 CRITICAL: Tor Hidden Service UNREACHABLE for {} consecutive checks! \
+                                error!(
+                                    "CRITICAL: Tor Hidden Service UNREACHABLE for {} consecutive checks! \
                                      Error: {}. \
                                      ACTION REQUIRED: Restart Tor daemon or check hidden service config. \
                                      Command: sudo systemctl restart tor",
                                     consecutive_failures, e
                                 );
                             } else {
-                                eprintln!(
-                                    "🧅⚠️ Tor self-ping failed (attempt #{}/3): {} — {}",
+                                warn!(
+                                    "Tor self-ping failed (attempt #{}/3): {} — {}",
                                     consecutive_failures, e, health_url
                                 );
                             }
@@ -6978,7 +9322,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     } else {
         // No onion address configured — set metric to -1 (not applicable)
         tor_health_metrics.tor_onion_reachable.set(-1);
-        println!("🧅 Tor Health Monitor: skipped (no LOS_ONION_ADDRESS configured)");
+        info!("Tor Health Monitor: skipped (no LOS_ONION_ADDRESS configured)");
     }
 
     println!("\n==================================================================");
@@ -6993,7 +9337,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         println!("📡 REST API     : http://127.0.0.1:{}", api_port);
     }
     println!(
-        "🔌 gRPC API     : 127.0.0.1:{} (8 services)",
+        "🔌 gRPC API     : 127.0.0.1:{} (11 services)",
         api_port + 20000
     );
     println!("------------------------------------------------------------------");
@@ -7037,25 +9381,31 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // which skips Drop destructors entirely — preventing the UE hang.
     {
         let db_for_signal = Arc::clone(&database);
+        let mempool_for_signal = Arc::clone(&mempool_pool);
         let data_dir_for_signal = base_data_dir.clone();
         let json_log_signal = json_log;
         tokio::spawn(async move {
             // Helper: perform graceful shutdown
             let do_shutdown = |reason: &str| {
-                eprintln!("\n🛑 {} received — shutting down gracefully...", reason);
+                info!("{} received — shutting down gracefully...", reason);
                 if json_log_signal {
                     println!("{{\"event\":\"shutdown\",\"reason\":\"{}\"}}", reason);
                     use std::io::Write;
                     let _ = std::io::stdout().flush();
                 }
+                // Persist pending transactions not yet picked up by the flush task
+                let changes = safe_lock(&mempool_for_signal).take_changes();
+                if let Err(e) = db_for_signal.apply_mempool_changes(&changes) {
+                    warn!("Mempool save error: {}", e);
+                }
                 // Flush sled DB (schedules write-back, non-blocking)
                 if let Err(e) = db_for_signal.flush() {
-                    eprintln!("⚠️ DB flush error: {}", e);
+                    warn!("DB flush error: {}", e);
                 }
                 // Remove PID lockfile so next startup knows we exited cleanly
                 let pid_path = format!("{}/.los-node.pid", data_dir_for_signal);
                 let _ = std::fs::remove_file(&pid_path);
-                eprintln!("✅ Clean shutdown complete");
+                info!("Clean shutdown complete");
                 // exit(0) skips Drop destructors — avoids sled::Drop hanging in flock
                 std::process::exit(0);
             };
@@ -7084,7 +9434,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Clone database, metrics, and slashing_manager for event loop
     let db_clone = Arc::clone(&database);
-    let _metrics_clone = Arc::clone(&metrics);
+    let metrics_clone = Arc::clone(&metrics);
     let slashing_clone = Arc::clone(&slashing_manager);
     let burn_voters_clone = Arc::clone(&burn_voters);
     let send_voters_clone = Arc::clone(&send_voters);
@@ -7157,19 +9507,17 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                         println!("👥 Peers: {}", ab.len());
                         for (s, f) in ab.iter() { println!("  - {}: {}", s, f); }
                     },
-                    "dial" => {
-                        if p.len() == 2 {
-                            let tx = tx_out.clone();
-                            let ma = my_address.clone();
-                            let (s, b) = { let l = safe_lock(&ledger); (l.distribution.remaining_supply, l.distribution.total_burned_usd) };
-                            let target = p[1].to_string();
-                            tokio::spawn(async move {
-                                let _ = tx.send(format!("DIAL:{}", target)).await;
-                                tokio::time::sleep(Duration::from_secs(2)).await;
-                                let ts = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_millis();
-                                let _ = tx.send(format!("ID:{}:{}:{}:{}", ma, s, b, ts)).await;
-                            });
-                        }
+                    "dial" if p.len() == 2 => {
+                        let tx = tx_out.clone();
+                        let ma = my_address.clone();
+                        let (s, b) = { let l = safe_lock(&ledger); (l.distribution.remaining_supply, l.distribution.total_burned_usd) };
+                        let target = p[1].to_string();
+                        tokio::spawn(async move {
+                            let _ = tx.send(format!("DIAL:{}", target)).await;
+                            tokio::time::sleep(Duration::from_secs(2)).await;
+                            let ts = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_millis();
+                            let _ = tx.send(format!("ID:{}:{}:{}:{}", ma, s, b, ts)).await;
+                        });
                     },
                     "burn" => {
                         if p.len() == 3 {
@@ -7255,79 +9603,82 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                             println!("💡 Use format: burn <eth/btc> <txid>");
                         }
                     },
-                    "send" => {
-                        if p.len() == 3 {
-                            let target_short = p[1];
-                            let amt_raw = p[2].parse::<u128>().unwrap_or(0);
-                            let amt = amt_raw * CIL_PER_LOS;
-
-                            if amt == 0 {
-                                println!("❌ Send amount must be greater than 0!");
-                                continue;
-                            }
+                    "send" if p.len() == 3 => {
+                        let target_short = p[1];
+                        let amt_raw = p[2].parse::<u128>().unwrap_or(0);
+                        let amt = amt_raw * CIL_PER_LOS;
+
+                        if amt == 0 {
+                            println!("❌ Send amount must be greater than 0!");
+                            continue;
+                        }
 
-                            let target_full = safe_lock(&address_book).get(target_short).cloned();
+                        let target_full = safe_lock(&address_book).get(target_short).cloned();
 
-                            if let Some(d) = target_full {
-                                // DEADLOCK FIX #4e: Never hold L and PS simultaneously.
-                                // Step 1: Get state from Ledger (L lock only)
-                                let state = {
-                                    let l = safe_lock(&ledger);
-                                    l.accounts.get(&my_address).cloned().unwrap_or(AccountState {
-                                        head: "0".to_string(), balance: 0, block_count: 0, is_validator: false,
-                                    })
-                                }; // L dropped
+                        if let Some(d) = target_full {
+                            // DEADLOCK FIX #4e: Never hold L and PS simultaneously.
+                            // Step 1: Get state from Ledger (L lock only)
+                            let (state, base_fee) = {
+                                let l = safe_lock(&ledger);
+                                (l.accounts.get(&my_address).cloned().unwrap_or(AccountState {
+                                    head: "0".to_string(), balance: 0, block_count: 0, is_validator: false,
+                                }), l.base_fee_cil())
+                            }; // L dropped
 
-                                // Step 2: Check pending total (PS lock only)
-                                // FIX C11-M1: Only sum THIS sender's pending txs, not all
-                                let pending_total: u128 = safe_lock(&pending_sends).values()
-                                    .filter(|(b, _)| b.account == my_address)
-                                    .map(|(b, _)| b.amount).sum();
+                            // Step 2: Check pending total (PS lock only)
+                            // FIX C11-M1: Only sum THIS sender's pending txs, not all
+                            let pending_total: u128 = safe_lock(&pending_sends).values()
+                                .filter(|(b, _)| b.account == my_address)
+                                .map(|(b, _)| b.amount).sum();
 
-                                if state.balance < (amt + pending_total) {
-                                    println!("❌ Insufficient balance! (Balance: {} LOS, In process: {} LOS)",
-                                        format_u128(state.balance / CIL_PER_LOS),
-                                        format_u128(pending_total / CIL_PER_LOS));
-                                    continue;
-                                }
+                            if state.balance < (amt + pending_total) {
+                                println!("❌ Insufficient balance! (Balance: {} LOS, In process: {} LOS)",
+                                    format_u128(state.balance / CIL_PER_LOS),
+                                    format_u128(pending_total / CIL_PER_LOS));
+                                continue;
+                            }
 
-                                // Create Send block draft
-                                let mut blk = Block {
-                                    account: my_address.clone(),
-                                    previous: state.head.clone(),
-                                    block_type: BlockType::Send,
-                                    amount: amt,
-                                    link: d.clone(),
-                                    signature: "".to_string(),
-                                    public_key: hex::encode(&keys.public_key), // Node's public key
-                                    work: 0,
-                                    timestamp: std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_secs(),
-                                    fee: los_core::BASE_FEE_CIL, // Protocol constant from los-core
-                                };
+                            // Create Send block draft
+                            let mut blk = Block {
+                                account: my_address.clone(),
+                                previous: state.head.clone(),
+                                block_type: BlockType::Send,
+                                amount: amt,
+                                link: d.clone(),
+                                signature: "".to_string(),
+                                public_key: hex::encode(&keys.public_key), // Node's public key
+                                work: 0,
+                                timestamp: std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_secs(),
+                                fee: base_fee, // Governance-adjustable, from the ledger
+                                memo: None,
+                            };
 
-                                solve_pow(&mut blk);
-                                let signing_hash = blk.signing_hash();
-                                blk.signature = match try_sign_hex(signing_hash.as_bytes(), &secret_key) {
-                                    Ok(sig) => sig,
-                                    Err(e) => { eprintln!("❌ Signing failed: {}", e); continue; }
-                                };
-                                let hash = blk.calculate_hash();
+                            solve_pow(&mut blk);
+                            let signing_hash = blk.signing_hash();
+                            blk.signature = match try_sign_hex(signing_hash.as_bytes(), &secret_key) {
+                                Ok(sig) => sig,
+                                Err(e) => { eprintln!("❌ Signing failed: {}", e); continue; }
+                            };
+                            let hash = blk.calculate_hash();
 
-                                // Save to confirmation queue
-                                safe_lock(&pending_sends).insert(hash.clone(), (blk.clone(), 0));
+                            // Save to confirmation queue (mempool persists it across restarts)
+                            if let Err(e) = safe_lock(&mempool_pool).add_transaction(blk.clone(), blk.fee as u64, 0) {
+                                println!("❌ Mempool rejected transaction: {}", e);
+                                continue;
+                            }
+                            safe_lock(&pending_sends).insert(hash.clone(), (blk.clone(), 0));
 
-                                // Broadcast confirmation request (REQ) to network
-                                let ts = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_millis();
-                                // CONSENSUS FIX: Include block data (base64) so peers can validate
-                                let block_json = serde_json::to_string(&blk).unwrap_or_default();
-                                let block_b64 = base64::engine::general_purpose::STANDARD.encode(block_json.as_bytes());
-                                let req_msg = format!("CONFIRM_REQ:{}:{}:{}:{}:{}", hash, my_address, amt, ts, block_b64);
-                                let _ = tx_out.send(req_msg).await;
+                            // Broadcast confirmation request (REQ) to network
+                            let ts = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_millis();
+                            // CONSENSUS FIX: Include block data (base64) so peers can validate
+                            let block_json = serde_json::to_string(&blk).unwrap_or_default();
+                            let block_b64 = base64::engine::general_purpose::STANDARD.encode(block_json.as_bytes());
+                            let req_msg = format!("CONFIRM_REQ:{}:{}:{}:{}:{}", hash, my_address, amt, ts, block_b64);
+                            let _ = tx_out.send(req_msg).await;
 
-                                println!("⏳ Transaction created. Requesting network confirmation (Anti Double-Spend)...");
-                            } else {
-                                println!("❌ ID {} not found. Peer must connect first.", target_short);
-                            }
+                            println!("⏳ Transaction created. Requesting network confirmation (Anti Double-Spend)...");
+                        } else {
+                            println!("❌ ID {} not found. Peer must connect first.", target_short);
                         }
                     },
                     "exit" => break,
@@ -7339,11 +9690,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                         stdin_closed = true;
                         if json_log {
                             // In Flutter mode, node keeps running without stdin
-                            eprintln!("📡 Running in headless mode (stdin closed)");
+                            info!("Running in headless mode (stdin closed)");
                         }
                     },
                     Err(e) => {
-                        eprintln!("⚠️ stdin error: {}", e);
+                        warn!("stdin error: {}", e);
                         stdin_closed = true;
                     },
                 }
@@ -7351,7 +9702,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             event = rx_in.recv() => {
                 let Some(event) = event else {
                     // Network channel closed — P2P task exited/crashed
-                    eprintln!("⚠️ Network channel closed, node running in offline mode");
+                    warn!("Network channel closed, node running in offline mode");
                     // Keep node alive (API server still works) but just sleep
                     loop { tokio::time::sleep(Duration::from_secs(60)).await; }
                 };
@@ -7395,7 +9746,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                     // Persist peer to database for recovery after restart
                                     if is_new {
                                         if let Err(e) = db_clone.save_peer(&short, &full) {
-                                            eprintln!("⚠️ Failed to persist peer {}: {}", short, e);
+                                            warn!("Failed to persist peer {}: {}", short, e);
                                         }
                                     }
 
@@ -7423,14 +9774,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                                 l.distribution.remaining_supply = calculated_remaining;
                                                 l.distribution.total_burned_usd = tot_b;
                                                 SAVE_DIRTY.store(true, Ordering::Relaxed);
-                                                println!("🔄 Supply Verified & Synced with Peer: {} (calculated: {})", short, calculated_remaining);
+                                                info!("Supply Verified & Synced with Peer: {} (calculated: {})", short, calculated_remaining);
                                             } else {
-                                                println!("⚠️ Supply sync rejected from {}: peer claims {} but we calculated {}",
+                                                warn!("Supply sync rejected from {}: peer claims {} but we calculated {}",
                                                     short, rem_s, calculated_remaining);
                                             }
                                         }
 
-                                        println!("🤝 Handshake: {}", short);
+                                        info!("Handshake: {}", short);
 
                                         let supply = (l.distribution.remaining_supply, l.distribution.total_burned_usd);
                                         let json = if is_new { serde_json::to_string(&*l).ok() } else { None };
@@ -7450,7 +9801,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                     }; // PS dropped
                                     for (retry_msg, hash_short) in &retry_msgs {
                                         let _ = tx_out.send(retry_msg.clone()).await;
-                                        println!("📡 Resending confirmation request to new peer for TX: {}", hash_short);
+                                        info!("Resending confirmation request to new peer for TX: {}", hash_short);
                                     }
 
                                     // Step 3: Send identity and state to new peer
@@ -7473,10 +9824,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                                 if compressed_bytes.len() <= MAX_SYNC_PAYLOAD {
                                                     let encoded_data = base64::engine::general_purpose::STANDARD.encode(&compressed_bytes);
                                                     let _ = tx_out.send(format!("SYNC_GZIP:{}", encoded_data)).await;
-                                                    println!("📦 Sent state sync to new peer ({:.1} KB compressed)",
+                                                    info!("Sent state sync to new peer ({:.1} KB compressed)",
                                                         compressed_bytes.len() as f64 / 1024.0);
                                                 } else {
-                                                    println!("⚠️ State too large for gossipsub sync ({:.1} MB > 8 MB limit). New peer should use SYNC_REQUEST.",
+                                                    warn!("State too large for gossipsub sync ({:.1} MB > 8 MB limit). New peer should use SYNC_REQUEST.",
                                                         compressed_bytes.len() as f64 / 1_048_576.0);
                                                 }
                                             }
@@ -7492,7 +9843,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                 .duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_secs();
                             let last = LAST_SYNC.load(Ordering::Relaxed);
                             if now_secs.saturating_sub(last) < 10 {
-                                println!("⚠️ SYNC_GZIP rate limited (min 10s between syncs)");
+                                warn!("SYNC_GZIP rate limited (min 10s between syncs)");
                                 continue;
                             }
                             LAST_SYNC.store(now_secs, Ordering::Relaxed);
@@ -7569,11 +9920,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                                                 .as_secs();
 
                                                             if let Some(acc) = l.accounts.get(&blk.account) {
-                                                                if acc.balance >= MIN_VALIDATOR_STAKE_CIL {
+                                                                if acc.balance >= l.min_validator_stake_cil() {
                                                                     if sm.get_profile(&blk.account).is_none() {
                                                                         sm.register_validator(blk.account.clone());
                                                                     }
-                                                                    let _ = sm.record_block_participation(&blk.account, l.blocks.len() as u64, timestamp);
+                                                                    let _ = sm.record_block_participation(&blk.account, l.total_blocks() as u64, timestamp);
                                                                 }
                                                             }
                                                         }
@@ -7589,18 +9940,18 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                         // 2. AUTOMATIC BLACKLIST: If garbage blocks > threshold, remove from address book
                                         const GARBAGE_BLOCK_THRESHOLD: usize = 10;
                                         if invalid_count > GARBAGE_BLOCK_THRESHOLD {
-                                            println!("🚫 BLACKLIST: Peer sent {} garbage blocks (threshold: {}). Disconnecting...", invalid_count, GARBAGE_BLOCK_THRESHOLD);
+                                            warn!("BLACKLIST: Peer sent {} garbage blocks (threshold: {}). Disconnecting...", invalid_count, GARBAGE_BLOCK_THRESHOLD);
                                             // Remove the peer that sent us this sync data
                                             // Note: In a real implementation we'd track which peer sent SYNC_GZIP
                                             let ab = safe_lock(&address_book);
                                             // Remove peers whose full addresses are contained in our address book
                                             // For now, log the event - full peer tracking requires connection-level metadata
-                                            println!("🚫 {} peers in address book. Consider manual cleanup via 'peers' command.", ab.len());
+                                            warn!("{} peers in address book. Consider manual cleanup via 'peers' command.", ab.len());
                                         }
 
                                         if added_count > 0 {
                                             SAVE_DIRTY.store(true, Ordering::Relaxed);
-                                            println!("📚 Sync Complete: {} new blocks validated", added_count);
+                                            info!("Sync Complete: {} new blocks validated", added_count);
                                         }
                                     }
                                 }
@@ -7630,10 +9981,15 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                     times.retain(|_, ts| now_secs.saturating_sub(*ts) < 300);
                                 }
 
+                                // Pruned nodes cannot replay full history — archive peers answer instead
+                                if pruning.is_pruned() {
+                                    continue;
+                                }
+
                                 // Only respond if we have more blocks than the requester
-                                let our_count = safe_lock(&ledger).blocks.len();
+                                let our_count = safe_lock(&ledger).total_blocks();
                                 if our_count > their_count && requester != my_address {
-                                    println!("📡 Sync request from {} (they have {} blocks, we have {})",
+                                    info!("Sync request from {} (they have {} blocks, we have {})",
                                         get_short_addr(&requester), their_count, our_count);
 
                                     // Send only the BLOCKS the requester is missing (not full ledger)
@@ -7654,7 +10010,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                             if compressed.len() <= 8 * 1024 * 1024 {
                                                 let encoded = base64::engine::general_purpose::STANDARD.encode(&compressed);
                                                 let _ = tx_out.send(format!("SYNC_GZIP:{}", encoded)).await;
-                                                println!("📤 Sent state sync ({} blocks, {}KB compressed)", our_count, compressed.len() / 1024);
+                                                info!("Sent state sync ({} blocks, {}KB compressed)", our_count, compressed.len() / 1024);
                                             }
                                         }
                                     }
@@ -7678,16 +10034,12 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
                                 tokio::spawn(async move {
                                     // 1. Check Ledger: Ensure this TXID has never been minted before
-                                    let link_to_check = format!("{}:{}", coin_type.to_uppercase(), txid);
-                                    let already_exists = {
-                                        let l = safe_lock(&ledger_ref);
-                                        l.blocks.values().any(|b| b.block_type == los_core::BlockType::Mint && (b.link == link_to_check || b.link.contains(&txid)))
-                                    };
+                                    let already_exists = safe_lock(&ledger_ref).txid_minted(&txid);
 
                                     if already_exists {
                                         // IF DOUBLE CLAIM DETECTED FROM OTHER PEER
                                         if requester != my_addr_clone {
-                                            println!("🚨 DOUBLE CLAIM DETECTED: {} trying to claim existing TXID!", get_short_addr(&requester));
+                                            warn!("DOUBLE CLAIM DETECTED: {} trying to claim existing TXID!", get_short_addr(&requester));
                                             // SECURITY FIX S1: Sign SLASH_REQ with Dilithium5
                                             let slash_ts = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_millis();
                                             let slash_payload = format!("SLASH:{}:{}:{}:{}", requester, txid, my_addr_clone, slash_ts);
@@ -7695,7 +10047,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                                 let slash_msg = format!("SLASH_REQ:{}:{}:{}:{}:{}:{}", requester, txid, my_addr_clone, slash_ts, hex::encode(&slash_sig), hex::encode(&vote_pk));
                                                 let _ = tx_vote.send(slash_msg).await;
                                             } else {
-                                                eprintln!("⚠️ Signing failed for SLASH_REQ — skipping");
+                                                warn!("Signing failed for SLASH_REQ — skipping");
                                             }
                                         }
                                         return;
@@ -7718,10 +10070,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                             let response = format!("VOTE_RES:{}:{}:YES:{}:{}:{}:{}", txid, requester, my_addr_clone, ts_res, hex::encode(&sig), hex::encode(&vote_pk));
                                             let _ = tx_vote.send(response).await;
                                         } else {
-                                            eprintln!("⚠️ Signing failed for VOTE_RES — skipping vote");
+                                            warn!("Signing failed for VOTE_RES — skipping vote");
                                         }
 
-                                        println!("🗳️ Casting YES vote for TXID: {} from {}",
+                                        info!("Casting YES vote for TXID: {} from {}",
                                             &txid[..8],
                                             get_short_addr(&requester)
                                         );
@@ -7729,7 +10081,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                         // SECURITY P2-6: TXID not verified — ABSTAIN instead of SLASH
                                         // The API may be unreachable, not necessarily fraud.
                                         // Double-claim detection (above) already handles confirmed fraud.
-                                        println!("⚠️ TXID {} from {} could not be verified — abstaining (API may be down)",
+                                        warn!("TXID {} from {} could not be verified — abstaining (API may be down)",
                                             &txid[..std::cmp::min(8, txid.len())], get_short_addr(&requester));
                                     }
                                 });
@@ -7751,7 +10103,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                 let pk_bytes = hex::decode(pk_hex).unwrap_or_default();
 
                                 if !los_crypto::verify_signature(payload.as_bytes(), &sig_bytes, &pk_bytes) {
-                                    println!("🚨 Rejected oracle submission: invalid signature from {}",
+                                    warn!("Rejected oracle submission: invalid signature from {}",
                                         get_short_addr(&validator_addr));
                                     continue;
                                 }
@@ -7759,7 +10111,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                 // Verify public key matches claimed validator address
                                 let derived_addr = los_crypto::public_key_to_address(&pk_bytes);
                                 if derived_addr != validator_addr {
-                                    println!("🚨 Rejected oracle submission: address mismatch (claimed {} but key derives {})",
+                                    warn!("Rejected oracle submission: address mismatch (claimed {} but key derives {})",
                                         get_short_addr(&validator_addr), get_short_addr(&derived_addr));
                                     continue;
                                 }
@@ -7767,11 +10119,12 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                 // Verify submitter is a validator (min 1000 LOS stake)
                                 {
                                     let l = safe_lock(&ledger);
+                                    let min_stake = l.min_validator_stake_cil();
                                     let is_validator = l.accounts.get(&validator_addr)
-                                        .map(|a| a.balance >= MIN_VALIDATOR_STAKE_CIL)
+                                        .map(|a| a.balance >= min_stake)
                                         .unwrap_or(false);
                                     if !is_validator {
-                                        println!("⚠️  Rejected oracle from non-validator: {}", get_short_addr(&validator_addr));
+                                        warn!("Rejected oracle from non-validator: {}", get_short_addr(&validator_addr));
                                         continue;
                                     }
                                 }
@@ -7782,12 +10135,12 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
                                 // Check if consensus achieved
                                 if let Some((eth_median, btc_median)) = oc.get_consensus_price() {
-                                    println!("✅ Oracle Consensus: ETH=${}.{:02}, BTC=${}.{:02} (from {} validators)",
+                                    info!("Oracle Consensus: ETH=${}.{:02}, BTC=${}.{:02} (from {} validators)",
                                         eth_median / 1_000_000, (eth_median % 1_000_000) / 10_000,
                                         btc_median / 1_000_000, (btc_median % 1_000_000) / 10_000,
                                         oc.submission_count());
                                 } else {
-                                    println!("📊 Oracle submission from {} ({} more validators needed)",
+                                    info!("Oracle submission from {} ({} more validators needed)",
                                         get_short_addr(&validator_addr),
                                         2_usize.saturating_sub(oc.submission_count())
                                     );
@@ -7808,22 +10161,22 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                 let slash_pk_bytes = hex::decode(slash_pk_hex).unwrap_or_default();
 
                                 if !los_crypto::verify_signature(slash_payload.as_bytes(), &slash_sig_bytes, &slash_pk_bytes) {
-                                    println!("🚨 Rejected SLASH_REQ: invalid signature from {}", get_short_addr(&proposer_addr));
+                                    warn!("Rejected SLASH_REQ: invalid signature from {}", get_short_addr(&proposer_addr));
                                     continue;
                                 }
                                 // Verify pubkey matches claimed proposer address
                                 let derived_proposer = los_crypto::public_key_to_address(&slash_pk_bytes);
                                 if derived_proposer != proposer_addr {
-                                    println!("🚨 Rejected SLASH_REQ: pubkey mismatch for {}", get_short_addr(&proposer_addr));
+                                    warn!("Rejected SLASH_REQ: pubkey mismatch for {}", get_short_addr(&proposer_addr));
                                     continue;
                                 }
                             } else if parts.len() == 3 {
                                 // Legacy unsigned format — reject on mainnet, warn on testnet
                                 if los_core::is_mainnet_build() {
-                                    println!("🚨 Rejected unsigned SLASH_REQ (mainnet requires signed messages)");
+                                    warn!("Rejected unsigned SLASH_REQ (mainnet requires signed messages)");
                                     continue;
                                 }
-                                println!("⚠️ Accepted unsigned SLASH_REQ (testnet only — will be rejected on mainnet)");
+                                warn!("Accepted unsigned SLASH_REQ (testnet only — will be rejected on mainnet)");
                             } else {
                                 continue;
                             }
@@ -7831,15 +10184,15 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                 let cheater_addr = parts[1].to_string();
                                 let fake_txid = parts[2].to_string();
 
-                                println!("⚖️  Slash proposal received for: {}", get_short_addr(&cheater_addr));
+                                info!("Slash proposal received for: {}", get_short_addr(&cheater_addr));
 
                                 // Step 1: Validate this node is a validator
-                                let my_balance = {
+                                let (my_balance, min_stake) = {
                                     let l = safe_lock(&ledger);
-                                    l.accounts.get(&my_address).map(|a| a.balance).unwrap_or(0)
+                                    (l.accounts.get(&my_address).map(|a| a.balance).unwrap_or(0), l.min_validator_stake_cil())
                                 };
-                                if my_balance < MIN_VALIDATOR_STAKE_CIL {
-                                    println!("⚠️ Ignoring SLASH_REQ: this node is not a validator");
+                                if my_balance < min_stake {
+                                    warn!("Ignoring SLASH_REQ: this node is not a validator");
                                     continue;
                                 }
 
@@ -7851,14 +10204,12 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                     let l = safe_lock(&ledger);
                                     let cheater_exists = l.accounts.contains_key(&cheater_addr);
                                     // Check that no Mint block references this TXID in its link
-                                    let txid_was_minted = l.blocks.values().any(|b| {
-                                        b.block_type == los_core::BlockType::Mint && b.link.contains(&fake_txid)
-                                    });
+                                    let txid_was_minted = l.txid_minted(&fake_txid);
                                     cheater_exists && !txid_was_minted
                                 };
 
                                 if !is_valid_evidence {
-                                    println!("⚠️ SLASH_REQ rejected: evidence not confirmed independently");
+                                    warn!("SLASH_REQ rejected: evidence not confirmed independently");
                                     continue;
                                 }
 
@@ -7889,7 +10240,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                         .filter(|p| p.offender == cheater_addr && !p.executed)
                                         .count();
 
-                                    println!("⚖️  Slash votes for {}: {}/{} (need {})",
+                                    info!("Slash votes for {}: {}/{} (need {})",
                                         get_short_addr(&cheater_addr), proposal_count, total_validators, threshold);
 
                                     proposal_count >= threshold
@@ -7918,6 +10269,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                                     timestamp: std::time::SystemTime::now()
                                                         .duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_secs(),
                                                     fee: 0,
+                                                    memo: None,
                                                 };
 
                                                 solve_pow(&mut slash_blk);
@@ -7929,16 +10281,16 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                                             let hash = result.into_hash();
                                                             SAVE_DIRTY.store(true, Ordering::Relaxed);
                                                             gossip = Some(serde_json::to_string(&slash_blk).unwrap_or_default());
-                                                            println!("🔨 SLASHED (consensus 2/3+1)! {} penalized {} LOS (block: {})",
+                                                            info!("SLASHED (consensus 2/3+1)! {} penalized {} LOS (block: {})",
                                                                 get_short_addr(&cheater_addr),
                                                                 penalty_amount / CIL_PER_LOS,
                                                                 &hash[..8]
                                                             );
                                                         },
-                                                        Err(e) => println!("⚠️ Slash block failed for {}: {}", get_short_addr(&cheater_addr), e),
+                                                        Err(e) => warn!("Slash block failed for {}: {}", get_short_addr(&cheater_addr), e),
                                                     }
                                                 } else {
-                                                    println!("⚠️ Slash signing failed for {}", get_short_addr(&cheater_addr));
+                                                    warn!("Slash signing failed for {}", get_short_addr(&cheater_addr));
                                                 }
                                             }
                                         }
@@ -7948,7 +10300,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                         let _ = tx_out.send(msg).await;
                                     }
                                 } else {
-                                    println!("⏳ Slash proposal registered, waiting for more validator votes...");
+                                    info!("Slash proposal registered, waiting for more validator votes...");
                                 }
                             }
                         } else if data.starts_with("VOTE_RES:") {
@@ -7968,13 +10320,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                 let pk_bytes = hex::decode(pk_hex).unwrap_or_default();
 
                                 if !los_crypto::verify_signature(payload.as_bytes(), &sig_bytes, &pk_bytes) {
-                                    println!("🚨 Rejected VOTE_RES: invalid signature from {}", get_short_addr(&voter_addr));
+                                    warn!("Rejected VOTE_RES: invalid signature from {}", get_short_addr(&voter_addr));
                                     continue;
                                 }
                                 // Verify pubkey matches claimed voter address
                                 let derived_addr = los_crypto::public_key_to_address(&pk_bytes);
                                 if derived_addr != voter_addr {
-                                    println!("🚨 Rejected VOTE_RES: pubkey mismatch for {}", get_short_addr(&voter_addr));
+                                    warn!("Rejected VOTE_RES: pubkey mismatch for {}", get_short_addr(&voter_addr));
                                     continue;
                                 }
 
@@ -8005,7 +10357,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                     let voter_power_display = voter_balance / CIL_PER_LOS;
 
                                     if voter_power_quadratic == 0 {
-                                        println!("⚠️ Vote ignored: {} (Stake {} LOS insufficient, need 1000 LOS min)",
+                                        warn!("Vote ignored: {} (Stake {} LOS insufficient, need 1000 LOS min)",
                                             get_short_addr(&voter_addr),
                                             voter_power_display
                                         );
@@ -8018,7 +10370,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                         let mut voters = safe_lock(&burn_voters_clone);
                                         let voter_set = voters.entry(txid.clone()).or_default();
                                         if voter_set.contains(&voter_addr) {
-                                            println!("⚠️ Duplicate burn vote from {} — ignored", get_short_addr(&voter_addr));
+                                            warn!("Duplicate burn vote from {} — ignored", get_short_addr(&voter_addr));
                                             continue;
                                         }
                                         voter_set.insert(voter_addr.clone());
@@ -8032,7 +10384,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                             burn_info.3 += power_scaled;
 
                                             let min_voters = if !testnet_config::get_testnet_config().should_enable_consensus() { 1 } else { MIN_DISTINCT_VOTERS };
-                                            println!("📩 Vote Received: {} (Stake: {} LOS, Quadratic Power: {}) | Progress: {}/20000 (Voters: {}/{})",
+                                            info!("Vote Received: {} (Stake: {} LOS, Quadratic Power: {}) | Progress: {}/20000 (Voters: {}/{})",
                                                 get_short_addr(&voter_addr),
                                                 voter_power_display,
                                                 voter_power_quadratic,
@@ -8043,7 +10395,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
                                             let threshold = if !testnet_config::get_testnet_config().should_enable_consensus() { TESTNET_FUNCTIONAL_THRESHOLD } else { BURN_CONSENSUS_THRESHOLD };
                                             if burn_info.3 >= threshold && distinct_count >= min_voters {
-                                                println!("✅ Quadratic Stake Consensus Achieved (Total Power: {}, Voters: {})!", burn_info.3, distinct_count);
+                                                info!("Quadratic Stake Consensus Achieved (Total Power: {}, Voters: {})!", burn_info.3, distinct_count);
                                                 let (amt_coin, price, sym, _, _, recipient) = burn_info.clone();
                                                 Some((amt_coin, price, sym, recipient))
                                             } else { None }
@@ -8056,7 +10408,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                         let los_to_mint = match calculate_mint_cil(amt_coin, price, &sym) {
                                             Ok(v) => v,
                                             Err(e) => {
-                                                eprintln!("❌ Mint calculation overflow: {}", e);
+                                                error!("Mint calculation overflow: {}", e);
                                                 continue;
                                             }
                                         };
@@ -8086,22 +10438,23 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                                 work: 0,
                                                 timestamp: std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_secs(),
                                                 fee: 0,
+                                                memo: None,
                                             };
 
                                             solve_pow(&mut mint_blk);
                                             let signing_hash = mint_blk.signing_hash();
                                             mint_blk.signature = match try_sign_hex(signing_hash.as_bytes(), &secret_key) {
                                                 Ok(sig) => sig,
-                                                Err(e) => { eprintln!("❌ Mint signing failed: {} — skipping", e); continue; }
+                                                Err(e) => { error!("Mint signing failed: {} — skipping", e); continue; }
                                             };
 
                                             match l.process_block(&mint_blk) {
                                                 Ok(_) => {
                                                     SAVE_DIRTY.store(true, Ordering::Relaxed);
-                                                    println!("🔥 Minting Successful: +{} LOS to {}!", format_u128(los_to_mint / CIL_PER_LOS), get_short_addr(&mint_recipient));
+                                                    info!("Minting Successful: +{} LOS to {}!", format_u128(los_to_mint / CIL_PER_LOS), get_short_addr(&mint_recipient));
                                                     Some(serde_json::to_string(&mint_blk).unwrap_or_default())
                                                 },
-                                                Err(e) => { println!("❌ Failed to process Mint block: {}", e); None },
+                                                Err(e) => { warn!("Failed to process Mint block: {}", e); None },
                                             }
                                         }; // L dropped
                                         if let Some(msg) = mint_gossip {
@@ -8114,11 +10467,101 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                     }
                                 }
                             }
+                        } else if data.starts_with("EPOCH_SIGN:") {
+                            // FORMAT: EPOCH_SIGN:round:transition_b64:signer:signature:pubkey
+                            let parts: Vec<&str> = data.split(':').collect();
+                            if parts.len() == 6 {
+                                let Ok(round) = parts[1].parse::<u64>() else { continue };
+                                let Some(transition) = base64::engine::general_purpose::STANDARD
+                                    .decode(parts[2])
+                                    .ok()
+                                    .and_then(|bytes| serde_json::from_slice::<EpochTransition>(&bytes).ok())
+                                else {
+                                    continue;
+                                };
+                                let signature = EpochSignature {
+                                    signer: parts[3].to_string(),
+                                    signature: parts[4].to_string(),
+                                    public_key: parts[5].to_string(),
+                                };
+                                if !verify_epoch_signature(&transition, &signature) {
+                                    warn!("Rejected EPOCH_SIGN: invalid signature from {}", get_short_addr(&signature.signer));
+                                    continue;
+                                }
+                                let signer = signature.signer.clone();
+                                let mut msgs: Vec<String> = count_epoch_signature(&abft_event, &checkpoint_manager, &ledger, &transition, signature)
+                                    .into_iter()
+                                    .collect();
+
+                                // Co-sign the round's proposal once this node reached the epoch's
+                                // start height and its ledger holds the same validators
+                                let proposal = {
+                                    let abft = safe_lock(&abft_event);
+                                    transition.epoch == abft.current_epoch.epoch + 1
+                                        && abft.epoch_proposer(round) == Some(signer.as_str())
+                                        && abft.validator_set.contains(&my_address)
+                                }; // ABFT dropped
+                                let agrees = proposal && {
+                                    let l = safe_lock(&ledger);
+                                    l.total_blocks() as u64 >= transition.start_height
+                                        && staked_validators(&l).iter().map(|(a, _)| a.as_str()).eq(transition.addresses())
+                                }; // L dropped
+                                if agrees {
+                                    msgs.extend(cast_epoch_signature(&abft_event, &checkpoint_manager, &ledger, &transition, round, &my_address, &secret_key, &keys.public_key));
+                                } else if proposal {
+                                    debug!("Not co-signing epoch {} proposal from {}: ledger disagrees or is behind", transition.epoch, get_short_addr(&signer));
+                                }
+                                for msg in msgs {
+                                    let _ = tx_out.send(msg).await;
+                                }
+                            }
+                        } else if let Some(payload) = data.strip_prefix("EPOCH_COMMIT:") {
+                            // FORMAT: EPOCH_COMMIT:commit_b64 (transition + quorum signatures)
+                            let Some(commit) = base64::engine::general_purpose::STANDARD
+                                .decode(payload)
+                                .ok()
+                                .and_then(|bytes| serde_json::from_slice::<EpochCommit>(&bytes).ok())
+                            else {
+                                continue;
+                            };
+                            let current = safe_lock(&abft_event).current_epoch.epoch; // ABFT dropped
+                            if commit.transition.epoch <= current {
+                                continue;
+                            }
+                            if commit.transition.epoch > current + 1 {
+                                // Missed transitions: ask peers to replay the chain from ours
+                                let _ = tx_out.send(format!("EPOCH_SYNC_REQ:{}", current)).await;
+                                continue;
+                            }
+                            if apply_epoch_commit(&abft_event, &checkpoint_manager, &ledger, commit.clone()) {
+                                let _ = tx_out.send(epoch_commit_message(&commit)).await;
+                            }
+                        } else if let Some(payload) = data.strip_prefix("EPOCH_SYNC_REQ:") {
+                            // FORMAT: EPOCH_SYNC_REQ:<requester's epoch>
+                            // Answered at most once per 10 seconds; every answer is a plain EPOCH_COMMIT
+                            static EPOCH_SYNC_LAST: AtomicU64 = AtomicU64::new(0);
+                            let Ok(epoch) = payload.parse::<u64>() else { continue };
+                            let now = std::time::SystemTime::now()
+                                .duration_since(std::time::UNIX_EPOCH)
+                                .unwrap_or_default()
+                                .as_secs();
+                            if now.saturating_sub(EPOCH_SYNC_LAST.load(Ordering::Relaxed)) < 10 {
+                                continue;
+                            }
+                            let commits = safe_lock(&abft_event).epoch_commits_after(epoch, EPOCH_SYNC_BATCH); // ABFT dropped
+                            if !commits.is_empty() {
+                                EPOCH_SYNC_LAST.store(now, Ordering::Relaxed);
+                            }
+                            for commit in commits {
+                                let _ = tx_out.send(epoch_commit_message(&commit)).await;
+                            }
                         } else if data.starts_with("CONFIRM_REQ:") {
                             let parts: Vec<&str> = data.split(':').collect();
                             // Support both V1 (5 parts) and V2 (6 parts with block data)
                             if parts.len() >= 5 {
                                 let tx_hash = parts[1].to_string();
+                                let block_span = tracing::info_span!("block", hash = %tx_hash, msg = "CONFIRM_REQ");
+                                let _block = block_span.enter();
                                 let sender_addr = parts[2].to_string();
                                 let amount = parts[3].parse::<u128>().unwrap_or(0);
 
@@ -8131,27 +10574,47 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                     None
                                 };
 
+                                // FORK CHOICE: a block competing with one the ledger applied (any block
+                                // type) is voted on like any confirmation, one CONFIRM_RES per predecessor.
+                                if let Some(candidate) = block_from_msg.as_ref().filter(|b| b.calculate_hash() == tx_hash && b.account == sender_addr) {
+                                    let now = std::time::SystemTime::now()
+                                        .duration_since(std::time::UNIX_EPOCH)
+                                        .unwrap_or_default()
+                                        .as_secs();
+                                    if observe_fork(&ledger, &fork_choice, &metrics_clone, candidate, now).is_some() {
+                                        drop(_block); // not held across awaits
+                                        for msg in cast_fork_vote(&ledger, &fork_choice, &abft_event, &metrics_clone, &candidate.account, &candidate.previous, &my_address, &secret_key, &keys.public_key) {
+                                            let _ = tx_out.send(msg).await;
+                                        }
+                                        continue;
+                                    }
+                                }
+
                                 let tx_confirm = tx_out.clone();
                                 let ledger_ref = Arc::clone(&ledger);
                                 let my_addr_clone = my_address.clone();
                                 // FIX C12-03: Zeroize cloned secret key on async task drop
                                 let confirm_sk = secret_key.clone();
                                 let confirm_pk = keys.public_key.clone();
+                                let abft_confirm = Arc::clone(&abft_event);
 
                                 tokio::spawn(async move {
+                                    // Governance blocks are checked against the running epoch
+                                    let electorate = safe_lock(&abft_confirm).current_epoch.stakes(); // ABFT dropped
                                     // SECURITY P0-2: Verify the block exists and matches claims.
                                     // First check ledger (for re-gossipped blocks), then validate
                                     // the embedded block from the CONFIRM_REQ message (consensus fix).
-                                    let (sender_balance, block_valid) = {
+                                    let (sender_balance, block_valid, previous, debit) = {
                                         let l_guard = safe_lock(&ledger_ref);
                                         let bal = l_guard.accounts.get(&sender_addr).map(|a| a.balance).unwrap_or(0);
 
                                         // Path 1: Block already in ledger (re-gossip or skip_consensus)
-                                        let ledger_valid = l_guard.blocks.get(&tx_hash).map(|b| {
-                                            b.block_type == los_core::BlockType::Send
+                                        let ledger_block = l_guard.blocks.get(&tx_hash).filter(|b| {
+                                            matches!(b.block_type, BlockType::Send | BlockType::Proposal | BlockType::Vote)
                                                 && b.account == sender_addr
                                                 && b.amount == amount
-                                        }).unwrap_or(false);
+                                        });
+                                        let ledger_valid = ledger_block.is_some();
 
                                         // Path 2: Validate embedded block from CONFIRM_REQ message
                                         // Full cryptographic validation: hash, signature, PoW, sender binding
@@ -8159,8 +10622,24 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                             block_from_msg.as_ref().map(|b| {
                                                 // 1. Hash must match claimed tx_hash
                                                 let hash_ok = b.calculate_hash() == tx_hash;
-                                                // 2. Must be a Send block
-                                                let type_ok = b.block_type == los_core::BlockType::Send;
+                                                // 2. Must be a Send block, or a governance block
+                                                //    that passes the governance rules; votes and
+                                                //    settlement tallies over the running epoch
+                                                let type_ok = match b.block_type {
+                                                    BlockType::Send => true,
+                                                    BlockType::Proposal | BlockType::Vote => {
+                                                        match l_guard.check_governance(b).and_then(|_| {
+                                                            l_guard.governance.check_electorate(b, &electorate)
+                                                        }) {
+                                                            Ok(()) => true,
+                                                            Err(e) => {
+                                                                warn!("CONFIRM_REQ governance check failed: {}", e);
+                                                                false
+                                                            }
+                                                        }
+                                                    }
+                                                    _ => false,
+                                                };
                                                 // 3. Sender must match
                                                 let sender_ok = b.account == sender_addr;
                                                 // 4. Amount must match
@@ -8175,7 +10654,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                                 let pk_ok = derived == sender_addr;
 
                                                 if !hash_ok || !type_ok || !sender_ok || !amount_ok || !sig_ok || !pow_ok || !pk_ok {
-                                                    println!("⚠️ CONFIRM_REQ block validation failed: hash={} type={} sender={} amount={} sig={} pow={} pk={}",
+                                                    warn!("CONFIRM_REQ block validation failed: hash={} type={} sender={} amount={} sig={} pow={} pk={}",
                                                         hash_ok, type_ok, sender_ok, amount_ok, sig_ok, pow_ok, pk_ok);
                                                 }
 
@@ -8183,15 +10662,48 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                             }).unwrap_or(false)
                                         } else { false };
 
-                                        (bal, ledger_valid || msg_valid)
-                                    };
+                                        let checked = ledger_block.or(block_from_msg.as_ref());
+                                        let previous = checked
+                                            .map(|b| b.previous.clone())
+                                            .unwrap_or_default();
+                                        // A settlement credits its amount (the refunded deposit)
+                                        let debit = if checked.is_some_and(|b| b.link.starts_with("SETTLE:")) {
+                                            0
+                                        } else {
+                                            amount
+                                        };
+                                        (bal, ledger_valid || msg_valid, previous, debit)
+                                    }; // L dropped
 
                                     if !block_valid {
                                         // P0-2: Block doesn't exist/match and no valid embedded block — don't vote
                                         return;
                                     }
 
-                                    if sender_balance >= amount {
+                                    if sender_balance >= debit {
+                                        // WAL: one confirmation per (account, previous), durable before
+                                        // it is sent, so a restart cannot confirm a competing block
+                                        let wal = {
+                                            let mut abft = safe_lock(&abft_confirm);
+                                            if let Err(e) = abft.vote_confirm(&sender_addr, &previous, &tx_hash) {
+                                                warn!("Not confirming {}: {}", tx_hash, e);
+                                                return;
+                                            }
+                                            abft.wal()
+                                        }; // ABFT dropped
+                                        if let Some(wal) = wal {
+                                            match tokio::task::spawn_blocking(move || wal.flush()).await {
+                                                Ok(Ok(())) => {}
+                                                Ok(Err(e)) => {
+                                                    warn!("Not confirming {}: {}", tx_hash, e);
+                                                    return;
+                                                }
+                                                Err(e) => {
+                                                    warn!("Not confirming {}: WAL flush task failed: {}", tx_hash, e);
+                                                    return;
+                                                }
+                                            }
+                                        }
                                         let ts = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_millis();
                                         // SECURITY P0-1: Sign CONFIRM_RES with Dilithium5
                                         let payload = format!("{}:{}:YES:{}:{}", tx_hash, sender_addr, my_addr_clone, ts);
@@ -8199,16 +10711,18 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                             let res = format!("CONFIRM_RES:{}:{}:YES:{}:{}:{}:{}", tx_hash, sender_addr, my_addr_clone, ts, hex::encode(&sig), hex::encode(&confirm_pk));
                                             let _ = tx_confirm.send(res).await;
                                         } else {
-                                            eprintln!("⚠️ Signing failed for CONFIRM_RES — skipping");
+                                            warn!("Signing failed for CONFIRM_RES — skipping");
                                         }
                                     }
-                                });
+                                }.instrument(block_span.clone()));
                             }
                         } else if data.starts_with("CONFIRM_RES:") {
                             let parts: Vec<&str> = data.split(':').collect();
                             // FORMAT: CONFIRM_RES:tx_hash:sender:YES:voter:timestamp:signature:pubkey (8 parts)
                             if parts.len() == 8 {
                                 let tx_hash = parts[1].to_string();
+                                let block_span = tracing::info_span!("block", hash = %tx_hash, msg = "CONFIRM_RES");
+                                let mut _block = block_span.enter();
                                 let _requester = parts[2].to_string();
                                 let voter_addr = parts[4].to_string();
                                 let sig_hex = parts[6];
@@ -8220,12 +10734,25 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                 let pk_bytes = hex::decode(pk_hex).unwrap_or_default();
 
                                 if !los_crypto::verify_signature(payload.as_bytes(), &sig_bytes, &pk_bytes) {
-                                    println!("🚨 Rejected CONFIRM_RES: invalid signature from {}", get_short_addr(&voter_addr));
+                                    warn!("Rejected CONFIRM_RES: invalid signature from {}", get_short_addr(&voter_addr));
                                     continue;
                                 }
                                 let derived_addr = los_crypto::public_key_to_address(&pk_bytes);
                                 if derived_addr != voter_addr {
-                                    println!("🚨 Rejected CONFIRM_RES: pubkey mismatch for {}", get_short_addr(&voter_addr));
+                                    warn!("Rejected CONFIRM_RES: pubkey mismatch for {}", get_short_addr(&voter_addr));
+                                    continue;
+                                }
+
+                                // FORK CHOICE: confirmations of a fork candidate are fork votes
+                                let fork_slot = safe_lock(&fork_choice)
+                                    .fork_of(&tx_hash)
+                                    .map(|f| (f.account.clone(), f.previous.clone())); // FC dropped
+                                if let Some((account, previous)) = fork_slot {
+                                    if let Some(winner) = apply_fork_vote(&ledger, &fork_choice, &metrics_clone, &account, &previous, &tx_hash, &voter_addr) {
+                                        // Re-gossip the winner so peers still on the losing branch see it
+                                        drop(_block); // not held across awaits
+                                        let _ = tx_out.send(serde_json::to_string(&winner).unwrap_or_default()).await;
+                                    }
                                     continue;
                                 }
 
@@ -8261,7 +10788,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                         let mut voters = safe_lock(&send_voters_clone);
                                         let voter_set = voters.entry(tx_hash.clone()).or_default();
                                         if voter_set.contains(&voter_addr) {
-                                            println!("⚠️ Duplicate send vote from {} — ignored", get_short_addr(&voter_addr));
+                                            warn!("Duplicate send vote from {} — ignored", get_short_addr(&voter_addr));
                                             continue;
                                         }
                                         voter_set.insert(voter_addr.clone());
@@ -8275,11 +10802,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                                 let power_scaled = voter_power_quadratic * 1000;
                                                 *total_power_votes += power_scaled;
                                                 let min_voters = if !testnet_config::get_testnet_config().should_enable_consensus() { 1 } else { MIN_DISTINCT_VOTERS };
-                                                println!("📩 Konfirmasi Power: {} (Stake: {} LOS, Quadratic: {}) | Total: {}/{} (Voters: {}/{})",
+                                                info!("Konfirmasi Power: {} (Stake: {} LOS, Quadratic: {}) | Total: {}/{} (Voters: {}/{})",
                                                     get_short_addr(&voter_addr), voter_power_display, voter_power_quadratic, total_power_votes, SEND_CONSENSUS_THRESHOLD, distinct_count, min_voters
                                                 );
                                             } else {
-                                                println!("⚠️ Vote from {} has 0 power (balance: {} CIL, {} LOS) — check genesis sync",
+                                                warn!("Vote from {} has 0 power (balance: {} CIL, {} LOS) — check genesis sync",
                                                     get_short_addr(&voter_addr), voter_balance, voter_power_display);
                                             }
 
@@ -8293,6 +10820,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
                                     // Step 4: If threshold met, finalize (L lock only, then SM lock only)
                                     if let Some(blk_to_finalize) = finalize_data {
+                                        let mut finalize_error = None;
                                         let process_success = {
                                             let mut l = safe_lock(&ledger);
                                             match l.process_block(&blk_to_finalize) {
@@ -8306,11 +10834,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                                             .as_secs();
 
                                                         if let Some(acc) = l.accounts.get(&blk_to_finalize.account) {
-                                                            if acc.balance >= MIN_VALIDATOR_STAKE_CIL {
+                                                            if acc.balance >= l.min_validator_stake_cil() {
                                                                 if sm.get_profile(&blk_to_finalize.account).is_none() {
                                                                     sm.register_validator(blk_to_finalize.account.clone());
                                                                 }
-                                                                let _ = sm.record_block_participation(&blk_to_finalize.account, l.blocks.len() as u64, timestamp);
+                                                                let _ = sm.record_block_participation(&blk_to_finalize.account, l.total_blocks() as u64, timestamp);
                                                             }
                                                         }
                                                     }
@@ -8318,27 +10846,40 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                                     true
                                                 },
                                                 Err(e) => {
-                                                    println!("❌ Finalization Failed: {:?}", e);
+                                                    warn!("Finalization Failed: {:?}", e);
+                                                    finalize_error = Some(format!("Finalization failed: {:?}", e));
                                                     false
                                                 }
                                             }
                                         }; // L dropped
+                                        if let Some(reason) = finalize_error {
+                                            safe_lock(&tx_tracker).rejected(&tx_hash, &blk_to_finalize.account, reason);
+                                        }
 
                                         if process_success {
-                                            println!("✅ Transaction Confirmed (Power Verified) & Added to Ledger");
+                                            info!("Transaction Confirmed (Power Verified) & Added to Ledger");
+
+                                            if matches!(blk_to_finalize.block_type, BlockType::Proposal | BlockType::Vote) {
+                                                let block_b64 = base64::engine::general_purpose::STANDARD
+                                                    .encode(serde_json::to_vec(&blk_to_finalize).unwrap_or_default());
+                                                drop(_block);
+                                                let _ = tx_out.send(format!("GOVERNANCE_CONFIRMED:{}", block_b64)).await;
+                                                _block = block_span.enter();
+                                            }
 
                                             // AUTO-UNREGISTER: If sender's balance dropped below minimum
                                             // stake after this send, automatically unregister them.
                                             if blk_to_finalize.block_type == BlockType::Send {
                                                 let mut l = safe_lock(&ledger);
+                                                let min_stake = l.min_validator_stake_cil();
                                                 if let Some(sender_acct) = l.accounts.get_mut(&blk_to_finalize.account) {
-                                                    if sender_acct.is_validator && sender_acct.balance < MIN_VALIDATOR_STAKE_CIL {
+                                                    if sender_acct.is_validator && sender_acct.balance < min_stake {
                                                         sender_acct.is_validator = false;
                                                         SAVE_DIRTY.store(true, Ordering::Relaxed);
-                                                        println!("⚠️ Auto-unregistered validator {}: balance {} < minimum stake {} LOS",
+                                                        warn!("Auto-unregistered validator {}: balance {} < minimum stake {} LOS",
                                                             get_short_addr(&blk_to_finalize.account),
                                                             sender_acct.balance / CIL_PER_LOS,
-                                                            MIN_VALIDATOR_STAKE_CIL / CIL_PER_LOS);
+                                                            min_stake / CIL_PER_LOS);
                                                     }
                                                 }
                                             }
@@ -8373,11 +10914,12 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                                             timestamp: std::time::SystemTime::now()
                                                                 .duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_secs(),
                                                             fee: 0,
+                                                            memo: None,
                                                         };
                                                         solve_pow(&mut recv_blk);
                                                         recv_blk.signature = match try_sign_hex(recv_blk.signing_hash().as_bytes(), &secret_key) {
                                                             Ok(sig) => sig,
-                                                            Err(e) => { eprintln!("⚠️ Auto-Receive signing failed: {}", e); String::new() }
+                                                            Err(e) => { warn!("Auto-Receive signing failed: {}", e); String::new() }
                                                         };
                                                         if recv_blk.signature.is_empty() {
                                                             None
@@ -8391,11 +10933,12 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                                                 recv_acct.block_count += 1;
                                                             }
                                                             l.blocks.insert(recv_hash.clone(), recv_blk.clone());
+                                                            l.index_block(&recv_hash, &recv_blk);
                                                             // SECURITY FIX M-4: Track claimed Send hash for double-receive prevention.
                                                             // See M-4 comment in /send endpoint for full rationale.
                                                             l.claimed_sends.insert(recv_blk.link.clone());
                                                             SAVE_DIRTY.store(true, Ordering::Relaxed);
-                                                            println!("📨 Auto-Receive created for {} (+{} CIL)",
+                                                            info!("Auto-Receive created for {} (+{} CIL)",
                                                                 get_short_addr(&target), blk_to_finalize.amount);
                                                             // BLOCK_CONFIRMED: Encode Send+Receive for cross-node state propagation.
                                                             // Peers apply via direct ledger manipulation (bypasses process_block
@@ -8412,15 +10955,32 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                                     } else { None }
                                                 }; // l dropped
                                                 if let Some(msg) = recv_gossip {
+                                                    drop(_block);
                                                     let _ = tx_out.send(msg).await;
+                                                    _block = block_span.enter();
                                                 }
                                             }
                                         }
-                                        // Step 5: Remove from pending (PS lock only)
-                                        safe_lock(&pending_sends).remove(&tx_hash);
+                                        // Step 5: Remove from pending and pick the send queued behind it (PS lock only)
+                                        let next_send = {
+                                            let mut ps = safe_lock(&pending_sends);
+                                            ps.remove(&tx_hash);
+                                            ps.iter()
+                                                .find(|(_, (b, _))| b.account == blk_to_finalize.account && b.previous == tx_hash)
+                                                .map(|(h, (b, _))| (h.clone(), b.clone()))
+                                        }; // PS dropped
                                         safe_lock(&send_voters_clone).remove(&tx_hash);
                                         // Clean from mempool on confirmation
                                         safe_lock(&mempool_pool).remove_transaction(&tx_hash);
+                                        if let (true, Some((next_hash, next_blk))) = (process_success, next_send) {
+                                            let block_b64 = base64::engine::general_purpose::STANDARD
+                                                .encode(serde_json::to_string(&next_blk).unwrap_or_default().as_bytes());
+                                            let ts = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_millis();
+                                            drop(_block);
+                                            let _ = tx_out.send(format!("CONFIRM_REQ:{}:{}:{}:{}:{}",
+                                                next_hash, next_blk.account, next_blk.amount, ts, block_b64)).await;
+                                            _block = block_span.enter();
+                                        }
                                     }
                                 }
                             }
@@ -8526,17 +11086,17 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
                                     // Validate address format
                                     if addr.is_empty() || !los_crypto::validate_address(&addr) {
-                                        println!("🚫 VALIDATOR_REG: invalid address from peer");
+                                        warn!("VALIDATOR_REG: invalid address from peer");
                                         continue;
                                     }
 
                                     // Verify public_key derives to claimed address
                                     let pk_bytes = match hex::decode(&pk_hex) {
                                         Ok(b) => b,
-                                        Err(_) => { println!("🚫 VALIDATOR_REG: invalid pk hex"); continue; }
+                                        Err(_) => { warn!("VALIDATOR_REG: invalid pk hex"); continue; }
                                     };
                                     if los_crypto::public_key_to_address(&pk_bytes) != addr {
-                                        println!("🚫 VALIDATOR_REG: pk does not match address");
+                                        warn!("VALIDATOR_REG: pk does not match address");
                                         continue;
                                     }
 
@@ -8544,10 +11104,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                     let message = format!("REGISTER_VALIDATOR:{}:{}", addr, ts);
                                     let sig_bytes = match hex::decode(&sig_hex) {
                                         Ok(b) => b,
-                                        Err(_) => { println!("🚫 VALIDATOR_REG: invalid sig hex"); continue; }
+                                        Err(_) => { warn!("VALIDATOR_REG: invalid sig hex"); continue; }
                                     };
                                     if !los_crypto::verify_signature(message.as_bytes(), &sig_bytes, &pk_bytes) {
-                                        println!("🚫 VALIDATOR_REG: signature verification failed for {}", get_short_addr(&addr));
+                                        warn!("VALIDATOR_REG: signature verification failed for {}", get_short_addr(&addr));
                                         continue;
                                     }
 
@@ -8557,17 +11117,18 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                         .unwrap_or_default()
                                         .as_secs();
                                     if ts == 0 || now.abs_diff(ts) > 300 {
-                                        println!("🚫 VALIDATOR_REG: stale timestamp from {}", get_short_addr(&addr));
+                                        warn!("VALIDATOR_REG: stale timestamp from {}", get_short_addr(&addr));
                                         continue;
                                     }
 
                                     // Check balance & skip if already registered
-                                    let (balance, already) = {
+                                    let (balance, already, min_stake) = {
                                         let l = safe_lock(&ledger);
-                                        match l.accounts.get(&addr) {
+                                        let (balance, already) = match l.accounts.get(&addr) {
                                             Some(acc) => (acc.balance, acc.is_validator),
                                             None => (0, false),
-                                        }
+                                        };
+                                        (balance, already, l.min_validator_stake_cil())
                                     };
 
                                     if already {
@@ -8575,8 +11136,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                         continue;
                                     }
 
-                                    if balance < MIN_VALIDATOR_STAKE_CIL {
-                                        println!("🚫 VALIDATOR_REG: {} has insufficient stake ({} LOS)",
+                                    if balance < min_stake {
+                                        warn!("VALIDATOR_REG: {} has insufficient stake ({} LOS)",
                                             get_short_addr(&addr), balance / CIL_PER_LOS);
                                         continue;
                                     }
@@ -8600,20 +11161,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                     }
 
                                     SAVE_DIRTY.store(true, Ordering::Relaxed);
-                                    println!("✅ Validator registered via P2P: {} (stake: {} LOS)",
+                                    info!("Validator registered via P2P: {} (stake: {} LOS)",
                                         get_short_addr(&addr), balance / CIL_PER_LOS);
 
-                                    // Dynamically update aBFT validator set (no restart required)
+                                    // Stage aBFT validator set change for the next epoch (no restart required)
                                     {
-                                        let l = safe_lock(&ledger);
-                                        let mut validators: Vec<String> = l
-                                            .accounts
-                                            .iter()
-                                            .filter(|(_, a)| a.balance >= MIN_VALIDATOR_STAKE_CIL && a.is_validator)
-                                            .map(|(addr, _)| addr.clone())
-                                            .collect();
-                                        validators.sort();
-                                        safe_lock(&abft_event).update_validator_set(validators);
+                                        let validators = staked_validators(&safe_lock(&ledger));
+                                        safe_lock(&abft_event).schedule_validator_set(validators);
                                     }
 
                                     // Add to address_book so heartbeats are recorded for this validator
@@ -8626,12 +11180,12 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                     if let Some(onion) = reg["onion_address"].as_str() {
                                         if !onion.is_empty() && onion.ends_with(".onion") {
                                             insert_validator_endpoint(&mut safe_lock(&ve_event), addr.clone(), onion.to_string());
-                                            println!("🧅 Discovered validator endpoint: {} → {}", get_short_addr(&addr), onion);
+                                            info!("Discovered validator endpoint: {} → {}", get_short_addr(&addr), onion);
                                         }
                                     }
                                 },
                                 Err(e) => {
-                                    println!("⚠️ VALIDATOR_REG: invalid JSON from peer: {}", e);
+                                    warn!("VALIDATOR_REG: invalid JSON from peer: {}", e);
                                 }
                             }
                         } else if let Some(json_str) = data.strip_prefix("VALIDATOR_UNREG:") {
@@ -8646,17 +11200,17 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
                                     // Validate address format
                                     if addr.is_empty() || !los_crypto::validate_address(&addr) {
-                                        println!("🚫 VALIDATOR_UNREG: invalid address from peer");
+                                        warn!("VALIDATOR_UNREG: invalid address from peer");
                                         continue;
                                     }
 
                                     // Verify public_key derives to claimed address
                                     let pk_bytes = match hex::decode(&pk_hex) {
                                         Ok(b) => b,
-                                        Err(_) => { println!("🚫 VALIDATOR_UNREG: invalid pk hex"); continue; }
+                                        Err(_) => { warn!("VALIDATOR_UNREG: invalid pk hex"); continue; }
                                     };
                                     if los_crypto::public_key_to_address(&pk_bytes) != addr {
-                                        println!("🚫 VALIDATOR_UNREG: pk does not match address");
+                                        warn!("VALIDATOR_UNREG: pk does not match address");
                                         continue;
                                     }
 
@@ -8664,10 +11218,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                     let message = format!("UNREGISTER_VALIDATOR:{}:{}", addr, ts);
                                     let sig_bytes = match hex::decode(&sig_hex) {
                                         Ok(b) => b,
-                                        Err(_) => { println!("🚫 VALIDATOR_UNREG: invalid sig hex"); continue; }
+                                        Err(_) => { warn!("VALIDATOR_UNREG: invalid sig hex"); continue; }
                                     };
                                     if !los_crypto::verify_signature(message.as_bytes(), &sig_bytes, &pk_bytes) {
-                                        println!("🚫 VALIDATOR_UNREG: signature verification failed for {}", get_short_addr(&addr));
+                                        warn!("VALIDATOR_UNREG: signature verification failed for {}", get_short_addr(&addr));
                                         continue;
                                     }
 
@@ -8677,7 +11231,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                         .unwrap_or_default()
                                         .as_secs();
                                     if ts == 0 || now.abs_diff(ts) > 300 {
-                                        println!("🚫 VALIDATOR_UNREG: stale timestamp from {}", get_short_addr(&addr));
+                                        warn!("VALIDATOR_UNREG: stale timestamp from {}", get_short_addr(&addr));
                                         continue;
                                     }
 
@@ -8713,24 +11267,17 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                         ve.remove(&addr);
                                     }
 
-                                    // Update aBFT validator set
+                                    // Stage aBFT validator set change for the next epoch
                                     {
-                                        let l = safe_lock(&ledger);
-                                        let mut validators: Vec<String> = l
-                                            .accounts
-                                            .iter()
-                                            .filter(|(_, a)| a.balance >= MIN_VALIDATOR_STAKE_CIL && a.is_validator)
-                                            .map(|(addr, _)| addr.clone())
-                                            .collect();
-                                        validators.sort();
-                                        safe_lock(&abft_event).update_validator_set(validators);
+                                        let validators = staked_validators(&safe_lock(&ledger));
+                                        safe_lock(&abft_event).schedule_validator_set(validators);
                                     }
 
                                     SAVE_DIRTY.store(true, Ordering::Relaxed);
-                                    println!("🔻 Validator unregistered via P2P: {}", get_short_addr(&addr));
+                                    info!("Validator unregistered via P2P: {}", get_short_addr(&addr));
                                 },
                                 Err(e) => {
-                                    println!("⚠️ VALIDATOR_UNREG: invalid JSON from peer: {}", e);
+                                    warn!("VALIDATOR_UNREG: invalid JSON from peer: {}", e);
                                 }
                             }
                         } else if let Some(json_str) = data.strip_prefix("PEER_LIST:") {
@@ -8755,7 +11302,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                         }
                                     }
                                     if added > 0 {
-                                        println!("🔄 PEX: merged {} new validator endpoint(s) from peer", added);
+                                        info!("PEX: merged {} new validator endpoint(s) from peer", added);
                                     }
                                 }
                             }
@@ -8774,6 +11321,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
                                 if let (Some(send_blk), Some(recv_blk)) = (send_block, recv_block) {
                                     let send_hash = send_blk.calculate_hash();
+                                    let _block = tracing::info_span!("block", hash = %send_hash, msg = "BLOCK_CONFIRMED").entered();
 
                                     // Validate Send block: signature + PoW + must be Send type
                                     let send_valid = send_blk.block_type == BlockType::Send
@@ -8787,7 +11335,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                         && recv_blk.account == send_blk.link;
 
                                     if !send_valid || !recv_valid {
-                                        println!("🚫 Rejected BLOCK_CONFIRMED: validation failed (send={}, recv={})", send_valid, recv_valid);
+                                        warn!("Rejected BLOCK_CONFIRMED: validation failed (send={}, recv={})", send_valid, recv_valid);
                                     } else {
                                         let mut l = safe_lock(&ledger);
                                         // Idempotency: skip if already applied
@@ -8807,17 +11355,20 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                             // originator could broadcast conflicting BLOCK_CONFIRMED
                                             // messages (double-spend) — receiving nodes would apply
                                             // both via saturating_sub, creating money from nothing.
-                                            let send_rejected = if let Some(sender) = l.accounts.get(&send_blk.account) {
+                                            let send_rejected = if let Err(e) = l.check_memo(&send_blk) {
+                                                warn!("Rejected BLOCK_CONFIRMED: {}", e);
+                                                true
+                                            } else if let Some(sender) = l.accounts.get(&send_blk.account) {
                                                 let total_debit = send_blk.amount.saturating_add(send_blk.fee);
                                                 if sender.head != send_blk.previous {
-                                                    println!("🚫 Rejected BLOCK_CONFIRMED: chain fork detected \
+                                                    warn!("Rejected BLOCK_CONFIRMED: chain fork detected \
                                                         (sender={}, head={}, block.previous={})",
                                                         get_short_addr(&send_blk.account),
                                                         get_short_addr(&sender.head),
                                                         get_short_addr(&send_blk.previous));
                                                     true
                                                 } else if sender.balance < total_debit {
-                                                    println!("🚫 Rejected BLOCK_CONFIRMED: insufficient sender \
+                                                    warn!("Rejected BLOCK_CONFIRMED: insufficient sender \
                                                         balance ({} < {}) for {}",
                                                         sender.balance, total_debit,
                                                         get_short_addr(&send_blk.account));
@@ -8840,6 +11391,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                             // Track fees for validator redistribution
                                             l.accumulated_fees_cil = l.accumulated_fees_cil.saturating_add(send_blk.fee);
                                             l.blocks.insert(send_hash.clone(), send_blk.clone());
+                                            l.index_block(&send_hash, &send_blk);
 
                                             // Apply Receive: credit recipient
                                             if !l.accounts.contains_key(&recv_blk.account) {
@@ -8852,7 +11404,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                                 recipient.head = recv_hash.clone();
                                                 recipient.block_count += 1;
                                             }
-                                            l.blocks.insert(recv_hash, recv_blk.clone());
+                                            l.blocks.insert(recv_hash.clone(), recv_blk.clone());
+                                            l.index_block(&recv_hash, &recv_blk);
                                             // SECURITY FIX M-4: Track claimed Send for double-receive prevention.
                                             // BLOCK_CONFIRMED bypasses process_block(); without this insert,
                                             // a subsequent Receive via process_block() could re-claim the
@@ -8860,33 +11413,73 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                             l.claimed_sends.insert(send_hash.clone());
 
                                             SAVE_DIRTY.store(true, Ordering::Relaxed);
-                                            println!("✅ Applied BLOCK_CONFIRMED: {} → {} ({} CIL)",
+                                            info!("Applied BLOCK_CONFIRMED: {} → {} ({} CIL)",
                                                 get_short_addr(&send_blk.account), get_short_addr(&recv_blk.account), send_blk.amount);
                                         } // end if !send_rejected
                                         }
                                     }
                                 }
                             }
+                        } else if let Some(block_b64) = data.strip_prefix("GOVERNANCE_CONFIRMED:") {
+                            // CROSS-NODE GOVERNANCE REPLICATION
+                            // Format: GOVERNANCE_CONFIRMED:{block_b64}. Sent by the originating node
+                            // once the block reached CONFIRM_RES quorum, like BLOCK_CONFIRMED for
+                            // sends. process_block re-checks signature, PoW, chain sequence and
+                            // governance rules.
+                            let block_opt: Option<Block> = base64::engine::general_purpose::STANDARD
+                                .decode(block_b64).ok()
+                                .and_then(|bytes| serde_json::from_slice(&bytes).ok());
+                            match block_opt {
+                                Some(gov_blk) if matches!(gov_blk.block_type, BlockType::Proposal | BlockType::Vote) => {
+                                    let result = safe_lock(&ledger).process_block(&gov_blk);
+                                    match result {
+                                        Ok(r) if r.is_new() => {
+                                            SAVE_DIRTY.store(true, Ordering::Relaxed);
+                                            info!("Applied governance {:?} from {}: {}",
+                                                gov_blk.block_type, get_short_addr(&gov_blk.account), get_short_addr(r.hash()));
+                                        }
+                                        Ok(_) => {}
+                                        Err(e) => warn!("Rejected GOVERNANCE_CONFIRMED: {}", e),
+                                    }
+                                }
+                                _ => warn!("Rejected GOVERNANCE_CONFIRMED: not a Proposal/Vote block"),
+                            }
                         } else if data.starts_with("CONTRACT_DEPLOYED:") {
                             // CROSS-NODE CONTRACT REPLICATION
-                            // Format: CONTRACT_DEPLOYED:{block_b64}:{bytecode_b64}:{contract_addr}
-                            let parts: Vec<&str> = data.splitn(4, ':').collect();
-                            if parts.len() == 4 {
+                            // Format: CONTRACT_DEPLOYED:{block_b64}:{bytecode_b64}:{contract_addr}[:{abi_b64}]
+                            let parts: Vec<&str> = data.splitn(5, ':').collect();
+                            if parts.len() >= 4 {
                                 let block_opt: Option<Block> = base64::engine::general_purpose::STANDARD
                                     .decode(parts[1]).ok()
                                     .and_then(|bytes| serde_json::from_slice(&bytes).ok());
                                 let bytecode_opt = base64::engine::general_purpose::STANDARD.decode(parts[2]).ok();
                                 let _contract_addr = parts[3].to_string();
+                                // Attached ABI must match the hash in the signed link (checked below)
+                                let attached_abi: Option<ContractAbi> = parts.get(4)
+                                    .and_then(|b64| base64::engine::general_purpose::STANDARD.decode(b64).ok())
+                                    .and_then(|bytes| ContractAbi::from_json(&bytes).ok());
 
                                 if let (Some(deploy_blk), Some(bytecode)) = (block_opt, bytecode_opt) {
                                     // Validate: must be ContractDeploy + valid sig + valid PoW
+                                    let link_parts = los_core::abi::parse_deploy_link(&deploy_blk.link);
                                     let valid = deploy_blk.block_type == BlockType::ContractDeploy
                                         && deploy_blk.verify_signature()
                                         && deploy_blk.verify_pow()
-                                        && deploy_blk.link.starts_with("DEPLOY:");
+                                        && link_parts.is_some();
+                                    // Only an ABI the deployer signed for is kept; one relayed
+                                    // without a commitment in the link is ignored
+                                    let (abi_ok, attached_abi) = match link_parts.and_then(|(_, h)| h) {
+                                        Some(signed_hash) => match attached_abi {
+                                            Some(abi) if abi.hash() == signed_hash => (true, Some(abi)),
+                                            _ => (false, None),
+                                        },
+                                        None => (true, None),
+                                    };
 
                                     if !valid {
-                                        println!("🚫 Rejected CONTRACT_DEPLOYED: validation failed");
+                                        warn!("Rejected CONTRACT_DEPLOYED: validation failed");
+                                    } else if !abi_ok {
+                                        warn!("Rejected CONTRACT_DEPLOYED: attached ABI does not match the signed deploy block");
                                     } else {
                                         let deploy_hash = deploy_blk.calculate_hash();
                                         let mut l = safe_lock(&ledger);
@@ -8902,14 +11495,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                             let deploy_rejected = if let Some(deployer) = l.accounts.get(&deploy_blk.account) {
                                                 let total_debit = deploy_blk.amount.saturating_add(deploy_blk.fee);
                                                 if deployer.head != deploy_blk.previous {
-                                                    println!("🚫 Rejected CONTRACT_DEPLOYED: chain fork \
+                                                    warn!("Rejected CONTRACT_DEPLOYED: chain fork \
                                                         (deployer={}, head={}, block.previous={})",
                                                         get_short_addr(&deploy_blk.account),
                                                         get_short_addr(&deployer.head),
                                                         get_short_addr(&deploy_blk.previous));
                                                     true
                                                 } else if deployer.balance < total_debit {
-                                                    println!("🚫 Rejected CONTRACT_DEPLOYED: insufficient \
+                                                    warn!("Rejected CONTRACT_DEPLOYED: insufficient \
                                                         deployer balance ({} < {})",
                                                         deployer.balance, total_debit);
                                                     true
@@ -8927,22 +11520,24 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                                     deployer.block_count += 1;
                                                 }
                                                 l.accumulated_fees_cil = l.accumulated_fees_cil.saturating_add(deploy_blk.fee);
-                                                l.blocks.insert(deploy_hash, deploy_blk.clone());
+                                                l.blocks.insert(deploy_hash.clone(), deploy_blk.clone());
+                                                l.index_block(&deploy_hash, &deploy_blk);
                                                 drop(l); // Release ledger lock before VM operations
 
                                                 // Deploy to local WASM engine
                                                 let code_hash = WasmEngine::compute_code_hash(&bytecode);
-                                                let expected_hash = &deploy_blk.link[7..]; // After "DEPLOY:"
+                                                let expected_hash = link_parts.map(|(h, _)| h).unwrap_or_default();
                                                 if code_hash.starts_with(expected_hash) || expected_hash.starts_with(&code_hash[..expected_hash.len().min(code_hash.len())]) {
                                                     let now_ts = std::time::SystemTime::now()
                                                         .duration_since(std::time::UNIX_EPOCH)
                                                         .unwrap_or_default()
                                                         .as_secs();
-                                                    match wasm_engine.deploy_contract(
+                                                    match wasm_engine.deploy_contract_with_abi(
                                                         deploy_blk.account.clone(),
                                                         bytecode,
                                                         BTreeMap::new(),
                                                         now_ts,
+                                                        attached_abi,
                                                     ) {
                                                         Ok(addr) => {
                                                             // Fund contract if amount > 0
@@ -8953,13 +11548,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                                             if let Ok(vm_data) = wasm_engine.serialize_all() {
                                                                 let _ = database.save_contracts(&vm_data);
                                                             }
-                                                            println!("✅ Replicated CONTRACT_DEPLOYED: {} (owner: {})",
+                                                            info!("Replicated CONTRACT_DEPLOYED: {} (owner: {})",
                                                                 addr, get_short_addr(&deploy_blk.account));
                                                         }
-                                                        Err(e) => eprintln!("⚠️ Failed to replicate contract deploy: {}", e),
+                                                        Err(e) => warn!("Failed to replicate contract deploy: {}", e),
                                                     }
                                                 } else {
-                                                    eprintln!("🚫 CONTRACT_DEPLOYED: code hash mismatch");
+                                                    warn!("CONTRACT_DEPLOYED: code hash mismatch");
                                                 }
 
                                                 SAVE_DIRTY.store(true, Ordering::Relaxed);
@@ -8984,99 +11579,112 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                         && call_blk.link.starts_with("CALL:");
 
                                     if !valid {
-                                        println!("🚫 Rejected CONTRACT_CALLED: validation failed");
+                                        warn!("Rejected CONTRACT_CALLED: validation failed");
                                     } else {
                                         let call_hash = call_blk.calculate_hash();
-                                        let mut l = safe_lock(&ledger);
-                                        if !l.blocks.contains_key(&call_hash) {
-                                            // Ensure caller account exists
-                                            if !l.accounts.contains_key(&call_blk.account) {
-                                                l.accounts.insert(call_blk.account.clone(), AccountState {
-                                                    head: "0".to_string(), balance: 0, block_count: 0, is_validator: false,
-                                                });
-                                            }
-                                            // SECURITY FIX M-10: Chain-sequence + balance validation
-                                            // for CONTRACT_CALLED — same pattern as BLOCK_CONFIRMED fix.
-                                            let call_rejected = if let Some(caller) = l.accounts.get(&call_blk.account) {
-                                                let total_debit = call_blk.amount.saturating_add(call_blk.fee);
-                                                if caller.head != call_blk.previous {
-                                                    println!("🚫 Rejected CONTRACT_CALLED: chain fork \
-                                                        (caller={}, head={}, block.previous={})",
-                                                        get_short_addr(&call_blk.account),
-                                                        get_short_addr(&caller.head),
-                                                        get_short_addr(&call_blk.previous));
-                                                    true
-                                                } else if caller.balance < total_debit {
-                                                    println!("🚫 Rejected CONTRACT_CALLED: insufficient \
-                                                        caller balance ({} < {})",
-                                                        caller.balance, total_debit);
-                                                    true
-                                                } else {
-                                                    false
-                                                }
-                                            } else {
+                                        let call_applied = {
+                                            let mut l = safe_lock(&ledger);
+                                            if l.blocks.contains_key(&call_hash) {
                                                 false
-                                            };
-                                            if !call_rejected {
-                                                if let Some(caller_acct) = l.accounts.get_mut(&call_blk.account) {
-                                                    let total_debit = call_blk.amount.saturating_add(call_blk.fee);
-                                                    caller_acct.balance -= total_debit; // Safe: checked above
-                                                    caller_acct.head = call_hash.clone();
-                                                    caller_acct.block_count += 1;
+                                            } else {
+                                                // Ensure caller account exists
+                                                if !l.accounts.contains_key(&call_blk.account) {
+                                                    l.accounts.insert(call_blk.account.clone(), AccountState {
+                                                        head: "0".to_string(), balance: 0, block_count: 0, is_validator: false,
+                                                    });
                                                 }
-                                                l.accumulated_fees_cil = l.accumulated_fees_cil.saturating_add(call_blk.fee);
-                                                l.blocks.insert(call_hash, call_blk.clone());
-                                                drop(l);
-
-                                                // Parse call data from link: "CALL:{addr}:{func}:{args_b64}"
-                                                let call_data = &call_blk.link[5..];
-                                                let call_parts: Vec<&str> = call_data.splitn(3, ':').collect();
-                                                if call_parts.len() >= 2 {
-                                                    let contract_addr = call_parts[0];
-                                                    let function = call_parts[1];
-                                                    let args: Vec<String> = if call_parts.len() == 3 {
-                                                        base64::engine::general_purpose::STANDARD
-                                                            .decode(call_parts[2]).ok()
-                                                            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
-                                                            .unwrap_or_default()
+                                                // SECURITY FIX M-10: Chain-sequence + balance validation
+                                                // for CONTRACT_CALLED — same pattern as BLOCK_CONFIRMED fix.
+                                                let call_rejected = if let Some(caller) = l.accounts.get(&call_blk.account) {
+                                                    let total_debit = call_blk.amount.saturating_add(call_blk.fee);
+                                                    if caller.head != call_blk.previous {
+                                                        warn!("Rejected CONTRACT_CALLED: chain fork \
+                                                            (caller={}, head={}, block.previous={})",
+                                                            get_short_addr(&call_blk.account),
+                                                            get_short_addr(&caller.head),
+                                                            get_short_addr(&call_blk.previous));
+                                                        true
+                                                    } else if caller.balance < total_debit {
+                                                        warn!("Rejected CONTRACT_CALLED: insufficient \
+                                                            caller balance ({} < {})",
+                                                            caller.balance, total_debit);
+                                                        true
                                                     } else {
-                                                        Vec::new()
-                                                    };
-                                                    let gas_limit = call_blk.fee / los_core::GAS_PRICE_CIL.max(1);
-
-                                                    // Value transfer to contract
-                                                    if call_blk.amount > 0 {
-                                                        let _ = wasm_engine.send_to_contract(contract_addr, call_blk.amount);
+                                                        false
                                                     }
+                                                } else {
+                                                    false
+                                                };
+                                                if call_rejected {
+                                                    false
+                                                } else {
+                                                    if let Some(caller_acct) = l.accounts.get_mut(&call_blk.account) {
+                                                        let total_debit = call_blk.amount.saturating_add(call_blk.fee);
+                                                        caller_acct.balance -= total_debit; // Safe: checked above
+                                                        caller_acct.head = call_hash.clone();
+                                                        caller_acct.block_count += 1;
+                                                    }
+                                                    l.accumulated_fees_cil = l.accumulated_fees_cil.saturating_add(call_blk.fee);
+                                                    l.blocks.insert(call_hash.clone(), call_blk.clone());
+                                                    l.index_block(&call_hash, &call_blk);
+                                                    // Same limits the originating node executed with
+                                                    sync_gas_schedule(&l, &wasm_engine, call_blk.timestamp);
+                                                    true
+                                                }
+                                            }
+                                        }; // L dropped
+                                        if call_applied {
+                                            // Parse call data from link: "CALL:{addr}:{func}:{args_b64}[:{allowances}]"
+                                            if let Ok(call_link) = los_core::parse_call_link(&call_blk.link) {
+                                                let contract_addr = call_link.contract.as_str();
+                                                let function = call_link.function.as_str();
+                                                let args: Vec<String> = base64::engine::general_purpose::STANDARD
+                                                    .decode(&call_link.args_b64).ok()
+                                                    .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+                                                    .unwrap_or_default();
+                                                let gas_limit = los_core::call_gas_paid(call_blk.fee);
+
+                                                // Value transfer to contract
+                                                if call_blk.amount > 0 {
+                                                    let _ = wasm_engine.send_to_contract(contract_addr, call_blk.amount);
+                                                }
 
-                                                    // Execute deterministically (same result on all nodes)
-                                                    let call = ContractCall {
-                                                        contract: contract_addr.to_string(),
-                                                        function: function.to_string(),
-                                                        args,
-                                                        gas_limit: gas_limit as u64,
-                                                        caller: call_blk.account.clone(),
-                                                        block_timestamp: call_blk.timestamp,
-                                                    };
-                                                    match wasm_engine.call_contract(call) {
-                                                        Ok(result) => {
-                                                            if let Ok(vm_data) = wasm_engine.serialize_all() {
-                                                                let _ = database.save_contracts(&vm_data);
-                                                            }
-                                                            println!("✅ Replicated CONTRACT_CALLED: {}::{} → {}",
-                                                                contract_addr, function,
-                                                                if result.success { "OK" } else { "FAIL" });
+                                                // Execute deterministically (same result on all nodes)
+                                                let call = ContractCall {
+                                                    contract: contract_addr.to_string(),
+                                                    function: function.to_string(),
+                                                    args,
+                                                    gas_limit,
+                                                    caller: call_blk.account.clone(),
+                                                    block_timestamp: call_blk.timestamp,
+                                                    value_cil: call_blk.amount,
+                                                    token_allowances: call_link.token_allowances.clone(),
+                                                };
+                                                // Off the swarm task: the executor may queue this call
+                                                let call_engine = Arc::clone(&wasm_engine);
+                                                let executed = tokio::task::spawn_blocking(move || call_engine.call_contract(call))
+                                                    .await
+                                                    .unwrap_or_else(|e| Err(format!("execution task failed: {}", e)));
+                                                match executed {
+                                                    Ok(result) => {
+                                                        if let Ok(vm_data) = wasm_engine.serialize_all() {
+                                                            let _ = database.save_contracts(&vm_data);
                                                         }
-                                                        Err(e) => eprintln!("⚠️ Failed to replicate contract call: {}", e),
+                                                        info!("Replicated CONTRACT_CALLED: {}::{} → {}",
+                                                            contract_addr, function,
+                                                            if result.success { "OK" } else { "FAIL" });
                                                     }
+                                                    Err(e) => warn!("Failed to replicate contract call: {}", e),
                                                 }
+                                            }
 
-                                                SAVE_DIRTY.store(true, Ordering::Relaxed);
-                                            } // end if !call_rejected
+                                            SAVE_DIRTY.store(true, Ordering::Relaxed);
                                         }
                                     }
                                 }
                             }                        } else if let Ok(inc) = serde_json::from_str::<Block>(&data) {
+                            let block_span = tracing::info_span!("block", hash = %inc.calculate_hash(), block_type = ?inc.block_type);
+                            let mut _block = block_span.enter();
                             // FIX C12-01: Mint/Slash blocks from P2P are accepted ONLY if they
                             // carry a valid validator signature + valid PoW. Previously blanket-
                             // rejected, which caused minted tokens to exist only on the originating
@@ -9084,7 +11692,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                             if matches!(inc.block_type, BlockType::Mint | BlockType::Slash) {
                                 // Verify: non-empty signature, valid signature, valid PoW
                                 if inc.signature.is_empty() || inc.public_key.is_empty() {
-                                    println!("🚫 Rejected unsigned {:?} block from P2P", inc.block_type);
+                                    warn!("Rejected unsigned {:?} block from P2P", inc.block_type);
                                     continue;
                                 }
                                 let sig_ok = hex::decode(&inc.signature).ok().and_then(|sig| {
@@ -9094,11 +11702,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                     })
                                 }).unwrap_or(false);
                                 if !sig_ok {
-                                    println!("🚫 Rejected {:?} block from P2P: invalid signature", inc.block_type);
+                                    warn!("Rejected {:?} block from P2P: invalid signature", inc.block_type);
                                     continue;
                                 }
                                 if !inc.verify_pow() {
-                                    println!("🚫 Rejected {:?} block from P2P: invalid PoW", inc.block_type);
+                                    warn!("Rejected {:?} block from P2P: invalid PoW", inc.block_type);
                                     continue;
                                 }
                                 // Also verify the signing key maps to a known staked validator.
@@ -9113,12 +11721,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                         .unwrap_or_default();
                                     let is_validator = {
                                         let l = safe_lock(&ledger);
+                                        let min_stake = l.min_validator_stake_cil();
                                         l.accounts.get(&signer_addr)
-                                            .map(|a| a.balance >= MIN_VALIDATOR_STAKE_CIL)
+                                            .map(|a| a.balance >= min_stake)
                                             .unwrap_or(false)
                                     };
                                     if !is_validator {
-                                        println!("🚫 Rejected {:?} block from P2P: signer {} is not a staked validator", inc.block_type, get_short_addr(&signer_addr));
+                                        warn!("Rejected {:?} block from P2P: signer {} is not a staked validator", inc.block_type, get_short_addr(&signer_addr));
                                         continue;
                                     }
                                 }
@@ -9188,13 +11797,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
                                 let mut gossip = None;
                                 if double_sign_detected {
-                                    println!("🚨 DOUBLE-SIGNING DETECTED from {}! Slashing...", get_short_addr(&inc.account));
+                                    warn!("DOUBLE-SIGNING DETECTED from {}! Slashing...", get_short_addr(&inc.account));
 
                                     // Slash validator for double-signing (100%) via proper Slash block
                                     let staked_amount = l.accounts.get(&inc.account).map(|a| a.balance).unwrap_or(0);
                                     let mut sm = safe_lock(&slashing_clone);
-                                    if let Ok(slashed) = sm.slash_double_signing(&inc.account, l.blocks.len() as u64, staked_amount, timestamp) {
-                                        println!("⚖️ Validator {} slashed {} CIL (100%) for double-signing",
+                                    if let Ok(slashed) = sm.slash_double_signing(&inc.account, l.total_blocks() as u64, staked_amount, timestamp) {
+                                        info!("Validator {} slashed {} CIL (100%) for double-signing",
                                             get_short_addr(&inc.account), slashed);
                                         drop(sm);
 
@@ -9214,19 +11823,20 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                             work: 0,
                                             timestamp,
                                             fee: 0,
+                                            memo: None,
                                         };
                                         solve_pow(&mut slash_blk);
                                         slash_blk.signature = match try_sign_hex(slash_blk.signing_hash().as_bytes(), &secret_key) {
                                             Ok(sig) => sig,
-                                            Err(e) => { eprintln!("⚠️ Slash signing failed: {}", e); String::new() }
+                                            Err(e) => { warn!("Slash signing failed: {}", e); String::new() }
                                         };
                                         if !slash_blk.signature.is_empty() {
                                         match l.process_block(&slash_blk) {
                                             Ok(_) => {
                                                 gossip = Some(serde_json::to_string(&slash_blk).unwrap_or_default());
-                                                println!("⚖️ Slash block created and broadcast for {}", get_short_addr(&inc.account));
+                                                info!("Slash block created and broadcast for {}", get_short_addr(&inc.account));
                                             },
-                                            Err(e) => eprintln!("⚠️ Slash block failed: {}", e),
+                                            Err(e) => warn!("Slash block failed: {}", e),
                                         }
                                         }
                                         SAVE_DIRTY.store(true, Ordering::Relaxed);
@@ -9236,16 +11846,19 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                             }; // l dropped — Phase 1 complete
 
                             if let Some(msg) = ds_gossip {
+                                drop(_block); // not held across awaits
                                 let _ = tx_out.send(msg).await;
+                                _block = block_span.enter();
                             }
                             if double_sign_detected {
                                 continue; // Don't process the original block
                             }
 
                             // Phase 2: Process incoming block + tracking + auto-receive (all synchronous)
-                            let phase2_gossip: Vec<String> = {
+                            let (phase2_gossip, fork_detected) = {
                                 let mut l = safe_lock(&ledger);
                                 let mut msgs = Vec::new();
+                                let mut fork_detected = false;
 
                                 match l.process_block(&inc) {
                                     Ok(result) => {
@@ -9253,7 +11866,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                         // 🛡️ SLASHING INTEGRATION: Record block participation for uptime tracking
                                         {
                                             let mut sm = safe_lock(&slashing_clone);
-                                            let global_height = l.blocks.len() as u64;
+                                            let global_height = l.total_blocks() as u64;
                                             let _ = sm.record_block_participation(&inc.account, global_height, timestamp);
 
                                             // Check for downtime and slash if needed
@@ -9264,7 +11877,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                                     acc.balance,
                                                     timestamp
                                                 ) {
-                                                    println!("⚖️ Validator {} downtime penalty: {} CIL (1%)",
+                                                    info!("Validator {} downtime penalty: {} CIL (1%)",
                                                         get_short_addr(&inc.account), slashed);
 
                                                     // Create proper Slash block for downtime penalty
@@ -9282,11 +11895,12 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                                         work: 0,
                                                         timestamp,
                                                         fee: 0,
+                                                        memo: None,
                                                     };
                                                     solve_pow(&mut dt_slash);
                                                     dt_slash.signature = match try_sign_hex(dt_slash.signing_hash().as_bytes(), &secret_key) {
                                                         Ok(sig) => sig,
-                                                        Err(e) => { eprintln!("⚠️ Downtime slash signing failed: {}", e); String::new() }
+                                                        Err(e) => { warn!("Downtime slash signing failed: {}", e); String::new() }
                                                     };
                                                     if !dt_slash.signature.is_empty() && l.process_block(&dt_slash).is_ok() {
                                                         msgs.push(serde_json::to_string(&dt_slash).unwrap_or_default());
@@ -9297,21 +11911,22 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
                                         if inc.block_type == BlockType::Mint {
                                             let burn_val = inc.amount / CIL_PER_LOS;
-                                            println!("🔥 Network Mint Verified: +{} LOS", format_u128(burn_val));
+                                            info!("Network Mint Verified: +{} LOS", format_u128(burn_val));
                                         }
                                         SAVE_DIRTY.store(true, Ordering::Relaxed);
-                                        println!("✅ Block Verified: {:?} from {}", inc.block_type, get_short_addr(&inc.account));
+                                        info!("Block Verified: {:?} from {}", inc.block_type, get_short_addr(&inc.account));
 
                                         // AUTO-UNREGISTER: If a Send block caused sender's balance
                                         // to drop below minimum stake, unregister them as validator.
                                         if inc.block_type == BlockType::Send {
+                                            let min_stake = l.min_validator_stake_cil();
                                             if let Some(sender_acct) = l.accounts.get_mut(&inc.account) {
-                                                if sender_acct.is_validator && sender_acct.balance < MIN_VALIDATOR_STAKE_CIL {
+                                                if sender_acct.is_validator && sender_acct.balance < min_stake {
                                                     sender_acct.is_validator = false;
-                                                    println!("⚠️ Auto-unregistered validator {}: balance {} < minimum stake {} LOS",
+                                                    warn!("Auto-unregistered validator {}: balance {} < minimum stake {} LOS",
                                                         get_short_addr(&inc.account),
                                                         sender_acct.balance / CIL_PER_LOS,
-                                                        MIN_VALIDATOR_STAKE_CIL / CIL_PER_LOS);
+                                                        min_stake / CIL_PER_LOS);
                                                 }
                                             }
                                         }
@@ -9328,29 +11943,48 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                                     work: 0,
                                                     timestamp: std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_secs(),
                                                     fee: 0,
+                                                    memo: None,
                                                 };
                                                 solve_pow(&mut rb);
                                                 rb.signature = match try_sign_hex(rb.signing_hash().as_bytes(), &secret_key) {
                                                     Ok(sig) => sig,
-                                                    Err(e) => { eprintln!("⚠️ Auto-Receive signing failed: {}", e); String::new() }
+                                                    Err(e) => { warn!("Auto-Receive signing failed: {}", e); String::new() }
                                                 };
                                                 if !rb.signature.is_empty() && l.process_block(&rb).is_ok() {
                                                     SAVE_DIRTY.store(true, Ordering::Relaxed);
                                                     msgs.push(serde_json::to_string(&rb).unwrap_or_default());
-                                                    println!("📥 Incoming Transfer Received Automatically!");
+                                                    info!("Incoming Transfer Received Automatically!");
                                                 }
                                             }
                                         }
                                     },
                                     Err(e) => {
-                                        println!("❌ Block Rejected: {:?} (Sender: {})", e, get_short_addr(&inc.account));
+                                        // FORK CHOICE: A validly signed block competing with an applied one
+                                        // for the same predecessor is a fork, not just a bad sequence.
+                                        // Track it and ask validators to vote (CONFIRM_REQ → CONFIRM_RES).
+                                        if l.find_conflict(&inc).is_some() {
+                                            fork_detected = true;
+                                        } else {
+                                            warn!("Block Rejected: {:?} (Sender: {})", e, get_short_addr(&inc.account));
+                                        }
                                     }
                                 }
-                                msgs
+                                (msgs, fork_detected)
                             }; // l dropped — Phase 2 complete
+                            drop(_block);
                             for msg in phase2_gossip {
                                 let _ = tx_out.send(msg).await;
                             }
+                            _block = block_span.enter();
+                            let fork = if fork_detected { observe_fork(&ledger, &fork_choice, &metrics_clone, &inc, timestamp) } else { None };
+                            if let Some(opened) = fork {
+                                let mut msgs = if opened { fork_confirm_requests(&fork_choice, &inc.account, &inc.previous) } else { Vec::new() };
+                                msgs.extend(cast_fork_vote(&ledger, &fork_choice, &abft_event, &metrics_clone, &inc.account, &inc.previous, &my_address, &secret_key, &keys.public_key));
+                                drop(_block);
+                                for msg in msgs {
+                                    let _ = tx_out.send(msg).await;
+                                }
+                            }
                         }
                     }
             }
@@ -2,21 +2,41 @@
 // UNAUTHORITY (LOS) - TRANSACTION MEMPOOL
 //
 // Manages pending transactions before inclusion in blocks.
-// - Priority queue based on fees and stake
+// - Priority queue based on fees (stake breaks ties)
 // - Anti-spam protection with duplicate detection
+// - Replace-by-fee for pending blocks with the same `previous`
+// - Base fee that rises with mempool pressure
 // - Automatic transaction expiration
+// - Change journal for persistence across restarts
 // ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
 
 use los_core::Block;
 use serde::{Deserialize, Serialize};
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 /// Maximum transactions in mempool
 const MAX_MEMPOOL_SIZE: usize = 10_000;
 
 /// Transaction expires after 24 hours
-const TX_EXPIRATION_SECS: u64 = 86_400;
+pub const TX_EXPIRATION_SECS: u64 = 86_400;
+
+/// Minimum fee increase to replace a pending transaction (10%)
+pub const RBF_MIN_BUMP_BPS: u64 = 1_000;
+
+/// Mempool fill level (bps of capacity) up to which the plain base fee applies
+const FEE_PRESSURE_THRESHOLD_BPS: u64 = 2_500;
+
+/// Base fee multiplier at a full mempool (bps: 40_000 = 4×)
+const FULL_MEMPOOL_FEE_MULTIPLIER_BPS: u64 = 40_000;
+
+/// "Fast" fee targets a place among the next N transactions
+const FAST_FEE_WINDOW: usize = 100;
+
+/// Lowest fee that may replace a pending transaction paying `fee`
+pub fn replacement_fee(fee: u64) -> u64 {
+    fee.saturating_add((fee.saturating_mul(RBF_MIN_BUMP_BPS) / 10_000).max(1))
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MempoolTransaction {
@@ -31,18 +51,25 @@ pub struct Mempool {
     /// Transactions indexed by hash
     transactions: HashMap<String, MempoolTransaction>,
 
-    /// Priority queue: priority -> tx_hash
-    /// Higher priority = processed first
-    priority_queue: BTreeMap<u64, Vec<String>>,
+    /// Priority queue: (fee, priority) -> tx_hash
+    /// Higher fee = processed first, priority (stake) breaks ties
+    priority_queue: BTreeMap<(u64, u64), Vec<String>>,
 
     /// Track transactions by sender address
     by_sender: HashMap<String, Vec<String>>,
 
+    /// Chain slot (account, previous) -> tx_hash, for replace-by-fee
+    by_slot: HashMap<(String, String), String>,
+
+    /// Hashes added or removed since the last `take_changes` (persistence journal)
+    dirty: HashSet<String>,
+
     /// Statistics
     pub total_received: u64,
     pub total_accepted: u64,
     pub total_rejected: u64,
     pub total_expired: u64,
+    pub total_replaced: u64,
 }
 
 impl Mempool {
@@ -51,23 +78,56 @@ impl Mempool {
             transactions: HashMap::new(),
             priority_queue: BTreeMap::new(),
             by_sender: HashMap::new(),
+            by_slot: HashMap::new(),
+            dirty: HashSet::new(),
             total_received: 0,
             total_accepted: 0,
             total_rejected: 0,
             total_expired: 0,
+            total_replaced: 0,
         }
     }
 
     /// Add transaction to mempool
-    /// Returns Ok(tx_hash) if accepted, Err(reason) if rejected
+    /// Returns Ok(tx_hash) if accepted, Err(reason) if rejected.
+    /// A pending transaction with the same account and `previous` is replaced
+    /// when the new fee is at least `replacement_fee(old_fee)`.
     pub fn add_transaction(
         &mut self,
         block: Block,
         fee: u64,
         priority: u64,
     ) -> Result<String, String> {
+        let received_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        self.admit(MempoolTransaction {
+            block,
+            received_at,
+            priority,
+            fee,
+        })
+    }
+
+    /// Re-admit a persisted transaction, keeping its original receive time
+    pub fn restore(&mut self, tx: MempoolTransaction) -> Result<String, String> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        if now.saturating_sub(tx.received_at) > TX_EXPIRATION_SECS {
+            self.total_expired += 1;
+            return Err("Transaction expired".to_string());
+        }
+        self.admit(tx)
+    }
+
+    fn admit(&mut self, mempool_tx: MempoolTransaction) -> Result<String, String> {
         self.total_received += 1;
 
+        let block = &mempool_tx.block;
+        let (fee, priority) = (mempool_tx.fee, mempool_tx.priority);
         let tx_hash = block.calculate_hash();
 
         // Check if already in mempool
@@ -76,19 +136,6 @@ impl Mempool {
             return Err("Transaction already in mempool".to_string());
         }
 
-        // Check mempool size limit
-        if self.transactions.len() >= MAX_MEMPOOL_SIZE {
-            // Try to evict lowest priority transaction
-            if let Some(lowest_priority) = self.priority_queue.keys().next().cloned() {
-                if lowest_priority < priority {
-                    self.evict_lowest_priority();
-                } else {
-                    self.total_rejected += 1;
-                    return Err("Mempool full and transaction priority too low".to_string());
-                }
-            }
-        }
-
         // Validate basic block structure
         if block.account.is_empty() {
             self.total_rejected += 1;
@@ -100,32 +147,59 @@ impl Mempool {
             return Err("Invalid block: missing signature".to_string());
         }
 
-        // Create mempool transaction
-        let mempool_tx = MempoolTransaction {
-            block: block.clone(),
-            received_at: SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .unwrap_or_default()
-                .as_secs(),
-            priority,
-            fee,
-        };
+        // Replace-by-fee: only one pending block per chain slot
+        if let Some(old_hash) = self.conflicting_transaction(block) {
+            let old_fee = self
+                .transactions
+                .get(&old_hash)
+                .map(|tx| tx.fee)
+                .unwrap_or(0);
+            let min_fee = replacement_fee(old_fee);
+            if fee < min_fee {
+                self.total_rejected += 1;
+                return Err(format!(
+                    "Conflicts with pending transaction {} (fee {} CIL); a replacement must pay at least {} CIL",
+                    old_hash, old_fee, min_fee
+                ));
+            }
+            self.remove_transaction(&old_hash);
+            self.total_replaced += 1;
+        }
 
-        // Add to main storage
-        self.transactions.insert(tx_hash.clone(), mempool_tx);
+        // Check mempool size limit
+        if self.transactions.len() >= MAX_MEMPOOL_SIZE {
+            // Try to evict lowest priority transaction
+            if let Some(lowest) = self.priority_queue.keys().next().cloned() {
+                if lowest < (fee, priority) {
+                    self.evict_lowest_priority();
+                } else {
+                    self.total_rejected += 1;
+                    return Err("Mempool full and transaction fee too low".to_string());
+                }
+            }
+        }
 
         // Add to priority queue
         self.priority_queue
-            .entry(priority)
+            .entry((fee, priority))
             .or_default()
             .push(tx_hash.clone());
 
+        self.by_slot.insert(
+            (block.account.clone(), block.previous.clone()),
+            tx_hash.clone(),
+        );
+
         // Track by sender
         self.by_sender
             .entry(block.account.clone())
             .or_default()
             .push(tx_hash.clone());
 
+        // Add to main storage
+        self.transactions.insert(tx_hash.clone(), mempool_tx);
+        self.dirty.insert(tx_hash.clone());
+
         self.total_accepted += 1;
 
         Ok(tx_hash)
@@ -140,13 +214,20 @@ impl Mempool {
     pub fn remove_transaction(&mut self, tx_hash: &str) -> Option<MempoolTransaction> {
         if let Some(tx) = self.transactions.remove(tx_hash) {
             // Remove from priority queue
-            if let Some(hashes) = self.priority_queue.get_mut(&tx.priority) {
+            let key = (tx.fee, tx.priority);
+            if let Some(hashes) = self.priority_queue.get_mut(&key) {
                 hashes.retain(|h| h != tx_hash);
                 if hashes.is_empty() {
-                    self.priority_queue.remove(&tx.priority);
+                    self.priority_queue.remove(&key);
                 }
             }
 
+            let slot = (tx.block.account.clone(), tx.block.previous.clone());
+            if self.by_slot.get(&slot).map(String::as_str) == Some(tx_hash) {
+                self.by_slot.remove(&slot);
+            }
+            self.dirty.insert(tx_hash.to_string());
+
             // Remove from sender tracking
             if let Some(hashes) = self.by_sender.get_mut(&tx.block.account) {
                 hashes.retain(|h| h != tx_hash);
@@ -177,13 +258,98 @@ impl Mempool {
         result
     }
 
+    /// Pending transaction occupying the same chain slot (account + previous)
+    pub fn conflicting_transaction(&self, block: &Block) -> Option<String> {
+        self.by_slot
+            .get(&(block.account.clone(), block.previous.clone()))
+            .cloned()
+    }
+
+    /// Pending transaction a replace-by-fee of `block` would evict, given the
+    /// account's current ledger `head`. Only sends still queued behind another
+    /// pending block are replaceable: a block on `head` has already gone out
+    /// as CONFIRM_REQ and validators hold their vote lock on that slot, so a
+    /// replacement could never reach quorum.
+    pub fn replaceable_conflict(
+        &self,
+        block: &Block,
+        head: &str,
+    ) -> Result<Option<String>, String> {
+        match self.conflicting_transaction(block) {
+            Some(old_hash) if block.previous == head => Err(format!(
+                "Pending transaction {} is already awaiting confirmation and cannot be replaced",
+                old_hash
+            )),
+            conflict => Ok(conflict),
+        }
+    }
+
+    /// End of `account`'s chain of pending blocks starting at `head`:
+    /// (hash a new block builds on, `previous` of the last pending block).
+    /// Both are `head` when nothing is pending.
+    pub fn pending_tip(&self, account: &str, head: &str) -> (String, String) {
+        let (mut tip, mut before) = (head.to_string(), head.to_string());
+        while let Some(next) = self.by_slot.get(&(account.to_string(), tip.clone())) {
+            before = std::mem::replace(&mut tip, next.clone());
+        }
+        (tip, before)
+    }
+
+    /// Fill level in basis points of MAX_MEMPOOL_SIZE
+    pub fn pressure_bps(&self) -> u64 {
+        (self.transactions.len() as u64 * 10_000) / MAX_MEMPOOL_SIZE as u64
+    }
+
+    /// Base fee under the current mempool pressure.
+    /// Unchanged up to FEE_PRESSURE_THRESHOLD_BPS, then rising linearly to
+    /// FULL_MEMPOOL_FEE_MULTIPLIER_BPS; a full mempool also requires
+    /// outbidding its cheapest transaction.
+    pub fn dynamic_base_fee(&self, base_fee: u128) -> u128 {
+        let pressure = self.pressure_bps().min(10_000);
+        let multiplier_bps = if pressure <= FEE_PRESSURE_THRESHOLD_BPS {
+            10_000
+        } else {
+            10_000
+                + (pressure - FEE_PRESSURE_THRESHOLD_BPS)
+                    * (FULL_MEMPOOL_FEE_MULTIPLIER_BPS - 10_000)
+                    / (10_000 - FEE_PRESSURE_THRESHOLD_BPS)
+        };
+        let dynamic = base_fee.saturating_mul(multiplier_bps as u128) / 10_000;
+        if self.transactions.len() >= MAX_MEMPOOL_SIZE {
+            let cheapest = self
+                .priority_queue
+                .keys()
+                .next()
+                .map(|(fee, _)| *fee as u128)
+                .unwrap_or(0);
+            dynamic.max(cheapest.saturating_add(1))
+        } else {
+            dynamic
+        }
+    }
+
+    /// Fee that places a new transaction among the next FAST_FEE_WINDOW
+    pub fn fast_fee(&self, base_fee: u128) -> u128 {
+        let dynamic = self.dynamic_base_fee(base_fee);
+        let cutoff = self
+            .priority_queue
+            .iter()
+            .rev()
+            .flat_map(|((fee, _), hashes)| hashes.iter().map(move |_| *fee))
+            .nth(FAST_FEE_WINDOW - 1);
+        match cutoff {
+            Some(fee) => dynamic.max(fee as u128 + 1),
+            None => dynamic,
+        }
+    }
+
     /// Get all transactions from a sender
     pub fn get_transactions_by_sender(&self, address: &str) -> Vec<String> {
         self.by_sender.get(address).cloned().unwrap_or_default()
     }
 
-    /// Remove expired transactions (older than 24 hours)
-    pub fn remove_expired(&mut self) -> usize {
+    /// Remove expired transactions (older than 24 hours); returns the removed hashes
+    pub fn remove_expired(&mut self) -> Vec<String> {
         let now = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .unwrap_or_default()
@@ -196,13 +362,37 @@ impl Mempool {
             .map(|(hash, _)| hash.clone())
             .collect();
 
-        let count = expired.len();
-        for hash in expired {
-            self.remove_transaction(&hash);
+        for hash in &expired {
+            self.remove_transaction(hash);
         }
 
-        self.total_expired += count as u64;
-        count
+        self.total_expired += expired.len() as u64;
+        expired
+    }
+
+    /// Remove every transaction `keep` rejects; returns the removed hashes
+    pub fn retain(&mut self, mut keep: impl FnMut(&MempoolTransaction) -> bool) -> Vec<String> {
+        let dropped: Vec<String> = self
+            .transactions
+            .iter()
+            .filter(|(_, tx)| !keep(tx))
+            .map(|(hash, _)| hash.clone())
+            .collect();
+        for hash in &dropped {
+            self.remove_transaction(hash);
+        }
+        dropped
+    }
+
+    /// Drain the persistence journal: `(hash, Some(tx))` to store, `(hash, None)` to delete
+    pub fn take_changes(&mut self) -> Vec<(String, Option<MempoolTransaction>)> {
+        self.dirty
+            .drain()
+            .map(|hash| {
+                let tx = self.transactions.get(&hash).cloned();
+                (hash, tx)
+            })
+            .collect()
     }
 
     /// Evict lowest priority transaction
@@ -223,6 +413,7 @@ impl Mempool {
             total_accepted: self.total_accepted,
             total_rejected: self.total_rejected,
             total_expired: self.total_expired,
+            total_replaced: self.total_replaced,
             unique_senders: self.by_sender.len(),
         }
     }
@@ -244,9 +435,11 @@ impl Mempool {
 
     /// Clear all transactions
     pub fn clear(&mut self) {
+        self.dirty.extend(self.transactions.keys().cloned());
         self.transactions.clear();
         self.priority_queue.clear();
         self.by_sender.clear();
+        self.by_slot.clear();
     }
 }
 
@@ -257,6 +450,7 @@ pub struct MempoolStats {
     pub total_accepted: u64,
     pub total_rejected: u64,
     pub total_expired: u64,
+    pub total_replaced: u64,
     pub unique_senders: usize,
 }
 
@@ -277,6 +471,7 @@ mod tests {
             work: 0,
             timestamp: 1234567890,
             fee: 0,
+            memo: None,
         }
     }
 
@@ -340,7 +535,8 @@ mod tests {
         let mut mempool = Mempool::new();
 
         let block1 = create_test_block("sender1", 1000);
-        let block2 = create_test_block("sender1", 2000);
+        let mut block2 = create_test_block("sender1", 2000);
+        block2.previous = block1.calculate_hash();
         let block3 = create_test_block("sender2", 3000);
 
         mempool.add_transaction(block1, 100, 1000).unwrap();
@@ -353,4 +549,153 @@ mod tests {
         let sender2_txs = mempool.get_transactions_by_sender("sender2");
         assert_eq!(sender2_txs.len(), 1);
     }
+
+    #[test]
+    fn test_fee_orders_before_priority() {
+        let mut mempool = Mempool::new();
+        let rich = mempool
+            .add_transaction(create_test_block("whale", 1000), 100, 1_000_000)
+            .unwrap();
+        let payer = mempool
+            .add_transaction(create_test_block("small", 1000), 300, 1)
+            .unwrap();
+        assert_eq!(mempool.get_next_transactions(2), vec![payer, rich]);
+    }
+
+    #[test]
+    fn test_replace_by_fee() {
+        let mut mempool = Mempool::new();
+        let original = create_test_block("sender1", 1000);
+        let old_hash = mempool.add_transaction(original, 1_000, 10).unwrap();
+
+        // Same slot (account + previous), fee bump below 10% is rejected
+        let mut low = create_test_block("sender1", 1000);
+        low.timestamp += 1;
+        let err = mempool.add_transaction(low, 1_050, 10).unwrap_err();
+        assert!(err.contains(&old_hash));
+        assert!(mempool.contains(&old_hash));
+
+        let mut bumped = create_test_block("sender1", 1000);
+        bumped.timestamp += 2;
+        assert_eq!(
+            mempool.conflicting_transaction(&bumped),
+            Some(old_hash.clone())
+        );
+        let new_hash = mempool
+            .add_transaction(bumped, replacement_fee(1_000), 10)
+            .unwrap();
+        assert!(!mempool.contains(&old_hash));
+        assert_eq!(mempool.len(), 1);
+        assert_eq!(mempool.stats().total_replaced, 1);
+        assert_eq!(
+            mempool.get_transactions_by_sender("sender1"),
+            vec![new_hash]
+        );
+    }
+
+    #[test]
+    fn test_replace_by_fee_only_for_queued_sends() {
+        let mut mempool = Mempool::new();
+        let first = mempool
+            .add_transaction(create_test_block("sender1", 1000), 1_000, 10)
+            .unwrap();
+        let mut queued = create_test_block("sender1", 2000);
+        queued.previous = first.clone();
+        let queued = mempool.add_transaction(queued, 1_000, 10).unwrap();
+
+        // The block on the ledger head is out for confirmation
+        let mut on_head = create_test_block("sender1", 1000);
+        on_head.timestamp += 1;
+        let err = mempool.replaceable_conflict(&on_head, "0").unwrap_err();
+        assert!(err.contains(&first));
+
+        // The one queued behind it can still be bumped
+        let mut bump = create_test_block("sender1", 2000);
+        bump.previous = first.clone();
+        bump.timestamp += 1;
+        assert_eq!(mempool.replaceable_conflict(&bump, "0"), Ok(Some(queued)));
+        // No conflict at all
+        let fresh = create_test_block("sender2", 1000);
+        assert_eq!(mempool.replaceable_conflict(&fresh, "0"), Ok(None));
+    }
+
+    #[test]
+    fn test_pending_tip_follows_the_chain() {
+        let mut mempool = Mempool::new();
+        assert_eq!(
+            mempool.pending_tip("sender1", "0"),
+            ("0".to_string(), "0".to_string())
+        );
+
+        let first = mempool
+            .add_transaction(create_test_block("sender1", 1000), 1_000, 10)
+            .unwrap();
+        let mut second = create_test_block("sender1", 2000);
+        second.previous = first.clone();
+        let second = mempool.add_transaction(second, 1_000, 10).unwrap();
+        assert_eq!(mempool.pending_tip("sender1", "0"), (second, first));
+        // Other accounts and other heads are unaffected
+        assert_eq!(mempool.pending_tip("sender2", "0").0, "0");
+        assert_eq!(mempool.pending_tip("sender1", "other").0, "other");
+    }
+
+    #[test]
+    fn test_dynamic_base_fee() {
+        let mut mempool = Mempool::new();
+        assert_eq!(mempool.dynamic_base_fee(1_000), 1_000);
+        assert_eq!(mempool.fast_fee(1_000), 1_000);
+
+        for i in 0..MAX_MEMPOOL_SIZE {
+            let mut block = create_test_block(&format!("sender{}", i), 1000);
+            block.previous = i.to_string();
+            mempool.add_transaction(block, 1_000, 1).unwrap();
+        }
+        assert_eq!(mempool.pressure_bps(), 10_000);
+        assert_eq!(
+            mempool.dynamic_base_fee(1_000),
+            1_000 * FULL_MEMPOOL_FEE_MULTIPLIER_BPS as u128 / 10_000
+        );
+        assert_eq!(mempool.fast_fee(100), 1_001);
+
+        // A full mempool only admits transactions that outbid its cheapest entry
+        let cheap = create_test_block("late", 1000);
+        assert!(mempool.add_transaction(cheap.clone(), 1_000, 1).is_err());
+        assert!(mempool.add_transaction(cheap, 1_001, 1).is_ok());
+        assert_eq!(mempool.len(), MAX_MEMPOOL_SIZE);
+    }
+
+    #[test]
+    fn test_change_journal_and_restore() {
+        let mut mempool = Mempool::new();
+        let kept = mempool
+            .add_transaction(create_test_block("sender1", 1000), 100, 1)
+            .unwrap();
+        let dropped = mempool
+            .add_transaction(create_test_block("sender2", 1000), 100, 1)
+            .unwrap();
+        mempool.remove_transaction(&dropped);
+
+        let changes: HashMap<_, _> = mempool.take_changes().into_iter().collect();
+        assert_eq!(changes.len(), 2);
+        assert!(changes[&kept].is_some());
+        assert!(changes[&dropped].is_none());
+        assert!(mempool.take_changes().is_empty());
+
+        // A restored transaction keeps its receive time; stale ones are refused
+        let mut restored = Mempool::new();
+        let tx = changes[&kept].clone().unwrap();
+        assert_eq!(restored.restore(tx.clone()).unwrap(), kept);
+        assert_eq!(
+            restored.get_transaction(&kept).unwrap().received_at,
+            tx.received_at
+        );
+        let mut stale = tx;
+        stale.block.amount += 1;
+        stale.received_at -= TX_EXPIRATION_SECS + 1;
+        assert!(restored.restore(stale).is_err());
+
+        let removed = restored.retain(|tx| tx.block.account != "sender1");
+        assert_eq!(removed, vec![kept]);
+        assert!(restored.is_empty());
+    }
 }
@@ -75,6 +75,11 @@ pub struct LosMetrics {
     pub slashing_events_total: IntCounter,
     pub slashing_total_amount: Counter,
 
+    // Fork choice metrics
+    pub forks_detected_total: IntCounter,
+    pub forks_resolved_total: IntCounter,
+    pub fork_rollback_blocks_total: IntCounter,
+
     // Smart contract metrics
     pub contracts_deployed_total: IntCounter,
     pub contract_executions_total: IntCounter,
@@ -351,6 +356,25 @@ impl LosMetrics {
         ))?;
         registry.register(Box::new(slashing_total_amount.clone()))?;
 
+        // Fork choice metrics
+        let forks_detected_total = IntCounter::with_opts(Opts::new(
+            "los_forks_detected_total",
+            "Account-chain forks detected (competing blocks for one predecessor)",
+        ))?;
+        registry.register(Box::new(forks_detected_total.clone()))?;
+
+        let forks_resolved_total = IntCounter::with_opts(Opts::new(
+            "los_forks_resolved_total",
+            "Account-chain forks resolved by confirmation voting",
+        ))?;
+        registry.register(Box::new(forks_resolved_total.clone()))?;
+
+        let fork_rollback_blocks_total = IntCounter::with_opts(Opts::new(
+            "los_fork_rollback_blocks_total",
+            "Blocks rolled back while switching to a winning fork branch",
+        ))?;
+        registry.register(Box::new(fork_rollback_blocks_total.clone()))?;
+
         // Smart contract metrics
         let contracts_deployed_total = IntCounter::with_opts(Opts::new(
             "los_contracts_deployed_total",
@@ -439,6 +463,9 @@ impl LosMetrics {
             rate_limit_active_ips,
             slashing_events_total,
             slashing_total_amount,
+            forks_detected_total,
+            forks_resolved_total,
+            fork_rollback_blocks_total,
             contracts_deployed_total,
             contract_executions_total,
             contract_gas_used_total,
@@ -461,15 +488,16 @@ impl LosMetrics {
     /// Update blockchain metrics from current ledger state
     pub fn update_blockchain_metrics(&self, ledger: &los_core::Ledger) {
         self.blocks_total.reset();
-        self.blocks_total.inc_by(ledger.blocks.len() as u64);
+        self.blocks_total.inc_by(ledger.total_blocks() as u64);
 
         self.accounts_total.set(ledger.accounts.len() as i64);
 
         // Count active validators (registered + staked above minimum)
+        let min_stake = ledger.min_validator_stake_cil();
         let validator_count = ledger
             .accounts
             .iter()
-            .filter(|(_, a)| a.is_validator && a.balance >= los_core::MIN_VALIDATOR_STAKE_CIL)
+            .filter(|(_, a)| a.is_validator && a.balance >= min_stake)
             .count();
         self.active_validators.set(validator_count as i64);
 
@@ -487,6 +515,7 @@ impl LosMetrics {
                 los_core::BlockType::Slash => {} // Slash blocks counted separately via slashing manager
                 los_core::BlockType::ContractDeploy => {} // Counted via contracts_deployed_total
                 los_core::BlockType::ContractCall => {} // Counted via contract_executions_total
+                los_core::BlockType::Proposal | los_core::BlockType::Vote => {} // Governance, see /governance
             }
         }
 
@@ -128,33 +128,37 @@ impl TestnetConfig {
 static TESTNET_CONFIG: std::sync::LazyLock<TestnetConfig> = std::sync::LazyLock::new(|| {
     // MAINNET: Hardcoded to Production. No env var can weaken this.
     if los_core::is_mainnet_build() {
-        println!("🔒 MAINNET BUILD: All security enforced (consensus, signatures, anti-whale)");
-        println!("   Faucet: DISABLED | Consensus: ENABLED | Signatures: REQUIRED");
+        tracing::info!(
+            "MAINNET BUILD: All security enforced (consensus, signatures, anti-whale, no faucet)"
+        );
         return TestnetConfig::production_simulation();
     }
 
     // TESTNET: Allow level selection via environment variable
     match std::env::var("LOS_TESTNET_LEVEL").as_deref() {
         Ok("functional") => {
-            println!("🧪 TESTNET Level 1: Functional testing (instant finalization, mock burns)");
+            tracing::info!(
+                "TESTNET Level 1: Functional testing (instant finalization, mock burns)"
+            );
             TestnetConfig::functional()
         }
         Ok("consensus") => {
-            println!("🧪 TESTNET Level 2: Consensus testing (real aBFT, real signatures, oracle aggregation)");
+            tracing::info!("TESTNET Level 2: Consensus testing (real aBFT, real signatures, oracle aggregation)");
             TestnetConfig::consensus_testing()
         }
         Ok("production") => {
-            println!(
-                "🧪 TESTNET Level 3: Production simulation (identical to mainnet, full security)"
+            tracing::info!(
+                "TESTNET Level 3: Production simulation (identical to mainnet, full security)"
             );
             TestnetConfig::production_simulation()
         }
         _ => {
             // Default: Consensus testing — this is the minimum for multi-node testnet over Tor
             // Single-node dev should explicitly set LOS_TESTNET_LEVEL=functional
-            println!("🧪 TESTNET: Defaulting to Level 2 (Consensus) for multi-node testing");
-            println!("   Set LOS_TESTNET_LEVEL=functional for single-node dev mode");
-            println!("   Set LOS_TESTNET_LEVEL=production for mainnet-equivalent testing");
+            tracing::info!(
+                "TESTNET: Defaulting to Level 2 (Consensus) for multi-node testing \
+                 (LOS_TESTNET_LEVEL=functional for single-node dev, =production for mainnet-equivalent)"
+            );
             TestnetConfig::consensus_testing()
         }
     }
@@ -192,3 +196,27 @@ pub fn legacy_dev_mode() -> bool {
     // Backward compatibility with old DEV_MODE flag
     matches!(get_testnet_config().level, TestnetLevel::Functional)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use los_sim::{Behaviour, SimConfig, Simulation};
+
+    #[test]
+    fn test_production_simulation_survives_byzantine_validator() {
+        let config = TestnetConfig::production_simulation();
+        assert!(config.should_test_byzantine_behavior());
+
+        // 7 validators tolerate f = 2; the 2f+1 quorum must meet the configured threshold
+        let mut sim = Simulation::new(SimConfig::new(2026, 7));
+        let f = sim.node(0).abft.f_max_faulty as u64;
+        assert!((2 * f + 1) * 10_000 >= config.get_consensus_threshold_bps() as u64 * 7);
+
+        let leader = sim.leader_of(0);
+        sim.set_behaviour(leader, Behaviour::Equivocate);
+        sim.set_behaviour((leader + 1) % 7, Behaviour::Silent);
+
+        assert!(sim.run_until_height(5, 300_000));
+        sim.check_safety().unwrap();
+    }
+}
@@ -0,0 +1,251 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - TRANSACTION STATUS TRACKING
+//
+// Lifecycle of a block hash as seen by this node:
+//   received → in_mempool → awaiting_quorum → applied → final
+//                      └──────────┴──────────→ rejected (with reason)
+//
+// The ledger, mempool and pending-send map already hold the live states; this
+// tracker only remembers what they forget: hashes accepted for processing but
+// not yet admitted, and hashes the node rejected (bounded, kept for 24 hours).
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use std::collections::{HashMap, VecDeque};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Maximum remembered rejections (oldest evicted first)
+const MAX_REJECTED: usize = 10_000;
+
+/// Rejections are forgotten after 24 hours
+const REJECTED_RETENTION_SECS: u64 = 86_400;
+
+/// Received-but-unadmitted hashes are forgotten after 10 minutes
+const RECEIVED_RETENTION_SECS: u64 = 600;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TxState {
+    Received,
+    InMempool,
+    AwaitingQuorum,
+    Applied,
+    Final,
+    Rejected,
+}
+
+impl TxState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TxState::Received => "received",
+            TxState::InMempool => "in_mempool",
+            TxState::AwaitingQuorum => "awaiting_quorum",
+            TxState::Applied => "applied",
+            TxState::Final => "final",
+            TxState::Rejected => "rejected",
+        }
+    }
+}
+
+/// What the node's live structures know about a hash
+#[derive(Debug, Clone, Default)]
+pub struct TxObservation {
+    /// Ledger height the block was applied at if it is in the ledger
+    pub applied_height: Option<u64>,
+    /// Height of the latest finality checkpoint
+    pub checkpoint_height: Option<u64>,
+    /// Accumulated CONFIRM_RES power and distinct voters if awaiting quorum
+    pub votes: Option<(u128, usize)>,
+    pub in_mempool: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxStatus {
+    pub state: TxState,
+    pub account: Option<String>,
+    pub reason: Option<String>,
+    pub vote_power: u128,
+    pub voters: usize,
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    account: String,
+    at: u64,
+    reason: Option<String>,
+}
+
+#[derive(Debug, Default)]
+pub struct TxStatusTracker {
+    received: HashMap<String, Entry>,
+    rejected: HashMap<String, Entry>,
+    /// Rejection order for bounded eviction
+    rejected_order: VecDeque<String>,
+}
+
+impl TxStatusTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a hash the node accepted for processing
+    pub fn received(&mut self, hash: &str, account: &str) {
+        let now = now_secs();
+        self.received
+            .retain(|_, e| now.saturating_sub(e.at) <= RECEIVED_RETENTION_SECS);
+        self.received.insert(
+            hash.to_string(),
+            Entry {
+                account: account.to_string(),
+                at: now,
+                reason: None,
+            },
+        );
+    }
+
+    /// Record a rejection; a later rejection of the same hash keeps the first reason
+    pub fn rejected(&mut self, hash: &str, account: &str, reason: impl Into<String>) {
+        self.received.remove(hash);
+        if self.rejected.contains_key(hash) {
+            return;
+        }
+        let now = now_secs();
+        while let Some(oldest) = self.rejected_order.front() {
+            let expired = self
+                .rejected
+                .get(oldest)
+                .is_none_or(|e| now.saturating_sub(e.at) > REJECTED_RETENTION_SECS);
+            if !expired && self.rejected_order.len() < MAX_REJECTED {
+                break;
+            }
+            if let Some(oldest) = self.rejected_order.pop_front() {
+                self.rejected.remove(&oldest);
+            }
+        }
+        self.rejected.insert(
+            hash.to_string(),
+            Entry {
+                account: account.to_string(),
+                at: now,
+                reason: Some(reason.into()),
+            },
+        );
+        self.rejected_order.push_back(hash.to_string());
+    }
+
+    /// The hash moved on to the mempool or ledger
+    pub fn admitted(&mut self, hash: &str) {
+        self.received.remove(hash);
+    }
+
+    /// Resolve a hash: live structures first, then remembered rejections
+    pub fn status(&self, hash: &str, obs: &TxObservation) -> Option<TxStatus> {
+        let remembered = self.received.get(hash).or(self.rejected.get(hash));
+        let status = |state, (vote_power, voters)| TxStatus {
+            state,
+            account: remembered.map(|e| e.account.clone()),
+            reason: None,
+            vote_power,
+            voters,
+        };
+        if let Some(height) = obs.applied_height {
+            let is_final = obs.checkpoint_height.is_some_and(|cp| height <= cp);
+            let state = if is_final {
+                TxState::Final
+            } else {
+                TxState::Applied
+            };
+            return Some(status(state, (0, 0)));
+        }
+        if let Some(votes) = obs.votes {
+            return Some(status(TxState::AwaitingQuorum, votes));
+        }
+        if obs.in_mempool {
+            return Some(status(TxState::InMempool, (0, 0)));
+        }
+        if let Some(e) = self.rejected.get(hash) {
+            return Some(TxStatus {
+                reason: e.reason.clone(),
+                ..status(TxState::Rejected, (0, 0))
+            });
+        }
+        if self.received.contains_key(hash) {
+            return Some(status(TxState::Received, (0, 0)));
+        }
+        None
+    }
+
+    /// Remembered (received or rejected) hashes for an account, newest first
+    pub fn hashes_for_account(&self, account: &str) -> Vec<String> {
+        let mut entries: Vec<(&String, u64)> = self
+            .received
+            .iter()
+            .chain(self.rejected.iter())
+            .filter(|(_, e)| e.account == account)
+            .map(|(h, e)| (h, e.at))
+            .collect();
+        entries.sort_by_key(|(_, at)| std::cmp::Reverse(*at));
+        entries.into_iter().map(|(h, _)| h.clone()).collect()
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_state_resolution() {
+        let mut tracker = TxStatusTracker::new();
+        assert!(tracker.status("h1", &TxObservation::default()).is_none());
+
+        tracker.received("h1", "alice");
+        let received = tracker.status("h1", &TxObservation::default()).unwrap();
+        assert_eq!(received.state, TxState::Received);
+        assert_eq!(received.account.as_deref(), Some("alice"));
+
+        let mut obs = TxObservation {
+            in_mempool: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            tracker.status("h1", &obs).unwrap().state,
+            TxState::InMempool
+        );
+
+        obs.votes = Some((5_000, 2));
+        let awaiting = tracker.status("h1", &obs).unwrap();
+        assert_eq!(awaiting.state, TxState::AwaitingQuorum);
+        assert_eq!((awaiting.vote_power, awaiting.voters), (5_000, 2));
+
+        obs.applied_height = Some(1_001);
+        assert_eq!(tracker.status("h1", &obs).unwrap().state, TxState::Applied);
+        obs.checkpoint_height = Some(1_000);
+        assert_eq!(tracker.status("h1", &obs).unwrap().state, TxState::Applied);
+        obs.checkpoint_height = Some(2_000);
+        assert_eq!(tracker.status("h1", &obs).unwrap().state, TxState::Final);
+    }
+
+    #[test]
+    fn test_rejections() {
+        let mut tracker = TxStatusTracker::new();
+        tracker.received("h1", "alice");
+        tracker.rejected("h1", "alice", "Insufficient balance");
+        tracker.rejected("h1", "alice", "Expired");
+        let status = tracker.status("h1", &TxObservation::default()).unwrap();
+        assert_eq!(status.state, TxState::Rejected);
+        assert_eq!(status.reason.as_deref(), Some("Insufficient balance"));
+        assert_eq!(tracker.hashes_for_account("alice"), vec!["h1".to_string()]);
+        assert!(tracker.hashes_for_account("bob").is_empty());
+
+        for i in 0..MAX_REJECTED + 5 {
+            tracker.rejected(&format!("x{}", i), "bob", "spam");
+        }
+        assert_eq!(tracker.rejected.len(), MAX_REJECTED);
+        assert!(tracker.status("h1", &TxObservation::default()).is_none());
+    }
+}
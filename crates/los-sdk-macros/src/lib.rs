@@ -0,0 +1,622 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+//! # LOS SDK Macros
+//!
+//! Procedural macros behind `#[los_sdk::contract]`. Re-exported by `los-sdk`;
+//! contracts should not depend on this crate directly.
+//!
+//! `#[contract]` is applied to an inline module. Inside it:
+//! - `#[entry]` / `#[view]` functions become WASM exports with typed
+//!   argument decoding (`los_sdk::codec::FromArg`) and a JSON response
+//!   envelope (`los_sdk::codec::respond`)
+//! - `#[event(name = "...")]` structs get a JSON encoding and an `emit()` method
+//! - `#[json]` structs get a JSON encoding for use as return values;
+//!   `Option` fields that are `None` are left out
+//!
+//! The module also gains `ABI_JSON` (the contract ABI as a string), an
+//! `exports` module with each entry point as a plain `fn() -> i32` (what
+//! `los_sdk::mock::call` runs natively) and, on wasm32, a `los_abi` custom
+//! section holding the ABI, so deploys carry it without a separate file.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{
+    Attribute, Expr, Fields, FnArg, GenericArgument, Ident, Item, ItemFn, ItemMod, ItemStruct, Lit,
+    LitStr, Meta, Pat, PathArguments, ReturnType, Type,
+};
+
+/// Mark an inline module as a LOS contract. Accepts `name = "..."` for the ABI.
+#[proc_macro_attribute]
+pub fn contract(attr: TokenStream, item: TokenStream) -> TokenStream {
+    expand_contract(attr.into(), item.into())
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/// State-changing contract entry point. Only valid inside `#[contract]`.
+#[proc_macro_attribute]
+pub fn entry(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    outside_contract("entry", item)
+}
+
+/// Read-only contract entry point. Only valid inside `#[contract]`.
+#[proc_macro_attribute]
+pub fn view(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    outside_contract("view", item)
+}
+
+/// Event payload struct, `#[event(name = "...")]` (defaults to the struct
+/// name). Outside `#[contract]` it still gets `emit()`,
+/// but is not listed in any ABI.
+#[proc_macro_attribute]
+pub fn event(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let expanded = (|| {
+        let mut s: ItemStruct = syn::parse(item)?;
+        let name = parse_event_name(attr.into())?.unwrap_or_else(|| s.ident.to_string());
+        let spec = StructSpec::parse(&mut s, Some(name))?;
+        let impls = spec.impls();
+        Ok::<_, syn::Error>(quote! { #s #impls })
+    })();
+    expanded.unwrap_or_else(|e| e.to_compile_error()).into()
+}
+
+/// Struct encoded as a JSON object when returned from an entry point.
+#[proc_macro_attribute]
+pub fn json(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    let expanded = (|| {
+        let mut s: ItemStruct = syn::parse(item)?;
+        let spec = StructSpec::parse(&mut s, None)?;
+        let impls = spec.impls();
+        Ok::<_, syn::Error>(quote! { #s #impls })
+    })();
+    expanded.unwrap_or_else(|e| e.to_compile_error()).into()
+}
+
+fn outside_contract(kind: &str, item: TokenStream) -> TokenStream {
+    let item = TokenStream2::from(item);
+    let err = syn::Error::new(
+        Span::call_site(),
+        format!(
+            "#[{}] is only valid inside a #[los_sdk::contract] module",
+            kind
+        ),
+    )
+    .to_compile_error();
+    quote! { #err #item }.into()
+}
+
+// ─────────────────────────────────────────────────────────────────
+// #[contract] expansion
+// ─────────────────────────────────────────────────────────────────
+
+fn expand_contract(attr: TokenStream2, item: TokenStream2) -> syn::Result<TokenStream2> {
+    let mut contract_name = String::new();
+    let parser = syn::meta::parser(|meta| {
+        if meta.path.is_ident("name") {
+            contract_name = meta.value()?.parse::<LitStr>()?.value();
+            Ok(())
+        } else {
+            Err(meta.error("unsupported contract attribute (expected `name = \"...\"`)"))
+        }
+    });
+    syn::parse::Parser::parse2(parser, attr)?;
+
+    let mut module: ItemMod = syn::parse2(item)?;
+    let Some((_, items)) = module.content.as_mut() else {
+        return Err(syn::Error::new_spanned(
+            &module,
+            "#[contract] requires an inline module: `mod name { ... }`",
+        ));
+    };
+
+    let mut functions = Vec::new();
+    let mut structs = Vec::new();
+    for item in items.iter_mut() {
+        match item {
+            Item::Fn(f) => {
+                if let Some(view) =
+                    take_marker(&mut f.attrs, &["entry", "view"]).map(|kind| kind == "view")
+                {
+                    functions.push(FnSpec::parse(f, view)?);
+                }
+            }
+            Item::Struct(s) => {
+                if let Some(attr) = take_attr(&mut s.attrs, "event") {
+                    let name = match &attr.meta {
+                        Meta::Path(_) => None,
+                        _ => parse_event_name(attr.meta.require_list()?.tokens.clone())?,
+                    }
+                    .unwrap_or_else(|| s.ident.to_string());
+                    structs.push(StructSpec::parse(s, Some(name))?);
+                } else if take_attr(&mut s.attrs, "json").is_some() {
+                    structs.push(StructSpec::parse(s, None)?);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut names = std::collections::BTreeSet::new();
+    for f in &functions {
+        if !names.insert(f.ident.to_string()) {
+            return Err(syn::Error::new_spanned(&f.ident, "duplicate entry point"));
+        }
+    }
+
+    let abi = abi_json(&contract_name, &functions, &structs);
+    let exports = functions.iter().map(FnSpec::export);
+    let entries = functions.iter().map(|f| {
+        let ident = &f.ident;
+        let entry_fn = format_ident!("__los_entry_{}", ident);
+        quote! { pub use super::#entry_fn as #ident; }
+    });
+    let impls = structs.iter().map(StructSpec::impls);
+    let generated: Vec<Item> = vec![
+        syn::parse2(quote! {
+            /// Contract ABI generated by `#[los_sdk::contract]`.
+            pub const ABI_JSON: &str = #abi;
+        })?,
+        syn::parse2(quote! {
+            ::los_sdk::embed_abi!(ABI_JSON);
+        })?,
+        syn::parse2(quote! {
+            /// Entry points as plain functions (decode args, call, set the
+            /// response), e.g. for `los_sdk::mock::call` in native tests.
+            pub mod exports {
+                #(#entries)*
+            }
+        })?,
+    ];
+    items.extend(generated);
+    for tokens in exports.chain(impls) {
+        items.push(Item::Verbatim(tokens));
+    }
+
+    Ok(quote! { #module })
+}
+
+/// Remove the first attribute whose last path segment is one of `kinds`.
+fn take_marker(attrs: &mut Vec<Attribute>, kinds: &[&str]) -> Option<String> {
+    let idx = attrs.iter().position(|a| {
+        a.path()
+            .segments
+            .last()
+            .is_some_and(|s| kinds.iter().any(|k| s.ident == k))
+    })?;
+    let attr = attrs.remove(idx);
+    attr.path().segments.last().map(|s| s.ident.to_string())
+}
+
+fn take_attr(attrs: &mut Vec<Attribute>, kind: &str) -> Option<Attribute> {
+    let idx = attrs
+        .iter()
+        .position(|a| a.path().segments.last().is_some_and(|s| s.ident == kind))?;
+    Some(attrs.remove(idx))
+}
+
+/// `name = "..."` from `#[event(...)]`; `None` means use the struct name.
+fn parse_event_name(tokens: TokenStream2) -> syn::Result<Option<String>> {
+    let mut name = None;
+    let parser = syn::meta::parser(|meta| {
+        if meta.path.is_ident("name") {
+            name = Some(meta.value()?.parse::<LitStr>()?.value());
+            Ok(())
+        } else {
+            Err(meta.error("unsupported event attribute (expected `name = \"...\"`)"))
+        }
+    });
+    syn::parse::Parser::parse2(parser, tokens)?;
+    Ok(name)
+}
+
+/// Collect `///` doc comments into a single line.
+fn doc_string(attrs: &[Attribute]) -> String {
+    let lines: Vec<String> = attrs
+        .iter()
+        .filter(|a| a.path().is_ident("doc"))
+        .filter_map(|a| match &a.meta {
+            Meta::NameValue(nv) => match &nv.value {
+                Expr::Lit(l) => match &l.lit {
+                    Lit::Str(s) => Some(s.value().trim().to_string()),
+                    _ => None,
+                },
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect();
+    // First paragraph only — the rest is implementation detail
+    lines
+        .split(|l| l.is_empty())
+        .find(|p| !p.is_empty())
+        .map(|p| p.join(" "))
+        .unwrap_or_default()
+}
+
+// ─────────────────────────────────────────────────────────────────
+// Entry points
+// ─────────────────────────────────────────────────────────────────
+
+enum Returns {
+    Unit,
+    Plain(Type),
+    Result(Type),
+}
+
+struct Param {
+    ident: Ident,
+    ty: Type,
+    abi_ty: &'static str,
+    optional: bool,
+}
+
+struct FnSpec {
+    ident: Ident,
+    view: bool,
+    params: Vec<Param>,
+    returns: Returns,
+    doc: String,
+}
+
+impl FnSpec {
+    fn parse(f: &ItemFn, view: bool) -> syn::Result<Self> {
+        let sig = &f.sig;
+        if !sig.generics.params.is_empty() || sig.asyncness.is_some() {
+            return Err(syn::Error::new_spanned(
+                sig,
+                "contract entry points cannot be generic or async",
+            ));
+        }
+
+        let mut params: Vec<Param> = Vec::new();
+        for input in &sig.inputs {
+            let FnArg::Typed(pt) = input else {
+                return Err(syn::Error::new_spanned(
+                    input,
+                    "entry points take no `self`",
+                ));
+            };
+            let Pat::Ident(pi) = &*pt.pat else {
+                return Err(syn::Error::new_spanned(
+                    &pt.pat,
+                    "expected a plain argument name",
+                ));
+            };
+            let (inner, optional) = match generic_inner(&pt.ty, "Option") {
+                Some(inner) => (inner, true),
+                None => (&*pt.ty, false),
+            };
+            let abi_ty = scalar_abi_type(inner).ok_or_else(|| {
+                syn::Error::new_spanned(
+                    &pt.ty,
+                    "unsupported argument type; use String, Address, Json, u128, u64, u32, u16, u8, bool or Option<_> of these",
+                )
+            })?;
+            if !optional && params.iter().any(|p| p.optional) {
+                return Err(syn::Error::new_spanned(
+                    &pt.ty,
+                    "required arguments cannot follow Option<_> arguments",
+                ));
+            }
+            params.push(Param {
+                ident: pi.ident.clone(),
+                ty: (*pt.ty).clone(),
+                abi_ty,
+                optional,
+            });
+        }
+
+        let returns = match &sig.output {
+            ReturnType::Default => Returns::Unit,
+            ReturnType::Type(_, ty) if is_unit(ty) => Returns::Unit,
+            ReturnType::Type(_, ty) => match result_ok_type(ty) {
+                Some(ok) => Returns::Result(ok.clone()),
+                None => Returns::Plain((**ty).clone()),
+            },
+        };
+
+        Ok(FnSpec {
+            ident: sig.ident.clone(),
+            view,
+            params,
+            returns,
+            doc: doc_string(&f.attrs),
+        })
+    }
+
+    fn export(&self) -> TokenStream2 {
+        let ident = &self.ident;
+        let export_name = ident.to_string();
+        let export_fn = format_ident!("__los_export_{}", ident);
+        let entry_fn = format_ident!("__los_entry_{}", ident);
+        let decode = self.params.iter().enumerate().map(|(i, p)| {
+            let (id, ty) = (&p.ident, &p.ty);
+            let name = id.to_string();
+            let idx = i as u32;
+            quote! {
+                let #id = <#ty as ::los_sdk::codec::FromArg>::from_arg(#name, ::los_sdk::arg(#idx))?;
+            }
+        });
+        let ids = self.params.iter().map(|p| &p.ident);
+        let call = quote! { #ident(#(#ids),*) };
+        let value = match self.returns {
+            Returns::Unit => quote! { #call; let __value = (); },
+            Returns::Plain(_) => quote! { let __value = #call; },
+            Returns::Result(_) => quote! {
+                let __value = #call.map_err(::los_sdk::codec::error_msg)?;
+            },
+        };
+        let encode = if self.view {
+            quote! { ::los_sdk::codec::data_field(&__value) }
+        } else {
+            quote! { ::los_sdk::codec::ToJson::to_json(&__value) }
+        };
+
+        quote! {
+            #[doc(hidden)]
+            pub fn #entry_fn() -> i32 {
+                let __outcome = (|| -> ::los_sdk::codec::Outcome {
+                    #(#decode)*
+                    #value
+                    Ok(#encode)
+                })();
+                ::los_sdk::codec::respond(__outcome)
+            }
+
+            #[cfg(target_arch = "wasm32")]
+            #[export_name = #export_name]
+            #[doc(hidden)]
+            pub extern "C" fn #export_fn() -> i32 {
+                #entry_fn()
+            }
+        }
+    }
+
+    fn return_abi_type(&self) -> Option<&'static str> {
+        match &self.returns {
+            Returns::Unit => None,
+            Returns::Plain(ty) | Returns::Result(ty) => {
+                if is_unit(ty) {
+                    return None;
+                }
+                let inner = generic_inner(ty, "Option").unwrap_or(ty);
+                Some(scalar_abi_type(inner).unwrap_or("json"))
+            }
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────
+// Event / JSON structs
+// ─────────────────────────────────────────────────────────────────
+
+struct StructSpec {
+    ident: Ident,
+    fields: Vec<(Ident, &'static str, bool)>,
+    event: Option<String>,
+}
+
+impl StructSpec {
+    fn parse(s: &mut ItemStruct, event: Option<String>) -> syn::Result<Self> {
+        if !s.generics.params.is_empty() {
+            return Err(syn::Error::new_spanned(
+                &s.generics,
+                "JSON structs cannot be generic",
+            ));
+        }
+        let fields = match &s.fields {
+            Fields::Named(named) => named
+                .named
+                .iter()
+                .map(|f| {
+                    let ty = strip_ref(&f.ty);
+                    let (inner, optional) = match generic_inner(ty, "Option") {
+                        Some(inner) => (strip_ref(inner), true),
+                        None => (ty, false),
+                    };
+                    let abi_ty = scalar_abi_type(inner).unwrap_or("json");
+                    (f.ident.clone().expect("named field"), abi_ty, optional)
+                })
+                .collect(),
+            Fields::Unit => Vec::new(),
+            Fields::Unnamed(_) => {
+                return Err(syn::Error::new_spanned(
+                    &s.fields,
+                    "JSON structs need named fields",
+                ))
+            }
+        };
+        Ok(StructSpec {
+            ident: s.ident.clone(),
+            fields,
+            event,
+        })
+    }
+
+    fn impls(&self) -> TokenStream2 {
+        let ident = &self.ident;
+        // `None` optional fields are omitted rather than written as null
+        let writes = self.fields.iter().map(|(f, _, optional)| {
+            let key = f.to_string();
+            if *optional {
+                quote! {
+                    if let Some(__v) = &self.#f {
+                        ::los_sdk::codec::write_field(out, __first, #key, __v);
+                        __first = false;
+                    }
+                }
+            } else {
+                quote! {
+                    ::los_sdk::codec::write_field(out, __first, #key, &self.#f);
+                    __first = false;
+                }
+            }
+        });
+        let emit = self.event.as_ref().map(|name| {
+            quote! {
+                impl #ident {
+                    /// Event type name as emitted.
+                    pub const EVENT_NAME: &'static str = #name;
+
+                    /// Emit this event from the current contract call.
+                    pub fn emit(&self) {
+                        ::los_sdk::event::emit(#name, &::los_sdk::codec::ToJson::to_json(self));
+                    }
+                }
+            }
+        });
+        quote! {
+            impl ::los_sdk::codec::ToJson for #ident {
+                fn write_json(&self, out: &mut ::los_sdk::codec::String) {
+                    let mut __first = true;
+                    out.push('{');
+                    #(#writes)*
+                    let _ = __first;
+                    out.push('}');
+                }
+            }
+            #emit
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────
+// Type mapping
+// ─────────────────────────────────────────────────────────────────
+
+/// ABI type for a directly supported scalar, by the type's last path segment.
+fn scalar_abi_type(ty: &Type) -> Option<&'static str> {
+    let Type::Path(tp) = strip_ref(ty) else {
+        return None;
+    };
+    let seg = tp.path.segments.last()?;
+    if !matches!(seg.arguments, PathArguments::None) {
+        return None;
+    }
+    Some(match seg.ident.to_string().as_str() {
+        "String" | "str" => "string",
+        "Address" => "address",
+        "u128" => "u128",
+        "u64" | "u32" | "u16" | "u8" => "u64",
+        "bool" => "bool",
+        "Json" => "json",
+        _ => return None,
+    })
+}
+
+/// `T` in `Wrapper<T, ...>` when the last path segment is `wrapper`.
+fn generic_inner<'a>(ty: &'a Type, wrapper: &str) -> Option<&'a Type> {
+    first_type_arg(ty, |ident| ident == wrapper)
+}
+
+/// Ok type of `Result<T, E>` or of an alias named `*Result<T>` (`CallResult<T>`,
+/// `io::Result<T>`). Matched by name since macros cannot resolve aliases.
+fn result_ok_type(ty: &Type) -> Option<&Type> {
+    first_type_arg(ty, |ident| ident.ends_with("Result"))
+}
+
+fn first_type_arg(ty: &Type, matches: impl Fn(&str) -> bool) -> Option<&Type> {
+    let Type::Path(tp) = ty else {
+        return None;
+    };
+    let seg = tp.path.segments.last()?;
+    if !matches(&seg.ident.to_string()) {
+        return None;
+    }
+    let PathArguments::AngleBracketed(args) = &seg.arguments else {
+        return None;
+    };
+    match args.args.first()? {
+        GenericArgument::Type(inner) => Some(inner),
+        _ => None,
+    }
+}
+
+fn strip_ref(ty: &Type) -> &Type {
+    match ty {
+        Type::Reference(r) => strip_ref(&r.elem),
+        other => other,
+    }
+}
+
+fn is_unit(ty: &Type) -> bool {
+    matches!(ty, Type::Tuple(t) if t.elems.is_empty())
+}
+
+// ─────────────────────────────────────────────────────────────────
+// ABI JSON
+// ─────────────────────────────────────────────────────────────────
+
+fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn param_json(name: &str, ty: &str, optional: bool) -> String {
+    let opt = if optional { r#","optional":true"# } else { "" };
+    format!(r#"{{"name":"{}","type":"{}"{}}}"#, escape(name), ty, opt)
+}
+
+/// Serialized in the field order and omission rules of `los_core::abi::ContractAbi`.
+fn abi_json(name: &str, functions: &[FnSpec], structs: &[StructSpec]) -> String {
+    let fns: Vec<String> = functions
+        .iter()
+        .map(|f| {
+            let args: Vec<String> = f
+                .params
+                .iter()
+                .map(|p| param_json(&p.ident.to_string(), p.abi_ty, p.optional))
+                .collect();
+            let mut out = format!(
+                r#"{{"name":"{}","args":[{}],"view":{}"#,
+                f.ident,
+                args.join(","),
+                f.view
+            );
+            if let Some(ret) = f.return_abi_type() {
+                out.push_str(&format!(r#","returns":"{}""#, ret));
+            }
+            if !f.doc.is_empty() {
+                out.push_str(&format!(r#","doc":"{}""#, escape(&f.doc)));
+            }
+            out.push('}');
+            out
+        })
+        .collect();
+    let events: Vec<String> = structs
+        .iter()
+        .filter_map(|s| {
+            let name = s.event.as_ref()?;
+            let fields: Vec<String> = s
+                .fields
+                .iter()
+                .map(|(f, ty, opt)| param_json(&f.to_string(), ty, *opt))
+                .collect();
+            Some(format!(
+                r#"{{"name":"{}","fields":[{}]}}"#,
+                escape(name),
+                fields.join(",")
+            ))
+        })
+        .collect();
+
+    let name_field = if name.is_empty() {
+        String::new()
+    } else {
+        format!(r#","name":"{}""#, escape(name))
+    };
+    format!(
+        r#"{{"version":1{},"functions":[{}],"events":[{}]}}"#,
+        name_field,
+        fns.join(","),
+        events.join(",")
+    )
+}
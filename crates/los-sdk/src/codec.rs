@@ -0,0 +1,443 @@
+//! Argument decoding and JSON encoding for contract entry points.
+//!
+//! Used by the code `#[los_sdk::contract]` generates, and usable directly:
+//! - [`FromArg`] turns a positional string arg into a typed value
+//! - [`ToJson`] writes return values and event payloads
+//! - [`respond`] wraps an entry point's outcome in the standard envelope
+//!
+//! Numeric conventions match contract state: `u128` travels as a quoted
+//! decimal string (JSON numbers lose precision above 2^53), smaller
+//! integers as bare numbers.
+
+use alloc::format;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+
+// Re-exported so generated code can name it from a `no_std` crate
+#[doc(hidden)]
+pub use alloc::string::String;
+
+/// Result of an entry point before it is wrapped by [`respond`]:
+/// the JSON payload, or an error message.
+pub type Outcome = Result<String, String>;
+
+// ─────────────────────────────────────────────────────────────────
+// Numeric / string helpers
+// ─────────────────────────────────────────────────────────────────
+
+/// Parse a decimal string to u128 with overflow protection.
+/// Returns 0 on empty string, non-digit chars, or overflow.
+pub fn parse_u128(s: &str) -> u128 {
+    checked_parse_u128(s).unwrap_or(0)
+}
+
+/// Parse a decimal string to u64 with overflow protection.
+/// Returns 0 on empty string, non-digit chars, or overflow.
+pub fn parse_u64(s: &str) -> u64 {
+    checked_parse_u128(s)
+        .and_then(|v| u64::try_from(v).ok())
+        .unwrap_or(0)
+}
+
+/// Strict decimal parse: `None` on empty input, non-digits or overflow.
+pub fn checked_parse_u128(s: &str) -> Option<u128> {
+    if s.is_empty() {
+        return None;
+    }
+    let mut result: u128 = 0;
+    for b in s.as_bytes() {
+        if !b.is_ascii_digit() {
+            return None;
+        }
+        result = result.checked_mul(10)?.checked_add((*b - b'0') as u128)?;
+    }
+    Some(result)
+}
+
+/// Convert u128 to a decimal string.
+pub fn u128_to_str(val: u128) -> String {
+    if val == 0 {
+        return String::from("0");
+    }
+    let mut buf = [0u8; 40];
+    let mut pos = buf.len();
+    let mut v = val;
+    while v > 0 {
+        pos -= 1;
+        buf[pos] = b'0' + (v % 10) as u8;
+        v /= 10;
+    }
+    // Only ASCII digits were written
+    String::from_utf8(buf[pos..].to_vec()).unwrap_or_default()
+}
+
+/// Escape a string for inclusion in a JSON string literal.
+pub fn json_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 8);
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+// ─────────────────────────────────────────────────────────────────
+// Typed wrappers
+// ─────────────────────────────────────────────────────────────────
+
+/// A non-empty account or contract address argument.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub struct Address(String);
+
+impl Address {
+    pub fn new(s: String) -> Self {
+        Address(s)
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+
+impl core::ops::Deref for Address {
+    type Target = str;
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl core::fmt::Display for Address {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<String> for Address {
+    fn from(s: String) -> Self {
+        Address(s)
+    }
+}
+
+impl PartialEq<str> for Address {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<String> for Address {
+    fn eq(&self, other: &String) -> bool {
+        &self.0 == other
+    }
+}
+
+/// A pre-encoded JSON document, written verbatim.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Json(pub String);
+
+// ─────────────────────────────────────────────────────────────────
+// Argument decoding
+// ─────────────────────────────────────────────────────────────────
+
+/// Decode a positional string argument. `raw` is `None` when the caller
+/// passed fewer args; `name` is only used in error messages.
+pub trait FromArg: Sized {
+    fn from_arg(name: &str, raw: Option<String>) -> Result<Self, String>;
+}
+
+fn required(name: &str, raw: Option<String>) -> Result<String, String> {
+    raw.ok_or_else(|| format!("missing argument '{}'", name))
+}
+
+fn expected(name: &str, ty: &str, value: &str) -> String {
+    format!("{}: expected {}, got '{}'", name, ty, json_escape(value))
+}
+
+impl FromArg for String {
+    fn from_arg(name: &str, raw: Option<String>) -> Result<Self, String> {
+        required(name, raw)
+    }
+}
+
+impl FromArg for Address {
+    fn from_arg(name: &str, raw: Option<String>) -> Result<Self, String> {
+        match required(name, raw)? {
+            s if s.is_empty() => Err(format!("{} address required", name)),
+            s => Ok(Address(s)),
+        }
+    }
+}
+
+impl FromArg for u128 {
+    fn from_arg(name: &str, raw: Option<String>) -> Result<Self, String> {
+        let s = required(name, raw)?;
+        checked_parse_u128(&s).ok_or_else(|| expected(name, "u128", &s))
+    }
+}
+
+macro_rules! from_arg_uint {
+    ($($t:ty),*) => {$(
+        impl FromArg for $t {
+            fn from_arg(name: &str, raw: Option<String>) -> Result<Self, String> {
+                let s = required(name, raw)?;
+                checked_parse_u128(&s)
+                    .and_then(|v| <$t>::try_from(v).ok())
+                    .ok_or_else(|| expected(name, stringify!($t), &s))
+            }
+        }
+    )*};
+}
+from_arg_uint!(u64, u32, u16, u8);
+
+impl FromArg for bool {
+    fn from_arg(name: &str, raw: Option<String>) -> Result<Self, String> {
+        let s = required(name, raw)?;
+        match s.as_str() {
+            "1" | "true" => Ok(true),
+            "0" | "false" => Ok(false),
+            _ => Err(expected(name, "bool", &s)),
+        }
+    }
+}
+
+/// Optional arguments: missing or empty decodes to `None`.
+impl<T: FromArg> FromArg for Option<T> {
+    fn from_arg(name: &str, raw: Option<String>) -> Result<Self, String> {
+        match raw {
+            None => Ok(None),
+            Some(s) if s.is_empty() => Ok(None),
+            Some(s) => T::from_arg(name, Some(s)).map(Some),
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────
+// JSON encoding
+// ─────────────────────────────────────────────────────────────────
+
+/// Append a JSON encoding of `self` to `out`.
+pub trait ToJson {
+    fn write_json(&self, out: &mut String);
+
+    fn to_json(&self) -> String {
+        let mut out = String::new();
+        self.write_json(&mut out);
+        out
+    }
+}
+
+impl ToJson for str {
+    fn write_json(&self, out: &mut String) {
+        out.push('"');
+        out.push_str(&json_escape(self));
+        out.push('"');
+    }
+}
+
+impl ToJson for String {
+    fn write_json(&self, out: &mut String) {
+        self.as_str().write_json(out)
+    }
+}
+
+impl ToJson for Address {
+    fn write_json(&self, out: &mut String) {
+        self.0.write_json(out)
+    }
+}
+
+impl ToJson for Json {
+    fn write_json(&self, out: &mut String) {
+        out.push_str(&self.0);
+    }
+}
+
+impl ToJson for u128 {
+    fn write_json(&self, out: &mut String) {
+        out.push('"');
+        out.push_str(&u128_to_str(*self));
+        out.push('"');
+    }
+}
+
+macro_rules! to_json_display {
+    ($($t:ty),*) => {$(
+        impl ToJson for $t {
+            fn write_json(&self, out: &mut String) {
+                out.push_str(&self.to_string());
+            }
+        }
+    )*};
+}
+to_json_display!(u64, u32, u16, u8, i64, i32, bool);
+
+impl ToJson for () {
+    fn write_json(&self, out: &mut String) {
+        out.push_str("null");
+    }
+}
+
+impl<T: ToJson> ToJson for Option<T> {
+    fn write_json(&self, out: &mut String) {
+        match self {
+            Some(v) => v.write_json(out),
+            None => out.push_str("null"),
+        }
+    }
+}
+
+impl<T: ToJson> ToJson for Vec<T> {
+    fn write_json(&self, out: &mut String) {
+        out.push('[');
+        for (i, v) in self.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            v.write_json(out);
+        }
+        out.push(']');
+    }
+}
+
+impl<T: ToJson + ?Sized> ToJson for &T {
+    fn write_json(&self, out: &mut String) {
+        (**self).write_json(out)
+    }
+}
+
+/// Writes `"key":value` (with a leading comma unless first). Used by
+/// generated `ToJson` impls for `#[event]` / `#[json]` structs, and handy
+/// for hand-written ones (e.g. generic wrappers the macros can't derive).
+pub fn write_field(out: &mut String, first: bool, key: &str, value: &dyn ToJson) {
+    if !first {
+        out.push(',');
+    }
+    out.push('"');
+    out.push_str(key);
+    out.push_str("\":");
+    value.write_json(out);
+}
+
+// ─────────────────────────────────────────────────────────────────
+// Response envelope
+// ─────────────────────────────────────────────────────────────────
+
+/// Error message for an entry point's `Err(e)`.
+#[doc(hidden)]
+pub fn error_msg<E: core::fmt::Display>(e: E) -> String {
+    e.to_string()
+}
+
+/// `{"data":...}` — how `#[view]` results are returned, so query output
+/// keeps the `{"success":true,"data":...}` shape.
+pub fn data_field<T: ToJson + ?Sized>(value: &T) -> String {
+    let mut out = String::from("{");
+    write_field(&mut out, true, "data", &value);
+    out.push('}');
+    out
+}
+
+/// Set the return data for an entry point outcome and return its exit code.
+///
+/// - `Ok` object payload → `{"success":true, ...fields}`
+/// - `Ok` other payload  → `{"success":true,"data":...}` (`null` is omitted)
+/// - `Err(msg)`          → `{"success":false,"msg":"..."}`, exit code 1 (state reverted)
+pub fn respond(outcome: Outcome) -> i32 {
+    let (body, code) = envelope(outcome);
+    crate::set_return_str(&body);
+    code
+}
+
+/// The JSON body and exit code [`respond`] would produce.
+pub fn envelope(outcome: Outcome) -> (String, i32) {
+    match outcome {
+        Ok(payload) => {
+            let body = if payload == "null" {
+                String::from(r#"{"success":true}"#)
+            } else if let Some(fields) = payload.strip_prefix('{') {
+                if fields.trim_start() == "}" {
+                    String::from(r#"{"success":true}"#)
+                } else {
+                    format!(r#"{{"success":true,{}"#, fields)
+                }
+            } else {
+                format!(r#"{{"success":true,"data":{}}}"#, payload)
+            };
+            (body, 0)
+        }
+        Err(msg) => (
+            format!(r#"{{"success":false,"msg":"{}"}}"#, json_escape(&msg)),
+            1,
+        ),
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_from_arg_typed_decoding() {
+        let s = |v: &str| Some(String::from(v));
+        assert_eq!(u128::from_arg("amount", s("42")), Ok(42));
+        assert!(u128::from_arg("amount", s("-1"))
+            .unwrap_err()
+            .contains("expected u128"));
+        assert!(u128::from_arg("amount", None)
+            .unwrap_err()
+            .contains("missing argument 'amount'"));
+        assert!(u8::from_arg("decimals", s("256")).is_err());
+        assert_eq!(bool::from_arg("flag", s("1")), Ok(true));
+        assert!(Address::from_arg("to", s("")).is_err());
+        assert_eq!(Option::<u128>::from_arg("fee", s("")), Ok(None));
+        assert_eq!(Option::<u128>::from_arg("fee", None), Ok(None));
+        assert_eq!(Option::<u128>::from_arg("fee", s("30")), Ok(Some(30)));
+    }
+
+    #[test]
+    fn test_to_json_and_envelope() {
+        let mut out = String::new();
+        out.push('{');
+        write_field(&mut out, true, "to", &"a\"b");
+        write_field(&mut out, false, "amount", &7u128);
+        write_field(&mut out, false, "decimals", &8u64);
+        write_field(&mut out, false, "tags", &vec![Some(1u8), None]);
+        out.push('}');
+        assert_eq!(
+            out,
+            r#"{"to":"a\"b","amount":"7","decimals":8,"tags":[1,null]}"#
+        );
+
+        assert_eq!(
+            envelope(Ok(out)).0,
+            r#"{"success":true,"to":"a\"b","amount":"7","decimals":8,"tags":[1,null]}"#
+        );
+        assert_eq!(envelope(Ok("null".into())).0, r#"{"success":true}"#);
+        assert_eq!(envelope(Ok("[]".into())).0, r#"{"success":true,"data":[]}"#);
+        assert_eq!(
+            envelope(Err("bad \"x\"".into())),
+            (String::from(r#"{"success":false,"msg":"bad \"x\""}"#), 1)
+        );
+    }
+
+    #[test]
+    fn test_json_escape_control_chars() {
+        assert_eq!(json_escape("a\tb\u{1}"), "a\\tb\\u0001");
+        assert_eq!(
+            u128_to_str(u128::MAX),
+            "340282366920938463463374607431768211455"
+        );
+        assert_eq!(parse_u64("18446744073709551616"), 0);
+    }
+}
@@ -9,8 +9,13 @@
 //! - Key-value state storage via [`state::set`] / [`state::get`]
 //! - Structured event emission via [`event::emit`]
 //! - Native CIL transfers via [`transfer`]
-//! - Caller/contract context via [`caller`], [`self_address`], [`balance`]
+//! - USP-01 token moves under allowance rules via [`token_transfer`]
+//! - Caller/contract context via [`caller`], [`self_address`], [`balance`], [`call_value`]
 //! - Blake3 hashing via [`crypto::blake3`]
+//! - ABI metadata embedded at compile time via [`embed_abi!`]
+//! - `#[contract]` framework: typed args, JSON returns, events and ABI
+//!   generated from plain Rust functions (see [`codec`])
+//! - Native mock host for `cargo test` on contract crates (see `mock`)
 //! - Custom global allocator for WASM heap
 //!
 //! ## Quick Start
@@ -33,6 +38,27 @@
 //! }
 //! ```
 //!
+//! With the contract macros, exports, argument decoding and the ABI are
+//! generated:
+//!
+//! ```rust,ignore
+//! #[los_sdk::contract(name = "Counter")]
+//! mod counter {
+//!     // #[entry]/#[view]/#[event]/#[json] are resolved by #[contract] itself
+//!     use los_sdk::codec::Address;
+//!
+//!     #[event(name = "Counter:Bumped")]
+//!     pub struct Bumped { by: Address, value: u128 }
+//!
+//!     /// Add `step` (default 1) to the counter.
+//!     #[entry]
+//!     pub fn bump(step: Option<u128>) -> Result<u128, &'static str> { /* ... */ }
+//!
+//!     #[view]
+//!     pub fn value() -> u128 { /* ... */ }
+//! }
+//! ```
+//!
 //! ## Compilation
 //!
 //! ```bash
@@ -42,6 +68,16 @@
 #![no_std]
 
 extern crate alloc;
+#[cfg(not(target_arch = "wasm32"))]
+extern crate std;
+// Lets `#[contract]`-generated `::los_sdk::` paths resolve inside this crate's tests
+extern crate self as los_sdk;
+
+pub mod codec;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod mock;
+
+pub use los_sdk_macros::{contract, entry, event, json, view};
 
 use alloc::string::String;
 use alloc::vec;
@@ -155,9 +191,13 @@ impl<'a> FmtWrite for WriteCursor<'a> {
 }
 
 // ─────────────────────────────────────────────────────────────────
-// Host function imports (extern "C" from UVM)
+// Host function imports (extern "C" from UVM; `mock` on native targets)
 // ─────────────────────────────────────────────────────────────────
 
+#[cfg(not(target_arch = "wasm32"))]
+use mock::ffi::*;
+
+#[cfg(target_arch = "wasm32")]
 extern "C" {
     fn host_log(ptr: *const u8, len: u32);
     fn host_abort(ptr: *const u8, len: u32);
@@ -169,11 +209,24 @@ extern "C" {
     fn host_emit_event(type_ptr: *const u8, type_len: u32, data_ptr: *const u8, data_len: u32);
 
     fn host_transfer(addr_ptr: *const u8, addr_len: u32, amount_lo: i64, amount_hi: i64) -> i32;
+    #[allow(clippy::too_many_arguments)]
+    fn host_token_transfer(
+        token_ptr: *const u8,
+        token_len: u32,
+        from_ptr: *const u8,
+        from_len: u32,
+        to_ptr: *const u8,
+        to_len: u32,
+        amount_lo: i64,
+        amount_hi: i64,
+    ) -> i32;
 
     fn host_get_caller(out_ptr: *mut u8, out_max: u32) -> i32;
     fn host_get_self_address(out_ptr: *mut u8, out_max: u32) -> i32;
     fn host_get_balance_lo() -> i64;
     fn host_get_balance_hi() -> i64;
+    fn host_get_call_value_lo() -> i64;
+    fn host_get_call_value_hi() -> i64;
     fn host_get_timestamp() -> i64;
 
     fn host_get_arg_count() -> i32;
@@ -358,6 +411,15 @@ pub fn balance() -> u128 {
     (hi << 64) | lo
 }
 
+/// Get the CIL the caller sent with this call (u128). It is already part
+/// of [`balance`]; use this rather than diffing the balance to see what a
+/// call paid in.
+pub fn call_value() -> u128 {
+    let lo = unsafe { host_get_call_value_lo() } as u64 as u128;
+    let hi = unsafe { host_get_call_value_hi() } as u64 as u128;
+    (hi << 64) | lo
+}
+
 /// Get the current block timestamp (seconds since UNIX epoch).
 pub fn timestamp() -> u64 {
     unsafe { host_get_timestamp() as u64 }
@@ -407,6 +469,37 @@ pub fn transfer(recipient: &str, amount: u128) -> Result<(), &'static str> {
     }
 }
 
+/// Move `amount` of USP-01 token `token` from `from` to `to`, with this
+/// contract as spender: its own tokens (`from` = [`self_address`]) move
+/// freely, anyone else's spend the allowance they approved for it. The
+/// caller's tokens first spend any allowance attached to this call.
+/// The token writes commit or revert together with this call.
+pub fn token_transfer(token: &str, from: &str, to: &str, amount: u128) -> Result<(), &'static str> {
+    let lo = (amount & 0xFFFF_FFFF_FFFF_FFFF) as u64 as i64;
+    let hi = (amount >> 64) as u64 as i64;
+    let result = unsafe {
+        host_token_transfer(
+            token.as_ptr(),
+            token.len() as u32,
+            from.as_ptr(),
+            from.len() as u32,
+            to.as_ptr(),
+            to.len() as u32,
+            lo,
+            hi,
+        )
+    };
+    match result {
+        0 => Ok(()),
+        1 => Err("Insufficient token balance"),
+        2 => Err("Insufficient token allowance"),
+        3 => Err("Not a USP-01 token contract"),
+        4 => Err("Invalid token transfer"),
+        5 => Err("Too many transfers in single execution"),
+        _ => Err("Unknown token transfer error"),
+    }
+}
+
 // ─────────────────────────────────────────────────────────────────
 // Safe wrappers — Return data
 // ─────────────────────────────────────────────────────────────────
@@ -451,6 +544,42 @@ pub fn abort(msg: &str) -> ! {
     }
 }
 
+// ─────────────────────────────────────────────────────────────────
+// ABI metadata
+// ─────────────────────────────────────────────────────────────────
+
+/// Copy a string into a fixed-size byte array at compile time.
+/// Used by [`embed_abi!`]; `N` must equal `s.len()`.
+pub const fn abi_bytes<const N: usize>(s: &str) -> [u8; N] {
+    let bytes = s.as_bytes();
+    assert!(bytes.len() == N, "abi_bytes: length mismatch");
+    let mut out = [0u8; N];
+    let mut i = 0;
+    while i < N {
+        out[i] = bytes[i];
+        i += 1;
+    }
+    out
+}
+
+/// Embed the contract's ABI (JSON) in the `los_abi` WASM custom section.
+/// The node reads it at deploy and serves it via `GET /contract/{address}`.
+///
+/// ```rust,ignore
+/// los_sdk::embed_abi!(r#"{"version":1,"functions":[
+///     {"name":"transfer","args":[{"name":"to","type":"address"},{"name":"amount","type":"u128"}]},
+///     {"name":"balance_of","view":true,"returns":"u128","args":[{"name":"account","type":"address"}]}
+/// ]}"#);
+/// ```
+#[macro_export]
+macro_rules! embed_abi {
+    ($json:expr) => {
+        #[cfg_attr(target_arch = "wasm32", link_section = "los_abi")]
+        #[used]
+        static __LOS_ABI: [u8; $json.len()] = $crate::abi_bytes::<{ $json.len() }>($json);
+    };
+}
+
 // ─────────────────────────────────────────────────────────────────
 // Exported allocation functions (used by host to write into guest memory)
 // ─────────────────────────────────────────────────────────────────
@@ -488,11 +617,86 @@ pub extern "C" fn __los_dealloc(ptr: *mut u8, size: u32) {
 
 #[cfg(all(test, not(target_arch = "wasm32")))]
 mod tests {
-    // Note: Host functions are not available on native targets.
-    // These tests verify SDK type-level and logic correctness only.
+    // These tests verify SDK type-level and logic correctness only;
+    // host function behaviour is covered in `mock`.
     use alloc::vec;
     use alloc::vec::Vec;
 
+    const ABI_JSON: &str = r#"{"version":1,"functions":[{"name":"ping"}]}"#;
+    crate::embed_abi!(ABI_JSON);
+
+    #[test]
+    fn test_embed_abi_copies_json() {
+        assert_eq!(&__LOS_ABI[..], ABI_JSON.as_bytes());
+    }
+
+    #[crate::contract(name = "Sample")]
+    mod sample {
+        #![allow(dead_code)]
+        use crate::codec::Address;
+        use alloc::string::String;
+
+        #[event(name = "Sample:Sent")]
+        pub struct Sent {
+            pub to: Address,
+            pub amount: u128,
+            pub memo: Option<String>,
+        }
+
+        #[json]
+        pub struct Info {
+            pub owner: String,
+            pub decimals: u64,
+        }
+
+        /// Send `amount` to `to`.
+        ///
+        /// Not shown in the ABI.
+        #[entry]
+        pub fn send(to: Address, amount: u128, memo: Option<String>) -> Result<(), String> {
+            let _ = (to, amount, memo);
+            Ok(())
+        }
+
+        #[view]
+        pub fn info() -> Info {
+            Info {
+                owner: String::from("LOSx"),
+                decimals: 8,
+            }
+        }
+    }
+
+    #[test]
+    fn test_contract_macro_generates_abi_and_json() {
+        use crate::codec::{Address, ToJson};
+        use alloc::string::String;
+
+        assert_eq!(
+            sample::ABI_JSON,
+            concat!(
+                r#"{"version":1,"name":"Sample","functions":["#,
+                r#"{"name":"send","args":[{"name":"to","type":"address"},{"name":"amount","type":"u128"},"#,
+                r#"{"name":"memo","type":"string","optional":true}],"view":false,"doc":"Send `amount` to `to`."},"#,
+                r#"{"name":"info","args":[],"view":true,"returns":"json"}],"#,
+                r#""events":[{"name":"Sample:Sent","fields":[{"name":"to","type":"address"},"#,
+                r#"{"name":"amount","type":"u128"},{"name":"memo","type":"string","optional":true}]}]}"#
+            )
+        );
+
+        let sent = sample::Sent {
+            to: Address::from(String::from("LOSabc")),
+            amount: 5,
+            memo: None,
+        };
+        assert_eq!(sample::Sent::EVENT_NAME, "Sample:Sent");
+        assert_eq!(sent.to_json(), r#"{"to":"LOSabc","amount":"5"}"#);
+        assert_eq!(
+            crate::codec::data_field(&sample::info()),
+            r#"{"data":{"owner":"LOSx","decimals":8}}"#
+        );
+    }
+
     #[test]
     fn test_u128_split_reconstruct() {
         let amount: u128 = 1_000_000_000_000; // 1 trillion
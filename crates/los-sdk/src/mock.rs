@@ -0,0 +1,775 @@
+//! Native mock host for unit-testing contracts without compiling to WASM.
+//!
+//! On non-wasm32 targets the SDK's host imports are served from a
+//! thread-local in-memory host instead of the UVM, so contract code can be
+//! exercised with plain `cargo test`. Each test thread gets its own host.
+//!
+//! ```rust,ignore
+//! use los_sdk::mock;
+//!
+//! #[test]
+//! fn transfer_moves_balance() {
+//!     mock::reset();
+//!     mock::set_caller("LOSalice");
+//!     let init = mock::call(usp01::exports::init, &["Tok", "TK", "8", "1000"]);
+//!     assert!(init.success, "{}", init.output);
+//!
+//!     let r = mock::call(usp01::exports::transfer, &["LOSbob", "10"]);
+//!     assert!(r.success);
+//!     assert_eq!(r.events[0].event_type, "USP01:Transfer");
+//!     assert_eq!(mock::state_str("bal:LOSbob").as_deref(), Some("10"));
+//! }
+//! ```
+//!
+//! `#[contract]` functions can also be called directly as Rust functions;
+//! their effects are then read with [`take_events`], [`transfers`] etc.
+//!
+//! Semantics follow the UVM host (`los-vm/src/host.rs`):
+//! - `transfer` debits the mock balance; 0 is a no-op, empty recipient is rejected
+//! - `token_transfer` moves balances of tokens seeded with [`set_token_balance`],
+//!   spending allowances from [`set_token_allowance`] unless `from` is the contract;
+//!   allowances from [`set_call_token_allowance`] are spent first
+//! - [`set_call_value`] and [`set_call_token_allowance`] apply to the next [`call`] only
+//! - a non-zero return code does NOT roll back state written before it
+//! - `abort()` (or any panic, which traps on-chain) reverts state, balance,
+//!   events and transfers of that call
+
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::cell::RefCell;
+use std::panic::{self, AssertUnwindSafe};
+
+/// Per-execution limits, mirrored from the UVM host.
+const MAX_EVENTS: usize = 256;
+const MAX_TRANSFERS: usize = 64;
+const MAX_LOGS: usize = 256;
+
+/// Default contract address for a fresh mock host.
+pub const MOCK_CONTRACT_ADDRESS: &str = "LOSConMOCK0000000000000000000000000";
+
+/// An event emitted through `event::emit`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MockEvent {
+    pub event_type: String,
+    /// Raw JSON payload as emitted.
+    pub data: String,
+}
+
+/// Outcome of [`call`].
+#[derive(Debug, Clone)]
+pub struct MockCall {
+    /// Export return code; 0 = success.
+    pub code: i32,
+    /// `code == 0` and not aborted, as the VM reports it.
+    pub success: bool,
+    /// Return data (or the return code, if none was set), like `ContractResult.output`.
+    pub output: String,
+    /// Abort / panic message, if the call trapped.
+    pub aborted: Option<String>,
+    pub events: Vec<MockEvent>,
+    pub transfers: Vec<(String, u128)>,
+    pub logs: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+struct Host {
+    state: BTreeMap<String, Vec<u8>>,
+    caller: String,
+    self_address: String,
+    balance: u128,
+    timestamp: u64,
+    args: Vec<String>,
+    return_data: Vec<u8>,
+    events: Vec<MockEvent>,
+    transfers: Vec<(String, u128)>,
+    logs: Vec<String>,
+    /// USP-01 tokens other contracts hold: token → `bal:`/`allow:` key → amount.
+    tokens: BTreeMap<String, BTreeMap<String, u128>>,
+    token_transfers: usize,
+    call_value: u128,
+    /// Per-call token allowances the caller attached: token → amount.
+    call_allowances: BTreeMap<String, u128>,
+}
+
+impl Default for Host {
+    fn default() -> Self {
+        Host {
+            state: BTreeMap::new(),
+            caller: String::new(),
+            self_address: MOCK_CONTRACT_ADDRESS.to_string(),
+            balance: 0,
+            timestamp: 0,
+            args: Vec::new(),
+            return_data: Vec::new(),
+            events: Vec::new(),
+            transfers: Vec::new(),
+            logs: Vec::new(),
+            tokens: BTreeMap::new(),
+            token_transfers: 0,
+            call_value: 0,
+            call_allowances: BTreeMap::new(),
+        }
+    }
+}
+
+std::thread_local! {
+    static HOST: RefCell<Host> = RefCell::new(Host::default());
+}
+
+fn with_host<R>(f: impl FnOnce(&mut Host) -> R) -> R {
+    HOST.with(|h| f(&mut h.borrow_mut()))
+}
+
+/// Panic payload used by `abort()` so [`call`] can tell it from other panics.
+struct Aborted(String);
+
+// ─────────────────────────────────────────────────────────────────
+// Setup
+// ─────────────────────────────────────────────────────────────────
+
+/// Reset this thread's host: empty state, no caller, zero balance and time.
+pub fn reset() {
+    with_host(|h| *h = Host::default());
+}
+
+pub fn set_caller(addr: &str) {
+    with_host(|h| h.caller = addr.to_string());
+}
+
+pub fn set_self_address(addr: &str) {
+    with_host(|h| h.self_address = addr.to_string());
+}
+
+/// Contract CIL balance as seen by `balance()` and `transfer()`.
+pub fn set_balance(cil: u128) {
+    with_host(|h| h.balance = cil);
+}
+
+/// CIL the caller sends with the next call, as seen by `call_value()`.
+/// Credited to the contract balance, like the UVM does before execution.
+pub fn set_call_value(cil: u128) {
+    with_host(|h| {
+        h.balance = h.balance.saturating_add(cil);
+        h.call_value = cil;
+    });
+}
+
+/// Let the contract pull up to `amount` of the caller's `token` during the
+/// next call, without a stored approval.
+pub fn set_call_token_allowance(token: &str, amount: u128) {
+    with_host(|h| {
+        h.call_allowances.insert(token.to_string(), amount);
+    });
+}
+
+pub fn set_timestamp(secs: u64) {
+    with_host(|h| h.timestamp = secs);
+}
+
+/// Args for direct calls; [`call`] sets them itself.
+pub fn set_args(args: &[&str]) {
+    with_host(|h| h.args = args.iter().map(|a| a.to_string()).collect());
+}
+
+/// Seed a state key (e.g. to simulate state left by an earlier version).
+pub fn set_state(key: &str, value: &str) {
+    with_host(|h| {
+        h.state.insert(key.to_string(), value.as_bytes().to_vec());
+    });
+}
+
+/// Give `owner` a balance of USP-01 token `token` (creating the token).
+pub fn set_token_balance(token: &str, owner: &str, amount: u128) {
+    with_host(|h| {
+        h.tokens
+            .entry(token.to_string())
+            .or_default()
+            .insert(std::format!("bal:{}", owner), amount);
+    });
+}
+
+/// Let `spender` move up to `amount` of `owner`'s `token` (creating the token).
+pub fn set_token_allowance(token: &str, owner: &str, spender: &str, amount: u128) {
+    with_host(|h| {
+        h.tokens
+            .entry(token.to_string())
+            .or_default()
+            .insert(std::format!("allow:{}:{}", owner, spender), amount);
+    });
+}
+
+// ─────────────────────────────────────────────────────────────────
+// Inspection
+// ─────────────────────────────────────────────────────────────────
+
+pub fn state_str(key: &str) -> Option<String> {
+    with_host(|h| {
+        h.state
+            .get(key)
+            .map(|v| String::from_utf8_lossy(v).into_owned())
+    })
+}
+
+/// Whole state as strings, the way `Contract.state` stores it.
+pub fn state() -> BTreeMap<String, String> {
+    with_host(|h| {
+        h.state
+            .iter()
+            .map(|(k, v)| (k.clone(), String::from_utf8_lossy(v).into_owned()))
+            .collect()
+    })
+}
+
+pub fn balance() -> u128 {
+    with_host(|h| h.balance)
+}
+
+pub fn events() -> Vec<MockEvent> {
+    with_host(|h| h.events.clone())
+}
+
+/// Drain events emitted so far.
+pub fn take_events() -> Vec<MockEvent> {
+    with_host(|h| core::mem::take(&mut h.events))
+}
+
+pub fn transfers() -> Vec<(String, u128)> {
+    with_host(|h| h.transfers.clone())
+}
+
+pub fn logs() -> Vec<String> {
+    with_host(|h| h.logs.clone())
+}
+
+pub fn token_balance(token: &str, owner: &str) -> u128 {
+    token_amount(token, &std::format!("bal:{}", owner))
+}
+
+pub fn token_allowance(token: &str, owner: &str, spender: &str) -> u128 {
+    token_amount(token, &std::format!("allow:{}:{}", owner, spender))
+}
+
+fn token_amount(token: &str, key: &str) -> u128 {
+    with_host(|h| {
+        h.tokens
+            .get(token)
+            .and_then(|t| t.get(key))
+            .copied()
+            .unwrap_or(0)
+    })
+}
+
+/// Last value passed to `set_return`.
+pub fn return_str() -> String {
+    with_host(|h| String::from_utf8_lossy(&h.return_data).into_owned())
+}
+
+// ─────────────────────────────────────────────────────────────────
+// Execution
+// ─────────────────────────────────────────────────────────────────
+
+/// Invoke an exported entry point (e.g. `my_contract::exports::transfer`)
+/// with `args`, the way the VM would. Events, transfers and logs are
+/// collected per call.
+pub fn call(export: fn() -> i32, args: &[&str]) -> MockCall {
+    let snapshot = with_host(|h| {
+        h.args = args.iter().map(|a| a.to_string()).collect();
+        h.return_data.clear();
+        h.events.clear();
+        h.transfers.clear();
+        h.logs.clear();
+        h.token_transfers = 0;
+        (h.state.clone(), h.balance, h.tokens.clone())
+    });
+
+    let outcome = panic::catch_unwind(AssertUnwindSafe(export));
+
+    with_host(|h| {
+        let (code, aborted) = match outcome {
+            Ok(code) => (code, None),
+            Err(payload) => {
+                let msg = if let Some(a) = payload.downcast_ref::<Aborted>() {
+                    a.0.clone()
+                } else if let Some(s) = payload.downcast_ref::<&str>() {
+                    s.to_string()
+                } else if let Some(s) = payload.downcast_ref::<String>() {
+                    s.clone()
+                } else {
+                    "panic".to_string()
+                };
+                // A trap discards everything the call did
+                h.state = snapshot.0;
+                h.balance = snapshot.1;
+                h.tokens = snapshot.2;
+                h.events.clear();
+                h.transfers.clear();
+                (-1, Some(msg))
+            }
+        };
+        h.call_value = 0;
+        h.call_allowances.clear();
+        let output = if h.return_data.is_empty() {
+            code.to_string()
+        } else {
+            String::from_utf8_lossy(&h.return_data).into_owned()
+        };
+        MockCall {
+            code,
+            success: code == 0 && aborted.is_none(),
+            output,
+            aborted,
+            events: h.events.clone(),
+            transfers: h.transfers.clone(),
+            logs: h.logs.clone(),
+        }
+    })
+}
+
+// ─────────────────────────────────────────────────────────────────
+// Host function implementations (same signatures as the UVM imports)
+// ─────────────────────────────────────────────────────────────────
+
+pub(crate) mod ffi {
+    use super::*;
+
+    unsafe fn bytes<'a>(ptr: *const u8, len: u32) -> &'a [u8] {
+        if len == 0 {
+            return &[];
+        }
+        core::slice::from_raw_parts(ptr, len as usize)
+    }
+
+    unsafe fn string(ptr: *const u8, len: u32) -> String {
+        String::from_utf8_lossy(bytes(ptr, len)).into_owned()
+    }
+
+    /// Copy up to `out_max` bytes into the guest buffer; returns bytes written.
+    unsafe fn write_out(data: &[u8], out_ptr: *mut u8, out_max: u32) -> i32 {
+        let n = data.len().min(out_max as usize);
+        if n > 0 {
+            core::ptr::copy_nonoverlapping(data.as_ptr(), out_ptr, n);
+        }
+        n as i32
+    }
+
+    pub(crate) unsafe fn host_log(ptr: *const u8, len: u32) {
+        let msg = string(ptr, len);
+        with_host(|h| {
+            if h.logs.len() < MAX_LOGS {
+                h.logs.push(msg);
+            }
+        });
+    }
+
+    pub(crate) unsafe fn host_abort(ptr: *const u8, len: u32) {
+        let msg = string(ptr, len);
+        panic::panic_any(Aborted(msg));
+    }
+
+    pub(crate) unsafe fn host_set_state(
+        key_ptr: *const u8,
+        key_len: u32,
+        val_ptr: *const u8,
+        val_len: u32,
+    ) {
+        let key = string(key_ptr, key_len);
+        let val = bytes(val_ptr, val_len).to_vec();
+        with_host(|h| {
+            h.state.insert(key, val);
+        });
+    }
+
+    pub(crate) unsafe fn host_get_state(
+        key_ptr: *const u8,
+        key_len: u32,
+        out_ptr: *mut u8,
+        out_max: u32,
+    ) -> i32 {
+        let key = string(key_ptr, key_len);
+        match with_host(|h| h.state.get(&key).cloned()) {
+            Some(v) => write_out(&v, out_ptr, out_max),
+            None => -1,
+        }
+    }
+
+    pub(crate) unsafe fn host_del_state(key_ptr: *const u8, key_len: u32) {
+        let key = string(key_ptr, key_len);
+        with_host(|h| {
+            h.state.remove(&key);
+        });
+    }
+
+    pub(crate) unsafe fn host_emit_event(
+        type_ptr: *const u8,
+        type_len: u32,
+        data_ptr: *const u8,
+        data_len: u32,
+    ) {
+        let event = MockEvent {
+            event_type: string(type_ptr, type_len),
+            data: string(data_ptr, data_len),
+        };
+        with_host(|h| {
+            if h.events.len() < MAX_EVENTS {
+                h.events.push(event);
+            }
+        });
+    }
+
+    pub(crate) unsafe fn host_transfer(
+        addr_ptr: *const u8,
+        addr_len: u32,
+        amount_lo: i64,
+        amount_hi: i64,
+    ) -> i32 {
+        let recipient = string(addr_ptr, addr_len);
+        if recipient.is_empty() {
+            return 2;
+        }
+        let amount = ((amount_hi as u64 as u128) << 64) | (amount_lo as u64 as u128);
+        if amount == 0 {
+            return 0;
+        }
+        with_host(|h| {
+            if h.transfers.len() >= MAX_TRANSFERS {
+                return 3;
+            }
+            if h.balance < amount {
+                return 1;
+            }
+            h.balance -= amount;
+            h.transfers.push((recipient, amount));
+            0
+        })
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) unsafe fn host_token_transfer(
+        token_ptr: *const u8,
+        token_len: u32,
+        from_ptr: *const u8,
+        from_len: u32,
+        to_ptr: *const u8,
+        to_len: u32,
+        amount_lo: i64,
+        amount_hi: i64,
+    ) -> i32 {
+        let token = string(token_ptr, token_len);
+        let from = string(from_ptr, from_len);
+        let to = string(to_ptr, to_len);
+        let amount = ((amount_hi as u64 as u128) << 64) | (amount_lo as u64 as u128);
+        with_host(|h| {
+            if token.is_empty() || from.is_empty() || to.is_empty() || from == to {
+                return 4;
+            }
+            if token == h.self_address {
+                return 4;
+            }
+            if h.token_transfers >= MAX_TRANSFERS {
+                return 5;
+            }
+            let spender = h.self_address.clone();
+            let Some(ledger) = h.tokens.get_mut(&token) else {
+                return 3;
+            };
+            if amount == 0 {
+                return 0;
+            }
+            let from_key = std::format!("bal:{}", from);
+            let from_bal = ledger.get(&from_key).copied().unwrap_or(0);
+            if from_bal < amount {
+                return 1;
+            }
+            let allow_key = std::format!("allow:{}:{}", from, spender);
+            let call_allowance = if from == h.caller {
+                h.call_allowances.get(&token).copied().unwrap_or(0)
+            } else {
+                0
+            };
+            let from_call = call_allowance.min(amount);
+            if from != spender && amount > from_call {
+                let allowance = ledger.get(&allow_key).copied().unwrap_or(0);
+                if allowance < amount - from_call {
+                    return 2;
+                }
+                ledger.insert(allow_key, allowance - (amount - from_call));
+            }
+            ledger.insert(from_key, from_bal - amount);
+            *ledger.entry(std::format!("bal:{}", to)).or_insert(0) += amount;
+            h.token_transfers += 1;
+            if from_call > 0 {
+                h.call_allowances
+                    .insert(token.clone(), call_allowance - from_call);
+            }
+            if h.events.len() < MAX_EVENTS {
+                h.events.push(MockEvent {
+                    event_type: "USP01:Transfer".to_string(),
+                    data: std::format!(
+                        r#"{{"from":"{}","to":"{}","amount":"{}","spender":"{}"}}"#,
+                        from,
+                        to,
+                        amount,
+                        spender
+                    ),
+                });
+            }
+            0
+        })
+    }
+
+    pub(crate) unsafe fn host_get_caller(out_ptr: *mut u8, out_max: u32) -> i32 {
+        let caller = with_host(|h| h.caller.clone());
+        write_out(caller.as_bytes(), out_ptr, out_max)
+    }
+
+    pub(crate) unsafe fn host_get_self_address(out_ptr: *mut u8, out_max: u32) -> i32 {
+        let addr = with_host(|h| h.self_address.clone());
+        write_out(addr.as_bytes(), out_ptr, out_max)
+    }
+
+    pub(crate) unsafe fn host_get_balance_lo() -> i64 {
+        with_host(|h| (h.balance & 0xFFFF_FFFF_FFFF_FFFF) as i64)
+    }
+
+    pub(crate) unsafe fn host_get_balance_hi() -> i64 {
+        with_host(|h| (h.balance >> 64) as i64)
+    }
+
+    pub(crate) unsafe fn host_get_call_value_lo() -> i64 {
+        with_host(|h| (h.call_value & 0xFFFF_FFFF_FFFF_FFFF) as i64)
+    }
+
+    pub(crate) unsafe fn host_get_call_value_hi() -> i64 {
+        with_host(|h| (h.call_value >> 64) as i64)
+    }
+
+    pub(crate) unsafe fn host_get_timestamp() -> i64 {
+        with_host(|h| h.timestamp as i64)
+    }
+
+    pub(crate) unsafe fn host_get_arg_count() -> i32 {
+        with_host(|h| h.args.len() as i32)
+    }
+
+    pub(crate) unsafe fn host_get_arg(idx: i32, out_ptr: *mut u8, out_max: u32) -> i32 {
+        match with_host(|h| h.args.get(idx as usize).cloned()) {
+            Some(a) => write_out(a.as_bytes(), out_ptr, out_max),
+            None => -1,
+        }
+    }
+
+    pub(crate) unsafe fn host_set_return(ptr: *const u8, len: u32) {
+        let data = bytes(ptr, len).to_vec();
+        with_host(|h| h.return_data = data);
+    }
+
+    pub(crate) unsafe fn host_blake3(data_ptr: *const u8, data_len: u32, out_ptr: *mut u8) -> i32 {
+        let hash = blake3::hash(bytes(data_ptr, data_len));
+        write_out(hash.as_bytes(), out_ptr, 32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[crate::contract(name = "Vault")]
+    mod vault {
+        use crate::codec::Address;
+        use crate::{caller, state, timestamp};
+
+        #[event(name = "Vault:Paid")]
+        pub struct Paid {
+            to: Address,
+            amount: u128,
+        }
+
+        #[entry]
+        pub fn pay(to: Address, amount: u128) -> Result<u128, &'static str> {
+            state::set_str("last_caller", &caller());
+            crate::transfer(to.as_str(), amount)?;
+            Paid { to, amount }.emit();
+            Ok(crate::balance())
+        }
+
+        #[entry]
+        pub fn stamp() -> u64 {
+            state::set_u64("stamp", timestamp());
+            timestamp()
+        }
+
+        #[entry]
+        pub fn explode() {
+            state::set_str("half", "written");
+            crate::abort("boom");
+        }
+    }
+
+    #[test]
+    fn test_call_runs_entry_point_with_context() {
+        reset();
+        set_caller("LOSalice");
+        set_balance(100);
+
+        let r = call(vault::exports::pay, &["LOSbob", "40"]);
+        assert!(r.success, "{}", r.output);
+        assert_eq!(r.output, r#"{"success":true,"data":"60"}"#);
+        assert_eq!(r.transfers, vec![("LOSbob".to_string(), 40)]);
+        assert_eq!(r.events.len(), 1);
+        assert_eq!(r.events[0].event_type, "Vault:Paid");
+        assert_eq!(r.events[0].data, r#"{"to":"LOSbob","amount":"40"}"#);
+        assert_eq!(balance(), 60);
+        assert_eq!(state_str("last_caller").as_deref(), Some("LOSalice"));
+
+        set_timestamp(1_700_000_000);
+        let r = call(vault::exports::stamp, &[]);
+        assert!(r.success);
+        assert!(r.output.contains("1700000000"));
+    }
+
+    #[test]
+    fn test_failed_call_keeps_earlier_writes() {
+        reset();
+        set_caller("LOSalice");
+        set_balance(10);
+
+        let r = call(vault::exports::pay, &["LOSbob", "11"]);
+        assert!(!r.success);
+        assert_eq!(r.code, 1);
+        assert!(r.output.contains("Insufficient contract balance"));
+        assert!(r.transfers.is_empty());
+        // Like the UVM, a non-zero return code is not a trap
+        assert_eq!(state_str("last_caller").as_deref(), Some("LOSalice"));
+    }
+
+    #[test]
+    fn test_bad_args_are_rejected() {
+        reset();
+        let r = call(vault::exports::pay, &["LOSbob"]);
+        assert!(!r.success);
+        assert!(r.output.contains("amount"), "{}", r.output);
+    }
+
+    #[test]
+    fn test_abort_reverts_call() {
+        reset();
+        set_state("keep", "1");
+        let r = call(vault::exports::explode, &[]);
+        assert!(!r.success);
+        assert_eq!(r.aborted.as_deref(), Some("boom"));
+        assert_eq!(state_str("half"), None);
+        assert_eq!(state_str("keep").as_deref(), Some("1"));
+    }
+
+    #[test]
+    fn test_token_transfer_spends_allowance_and_reverts_on_abort() {
+        reset();
+        set_self_address("LOSConX");
+        set_token_balance("LOSConTok", "LOSalice", 100);
+        set_token_allowance("LOSConTok", "LOSalice", "LOSConX", 60);
+
+        assert_eq!(
+            crate::token_transfer("LOSConTok", "LOSalice", "LOSConX", 50),
+            Ok(())
+        );
+        assert_eq!(token_balance("LOSConTok", "LOSConX"), 50);
+        assert_eq!(token_allowance("LOSConTok", "LOSalice", "LOSConX"), 10);
+        assert_eq!(
+            crate::token_transfer("LOSConTok", "LOSalice", "LOSConX", 20),
+            Err("Insufficient token allowance")
+        );
+        // The contract's own tokens need no allowance
+        assert_eq!(
+            crate::token_transfer("LOSConTok", "LOSConX", "LOSbob", 50),
+            Ok(())
+        );
+        assert_eq!(
+            crate::token_transfer("LOSConNope", "LOSConX", "LOSbob", 1),
+            Err("Not a USP-01 token contract")
+        );
+
+        let r = call(
+            || {
+                crate::token_transfer("LOSConTok", "LOSalice", "LOSConX", 10).unwrap();
+                crate::abort("undo")
+            },
+            &[],
+        );
+        assert_eq!(r.aborted.as_deref(), Some("undo"));
+        assert_eq!(token_balance("LOSConTok", "LOSalice"), 50);
+    }
+
+    #[test]
+    fn test_call_value_and_call_allowance_last_one_call() {
+        reset();
+        set_caller("LOSalice");
+        set_self_address("LOSConX");
+        set_balance(5);
+        set_token_balance("LOSConTok", "LOSalice", 100);
+        set_token_allowance("LOSConTok", "LOSalice", "LOSConX", 10);
+        set_call_value(30);
+        set_call_token_allowance("LOSConTok", 40);
+
+        let r = call(
+            || {
+                assert_eq!(crate::call_value(), 30);
+                assert_eq!(crate::balance(), 35);
+                // 40 from the call allowance, the last 5 from the stored one
+                crate::token_transfer("LOSConTok", "LOSalice", "LOSConX", 45).unwrap();
+                0
+            },
+            &[],
+        );
+        assert!(r.success, "{}", r.output);
+        assert_eq!(token_balance("LOSConTok", "LOSConX"), 45);
+        assert_eq!(token_allowance("LOSConTok", "LOSalice", "LOSConX"), 5);
+
+        let r = call(
+            || {
+                assert_eq!(crate::call_value(), 0);
+                match crate::token_transfer("LOSConTok", "LOSalice", "LOSConX", 6) {
+                    Ok(()) => 0,
+                    Err(_) => 2,
+                }
+            },
+            &[],
+        );
+        assert_eq!(r.code, 2);
+    }
+
+    #[test]
+    fn test_direct_host_calls() {
+        reset();
+        set_self_address("LOSConX");
+        set_args(&["a", "b"]);
+        assert_eq!(crate::self_address(), "LOSConX");
+        assert_eq!(crate::arg_count(), 2);
+        assert_eq!(crate::arg(1).as_deref(), Some("b"));
+        assert_eq!(crate::arg(2), None);
+
+        crate::state::set_u128("n", u128::MAX);
+        assert_eq!(crate::state::get_u128("n"), u128::MAX);
+        assert!(crate::state::exists("n"));
+        crate::state::del("n");
+        assert!(!crate::state::exists("n"));
+
+        set_balance(u128::MAX);
+        assert_eq!(crate::balance(), u128::MAX);
+        assert_eq!(crate::transfer("", 1), Err("Invalid recipient address"));
+
+        crate::event::emit("E", "{}");
+        crate::log("hi");
+        assert_eq!(take_events().len(), 1);
+        assert!(events().is_empty());
+        assert_eq!(logs(), vec!["hi".to_string()]);
+
+        assert_eq!(
+            crate::crypto::blake3(b"abc"),
+            *blake3::hash(b"abc").as_bytes()
+        );
+        crate::set_return_str("done");
+        assert_eq!(return_str(), "done");
+    }
+}
@@ -0,0 +1,21 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - DETERMINISTIC NETWORK SIMULATOR
+//
+// Runs N validators (ABFTConsensus + SlashingManager + Ledger) in one process:
+// - Virtual clock: no threads, no sleeps, no wall-clock dependence
+// - Message bus that delays, drops, reorders and partitions traffic
+// - Scriptable Byzantine behaviours (silence, equivocation, invalid blocks)
+// - Every random choice comes from one seeded RNG: same seed + same script
+//   ⇒ identical trace
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+pub mod network;
+pub mod node;
+pub mod simulation;
+
+pub use network::{MessageBus, NetworkConfig, NetworkStats};
+pub use node::{Behaviour, SimMessage, SimNode};
+pub use simulation::{SimAction, SimConfig, SimEvent, Simulation, TraceEntry};
+
+/// Index of a node inside a simulation (0..n)
+pub type NodeId = usize;
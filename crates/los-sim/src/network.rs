@@ -0,0 +1,276 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - SIMULATED MESSAGE BUS
+//
+// Point-to-point delivery on the virtual clock.
+// - Per-message delay drawn uniformly from [min_delay_ms, max_delay_ms]
+// - Loss and reordering expressed in basis points (10000 = 100%)
+// - Partitions are checked at send AND delivery time, so messages in
+//   flight when a partition starts are lost too
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use crate::node::SimMessage;
+use crate::NodeId;
+use rand::Rng;
+use rand_chacha::ChaCha8Rng;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Basis point denominator (10000 = 100%)
+pub const BPS_DENOMINATOR: u32 = 10_000;
+
+/// Link behaviour applied to every message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NetworkConfig {
+    pub min_delay_ms: u64,
+    pub max_delay_ms: u64,
+    /// Probability that a message is lost (basis points)
+    pub drop_bps: u32,
+    /// Probability that a message is held back by an extra `max_delay_ms`,
+    /// letting messages sent after it overtake it (basis points)
+    pub reorder_bps: u32,
+}
+
+impl Default for NetworkConfig {
+    fn default() -> Self {
+        Self {
+            min_delay_ms: 5,
+            max_delay_ms: 50,
+            drop_bps: 0,
+            reorder_bps: 0,
+        }
+    }
+}
+
+impl NetworkConfig {
+    /// Fixed 1 ms latency, no loss, no reordering
+    pub fn perfect() -> Self {
+        Self {
+            min_delay_ms: 1,
+            max_delay_ms: 1,
+            drop_bps: 0,
+            reorder_bps: 0,
+        }
+    }
+
+    /// WAN-like links: 20-200 ms latency, 5% loss, 10% reordering
+    pub fn lossy() -> Self {
+        Self {
+            min_delay_ms: 20,
+            max_delay_ms: 200,
+            drop_bps: 500,
+            reorder_bps: 1_000,
+        }
+    }
+}
+
+/// Delivery counters
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NetworkStats {
+    pub sent: u64,
+    pub delivered: u64,
+    /// Lost to `drop_bps`
+    pub dropped: u64,
+    /// Lost because sender and receiver were on different sides of a partition
+    pub partitioned: u64,
+}
+
+/// A message in flight
+#[derive(Debug, Clone)]
+pub struct Envelope {
+    pub from: NodeId,
+    pub to: NodeId,
+    pub message: SimMessage,
+}
+
+/// Seeded, virtual-time message bus
+pub struct MessageBus {
+    config: NetworkConfig,
+    /// Keyed by (delivery time, send order) so equal delivery times stay FIFO
+    queue: BTreeMap<(u64, u64), Envelope>,
+    next_seq: u64,
+    /// None = fully connected. Nodes absent from every group are isolated.
+    partition: Option<Vec<BTreeSet<NodeId>>>,
+    stats: NetworkStats,
+}
+
+impl MessageBus {
+    pub fn new(config: NetworkConfig) -> Self {
+        Self {
+            config,
+            queue: BTreeMap::new(),
+            next_seq: 0,
+            partition: None,
+            stats: NetworkStats::default(),
+        }
+    }
+
+    pub fn config(&self) -> NetworkConfig {
+        self.config
+    }
+
+    /// Change link behaviour for messages sent from now on
+    pub fn set_config(&mut self, config: NetworkConfig) {
+        self.config = config;
+    }
+
+    /// Split the network: only nodes in the same group can talk
+    pub fn partition(&mut self, groups: Vec<Vec<NodeId>>) {
+        self.partition = Some(
+            groups
+                .into_iter()
+                .map(|g| g.into_iter().collect())
+                .collect(),
+        );
+    }
+
+    /// Remove any partition
+    pub fn heal(&mut self) {
+        self.partition = None;
+    }
+
+    pub fn is_partitioned(&self) -> bool {
+        self.partition.is_some()
+    }
+
+    /// Can `from` currently reach `to`?
+    pub fn is_reachable(&self, from: NodeId, to: NodeId) -> bool {
+        match &self.partition {
+            None => true,
+            Some(groups) => groups.iter().any(|g| g.contains(&from) && g.contains(&to)),
+        }
+    }
+
+    /// Queue a message, applying partition, loss, delay and reordering
+    pub fn send(
+        &mut self,
+        rng: &mut ChaCha8Rng,
+        now_ms: u64,
+        from: NodeId,
+        to: NodeId,
+        message: SimMessage,
+    ) {
+        self.stats.sent += 1;
+
+        // Draw from the RNG unconditionally so a partition does not shift
+        // the random stream of unrelated messages
+        let lost = rng.gen_range(0..BPS_DENOMINATOR) < self.config.drop_bps;
+        let held_back = rng.gen_range(0..BPS_DENOMINATOR) < self.config.reorder_bps;
+        let max_delay = self.config.max_delay_ms.max(self.config.min_delay_ms);
+        let mut delay = rng.gen_range(self.config.min_delay_ms..=max_delay);
+
+        if !self.is_reachable(from, to) {
+            self.stats.partitioned += 1;
+            return;
+        }
+        if lost {
+            self.stats.dropped += 1;
+            return;
+        }
+        if held_back {
+            delay += max_delay;
+        }
+
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        self.queue
+            .insert((now_ms + delay, seq), Envelope { from, to, message });
+    }
+
+    /// Earliest pending delivery time
+    pub fn next_delivery_ms(&self) -> Option<u64> {
+        self.queue.keys().next().map(|(at, _)| *at)
+    }
+
+    /// Pop the next message due at or before `now_ms`
+    pub fn pop_due(&mut self, now_ms: u64) -> Option<Envelope> {
+        loop {
+            let key = *self.queue.keys().next()?;
+            if key.0 > now_ms {
+                return None;
+            }
+            let envelope = self.queue.remove(&key)?;
+            if !self.is_reachable(envelope.from, envelope.to) {
+                self.stats.partitioned += 1;
+                continue;
+            }
+            self.stats.delivered += 1;
+            return Some(envelope);
+        }
+    }
+
+    /// Messages still in flight
+    pub fn in_flight(&self) -> usize {
+        self.queue.len()
+    }
+
+    pub fn stats(&self) -> NetworkStats {
+        self.stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use los_consensus::abft::{ConsensusMessage, ConsensusMessageType};
+    use rand::SeedableRng;
+
+    fn msg() -> SimMessage {
+        SimMessage::Commit {
+            vote: ConsensusMessage::new(
+                ConsensusMessageType::Commit,
+                0,
+                1,
+                "hash".to_string(),
+                "LOS_SIM_00".to_string(),
+            ),
+        }
+    }
+
+    #[test]
+    fn test_delivery_respects_delay_and_order() {
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+        let mut bus = MessageBus::new(NetworkConfig {
+            min_delay_ms: 10,
+            max_delay_ms: 10,
+            drop_bps: 0,
+            reorder_bps: 0,
+        });
+        bus.send(&mut rng, 0, 0, 1, msg());
+        bus.send(&mut rng, 0, 0, 2, msg());
+
+        assert!(bus.pop_due(9).is_none());
+        assert_eq!(bus.next_delivery_ms(), Some(10));
+        assert_eq!(bus.pop_due(10).map(|e| e.to), Some(1));
+        assert_eq!(bus.pop_due(10).map(|e| e.to), Some(2));
+        assert_eq!(bus.stats().delivered, 2);
+    }
+
+    #[test]
+    fn test_partition_blocks_in_flight_messages() {
+        let mut rng = ChaCha8Rng::seed_from_u64(2);
+        let mut bus = MessageBus::new(NetworkConfig::perfect());
+        bus.send(&mut rng, 0, 0, 1, msg());
+        bus.partition(vec![vec![0], vec![1]]);
+        bus.send(&mut rng, 0, 1, 0, msg());
+
+        assert!(bus.pop_due(100).is_none());
+        assert_eq!(bus.stats().partitioned, 2);
+
+        bus.heal();
+        bus.send(&mut rng, 100, 1, 0, msg());
+        assert!(bus.pop_due(101).is_some());
+    }
+
+    #[test]
+    fn test_total_loss_drops_everything() {
+        let mut rng = ChaCha8Rng::seed_from_u64(3);
+        let mut bus = MessageBus::new(NetworkConfig {
+            drop_bps: BPS_DENOMINATOR,
+            ..NetworkConfig::default()
+        });
+        for _ in 0..10 {
+            bus.send(&mut rng, 0, 0, 1, msg());
+        }
+        assert_eq!(bus.in_flight(), 0);
+        assert_eq!(bus.stats().dropped, 10);
+    }
+}
@@ -0,0 +1,847 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - SIMULATED VALIDATOR
+//
+// Drives one validator's ABFTConsensus, SlashingManager and Ledger from
+// simulated network messages.
+// - Leader proposes a consensus block whose payload is a batch of ledger blocks
+// - Replicas validate parent/height/payload, then PREPARE → COMMIT → finalize
+// - PREPAREs echo the proposal so replicas detect a leader that equivocated
+// - Round timeout → VIEW-CHANGE; committed locks survive into the new view
+// - Consensus sequences never repeat across views: a new view proposes above
+//   every sequence its quorum reports, so no validator re-signs a sequence
+// - Lagging validators catch up from commit certificates
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use crate::simulation::SimEvent;
+use crate::NodeId;
+use los_consensus::abft::{
+    ABFTConsensus, Block as ConsensusBlock, ConsensusMessage, ConsensusMessageType, ValidatorState,
+};
+use los_consensus::slashing::{SlashingManager, ValidatorStatus};
+use los_core::{Block, BlockType, Ledger};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Parent hash of the first consensus block
+pub const GENESIS_PARENT_HASH: &str = "genesis";
+
+/// Maximum ledger blocks batched into one consensus block
+pub const MAX_TXS_PER_BLOCK: usize = 64;
+
+/// Maximum finalized blocks sent in one catch-up reply
+pub const MAX_CATCH_UP_BLOCKS: usize = 16;
+
+/// Cap on the timeout backoff multiplier after consecutive failed rounds
+const MAX_BACKOFF: u32 = 4;
+
+/// Scripted validator behaviour
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Behaviour {
+    /// Follows the protocol
+    Honest,
+    /// Processes messages but never sends any (crashed NIC, censoring node)
+    Silent,
+    /// As leader: sends different proposals to different replicas.
+    /// As replica: signs a second, conflicting PREPARE for every proposal.
+    Equivocate,
+    /// As leader: proposes blocks with a wrong parent or an invalid payload
+    InvalidBlocks,
+}
+
+/// Wire messages between simulated validators
+#[derive(Debug, Clone)]
+pub enum SimMessage {
+    /// Leader proposal (PRE-PREPARE + block body)
+    Proposal {
+        pre_prepare: ConsensusMessage,
+        block: ConsensusBlock,
+    },
+    /// PREPARE vote, echoing the proposal it answers
+    Prepare {
+        vote: ConsensusMessage,
+        proposal: ConsensusMessage,
+    },
+    Commit {
+        vote: ConsensusMessage,
+    },
+    /// VIEW-CHANGE vote (carrying the sender's sequence) with its next height
+    /// and committed lock (view, block)
+    ViewChange {
+        vote: ConsensusMessage,
+        height: u64,
+        lock: Option<(u64, ConsensusBlock)>,
+    },
+    /// Catch-up reply: consecutive finalized blocks with their commit certificates
+    Decided {
+        blocks: Vec<(ConsensusBlock, Vec<ConsensusMessage>)>,
+    },
+}
+
+/// Messages and trace events produced while handling one input
+#[derive(Debug, Default)]
+pub struct Outbox {
+    /// (recipient, message); None = broadcast to every peer
+    pub messages: Vec<(Option<NodeId>, SimMessage)>,
+    pub events: Vec<SimEvent>,
+}
+
+/// A VIEW-CHANGE sender's sequence and committed lock (view, block)
+type ViewChangeReport = (u64, Option<(u64, ConsensusBlock)>);
+
+/// One simulated validator
+pub struct SimNode {
+    pub id: NodeId,
+    pub address: String,
+    pub behaviour: Behaviour,
+    pub abft: ABFTConsensus,
+    pub slashing: SlashingManager,
+    pub ledger: Ledger,
+    /// Finalized consensus blocks; chain[i].height == i + 1
+    pub chain: Vec<ConsensusBlock>,
+    /// Ledger blocks waiting to be proposed
+    pub mempool: Vec<Block>,
+    /// Commit certificate per finalized height (served to lagging peers)
+    certificates: BTreeMap<u64, Vec<ConsensusMessage>>,
+    /// Block we sent a COMMIT for at the current height, with the view it was prepared in
+    committed_lock: Option<(u64, ConsensusBlock)>,
+    /// Highest committed lock reported by the view-change quorum
+    reproposal: Option<ConsensusBlock>,
+    /// view → sender → (reported sequence, reported committed lock)
+    view_changes: BTreeMap<u64, BTreeMap<String, ViewChangeReport>>,
+    stakes: BTreeMap<String, u128>,
+    directory: BTreeMap<String, NodeId>,
+    round_timeout_ms: u64,
+    failed_rounds: u32,
+    deadline_ms: u64,
+}
+
+impl SimNode {
+    pub fn new(
+        id: NodeId,
+        abft: ABFTConsensus,
+        ledger: Ledger,
+        stakes: BTreeMap<String, u128>,
+        directory: BTreeMap<String, NodeId>,
+        round_timeout_ms: u64,
+    ) -> Self {
+        let mut slashing = SlashingManager::new();
+        for address in stakes.keys() {
+            slashing.register_validator(address.clone());
+        }
+        Self {
+            id,
+            address: abft.validator_id.clone(),
+            behaviour: Behaviour::Honest,
+            abft,
+            slashing,
+            ledger,
+            chain: Vec::new(),
+            mempool: Vec::new(),
+            certificates: BTreeMap::new(),
+            committed_lock: None,
+            reproposal: None,
+            view_changes: BTreeMap::new(),
+            stakes,
+            directory,
+            round_timeout_ms,
+            failed_rounds: 0,
+            deadline_ms: round_timeout_ms,
+        }
+    }
+
+    /// Height of the next block to finalize
+    pub fn next_height(&self) -> u64 {
+        self.chain.len() as u64 + 1
+    }
+
+    /// Hash of the last finalized block (GENESIS_PARENT_HASH before the first)
+    pub fn tip_hash(&self) -> String {
+        self.chain
+            .last()
+            .map(|b| b.calculate_hash())
+            .unwrap_or_else(|| GENESIS_PARENT_HASH.to_string())
+    }
+
+    /// Virtual time at which the current round times out
+    pub fn deadline_ms(&self) -> u64 {
+        self.deadline_ms
+    }
+
+    pub fn is_banned(&self, address: &str) -> bool {
+        self.slashing.get_status(address) == Some(ValidatorStatus::Banned)
+    }
+
+    /// Start the first round (leader of view 0 proposes)
+    pub fn start(&mut self, now_ms: u64, out: &mut Outbox) {
+        self.deadline_ms = now_ms + self.round_timeout_ms;
+        self.propose(now_ms, out);
+    }
+
+    /// Round timer expired: move to the next view
+    pub fn on_timeout(&mut self, now_ms: u64, out: &mut Outbox) {
+        self.failed_rounds += 1;
+        self.reset_deadline(now_ms);
+        self.start_view_change(now_ms, out);
+    }
+
+    pub fn on_message(&mut self, now_ms: u64, from: NodeId, message: SimMessage, out: &mut Outbox) {
+        match message {
+            SimMessage::Proposal { pre_prepare, block } => {
+                self.on_proposal(now_ms, pre_prepare, block, out)
+            }
+            SimMessage::Prepare { vote, proposal } => self.on_prepare(now_ms, vote, proposal, out),
+            SimMessage::Commit { vote } => self.on_commit(now_ms, vote, out),
+            SimMessage::ViewChange { vote, height, lock } => {
+                self.on_view_change(now_ms, from, vote, height, lock, out)
+            }
+            SimMessage::Decided { blocks } => self.on_decided(now_ms, blocks, out),
+        }
+    }
+
+    // ─────────────────────────────────────────────────────────────
+    // PROPOSAL
+    // ─────────────────────────────────────────────────────────────
+
+    fn propose(&mut self, now_ms: u64, out: &mut Outbox) {
+        if self.abft.state == ValidatorState::ViewChanging || !self.abft.is_leader() {
+            return;
+        }
+        let height = self.next_height();
+        let (view, finalized) = (self.abft.view, self.abft.finalized_sequence);
+        if self
+            .abft
+            .pre_prepare_messages
+            .range(finalized + 1..)
+            .any(|(_, m)| m.view == view && m.sender == self.address)
+        {
+            return; // Already proposed in this view
+        }
+
+        let mut block = match self.reproposal.clone() {
+            Some(locked) => locked,
+            None => self.build_block(now_ms),
+        };
+        if self.behaviour == Behaviour::InvalidBlocks {
+            if height % 2 == 1 {
+                block.parent_hash = "0".repeat(64);
+            } else {
+                block.data =
+                    serde_json::to_vec(&vec![unmined_block(&self.address)]).unwrap_or_default();
+            }
+        }
+
+        let pre_prepare = match self.abft.pre_prepare(block.clone()) {
+            Ok(m) => m,
+            Err(reason) => {
+                out.events.push(SimEvent::Rejected { height, reason });
+                return;
+            }
+        };
+        out.events.push(SimEvent::Proposed {
+            height,
+            view: pre_prepare.view,
+            hash: pre_prepare.block_hash.clone(),
+        });
+
+        if self.behaviour == Behaviour::Equivocate {
+            self.propose_equivocating(pre_prepare, block, out);
+            return;
+        }
+
+        self.broadcast(
+            out,
+            SimMessage::Proposal {
+                pre_prepare: pre_prepare.clone(),
+                block,
+            },
+        );
+        self.vote_for_proposal(now_ms, &pre_prepare, out);
+    }
+
+    /// Byzantine leader: proposal A (and a PREPARE for it) to even peers,
+    /// a conflicting proposal B to odd peers
+    fn propose_equivocating(
+        &mut self,
+        pre_prepare: ConsensusMessage,
+        block: ConsensusBlock,
+        out: &mut Outbox,
+    ) {
+        let twin = ConsensusBlock {
+            timestamp: block.timestamp + 1,
+            ..block.clone()
+        };
+        let twin_hash = twin.calculate_hash();
+        let twin_pre_prepare =
+            self.forge(ConsensusMessageType::PrePrepare, &pre_prepare, &twin_hash);
+        let twin_prepare = self.forge(ConsensusMessageType::Prepare, &pre_prepare, &twin_hash);
+        let prepare = match self
+            .abft
+            .vote_prepare(pre_prepare.sequence, pre_prepare.block_hash.clone())
+        {
+            Ok(v) => v,
+            Err(_) => return,
+        };
+        let _ = self.abft.prepare(prepare.clone());
+
+        for peer in self.peers() {
+            let (pp, body, vote) = if peer % 2 == 0 {
+                (pre_prepare.clone(), block.clone(), prepare.clone())
+            } else {
+                (twin_pre_prepare.clone(), twin.clone(), twin_prepare.clone())
+            };
+            out.messages.push((
+                Some(peer),
+                SimMessage::Proposal {
+                    pre_prepare: pp.clone(),
+                    block: body,
+                },
+            ));
+            out.messages
+                .push((Some(peer), SimMessage::Prepare { vote, proposal: pp }));
+        }
+    }
+
+    fn build_block(&self, now_ms: u64) -> ConsensusBlock {
+        // Only batch ledger blocks that still apply, in mempool order
+        let mut scratch = self.ledger.clone();
+        let txs: Vec<Block> = self
+            .mempool
+            .iter()
+            .filter(|tx| scratch.process_block(tx).is_ok_and(|r| r.is_new()))
+            .take(MAX_TXS_PER_BLOCK)
+            .cloned()
+            .collect();
+
+        ConsensusBlock {
+            height: self.next_height(),
+            timestamp: now_ms,
+            data: serde_json::to_vec(&txs).unwrap_or_default(),
+            proposer: self.address.clone(),
+            parent_hash: self.tip_hash(),
+        }
+    }
+
+    /// Check a proposed block against our finalized chain and ledger.
+    /// `block.proposer` is not checked: a re-proposed lock keeps its original proposer.
+    fn validate_block(&self, block: &ConsensusBlock) -> Result<(), String> {
+        if block.height != self.next_height() {
+            return Err(format!(
+                "Wrong height {} (expected {})",
+                block.height,
+                self.next_height()
+            ));
+        }
+        if block.parent_hash != self.tip_hash() {
+            return Err(format!("Unknown parent {}", block.parent_hash));
+        }
+        let txs: Vec<Block> =
+            serde_json::from_slice(&block.data).map_err(|e| format!("Malformed payload: {}", e))?;
+        let mut scratch = self.ledger.clone();
+        for tx in &txs {
+            scratch
+                .process_block(tx)
+                .map_err(|e| format!("Invalid transaction: {}", e))?;
+        }
+        Ok(())
+    }
+
+    fn on_proposal(
+        &mut self,
+        now_ms: u64,
+        pre_prepare: ConsensusMessage,
+        block: ConsensusBlock,
+        out: &mut Outbox,
+    ) {
+        self.observe_signature(now_ms, &pre_prepare, out);
+        let height = self.next_height();
+        if self.is_banned(&pre_prepare.sender)
+            || block.height != height
+            || pre_prepare.view != self.abft.view
+        {
+            return;
+        }
+        if self.abft.state == ValidatorState::ViewChanging {
+            // The new leader only proposes after a view-change quorum:
+            // its proposal doubles as the NEW-VIEW announcement
+            self.enter_view(now_ms, pre_prepare.view);
+        }
+
+        if let Err(reason) = self.validate_block(&block) {
+            out.events.push(SimEvent::Rejected { height, reason });
+            return;
+        }
+        let hash = block.calculate_hash();
+        if let Some((_, locked)) = &self.committed_lock {
+            let justified = self
+                .reproposal
+                .as_ref()
+                .is_some_and(|b| b.calculate_hash() == hash);
+            if locked.calculate_hash() != hash && !justified {
+                out.events.push(SimEvent::Rejected {
+                    height,
+                    reason: format!("Locked on {}", locked.calculate_hash()),
+                });
+                return;
+            }
+        }
+        if let Err(reason) = self.abft.accept_pre_prepare(pre_prepare.clone(), block) {
+            out.events.push(SimEvent::Rejected { height, reason });
+            return;
+        }
+        if self
+            .committed_lock
+            .as_ref()
+            .is_some_and(|(_, b)| b.calculate_hash() != hash)
+        {
+            self.committed_lock = None;
+        }
+
+        self.vote_for_proposal(now_ms, &pre_prepare, out);
+    }
+
+    // ─────────────────────────────────────────────────────────────
+    // PREPARE / COMMIT
+    // ─────────────────────────────────────────────────────────────
+
+    fn vote_for_proposal(&mut self, now_ms: u64, pre_prepare: &ConsensusMessage, out: &mut Outbox) {
+        let vote = match self
+            .abft
+            .vote_prepare(pre_prepare.sequence, pre_prepare.block_hash.clone())
+        {
+            Ok(v) => v,
+            Err(reason) => {
+                out.events.push(SimEvent::Rejected {
+                    height: self.next_height(),
+                    reason,
+                });
+                return;
+            }
+        };
+        let _ = self.abft.prepare(vote.clone());
+        self.broadcast(
+            out,
+            SimMessage::Prepare {
+                vote: vote.clone(),
+                proposal: pre_prepare.clone(),
+            },
+        );
+
+        if self.behaviour == Behaviour::Equivocate {
+            let conflicting = format!("{}-twin", vote.block_hash);
+            let forged = self.forge(ConsensusMessageType::Prepare, &vote, &conflicting);
+            self.broadcast(
+                out,
+                SimMessage::Prepare {
+                    vote: forged,
+                    proposal: pre_prepare.clone(),
+                },
+            );
+        }
+
+        self.try_commit(now_ms, out);
+    }
+
+    fn on_prepare(
+        &mut self,
+        now_ms: u64,
+        vote: ConsensusMessage,
+        proposal: ConsensusMessage,
+        out: &mut Outbox,
+    ) {
+        self.observe_signature(now_ms, &proposal, out);
+        self.observe_signature(now_ms, &vote, out);
+        if self.is_banned(&vote.sender)
+            || vote.msg_type != ConsensusMessageType::Prepare
+            || vote.sequence <= self.abft.finalized_sequence
+        {
+            return;
+        }
+        if self.abft.prepare(vote).is_ok() {
+            self.try_commit(now_ms, out);
+        }
+    }
+
+    /// Send our COMMIT once 2f+1 PREPAREs agree on the block we are locked on
+    fn try_commit(&mut self, now_ms: u64, out: &mut Outbox) {
+        let height = self.next_height();
+        let Some(block) = self.abft.locked_block.clone() else {
+            return;
+        };
+        let hash = block.calculate_hash();
+        let Some(sequence) = self.proposal_sequence(&hash) else {
+            return;
+        };
+        if block.height != height || !self.abft.can_commit_block(sequence, &hash) {
+            return;
+        }
+        if self
+            .committed_lock
+            .as_ref()
+            .is_some_and(|(view, _)| *view == self.abft.view)
+        {
+            return;
+        }
+
+        let vote = match self.abft.vote_commit(sequence, hash) {
+            Ok(v) => v,
+            Err(reason) => {
+                out.events.push(SimEvent::Rejected { height, reason });
+                return;
+            }
+        };
+        self.committed_lock = Some((self.abft.view, block));
+        self.broadcast(out, SimMessage::Commit { vote: vote.clone() });
+        self.apply_commit(now_ms, vote, out);
+    }
+
+    fn on_commit(&mut self, now_ms: u64, vote: ConsensusMessage, out: &mut Outbox) {
+        self.observe_signature(now_ms, &vote, out);
+        if self.is_banned(&vote.sender)
+            || vote.msg_type != ConsensusMessageType::Commit
+            || vote.sequence <= self.abft.finalized_sequence
+        {
+            return;
+        }
+        self.apply_commit(now_ms, vote, out);
+    }
+
+    fn apply_commit(&mut self, now_ms: u64, vote: ConsensusMessage, out: &mut Outbox) {
+        // finalize_block drops the commit votes, so keep the certificate first
+        let mut certificate = self
+            .abft
+            .commit_votes
+            .get(&vote.sequence)
+            .cloned()
+            .unwrap_or_default();
+        if !certificate.iter().any(|v| v.sender == vote.sender) {
+            certificate.push(vote.clone());
+        }
+
+        if let Ok(true) = self.abft.commit(vote) {
+            if let Some(block) = self.abft.get_last_finalized_block() {
+                let hash = block.calculate_hash();
+                certificate.retain(|v| v.block_hash == hash);
+                self.on_finalized(now_ms, block, certificate, false, out);
+            }
+        }
+    }
+
+    fn on_finalized(
+        &mut self,
+        now_ms: u64,
+        block: ConsensusBlock,
+        certificate: Vec<ConsensusMessage>,
+        caught_up: bool,
+        out: &mut Outbox,
+    ) {
+        let height = block.height;
+        let hash = block.calculate_hash();
+        let txs: Vec<Block> = serde_json::from_slice(&block.data).unwrap_or_default();
+        for tx in &txs {
+            let _ = self.ledger.process_block(tx);
+        }
+        let ledger = &self.ledger;
+        self.mempool
+            .retain(|tx| !ledger.blocks.contains_key(&tx.calculate_hash()));
+        for vote in &certificate {
+            let _ = self
+                .slashing
+                .record_block_participation(&vote.sender, height, now_ms / 1000);
+        }
+
+        self.certificates.insert(height, certificate);
+        self.chain.push(block);
+        self.committed_lock = None;
+        self.reproposal = None;
+        self.failed_rounds = 0;
+        self.view_changes = self.view_changes.split_off(&(self.abft.view + 1));
+        self.reset_deadline(now_ms);
+
+        out.events.push(if caught_up {
+            SimEvent::CaughtUp { height, hash }
+        } else {
+            SimEvent::Finalized {
+                height,
+                view: self.abft.view,
+                hash,
+            }
+        });
+
+        self.propose(now_ms, out);
+    }
+
+    // ─────────────────────────────────────────────────────────────
+    // VIEW CHANGE
+    // ─────────────────────────────────────────────────────────────
+
+    fn start_view_change(&mut self, now_ms: u64, out: &mut Outbox) {
+        let vote = match self.abft.initiate_view_change() {
+            Ok(v) => v,
+            Err(_) => return,
+        };
+        let height = self.next_height();
+        let lock = self.committed_lock.clone();
+        self.view_changes
+            .entry(vote.view)
+            .or_default()
+            .insert(self.address.clone(), (vote.sequence, lock.clone()));
+        self.broadcast(out, SimMessage::ViewChange { vote, height, lock });
+        self.try_complete_view_change(now_ms, out);
+    }
+
+    fn on_view_change(
+        &mut self,
+        now_ms: u64,
+        from: NodeId,
+        vote: ConsensusMessage,
+        height: u64,
+        lock: Option<(u64, ConsensusBlock)>,
+        out: &mut Outbox,
+    ) {
+        if height < self.next_height() {
+            // Sender is behind: a view change will not help it, our certificates will
+            let blocks: Vec<_> = (height..self.next_height())
+                .take(MAX_CATCH_UP_BLOCKS)
+                .filter_map(|h| {
+                    let block = self.chain.get(h as usize - 1)?.clone();
+                    Some((block, self.certificates.get(&h)?.clone()))
+                })
+                .collect();
+            if !blocks.is_empty() {
+                self.send(out, from, SimMessage::Decided { blocks });
+            }
+            return;
+        }
+        if height > self.next_height()
+            || vote.msg_type != ConsensusMessageType::ViewChange
+            || vote.view < self.abft.view
+            || self.is_banned(&vote.sender)
+            || !vote.verify_mac_with_secret(&self.abft.shared_secret)
+        {
+            return;
+        }
+        self.view_changes
+            .entry(vote.view)
+            .or_default()
+            .insert(vote.sender, (vote.sequence, lock));
+
+        // f+1 validators are past our view, so at least one honest one timed out:
+        // join the smallest of their views (PBFT liveness rule)
+        let mut ahead: BTreeMap<&str, u64> = BTreeMap::new();
+        for (view, senders) in self.view_changes.range(self.abft.view + 1..) {
+            for sender in senders.keys() {
+                ahead.insert(sender, *view);
+            }
+        }
+        if ahead.len() > self.abft.f_max_faulty {
+            if let Some(target) = ahead.values().min().copied() {
+                self.abft.view = target - 1;
+                self.start_view_change(now_ms, out);
+                return;
+            }
+        }
+
+        self.try_complete_view_change(now_ms, out);
+    }
+
+    fn try_complete_view_change(&mut self, now_ms: u64, out: &mut Outbox) {
+        if self.abft.state != ValidatorState::ViewChanging {
+            return;
+        }
+        let view = self.abft.view;
+        let quorum = 2 * self.abft.f_max_faulty + 1;
+        if self.view_changes.get(&view).map_or(0, |v| v.len()) < quorum {
+            return;
+        }
+
+        self.enter_view(now_ms, view);
+        out.events.push(SimEvent::ViewChanged {
+            view,
+            leader: self.abft.get_leader(view),
+        });
+        self.propose(now_ms, out);
+    }
+
+    /// Install `view`, remember the highest committed lock its quorum reported
+    /// and move past every sequence the quorum has already signed
+    fn enter_view(&mut self, now_ms: u64, view: u64) {
+        let reports = self.view_changes.get(&view);
+        self.reproposal = reports.and_then(|votes| {
+            votes
+                .values()
+                .filter_map(|(_, lock)| lock.as_ref())
+                .max_by_key(|(locked_view, _)| *locked_view)
+                .map(|(_, block)| block.clone())
+        });
+        let reported = reports
+            .and_then(|votes| votes.values().map(|(sequence, _)| *sequence).max())
+            .unwrap_or(0);
+        if self.abft.complete_view_change(view).is_err() {
+            return;
+        }
+        self.abft.sequence = self.abft.sequence.max(reported);
+        if self.committed_lock.is_none() {
+            let _ = self.abft.abandon_proposal();
+        }
+        self.view_changes = self.view_changes.split_off(&view);
+        self.reset_deadline(now_ms);
+    }
+
+    // ─────────────────────────────────────────────────────────────
+    // CATCH-UP
+    // ─────────────────────────────────────────────────────────────
+
+    fn on_decided(
+        &mut self,
+        now_ms: u64,
+        blocks: Vec<(ConsensusBlock, Vec<ConsensusMessage>)>,
+        out: &mut Outbox,
+    ) {
+        let quorum = 2 * self.abft.f_max_faulty + 1;
+        for (block, certificate) in blocks {
+            let height = self.next_height();
+            if block.height < height {
+                continue;
+            }
+            let hash = block.calculate_hash();
+            let sequence = certificate
+                .iter()
+                .filter(|v| v.block_hash == hash)
+                .map(|v| v.sequence)
+                .max()
+                .unwrap_or(0);
+            let signers: BTreeSet<&str> = certificate
+                .iter()
+                .filter(|v| {
+                    v.msg_type == ConsensusMessageType::Commit
+                        && v.sequence == sequence
+                        && v.block_hash == hash
+                        && self.stakes.contains_key(&v.sender)
+                        && v.verify_mac_with_secret(&self.abft.shared_secret)
+                })
+                .map(|v| v.sender.as_str())
+                .collect();
+            if signers.len() < quorum {
+                out.events.push(SimEvent::Rejected {
+                    height,
+                    reason: format!("Certificate has {} of {} commits", signers.len(), quorum),
+                });
+                return;
+            }
+            if let Err(reason) = self.validate_block(&block) {
+                out.events.push(SimEvent::Rejected { height, reason });
+                return;
+            }
+
+            // Follow the view the network decided in
+            let decided_view = certificate.iter().map(|v| v.view).max().unwrap_or(0);
+            let was_changing = self.abft.state == ValidatorState::ViewChanging;
+            if self.abft.sync_finalized(block.clone(), sequence).is_err() {
+                return;
+            }
+            if was_changing || decided_view > self.abft.view {
+                self.abft.view = decided_view;
+            }
+            self.on_finalized(now_ms, block, certificate, true, out);
+        }
+    }
+
+    // ─────────────────────────────────────────────────────────────
+    // HELPERS
+    // ─────────────────────────────────────────────────────────────
+
+    /// Feed a signed message to double-signing detection; slash on conflict
+    fn observe_signature(&mut self, now_ms: u64, msg: &ConsensusMessage, out: &mut Outbox) {
+        if msg.block_hash.is_empty() || !msg.verify_mac_with_secret(&self.abft.shared_secret) {
+            return;
+        }
+        // Sequences never repeat across views, so one sequence is one slot
+        let timestamp = now_ms / 1000;
+        if self
+            .slashing
+            .record_signature(&msg.sender, msg.sequence, msg.block_hash.clone(), timestamp)
+            .is_ok()
+        {
+            return;
+        }
+        let stake = self.stakes.get(&msg.sender).copied().unwrap_or(0);
+        if self
+            .slashing
+            .slash_double_signing(&msg.sender, msg.sequence, stake, timestamp)
+            .is_ok()
+        {
+            out.events.push(SimEvent::DoubleSignDetected {
+                offender: msg.sender.clone(),
+                height: self.next_height(),
+            });
+        }
+    }
+
+    /// Sequence at which `hash` was proposed in the current view
+    fn proposal_sequence(&self, hash: &str) -> Option<u64> {
+        self.abft
+            .pre_prepare_messages
+            .iter()
+            .rev()
+            .find(|(_, m)| m.view == self.abft.view && m.block_hash == hash)
+            .map(|(sequence, _)| *sequence)
+    }
+
+    /// Byzantine helper: sign `template` again for a different block hash
+    fn forge(
+        &self,
+        msg_type: ConsensusMessageType,
+        template: &ConsensusMessage,
+        block_hash: &str,
+    ) -> ConsensusMessage {
+        ConsensusMessage::new_with_secret(
+            msg_type,
+            template.view,
+            template.sequence,
+            block_hash.to_string(),
+            self.address.clone(),
+            &self.abft.shared_secret,
+        )
+    }
+
+    fn reset_deadline(&mut self, now_ms: u64) {
+        let backoff = 1 + u64::from(self.failed_rounds.min(MAX_BACKOFF));
+        self.deadline_ms = now_ms + self.round_timeout_ms * backoff;
+    }
+
+    fn peers(&self) -> Vec<NodeId> {
+        self.directory
+            .values()
+            .copied()
+            .filter(|id| *id != self.id)
+            .collect()
+    }
+
+    fn broadcast(&self, out: &mut Outbox, message: SimMessage) {
+        if self.behaviour != Behaviour::Silent {
+            out.messages.push((None, message));
+        }
+    }
+
+    fn send(&self, out: &mut Outbox, to: NodeId, message: SimMessage) {
+        if self.behaviour != Behaviour::Silent {
+            out.messages.push((Some(to), message));
+        }
+    }
+}
+
+/// A ledger block without proof-of-work (rejected by `Ledger::process_block`)
+fn unmined_block(account: &str) -> Block {
+    Block {
+        account: account.to_string(),
+        previous: "0".to_string(),
+        block_type: BlockType::Send,
+        amount: 1,
+        link: "LOS_SIM_SINK".to_string(),
+        signature: String::new(),
+        public_key: String::new(),
+        work: 0,
+        timestamp: 0,
+        fee: 0,
+        memo: None,
+    }
+}
@@ -0,0 +1,567 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - SIMULATION DRIVER
+//
+// Discrete-event loop over the virtual clock. At each instant, in order:
+// 1. Scripted actions (partition, heal, behaviour/network changes)
+// 2. Message deliveries (FIFO among equal delivery times)
+// 3. Round timeouts (by node id)
+// All state is in BTreeMaps/Vecs and all randomness comes from one seeded
+// ChaCha8 stream, so a (seed, script) pair always yields the same trace.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use crate::network::{MessageBus, NetworkConfig, NetworkStats};
+use crate::node::{Behaviour, Outbox, SimMessage, SimNode};
+use crate::NodeId;
+use los_consensus::abft::ABFTConsensus;
+use los_core::{AccountState, Block, Ledger, MIN_VALIDATOR_STAKE_CIL};
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use sha3::{Digest, Keccak256};
+use std::collections::BTreeMap;
+
+/// Something a node observed or did, recorded in the trace
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SimEvent {
+    Proposed {
+        height: u64,
+        view: u64,
+        hash: String,
+    },
+    Finalized {
+        height: u64,
+        view: u64,
+        hash: String,
+    },
+    /// Finalized from a peer's commit certificate
+    CaughtUp {
+        height: u64,
+        hash: String,
+    },
+    ViewChanged {
+        view: u64,
+        leader: String,
+    },
+    Rejected {
+        height: u64,
+        reason: String,
+    },
+    DoubleSignDetected {
+        offender: String,
+        height: u64,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceEntry {
+    pub at_ms: u64,
+    pub node: NodeId,
+    pub event: SimEvent,
+}
+
+/// Scripted change applied at a virtual time
+#[derive(Debug, Clone)]
+pub enum SimAction {
+    /// Only nodes in the same group can communicate
+    Partition(Vec<Vec<NodeId>>),
+    Heal,
+    SetBehaviour(NodeId, Behaviour),
+    SetNetwork(NetworkConfig),
+    /// Hand a ledger block to every node's mempool
+    Submit(Block),
+}
+
+#[derive(Debug, Clone)]
+pub struct SimConfig {
+    pub seed: u64,
+    pub validators: usize,
+    /// Stake per validator (CIL); drives stake-weighted leader selection
+    pub stake_cil: u128,
+    pub network: NetworkConfig,
+    pub round_timeout_ms: u64,
+    /// Accounts present in every node's genesis ledger
+    pub genesis_accounts: Vec<(String, AccountState)>,
+}
+
+impl SimConfig {
+    /// `validators` equally staked nodes on the default network
+    pub fn new(seed: u64, validators: usize) -> Self {
+        Self {
+            seed,
+            validators,
+            stake_cil: MIN_VALIDATOR_STAKE_CIL,
+            network: NetworkConfig::default(),
+            round_timeout_ms: 1_000,
+            genesis_accounts: Vec::new(),
+        }
+    }
+}
+
+pub struct Simulation {
+    nodes: Vec<SimNode>,
+    bus: MessageBus,
+    rng: ChaCha8Rng,
+    now_ms: u64,
+    actions: BTreeMap<(u64, u64), SimAction>,
+    next_action_seq: u64,
+    trace: Vec<TraceEntry>,
+    started: bool,
+}
+
+impl Simulation {
+    pub fn new(config: SimConfig) -> Self {
+        let addresses: Vec<String> = (0..config.validators)
+            .map(|i| format!("LOS_SIM_{:02}", i))
+            .collect();
+        let stakes: BTreeMap<String, u128> = addresses
+            .iter()
+            .map(|a| (a.clone(), config.stake_cil))
+            .collect();
+        let directory: BTreeMap<String, NodeId> = addresses
+            .iter()
+            .enumerate()
+            .map(|(i, a)| (a.clone(), i))
+            .collect();
+
+        let mut hasher = Keccak256::new();
+        hasher.update(b"LOS_SIM_SHARED_SECRET");
+        hasher.update(config.seed.to_le_bytes());
+        let secret = hasher.finalize().to_vec();
+
+        let mut genesis = Ledger::new();
+        for (address, state) in &config.genesis_accounts {
+            genesis.accounts.insert(address.clone(), state.clone());
+        }
+
+        let nodes = addresses
+            .iter()
+            .enumerate()
+            .map(|(id, address)| {
+                let mut abft = ABFTConsensus::new(address.clone(), config.validators);
+                abft.set_shared_secret(secret.clone());
+                abft.update_validator_set_with_stake(stakes.clone().into_iter().collect());
+                SimNode::new(
+                    id,
+                    abft,
+                    genesis.clone(),
+                    stakes.clone(),
+                    directory.clone(),
+                    config.round_timeout_ms,
+                )
+            })
+            .collect();
+
+        Self {
+            nodes,
+            bus: MessageBus::new(config.network),
+            rng: ChaCha8Rng::seed_from_u64(config.seed),
+            now_ms: 0,
+            actions: BTreeMap::new(),
+            next_action_seq: 0,
+            trace: Vec::new(),
+            started: false,
+        }
+    }
+
+    pub fn now_ms(&self) -> u64 {
+        self.now_ms
+    }
+
+    pub fn nodes(&self) -> &[SimNode] {
+        &self.nodes
+    }
+
+    pub fn node(&self, id: NodeId) -> &SimNode {
+        &self.nodes[id]
+    }
+
+    /// Node id of the leader of `view` (all nodes share the genesis epoch)
+    pub fn leader_of(&self, view: u64) -> NodeId {
+        let leader = self.nodes[0].abft.get_leader(view);
+        self.nodes
+            .iter()
+            .position(|n| n.address == leader)
+            .unwrap_or(0)
+    }
+
+    pub fn set_behaviour(&mut self, id: NodeId, behaviour: Behaviour) {
+        self.nodes[id].behaviour = behaviour;
+    }
+
+    pub fn bus(&self) -> &MessageBus {
+        &self.bus
+    }
+
+    pub fn network_stats(&self) -> NetworkStats {
+        self.bus.stats()
+    }
+
+    /// Apply `action` when the virtual clock reaches `at_ms`
+    pub fn schedule(&mut self, at_ms: u64, action: SimAction) {
+        self.actions.insert((at_ms, self.next_action_seq), action);
+        self.next_action_seq += 1;
+    }
+
+    pub fn trace(&self) -> &[TraceEntry] {
+        &self.trace
+    }
+
+    /// Keccak256 over the whole trace; equal seeds and scripts give equal fingerprints
+    pub fn fingerprint(&self) -> String {
+        let mut hasher = Keccak256::new();
+        for entry in &self.trace {
+            hasher.update(format!("{:?}", entry).as_bytes());
+        }
+        hasher
+            .finalize()
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+
+    /// Lowest finalized height among honest nodes
+    pub fn honest_height(&self) -> u64 {
+        self.nodes
+            .iter()
+            .filter(|n| n.behaviour == Behaviour::Honest)
+            .map(|n| n.chain.len() as u64)
+            .min()
+            .unwrap_or(0)
+    }
+
+    /// Process the next event. Returns false once `until_ms` is reached.
+    pub fn step(&mut self, until_ms: u64) -> bool {
+        if !self.started {
+            self.started = true;
+            for id in 0..self.nodes.len() {
+                let mut out = Outbox::default();
+                self.nodes[id].start(self.now_ms, &mut out);
+                self.dispatch(id, out);
+            }
+        }
+
+        let next_action = self.actions.keys().next().map(|(at, _)| *at);
+        let next_delivery = self.bus.next_delivery_ms();
+        let next_timeout = self.nodes.iter().map(|n| n.deadline_ms()).min();
+        let Some(next) = [next_action, next_delivery, next_timeout]
+            .into_iter()
+            .flatten()
+            .min()
+        else {
+            return false;
+        };
+        if next > until_ms {
+            self.now_ms = until_ms;
+            return false;
+        }
+        self.now_ms = self.now_ms.max(next);
+
+        if next_action == Some(next) {
+            if let Some((_, action)) = self.actions.pop_first() {
+                self.apply(action);
+            }
+        } else if next_delivery == Some(next) {
+            if let Some(envelope) = self.bus.pop_due(self.now_ms) {
+                let mut out = Outbox::default();
+                self.nodes[envelope.to].on_message(
+                    self.now_ms,
+                    envelope.from,
+                    envelope.message,
+                    &mut out,
+                );
+                self.dispatch(envelope.to, out);
+            }
+        } else if let Some(id) = self.nodes.iter().position(|n| n.deadline_ms() == next) {
+            let mut out = Outbox::default();
+            self.nodes[id].on_timeout(self.now_ms, &mut out);
+            self.dispatch(id, out);
+        }
+        true
+    }
+
+    /// Run until the virtual clock reaches `until_ms`
+    pub fn run_until(&mut self, until_ms: u64) {
+        while self.step(until_ms) {}
+    }
+
+    /// Run until every honest node finalized `height`, or `deadline_ms` passes.
+    /// Returns whether the height was reached.
+    pub fn run_until_height(&mut self, height: u64, deadline_ms: u64) -> bool {
+        while self.honest_height() < height {
+            if !self.step(deadline_ms) {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Safety invariant: honest chains never diverge, and honest nodes at
+    /// the same height hold identical ledger state
+    pub fn check_safety(&self) -> Result<(), String> {
+        let honest: Vec<&SimNode> = self
+            .nodes
+            .iter()
+            .filter(|n| n.behaviour == Behaviour::Honest)
+            .collect();
+        for (i, a) in honest.iter().enumerate() {
+            for b in &honest[i + 1..] {
+                for (x, y) in a.chain.iter().zip(&b.chain) {
+                    if x.calculate_hash() != y.calculate_hash() {
+                        return Err(format!(
+                            "Fork at height {}: {} has {}, {} has {}",
+                            x.height,
+                            a.address,
+                            x.calculate_hash(),
+                            b.address,
+                            y.calculate_hash()
+                        ));
+                    }
+                }
+                if a.chain.len() == b.chain.len()
+                    && serde_json::to_string(&a.ledger.accounts).ok()
+                        != serde_json::to_string(&b.ledger.accounts).ok()
+                {
+                    return Err(format!(
+                        "Ledger state differs between {} and {} at height {}",
+                        a.address,
+                        b.address,
+                        a.chain.len()
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn apply(&mut self, action: SimAction) {
+        match action {
+            SimAction::Partition(groups) => self.bus.partition(groups),
+            SimAction::Heal => self.bus.heal(),
+            SimAction::SetBehaviour(id, behaviour) => self.set_behaviour(id, behaviour),
+            SimAction::SetNetwork(config) => self.bus.set_config(config),
+            SimAction::Submit(block) => {
+                for node in &mut self.nodes {
+                    node.mempool.push(block.clone());
+                }
+            }
+        }
+    }
+
+    fn dispatch(&mut self, from: NodeId, out: Outbox) {
+        for event in out.events {
+            self.trace.push(TraceEntry {
+                at_ms: self.now_ms,
+                node: from,
+                event,
+            });
+        }
+        for (to, message) in out.messages {
+            match to {
+                Some(to) => self.send(from, to, message),
+                None => {
+                    for to in (0..self.nodes.len()).filter(|to| *to != from) {
+                        self.send(from, to, message.clone());
+                    }
+                }
+            }
+        }
+    }
+
+    fn send(&mut self, from: NodeId, to: NodeId, message: SimMessage) {
+        self.bus.send(&mut self.rng, self.now_ms, from, to, message);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn count(sim: &Simulation, pred: impl Fn(&SimEvent) -> bool) -> usize {
+        sim.trace().iter().filter(|e| pred(&e.event)).count()
+    }
+
+    #[test]
+    fn test_honest_network_finalizes() {
+        let mut sim = Simulation::new(SimConfig::new(1, 4));
+        assert!(
+            sim.run_until_height(10, 60_000),
+            "stalled at {}",
+            sim.honest_height()
+        );
+        sim.check_safety().unwrap();
+        assert_eq!(count(&sim, |e| matches!(e, SimEvent::Rejected { .. })), 0);
+    }
+
+    #[test]
+    fn test_lossy_network_stays_safe_and_live() {
+        let mut config = SimConfig::new(7, 4);
+        config.network = NetworkConfig::lossy();
+        let mut sim = Simulation::new(config);
+
+        assert!(
+            sim.run_until_height(5, 300_000),
+            "stalled at {}",
+            sim.honest_height()
+        );
+        sim.check_safety().unwrap();
+        let stats = sim.network_stats();
+        assert!(stats.dropped > 0);
+    }
+
+    #[test]
+    fn test_silent_leader_triggers_view_change() {
+        let mut sim = Simulation::new(SimConfig::new(3, 4));
+        let leader = sim.leader_of(0);
+        sim.set_behaviour(leader, Behaviour::Silent);
+
+        assert!(
+            sim.run_until_height(3, 120_000),
+            "stalled at {}",
+            sim.honest_height()
+        );
+        sim.check_safety().unwrap();
+        assert!(count(&sim, |e| matches!(e, SimEvent::ViewChanged { .. })) > 0);
+        assert!(sim
+            .node(leader)
+            .chain
+            .iter()
+            .zip(&sim.node((leader + 1) % 4).chain)
+            .all(|(a, b)| a.calculate_hash() == b.calculate_hash()));
+    }
+
+    #[test]
+    fn test_equivocating_leader_is_slashed_without_fork() {
+        let mut sim = Simulation::new(SimConfig::new(5, 4));
+        let leader = sim.leader_of(0);
+        let offender = sim.node(leader).address.clone();
+        sim.set_behaviour(leader, Behaviour::Equivocate);
+
+        assert!(
+            sim.run_until_height(3, 120_000),
+            "stalled at {}",
+            sim.honest_height()
+        );
+        sim.check_safety().unwrap();
+        assert!(
+            count(&sim, |e| matches!(
+                e,
+                SimEvent::DoubleSignDetected { offender: o, .. } if *o == offender
+            )) > 0
+        );
+        let banned = sim
+            .nodes()
+            .iter()
+            .filter(|n| n.behaviour == Behaviour::Honest && n.is_banned(&offender))
+            .count();
+        assert!(banned > 0);
+    }
+
+    #[test]
+    fn test_equivocating_replica_is_slashed() {
+        let mut sim = Simulation::new(SimConfig::new(11, 4));
+        let replica = (sim.leader_of(0) + 1) % 4;
+        let offender = sim.node(replica).address.clone();
+        sim.set_behaviour(replica, Behaviour::Equivocate);
+
+        assert!(
+            sim.run_until_height(3, 120_000),
+            "stalled at {}",
+            sim.honest_height()
+        );
+        sim.check_safety().unwrap();
+        for node in sim
+            .nodes()
+            .iter()
+            .filter(|n| n.behaviour == Behaviour::Honest)
+        {
+            assert!(
+                node.is_banned(&offender),
+                "{} missed the equivocation",
+                node.address
+            );
+        }
+    }
+
+    #[test]
+    fn test_invalid_blocks_are_rejected() {
+        let mut sim = Simulation::new(SimConfig::new(13, 4));
+        let leader = sim.leader_of(0);
+        let byzantine = sim.node(leader).address.clone();
+        sim.set_behaviour(leader, Behaviour::InvalidBlocks);
+
+        assert!(
+            sim.run_until_height(4, 120_000),
+            "stalled at {}",
+            sim.honest_height()
+        );
+        sim.check_safety().unwrap();
+        assert!(count(&sim, |e| matches!(e, SimEvent::Rejected { .. })) > 0);
+        for node in sim
+            .nodes()
+            .iter()
+            .filter(|n| n.behaviour == Behaviour::Honest)
+        {
+            assert!(node.chain.iter().all(|b| b.proposer != byzantine));
+        }
+    }
+
+    #[test]
+    fn test_even_partition_stalls_then_heals() {
+        let mut sim = Simulation::new(SimConfig::new(17, 4));
+        sim.schedule(0, SimAction::Partition(vec![vec![0, 1], vec![2, 3]]));
+        sim.schedule(20_000, SimAction::Heal);
+
+        sim.run_until(19_999);
+        assert_eq!(sim.honest_height(), 0, "2 of 4 must not reach a quorum");
+        assert!(sim.network_stats().partitioned > 0);
+
+        assert!(
+            sim.run_until_height(3, 200_000),
+            "stalled at {}",
+            sim.honest_height()
+        );
+        sim.check_safety().unwrap();
+    }
+
+    #[test]
+    fn test_minority_partition_catches_up() {
+        let mut sim = Simulation::new(SimConfig::new(19, 4));
+        sim.schedule(0, SimAction::Partition(vec![vec![0, 1, 2], vec![3]]));
+        sim.schedule(30_000, SimAction::Heal);
+
+        sim.run_until(29_999);
+        let majority = sim.node(0).chain.len();
+        assert!(majority > 0);
+        assert_eq!(sim.node(3).chain.len(), 0);
+
+        assert!(sim.run_until_height(majority as u64 + 1, 200_000));
+        sim.check_safety().unwrap();
+        assert!(sim
+            .trace()
+            .iter()
+            .any(|e| e.node == 3 && matches!(e.event, SimEvent::CaughtUp { .. })));
+    }
+
+    #[test]
+    fn test_same_seed_same_trace() {
+        let run = |seed: u64| {
+            let mut config = SimConfig::new(seed, 4);
+            config.network = NetworkConfig::lossy();
+            let mut sim = Simulation::new(config);
+            sim.set_behaviour(1, Behaviour::Equivocate);
+            sim.schedule(5_000, SimAction::Partition(vec![vec![0, 1], vec![2, 3]]));
+            sim.schedule(10_000, SimAction::Heal);
+            sim.run_until(60_000);
+            (sim.trace().to_vec(), sim.fingerprint(), sim.network_stats())
+        };
+
+        let (trace_a, fp_a, stats_a) = run(42);
+        let (trace_b, fp_b, stats_b) = run(42);
+        assert!(!trace_a.is_empty());
+        assert_eq!(trace_a, trace_b);
+        assert_eq!(fp_a, fp_b);
+        assert_eq!(stats_a, stats_b);
+
+        let (_, fp_c, _) = run(43);
+        assert_ne!(fp_a, fp_c);
+    }
+}
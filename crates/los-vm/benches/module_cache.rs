@@ -0,0 +1,88 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - MODULE CACHE BENCHMARK
+//
+// Per-call latency of `execute_wasm_hosted` with a cold module cache (every
+// call compiles, the behaviour before the cache existed) versus a warm one.
+//
+//   cargo bench -p los-vm --bench module_cache
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use los_vm::WasmEngine;
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+const ITERATIONS: u32 = 50;
+const GAS_LIMIT: u64 = 10_000_000;
+
+/// A contract-sized module: `fn_count` exported arithmetic functions
+fn build_module(fn_count: usize) -> Vec<u8> {
+    let mut wat = String::from("(module\n");
+    for i in 0..fn_count {
+        wat.push_str(&format!(
+            "(func (export \"f{i}\") (param i32 i32) (result i32)
+               local.get 0 local.get 1 i32.add i32.const {i} i32.mul
+               local.get 0 i32.xor local.get 1 i32.sub)\n"
+        ));
+    }
+    wat.push(')');
+    wasmer::wat2wasm(wat.as_bytes()).unwrap().to_vec()
+}
+
+fn call(engine: &WasmEngine, bytecode: &[u8]) -> u64 {
+    engine
+        .execute_wasm_hosted(
+            bytecode,
+            "f1",
+            &["5".to_string(), "7".to_string()],
+            GAS_LIMIT,
+            "LOS_bench_caller",
+            "LOSCon_bench",
+            &BTreeMap::new(),
+            0,
+            0,
+            0,
+        )
+        .expect("benchmark call failed")
+        .gas_used
+}
+
+fn per_call(total: Duration) -> String {
+    format!(
+        "{:>10.1} µs/call",
+        total.as_secs_f64() * 1e6 / ITERATIONS as f64
+    )
+}
+
+fn main() {
+    for fn_count in [10, 200] {
+        let bytecode = build_module(fn_count);
+
+        // Cold: a fresh engine per call compiles every time
+        let start = Instant::now();
+        let mut cold_gas = 0;
+        for _ in 0..ITERATIONS {
+            cold_gas = call(&WasmEngine::new(), &bytecode);
+        }
+        let cold = start.elapsed();
+
+        // Warm: one engine, compiled once
+        let engine = WasmEngine::new();
+        call(&engine, &bytecode);
+        let start = Instant::now();
+        let mut warm_gas = 0;
+        for _ in 0..ITERATIONS {
+            warm_gas = call(&engine, &bytecode);
+        }
+        let warm = start.elapsed();
+
+        assert_eq!(cold_gas, warm_gas, "cache must not change gas_used");
+        println!(
+            "module {:>6} bytes | uncached {} | cached {} | speedup {:>5.1}x | gas {}",
+            bytecode.len(),
+            per_call(cold),
+            per_call(warm),
+            cold.as_secs_f64() / warm.as_secs_f64(),
+            warm_gas
+        );
+    }
+}
@@ -246,16 +246,10 @@ pub fn compute_quote(
 
     // Price impact
     let precision: u128 = 1_000_000_000_000;
-    let spot = if reserve_in > 0 {
-        reserve_out * precision / reserve_in
-    } else {
-        0
-    };
-    let exec = if amount_in > 0 {
-        amount_out * precision / amount_in
-    } else {
-        0
-    };
+    let spot = (reserve_out * precision)
+        .checked_div(reserve_in)
+        .unwrap_or(0);
+    let exec = (amount_out * precision).checked_div(amount_in).unwrap_or(0);
     let impact_bps = if spot > 0 && spot > exec {
         ((spot - exec) * 10_000) / spot
     } else {
@@ -0,0 +1,290 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - WASM EXECUTOR POOL
+//
+// Contract calls run on a fixed set of worker threads fed by a bounded queue.
+// Previously every call spawned a thread and abandoned it on timeout; 16
+// abandoned threads disabled the VM until restart. Now:
+// - Every call is bounded by metering (MAX_GAS_PER_CALL), so a worker always
+//   gets its thread back, even from an infinite loop.
+// - At most `workers` calls execute at once and MAX_QUEUED_CALLS wait. What
+//   happens beyond that is the caller's `Backpressure`: calls that are already
+//   part of the chain wait for a slot (dropping one would diverge state from
+//   the other validators), everything else fails fast with "busy". New
+//   transactions are turned away at admission (`has_capacity`) instead.
+// - Read-only queries and simulations each get their own small pool
+//   (`queries()`, `simulations()`), so a burst of API traffic can never
+//   occupy the workers that apply blocks.
+// - A panicking call is caught and never takes its worker down.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, SyncSender, TrySendError};
+use std::sync::{Arc, Mutex, OnceLock};
+use tracing::warn;
+
+/// Calls allowed to wait for a free worker before submissions are rejected
+pub const MAX_QUEUED_CALLS: usize = 64;
+/// Upper bound on worker threads (defaults to available parallelism)
+pub const MAX_WORKERS: usize = 8;
+/// Worker threads reserved for read-only queries
+pub const QUERY_WORKERS: usize = 2;
+/// Queries allowed to wait for a free query worker
+pub const MAX_QUEUED_QUERIES: usize = 16;
+/// Worker threads reserved for simulations
+pub const SIMULATION_WORKERS: usize = 2;
+/// Simulations allowed to wait for a free simulation worker
+pub const MAX_QUEUED_SIMULATIONS: usize = 8;
+
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+/// What `Executor::submit` does when every worker is busy and the queue is full
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backpressure {
+    /// Block the caller until a queue slot frees up
+    Wait,
+    /// Fail immediately with "busy"
+    Reject,
+}
+
+/// Executor counters
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExecutorStats {
+    pub workers: usize,
+    pub busy: usize,
+    pub completed: u64,
+    pub rejected: u64,
+    pub panicked: u64,
+}
+
+#[derive(Default)]
+struct Counters {
+    busy: AtomicUsize,
+    queued: AtomicUsize,
+    completed: AtomicU64,
+    rejected: AtomicU64,
+    panicked: AtomicU64,
+}
+
+/// Fixed-size worker pool with a bounded job queue
+pub struct Executor {
+    queue: SyncSender<Job>,
+    workers: usize,
+    max_queued: usize,
+    counters: Arc<Counters>,
+}
+
+impl Executor {
+    /// Start `workers` threads (at least 1) sharing a queue of `max_queued` jobs
+    pub fn new(workers: usize, max_queued: usize) -> Self {
+        Self::named("los-wasm", workers, max_queued)
+    }
+
+    fn named(name: &str, workers: usize, max_queued: usize) -> Self {
+        let workers = workers.max(1);
+        let (queue, rx) = mpsc::sync_channel::<Job>(max_queued);
+        let rx = Arc::new(Mutex::new(rx));
+        let counters = Arc::new(Counters::default());
+        for i in 0..workers {
+            let rx = Arc::clone(&rx);
+            let counters = Arc::clone(&counters);
+            let spawned = std::thread::Builder::new()
+                .name(format!("{}-{}", name, i))
+                .spawn(move || worker_loop(rx, counters));
+            if let Err(e) = spawned {
+                warn!("Failed to start WASM worker {}: {}", i, e);
+            }
+        }
+        Executor {
+            queue,
+            workers,
+            max_queued,
+            counters,
+        }
+    }
+
+    /// Queue a job. When every worker is busy and the queue is full, waits or
+    /// fails with "busy" depending on `backpressure`.
+    pub fn submit(
+        &self,
+        job: impl FnOnce() + Send + 'static,
+        backpressure: Backpressure,
+    ) -> Result<(), String> {
+        let counters = Arc::clone(&self.counters);
+        let job: Job = Box::new(move || {
+            counters.queued.fetch_sub(1, Ordering::Relaxed);
+            job()
+        });
+        self.counters.queued.fetch_add(1, Ordering::Relaxed);
+        let sent = match backpressure {
+            Backpressure::Wait => self
+                .queue
+                .send(job)
+                .map_err(|e| TrySendError::Disconnected(e.0)),
+            Backpressure::Reject => self.queue.try_send(job),
+        };
+        if sent.is_err() {
+            self.counters.queued.fetch_sub(1, Ordering::Relaxed);
+        }
+        match sent {
+            Ok(()) => Ok(()),
+            Err(TrySendError::Full(_)) => {
+                self.counters.rejected.fetch_add(1, Ordering::Relaxed);
+                Err(format!(
+                    "WASM executor busy: {} calls running, queue full",
+                    self.workers
+                ))
+            }
+            Err(TrySendError::Disconnected(_)) => Err("WASM executor stopped".to_string()),
+        }
+    }
+
+    /// Whether a new call could start or queue right now. Used to turn new
+    /// transactions away at admission rather than stall the pool.
+    pub fn has_capacity(&self) -> bool {
+        let busy = self.counters.busy.load(Ordering::Relaxed);
+        let queued = self.counters.queued.load(Ordering::Relaxed);
+        busy + queued < self.workers + self.max_queued
+    }
+
+    pub fn stats(&self) -> ExecutorStats {
+        ExecutorStats {
+            workers: self.workers,
+            busy: self.counters.busy.load(Ordering::Relaxed),
+            completed: self.counters.completed.load(Ordering::Relaxed),
+            rejected: self.counters.rejected.load(Ordering::Relaxed),
+            panicked: self.counters.panicked.load(Ordering::Relaxed),
+        }
+    }
+}
+
+fn worker_loop(rx: Arc<Mutex<Receiver<Job>>>, counters: Arc<Counters>) {
+    loop {
+        // Hold the lock only while waiting for the next job
+        let job = match rx.lock() {
+            Ok(rx) => rx.recv(),
+            Err(_) => return,
+        };
+        let Ok(job) = job else {
+            return; // Executor dropped
+        };
+        counters.busy.fetch_add(1, Ordering::Relaxed);
+        let outcome = std::panic::catch_unwind(std::panic::AssertUnwindSafe(job));
+        counters.busy.fetch_sub(1, Ordering::Relaxed);
+        match outcome {
+            Ok(()) => counters.completed.fetch_add(1, Ordering::Relaxed),
+            Err(_) => counters.panicked.fetch_add(1, Ordering::Relaxed),
+        };
+    }
+}
+
+/// Process-wide executor for contract calls carried by blocks
+pub fn global() -> &'static Executor {
+    static EXECUTOR: OnceLock<Executor> = OnceLock::new();
+    EXECUTOR.get_or_init(|| {
+        let workers = std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1)
+            .min(MAX_WORKERS);
+        Executor::new(workers, MAX_QUEUED_CALLS)
+    })
+}
+
+/// Process-wide executor for read-only queries, separate from `global()`
+pub fn queries() -> &'static Executor {
+    static EXECUTOR: OnceLock<Executor> = OnceLock::new();
+    EXECUTOR.get_or_init(|| Executor::named("los-query", QUERY_WORKERS, MAX_QUEUED_QUERIES))
+}
+
+/// Process-wide executor for `/simulate` dry-runs, separate from `global()`
+pub fn simulations() -> &'static Executor {
+    static EXECUTOR: OnceLock<Executor> = OnceLock::new();
+    EXECUTOR.get_or_init(|| Executor::named("los-sim", SIMULATION_WORKERS, MAX_QUEUED_SIMULATIONS))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_bounded_queue_rejects_when_full() {
+        let executor = Executor::new(1, 1);
+        let (release_tx, release_rx) = mpsc::channel::<()>();
+        let (started_tx, started_rx) = mpsc::channel::<()>();
+
+        // Occupy the only worker, then fill the single queue slot
+        executor
+            .submit(
+                move || {
+                    started_tx.send(()).unwrap();
+                    let _ = release_rx.recv();
+                },
+                Backpressure::Reject,
+            )
+            .unwrap();
+        started_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        executor.submit(|| {}, Backpressure::Reject).unwrap();
+
+        assert!(!executor.has_capacity());
+        assert!(executor
+            .submit(|| {}, Backpressure::Reject)
+            .unwrap_err()
+            .contains("busy"));
+        assert_eq!(executor.stats().rejected, 1);
+        release_tx.send(()).unwrap();
+    }
+
+    #[test]
+    fn test_wait_backpressure_queues_instead_of_failing() {
+        let executor = Arc::new(Executor::new(1, 1));
+        let (release_tx, release_rx) = mpsc::channel::<()>();
+        let (started_tx, started_rx) = mpsc::channel::<()>();
+        executor
+            .submit(
+                move || {
+                    started_tx.send(()).unwrap();
+                    let _ = release_rx.recv();
+                },
+                Backpressure::Wait,
+            )
+            .unwrap();
+        started_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        executor.submit(|| {}, Backpressure::Wait).unwrap();
+
+        // The pool is full: the next submission blocks until the worker is released
+        let (done_tx, done_rx) = mpsc::channel();
+        let pool = Arc::clone(&executor);
+        std::thread::spawn(move || {
+            pool.submit(move || done_tx.send(7).unwrap(), Backpressure::Wait)
+                .unwrap();
+        });
+        assert!(done_rx.recv_timeout(Duration::from_millis(200)).is_err());
+        release_tx.send(()).unwrap();
+        assert_eq!(done_rx.recv_timeout(Duration::from_secs(5)).unwrap(), 7);
+        assert_eq!(executor.stats().rejected, 0);
+    }
+
+    #[test]
+    fn test_queries_do_not_share_the_block_pool() {
+        assert!(!std::ptr::eq(queries(), global()));
+        assert!(!std::ptr::eq(simulations(), global()));
+        assert!(!std::ptr::eq(simulations(), queries()));
+        assert_eq!(queries().stats().workers, QUERY_WORKERS);
+        assert_eq!(simulations().stats().workers, SIMULATION_WORKERS);
+    }
+
+    #[test]
+    fn test_panicking_job_keeps_worker() {
+        let executor = Executor::new(1, 4);
+        executor
+            .submit(|| panic!("contract bug"), Backpressure::Reject)
+            .unwrap();
+        let (tx, rx) = mpsc::channel();
+        executor
+            .submit(move || tx.send(42).unwrap(), Backpressure::Reject)
+            .unwrap();
+        assert_eq!(rx.recv_timeout(Duration::from_secs(5)).unwrap(), 42);
+        assert_eq!(executor.stats().panicked, 1);
+    }
+}
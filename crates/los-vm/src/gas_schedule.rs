@@ -0,0 +1,184 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - HOST FUNCTION GAS SCHEDULE
+//
+// WASM instructions cost 1 gas each (Metering middleware). Host functions do
+// far more work than the `call` instruction that invokes them, so each one
+// also charges from this table: a base cost per call plus per-byte costs for
+// data read or written, events by payload size, transfers at a fixed cost.
+//
+// Schedules are versioned and activate at a unix time. A call is charged by
+// the schedule in force at its block's signed timestamp (`ContractCall::
+// block_timestamp`), which every validator reads from the same block, so
+// `gas_used` stays identical across the network however far each node has
+// synced.
+// A new schedule is only ever appended, with an activation time in the
+// future; existing entries are never edited.
+// On-chain governance may pin a version instead (`GovParam::GasScheduleVersion`,
+// applied through `WasmEngine::pin_gas_schedule`).
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use serde::Serialize;
+
+/// Gas costs charged by host functions, on top of instruction metering
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct GasSchedule {
+    pub version: u32,
+    /// First call block timestamp (unix seconds) this schedule applies to
+    pub activation_time: u64,
+    /// Every host call
+    pub host_call: u64,
+    /// `host_get_state`: per byte of key and value read
+    pub state_read_byte: u64,
+    /// `host_set_state`: per write
+    pub state_write: u64,
+    /// `host_set_state`: per byte of key and value written
+    pub state_write_byte: u64,
+    /// `host_del_state`: per delete (plus `state_read_byte` per key byte)
+    pub state_delete: u64,
+    /// `host_emit_event`: per event
+    pub event: u64,
+    /// `host_emit_event`: per byte of type and payload
+    pub event_byte: u64,
+    /// `host_transfer`: fixed cost per transfer
+    pub transfer: u64,
+    /// `host_log`, `host_get_arg`, `host_set_return` and context getters:
+    /// per byte copied across the guest boundary
+    pub copy_byte: u64,
+    /// `host_blake3`: per byte hashed
+    pub hash_byte: u64,
+}
+
+/// Time at which host functions start being charged (2027-01-01 00:00 UTC)
+pub const GAS_SCHEDULE_V1_TIME: u64 = 1_798_761_600;
+
+/// All schedules, ordered by activation time
+pub const GAS_SCHEDULES: &[GasSchedule] = &[
+    // V0: genesis behaviour, host calls cost only their `call` instruction
+    GasSchedule {
+        version: 0,
+        activation_time: 0,
+        host_call: 0,
+        state_read_byte: 0,
+        state_write: 0,
+        state_write_byte: 0,
+        state_delete: 0,
+        event: 0,
+        event_byte: 0,
+        transfer: 0,
+        copy_byte: 0,
+        hash_byte: 0,
+    },
+    GasSchedule {
+        version: 1,
+        activation_time: GAS_SCHEDULE_V1_TIME,
+        host_call: 50,
+        state_read_byte: 1,
+        state_write: 1_000,
+        state_write_byte: 10,
+        state_delete: 500,
+        event: 500,
+        event_byte: 5,
+        transfer: 5_000,
+        copy_byte: 1,
+        hash_byte: 2,
+    },
+];
+
+/// Schedule in force for a call block timestamped `time`
+pub fn schedule_at(time: u64) -> &'static GasSchedule {
+    GAS_SCHEDULES
+        .iter()
+        .rev()
+        .find(|s| s.activation_time <= time)
+        .unwrap_or(&GAS_SCHEDULES[0])
+}
+
+/// Schedule for a call at `time`, unless governance pinned a known `version`
+pub fn schedule_for(time: u64, pinned: Option<u32>) -> &'static GasSchedule {
+    pinned
+        .and_then(|v| GAS_SCHEDULES.iter().find(|s| s.version == v))
+        .unwrap_or_else(|| schedule_at(time))
+}
+
+impl GasSchedule {
+    /// Schedule in force for new calls (the last one)
+    pub fn latest() -> &'static GasSchedule {
+        &GAS_SCHEDULES[GAS_SCHEDULES.len() - 1]
+    }
+
+    pub fn state_read(&self, bytes: usize) -> u64 {
+        self.host_call
+            .saturating_add(self.state_read_byte.saturating_mul(bytes as u64))
+    }
+
+    pub fn state_write(&self, bytes: usize) -> u64 {
+        (self.host_call + self.state_write)
+            .saturating_add(self.state_write_byte.saturating_mul(bytes as u64))
+    }
+
+    pub fn state_delete(&self, key_bytes: usize) -> u64 {
+        (self.host_call + self.state_delete)
+            .saturating_add(self.state_read_byte.saturating_mul(key_bytes as u64))
+    }
+
+    pub fn event(&self, bytes: usize) -> u64 {
+        (self.host_call + self.event).saturating_add(self.event_byte.saturating_mul(bytes as u64))
+    }
+
+    pub fn transfer(&self) -> u64 {
+        self.host_call + self.transfer
+    }
+
+    pub fn copy(&self, bytes: usize) -> u64 {
+        self.host_call
+            .saturating_add(self.copy_byte.saturating_mul(bytes as u64))
+    }
+
+    pub fn hash(&self, bytes: usize) -> u64 {
+        self.host_call
+            .saturating_add(self.hash_byte.saturating_mul(bytes as u64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schedules_are_ordered_and_versioned() {
+        assert_eq!(GAS_SCHEDULES[0].activation_time, 0);
+        for (i, pair) in GAS_SCHEDULES.windows(2).enumerate() {
+            assert!(pair[0].activation_time < pair[1].activation_time);
+            assert_eq!(pair[1].version, i as u32 + 1);
+        }
+    }
+
+    #[test]
+    fn test_schedule_at_activation_boundary() {
+        assert_eq!(schedule_at(0).version, 0);
+        assert_eq!(schedule_at(GAS_SCHEDULE_V1_TIME - 1).version, 0);
+        assert_eq!(schedule_at(GAS_SCHEDULE_V1_TIME).version, 1);
+        assert_eq!(schedule_for(0, Some(1)).version, 1);
+        assert_eq!(schedule_for(GAS_SCHEDULE_V1_TIME, Some(0)).version, 0);
+        assert_eq!(schedule_for(0, Some(99)).version, 0);
+        assert_eq!(schedule_at(u64::MAX), GasSchedule::latest());
+    }
+
+    #[test]
+    fn test_v0_charges_nothing() {
+        let v0 = schedule_at(0);
+        assert_eq!(v0.state_write(1_000), 0);
+        assert_eq!(v0.event(1_000), 0);
+        assert_eq!(v0.transfer(), 0);
+    }
+
+    #[test]
+    fn test_v1_scales_with_bytes() {
+        let v1 = schedule_at(GAS_SCHEDULE_V1_TIME);
+        assert_eq!(v1.state_write(10), 50 + 1_000 + 100);
+        assert!(v1.state_write(1_000) > v1.state_write(10));
+        assert_eq!(v1.event(0), 550);
+        assert_eq!(v1.transfer(), 5_050);
+        assert_eq!(v1.state_read(u64::MAX as usize), u64::MAX);
+    }
+}
@@ -9,7 +9,7 @@
 //! |------------------------------|------------------------------------------------------|--------------------------------------|
 //! | `host_log`                   | `(i32, i32) -> ()`                                   | Debug log                            |
 //! | `host_abort`                 | `(i32, i32) -> ()`                                   | Abort + revert state                 |
-//! | `host_set_state`             | `(i32, i32, i32, i32) -> ()`                         | Write key-value to contract state    |
+//! | `host_set_state`             | `(i32, i32, i32, i32) -> ()`                         | Write key-value (deposit-metered)    |
 //! | `host_get_state`             | `(i32, i32, i32, i32) -> i32`                        | Read state (-1 = not found)          |
 //! | `host_del_state`             | `(i32, i32) -> ()`                                   | Delete state key                     |
 //! | `host_emit_event`            | `(i32, i32, i32, i32) -> ()`                         | Emit event (type + JSON data)        |
@@ -18,16 +18,48 @@
 //! | `host_get_self_address`      | `(i32, i32) -> i32`                                  | Get contract address                 |
 //! | `host_get_balance_lo`        | `() -> i64`                                          | Balance lower 64 bits                |
 //! | `host_get_balance_hi`        | `() -> i64`                                          | Balance upper 64 bits                |
+//! | `host_get_call_value_lo`     | `() -> i64`                                          | CIL sent with the call, lower 64 bits |
+//! | `host_get_call_value_hi`     | `() -> i64`                                          | CIL sent with the call, upper 64 bits |
 //! | `host_get_timestamp`         | `() -> i64`                                          | Block timestamp                      |
 //! | `host_get_arg_count`         | `() -> i32`                                          | Number of call arguments             |
 //! | `host_get_arg`               | `(i32, i32, i32) -> i32`                             | Get argument by index                |
 //! | `host_set_return`            | `(i32, i32) -> ()`                                   | Set return data                      |
 //! | `host_blake3`                | `(i32, i32, i32) -> i32`                             | Compute blake3 hash (32 bytes)       |
+//! | `host_token_transfer`        | `(i32, i32, i32, i32, i32, i32, i64, i64) -> i32`    | Move USP-01 tokens (see below)       |
+//!
+//! ## Gas
+//!
+//! Besides the `call` instruction itself, every host function charges from
+//! the [`GasSchedule`] in force at the call's block height (see
+//! `gas_schedule.rs`), drawing on the same metering points as instructions.
+//!
+//! ## Storage accounting
+//!
+//! Every state entry counts `key.len() + value.len()` bytes. A contract may
+//! hold up to [`MAX_CONTRACT_STORAGE_BYTES`]; beyond [`FREE_STORAGE_BYTES`]
+//! each byte locks [`STORAGE_DEPOSIT_PER_BYTE`] CIL of its balance, which is
+//! refunded when the state shrinks. A write that breaks either rule aborts
+//! the call.
+//!
+//! ## USP-01 token transfers
+//!
+//! `host_token_transfer(token, from, to, amount)` moves balances inside
+//! another contract that follows the USP-01 state layout (`bal:{addr}`,
+//! `allow:{owner}:{spender}`), with the calling contract as spender: tokens
+//! it holds itself move freely, anyone else's need an allowance, which is
+//! spent. Allowances the caller attached to the call itself (signed in the
+//! `CALL:` link) are spent first. The writes land in the token contract together with the caller's
+//! own state, so they commit or revert as one. The token's storage deposit
+//! is accounted like the caller's own.
 
-use crate::ContractEvent;
+use crate::gas_schedule::GasSchedule;
+use crate::{Contract, ContractEvent};
 use std::collections::{BTreeMap, HashSet};
 use std::sync::{Arc, Mutex};
-use wasmer::{imports, Function, FunctionEnv, FunctionEnvMut, Imports, Memory, Store};
+use wasmer::{
+    imports, Function, FunctionEnv, FunctionEnvMut, Global, Imports, Instance, Memory,
+    RuntimeError, Store, Type, Value,
+};
 
 // ─────────────────────────────────────────────────────────────────
 // Limits (prevent abuse from malicious contracts)
@@ -50,6 +82,71 @@ const MAX_STATE_KEYS: usize = 1_024;
 /// Maximum number of log lines per execution
 const MAX_LOGS: usize = 256;
 
+// ─────────────────────────────────────────────────────────────────
+// Storage accounting (bounds in-memory contract state)
+// ─────────────────────────────────────────────────────────────────
+
+/// Maximum state a single contract may hold, keys + values (16 MB)
+pub const MAX_CONTRACT_STORAGE_BYTES: u64 = 16_777_216;
+/// State every contract may hold without a deposit (64 KB)
+pub const FREE_STORAGE_BYTES: u64 = 65_536;
+/// Deposit locked from the contract balance per byte above the free
+/// allowance (1,000,000 CIL = 0.00001 LOS, so 10 LOS per MB)
+pub const STORAGE_DEPOSIT_PER_BYTE: u128 = 1_000_000;
+
+/// Bytes one state entry counts for: key length + value length.
+pub fn entry_size(key: &str, value_len: usize) -> u64 {
+    (key.len() + value_len) as u64
+}
+
+/// Deposit owed for holding `storage_bytes` of state.
+pub fn storage_deposit_for(storage_bytes: u64) -> u128 {
+    storage_bytes.saturating_sub(FREE_STORAGE_BYTES) as u128 * STORAGE_DEPOSIT_PER_BYTE
+}
+
+/// Account for an entry changing size from `old` to `new` bytes in a
+/// contract holding `bytes` of state, `deposit` and `balance`.
+///
+/// Growth locks deposit out of `balance`; shrinking refunds it, up to the
+/// deposit actually held (state older than the deposit rules is free).
+/// Nothing changes if the write would exceed the storage quota or the
+/// contract can't cover the deposit.
+fn account_storage(
+    bytes: &mut u64,
+    deposit: &mut u128,
+    balance: &mut u128,
+    old: u64,
+    new: u64,
+) -> Result<(), String> {
+    let after_bytes = bytes.saturating_sub(old).saturating_add(new);
+    if new > old && after_bytes > MAX_CONTRACT_STORAGE_BYTES {
+        return Err(format!(
+            "storage quota exceeded: {} bytes (max {})",
+            after_bytes, MAX_CONTRACT_STORAGE_BYTES
+        ));
+    }
+
+    let before = storage_deposit_for(*bytes);
+    let after = storage_deposit_for(after_bytes);
+    if after > before {
+        let owed = after - before;
+        if *balance < owed {
+            return Err(format!(
+                "insufficient balance for storage deposit: need {} CIL, have {}",
+                owed, balance
+            ));
+        }
+        *balance -= owed;
+        *deposit = deposit.saturating_add(owed);
+    } else {
+        let refund = (before - after).min(*deposit);
+        *balance = balance.saturating_add(refund);
+        *deposit -= refund;
+    }
+    *bytes = after_bytes;
+    Ok(())
+}
+
 // ─────────────────────────────────────────────────────────────────
 // Shared state types
 // ─────────────────────────────────────────────────────────────────
@@ -63,10 +160,40 @@ const MAX_LOGS: usize = 256;
 pub struct HostState {
     /// Reference to the guest's linear memory. Set after instantiation.
     pub memory: Option<Memory>,
+    /// The instance's metering globals, so host calls draw from the same
+    /// gas budget as instructions. Set after instantiation.
+    pub metering: Option<MeteringGlobals>,
+    /// Host function costs for this call (selected by block height).
+    pub gas_schedule: &'static GasSchedule,
     /// Shared mutable data accessed by host functions during execution.
     pub inner: Arc<Mutex<HostData>>,
 }
 
+/// Globals the Metering middleware exports from every metered instance.
+#[derive(Clone)]
+pub struct MeteringGlobals {
+    remaining: Global,
+    exhausted: Global,
+}
+
+impl MeteringGlobals {
+    /// Look up the metering globals of `instance` (None if it isn't metered).
+    pub fn of(instance: &Instance) -> Option<Self> {
+        Some(MeteringGlobals {
+            remaining: instance
+                .exports
+                .get_global("wasmer_metering_remaining_points")
+                .ok()?
+                .clone(),
+            exhausted: instance
+                .exports
+                .get_global("wasmer_metering_points_exhausted")
+                .ok()?
+                .clone(),
+        })
+    }
+}
+
 /// Mutable data accessed by host functions during a single WASM execution.
 ///
 /// All mutations here are transactional: on success, `dirty_keys` + `state`
@@ -99,6 +226,204 @@ pub struct HostData {
     pub aborted: bool,
     /// Human-readable abort reason.
     pub abort_message: String,
+    /// Bytes of state held (keys + values), updated on every set/delete.
+    pub storage_bytes: u64,
+    /// Storage deposit held, already taken out of `balance`.
+    pub storage_deposit: u128,
+    /// Deployed contracts, read by `host_token_transfer` (None = unavailable).
+    pub contracts: Option<Arc<Mutex<BTreeMap<String, Contract>>>>,
+    /// Pending writes to USP-01 token contracts, keyed by token address.
+    pub token_changes: BTreeMap<String, TokenChanges>,
+    /// Token transfers made so far (bounded like CIL transfers).
+    pub token_transfers: usize,
+    /// CIL sent with this call (already included in `balance`).
+    pub call_value: u128,
+    /// Token amounts the caller let this contract pull from it in this
+    /// call, spent before any stored allowance.
+    pub call_allowances: BTreeMap<String, u128>,
+}
+
+/// Writes one call makes to a USP-01 token contract's state.
+#[derive(Debug, Clone, Default)]
+pub struct TokenChanges {
+    /// Key → new value (decimal strings, as the token stores them).
+    pub state: BTreeMap<String, String>,
+    /// Token state held after the writes (keys + values).
+    pub storage_bytes: u64,
+    /// Token storage deposit held after the writes.
+    pub storage_deposit: u128,
+    /// Token balance after deposit changes.
+    pub balance: u128,
+}
+
+impl HostData {
+    /// Account for an entry changing size from `old` to `new` bytes.
+    /// Returns false, after aborting the call, if the write would exceed the
+    /// storage quota or the contract can't cover the deposit.
+    fn charge_storage(&mut self, old: u64, new: u64) -> bool {
+        match account_storage(
+            &mut self.storage_bytes,
+            &mut self.storage_deposit,
+            &mut self.balance,
+            old,
+            new,
+        ) {
+            Ok(()) => true,
+            Err(e) => {
+                self.aborted = true;
+                self.abort_message = e;
+                false
+            }
+        }
+    }
+
+    /// Current value of `key` in token contract `token`, including writes
+    /// made earlier in this call.
+    fn token_value(&self, token: &str, key: &str) -> Option<String> {
+        if let Some(v) = self.token_changes.get(token).and_then(|t| t.state.get(key)) {
+            return Some(v.clone());
+        }
+        let contracts = self.contracts.as_ref()?.lock().ok()?;
+        contracts.get(token)?.state.get(key).cloned()
+    }
+
+    fn token_amount(&self, token: &str, key: &str) -> u128 {
+        self.token_value(token, key)
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(0)
+    }
+
+    /// Start tracking writes to `token`. False if it isn't an initialized
+    /// USP-01 contract.
+    fn load_token(&mut self, token: &str) -> bool {
+        if self.token_changes.contains_key(token) {
+            return true;
+        }
+        let changes = {
+            let contracts = match self.contracts.as_ref().and_then(|c| c.lock().ok()) {
+                Some(c) => c,
+                None => return false,
+            };
+            match contracts.get(token) {
+                Some(c) if c.state.get("usp01:init").map(String::as_str) == Some("1") => {
+                    TokenChanges {
+                        state: BTreeMap::new(),
+                        storage_bytes: c.storage_bytes(),
+                        storage_deposit: c.storage_deposit,
+                        balance: c.balance,
+                    }
+                }
+                _ => return false,
+            }
+        };
+        self.token_changes.insert(token.to_string(), changes);
+        true
+    }
+
+    /// Write `amount` under `key` in `token`, charging its storage deposit.
+    fn set_token_amount(&mut self, token: &str, key: &str, amount: u128) -> Result<(), String> {
+        let old = self
+            .token_value(token, key)
+            .map_or(0, |v| entry_size(key, v.len()));
+        let value = amount.to_string();
+        let changes = self
+            .token_changes
+            .get_mut(token)
+            .ok_or("token not loaded")?;
+        account_storage(
+            &mut changes.storage_bytes,
+            &mut changes.storage_deposit,
+            &mut changes.balance,
+            old,
+            entry_size(key, value.len()),
+        )
+        .map_err(|e| format!("token {}: {}", token, e))?;
+        changes.state.insert(key.to_string(), value);
+        Ok(())
+    }
+
+    /// Move `amount` of `token` from `from` to `to` with this contract as
+    /// spender. Returns the `host_token_transfer` result code.
+    fn transfer_tokens(&mut self, token: &str, from: &str, to: &str, amount: u128) -> i32 {
+        if self.aborted {
+            return 4;
+        }
+        if token.is_empty() || from.is_empty() || to.is_empty() || from == to {
+            return 4;
+        }
+        if token == self.self_address {
+            return 4;
+        }
+        if self.token_transfers >= MAX_TRANSFERS {
+            return 5;
+        }
+        if !self.load_token(token) {
+            return 3;
+        }
+        if amount == 0 {
+            return 0; // Zero transfer is a no-op
+        }
+
+        let spender = self.self_address.clone();
+        let from_key = format!("bal:{}", from);
+        let from_bal = self.token_amount(token, &from_key);
+        if from_bal < amount {
+            return 1;
+        }
+        let allow_key = format!("allow:{}:{}", from, spender);
+        // The caller's per-call allowance is spent first, then the stored one
+        let call_allowance = if from == self.caller {
+            self.call_allowances.get(token).copied().unwrap_or(0)
+        } else {
+            0
+        };
+        let from_call = call_allowance.min(amount);
+        let allowance = if from == spender {
+            u128::MAX
+        } else {
+            self.token_amount(token, &allow_key)
+        };
+        if allowance < amount - from_call {
+            return 2;
+        }
+
+        let to_key = format!("bal:{}", to);
+        let to_bal = match self.token_amount(token, &to_key).checked_add(amount) {
+            Some(b) => b,
+            None => return 4,
+        };
+        let mut writes = vec![(from_key, from_bal - amount), (to_key, to_bal)];
+        if from != spender && amount > from_call {
+            writes.push((allow_key, allowance - (amount - from_call)));
+        }
+        for (key, value) in writes {
+            if let Err(e) = self.set_token_amount(token, &key, value) {
+                self.aborted = true;
+                self.abort_message = e;
+                return 4;
+            }
+        }
+        self.token_transfers += 1;
+        if from_call > 0 {
+            self.call_allowances
+                .insert(token.to_string(), call_allowance - from_call);
+        }
+
+        if self.events.len() < MAX_EVENTS {
+            let mut data = BTreeMap::new();
+            data.insert("from".to_string(), from.to_string());
+            data.insert("to".to_string(), to.to_string());
+            data.insert("amount".to_string(), amount.to_string());
+            data.insert("spender".to_string(), spender);
+            self.events.push(ContractEvent {
+                contract: token.to_string(),
+                event_type: "USP01:Transfer".to_string(),
+                data,
+                timestamp: self.timestamp,
+            });
+        }
+        0
+    }
 }
 
 /// Result of hosted WASM execution, returned to the caller.
@@ -112,10 +437,18 @@ pub struct HostExecResult {
     pub gas_used: u64,
     /// State changes (only dirty keys). Key → new value bytes.
     pub state_changes: BTreeMap<String, Vec<u8>>,
+    /// Dirty keys that were deleted.
+    pub deleted_keys: Vec<String>,
+    /// State held after execution (keys + values).
+    pub storage_bytes: u64,
+    /// Storage deposit held after execution.
+    pub storage_deposit: u128,
     /// Events emitted during execution.
     pub events: Vec<ContractEvent>,
     /// Pending transfers (recipient, amount_cil).
     pub transfers: Vec<(String, u128)>,
+    /// Writes to USP-01 token contracts, keyed by token address.
+    pub token_changes: BTreeMap<String, TokenChanges>,
     /// Debug logs.
     pub logs: Vec<String>,
     /// Whether contract called abort.
@@ -171,10 +504,34 @@ fn write_guest_bytes(env: &FunctionEnvMut<HostState>, ptr: u32, data: &[u8], max
 // Host function implementations
 // ─────────────────────────────────────────────────────────────────
 
+/// Charge `cost` gas against the call's metering points. Running out traps
+/// the call with the same out-of-gas outcome as instruction metering.
+fn charge_gas(env: &mut FunctionEnvMut<HostState>, cost: u64) -> Result<(), RuntimeError> {
+    if cost == 0 {
+        return Ok(());
+    }
+    let (remaining, exhausted) = match &env.data().metering {
+        Some(m) => (m.remaining.clone(), m.exhausted.clone()),
+        None => return Ok(()),
+    };
+    let left = match remaining.get(env) {
+        Value::I64(points) => points as u64,
+        _ => 0,
+    };
+    if left < cost {
+        remaining.set(env, Value::I64(0))?;
+        exhausted.set(env, Value::I32(1))?;
+        return Err(RuntimeError::new("Out of gas: host call"));
+    }
+    remaining.set(env, Value::I64((left - cost) as i64))
+}
+
 /// `host_log(ptr: i32, len: i32)` — Write a debug log line.
-/// Gas-metered by wasmer instruction counting. Capped at MAX_LOG_SIZE bytes.
-fn host_log_fn(env: FunctionEnvMut<HostState>, ptr: i32, len: i32) {
+/// Charged per byte copied. Capped at MAX_LOG_SIZE bytes.
+fn host_log_fn(mut env: FunctionEnvMut<HostState>, ptr: i32, len: i32) -> Result<(), RuntimeError> {
     let len = (len as u32).min(MAX_LOG_SIZE);
+    let cost = env.data().gas_schedule.copy(len as usize);
+    charge_gas(&mut env, cost)?;
     if let Some(msg) = read_guest_string(&env, ptr as u32, len) {
         if let Ok(mut inner) = env.data().inner.lock() {
             if inner.logs.len() < MAX_LOGS {
@@ -182,6 +539,7 @@ fn host_log_fn(env: FunctionEnvMut<HostState>, ptr: i32, len: i32) {
             }
         }
     }
+    Ok(())
 }
 
 /// `host_abort(ptr: i32, len: i32)` — Set abort flag. The SDK calls `unreachable` after
@@ -199,96 +557,136 @@ fn host_abort_fn(env: FunctionEnvMut<HostState>, ptr: i32, len: i32) {
 /// `host_set_state(key_ptr, key_len, val_ptr, val_len)` — Write a key-value pair to
 /// the contract's persistent state. Overwrites existing values.
 fn host_set_state_fn(
-    env: FunctionEnvMut<HostState>,
+    mut env: FunctionEnvMut<HostState>,
     key_ptr: i32,
     key_len: i32,
     val_ptr: i32,
     val_len: i32,
-) {
+) -> Result<(), RuntimeError> {
     let key_len = (key_len as u32).min(MAX_STATE_KEY_SIZE);
     let val_len = (val_len as u32).min(MAX_STATE_VALUE_SIZE);
+    let cost = env
+        .data()
+        .gas_schedule
+        .state_write((key_len + val_len) as usize);
+    charge_gas(&mut env, cost)?;
 
     let key = match read_guest_string(&env, key_ptr as u32, key_len) {
         Some(k) => k,
-        None => return,
+        None => return Ok(()),
     };
     let val = match read_guest_bytes(&env, val_ptr as u32, val_len) {
         Some(v) => v,
-        None => return,
+        None => return Ok(()),
     };
 
     if let Ok(mut inner) = env.data().inner.lock() {
+        if inner.aborted {
+            return Ok(());
+        }
         // Rate-limit: max distinct keys per execution
         if inner.dirty_keys.len() >= MAX_STATE_KEYS && !inner.dirty_keys.contains(&key) {
-            return;
+            return Ok(());
+        }
+        let old = inner
+            .state
+            .get(&key)
+            .map_or(0, |v| entry_size(&key, v.len()));
+        if !inner.charge_storage(old, entry_size(&key, val.len())) {
+            return Ok(());
         }
         inner.state.insert(key.clone(), val);
         inner.dirty_keys.insert(key);
     }
+    Ok(())
 }
 
 /// `host_get_state(key_ptr, key_len, out_ptr, out_max) -> i32`
 /// Read a value from the contract's state. Returns actual byte length, or -1 if key not found.
 /// If actual length exceeds `out_max`, data is truncated.
+/// Charged per byte of key and stored value.
 fn host_get_state_fn(
-    env: FunctionEnvMut<HostState>,
+    mut env: FunctionEnvMut<HostState>,
     key_ptr: i32,
     key_len: i32,
     out_ptr: i32,
     out_max: i32,
-) -> i32 {
+) -> Result<i32, RuntimeError> {
     let key_len = (key_len as u32).min(MAX_STATE_KEY_SIZE);
     let key = match read_guest_string(&env, key_ptr as u32, key_len) {
         Some(k) => k,
-        None => return -1,
+        None => return Ok(-1),
     };
 
-    let data = {
-        let inner = match env.data().inner.lock() {
-            Ok(i) => i,
-            Err(_) => return -1,
-        };
-        match inner.state.get(&key) {
-            Some(v) => v.clone(),
-            None => return -1,
-        }
-    };
+    let data = env
+        .data()
+        .inner
+        .lock()
+        .ok()
+        .and_then(|inner| inner.state.get(&key).cloned());
+    let cost = env
+        .data()
+        .gas_schedule
+        .state_read(key.len() + data.as_ref().map_or(0, Vec::len));
+    charge_gas(&mut env, cost)?;
 
-    write_guest_bytes(&env, out_ptr as u32, &data, out_max as u32)
+    Ok(match data {
+        Some(data) => write_guest_bytes(&env, out_ptr as u32, &data, out_max as u32),
+        None => -1,
+    })
 }
 
 /// `host_del_state(key_ptr, key_len)` — Delete a key from the contract's state.
-fn host_del_state_fn(env: FunctionEnvMut<HostState>, key_ptr: i32, key_len: i32) {
+fn host_del_state_fn(
+    mut env: FunctionEnvMut<HostState>,
+    key_ptr: i32,
+    key_len: i32,
+) -> Result<(), RuntimeError> {
     let key_len = (key_len as u32).min(MAX_STATE_KEY_SIZE);
+    let cost = env.data().gas_schedule.state_delete(key_len as usize);
+    charge_gas(&mut env, cost)?;
     let key = match read_guest_string(&env, key_ptr as u32, key_len) {
         Some(k) => k,
-        None => return,
+        None => return Ok(()),
     };
     if let Ok(mut inner) = env.data().inner.lock() {
-        inner.state.remove(&key);
+        if inner.aborted {
+            return Ok(());
+        }
+        if let Some(v) = inner.state.remove(&key) {
+            // Shrinking never fails; this only refunds deposit
+            inner.charge_storage(entry_size(&key, v.len()), 0);
+        }
         inner.dirty_keys.insert(key); // Mark as changed (deletion)
     }
+    Ok(())
 }
 
 /// `host_emit_event(type_ptr, type_len, data_ptr, data_len)` — Emit a structured event.
 /// `data` is JSON: `{"key1":"val1","key2":"val2"}`.
+/// Charged per event plus per byte of type and payload.
 fn host_emit_event_fn(
-    env: FunctionEnvMut<HostState>,
+    mut env: FunctionEnvMut<HostState>,
     type_ptr: i32,
     type_len: i32,
     data_ptr: i32,
     data_len: i32,
-) {
+) -> Result<(), RuntimeError> {
     let type_len = (type_len as u32).min(256);
     let data_len = (data_len as u32).min(MAX_STATE_VALUE_SIZE);
+    let cost = env
+        .data()
+        .gas_schedule
+        .event((type_len + data_len) as usize);
+    charge_gas(&mut env, cost)?;
 
     let event_type = match read_guest_string(&env, type_ptr as u32, type_len) {
         Some(t) => t,
-        None => return,
+        None => return Ok(()),
     };
     let data_str = match read_guest_string(&env, data_ptr as u32, data_len) {
         Some(d) => d,
-        None => return,
+        None => return Ok(()),
     };
 
     // Parse event data as JSON key-value pairs (gracefully defaults to empty on parse errors)
@@ -296,7 +694,7 @@ fn host_emit_event_fn(
 
     if let Ok(mut inner) = env.data().inner.lock() {
         if inner.events.len() >= MAX_EVENTS {
-            return;
+            return Ok(());
         }
         let contract_addr = inner.self_address.clone();
         let ts = inner.timestamp;
@@ -307,157 +705,300 @@ fn host_emit_event_fn(
             timestamp: ts,
         });
     }
+    Ok(())
 }
 
 /// `host_transfer(addr_ptr, addr_len, amount_lo: i64, amount_hi: i64) -> i32`
 /// Request a CIL transfer from the contract to `recipient`.
 /// `amount` is reconstructed as `(amount_hi << 64) | amount_lo` (u128).
 /// Returns: 0 = success, 1 = insufficient balance, 2 = invalid address, 3 = too many transfers.
+/// Charged a fixed cost whatever the outcome.
 fn host_transfer_fn(
-    env: FunctionEnvMut<HostState>,
+    mut env: FunctionEnvMut<HostState>,
     addr_ptr: i32,
     addr_len: i32,
     amount_lo: i64,
     amount_hi: i64,
-) -> i32 {
+) -> Result<i32, RuntimeError> {
+    let cost = env.data().gas_schedule.transfer();
+    charge_gas(&mut env, cost)?;
     let addr_len = (addr_len as u32).min(256);
     let recipient = match read_guest_string(&env, addr_ptr as u32, addr_len) {
         Some(a) if !a.is_empty() => a,
-        _ => return 2, // Invalid address
+        _ => return Ok(2), // Invalid address
     };
 
     // Reconstruct u128 from two i64 halves (reinterpreted as unsigned)
     let amount = ((amount_hi as u64 as u128) << 64) | (amount_lo as u64 as u128);
     if amount == 0 {
-        return 0; // Zero transfer is a no-op
+        return Ok(0); // Zero transfer is a no-op
     }
 
-    if let Ok(mut inner) = env.data().inner.lock() {
+    Ok(if let Ok(mut inner) = env.data().inner.lock() {
         if inner.transfers.len() >= MAX_TRANSFERS {
-            return 3;
+            return Ok(3);
         }
         if inner.balance < amount {
-            return 1; // Insufficient balance
+            return Ok(1); // Insufficient balance
         }
         inner.balance -= amount;
         inner.transfers.push((recipient, amount));
         0
     } else {
         2 // Lock failure treated as error
-    }
+    })
+}
+
+/// `host_token_transfer(token_ptr, token_len, from_ptr, from_len, to_ptr, to_len,
+/// amount_lo: i64, amount_hi: i64) -> i32`
+/// Move USP-01 tokens held by `from` in contract `token` to `to`, with this
+/// contract as spender (see "USP-01 token transfers" above).
+/// Returns: 0 = success, 1 = insufficient balance, 2 = insufficient allowance,
+/// 3 = not a USP-01 token, 4 = invalid arguments, 5 = too many transfers.
+/// Charged like `host_transfer`.
+#[allow(clippy::too_many_arguments)]
+fn host_token_transfer_fn(
+    mut env: FunctionEnvMut<HostState>,
+    token_ptr: i32,
+    token_len: i32,
+    from_ptr: i32,
+    from_len: i32,
+    to_ptr: i32,
+    to_len: i32,
+    amount_lo: i64,
+    amount_hi: i64,
+) -> Result<i32, RuntimeError> {
+    let cost = env.data().gas_schedule.transfer();
+    charge_gas(&mut env, cost)?;
+    let read = |ptr: i32, len: i32| read_guest_string(&env, ptr as u32, (len as u32).min(256));
+    let (token, from, to) = match (
+        read(token_ptr, token_len),
+        read(from_ptr, from_len),
+        read(to_ptr, to_len),
+    ) {
+        (Some(t), Some(f), Some(r)) => (t, f, r),
+        _ => return Ok(4),
+    };
+    let amount = ((amount_hi as u64 as u128) << 64) | (amount_lo as u64 as u128);
+
+    Ok(match env.data().inner.lock() {
+        Ok(mut inner) => inner.transfer_tokens(&token, &from, &to, amount),
+        Err(_) => 4,
+    })
 }
 
 /// `host_get_caller(out_ptr, out_max) -> i32` — Write caller's LOS address to guest memory.
 /// Returns number of bytes written, or -1 on error.
-fn host_get_caller_fn(env: FunctionEnvMut<HostState>, out_ptr: i32, out_max: i32) -> i32 {
+fn host_get_caller_fn(
+    mut env: FunctionEnvMut<HostState>,
+    out_ptr: i32,
+    out_max: i32,
+) -> Result<i32, RuntimeError> {
     let caller = {
         let inner = match env.data().inner.lock() {
             Ok(i) => i,
-            Err(_) => return -1,
+            Err(_) => return Ok(-1),
         };
         inner.caller.clone()
     };
-    write_guest_bytes(&env, out_ptr as u32, caller.as_bytes(), out_max as u32)
+    let cost = env.data().gas_schedule.copy(caller.len());
+    charge_gas(&mut env, cost)?;
+    Ok(write_guest_bytes(
+        &env,
+        out_ptr as u32,
+        caller.as_bytes(),
+        out_max as u32,
+    ))
 }
 
 /// `host_get_self_address(out_ptr, out_max) -> i32` — Write contract's own address to guest memory.
 /// Returns number of bytes written, or -1 on error.
-fn host_get_self_address_fn(env: FunctionEnvMut<HostState>, out_ptr: i32, out_max: i32) -> i32 {
+fn host_get_self_address_fn(
+    mut env: FunctionEnvMut<HostState>,
+    out_ptr: i32,
+    out_max: i32,
+) -> Result<i32, RuntimeError> {
     let addr = {
         let inner = match env.data().inner.lock() {
             Ok(i) => i,
-            Err(_) => return -1,
+            Err(_) => return Ok(-1),
         };
         inner.self_address.clone()
     };
-    write_guest_bytes(&env, out_ptr as u32, addr.as_bytes(), out_max as u32)
+    let cost = env.data().gas_schedule.copy(addr.len());
+    charge_gas(&mut env, cost)?;
+    Ok(write_guest_bytes(
+        &env,
+        out_ptr as u32,
+        addr.as_bytes(),
+        out_max as u32,
+    ))
 }
 
 /// `host_get_balance_lo() -> i64` — Lower 64 bits of the contract's CIL balance.
-fn host_get_balance_lo_fn(env: FunctionEnvMut<HostState>) -> i64 {
+fn host_get_balance_lo_fn(mut env: FunctionEnvMut<HostState>) -> Result<i64, RuntimeError> {
+    let cost = env.data().gas_schedule.copy(0);
+    charge_gas(&mut env, cost)?;
     let inner = match env.data().inner.lock() {
         Ok(i) => i,
-        Err(_) => return 0,
+        Err(_) => return Ok(0),
     };
-    (inner.balance & 0xFFFF_FFFF_FFFF_FFFF) as i64
+    Ok((inner.balance & 0xFFFF_FFFF_FFFF_FFFF) as i64)
 }
 
 /// `host_get_balance_hi() -> i64` — Upper 64 bits of the contract's CIL balance.
-fn host_get_balance_hi_fn(env: FunctionEnvMut<HostState>) -> i64 {
+fn host_get_balance_hi_fn(mut env: FunctionEnvMut<HostState>) -> Result<i64, RuntimeError> {
+    let cost = env.data().gas_schedule.copy(0);
+    charge_gas(&mut env, cost)?;
     let inner = match env.data().inner.lock() {
         Ok(i) => i,
-        Err(_) => return 0,
+        Err(_) => return Ok(0),
     };
-    (inner.balance >> 64) as i64
+    Ok((inner.balance >> 64) as i64)
+}
+
+/// `host_get_call_value_lo() -> i64` — Lower 64 bits of the CIL sent with the call.
+fn host_get_call_value_lo_fn(mut env: FunctionEnvMut<HostState>) -> Result<i64, RuntimeError> {
+    let cost = env.data().gas_schedule.copy(0);
+    charge_gas(&mut env, cost)?;
+    let inner = match env.data().inner.lock() {
+        Ok(i) => i,
+        Err(_) => return Ok(0),
+    };
+    Ok((inner.call_value & 0xFFFF_FFFF_FFFF_FFFF) as i64)
+}
+
+/// `host_get_call_value_hi() -> i64` — Upper 64 bits of the CIL sent with the call.
+fn host_get_call_value_hi_fn(mut env: FunctionEnvMut<HostState>) -> Result<i64, RuntimeError> {
+    let cost = env.data().gas_schedule.copy(0);
+    charge_gas(&mut env, cost)?;
+    let inner = match env.data().inner.lock() {
+        Ok(i) => i,
+        Err(_) => return Ok(0),
+    };
+    Ok((inner.call_value >> 64) as i64)
 }
 
 /// `host_get_timestamp() -> i64` — Block timestamp in seconds since UNIX epoch.
-fn host_get_timestamp_fn(env: FunctionEnvMut<HostState>) -> i64 {
+fn host_get_timestamp_fn(mut env: FunctionEnvMut<HostState>) -> Result<i64, RuntimeError> {
+    let cost = env.data().gas_schedule.copy(0);
+    charge_gas(&mut env, cost)?;
     let inner = match env.data().inner.lock() {
         Ok(i) => i,
-        Err(_) => return 0,
+        Err(_) => return Ok(0),
     };
-    inner.timestamp as i64
+    Ok(inner.timestamp as i64)
 }
 
 /// `host_get_arg_count() -> i32` — Number of string arguments passed to this call.
-fn host_get_arg_count_fn(env: FunctionEnvMut<HostState>) -> i32 {
+fn host_get_arg_count_fn(mut env: FunctionEnvMut<HostState>) -> Result<i32, RuntimeError> {
+    let cost = env.data().gas_schedule.copy(0);
+    charge_gas(&mut env, cost)?;
     let inner = match env.data().inner.lock() {
         Ok(i) => i,
-        Err(_) => return 0,
+        Err(_) => return Ok(0),
     };
-    inner.args.len() as i32
+    Ok(inner.args.len() as i32)
 }
 
 /// `host_get_arg(idx, out_ptr, out_max) -> i32` — Get argument by index.
 /// Returns byte length of the argument, or -1 if index is out of bounds.
-fn host_get_arg_fn(env: FunctionEnvMut<HostState>, idx: i32, out_ptr: i32, out_max: i32) -> i32 {
-    let arg_data = {
-        let inner = match env.data().inner.lock() {
-            Ok(i) => i,
-            Err(_) => return -1,
-        };
-        match inner.args.get(idx as usize) {
-            Some(a) => a.clone(),
-            None => return -1,
-        }
-    };
-    write_guest_bytes(&env, out_ptr as u32, arg_data.as_bytes(), out_max as u32)
+fn host_get_arg_fn(
+    mut env: FunctionEnvMut<HostState>,
+    idx: i32,
+    out_ptr: i32,
+    out_max: i32,
+) -> Result<i32, RuntimeError> {
+    let arg_data = env
+        .data()
+        .inner
+        .lock()
+        .ok()
+        .and_then(|inner| inner.args.get(idx as usize).cloned());
+    let cost = env
+        .data()
+        .gas_schedule
+        .copy(arg_data.as_ref().map_or(0, String::len));
+    charge_gas(&mut env, cost)?;
+    Ok(match arg_data {
+        Some(a) => write_guest_bytes(&env, out_ptr as u32, a.as_bytes(), out_max as u32),
+        None => -1,
+    })
 }
 
 /// `host_set_return(ptr, len)` — Set the contract's return data.
 /// Called by the contract to return structured data (e.g., JSON response).
-fn host_set_return_fn(env: FunctionEnvMut<HostState>, ptr: i32, len: i32) {
+fn host_set_return_fn(
+    mut env: FunctionEnvMut<HostState>,
+    ptr: i32,
+    len: i32,
+) -> Result<(), RuntimeError> {
     let len = (len as u32).min(MAX_RETURN_DATA_SIZE);
+    let cost = env.data().gas_schedule.copy(len as usize);
+    charge_gas(&mut env, cost)?;
     if let Some(data) = read_guest_bytes(&env, ptr as u32, len) {
         if let Ok(mut inner) = env.data().inner.lock() {
             inner.return_data = data;
         }
     }
+    Ok(())
 }
 
 /// `host_blake3(data_ptr, data_len, out_ptr) -> i32`
 /// Compute blake3 hash of input data, write 32 bytes to `out_ptr`.
 /// Returns 32 on success, -1 on error.
 fn host_blake3_fn(
-    env: FunctionEnvMut<HostState>,
+    mut env: FunctionEnvMut<HostState>,
     data_ptr: i32,
     data_len: i32,
     out_ptr: i32,
-) -> i32 {
+) -> Result<i32, RuntimeError> {
     let data_len = (data_len as u32).min(MAX_STATE_VALUE_SIZE);
+    let cost = env.data().gas_schedule.hash(data_len as usize);
+    charge_gas(&mut env, cost)?;
     let data = match read_guest_bytes(&env, data_ptr as u32, data_len) {
         Some(d) => d,
-        None => return -1,
+        None => return Ok(-1),
     };
     let hash = blake3::hash(&data);
-    write_guest_bytes(&env, out_ptr as u32, hash.as_bytes(), 32)
+    Ok(write_guest_bytes(&env, out_ptr as u32, hash.as_bytes(), 32))
 }
 
 // ─────────────────────────────────────────────────────────────────
 // Import object construction
 // ─────────────────────────────────────────────────────────────────
 
+const I32: Type = Type::I32;
+const I64: Type = Type::I64;
+
+/// Host functions as (name, params, results), in `create_host_imports` order.
+/// Part of the compiled-module cache key: changing it invalidates cached artifacts.
+pub const HOST_ABI: &[(&str, &[Type], &[Type])] = &[
+    ("host_log", &[I32, I32], &[]),
+    ("host_abort", &[I32, I32], &[]),
+    ("host_set_state", &[I32, I32, I32, I32], &[]),
+    ("host_get_state", &[I32, I32, I32, I32], &[I32]),
+    ("host_del_state", &[I32, I32], &[]),
+    ("host_emit_event", &[I32, I32, I32, I32], &[]),
+    ("host_transfer", &[I32, I32, I64, I64], &[I32]),
+    ("host_get_caller", &[I32, I32], &[I32]),
+    ("host_get_self_address", &[I32, I32], &[I32]),
+    ("host_get_balance_lo", &[], &[I64]),
+    ("host_get_balance_hi", &[], &[I64]),
+    ("host_get_call_value_lo", &[], &[I64]),
+    ("host_get_call_value_hi", &[], &[I64]),
+    ("host_get_timestamp", &[], &[I64]),
+    ("host_get_arg_count", &[], &[I32]),
+    ("host_get_arg", &[I32, I32, I32], &[I32]),
+    ("host_set_return", &[I32, I32], &[]),
+    ("host_blake3", &[I32, I32, I32], &[I32]),
+    (
+        "host_token_transfer",
+        &[I32, I32, I32, I32, I32, I32, I64, I64],
+        &[I32],
+    ),
+];
+
 /// Create wasmer `Imports` containing all LOS host functions.
 ///
 /// Must be called on the same thread that owns the `Store` (inside the execution thread).
@@ -475,11 +1016,14 @@ pub fn create_host_imports(store: &mut Store, env: &FunctionEnv<HostState>) -> I
     let f_get_self = Function::new_typed_with_env(store, env, host_get_self_address_fn);
     let f_bal_lo = Function::new_typed_with_env(store, env, host_get_balance_lo_fn);
     let f_bal_hi = Function::new_typed_with_env(store, env, host_get_balance_hi_fn);
+    let f_value_lo = Function::new_typed_with_env(store, env, host_get_call_value_lo_fn);
+    let f_value_hi = Function::new_typed_with_env(store, env, host_get_call_value_hi_fn);
     let f_timestamp = Function::new_typed_with_env(store, env, host_get_timestamp_fn);
     let f_arg_count = Function::new_typed_with_env(store, env, host_get_arg_count_fn);
     let f_get_arg = Function::new_typed_with_env(store, env, host_get_arg_fn);
     let f_set_return = Function::new_typed_with_env(store, env, host_set_return_fn);
     let f_blake3 = Function::new_typed_with_env(store, env, host_blake3_fn);
+    let f_token_transfer = Function::new_typed_with_env(store, env, host_token_transfer_fn);
 
     imports! {
         "env" => {
@@ -494,11 +1038,14 @@ pub fn create_host_imports(store: &mut Store, env: &FunctionEnv<HostState>) -> I
             "host_get_self_address" => f_get_self,
             "host_get_balance_lo" => f_bal_lo,
             "host_get_balance_hi" => f_bal_hi,
+            "host_get_call_value_lo" => f_value_lo,
+            "host_get_call_value_hi" => f_value_hi,
             "host_get_timestamp" => f_timestamp,
             "host_get_arg_count" => f_arg_count,
             "host_get_arg" => f_get_arg,
             "host_set_return" => f_set_return,
             "host_blake3" => f_blake3,
+            "host_token_transfer" => f_token_transfer,
         }
     }
 }
@@ -527,12 +1074,179 @@ mod tests {
             logs: Vec::new(),
             aborted: false,
             abort_message: String::new(),
+            storage_bytes: 0,
+            storage_deposit: 0,
+            contracts: None,
+            token_changes: BTreeMap::new(),
+            token_transfers: 0,
+            call_value: 0,
+            call_allowances: BTreeMap::new(),
         };
         assert_eq!(data.args.len(), 2);
         assert_eq!(data.balance, 1_000_000);
         assert!(!data.aborted);
     }
 
+    #[test]
+    fn test_host_abi_matches_imports() {
+        let mut store = Store::default();
+        let env = FunctionEnv::new(
+            &mut store,
+            HostState {
+                memory: None,
+                metering: None,
+                gas_schedule: GasSchedule::latest(),
+                inner: Arc::new(Mutex::new(storage_host(0, 0))),
+            },
+        );
+        let imports = create_host_imports(&mut store, &env);
+        assert_eq!(imports.iter().count(), HOST_ABI.len());
+        for (name, params, results) in HOST_ABI {
+            let function = imports
+                .get_export("env", name)
+                .unwrap_or_else(|| panic!("{} missing from the imports", name));
+            assert_eq!(
+                function.ty(&store),
+                wasmer::ExternType::Function(wasmer::FunctionType::new(*params, *results)),
+                "{}",
+                name
+            );
+        }
+    }
+
+    fn storage_host(balance: u128, storage_bytes: u64) -> HostData {
+        HostData {
+            state: BTreeMap::new(),
+            dirty_keys: HashSet::new(),
+            events: Vec::new(),
+            transfers: Vec::new(),
+            caller: String::new(),
+            self_address: String::new(),
+            balance,
+            timestamp: 0,
+            args: Vec::new(),
+            return_data: Vec::new(),
+            logs: Vec::new(),
+            aborted: false,
+            abort_message: String::new(),
+            storage_bytes,
+            storage_deposit: 0,
+            contracts: None,
+            token_changes: BTreeMap::new(),
+            token_transfers: 0,
+            call_value: 0,
+            call_allowances: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_storage_within_free_allowance_is_free() {
+        let mut data = storage_host(0, 0);
+        assert!(data.charge_storage(0, FREE_STORAGE_BYTES));
+        assert_eq!(data.storage_bytes, FREE_STORAGE_BYTES);
+        assert_eq!(data.storage_deposit, 0);
+        assert!(!data.aborted);
+    }
+
+    #[test]
+    fn test_storage_deposit_locks_and_refunds_balance() {
+        let mut data = storage_host(1_000 * STORAGE_DEPOSIT_PER_BYTE, FREE_STORAGE_BYTES);
+        assert!(data.charge_storage(0, 100));
+        assert_eq!(data.storage_deposit, 100 * STORAGE_DEPOSIT_PER_BYTE);
+        assert_eq!(data.balance, 900 * STORAGE_DEPOSIT_PER_BYTE);
+
+        // Overwriting with a smaller value refunds the difference
+        assert!(data.charge_storage(100, 40));
+        assert_eq!(data.storage_deposit, 40 * STORAGE_DEPOSIT_PER_BYTE);
+        assert_eq!(data.balance, 960 * STORAGE_DEPOSIT_PER_BYTE);
+    }
+
+    #[test]
+    fn test_storage_refund_capped_at_deposit_held() {
+        // State held from before deposits existed: shrinking refunds nothing
+        let mut data = storage_host(0, FREE_STORAGE_BYTES + 500);
+        assert!(data.charge_storage(500, 0));
+        assert_eq!(data.balance, 0);
+        assert_eq!(data.storage_deposit, 0);
+    }
+
+    #[test]
+    fn test_storage_deposit_and_quota_abort() {
+        let mut data = storage_host(STORAGE_DEPOSIT_PER_BYTE - 1, FREE_STORAGE_BYTES);
+        assert!(!data.charge_storage(0, 1));
+        assert!(data.aborted);
+        assert!(data.abort_message.contains("storage deposit"));
+        assert_eq!(data.storage_bytes, FREE_STORAGE_BYTES);
+
+        let mut data = storage_host(u128::MAX, MAX_CONTRACT_STORAGE_BYTES);
+        assert!(!data.charge_storage(0, 1));
+        assert!(data.abort_message.contains("quota"));
+        // Shrinking is allowed even at the quota
+        let mut data = storage_host(0, MAX_CONTRACT_STORAGE_BYTES + 10);
+        assert!(data.charge_storage(10, 5));
+    }
+
+    /// Host for contract "LOSConDex" that can see a USP-01 token "LOSConTok"
+    /// where alice holds 100 and has approved the DEX for 40.
+    fn token_host() -> HostData {
+        let mut token_state = BTreeMap::new();
+        token_state.insert("usp01:init".to_string(), "1".to_string());
+        token_state.insert("bal:alice".to_string(), "100".to_string());
+        token_state.insert("allow:alice:LOSConDex".to_string(), "40".to_string());
+        let token = Contract {
+            address: "LOSConTok".to_string(),
+            bytecode: Vec::new(),
+            code_hash: String::new(),
+            state: token_state,
+            balance: 0,
+            storage_deposit: 0,
+            owner: "alice".to_string(),
+            created_at_block: 0,
+            abi: None,
+        };
+        let mut contracts = BTreeMap::new();
+        contracts.insert(token.address.clone(), token);
+        let mut data = storage_host(0, 0);
+        data.self_address = "LOSConDex".to_string();
+        data.contracts = Some(Arc::new(Mutex::new(contracts)));
+        data
+    }
+
+    #[test]
+    fn test_token_transfer_spends_allowance() {
+        let mut data = token_host();
+        assert_eq!(
+            data.transfer_tokens("LOSConTok", "alice", "LOSConDex", 30),
+            0
+        );
+        let changes = &data.token_changes["LOSConTok"].state;
+        assert_eq!(changes["bal:alice"], "70");
+        assert_eq!(changes["bal:LOSConDex"], "30");
+        assert_eq!(changes["allow:alice:LOSConDex"], "10");
+        assert_eq!(data.events[0].contract, "LOSConTok");
+        assert_eq!(data.events[0].event_type, "USP01:Transfer");
+
+        // The remaining allowance is 10, the contract's own tokens need none
+        assert_eq!(
+            data.transfer_tokens("LOSConTok", "alice", "LOSConDex", 11),
+            2
+        );
+        assert_eq!(data.transfer_tokens("LOSConTok", "LOSConDex", "bob", 30), 0);
+        assert_eq!(data.token_amount("LOSConTok", "bal:bob"), 30);
+        assert_eq!(data.token_amount("LOSConTok", "bal:LOSConDex"), 0);
+    }
+
+    #[test]
+    fn test_token_transfer_rejects_bad_requests() {
+        let mut data = token_host();
+        assert_eq!(data.transfer_tokens("LOSConTok", "bob", "carol", 1), 1);
+        assert_eq!(data.transfer_tokens("LOSConNone", "alice", "bob", 1), 3);
+        assert_eq!(data.transfer_tokens("LOSConDex", "alice", "bob", 1), 4);
+        assert_eq!(data.transfer_tokens("LOSConTok", "alice", "alice", 1), 4);
+        assert!(data.token_changes["LOSConTok"].state.is_empty());
+        assert!(data.events.is_empty());
+    }
+
     #[test]
     fn test_host_state_is_send() {
         // Compile-time check: HostState must be Send + 'static for wasmer FunctionEnv
@@ -547,8 +1261,12 @@ mod tests {
             return_data: Vec::new(),
             gas_used: 100,
             state_changes: BTreeMap::new(),
+            deleted_keys: Vec::new(),
+            storage_bytes: 0,
+            storage_deposit: 0,
             events: Vec::new(),
             transfers: Vec::new(),
+            token_changes: BTreeMap::new(),
             logs: Vec::new(),
             aborted: false,
             abort_message: String::new(),
@@ -6,23 +6,18 @@
 // - Gas metering via Metering middleware
 // - Sandboxed execution with resource limits
 // - Host functions for state access, transfers, and events
+// - Compiled module cache keyed by code hash (see module_cache.rs)
 // ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
 
+use executor::Backpressure;
+use los_core::abi::{self, ContractAbi};
+use module_cache::{CompiledModule, ModuleCache, ModuleCacheStats};
 use serde::{Deserialize, Serialize};
-use std::collections::BTreeMap;
-use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
+use std::collections::{BTreeMap, BTreeSet};
 use std::sync::{Arc, Mutex};
-use wasmer::{imports, CompilerConfig, FunctionEnv, Instance, Module, Store, Value};
-use wasmer_compiler_cranelift::Cranelift;
-use wasmer_middlewares::metering::get_remaining_points;
+use wasmer::{imports, FunctionEnv, Instance, Store, Value};
 use wasmer_middlewares::metering::MeteringPoints;
-use wasmer_middlewares::Metering;
-
-/// Global counter for leaked WASM timeout threads.
-/// Once MAX_LEAKED_THREADS is reached, new WASM executions are rejected
-/// to prevent unbounded resource consumption from pathological contracts.
-static LEAKED_THREADS: AtomicUsize = AtomicUsize::new(0);
-const MAX_LEAKED_THREADS: usize = 16;
+use wasmer_middlewares::metering::{get_remaining_points, set_remaining_points};
 
 // Provide __rust_probestack stub for wasmer-vm 4.x compatibility with
 // Rust 1.85+ where this symbol was removed from compiler_builtins.
@@ -44,6 +39,12 @@ pub mod usp01;
 pub mod token_registry;
 // DEX Registry: node-level DEX pool discovery and query helpers
 pub mod dex_registry;
+// Compiled module cache keyed by contract code hash
+pub mod module_cache;
+// Bounded worker pool that runs contract calls
+pub mod executor;
+// Versioned host function gas costs, activated by block height
+pub mod gas_schedule;
 
 /// Unauthority Virtual Machine (UVM)
 /// Executes WebAssembly smart contracts with permissionless deployment
@@ -52,6 +53,13 @@ pub mod dex_registry;
 const MAX_BYTECODE_SIZE: usize = 1_048_576;
 /// Maximum WASM execution time before timeout (5 seconds)
 const MAX_EXECUTION_SECS: u64 = 5;
+/// Hard cap on gas per call, whatever the chain parameters say
+/// (`ChainParams::max_gas_per_call` can only lower it). Metering aborts any
+/// call at this point (well under a second for a tight loop), which bounds how
+/// long a worker can be held even by an infinite loop.
+pub const MAX_GAS_PER_CALL: u64 = 100_000_000;
+/// Gas cap for read-only queries (they are free, so the cap is tighter)
+pub const MAX_QUERY_GAS: u64 = 10_000_000;
 /// Gas cost per kilobyte of bytecode (compilation cost)
 const GAS_PER_KB_BYTECODE: u64 = 100;
 
@@ -65,6 +73,22 @@ pub struct Contract {
     pub balance: u128,
     pub created_at_block: u64,
     pub owner: String,
+    /// ABI metadata (embedded `los_abi` section or attached at deploy)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub abi: Option<ContractAbi>,
+    /// CIL locked out of `balance` for state above the free allowance
+    #[serde(default)]
+    pub storage_deposit: u128,
+}
+
+impl Contract {
+    /// State held, as counted for storage accounting (keys + values)
+    pub fn storage_bytes(&self) -> u64 {
+        self.state
+            .iter()
+            .map(|(k, v)| host::entry_size(k, v.len()))
+            .sum()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -81,8 +105,18 @@ pub struct ContractCall {
     /// All validators MUST use the SAME timestamp (from the block being processed)
     /// to ensure identical WASM execution results across the network.
     /// If 0, falls back to SystemTime::now() (backward-compatible, but non-deterministic).
+    /// Also selects the host function gas schedule (see `gas_schedule`);
+    /// 0 = genesis schedule (legacy calls).
     #[serde(default)]
     pub block_timestamp: u64,
+    /// CIL sent with the call (the block's amount, already credited to the
+    /// contract). Read by the contract as `call_value()`.
+    #[serde(default)]
+    pub value_cil: u128,
+    /// USP-01 amounts the caller lets this contract pull from it during the
+    /// call, from the signed block link (see `los_core::parse_call_link`)
+    #[serde(default)]
+    pub token_allowances: BTreeMap<String, u128>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -96,6 +130,88 @@ pub struct ContractResult {
     pub events: Vec<ContractEvent>,
 }
 
+/// Result of a read-only contract query. State changes and transfers the
+/// function attempted are reported but never applied.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContractQueryResult {
+    pub success: bool,
+    /// Return data as text (or the return code if the contract set none)
+    pub output: String,
+    pub return_code: i32,
+    /// Raw return data, hex encoded
+    pub return_data: String,
+    pub gas_used: u64,
+    pub logs: Vec<String>,
+    pub events: Vec<ContractEvent>,
+    /// Writes the call would have made (discarded)
+    pub state_changes: BTreeMap<String, String>,
+}
+
+/// Before/after value of one state key touched by a simulated call
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateDiffEntry {
+    pub before: Option<String>,
+    pub after: String,
+}
+
+/// CIL transfer requested by a contract (via `host_transfer`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContractTransfer {
+    pub recipient: String,
+    pub amount: u128,
+}
+
+/// Result of dry-running a ContractCall block
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CallSimulation {
+    pub success: bool,
+    pub output: String,
+    pub gas_used: u64,
+    /// `gas_used` plus headroom for state drift before inclusion
+    pub recommended_gas_limit: u64,
+    pub state_diff: BTreeMap<String, StateDiffEntry>,
+    pub events: Vec<ContractEvent>,
+    pub transfers: Vec<ContractTransfer>,
+    pub logs: Vec<String>,
+}
+
+/// Result of dry-running a ContractDeploy block
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeploySimulation {
+    /// Address `deploy_contract` would assign for the given block number
+    pub contract_address: String,
+    pub code_hash: String,
+    pub bytecode_size: usize,
+    /// Compilation gas charged on every call to this contract
+    pub compile_gas_per_call: u64,
+    /// Exported functions callable via /call-contract
+    pub exports: Vec<String>,
+    /// ABI that would be stored with the contract
+    pub abi: Option<ContractAbi>,
+}
+
+/// Headroom added to simulated gas when recommending a gas limit (percent)
+pub const GAS_ESTIMATE_HEADROOM_PERCENT: u64 = 20;
+
+/// Gas limit to submit for a call that used `gas_used` in simulation
+pub fn recommended_gas_limit(gas_used: u64) -> u64 {
+    gas_used
+        .saturating_add(gas_used.saturating_mul(GAS_ESTIMATE_HEADROOM_PERCENT) / 100)
+        .min(MAX_GAS_PER_CALL)
+}
+
+/// (success, output) as reported to callers: SDK contracts succeed with return
+/// code 0 and answer through return data; legacy contracts return the value.
+fn exec_outcome(exec: &host::HostExecResult) -> (bool, String) {
+    let success = !exec.sdk_mode || exec.return_code == 0;
+    let output = if exec.return_data.is_empty() {
+        exec.return_code.to_string()
+    } else {
+        String::from_utf8_lossy(&exec.return_data).to_string()
+    };
+    (success, output)
+}
+
 /// Contract event (emitted during execution, stored for indexing)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ContractEvent {
@@ -109,6 +225,115 @@ pub struct ContractEvent {
 pub struct WasmEngine {
     contracts: Arc<Mutex<BTreeMap<String, Contract>>>,
     nonce: Arc<Mutex<BTreeMap<String, u64>>>,
+    /// Compiled modules keyed by code hash (shared with execution threads)
+    module_cache: Arc<Mutex<ModuleCache>>,
+    /// Gas schedule version pinned by governance (`None` = select by height)
+    gas_schedule_pin: Arc<Mutex<Option<u32>>>,
+    /// Per-call gas cap in force on chain (`None` = not activated yet)
+    max_gas_per_call: Arc<Mutex<Option<u64>>>,
+    /// One gate per contract. A call holds the gates of every contract it
+    /// writes, from snapshot to commit, so concurrent calls never overwrite
+    /// each other's state (see `try_hosted_call`).
+    call_gates: Arc<Mutex<BTreeMap<String, Arc<Mutex<()>>>>>,
+    /// Token contracts each contract's calls have moved tokens of, so later
+    /// calls take those gates up front
+    token_deps: Arc<Mutex<BTreeMap<String, BTreeSet<String>>>>,
+}
+
+/// Outcome of a hosted call run under a set of contract gates
+enum GatedCall {
+    Done(Option<ContractResult>),
+    /// The call moved tokens of contracts whose gates were not held.
+    /// Nothing was committed.
+    NeedsGates(BTreeSet<String>),
+}
+
+/// What the caller attached to a call (see `ContractCall`)
+#[derive(Debug, Clone, Default)]
+struct Attached {
+    value_cil: u128,
+    token_allowances: BTreeMap<String, u128>,
+}
+
+impl Attached {
+    fn of(call: &ContractCall) -> Self {
+        Attached {
+            value_cil: call.value_cil,
+            token_allowances: call.token_allowances.clone(),
+        }
+    }
+}
+
+/// Pool and backpressure policy for one WASM call
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Lane {
+    /// Calls carried by blocks: wait for a worker, never dropped
+    Chain,
+    /// Read-only queries: own pool, fail fast with "busy"
+    Query,
+    /// Simulations: own pool, fail fast with "busy"
+    Simulation,
+}
+
+impl Lane {
+    fn submit(self, job: impl FnOnce() + Send + 'static) -> Result<(), String> {
+        match self {
+            Lane::Chain => executor::global().submit(job, Backpressure::Wait),
+            Lane::Query => executor::queries().submit(job, Backpressure::Reject),
+            Lane::Simulation => executor::simulations().submit(job, Backpressure::Reject),
+        }
+    }
+}
+
+/// Wait for a pooled call's result. The wall-clock limit counts from the
+/// moment a worker picks the call up: time spent queued behind other calls
+/// must not fail a call that is part of the chain.
+fn recv_started<T>(
+    rx: &std::sync::mpsc::Receiver<T>,
+    started: &std::sync::atomic::AtomicBool,
+) -> Result<T, std::sync::mpsc::RecvTimeoutError> {
+    let timeout = std::time::Duration::from_secs(MAX_EXECUTION_SECS);
+    loop {
+        let was_started = started.load(std::sync::atomic::Ordering::Relaxed);
+        match rx.recv_timeout(timeout) {
+            Err(std::sync::mpsc::RecvTimeoutError::Timeout) if !was_started => continue,
+            other => return other,
+        }
+    }
+}
+
+/// Fetch the metered module for `bytecode`, compiling it on a cache miss.
+/// Returns `true` when the module is shared (cached) and its metering points
+/// must be reset to `remaining_gas` right after instantiation.
+fn prepare_module(
+    cache: &Mutex<ModuleCache>,
+    bytecode: &[u8],
+    remaining_gas: u64,
+) -> Result<(CompiledModule, bool), String> {
+    // Start functions run inside Instance::new, before points can be reset,
+    // so those modules are compiled with the exact limit every time.
+    if module_cache::has_start_section(bytecode) {
+        return Ok((
+            module_cache::compile_metered(bytecode, remaining_gas)?,
+            false,
+        ));
+    }
+
+    let code_hash = WasmEngine::compute_code_hash(bytecode);
+    if let Some(compiled) = cache
+        .lock()
+        .map_err(|_| "Module cache lock poisoned".to_string())?
+        .get(&code_hash)
+    {
+        return Ok((compiled, true));
+    }
+
+    // Compile without holding the lock so other contracts keep executing
+    let compiled = module_cache::compile_metered(bytecode, 0)?;
+    if let Ok(mut cache) = cache.lock() {
+        cache.insert(&code_hash, compiled.clone());
+    }
+    Ok((compiled, true))
 }
 
 impl WasmEngine {
@@ -117,9 +342,67 @@ impl WasmEngine {
         WasmEngine {
             contracts: Arc::new(Mutex::new(BTreeMap::new())),
             nonce: Arc::new(Mutex::new(BTreeMap::new())),
+            module_cache: Arc::new(Mutex::new(ModuleCache::default())),
+            gas_schedule_pin: Arc::new(Mutex::new(None)),
+            max_gas_per_call: Arc::new(Mutex::new(None)),
+            call_gates: Arc::new(Mutex::new(BTreeMap::new())),
+            token_deps: Arc::new(Mutex::new(BTreeMap::new())),
         }
     }
 
+    /// Apply the on-chain per-call gas cap (`ChainParams::max_gas_per_call`).
+    /// `None` leaves MAX_GAS_PER_CALL as the only cap.
+    pub fn set_max_gas_per_call(&self, cap: Option<u64>) {
+        if let Ok(mut current) = self.max_gas_per_call.lock() {
+            *current = cap;
+        }
+    }
+
+    /// `gas_limit` reduced to MAX_GAS_PER_CALL and to the on-chain cap, if one is set
+    fn capped_gas(&self, gas_limit: u64) -> u64 {
+        let cap = self
+            .max_gas_per_call
+            .lock()
+            .map(|c| *c)
+            .unwrap_or(None)
+            .unwrap_or(MAX_GAS_PER_CALL);
+        gas_limit.min(cap).min(MAX_GAS_PER_CALL)
+    }
+
+    /// Pin host-function charges to gas schedule `version` (set by on-chain
+    /// governance), or return to time-based selection with `None`.
+    /// Unknown versions are ignored by `gas_schedule::schedule_for`.
+    pub fn pin_gas_schedule(&self, version: Option<u32>) {
+        if let Ok(mut pin) = self.gas_schedule_pin.lock() {
+            *pin = version;
+        }
+    }
+
+    /// Create an engine that also persists compiled modules under `dir`,
+    /// so contracts don't need to be recompiled after a restart.
+    pub fn with_module_cache_dir(dir: impl Into<std::path::PathBuf>) -> Result<Self, String> {
+        let cache = ModuleCache::default().with_artifact_dir(dir)?;
+        let engine = Self::new();
+        *engine
+            .module_cache
+            .lock()
+            .map_err(|_| "Module cache lock poisoned".to_string())? = cache;
+        Ok(engine)
+    }
+
+    /// Executor pool counters (shared by every engine in the process)
+    pub fn executor_stats(&self) -> executor::ExecutorStats {
+        executor::global().stats()
+    }
+
+    /// Compiled module cache counters
+    pub fn module_cache_stats(&self) -> ModuleCacheStats {
+        self.module_cache
+            .lock()
+            .map(|c| c.stats())
+            .unwrap_or_default()
+    }
+
     /// Deploy a WASM contract (Permissionless)
     pub fn deploy_contract(
         &self,
@@ -127,6 +410,20 @@ impl WasmEngine {
         bytecode: Vec<u8>,
         initial_state: BTreeMap<String, String>,
         block_number: u64,
+    ) -> Result<String, String> {
+        self.deploy_contract_with_abi(owner, bytecode, initial_state, block_number, None)
+    }
+
+    /// Deploy a WASM contract with optional ABI metadata.
+    ///
+    /// An attached ABI takes precedence over one embedded in the bytecode.
+    pub fn deploy_contract_with_abi(
+        &self,
+        owner: String,
+        bytecode: Vec<u8>,
+        initial_state: BTreeMap<String, String>,
+        block_number: u64,
+        attached_abi: Option<ContractAbi>,
     ) -> Result<String, String> {
         // Validate WASM magic bytes (0x00 0x61 0x73 0x6d)
         if bytecode.len() < 4 || &bytecode[0..4] != b"\0asm" {
@@ -142,6 +439,21 @@ impl WasmEngine {
             ));
         }
 
+        // Resolve ABI before taking a nonce so a bad ABI doesn't consume one
+        let abi = Self::resolve_abi(&bytecode, attached_abi)?;
+
+        let storage_bytes: u64 = initial_state
+            .iter()
+            .map(|(k, v)| host::entry_size(k, v.len()))
+            .sum();
+        if storage_bytes > host::MAX_CONTRACT_STORAGE_BYTES {
+            return Err(format!(
+                "Initial state too large: {} bytes (max {} bytes)",
+                storage_bytes,
+                host::MAX_CONTRACT_STORAGE_BYTES
+            ));
+        }
+
         let mut nonce = self
             .nonce
             .lock()
@@ -163,11 +475,13 @@ impl WasmEngine {
         let contract = Contract {
             address: address.clone(),
             code_hash,
+            abi,
             bytecode,
             state: initial_state,
             balance: 0,
             created_at_block: block_number,
             owner,
+            storage_deposit: 0,
         };
 
         let mut contracts = self
@@ -179,6 +493,20 @@ impl WasmEngine {
         Ok(address)
     }
 
+    /// Attached ABI if given, else the one embedded in the bytecode
+    fn resolve_abi(
+        bytecode: &[u8],
+        attached: Option<ContractAbi>,
+    ) -> Result<Option<ContractAbi>, String> {
+        match attached {
+            Some(abi) => {
+                abi.validate()?;
+                Ok(Some(abi))
+            }
+            None => abi::extract_embedded(bytecode),
+        }
+    }
+
     /// Get contract by address
     pub fn get_contract(&self, address: &str) -> Result<Contract, String> {
         let contracts = self
@@ -197,9 +525,9 @@ impl WasmEngine {
     ///
     /// Gas metering is DETERMINISTIC: every WASM instruction costs exactly 1 gas unit.
     /// This ensures all validators compute identical gas usage for the same contract call,
-    /// which is essential for consensus. The wall-clock timeout (MAX_EXECUTION_SECS) is
-    /// kept as a safety net against pathological cases where metering overhead itself
-    /// could stall the node.
+    /// which is essential for consensus. Gas is capped at MAX_GAS_PER_CALL, so metering
+    /// always ends the call and its executor worker is reused. The wall-clock
+    /// timeout (MAX_EXECUTION_SECS) only stops the caller from waiting on a slow call.
     fn execute_wasm(
         &self,
         bytecode: &[u8],
@@ -207,14 +535,8 @@ impl WasmEngine {
         args: &[i32],
         gas_limit: u64,
     ) -> Result<(i32, u64), String> {
-        // W-07: Reject new WASM execution if too many threads are leaked from timeouts
-        let leaked = LEAKED_THREADS.load(AtomicOrdering::Relaxed);
-        if leaked >= MAX_LEAKED_THREADS {
-            return Err(format!(
-                "WASM execution rejected: {} leaked timeout threads (max {}). Node restart required.",
-                leaked, MAX_LEAKED_THREADS
-            ));
-        }
+        // Metering-based interruption: no call may run past the per-call cap
+        let gas_limit = self.capped_gas(gas_limit);
 
         // 1. Bytecode size limit
         if bytecode.len() > MAX_BYTECODE_SIZE {
@@ -242,11 +564,15 @@ impl WasmEngine {
         let args_owned = args.to_vec();
         let abort_flag = Arc::new(std::sync::atomic::AtomicBool::new(false));
         let abort_clone = Arc::clone(&abort_flag);
+        let started = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let started_clone = Arc::clone(&started);
+        let module_cache = Arc::clone(&self.module_cache);
 
-        // 4. Execute in a separate thread with timeout
+        // 4. Execute on the executor pool with timeout (legacy calls are always chain calls)
         let (result_tx, result_rx) = std::sync::mpsc::channel();
 
-        let _handle = std::thread::spawn(move || {
+        Lane::Chain.submit(move || {
+            started_clone.store(true, std::sync::atomic::Ordering::Relaxed);
             // Check abort flag before each expensive phase
             if abort_clone.load(std::sync::atomic::Ordering::Relaxed) {
                 return;
@@ -254,20 +580,16 @@ impl WasmEngine {
 
             // DETERMINISTIC GAS METERING: Each WASM instruction costs 1 gas unit.
             // This is injected at compilation time by wasmer-middlewares::Metering.
-            let cost_fn = |_operator: &wasmer::wasmparser::Operator| -> u64 { 1 };
-            let metering = Arc::new(Metering::new(remaining_gas, cost_fn));
-
-            let mut compiler = Cranelift::default();
-            compiler.push_middleware(metering);
-            let mut store = Store::new(compiler);
-
-            let module = match Module::new(&store, &bytecode_owned) {
-                Ok(m) => m,
-                Err(e) => {
-                    let _ = result_tx.send(Err(format!("Failed to compile WASM: {}", e)));
-                    return;
-                }
-            };
+            let (compiled, cached) =
+                match prepare_module(&module_cache, &bytecode_owned, remaining_gas) {
+                    Ok(c) => c,
+                    Err(e) => {
+                        let _ = result_tx.send(Err(e));
+                        return;
+                    }
+                };
+            let mut store = Store::new(compiled.engine);
+            let module = compiled.module;
 
             if abort_clone.load(std::sync::atomic::Ordering::Relaxed) {
                 return;
@@ -281,6 +603,9 @@ impl WasmEngine {
                     return;
                 }
             };
+            if cached {
+                set_remaining_points(&mut store, &instance, remaining_gas);
+            }
 
             let func = match instance.exports.get_function(&function_owned) {
                 Ok(f) => f,
@@ -341,11 +666,10 @@ impl WasmEngine {
                     }
                 }
             }
-        });
+        })?;
 
         // 5. Wait with timeout (safety net — deterministic metering should terminate first)
-        let timeout = std::time::Duration::from_secs(MAX_EXECUTION_SECS);
-        match result_rx.recv_timeout(timeout) {
+        match recv_started(&result_rx, &started) {
             Ok(Ok((value, exec_gas))) => {
                 let total_gas = compile_gas + exec_gas;
                 if total_gas > gas_limit {
@@ -358,13 +682,10 @@ impl WasmEngine {
             }
             Ok(Err(e)) => Err(e),
             Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {
-                // SECURITY: Set abort flag so the thread exits at its next checkpoint
+                // SECURITY: Set abort flag so the worker skips its remaining phases.
+                // A call already inside func.call() is stopped by metering at
+                // MAX_GAS_PER_CALL, so the worker always returns to the pool.
                 abort_flag.store(true, std::sync::atomic::Ordering::Relaxed);
-                // W-07: Track leaked thread count
-                LEAKED_THREADS.fetch_add(1, AtomicOrdering::Relaxed);
-                // FIX C11-H5: Do NOT join — if WASM entered an infinite loop inside
-                // func.call(), the thread is permanently stuck and join() would block
-                // the calling thread forever. Let the thread leak (bounded damage).
                 Err(format!(
                     "WASM execution timeout: exceeded {} second limit",
                     MAX_EXECUTION_SECS
@@ -395,19 +716,50 @@ impl WasmEngine {
         contract_addr: &str,
         contract_state: &BTreeMap<String, String>,
         balance: u128,
+        storage_deposit: u128,
+        timestamp: u64,
+    ) -> Result<host::HostExecResult, String> {
+        self.execute_hosted_on(
+            Lane::Chain,
+            bytecode,
+            function,
+            args,
+            gas_limit,
+            caller,
+            contract_addr,
+            contract_state,
+            balance,
+            storage_deposit,
+            timestamp,
+            timestamp,
+            &Attached::default(),
+        )
+    }
+
+    /// `execute_wasm_hosted` on the given lane. `schedule_time` is the call
+    /// block's signed timestamp and selects the gas schedule.
+    #[allow(clippy::too_many_arguments)]
+    fn execute_hosted_on(
+        &self,
+        lane: Lane,
+        bytecode: &[u8],
+        function: &str,
+        args: &[String],
+        gas_limit: u64,
+        caller: &str,
+        contract_addr: &str,
+        contract_state: &BTreeMap<String, String>,
+        balance: u128,
+        storage_deposit: u128,
         timestamp: u64,
+        schedule_time: u64,
+        attached: &Attached,
     ) -> Result<host::HostExecResult, String> {
         use host::{HostData, HostExecResult, HostState};
         use std::collections::HashSet;
 
         // Reuse the same safety checks as execute_wasm
-        let leaked = LEAKED_THREADS.load(AtomicOrdering::Relaxed);
-        if leaked >= MAX_LEAKED_THREADS {
-            return Err(format!(
-                "WASM execution rejected: {} leaked timeout threads (max {}). Node restart required.",
-                leaked, MAX_LEAKED_THREADS
-            ));
-        }
+        let gas_limit = self.capped_gas(gas_limit);
         if bytecode.len() > MAX_BYTECODE_SIZE {
             return Err(format!(
                 "WASM bytecode too large: {} bytes (max {} bytes)",
@@ -423,12 +775,18 @@ impl WasmEngine {
             ));
         }
         let remaining_gas = gas_limit - compile_gas;
+        let pinned = self.gas_schedule_pin.lock().map(|p| *p).unwrap_or(None);
+        let schedule = gas_schedule::schedule_for(schedule_time, pinned);
 
         // Convert contract state (String→String) to byte state (String→Vec<u8>)
         let state_bytes: BTreeMap<String, Vec<u8>> = contract_state
             .iter()
             .map(|(k, v)| (k.clone(), v.as_bytes().to_vec()))
             .collect();
+        let storage_bytes = state_bytes
+            .iter()
+            .map(|(k, v)| host::entry_size(k, v.len()))
+            .sum();
 
         // Shared host data (accessed by host functions inside the WASM thread,
         // then read back by the caller after execution completes).
@@ -446,6 +804,13 @@ impl WasmEngine {
             logs: Vec::new(),
             aborted: false,
             abort_message: String::new(),
+            storage_bytes,
+            storage_deposit,
+            contracts: Some(Arc::clone(&self.contracts)),
+            token_changes: BTreeMap::new(),
+            token_transfers: 0,
+            call_value: attached.value_cil,
+            call_allowances: attached.token_allowances.clone(),
         }));
         let host_data_thread = Arc::clone(&host_data);
 
@@ -454,29 +819,29 @@ impl WasmEngine {
         let args_owned = args.to_vec();
         let abort_flag = Arc::new(std::sync::atomic::AtomicBool::new(false));
         let abort_clone = Arc::clone(&abort_flag);
+        let started = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let started_clone = Arc::clone(&started);
+        let module_cache = Arc::clone(&self.module_cache);
 
         let (result_tx, result_rx) = std::sync::mpsc::channel::<Result<(i32, u64, bool), String>>();
 
-        let _handle = std::thread::spawn(move || {
+        lane.submit(move || {
+            started_clone.store(true, std::sync::atomic::Ordering::Relaxed);
             if abort_clone.load(std::sync::atomic::Ordering::Relaxed) {
                 return;
             }
 
             // Deterministic gas metering: 1 WASM instruction = 1 gas unit
-            let cost_fn = |_operator: &wasmer::wasmparser::Operator| -> u64 { 1 };
-            let metering = Arc::new(wasmer_middlewares::Metering::new(remaining_gas, cost_fn));
-
-            let mut compiler = Cranelift::default();
-            compiler.push_middleware(metering);
-            let mut store = Store::new(compiler);
-
-            let module = match Module::new(&store, &bytecode_owned) {
-                Ok(m) => m,
-                Err(e) => {
-                    let _ = result_tx.send(Err(format!("Failed to compile WASM: {}", e)));
-                    return;
-                }
-            };
+            let (compiled, cached) =
+                match prepare_module(&module_cache, &bytecode_owned, remaining_gas) {
+                    Ok(c) => c,
+                    Err(e) => {
+                        let _ = result_tx.send(Err(e));
+                        return;
+                    }
+                };
+            let mut store = Store::new(compiled.engine);
+            let module = compiled.module;
 
             if abort_clone.load(std::sync::atomic::Ordering::Relaxed) {
                 return;
@@ -485,11 +850,13 @@ impl WasmEngine {
             // Create FunctionEnv with host state (memory set after instantiation)
             let host_state = HostState {
                 memory: None,
+                metering: None,
+                gas_schedule: schedule,
                 inner: host_data_thread,
             };
             let env = FunctionEnv::new(&mut store, host_state);
 
-            // Create imports with all 16 host functions
+            // Create imports with all 17 host functions
             let import_object = host::create_host_imports(&mut store, &env);
 
             let instance = match Instance::new(&mut store, &module, &import_object) {
@@ -507,10 +874,16 @@ impl WasmEngine {
                 }
             };
 
+            if cached {
+                set_remaining_points(&mut store, &instance, remaining_gas);
+            }
+
             // Set memory reference in env (so host functions can read/write guest memory)
             if let Ok(memory) = instance.exports.get_memory("memory") {
                 env.as_mut(&mut store).memory = Some(memory.clone());
             }
+            // Host functions charge their schedule costs against the same points
+            env.as_mut(&mut store).metering = host::MeteringGlobals::of(&instance);
 
             let func = match instance.exports.get_function(&function_owned) {
                 Ok(f) => f,
@@ -591,11 +964,10 @@ impl WasmEngine {
                     }
                 }
             }
-        });
+        })?;
 
         // Wait with timeout (safety net)
-        let timeout = std::time::Duration::from_secs(MAX_EXECUTION_SECS);
-        match result_rx.recv_timeout(timeout) {
+        match recv_started(&result_rx, &started) {
             Ok(Ok((return_code, exec_gas, is_sdk_mode))) => {
                 let total_gas = compile_gas + exec_gas;
                 if total_gas > gas_limit {
@@ -620,14 +992,25 @@ impl WasmEngine {
                     .iter()
                     .filter_map(|k| data.state.get(k).map(|v| (k.clone(), v.clone())))
                     .collect();
+                let mut deleted_keys: Vec<String> = data
+                    .dirty_keys
+                    .iter()
+                    .filter(|k| !data.state.contains_key(*k))
+                    .cloned()
+                    .collect();
+                deleted_keys.sort();
 
                 Ok(HostExecResult {
                     return_code,
                     return_data: data.return_data.clone(),
                     gas_used: total_gas,
                     state_changes,
+                    deleted_keys,
+                    storage_bytes: data.storage_bytes,
+                    storage_deposit: data.storage_deposit,
                     events: data.events.clone(),
                     transfers: data.transfers.clone(),
+                    token_changes: data.token_changes.clone(),
                     logs: data.logs.clone(),
                     aborted: false,
                     abort_message: String::new(),
@@ -645,7 +1028,6 @@ impl WasmEngine {
             }
             Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {
                 abort_flag.store(true, std::sync::atomic::Ordering::Relaxed);
-                LEAKED_THREADS.fetch_add(1, AtomicOrdering::Relaxed);
                 Err(format!(
                     "WASM execution timeout: exceeded {} second limit",
                     MAX_EXECUTION_SECS
@@ -655,10 +1037,62 @@ impl WasmEngine {
         }
     }
 
+    /// Gate of each contract in `addresses`, in address order
+    fn gates_for(&self, addresses: &BTreeSet<String>) -> Result<Vec<Arc<Mutex<()>>>, String> {
+        let mut gates = self
+            .call_gates
+            .lock()
+            .map_err(|_| "Failed to lock contract gates".to_string())?;
+        Ok(addresses
+            .iter()
+            .map(|a| Arc::clone(gates.entry(a.clone()).or_default()))
+            .collect())
+    }
+
     /// Try hosted WASM execution for a contract call.
     /// Returns `Ok(Some(result))` on success, `Ok(None)` if fallback is needed,
     /// or `Err(e)` for fatal errors that should propagate immediately.
+    ///
+    /// The call commits absolute values computed from its snapshot, so it runs
+    /// holding the gates of the contract and of every token it moves. Gates
+    /// are always taken in address order (no deadlock). A call that moves a
+    /// token whose gate it didn't hold is discarded and re-run with it.
     fn try_hosted_call(&self, call: &ContractCall) -> Result<Option<ContractResult>, String> {
+        let mut gated: BTreeSet<String> = self
+            .token_deps
+            .lock()
+            .map_err(|_| "Failed to lock token dependencies".to_string())?
+            .get(&call.contract)
+            .cloned()
+            .unwrap_or_default();
+        gated.insert(call.contract.clone());
+        loop {
+            let gates = self.gates_for(&gated)?;
+            let _held = gates
+                .iter()
+                .map(|g| g.lock())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|_| "Failed to lock contract gate".to_string())?;
+            match self.try_hosted_call_gated(call, &gated)? {
+                GatedCall::Done(result) => return Ok(result),
+                GatedCall::NeedsGates(tokens) => {
+                    if let Ok(mut deps) = self.token_deps.lock() {
+                        deps.entry(call.contract.clone())
+                            .or_default()
+                            .extend(tokens.iter().cloned());
+                    }
+                    gated.extend(tokens);
+                }
+            }
+        }
+    }
+
+    /// One hosted execution with the gates of `gated` held
+    fn try_hosted_call_gated(
+        &self,
+        call: &ContractCall,
+        gated: &BTreeSet<String>,
+    ) -> Result<GatedCall, String> {
         // Get contract snapshot (short lock, released before execution)
         let contract_snapshot = {
             let contracts = self
@@ -667,14 +1101,14 @@ impl WasmEngine {
                 .map_err(|_| "Failed to lock contracts".to_string())?;
             match contracts.get(&call.contract) {
                 Some(c) => c.clone(),
-                None => return Ok(None), // Let main code handle "not found"
+                None => return Ok(GatedCall::Done(None)), // Let main code handle "not found"
             }
         }; // lock released
 
         // Must be valid WASM to attempt hosted execution
         if contract_snapshot.bytecode.len() < 4 || !contract_snapshot.bytecode.starts_with(b"\0asm")
         {
-            return Ok(None);
+            return Ok(GatedCall::Done(None));
         }
 
         // DETERMINISM FIX: Use block timestamp for reproducible execution.
@@ -689,7 +1123,8 @@ impl WasmEngine {
                 .as_secs()
         };
 
-        match self.execute_wasm_hosted(
+        match self.execute_hosted_on(
+            Lane::Chain,
             &contract_snapshot.bytecode,
             &call.function,
             &call.args,
@@ -698,11 +1133,30 @@ impl WasmEngine {
             &call.contract,
             &contract_snapshot.state,
             contract_snapshot.balance,
+            contract_snapshot.storage_deposit,
             timestamp,
+            call.block_timestamp,
+            &Attached::of(call),
         ) {
             Ok(exec_result) => {
-                // Apply state changes + transfers back to contract (short lock)
-                if !exec_result.state_changes.is_empty() || !exec_result.transfers.is_empty() {
+                let ungated: BTreeSet<String> = exec_result
+                    .token_changes
+                    .keys()
+                    .filter(|t| !gated.contains(*t))
+                    .cloned()
+                    .collect();
+                if !ungated.is_empty() {
+                    return Ok(GatedCall::NeedsGates(ungated));
+                }
+
+                // Apply state changes, deletions, transfers and the storage
+                // deposit change back to the contract, and token moves to the
+                // USP-01 contracts involved, under one lock
+                if !exec_result.state_changes.is_empty()
+                    || !exec_result.deleted_keys.is_empty()
+                    || !exec_result.transfers.is_empty()
+                    || !exec_result.token_changes.is_empty()
+                {
                     let mut contracts = self
                         .contracts
                         .lock()
@@ -712,9 +1166,28 @@ impl WasmEngine {
                             c.state
                                 .insert(key.clone(), String::from_utf8_lossy(val).to_string());
                         }
+                        for key in &exec_result.deleted_keys {
+                            c.state.remove(key);
+                        }
                         for (_, amount) in &exec_result.transfers {
                             c.balance = c.balance.saturating_sub(*amount);
                         }
+                        c.balance = c
+                            .balance
+                            .saturating_add(c.storage_deposit)
+                            .saturating_sub(exec_result.storage_deposit);
+                        c.storage_deposit = exec_result.storage_deposit;
+                    }
+                    // Absolute token values are safe: this call holds every token's gate
+                    for (token, changes) in &exec_result.token_changes {
+                        if let Some(t) = contracts.get_mut(token) {
+                            t.state.extend(changes.state.clone());
+                            t.balance = t
+                                .balance
+                                .saturating_add(t.storage_deposit)
+                                .saturating_sub(changes.storage_deposit);
+                            t.storage_deposit = changes.storage_deposit;
+                        }
                     }
                 }
 
@@ -732,7 +1205,7 @@ impl WasmEngine {
                     (true, exec_result.return_code.to_string())
                 };
 
-                Ok(Some(ContractResult {
+                Ok(GatedCall::Done(Some(ContractResult {
                     success,
                     output,
                     gas_used: exec_result.gas_used,
@@ -742,19 +1215,180 @@ impl WasmEngine {
                         .map(|(k, v)| (k.clone(), String::from_utf8_lossy(v).to_string()))
                         .collect(),
                     events: exec_result.events,
-                }))
+                })))
             }
             Err(e)
                 if e.contains("Out of gas")
                     || e.contains("timeout")
                     || e.contains("too large")
-                    || e.contains("leaked")
+                    || e.contains("executor busy")
                     || e.contains("aborted") =>
             {
                 Err(e) // Fatal — propagate
             }
-            Err(_) => Ok(None), // Non-fatal — fall through to legacy/mock
+            Err(_) => Ok(GatedCall::Done(None)), // Non-fatal — fall through to legacy/mock
+        }
+    }
+
+    /// Execute a call against a snapshot of the contract (optionally credited
+    /// with `value_cil` first). Nothing is written back.
+    fn execute_snapshot(
+        &self,
+        lane: Lane,
+        call: &ContractCall,
+        value_cil: u128,
+        gas_cap: u64,
+    ) -> Result<host::HostExecResult, String> {
+        let contract = self.get_contract(&call.contract)?;
+        if contract.bytecode.len() < 8 || !contract.bytecode.starts_with(b"\0asm") {
+            return Err("Contract has no executable WASM code".to_string());
+        }
+
+        let timestamp = if call.block_timestamp > 0 {
+            call.block_timestamp
+        } else {
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs()
+        };
+
+        self.execute_hosted_on(
+            lane,
+            &contract.bytecode,
+            &call.function,
+            &call.args,
+            call.gas_limit.min(gas_cap),
+            &call.caller,
+            &call.contract,
+            &contract.state,
+            contract.balance.saturating_add(value_cil),
+            contract.storage_deposit,
+            timestamp,
+            call.block_timestamp,
+            &Attached {
+                value_cil,
+                token_allowances: call.token_allowances.clone(),
+            },
+        )
+    }
+
+    /// Run an exported function against current contract state without
+    /// committing anything (eth_call-style). Gas is capped at MAX_QUERY_GAS.
+    pub fn query_contract(&self, call: &ContractCall) -> Result<ContractQueryResult, String> {
+        let exec = self.execute_snapshot(Lane::Query, call, 0, MAX_QUERY_GAS)?;
+        let (success, output) = exec_outcome(&exec);
+        Ok(ContractQueryResult {
+            success,
+            output,
+            return_code: exec.return_code,
+            return_data: hex::encode(&exec.return_data),
+            gas_used: exec.gas_used,
+            logs: exec.logs,
+            events: exec.events,
+            state_changes: exec
+                .state_changes
+                .iter()
+                .map(|(k, v)| (k.clone(), String::from_utf8_lossy(v).to_string()))
+                .collect(),
+        })
+    }
+
+    /// Dry-run a ContractCall block: same gas cap as a real call,
+    /// `call.value_cil` credited to the contract first, nothing committed.
+    pub fn simulate_call(&self, call: &ContractCall) -> Result<CallSimulation, String> {
+        let before = self.get_contract_state(&call.contract)?;
+        let exec =
+            self.execute_snapshot(Lane::Simulation, call, call.value_cil, MAX_GAS_PER_CALL)?;
+        let (success, output) = exec_outcome(&exec);
+        let state_diff = exec
+            .state_changes
+            .iter()
+            .map(|(k, v)| {
+                (
+                    k.clone(),
+                    StateDiffEntry {
+                        before: before.get(k).cloned(),
+                        after: String::from_utf8_lossy(v).to_string(),
+                    },
+                )
+            })
+            .collect();
+        Ok(CallSimulation {
+            success,
+            output,
+            gas_used: exec.gas_used,
+            recommended_gas_limit: recommended_gas_limit(exec.gas_used),
+            state_diff,
+            events: exec.events,
+            transfers: exec
+                .transfers
+                .into_iter()
+                .map(|(recipient, amount)| ContractTransfer { recipient, amount })
+                .collect(),
+            logs: exec.logs,
+        })
+    }
+
+    /// Dry-run a ContractDeploy block: validates and compiles the bytecode and
+    /// predicts the address `deploy_contract` would assign at `block_number`.
+    /// The resolved ABI must only name functions the module exports.
+    pub fn simulate_deploy(
+        &self,
+        owner: &str,
+        bytecode: &[u8],
+        block_number: u64,
+        attached_abi: Option<ContractAbi>,
+    ) -> Result<DeploySimulation, String> {
+        if bytecode.len() < 4 || &bytecode[0..4] != b"\0asm" {
+            return Err("Invalid WASM bytecode (missing magic header)".to_string());
+        }
+        if bytecode.len() > MAX_BYTECODE_SIZE {
+            return Err(format!(
+                "WASM bytecode too large: {} bytes (max {} bytes)",
+                bytecode.len(),
+                MAX_BYTECODE_SIZE
+            ));
+        }
+
+        // Compiling also warms the module cache for the first real call
+        let (compiled, _) = prepare_module(&self.module_cache, bytecode, 0)?;
+        let exports = compiled
+            .module
+            .exports()
+            .functions()
+            .map(|f| f.name().to_string())
+            .collect::<Vec<_>>();
+
+        let abi = Self::resolve_abi(bytecode, attached_abi)?;
+        if let Some(abi) = &abi {
+            let missing = abi.missing_exports(&exports);
+            if !missing.is_empty() {
+                return Err(format!(
+                    "ABI names functions the module does not export: {}",
+                    missing.join(", ")
+                ));
+            }
         }
+
+        let contract_nonce = self
+            .nonce
+            .lock()
+            .map_err(|_| "Failed to lock nonce".to_string())?
+            .get(owner)
+            .copied()
+            .unwrap_or(0);
+        let addr_input = format!("{}:{}:{}", owner, contract_nonce, block_number);
+        let addr_hash = blake3::hash(addr_input.as_bytes());
+
+        Ok(DeploySimulation {
+            contract_address: format!("LOSCon{}", hex::encode(&addr_hash.as_bytes()[0..16])),
+            code_hash: Self::compute_code_hash(bytecode),
+            bytecode_size: bytecode.len(),
+            compile_gas_per_call: (bytecode.len() as u64 / 1024 + 1) * GAS_PER_KB_BYTECODE,
+            exports,
+            abi,
+        })
     }
 
     /// Execute contract function.
@@ -805,7 +1439,8 @@ impl WasmEngine {
                         Err(e)
                             if e.contains("Out of gas")
                                 || e.contains("timeout")
-                                || e.contains("too large") =>
+                                || e.contains("too large")
+                                || e.contains("executor busy") =>
                         {
                             return Err(e);
                         }
@@ -1118,6 +1753,8 @@ mod tests {
             gas_limit: 1000,
             caller: "charlie".to_string(),
             block_timestamp: 0,
+            value_cil: 0,
+            token_allowances: Default::default(),
         };
 
         let result = engine.call_contract(call).unwrap();
@@ -1142,6 +1779,8 @@ mod tests {
             gas_limit: 1000,
             caller: "dave".to_string(),
             block_timestamp: 0,
+            value_cil: 0,
+            token_allowances: Default::default(),
         };
 
         let result = engine.call_contract(set_call).unwrap();
@@ -1155,6 +1794,8 @@ mod tests {
             gas_limit: 1000,
             caller: "dave".to_string(),
             block_timestamp: 0,
+            value_cil: 0,
+            token_allowances: Default::default(),
         };
 
         let result = engine.call_contract(get_call).unwrap();
@@ -1179,6 +1820,8 @@ mod tests {
             gas_limit: 100,
             caller: "eve".to_string(),
             block_timestamp: 0,
+            value_cil: 0,
+            token_allowances: Default::default(),
         };
 
         let result = engine.call_contract(call).unwrap();
@@ -1195,6 +1838,8 @@ mod tests {
             gas_limit: 1000,
             caller: "nobody".to_string(),
             block_timestamp: 0,
+            value_cil: 0,
+            token_allowances: Default::default(),
         };
 
         let result = engine.call_contract(call);
@@ -1267,6 +1912,8 @@ mod tests {
             gas_limit: 50, // Too low
             caller: "henry".to_string(),
             block_timestamp: 0,
+            value_cil: 0,
+            token_allowances: Default::default(),
         };
 
         let result = engine.call_contract(call);
@@ -1290,6 +1937,8 @@ mod tests {
             gas_limit: 1000,
             caller: "iris".to_string(),
             block_timestamp: 0,
+            value_cil: 0,
+            token_allowances: Default::default(),
         };
 
         let result = engine.call_contract(call);
@@ -1331,6 +1980,8 @@ mod tests {
             gas_limit: 100,
             caller: "jack".to_string(),
             block_timestamp: 0,
+            value_cil: 0,
+            token_allowances: Default::default(),
         };
 
         engine.call_contract(call).unwrap();
@@ -1380,10 +2031,550 @@ mod tests {
             gas_limit: 1000,
             caller: "wasm_tester".to_string(),
             block_timestamp: 0,
+            value_cil: 0,
+            token_allowances: Default::default(),
         };
 
         let result = engine.call_contract(call).unwrap();
         assert!(result.success);
         assert_eq!(result.output, "12"); // 5 + 7 = 12
     }
+
+    #[test]
+    fn test_cached_module_charges_same_gas() {
+        let engine = WasmEngine::new();
+        let wasm_bytes = wasmer::wat2wasm(
+            br#"(module (func (export "add") (param i32 i32) (result i32)
+                local.get 0 local.get 1 i32.add))"#,
+        )
+        .unwrap()
+        .to_vec();
+
+        let cold = engine
+            .execute_wasm(&wasm_bytes, "add", &[5, 7], 1000)
+            .unwrap();
+        let warm = engine
+            .execute_wasm(&wasm_bytes, "add", &[5, 7], 1000)
+            .unwrap();
+        assert_eq!(cold, (12, cold.1));
+        assert_eq!(warm, cold);
+        // Compile gas is still charged on a cache hit
+        assert!(warm.1 > GAS_PER_KB_BYTECODE);
+
+        // Points are reset per call: a cached module still honours a tight limit
+        let tight = engine.execute_wasm(&wasm_bytes, "add", &[5, 7], GAS_PER_KB_BYTECODE + 1);
+        assert!(tight.unwrap_err().contains("Out of gas"));
+
+        let stats = engine.module_cache_stats();
+        assert_eq!(stats.entries, 1);
+        assert_eq!(stats.misses, 1);
+        assert_eq!(stats.hits, 2);
+
+        // Hosted path shares the cache and agrees on gas
+        let hosted = engine
+            .execute_wasm_hosted(
+                &wasm_bytes,
+                "add",
+                &["5".to_string(), "7".to_string()],
+                1000,
+                "caller",
+                "contract",
+                &BTreeMap::new(),
+                0,
+                0,
+                0,
+            )
+            .unwrap();
+        assert_eq!((hosted.return_code, hosted.gas_used), cold);
+        assert_eq!(engine.module_cache_stats().hits, 3);
+    }
+
+    #[test]
+    fn test_gas_is_always_capped() {
+        let engine = WasmEngine::new();
+        assert_eq!(engine.capped_gas(u64::MAX), MAX_GAS_PER_CALL);
+        assert_eq!(engine.capped_gas(1_000), 1_000);
+        // The chain may lower the cap, never raise it
+        engine.set_max_gas_per_call(Some(5_000_000));
+        assert_eq!(engine.capped_gas(u64::MAX), 5_000_000);
+        engine.set_max_gas_per_call(Some(u64::MAX));
+        assert_eq!(engine.capped_gas(u64::MAX), MAX_GAS_PER_CALL);
+    }
+
+    #[test]
+    fn test_infinite_loop_is_interrupted_by_metering() {
+        let engine = WasmEngine::new();
+        let spin = wasmer::wat2wasm(
+            br#"(module (func (export "spin") (result i32) (loop $l (br $l)) i32.const 0))"#,
+        )
+        .unwrap()
+        .to_vec();
+
+        // One more attempt than the old leaked-thread limit (16)
+        for _ in 0..17 {
+            let err = engine
+                .execute_wasm(&spin, "spin", &[], u64::MAX)
+                .unwrap_err();
+            assert!(err.contains("Out of gas"), "{}", err);
+        }
+
+        let add = wasmer::wat2wasm(
+            br#"(module (func (export "add") (param i32 i32) (result i32)
+                local.get 0 local.get 1 i32.add))"#,
+        )
+        .unwrap()
+        .to_vec();
+        assert_eq!(
+            engine.execute_wasm(&add, "add", &[2, 3], 1000).unwrap().0,
+            5
+        );
+    }
+
+    #[test]
+    fn test_query_contract_discards_state() {
+        let engine = WasmEngine::new();
+        let wasm_bytes = wasmer::wat2wasm(
+            br#"(module
+                (import "env" "host_set_state" (func $set (param i32 i32 i32 i32)))
+                (import "env" "host_set_return" (func $ret (param i32 i32)))
+                (import "env" "host_log" (func $log (param i32 i32)))
+                (memory (export "memory") 1)
+                (data (i32.const 0) "counter")
+                (data (i32.const 16) "42")
+                (func (export "bump") (result i32)
+                    i32.const 0 i32.const 7 i32.const 16 i32.const 2 call $set
+                    i32.const 0 i32.const 7 call $log
+                    i32.const 16 i32.const 2 call $ret
+                    i32.const 0))"#,
+        )
+        .unwrap()
+        .to_vec();
+        let addr = engine
+            .deploy_contract("lena".to_string(), wasm_bytes, BTreeMap::new(), 1)
+            .unwrap();
+
+        let call = ContractCall {
+            contract: addr.clone(),
+            function: "bump".to_string(),
+            args: vec![],
+            gas_limit: u64::MAX,
+            caller: String::new(),
+            block_timestamp: 0,
+            value_cil: 0,
+            token_allowances: Default::default(),
+        };
+        let result = engine.query_contract(&call).unwrap();
+        assert!(result.success);
+        assert_eq!(result.output, "42");
+        assert_eq!(result.return_data, hex::encode("42"));
+        assert_eq!(result.logs, vec!["counter".to_string()]);
+        assert_eq!(result.state_changes.get("counter"), Some(&"42".to_string()));
+        assert!(engine.get_contract_state(&addr).unwrap().is_empty());
+
+        // The same call as a transaction does commit
+        engine.call_contract(call).unwrap();
+        assert_eq!(
+            engine.get_contract_state(&addr).unwrap().get("counter"),
+            Some(&"42".to_string())
+        );
+    }
+
+    #[test]
+    fn test_storage_deposit_locked_and_refunded() {
+        let engine = WasmEngine::new();
+        let wasm_bytes = wasmer::wat2wasm(
+            br#"(module
+                (import "env" "host_set_state" (func $set (param i32 i32 i32 i32)))
+                (import "env" "host_del_state" (func $del (param i32 i32)))
+                (memory (export "memory") 2)
+                (data (i32.const 0) "big")
+                (func (export "grow") (result i32)
+                    i32.const 0 i32.const 3 i32.const 16 i32.const 70000 call $set
+                    i32.const 0)
+                (func (export "shrink") (result i32)
+                    i32.const 0 i32.const 3 call $del
+                    i32.const 0))"#,
+        )
+        .unwrap()
+        .to_vec();
+        let addr = engine
+            .deploy_contract("mia".to_string(), wasm_bytes, BTreeMap::new(), 1)
+            .unwrap();
+        let call = |function: &str| ContractCall {
+            contract: addr.clone(),
+            function: function.to_string(),
+            args: vec![],
+            gas_limit: 100_000,
+            caller: "mia".to_string(),
+            block_timestamp: 1,
+            value_cil: 0,
+            token_allowances: Default::default(),
+        };
+
+        // 70,003 bytes is over the free allowance and the contract holds nothing
+        let deposit = host::storage_deposit_for(70_003);
+        assert!(deposit > 0);
+        let err = engine.call_contract(call("grow")).unwrap_err();
+        assert!(err.contains("storage deposit"), "{}", err);
+        assert!(engine.get_contract_state(&addr).unwrap().is_empty());
+
+        engine.send_to_contract(&addr, deposit + 5).unwrap();
+        engine.call_contract(call("grow")).unwrap();
+        let c = engine.get_contract(&addr).unwrap();
+        assert_eq!(c.storage_bytes(), 70_003);
+        assert_eq!(c.storage_deposit, deposit);
+        assert_eq!(c.balance, 5);
+
+        // Deleting the key persists and hands the deposit back
+        engine.call_contract(call("shrink")).unwrap();
+        let c = engine.get_contract(&addr).unwrap();
+        assert!(c.state.is_empty());
+        assert_eq!(c.storage_deposit, 0);
+        assert_eq!(c.balance, deposit + 5);
+    }
+
+    #[test]
+    fn test_gas_schedule_charges_host_calls_from_activation_time() {
+        let engine = WasmEngine::new();
+        let wasm_bytes = wasmer::wat2wasm(
+            br#"(module
+                (import "env" "host_emit_event" (func $emit (param i32 i32 i32 i32)))
+                (memory (export "memory") 1)
+                (data (i32.const 0) "Ping")
+                (func (export "ping") (result i32)
+                    i32.const 0 i32.const 4 i32.const 16 i32.const 1000 call $emit
+                    i32.const 0))"#,
+        )
+        .unwrap()
+        .to_vec();
+        let run = |gas_limit: u64, timestamp: u64| {
+            engine.execute_wasm_hosted(
+                &wasm_bytes,
+                "ping",
+                &[],
+                gas_limit,
+                "mia",
+                "contract",
+                &BTreeMap::new(),
+                0,
+                0,
+                timestamp,
+            )
+        };
+
+        // Before activation the event costs only its instructions
+        let v0 = run(100_000, gas_schedule::GAS_SCHEDULE_V1_TIME - 1).unwrap();
+        let v1 = run(100_000, gas_schedule::GAS_SCHEDULE_V1_TIME).unwrap();
+        assert_eq!(v0.events.len(), 1);
+        assert_eq!(
+            v1.gas_used - v0.gas_used,
+            gas_schedule::GasSchedule::latest().event(1004)
+        );
+
+        // Host charges exhaust gas like instructions do
+        let err = match run(v0.gas_used, gas_schedule::GAS_SCHEDULE_V1_TIME) {
+            Err(e) => e,
+            Ok(_) => panic!("host charges should exhaust the gas limit"),
+        };
+        assert!(err.contains("Out of gas"), "{}", err);
+    }
+
+    #[test]
+    fn test_calls_are_charged_by_their_block_timestamp() {
+        let engine = WasmEngine::new();
+        let contract = engine
+            .deploy_contract(
+                "mia".to_string(),
+                wasmer::wat2wasm(
+                    br#"(module
+                        (import "env" "host_emit_event" (func $emit (param i32 i32 i32 i32)))
+                        (memory (export "memory") 1)
+                        (data (i32.const 0) "Ping")
+                        (func (export "ping") (result i32)
+                            i32.const 0 i32.const 4 i32.const 16 i32.const 1000 call $emit
+                            i32.const 0))"#,
+                )
+                .unwrap()
+                .to_vec(),
+                BTreeMap::new(),
+                1,
+            )
+            .unwrap();
+        let gas_at = |block_timestamp: u64| {
+            let result = engine
+                .call_contract(ContractCall {
+                    contract: contract.clone(),
+                    function: "ping".to_string(),
+                    args: vec![],
+                    gas_limit: 100_000,
+                    caller: "mia".to_string(),
+                    block_timestamp,
+                    value_cil: 0,
+                    token_allowances: Default::default(),
+                })
+                .unwrap();
+            assert!(result.success, "{}", result.output);
+            result.gas_used
+        };
+
+        // The signed block timestamp alone picks the schedule, so every node
+        // replaying the block charges the same gas
+        let before = gas_at(gas_schedule::GAS_SCHEDULE_V1_TIME - 1);
+        let after = gas_at(gas_schedule::GAS_SCHEDULE_V1_TIME);
+        assert_eq!(gas_at(gas_schedule::GAS_SCHEDULE_V1_TIME - 1), before);
+        assert_eq!(
+            after - before,
+            gas_schedule::GasSchedule::latest().event(1004)
+        );
+    }
+
+    /// A DEX whose `pull` moves 30 of token `arg0` from alice to itself,
+    /// and a token where alice holds 100 and allowed the DEX 50
+    fn deploy_pull_dex(engine: &WasmEngine) -> (String, String) {
+        let dex_wasm = wasmer::wat2wasm(
+            br#"(module
+                (import "env" "host_get_arg" (func $arg (param i32 i32 i32) (result i32)))
+                (import "env" "host_get_self_address" (func $self (param i32 i32) (result i32)))
+                (import "env" "host_token_transfer"
+                    (func $move (param i32 i32 i32 i32 i32 i32 i64 i64) (result i32)))
+                (import "env" "host_abort" (func $abort (param i32 i32)))
+                (memory (export "memory") 1)
+                (data (i32.const 0) "alice")
+                (func $pull (result i32) (local $t i32) (local $s i32)
+                    i32.const 0 i32.const 100 i32.const 64 call $arg local.set $t
+                    i32.const 200 i32.const 64 call $self local.set $s
+                    i32.const 100 local.get $t i32.const 0 i32.const 5
+                    i32.const 200 local.get $s i64.const 30 i64.const 0 call $move)
+                (func (export "pull") (result i32) call $pull)
+                (func (export "pull_then_abort") (result i32)
+                    call $pull drop
+                    i32.const 0 i32.const 5 call $abort
+                    i32.const 1))"#,
+        )
+        .unwrap()
+        .to_vec();
+        let dex = engine
+            .deploy_contract("mia".to_string(), dex_wasm, BTreeMap::new(), 1)
+            .unwrap();
+        let mut token_state = BTreeMap::new();
+        token_state.insert("usp01:init".to_string(), "1".to_string());
+        token_state.insert("bal:alice".to_string(), "100".to_string());
+        token_state.insert(format!("allow:alice:{}", dex), "50".to_string());
+        let token = engine
+            .deploy_contract(
+                "mia".to_string(),
+                wasmer::wat2wasm(b"(module)").unwrap().to_vec(),
+                token_state,
+                1,
+            )
+            .unwrap();
+        (dex, token)
+    }
+
+    #[test]
+    fn test_token_transfer_commits_with_the_calling_contract() {
+        let engine = WasmEngine::new();
+        let (dex, token) = deploy_pull_dex(&engine);
+        let call = |function: &str| ContractCall {
+            contract: dex.clone(),
+            function: function.to_string(),
+            args: vec![token.clone()],
+            gas_limit: 100_000,
+            caller: "alice".to_string(),
+            block_timestamp: 1,
+            value_cil: 0,
+            token_allowances: Default::default(),
+        };
+        let token_state = || engine.get_contract_state(&token).unwrap();
+
+        let result = engine.call_contract(call("pull")).unwrap();
+        assert!(result.success, "{}", result.output);
+        assert_eq!(result.events[0].contract, token);
+        assert_eq!(token_state()["bal:alice"], "70");
+        assert_eq!(token_state()[&format!("bal:{}", dex)], "30");
+        assert_eq!(token_state()[&format!("allow:alice:{}", dex)], "20");
+
+        // An abort after the move reverts it along with the caller's state
+        assert!(engine.call_contract(call("pull_then_abort")).is_err());
+        assert_eq!(token_state()["bal:alice"], "70");
+
+        // The remaining allowance doesn't cover another pull
+        let result = engine.call_contract(call("pull")).unwrap();
+        assert!(!result.success);
+        assert_eq!(result.output, "2");
+        assert_eq!(token_state()["bal:alice"], "70");
+    }
+
+    #[test]
+    fn test_call_allowance_is_spent_before_the_stored_one() {
+        let engine = WasmEngine::new();
+        let (dex, token) = deploy_pull_dex(&engine);
+        let call = ContractCall {
+            contract: dex.clone(),
+            function: "pull".to_string(),
+            args: vec![token.clone()],
+            gas_limit: 100_000,
+            caller: "alice".to_string(),
+            block_timestamp: 1,
+            value_cil: 0,
+            token_allowances: BTreeMap::from([(token.clone(), 25)]),
+        };
+        let result = engine.call_contract(call.clone()).unwrap();
+        assert!(result.success, "{}", result.output);
+        let token_state = engine.get_contract_state(&token).unwrap();
+        assert_eq!(token_state[&format!("bal:{}", dex)], "30");
+        // 25 came from the call, only 5 from the stored approval
+        assert_eq!(token_state[&format!("allow:alice:{}", dex)], "45");
+
+        // Without the caller's signature behind it the call allowance is ignored
+        let result = engine
+            .call_contract(ContractCall {
+                caller: "bob".to_string(),
+                ..call
+            })
+            .unwrap();
+        assert!(result.success, "{}", result.output);
+        let token_state = engine.get_contract_state(&token).unwrap();
+        assert_eq!(token_state[&format!("allow:alice:{}", dex)], "15");
+    }
+
+    #[test]
+    fn test_token_moves_wait_for_the_token_gate() {
+        let engine = WasmEngine::new();
+        let (dex, token) = deploy_pull_dex(&engine);
+        let call = ContractCall {
+            contract: dex.clone(),
+            function: "pull".to_string(),
+            args: vec![token.clone()],
+            gas_limit: 100_000,
+            caller: "alice".to_string(),
+            block_timestamp: 1,
+            value_cil: 0,
+            token_allowances: Default::default(),
+        };
+
+        // A call on the token is in flight: the DEX call must not commit over it
+        let gate = engine
+            .gates_for(&BTreeSet::from([token.clone()]))
+            .unwrap()
+            .remove(0);
+        let held = gate.lock().unwrap();
+        let (tx, rx) = std::sync::mpsc::channel();
+        std::thread::scope(|scope| {
+            let engine = &engine;
+            scope.spawn(move || tx.send(engine.call_contract(call)).unwrap());
+            assert!(rx
+                .recv_timeout(std::time::Duration::from_millis(200))
+                .is_err());
+            drop(held);
+            let result = rx
+                .recv_timeout(std::time::Duration::from_secs(5))
+                .unwrap()
+                .unwrap();
+            assert!(result.success, "{}", result.output);
+        });
+        assert_eq!(
+            engine.get_contract_state(&token).unwrap()["bal:alice"],
+            "70"
+        );
+        // Later calls take the token's gate up front
+        assert!(engine.token_deps.lock().unwrap()[&dex].contains(&token));
+    }
+
+    #[test]
+    fn test_deploy_stores_embedded_or_attached_abi() {
+        let engine = WasmEngine::new();
+        let wasm_bytes =
+            wasmer::wat2wasm(br#"(module (func (export "ping") (result i32) i32.const 0))"#)
+                .unwrap()
+                .to_vec();
+        let abi = ContractAbi::from_json(
+            br#"{"version": 1, "functions": [{"name": "ping", "view": true}]}"#,
+        )
+        .unwrap();
+        let embedded = abi::embed(&wasm_bytes, &abi).unwrap();
+
+        let sim = engine.simulate_deploy("mia", &embedded, 1, None).unwrap();
+        assert_eq!(sim.abi.as_ref(), Some(&abi));
+        let addr = engine
+            .deploy_contract("mia".to_string(), embedded, BTreeMap::new(), 1)
+            .unwrap();
+        assert_eq!(engine.get_contract(&addr).unwrap().abi, Some(abi.clone()));
+
+        // An attached ABI must match the module's exports
+        let mut wrong = abi;
+        wrong.functions[0].name = "pong".to_string();
+        let err = engine
+            .simulate_deploy("mia", &wasm_bytes, 1, Some(wrong))
+            .unwrap_err();
+        assert!(err.contains("pong"));
+
+        // No ABI at all is still a valid deploy
+        let addr = engine
+            .deploy_contract("mia".to_string(), wasm_bytes, BTreeMap::new(), 1)
+            .unwrap();
+        assert_eq!(engine.get_contract(&addr).unwrap().abi, None);
+    }
+
+    #[test]
+    fn test_simulate_matches_real_execution() {
+        let engine = WasmEngine::new();
+        let wasm_bytes = wasmer::wat2wasm(
+            br#"(module
+                (import "env" "host_set_state" (func $set (param i32 i32 i32 i32)))
+                (memory (export "memory") 1)
+                (data (i32.const 0) "owner")
+                (data (i32.const 16) "mia")
+                (func (export "init") (result i32)
+                    i32.const 0 i32.const 5 i32.const 16 i32.const 3 call $set
+                    i32.const 0))"#,
+        )
+        .unwrap()
+        .to_vec();
+
+        let predicted = engine.simulate_deploy("mia", &wasm_bytes, 7, None).unwrap();
+        assert_eq!(predicted.exports, vec!["init".to_string()]);
+        assert_eq!(predicted.compile_gas_per_call, GAS_PER_KB_BYTECODE);
+        let mut initial = BTreeMap::new();
+        initial.insert("owner".to_string(), "nobody".to_string());
+        let addr = engine
+            .deploy_contract("mia".to_string(), wasm_bytes, initial, 7)
+            .unwrap();
+        assert_eq!(predicted.contract_address, addr);
+
+        let call = ContractCall {
+            contract: addr.clone(),
+            function: "init".to_string(),
+            args: vec![],
+            gas_limit: 10_000,
+            caller: "mia".to_string(),
+            block_timestamp: 1,
+            value_cil: 0,
+            token_allowances: Default::default(),
+        };
+        let sim = engine.simulate_call(&call).unwrap();
+        assert_eq!(
+            sim.state_diff.get("owner"),
+            Some(&StateDiffEntry {
+                before: Some("nobody".to_string()),
+                after: "mia".to_string()
+            })
+        );
+        assert_eq!(
+            sim.recommended_gas_limit,
+            recommended_gas_limit(sim.gas_used)
+        );
+        assert!(sim.recommended_gas_limit > sim.gas_used);
+        assert_eq!(engine.get_contract_state(&addr).unwrap()["owner"], "nobody");
+
+        // The recommended limit is enough for the real call, which uses the same gas
+        let real = engine
+            .call_contract(ContractCall {
+                gas_limit: sim.recommended_gas_limit,
+                ..call
+            })
+            .unwrap();
+        assert_eq!(real.gas_used, sim.gas_used);
+        assert_eq!(engine.get_contract_state(&addr).unwrap()["owner"], "mia");
+    }
 }
@@ -0,0 +1,324 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - COMPILED WASM MODULE CACHE
+//
+// Cranelift compilation dominates the cost of a contract call, and the same
+// bytecode is executed over and over. Compiled modules are cached in memory
+// (bounded, least-recently-used eviction) keyed by the contract code hash, and
+// optionally persisted as serialized artifacts so a restarted node starts warm.
+//
+// Gas stays deterministic:
+// - Metering is injected at compile time and is identical for every compile
+//   of the same bytecode; only the initial points global differs, and it is
+//   reset with `set_remaining_points` on each instantiation.
+// - Callers still charge GAS_PER_KB_BYTECODE per call, cached or not, so
+//   cold and warm validators agree on `gas_used`.
+// - Modules with a WASM start section run code during instantiation, before
+//   the limit can be reset, so they are never cached.
+// - Artifact files are keyed on the host ABI and gas schedules as well as
+//   the code hash, so a node upgrade never loads modules built for older ones.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use wasmer::{CompilerConfig, Engine, Module};
+use wasmer_compiler_cranelift::Cranelift;
+use wasmer_middlewares::Metering;
+
+/// Maximum number of compiled modules kept in memory
+pub const MAX_CACHED_MODULES: usize = 256;
+/// Artifact format version (bump when metering or compiler settings change)
+const ARTIFACT_VERSION: u32 = 2;
+/// Initial metering points baked into cached modules (reset on every call)
+const CACHED_INITIAL_POINTS: u64 = 0;
+
+/// A compiled module together with the engine that owns its code
+#[derive(Clone)]
+pub struct CompiledModule {
+    pub engine: Engine,
+    pub module: Module,
+}
+
+/// Compile bytecode with deterministic metering (1 gas per WASM instruction).
+/// Each compile needs its own Metering instance, so it gets its own engine too.
+pub fn compile_metered(bytecode: &[u8], initial_points: u64) -> Result<CompiledModule, String> {
+    let engine = metered_engine(initial_points);
+    let module =
+        Module::new(&engine, bytecode).map_err(|e| format!("Failed to compile WASM: {}", e))?;
+    Ok(CompiledModule { engine, module })
+}
+
+fn metered_engine(initial_points: u64) -> Engine {
+    let cost_fn = |_operator: &wasmer::wasmparser::Operator| -> u64 { 1 };
+    let metering = Arc::new(Metering::new(initial_points, cost_fn));
+    let mut compiler = Cranelift::default();
+    compiler.push_middleware(metering);
+    Engine::from(compiler)
+}
+
+/// True if the module declares a start function (runs during instantiation)
+pub fn has_start_section(bytecode: &[u8]) -> bool {
+    use wasmer::wasmparser::{Parser, Payload};
+    Parser::new(0)
+        .parse_all(bytecode)
+        .any(|payload| matches!(payload, Ok(Payload::StartSection { .. })))
+}
+
+/// Cache counters (exposed for metrics and benchmarks)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModuleCacheStats {
+    pub entries: usize,
+    pub hits: u64,
+    pub misses: u64,
+    pub disk_hits: u64,
+    pub evictions: u64,
+}
+
+struct CacheEntry {
+    compiled: CompiledModule,
+    last_used: u64,
+}
+
+/// Bounded code-hash → compiled module cache
+pub struct ModuleCache {
+    entries: BTreeMap<String, CacheEntry>,
+    capacity: usize,
+    artifact_dir: Option<PathBuf>,
+    tick: u64,
+    stats: ModuleCacheStats,
+}
+
+impl Default for ModuleCache {
+    fn default() -> Self {
+        Self::new(MAX_CACHED_MODULES)
+    }
+}
+
+impl ModuleCache {
+    /// In-memory cache holding at most `capacity` modules (0 disables caching)
+    pub fn new(capacity: usize) -> Self {
+        ModuleCache {
+            entries: BTreeMap::new(),
+            capacity,
+            artifact_dir: None,
+            tick: 0,
+            stats: ModuleCacheStats::default(),
+        }
+    }
+
+    /// Also persist compiled artifacts under `dir` (created if missing)
+    pub fn with_artifact_dir(mut self, dir: impl Into<PathBuf>) -> Result<Self, String> {
+        let dir = dir.into();
+        std::fs::create_dir_all(&dir)
+            .map_err(|e| format!("Failed to create module cache dir {:?}: {}", dir, e))?;
+        self.artifact_dir = Some(dir);
+        Ok(self)
+    }
+
+    pub fn stats(&self) -> ModuleCacheStats {
+        ModuleCacheStats {
+            entries: self.entries.len(),
+            ..self.stats
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Look up a module in memory, then on disk. Counts a miss if neither has it.
+    pub fn get(&mut self, code_hash: &str) -> Option<CompiledModule> {
+        self.tick += 1;
+        if let Some(entry) = self.entries.get_mut(code_hash) {
+            entry.last_used = self.tick;
+            self.stats.hits += 1;
+            return Some(entry.compiled.clone());
+        }
+        if let Some(compiled) = self.load_artifact(code_hash) {
+            self.stats.disk_hits += 1;
+            self.insert_memory(code_hash, compiled.clone());
+            return Some(compiled);
+        }
+        self.stats.misses += 1;
+        None
+    }
+
+    /// Store a freshly compiled module (memory + artifact dir if configured)
+    pub fn insert(&mut self, code_hash: &str, compiled: CompiledModule) {
+        if let Some(path) = self.artifact_path(code_hash) {
+            // Best effort: a failed write only means the next restart recompiles
+            if let Ok(bytes) = compiled.module.serialize() {
+                let tmp = path.with_extension("tmp");
+                if std::fs::write(&tmp, &bytes).is_ok() {
+                    let _ = std::fs::rename(&tmp, &path);
+                }
+            }
+        }
+        self.insert_memory(code_hash, compiled);
+    }
+
+    /// Return the cached module for `bytecode`, compiling it on a miss
+    pub fn get_or_compile(
+        &mut self,
+        code_hash: &str,
+        bytecode: &[u8],
+    ) -> Result<CompiledModule, String> {
+        if let Some(compiled) = self.get(code_hash) {
+            return Ok(compiled);
+        }
+        let compiled = compile_metered(bytecode, CACHED_INITIAL_POINTS)?;
+        self.insert(code_hash, compiled.clone());
+        Ok(compiled)
+    }
+
+    fn insert_memory(&mut self, code_hash: &str, compiled: CompiledModule) {
+        if self.capacity == 0 {
+            return;
+        }
+        self.tick += 1;
+        while self.entries.len() >= self.capacity && !self.entries.contains_key(code_hash) {
+            let oldest = self
+                .entries
+                .iter()
+                .min_by_key(|(_, e)| e.last_used)
+                .map(|(k, _)| k.clone());
+            match oldest {
+                Some(key) => {
+                    self.entries.remove(&key);
+                    self.stats.evictions += 1;
+                }
+                None => break,
+            }
+        }
+        self.entries.insert(
+            code_hash.to_string(),
+            CacheEntry {
+                compiled,
+                last_used: self.tick,
+            },
+        );
+    }
+
+    fn artifact_path(&self, code_hash: &str) -> Option<PathBuf> {
+        // Code hashes are hex; anything else must not be turned into a path
+        if code_hash.is_empty() || !code_hash.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        self.artifact_dir
+            .as_ref()
+            .map(|dir| artifact_file(dir, code_hash))
+    }
+
+    fn load_artifact(&self, code_hash: &str) -> Option<CompiledModule> {
+        let path = self.artifact_path(code_hash)?;
+        let bytes = std::fs::read(&path).ok()?;
+        let engine = metered_engine(CACHED_INITIAL_POINTS);
+        // SAFETY: artifacts are only ever written by this node from its own
+        // compiles (never received from peers). Anything that fails to load is
+        // deleted and recompiled from bytecode.
+        match unsafe { Module::deserialize(&engine, bytes) } {
+            Ok(module) => Some(CompiledModule { engine, module }),
+            Err(_) => {
+                let _ = std::fs::remove_file(&path);
+                None
+            }
+        }
+    }
+}
+
+fn artifact_file(dir: &Path, code_hash: &str) -> PathBuf {
+    dir.join(format!(
+        "v{}-{}-{}.wasmu",
+        ARTIFACT_VERSION,
+        artifact_tag(),
+        code_hash
+    ))
+}
+
+/// Fingerprint of what compiled artifacts depend on besides the bytecode:
+/// the format version, the host ABI and the gas schedules
+fn artifact_tag() -> &'static str {
+    static TAG: std::sync::OnceLock<String> = std::sync::OnceLock::new();
+    TAG.get_or_init(|| {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(&ARTIFACT_VERSION.to_le_bytes());
+        for (name, params, results) in crate::host::HOST_ABI {
+            hasher.update(format!("{}{:?}{:?};", name, params, results).as_bytes());
+        }
+        hasher.update(
+            serde_json::to_string(crate::gas_schedule::GAS_SCHEDULES)
+                .unwrap_or_default()
+                .as_bytes(),
+        );
+        hasher.finalize().to_hex()[..16].to_string()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn add_module() -> Vec<u8> {
+        wasmer::wat2wasm(
+            br#"(module (func (export "add") (param i32 i32) (result i32)
+                local.get 0 local.get 1 i32.add))"#,
+        )
+        .unwrap()
+        .to_vec()
+    }
+
+    #[test]
+    fn test_cache_hits_and_evicts_least_recent() {
+        let bytecode = add_module();
+        let mut cache = ModuleCache::new(2);
+        cache.get_or_compile("aa", &bytecode).unwrap();
+        cache.get_or_compile("bb", &bytecode).unwrap();
+        cache.get_or_compile("aa", &bytecode).unwrap();
+        // "bb" is least recently used and makes room for "cc"
+        cache.get_or_compile("cc", &bytecode).unwrap();
+
+        let stats = cache.stats();
+        assert_eq!(stats.entries, 2);
+        assert_eq!(stats.hits, 1);
+        assert_eq!(stats.misses, 3);
+        assert_eq!(stats.evictions, 1);
+        assert!(cache.get("aa").is_some());
+        assert!(cache.get("bb").is_none());
+    }
+
+    #[test]
+    fn test_artifacts_survive_restart() {
+        let dir = std::env::temp_dir().join(format!("los_module_cache_{}", std::process::id()));
+        let bytecode = add_module();
+        let hash = "0123abcd";
+        {
+            let mut cache = ModuleCache::new(4).with_artifact_dir(&dir).unwrap();
+            cache.get_or_compile(hash, &bytecode).unwrap();
+        }
+        let mut cache = ModuleCache::new(4).with_artifact_dir(&dir).unwrap();
+        assert!(cache.get(hash).is_some());
+        assert_eq!(cache.stats().disk_hits, 1);
+        assert_eq!(cache.stats().misses, 0);
+
+        // A corrupt artifact is discarded instead of loaded
+        std::fs::write(artifact_file(&dir, "ffff"), b"garbage").unwrap();
+        assert!(cache.get("ffff").is_none());
+        assert!(!artifact_file(&dir, "ffff").exists());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_start_section_detection() {
+        let plain = add_module();
+        let with_start = wasmer::wat2wasm(br#"(module (func $init) (start $init))"#)
+            .unwrap()
+            .to_vec();
+        assert!(!has_start_section(&plain));
+        assert!(has_start_section(&with_start));
+    }
+}
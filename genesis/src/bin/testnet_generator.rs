@@ -15,10 +15,9 @@
 /// SECURITY: This binary is for TESTNET ONLY. Mainnet genesis must be
 /// generated offline with fresh random keys, NEVER from committed seeds.
 /// The seed phrases below are PUBLIC testnet keys — they have zero value.
-use bip39::{Language, Mnemonic};
+use genesis::{testnet_genesis_json, TestnetWallet, CIL_PER_LOS};
 use std::fs;
 
-const CIL_PER_LOS: u128 = 100_000_000_000;
 const TOTAL_SUPPLY_LOS: u128 = 21_936_236;
 
 // Genesis Allocation (~3.5% DEV / ~96.5% PUBLIC) per copilot-instructions.md
@@ -91,7 +90,7 @@ fn main() {
         DEV_TREASURY_3_LOS,
         DEV_TREASURY_4_LOS,
     ];
-    let mut wallet_entries: Vec<String> = Vec::new();
+    let mut wallets: Vec<TestnetWallet> = Vec::new();
 
     println!("===================================================");
     println!("TESTNET DEV TREASURY WALLETS (Dilithium5 Post-Quantum)");
@@ -100,31 +99,25 @@ fn main() {
     for (i, &seed_phrase) in TESTNET_SEEDS[..DEV_TREASURY_COUNT].iter().enumerate() {
         let wallet_num = i + 1;
 
-        // Validate seed phrase is valid BIP39
-        let mnemonic = Mnemonic::parse_in_normalized(Language::English, seed_phrase)
-            .expect("Invalid BIP39 seed phrase");
-
         // DETERMINISTIC: Derive Dilithium5 keypair from BIP39 seed
-        let bip39_seed = mnemonic.to_seed("");
-        let kp = los_crypto::generate_keypair_from_seed(&bip39_seed);
-        let pk_hex = hex::encode(&kp.public_key);
-        let sk_hex = hex::encode(&kp.secret_key);
-        let address = los_crypto::public_key_to_address(&kp.public_key);
-
-        let balance_los = dev_balances_los[i];
-        let balance_cil = balance_los * CIL_PER_LOS;
+        let wallet = TestnetWallet::from_seed(
+            &format!("DevTreasury({})", wallet_num),
+            seed_phrase,
+            dev_balances_los[i],
+            &format!("Dev Treasury #{}", wallet_num),
+        )
+        .expect("Invalid BIP39 seed phrase");
 
         println!("Dev Treasury #{}:", wallet_num);
-        println!("  Address:      {}", address);
-        println!("  Balance:      {} LOS ({} CIL)", balance_los, balance_cil);
+        println!("  Address:      {}", wallet.address);
+        println!(
+            "  Balance:      {} LOS ({} CIL)",
+            wallet.balance_los,
+            wallet.balance_cil()
+        );
         println!("  Seed Phrase:  {}", seed_phrase);
-        println!("  Public Key:   {}...\n", &pk_hex[..64]);
-
-        wallet_entries.push(format!(
-            "    {{\n      \"wallet_type\": \"DevTreasury({})\",\n      \"seed_phrase\": \"{}\",\n      \"address\": \"{}\",\n      \"balance_cil\": \"{}\",\n      \"balance_los\": \"{}\",\n      \"public_key\": \"{}\",\n      \"private_key\": \"{}\",\n      \"note\": \"Dev Treasury #{}\"\n    }}",
-            wallet_num, seed_phrase, address, balance_cil,
-            balance_los, pk_hex, sk_hex, wallet_num
-        ));
+        println!("  Public Key:   {}...\n", &wallet.public_key_hex[..64]);
+        wallets.push(wallet);
     }
 
     println!("===================================================");
@@ -133,35 +126,27 @@ fn main() {
 
     for i in 0..BOOTSTRAP_NODE_COUNT {
         let validator_num = i + 1;
-        let seed_index = DEV_TREASURY_COUNT + i;
-        let seed_phrase = TESTNET_SEEDS[seed_index];
-
-        let mnemonic_parsed = Mnemonic::parse_in_normalized(Language::English, seed_phrase)
-            .expect("Invalid BIP39 seed phrase");
+        let seed_phrase = TESTNET_SEEDS[DEV_TREASURY_COUNT + i];
 
         // DETERMINISTIC: Derive Dilithium5 keypair from BIP39 seed
-        let bip39_seed = mnemonic_parsed.to_seed("");
-        let kp = los_crypto::generate_keypair_from_seed(&bip39_seed);
-        let pk_hex = hex::encode(&kp.public_key);
-        let sk_hex = hex::encode(&kp.secret_key);
-        let address = los_crypto::public_key_to_address(&kp.public_key);
-
-        let balance_cil = BOOTSTRAP_NODE_STAKE_LOS * CIL_PER_LOS;
+        let wallet = TestnetWallet::from_seed(
+            &format!("BootstrapNode({})", validator_num),
+            seed_phrase,
+            BOOTSTRAP_NODE_STAKE_LOS,
+            &format!("Bootstrap Validator #{}", validator_num),
+        )
+        .expect("Invalid BIP39 seed phrase");
 
         println!("Bootstrap Validator #{}:", validator_num);
-        println!("  Address:      {}", address);
+        println!("  Address:      {}", wallet.address);
         println!(
             "  Balance:      {} LOS ({} CIL)",
-            BOOTSTRAP_NODE_STAKE_LOS, balance_cil
+            BOOTSTRAP_NODE_STAKE_LOS,
+            wallet.balance_cil()
         );
         println!("  Seed Phrase:  {}", seed_phrase);
-        println!("  Public Key:   {}...\n", &pk_hex[..64]);
-
-        wallet_entries.push(format!(
-            "    {{\n      \"wallet_type\": \"BootstrapNode({})\",\n      \"seed_phrase\": \"{}\",\n      \"address\": \"{}\",\n      \"balance_cil\": \"{}\",\n      \"balance_los\": \"{}\",\n      \"public_key\": \"{}\",\n      \"private_key\": \"{}\",\n      \"note\": \"Bootstrap Validator #{}\"\n    }}",
-            validator_num, seed_phrase, address, balance_cil,
-            BOOTSTRAP_NODE_STAKE_LOS, pk_hex, sk_hex, validator_num
-        ));
+        println!("  Public Key:   {}...\n", &wallet.public_key_hex[..64]);
+        wallets.push(wallet);
     }
 
     println!("===================================================");
@@ -184,16 +169,23 @@ fn main() {
     println!("Public:           {} LOS (~97%)", PUBLIC_SUPPLY_LOS);
     println!("===================================================\n");
 
-    // Build JSON manually
-    let wallets_json = wallet_entries.join(",\n");
-    let json = format!(
-        "{{\n  \"version\": \"11.0\",\n  \"network\": \"testnet\",\n  \"description\": \"Public testnet genesis v11.0 - 8 wallets (4 dev treasury + 4 bootstrap validators)\",\n  \"warning\": \"FOR TESTNET ONLY - NEVER use these seeds on mainnet!\",\n  \"crypto\": \"CRYSTALS-Dilithium5 (Post-Quantum)\",\n  \"note\": \"BIP39 seeds deterministically derive Dilithium5 keypairs. Same seed = same address.\",\n  \"allocation\": {{\n    \"total_supply_los\": \"{}\",\n    \"dev_treasury_total_los\": \"{}\",\n    \"dev_supply_total_los\": \"{}\",\n    \"public_supply_los\": \"{}\",\n    \"dev_percent\": \"~3.5%\"\n  }},\n  \"wallets\": [\n{}\n  ]\n}}",
-        TOTAL_SUPPLY_LOS,
-        DEV_TREASURY_TOTAL_LOS,
-        DEV_SUPPLY_TOTAL_LOS,
-        PUBLIC_SUPPLY_LOS,
-        wallets_json
+    let mut genesis = testnet_genesis_json(
+        "Public testnet genesis v11.0 - 8 wallets (4 dev treasury + 4 bootstrap validators)",
+        "FOR TESTNET ONLY - NEVER use these seeds on mainnet!",
+        &wallets,
     );
+    genesis["version"] = "11.0".into();
+    genesis["note"] =
+        "BIP39 seeds deterministically derive Dilithium5 keypairs. Same seed = same address."
+            .into();
+    genesis["allocation"] = serde_json::json!({
+        "total_supply_los": TOTAL_SUPPLY_LOS.to_string(),
+        "dev_treasury_total_los": DEV_TREASURY_TOTAL_LOS.to_string(),
+        "dev_supply_total_los": DEV_SUPPLY_TOTAL_LOS.to_string(),
+        "public_supply_los": PUBLIC_SUPPLY_LOS.to_string(),
+        "dev_percent": "~3.5%",
+    });
+    let json = serde_json::to_string_pretty(&genesis).expect("genesis JSON serializes");
 
     let output_path = "testnet-genesis/testnet_wallets.json";
     if let Err(e) = fs::create_dir_all("testnet-genesis") {
@@ -0,0 +1,94 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - TESTNET GENESIS BUILDING BLOCKS
+//
+// Shared by `testnet_generator` (public testnet, committed seeds) and
+// `los-node devnet` (private devnets, fresh seeds):
+// - `TestnetWallet`: BIP39 seed → Dilithium5 keypair → LOS address
+// - `testnet_genesis_json`: the legacy `wallets[]` genesis read by testnet builds
+//
+// TESTNET ONLY: wallets carry their seed phrase and private key in plain text.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use bip39::{Language, Mnemonic};
+use rand::RngCore;
+
+pub const CIL_PER_LOS: u128 = 100_000_000_000;
+
+/// A genesis wallet whose keypair is derived from its BIP39 seed
+#[derive(Debug, Clone)]
+pub struct TestnetWallet {
+    /// `DevTreasury(n)`, `BootstrapNode(n)`, `DevWallet`, ...
+    pub wallet_type: String,
+    pub seed_phrase: String,
+    pub address: String,
+    pub public_key_hex: String,
+    pub private_key_hex: String,
+    pub balance_los: u128,
+    pub note: String,
+}
+
+impl TestnetWallet {
+    /// Derive the wallet of `seed_phrase` (same seed = same address)
+    pub fn from_seed(
+        wallet_type: &str,
+        seed_phrase: &str,
+        balance_los: u128,
+        note: &str,
+    ) -> Result<Self, String> {
+        let mnemonic = Mnemonic::parse_in_normalized(Language::English, seed_phrase)
+            .map_err(|e| format!("Invalid BIP39 seed phrase: {}", e))?;
+        let kp = los_crypto::generate_keypair_from_seed(&mnemonic.to_seed(""));
+        Ok(Self {
+            wallet_type: wallet_type.to_string(),
+            seed_phrase: mnemonic.to_string(),
+            address: los_crypto::public_key_to_address(&kp.public_key),
+            public_key_hex: hex::encode(&kp.public_key),
+            private_key_hex: hex::encode(&kp.secret_key),
+            balance_los,
+            note: note.to_string(),
+        })
+    }
+
+    /// Fresh 24-word seed from the OS RNG
+    pub fn generate(wallet_type: &str, balance_los: u128, note: &str) -> Self {
+        let mut entropy = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut entropy);
+        let mnemonic =
+            Mnemonic::from_entropy(&entropy).expect("32 bytes is a valid BIP39 entropy length");
+        Self::from_seed(wallet_type, &mnemonic.to_string(), balance_los, note)
+            .expect("generated mnemonic is valid")
+    }
+
+    pub fn balance_cil(&self) -> u128 {
+        self.balance_los * CIL_PER_LOS
+    }
+
+    /// Entry of the genesis `wallets[]` array
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "wallet_type": self.wallet_type,
+            "seed_phrase": self.seed_phrase,
+            "address": self.address,
+            "balance_cil": self.balance_cil().to_string(),
+            "balance_los": self.balance_los.to_string(),
+            "public_key": self.public_key_hex,
+            "private_key": self.private_key_hex,
+            "note": self.note,
+        })
+    }
+}
+
+/// Legacy testnet genesis document; callers add their own metadata fields
+pub fn testnet_genesis_json(
+    description: &str,
+    warning: &str,
+    wallets: &[TestnetWallet],
+) -> serde_json::Value {
+    serde_json::json!({
+        "network": "testnet",
+        "description": description,
+        "warning": warning,
+        "crypto": "CRYSTALS-Dilithium5 (Post-Quantum)",
+        "wallets": wallets.iter().map(TestnetWallet::to_json).collect::<Vec<_>>(),
+    })
+}
@@ -107,6 +107,7 @@ fn make_mint_block(
         work: 0,
         timestamp: now,
         fee: 0,
+        memo: None,
     };
     mine_and_sign(&mut block, secret_key);
     block
@@ -132,6 +133,7 @@ fn make_send_block(
         work: 0,
         timestamp,
         fee: fee_cil,
+        memo: None,
     };
     mine_and_sign(&mut block, &sender.secret_key);
     block
@@ -158,6 +160,7 @@ fn make_receive_block(
         work: 0,
         timestamp,
         fee: 0,
+        memo: None,
     };
     mine_and_sign(&mut block, secret_key);
     block
@@ -715,6 +718,7 @@ async fn test_proof_of_burn_distribution() {
             work: 0,
             timestamp: ts_base + mint_count + 1,
             fee: 0,
+            memo: None,
         };
         let mut mint = mint;
         mine_and_sign(&mut mint, &node.secret_key);
@@ -760,6 +764,7 @@ async fn test_proof_of_burn_distribution() {
             work: 0,
             timestamp: ts_base + mint_count + 2,
             fee: 0,
+            memo: None,
         };
         let mut over_mint = over_mint;
         mine_and_sign(&mut over_mint, &node.secret_key);
@@ -1005,8 +1010,8 @@ async fn test_quadratic_voting_and_antiwhale() {
 
     // 10x stake should give ~3.16x power (√10 ≈ 3.16), not 10x
     // Integer: √10000 / √1000 = 100 / 31 ≈ 3.22
-    if powers[0] > 0 {
-        let ratio_10x = (powers[1] * 100) / powers[0]; // basis points-like
+    if let Some(ratio_10x) = (powers[1] * 100).checked_div(powers[0]) {
+        // basis points-like
         assert!(
             ratio_10x < 400 && ratio_10x > 250,
             "10x stake should yield ~3x power, got ratio {}",
@@ -1190,6 +1195,7 @@ async fn test_chain_id_replay_protection() {
         work: 0,
         timestamp: now_secs(),
         fee: 0,
+        memo: None,
     };
     mine_and_sign(&mut block1, &kp.secret_key);
 
@@ -199,6 +199,8 @@ fn vm_set_state(engine: &WasmEngine, contract: &str, key: &str, value: &str, cal
         gas_limit: 1000,
         caller: caller.to_string(),
         block_timestamp: now_secs(),
+        value_cil: 0,
+        token_allowances: Default::default(),
     };
     let result = engine.call_contract(call).expect("set_state must succeed");
     assert!(result.success, "set_state failed: {}", result.output);
@@ -213,6 +215,8 @@ fn vm_get_state(engine: &WasmEngine, contract: &str, key: &str, caller: &str) ->
         gas_limit: 1000,
         caller: caller.to_string(),
         block_timestamp: now_secs(),
+        value_cil: 0,
+        token_allowances: Default::default(),
     };
     let result = engine.call_contract(call).expect("get_state must succeed");
     result.output
@@ -698,6 +702,8 @@ fn test_usp01_burn_supply_reduction() {
         gas_limit: 1000,
         caller: deployer.to_string(),
         block_timestamp: now_secs(),
+        value_cil: 0,
+        token_allowances: Default::default(),
     };
     let result = engine.call_contract(burn_call).unwrap();
     assert!(result.success, "VM burn failed: {}", result.output);
@@ -727,6 +733,8 @@ fn test_usp01_burn_supply_reduction() {
         gas_limit: 1000,
         caller: deployer.to_string(),
         block_timestamp: now_secs(),
+        value_cil: 0,
+        token_allowances: Default::default(),
     };
     let mint_result = engine.call_contract(mint_call);
     assert!(
@@ -1146,6 +1154,7 @@ fn test_blockchain_contract_blocks() {
         work: 0,
         timestamp: now_secs(),
         fee: MIN_DEPLOY_FEE_CIL,
+        memo: None,
     };
 
     mine_and_sign(&mut deploy_block, &node.secret_key);
@@ -1186,6 +1195,7 @@ fn test_blockchain_contract_blocks() {
         work: 0,
         timestamp: now_secs(),
         fee: MIN_CALL_FEE_CIL,
+        memo: None,
     };
 
     mine_and_sign(&mut call_block, &node.secret_key);
@@ -1229,6 +1239,7 @@ fn test_blockchain_contract_blocks() {
         work: 0,
         timestamp: now_secs(),
         fee: 0,
+        memo: None,
     };
     mine_and_sign(&mut mint_block, &node.secret_key);
     let mint_hash = mint_block.calculate_hash();
@@ -1345,6 +1356,8 @@ fn test_gas_limits_and_errors() {
         gas_limit: 1, // Way too low
         caller: "alice".to_string(),
         block_timestamp: now_secs(),
+        value_cil: 0,
+        token_allowances: Default::default(),
     };
     let result = engine.call_contract(call);
     assert!(result.is_err(), "Gas limit too low must fail: {:?}", result);
@@ -1358,6 +1371,8 @@ fn test_gas_limits_and_errors() {
         gas_limit: 1000,
         caller: "alice".to_string(),
         block_timestamp: now_secs(),
+        value_cil: 0,
+        token_allowances: Default::default(),
     };
     let result = engine.call_contract(call);
     assert!(result.is_err(), "Unknown function must fail");
@@ -1371,6 +1386,8 @@ fn test_gas_limits_and_errors() {
         gas_limit: 1000,
         caller: "alice".to_string(),
         block_timestamp: now_secs(),
+        value_cil: 0,
+        token_allowances: Default::default(),
     };
     let result = engine.call_contract(call);
     assert!(result.is_err(), "Nonexistent contract must fail");
@@ -1384,6 +1401,8 @@ fn test_gas_limits_and_errors() {
         gas_limit: 1000,
         caller: "alice".to_string(),
         block_timestamp: now_secs(),
+        value_cil: 0,
+        token_allowances: Default::default(),
     };
     let result = engine.call_contract(call);
     assert!(result.is_err(), "Insufficient balance transfer must fail");
@@ -1732,6 +1751,7 @@ fn test_full_integration_flow() {
         work: 0,
         timestamp: now_secs(),
         fee: MIN_DEPLOY_FEE_CIL,
+        memo: None,
     };
     mine_and_sign(&mut deploy_blk, &node.secret_key);
     assert!(deploy_blk.verify_pow());
@@ -1756,6 +1776,7 @@ fn test_full_integration_flow() {
         work: 0,
         timestamp: now_secs(),
         fee: MIN_CALL_FEE_CIL,
+        memo: None,
     };
     mine_and_sign(&mut call_blk, &node.secret_key);
     assert!(call_blk.verify_pow());
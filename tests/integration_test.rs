@@ -74,6 +74,7 @@ async fn test_three_validator_consensus() {
             .unwrap_or_default()
             .as_secs(),
         fee: 0,
+        memo: None,
     };
 
     // Broadcast block to all validators (simulate consensus)